@@ -1,5 +1,6 @@
 //! Abstract Syntax Tree (AST) definitions for the Eä programming language.
 
+use std::collections::HashMap;
 use std::fmt;
 
 /// Represents a binary operator in an expression
@@ -63,6 +64,7 @@ pub enum UnaryOp {
     Negate,    // -
     Not,       // !
     Reference, // &
+    Deref,     // *
 }
 
 impl fmt::Display for UnaryOp {
@@ -71,6 +73,7 @@ impl fmt::Display for UnaryOp {
             UnaryOp::Negate => write!(f, "-"),
             UnaryOp::Not => write!(f, "!"),
             UnaryOp::Reference => write!(f, "&"),
+            UnaryOp::Deref => write!(f, "*"),
         }
     }
 }
@@ -87,7 +90,7 @@ pub enum Literal {
         vector_type: Option<SIMDVectorType>,
     },
 }
-use crate::lexer::Position; // Add this import
+use crate::lexer::{Position, Span}; // Add this import
 
 /// SIMD Expression types for industry-first SIMD support
 #[derive(Debug, Clone, PartialEq)]
@@ -150,6 +153,69 @@ pub enum SIMDExpr {
         alignment: Option<u32>, // Optional alignment in bytes
         position: Position,
     },
+
+    /// Lane-wise blend of two vectors: select(a .< b, a, b)
+    Select {
+        mask: Box<Expr>,
+        if_true: Box<Expr>,
+        if_false: Box<Expr>,
+        position: Position,
+    },
+
+    /// Load of the lanes set in the mask, the rest taken from passthru:
+    /// masked_load(address, mask, passthru)
+    MaskedLoad {
+        address: Box<Expr>,
+        mask: Box<Expr>,
+        passthru: Box<Expr>,
+        position: Position,
+    },
+
+    /// Store of the lanes set in the mask: masked_store(address, vector, mask)
+    MaskedStore {
+        address: Box<Expr>,
+        vector: Box<Expr>,
+        mask: Box<Expr>,
+        position: Position,
+    },
+
+    /// Load of one element per index lane: gather(base, [0, 8, 16, 24]i32x4)
+    Gather {
+        base: Box<Expr>,
+        indices: Box<Expr>,
+        position: Position,
+    },
+
+    /// Store of one element per index lane: scatter(base, indices, vector)
+    Scatter {
+        base: Box<Expr>,
+        indices: Box<Expr>,
+        vector: Box<Expr>,
+        position: Position,
+    },
+
+    /// Lanes picked from two vectors by a constant mask: shuffle(a, b, [0, 4, 1, 5]),
+    /// where indices past the lanes of `a` pick from `b`
+    Shuffle {
+        left: Box<Expr>,
+        right: Box<Expr>,
+        mask: Box<Expr>,
+        position: Position,
+    },
+
+    /// Load of the target's widest vector of an element type: load_vector(src, simd<f32>)
+    NativeVectorLoad {
+        address: Box<Expr>,
+        element_type: String,
+        alignment: Option<u32>,
+        position: Position,
+    },
+
+    /// Number of lanes in `simd<element_type>` on the target: lanes<f32>()
+    Lanes {
+        element_type: String,
+        position: Position,
+    },
 }
 
 /// SIMD vector types - all 32 types from SIMD-001
@@ -326,6 +392,43 @@ impl SIMDVectorType {
         }
     }
 
+    /// The vector type with `width` lanes of `element_type` (as returned by
+    /// `element_type`), if there is one.
+    pub fn from_element_type(element_type: &str, width: usize) -> Option<SIMDVectorType> {
+        let vector_type = match (element_type, width) {
+            ("f32", 2) => SIMDVectorType::F32x2,
+            ("f32", 4) => SIMDVectorType::F32x4,
+            ("f32", 8) => SIMDVectorType::F32x8,
+            ("f32", 16) => SIMDVectorType::F32x16,
+            ("f64", 2) => SIMDVectorType::F64x2,
+            ("f64", 4) => SIMDVectorType::F64x4,
+            ("f64", 8) => SIMDVectorType::F64x8,
+            ("i32", 2) => SIMDVectorType::I32x2,
+            ("i32", 4) => SIMDVectorType::I32x4,
+            ("i32", 8) => SIMDVectorType::I32x8,
+            ("i32", 16) => SIMDVectorType::I32x16,
+            ("i64", 2) => SIMDVectorType::I64x2,
+            ("i64", 4) => SIMDVectorType::I64x4,
+            ("i64", 8) => SIMDVectorType::I64x8,
+            ("i16", 4) => SIMDVectorType::I16x4,
+            ("i16", 8) => SIMDVectorType::I16x8,
+            ("i16", 16) => SIMDVectorType::I16x16,
+            ("i16", 32) => SIMDVectorType::I16x32,
+            ("i8", 8) => SIMDVectorType::I8x8,
+            ("i8", 16) => SIMDVectorType::I8x16,
+            ("i8", 32) => SIMDVectorType::I8x32,
+            ("i8", 64) => SIMDVectorType::I8x64,
+            ("u32", 4) => SIMDVectorType::U32x4,
+            ("u32", 8) => SIMDVectorType::U32x8,
+            ("u16", 8) => SIMDVectorType::U16x8,
+            ("u16", 16) => SIMDVectorType::U16x16,
+            ("u8", 16) => SIMDVectorType::U8x16,
+            ("u8", 32) => SIMDVectorType::U8x32,
+            _ => return None,
+        };
+        Some(vector_type)
+    }
+
     /// Check if two SIMD types are compatible for operations
     pub fn is_compatible_with(&self, other: &SIMDVectorType) -> bool {
         // Same type is always compatible
@@ -525,6 +628,51 @@ impl std::fmt::Display for SIMDExpr {
                     write!(f, "store_vector({}, {})", address, vector)
                 }
             }
+            SIMDExpr::Select {
+                mask,
+                if_true,
+                if_false,
+                ..
+            } => write!(f, "select({}, {}, {})", mask, if_true, if_false),
+            SIMDExpr::MaskedLoad {
+                address,
+                mask,
+                passthru,
+                ..
+            } => write!(f, "masked_load({}, {}, {})", address, mask, passthru),
+            SIMDExpr::MaskedStore {
+                address,
+                vector,
+                mask,
+                ..
+            } => write!(f, "masked_store({}, {}, {})", address, vector, mask),
+            SIMDExpr::Gather { base, indices, .. } => write!(f, "gather({}, {})", base, indices),
+            SIMDExpr::Scatter {
+                base,
+                indices,
+                vector,
+                ..
+            } => write!(f, "scatter({}, {}, {})", base, indices, vector),
+            SIMDExpr::Shuffle {
+                left, right, mask, ..
+            } => write!(f, "shuffle({}, {}, {})", left, right, mask),
+            SIMDExpr::NativeVectorLoad {
+                address,
+                element_type,
+                alignment,
+                ..
+            } => {
+                if let Some(align) = alignment {
+                    write!(
+                        f,
+                        "load_vector({}, simd<{}>, {})",
+                        address, element_type, align
+                    )
+                } else {
+                    write!(f, "load_vector({}, simd<{}>)", address, element_type)
+                }
+            }
+            SIMDExpr::Lanes { element_type, .. } => write!(f, "lanes<{}>()", element_type),
         }
     }
 }
@@ -564,43 +712,48 @@ impl fmt::Display for Literal {
 }
 
 /// Represents an expression in the AST
+///
+/// Every variant carries the `Span` of its source text; nodes the compiler
+/// builds itself use `Span::default()`.
 #[derive(Debug, Clone, PartialEq)]
 pub enum Expr {
     /// A literal value (number, string, boolean)
-    Literal(Literal),
+    Literal(Literal, Span),
 
     /// Variable reference
-    Variable(String),
+    Variable(String, Span),
 
     /// Unary operation: !x, -x, &x
-    Unary(UnaryOp, Box<Expr>),
+    Unary(UnaryOp, Box<Expr>, Span),
 
     /// Binary operation: a + b, a * b, etc.
-    Binary(Box<Expr>, BinaryOp, Box<Expr>),
+    Binary(Box<Expr>, BinaryOp, Box<Expr>, Span),
 
     /// Grouping with parentheses: (expr)
-    Grouping(Box<Expr>),
+    Grouping(Box<Expr>, Span),
 
     /// Function call: func(arg1, arg2)
-    Call(Box<Expr>, Vec<Expr>),
+    Call(Box<Expr>, Vec<Expr>, Span),
 
     /// Array indexing: array[index]
-    Index(Box<Expr>, Box<Expr>),
+    Index(Box<Expr>, Box<Expr>, Span),
 
     /// Array slicing: array[start:end]
     Slice {
         array: Box<Expr>,
         start: Box<Expr>,
         end: Box<Expr>,
+        span: Span,
     },
 
     /// Field access: object.field
-    FieldAccess(Box<Expr>, String),
+    FieldAccess(Box<Expr>, String, Span),
 
     /// Struct literal: StructName { field1: value1, field2: value2 }
     StructLiteral {
         name: String,
         fields: Vec<StructFieldInit>,
+        span: Span,
     },
 
     /// Enum literal: EnumName::Variant or EnumName::Variant(args)
@@ -608,29 +761,228 @@ pub enum Expr {
         enum_name: String,
         variant: String,
         args: Vec<Expr>,
+        span: Span,
     },
 
     /// Match expression: match value { pattern => expr, ... }
     Match {
         value: Box<Expr>,
         arms: Vec<MatchArm>,
+        span: Span,
+    },
+
+    /// Tuple literal: `(a, b)`, or `(a,)` for a single element
+    Tuple {
+        elements: Vec<Expr>,
+        span: Span,
+    },
+
+    /// Closure: `|x: f32| -> f32 { x * 2.0 }` or `|x: i32| x + offset`.
+    /// Variables of the enclosing scope used in `body` are captured by value.
+    Closure {
+        params: Vec<Parameter>,
+        return_type: Option<TypeAnnotation>,
+        body: Box<Expr>,
+        span: Span,
     },
 
     /// Block expression: { statements... }
-    Block(Vec<Stmt>),
+    Block(Vec<Stmt>, Span),
+
+    /// Compile-time block: `comptime { statements... }`, evaluated by the comptime
+    /// engine while type checking. Its value is the value of the last statement.
+    Comptime(Vec<Stmt>, Span),
+
+    /// Unsafe block: `unsafe { statements... }`, the only place raw pointers may be
+    /// dereferenced or offset. Its value is the value of the last statement.
+    Unsafe(Vec<Stmt>, Span),
+
+    /// Array repeat: `[value; count]`, or `[value; count]f32x4` for a SIMD vector.
+    /// `count` must be a compile-time integer, e.g. a literal or a `const`.
+    ArrayRepeat {
+        value: Box<Expr>,
+        count: Box<Expr>,
+        vector_type: Option<SIMDVectorType>,
+        span: Span,
+    },
+
+    SIMD(SIMDExpr, Span),
+}
 
-    SIMD(SIMDExpr),
+impl Expr {
+    /// The source range of this expression
+    pub fn span(&self) -> Span {
+        match self {
+            Expr::Literal(_, span)
+            | Expr::Variable(_, span)
+            | Expr::Unary(_, _, span)
+            | Expr::Binary(_, _, _, span)
+            | Expr::Grouping(_, span)
+            | Expr::Call(_, _, span)
+            | Expr::Index(_, _, span)
+            | Expr::FieldAccess(_, _, span)
+            | Expr::Block(_, span)
+            | Expr::Comptime(_, span)
+            | Expr::Unsafe(_, span)
+            | Expr::SIMD(_, span)
+            | Expr::Slice { span, .. }
+            | Expr::StructLiteral { span, .. }
+            | Expr::EnumLiteral { span, .. }
+            | Expr::Match { span, .. }
+            | Expr::Tuple { span, .. }
+            | Expr::Closure { span, .. }
+            | Expr::ArrayRepeat { span, .. } => span.clone(),
+        }
+    }
+
+    /// The names of all variables this expression reads or assigns, in source
+    /// order and without duplicates. Names bound inside the expression itself
+    /// are included too, so callers filter against the scope they care about.
+    pub fn variable_names(&self) -> Vec<String> {
+        let mut names = Vec::new();
+        self.collect_variable_names(&mut names);
+        names
+    }
+
+    fn collect_variable_names(&self, names: &mut Vec<String>) {
+        match self {
+            Expr::Variable(name, _) => {
+                if !names.contains(name) {
+                    names.push(name.clone());
+                }
+            }
+            Expr::Literal(..) => {}
+            Expr::Unary(_, expr, _) | Expr::Grouping(expr, _) | Expr::FieldAccess(expr, _, _) => {
+                expr.collect_variable_names(names)
+            }
+            Expr::Binary(left, _, right, _) | Expr::Index(left, right, _) => {
+                left.collect_variable_names(names);
+                right.collect_variable_names(names);
+            }
+            Expr::Call(callee, args, _) => {
+                callee.collect_variable_names(names);
+                for arg in args {
+                    arg.collect_variable_names(names);
+                }
+            }
+            Expr::Slice {
+                array, start, end, ..
+            } => {
+                for expr in [array, start, end] {
+                    expr.collect_variable_names(names);
+                }
+            }
+            Expr::StructLiteral { fields, .. } => {
+                for field in fields {
+                    field.value.collect_variable_names(names);
+                }
+            }
+            Expr::EnumLiteral { args: exprs, .. }
+            | Expr::Tuple {
+                elements: exprs, ..
+            } => {
+                for expr in exprs {
+                    expr.collect_variable_names(names);
+                }
+            }
+            Expr::Match { value, arms, .. } => {
+                value.collect_variable_names(names);
+                for arm in arms {
+                    if let Some(guard) = &arm.guard {
+                        guard.collect_variable_names(names);
+                    }
+                    arm.expression.collect_variable_names(names);
+                }
+            }
+            Expr::Closure { body, .. } => body.collect_variable_names(names),
+            Expr::Block(statements, _)
+            | Expr::Comptime(statements, _)
+            | Expr::Unsafe(statements, _) => {
+                for stmt in statements {
+                    stmt.collect_variable_names(names);
+                }
+            }
+            Expr::ArrayRepeat { value, count, .. } => {
+                value.collect_variable_names(names);
+                count.collect_variable_names(names);
+            }
+            Expr::SIMD(simd_expr, _) => match simd_expr {
+                SIMDExpr::VectorLiteral { elements, .. } => {
+                    for element in elements {
+                        element.collect_variable_names(names);
+                    }
+                }
+                SIMDExpr::ElementWise { left, right, .. }
+                | SIMDExpr::DotProduct { left, right, .. }
+                | SIMDExpr::VectorStore {
+                    address: left,
+                    vector: right,
+                    ..
+                }
+                | SIMDExpr::Gather {
+                    base: left,
+                    indices: right,
+                    ..
+                } => {
+                    left.collect_variable_names(names);
+                    right.collect_variable_names(names);
+                }
+                SIMDExpr::Broadcast { value: expr, .. }
+                | SIMDExpr::Swizzle { vector: expr, .. }
+                | SIMDExpr::Reduction { vector: expr, .. }
+                | SIMDExpr::VectorLoad { address: expr, .. }
+                | SIMDExpr::NativeVectorLoad { address: expr, .. } => {
+                    expr.collect_variable_names(names)
+                }
+                SIMDExpr::Lanes { .. } => {}
+                SIMDExpr::Select {
+                    mask: first,
+                    if_true: second,
+                    if_false: third,
+                    ..
+                }
+                | SIMDExpr::MaskedLoad {
+                    address: first,
+                    mask: second,
+                    passthru: third,
+                    ..
+                }
+                | SIMDExpr::MaskedStore {
+                    address: first,
+                    vector: second,
+                    mask: third,
+                    ..
+                }
+                | SIMDExpr::Scatter {
+                    base: first,
+                    indices: second,
+                    vector: third,
+                    ..
+                }
+                | SIMDExpr::Shuffle {
+                    left: first,
+                    right: second,
+                    mask: third,
+                    ..
+                } => {
+                    first.collect_variable_names(names);
+                    second.collect_variable_names(names);
+                    third.collect_variable_names(names);
+                }
+            },
+        }
+    }
 }
 
 impl fmt::Display for Expr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Expr::Literal(lit) => write!(f, "{}", lit),
-            Expr::Variable(name) => write!(f, "{}", name),
-            Expr::Unary(op, expr) => write!(f, "{}({})", op, expr),
-            Expr::Binary(left, op, right) => write!(f, "({} {} {})", left, op, right),
-            Expr::Grouping(expr) => write!(f, "({})", expr),
-            Expr::Call(callee, args) => {
+            Expr::Literal(lit, _) => write!(f, "{}", lit),
+            Expr::Variable(name, _) => write!(f, "{}", name),
+            Expr::Unary(op, expr, _) => write!(f, "{}({})", op, expr),
+            Expr::Binary(left, op, right, _) => write!(f, "({} {} {})", left, op, right),
+            Expr::Grouping(expr, _) => write!(f, "({})", expr),
+            Expr::Call(callee, args, _) => {
                 write!(f, "{}(", callee)?;
                 for (i, arg) in args.iter().enumerate() {
                     if i > 0 {
@@ -640,10 +992,12 @@ impl fmt::Display for Expr {
                 }
                 write!(f, ")")
             }
-            Expr::Index(array, index) => write!(f, "{}[{}]", array, index),
-            Expr::Slice { array, start, end } => write!(f, "{}[{}:{}]", array, start, end),
-            Expr::FieldAccess(object, field) => write!(f, "{}.{}", object, field),
-            Expr::StructLiteral { name, fields } => {
+            Expr::Index(array, index, _) => write!(f, "{}[{}]", array, index),
+            Expr::Slice {
+                array, start, end, ..
+            } => write!(f, "{}[{}:{}]", array, start, end),
+            Expr::FieldAccess(object, field, _) => write!(f, "{}.{}", object, field),
+            Expr::StructLiteral { name, fields, .. } => {
                 write!(f, "{} {{", name)?;
                 for (i, field) in fields.iter().enumerate() {
                     if i > 0 {
@@ -657,6 +1011,7 @@ impl fmt::Display for Expr {
                 enum_name,
                 variant,
                 args,
+                ..
             } => {
                 write!(f, "{}::{}", enum_name, variant)?;
                 if !args.is_empty() {
@@ -671,25 +1026,112 @@ impl fmt::Display for Expr {
                 }
                 Ok(())
             }
-            Expr::Match { value, arms } => {
+            Expr::Match { value, arms, .. } => {
                 write!(f, "match {} {{", value)?;
                 for arm in arms {
                     write!(f, " {},", arm)?;
                 }
                 write!(f, " }}")
             }
-            Expr::Block(statements) => {
+            Expr::Tuple { elements, .. } => {
+                write!(f, "(")?;
+                for (i, element) in elements.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", element)?;
+                }
+                if elements.len() == 1 {
+                    write!(f, ",")?;
+                }
+                write!(f, ")")
+            }
+            Expr::Closure {
+                params,
+                return_type,
+                body,
+                ..
+            } => {
+                write!(f, "|")?;
+                for (i, param) in params.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", param)?;
+                }
+                write!(f, "| ")?;
+                if let Some(return_type) = return_type {
+                    write!(f, "-> {} ", return_type)?;
+                }
+                write!(f, "{}", body)
+            }
+            Expr::Block(statements, _) => {
                 write!(f, "{{")?;
                 for stmt in statements {
                     write!(f, " {};", stmt)?;
                 }
                 write!(f, " }}")
             }
-            Expr::SIMD(simd_expr) => write!(f, "{}", simd_expr),
+            Expr::Comptime(statements, _) => {
+                write!(f, "comptime {{")?;
+                for stmt in statements {
+                    write!(f, " {}", stmt)?;
+                }
+                write!(f, " }}")
+            }
+            Expr::Unsafe(statements, _) => {
+                write!(f, "unsafe {{")?;
+                for stmt in statements {
+                    write!(f, " {}", stmt)?;
+                }
+                write!(f, " }}")
+            }
+            Expr::ArrayRepeat {
+                value,
+                count,
+                vector_type,
+                ..
+            } => {
+                write!(f, "[{}; {}]", value, count)?;
+                if let Some(vtype) = vector_type {
+                    write!(f, "{}", vtype)?;
+                }
+                Ok(())
+            }
+            Expr::SIMD(simd_expr, _) => write!(f, "{}", simd_expr),
         }
     }
 }
 
+/// Expands `[value; count]` with a folded `count` into the equivalent literal.
+///
+/// Plain arrays only hold literals, so `None` is returned for any other
+/// `value` unless a SIMD vector type is given.
+pub fn expand_array_repeat(
+    value: &Expr,
+    count: usize,
+    vector_type: &Option<SIMDVectorType>,
+) -> Option<Expr> {
+    match (value, vector_type) {
+        (_, Some(vector_type)) => Some(Expr::SIMD(
+            SIMDExpr::VectorLiteral {
+                elements: vec![value.clone(); count],
+                vector_type: Some(vector_type.clone()),
+                position: value.span().start,
+            },
+            value.span(),
+        )),
+        (Expr::Literal(literal, span), None) => Some(Expr::Literal(
+            Literal::Vector {
+                elements: vec![literal.clone(); count],
+                vector_type: None,
+            },
+            span.clone(),
+        )),
+        _ => None,
+    }
+}
+
 /// Type annotation in the AST
 #[derive(Debug, Clone, PartialEq)]
 pub struct TypeAnnotation {
@@ -697,6 +1139,28 @@ pub struct TypeAnnotation {
     pub is_mutable: bool,
 }
 
+/// The lane indices of a constant shuffle mask such as `[0, 4, 1, 5]`, or `None`
+/// if `mask` is not an array of integer literals.
+pub fn constant_lane_indices(mask: &Expr) -> Option<Vec<i64>> {
+    match mask {
+        Expr::Literal(
+            Literal::Vector {
+                elements,
+                vector_type: None,
+            },
+            _,
+        ) => elements
+            .iter()
+            .map(|element| match element {
+                Literal::Integer(index) => Some(*index),
+                _ => None,
+            })
+            .collect(),
+        Expr::Grouping(inner, _) => constant_lane_indices(inner),
+        _ => None,
+    }
+}
+
 impl fmt::Display for TypeAnnotation {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if self.is_mutable {
@@ -707,7 +1171,294 @@ impl fmt::Display for TypeAnnotation {
     }
 }
 
+/// Formats the name of a generic instantiation, e.g. `Pair` + `[i32, f32]` -> `Pair<i32, f32>`.
+pub fn generic_instance_name(base: &str, type_args: &[String]) -> String {
+    format!("{}<{}>", base, type_args.join(", "))
+}
+
+/// Splits a generic type name into its base and type arguments.
+///
+/// `Pair<i32, Vec<f32>>` becomes `("Pair", ["i32", "Vec<f32>"])`; names without
+/// type arguments return `None`.
+pub fn split_generic_type_name(name: &str) -> Option<(&str, Vec<String>)> {
+    let open = name.find('<')?;
+    if !name.ends_with('>') || name.starts_with('(') || name.starts_with("func(") {
+        return None;
+    }
+
+    Some((
+        &name[..open],
+        split_type_list(&name[open + 1..name.len() - 1]),
+    ))
+}
+
+/// Splits a lane-width-agnostic vector type name into its element type, e.g.
+/// `simd<f32>` -> `f32`. The width is the widest the target supports.
+pub fn split_native_vector_type_name(name: &str) -> Option<String> {
+    match split_generic_type_name(name)? {
+        ("simd", args) if args.len() == 1 => args.into_iter().next(),
+        _ => None,
+    }
+}
+
+/// Formats the name of a tuple type, e.g. `[i32, f32]` -> `(i32, f32)`.
+pub fn tuple_type_name(element_types: &[String]) -> String {
+    if element_types.len() == 1 {
+        format!("({},)", element_types[0])
+    } else {
+        format!("({})", element_types.join(", "))
+    }
+}
+
+/// Splits a tuple type name into its element types.
+///
+/// `(i32, Pair<f32, f32>)` becomes `["i32", "Pair<f32, f32>"]`; names that are
+/// not tuple types return `None`.
+pub fn split_tuple_type_name(name: &str) -> Option<Vec<String>> {
+    // `()` is the unit type, not an empty tuple
+    if !name.starts_with('(') || !name.ends_with(')') || name == "()" {
+        return None;
+    }
+
+    let mut element_types = split_type_list(&name[1..name.len() - 1]);
+    // `(T,)` has a trailing comma
+    if element_types.last().map_or(false, |last| last.is_empty()) {
+        element_types.pop();
+    }
+    Some(element_types)
+}
+
+/// Formats the name of a function type, e.g. `[f32, f32]` + `f32` -> `func(f32, f32) -> f32`.
+pub fn function_type_name(param_types: &[String], return_type: &str) -> String {
+    format!("func({}) -> {}", param_types.join(", "), return_type)
+}
+
+/// Splits a function type name into its parameter types and return type.
+///
+/// `func(i32, f32) -> bool` becomes `(["i32", "f32"], "bool")`; names that are
+/// not function types return `None`.
+pub fn split_function_type_name(name: &str) -> Option<(Vec<String>, String)> {
+    let rest = name.strip_prefix("func(")?;
+    let mut depth = 1;
+    for (i, c) in rest.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    let return_type = rest[i + 1..].trim().strip_prefix("->")?.trim();
+                    let param_types = match rest[..i].trim() {
+                        "" => Vec::new(),
+                        params => split_type_list(params),
+                    };
+                    return Some((param_types, return_type.to_string()));
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Formats the name of a raw pointer type, e.g. `f32` -> `*f32` or `*mut f32`.
+pub fn pointer_type_name(target_type: &str, is_mutable: bool) -> String {
+    if is_mutable {
+        format!("*mut {}", target_type)
+    } else {
+        format!("*{}", target_type)
+    }
+}
+
+/// Splits a raw pointer type name into the type it points to and whether it
+/// allows writes: `*mut f32` becomes `("f32", true)`. Names that are not
+/// pointer types return `None`.
+pub fn split_pointer_type_name(name: &str) -> Option<(String, bool)> {
+    let target = name.strip_prefix('*')?;
+    match target.strip_prefix("mut ") {
+        Some(target) => Some((target.trim().to_string(), true)),
+        None => Some((target.trim().to_string(), false)),
+    }
+}
+
+/// Formats the name of a fixed-size array type, e.g. `f32` + `64` -> `[f32; 64]`.
+pub fn array_type_name(element_type: &str, length: &str) -> String {
+    format!("[{}; {}]", element_type, length)
+}
+
+/// Splits a fixed-size array type name into its element type and length.
+///
+/// `[[f32; 4]; SIZE]` becomes `("[f32; 4]", "SIZE")`; the length is an integer or
+/// the name of a constant. Names that are not array types return `None`.
+pub fn split_array_type_name(name: &str) -> Option<(String, String)> {
+    let inner = name.strip_prefix('[')?.strip_suffix(']')?;
+    let (element_type, length) = inner.rsplit_once(';')?;
+    Some((element_type.trim().to_string(), length.trim().to_string()))
+}
+
+/// The expression an array type's length stands for: an integer literal or a
+/// constant, to be folded by the caller.
+pub fn array_length_expr(length: &str) -> Expr {
+    match length.parse::<i64>() {
+        Ok(n) => Expr::Literal(Literal::Integer(n), Span::default()),
+        Err(_) => Expr::Variable(length.to_string(), Span::default()),
+    }
+}
+
+/// Returns true if the type named `name` holds unsigned integers: `u8`, or an
+/// array, pointer, reference or SIMD vector of them such as `[u16; 4]`,
+/// `*mut u32` or `u8x16`.
+pub fn is_unsigned_type_name(name: &str) -> bool {
+    if let Some(element_type) = split_native_vector_type_name(name) {
+        return is_unsigned_type_name(&element_type);
+    }
+    if let Some((target, _)) = split_pointer_type_name(name) {
+        return is_unsigned_type_name(&target);
+    }
+    if let Some((element_type, _)) = split_array_type_name(name) {
+        return is_unsigned_type_name(&element_type);
+    }
+    if let Some(target) = name.strip_prefix('&') {
+        return is_unsigned_type_name(target.trim_start_matches("mut "));
+    }
+    // `u8x16` holds `u8` lanes
+    let scalar = name.split('x').next().unwrap_or(name);
+    matches!(scalar.trim(), "u8" | "u16" | "u32" | "u64")
+}
+
+/// Builtins for integer arithmetic with defined overflow behavior, called like
+/// `wrapping_add(a, b)`. Both operands have one integer or integer SIMD vector type.
+pub const OVERFLOW_ARITHMETIC_BUILTINS: [&str; 5] = [
+    "wrapping_add",
+    "wrapping_sub",
+    "wrapping_mul",
+    "saturating_add",
+    "saturating_sub",
+];
+
+/// Splits a comma separated list of type names, ignoring commas nested inside
+/// generic arguments, tuple types or array types.
+fn split_type_list(list: &str) -> Vec<String> {
+    let mut types = Vec::new();
+    let mut depth = 0;
+    let mut current = String::new();
+    for c in list.chars() {
+        match c {
+            '<' | '(' | '[' => depth += 1,
+            // The `>` of a function type's `->` closes nothing
+            '>' if current.ends_with('-') => {}
+            '>' | ')' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                types.push(current.trim().to_string());
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    types.push(current.trim().to_string());
+    types
+}
+
+/// Replaces type parameters in a type name, e.g. `Pair<T>` with `T = i32` becomes `Pair<i32>`.
+pub fn substitute_type_params(name: &str, substitutions: &HashMap<String, String>) -> String {
+    if let Some(concrete) = substitutions.get(name) {
+        return concrete.clone();
+    }
+    if let Some(element_types) = split_tuple_type_name(name) {
+        let element_types: Vec<String> = element_types
+            .iter()
+            .map(|element| substitute_type_params(element, substitutions))
+            .collect();
+        return tuple_type_name(&element_types);
+    }
+    if let Some((target_type, is_mutable)) = split_pointer_type_name(name) {
+        return pointer_type_name(
+            &substitute_type_params(&target_type, substitutions),
+            is_mutable,
+        );
+    }
+    if let Some((element_type, length)) = split_array_type_name(name) {
+        return array_type_name(
+            &substitute_type_params(&element_type, substitutions),
+            &length,
+        );
+    }
+    if let Some((param_types, return_type)) = split_function_type_name(name) {
+        let param_types: Vec<String> = param_types
+            .iter()
+            .map(|param| substitute_type_params(param, substitutions))
+            .collect();
+        return function_type_name(
+            &param_types,
+            &substitute_type_params(&return_type, substitutions),
+        );
+    }
+    match split_generic_type_name(name) {
+        Some((base, args)) => {
+            let args: Vec<String> = args
+                .iter()
+                .map(|arg| substitute_type_params(arg, substitutions))
+                .collect();
+            generic_instance_name(base, &args)
+        }
+        None => name.to_string(),
+    }
+}
+
+/// Infers type parameters by matching a declared type name against a concrete one.
+///
+/// `Pair<T>` matched against `Pair<i32>` binds `T` to `i32`. Existing bindings are
+/// kept; if a parameter is already bound to a different type, its name is returned
+/// as the error.
+pub fn bind_type_params(
+    type_params: &[String],
+    declared: &str,
+    concrete: &str,
+    bindings: &mut HashMap<String, String>,
+) -> std::result::Result<(), String> {
+    if type_params.iter().any(|param| param == declared) {
+        return match bindings.get(declared) {
+            Some(bound) if bound != concrete => Err(declared.to_string()),
+            Some(_) => Ok(()),
+            None => {
+                bindings.insert(declared.to_string(), concrete.to_string());
+                Ok(())
+            }
+        };
+    }
+
+    if let (Some((declared_base, declared_args)), Some((concrete_base, concrete_args))) = (
+        split_generic_type_name(declared),
+        split_generic_type_name(concrete),
+    ) {
+        if declared_base == concrete_base && declared_args.len() == concrete_args.len() {
+            for (declared_arg, concrete_arg) in declared_args.iter().zip(concrete_args.iter()) {
+                bind_type_params(type_params, declared_arg, concrete_arg, bindings)?;
+            }
+        }
+    }
+
+    if let (Some(declared_elements), Some(concrete_elements)) = (
+        split_tuple_type_name(declared),
+        split_tuple_type_name(concrete),
+    ) {
+        if declared_elements.len() == concrete_elements.len() {
+            for (declared_element, concrete_element) in
+                declared_elements.iter().zip(concrete_elements.iter())
+            {
+                bind_type_params(type_params, declared_element, concrete_element, bindings)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
 /// Represents an attribute parameter (key-value pair)
+///
+/// Positional parameters, such as the features of `@target_clones(avx2, sse4)`,
+/// have an empty key.
 #[derive(Debug, Clone, PartialEq)]
 pub struct AttributeParam {
     pub key: String,
@@ -743,6 +1494,63 @@ pub struct Attribute {
     pub params: Vec<AttributeParam>,
 }
 
+impl Attribute {
+    /// Name of the attribute a `comptime func` declaration carries
+    pub const COMPTIME: &'static str = "comptime";
+
+    /// Name of the attribute that exports a function under a C symbol
+    pub const EXPORT: &'static str = "export";
+
+    /// Name of the attribute that compiles a function once per CPU feature level
+    pub const TARGET_CLONES: &'static str = "target_clones";
+
+    /// The marker attribute of a `comptime func`.
+    pub fn comptime() -> Self {
+        Self {
+            name: Self::COMPTIME.to_string(),
+            params: Vec::new(),
+        }
+    }
+}
+
+/// Returns true if `attributes` mark a `comptime func`, which is only evaluated at compile time.
+pub fn is_comptime_function(attributes: &[Attribute]) -> bool {
+    attributes
+        .iter()
+        .any(|attribute| attribute.name == Attribute::COMPTIME)
+}
+
+/// Returns the C symbol of a function marked `@export` (its own name) or
+/// `@export(name: "symbol")`, or `None` if it is not exported.
+pub fn exported_symbol(attributes: &[Attribute], function_name: &str) -> Option<String> {
+    let attribute = attributes
+        .iter()
+        .find(|attribute| attribute.name == Attribute::EXPORT)?;
+    let symbol = attribute
+        .params
+        .iter()
+        .find_map(|param| match (&param.key[..], &param.value) {
+            ("name", AttributeValue::String(symbol)) => Some(symbol.clone()),
+            _ => None,
+        });
+    Some(symbol.unwrap_or_else(|| function_name.to_string()))
+}
+
+/// Returns the feature levels of a function marked `@target_clones(avx512, avx2)`,
+/// in the order listed, or `None` if it is not multiversioned.
+pub fn target_clones(attributes: &[Attribute]) -> Option<Vec<String>> {
+    let attribute = attributes
+        .iter()
+        .find(|attribute| attribute.name == Attribute::TARGET_CLONES)?;
+    Some(
+        attribute
+            .params
+            .iter()
+            .map(|param| param.value.to_string())
+            .collect(),
+    )
+}
+
 impl fmt::Display for Attribute {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "@{}", self.name)?;
@@ -752,7 +1560,11 @@ impl fmt::Display for Attribute {
                 if i > 0 {
                     write!(f, ", ")?;
                 }
-                write!(f, "{}: {}", param.key, param.value)?;
+                if param.key.is_empty() {
+                    write!(f, "{}", param.value)?;
+                } else {
+                    write!(f, "{}: {}", param.key, param.value)?;
+                }
             }
             write!(f, ")")?;
         }
@@ -780,6 +1592,56 @@ pub struct StructField {
     pub type_annotation: TypeAnnotation,
 }
 
+/// A method signature in a trait declaration: `func area(self) -> f32;`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraitMethod {
+    pub name: String,
+    pub params: Vec<Parameter>, // Starts with the `self` receiver
+    pub return_type: Option<TypeAnnotation>,
+}
+
+impl fmt::Display for TraitMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "func {}(", self.name)?;
+        for (i, param) in self.params.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", param)?;
+        }
+        write!(f, ")")?;
+        if let Some(ret_type) = &self.return_type {
+            write!(f, " -> {}", ret_type)?;
+        }
+        write!(f, ";")
+    }
+}
+
+/// A C function declared in an `extern "C"` block: `func png_read(path: string) -> i32;`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExternFunction {
+    pub name: String,
+    pub params: Vec<Parameter>,
+    pub return_type: Option<TypeAnnotation>,
+}
+
+impl fmt::Display for ExternFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "func {}(", self.name)?;
+        for (i, param) in self.params.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", param)?;
+        }
+        write!(f, ")")?;
+        if let Some(ret_type) = &self.return_type {
+            write!(f, " -> {}", ret_type)?;
+        }
+        write!(f, ";")
+    }
+}
+
 /// Represents a variant in an enum declaration
 #[derive(Debug, Clone, PartialEq)]
 pub struct EnumVariant {
@@ -803,14 +1665,66 @@ pub enum Pattern {
         patterns: Vec<Pattern>, // Sub-patterns for variant data
     },
 
+    /// Tuple pattern: (a, b), binds each element of a tuple
+    Tuple { patterns: Vec<Pattern> },
+
+    /// Struct pattern: Point { x, y: 0 }, or Point { x, .. } to skip the other fields
+    Struct {
+        name: String,
+        fields: Vec<FieldPattern>,
+        has_rest: bool,
+    },
+
+    /// Range pattern: 1..=9 (inclusive) or 0..10 (exclusive)
+    Range {
+        start: Literal,
+        end: Literal,
+        inclusive: bool,
+    },
+
+    /// Or-pattern: A | B, matches when any alternative does
+    Or(Vec<Pattern>),
+
     /// Wildcard pattern: _
     Wildcard,
 }
 
+/// Represents one field of a struct pattern: `x: pattern`, or `x` to bind
+/// the field to a variable of the same name
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldPattern {
+    pub name: String,
+    pub pattern: Pattern,
+}
+
+impl Pattern {
+    /// The variable names this pattern binds, in source order.
+    pub fn bound_names(&self) -> Vec<&str> {
+        match self {
+            Pattern::Variable(name) => vec![name.as_str()],
+            Pattern::EnumVariant { patterns, .. } | Pattern::Tuple { patterns } => patterns
+                .iter()
+                .flat_map(|pattern| pattern.bound_names())
+                .collect(),
+            Pattern::Struct { fields, .. } => fields
+                .iter()
+                .flat_map(|field| field.pattern.bound_names())
+                .collect(),
+            // Every alternative binds the same names
+            Pattern::Or(alternatives) => alternatives
+                .first()
+                .map(|alternative| alternative.bound_names())
+                .unwrap_or_default(),
+            Pattern::Literal(_) | Pattern::Range { .. } | Pattern::Wildcard => Vec::new(),
+        }
+    }
+}
+
 /// Represents a match arm with pattern and expression
 #[derive(Debug, Clone, PartialEq)]
 pub struct MatchArm {
     pub pattern: Pattern,
+    pub guard: Option<Expr>, // `if condition` after the pattern
     pub expression: Expr,
 }
 
@@ -860,14 +1774,77 @@ impl fmt::Display for Pattern {
                 }
                 Ok(())
             }
+            Pattern::Tuple { patterns } => {
+                write!(f, "(")?;
+                for (i, pattern) in patterns.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", pattern)?;
+                }
+                if patterns.len() == 1 {
+                    write!(f, ",")?;
+                }
+                write!(f, ")")
+            }
+            Pattern::Struct {
+                name,
+                fields,
+                has_rest,
+            } => {
+                write!(f, "{} {{", name)?;
+                for (i, field) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, " {}", field)?;
+                }
+                if *has_rest {
+                    if !fields.is_empty() {
+                        write!(f, ",")?;
+                    }
+                    write!(f, " ..")?;
+                }
+                write!(f, " }}")
+            }
+            Pattern::Range {
+                start,
+                end,
+                inclusive,
+            } => {
+                let operator = if *inclusive { "..=" } else { ".." };
+                write!(f, "{}{}{}", start, operator, end)
+            }
+            Pattern::Or(alternatives) => {
+                for (i, alternative) in alternatives.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " | ")?;
+                    }
+                    write!(f, "{}", alternative)?;
+                }
+                Ok(())
+            }
             Pattern::Wildcard => write!(f, "_"),
         }
     }
 }
 
+impl fmt::Display for FieldPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.pattern {
+            Pattern::Variable(binding) if *binding == self.name => write!(f, "{}", self.name),
+            pattern => write!(f, "{}: {}", self.name, pattern),
+        }
+    }
+}
+
 impl fmt::Display for MatchArm {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} => {}", self.pattern, self.expression)
+        write!(f, "{}", self.pattern)?;
+        if let Some(guard) = &self.guard {
+            write!(f, " if {}", guard)?;
+        }
+        write!(f, " => {}", self.expression)
     }
 }
 
@@ -884,55 +1861,126 @@ impl fmt::Display for StructFieldInit {
     }
 }
 
+/// Represents a single name brought into scope by an import: `name` or `name as alias`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportItem {
+    pub name: String,
+    pub alias: Option<String>,
+}
+
+impl ImportItem {
+    /// The name this item is bound to in the importing module
+    pub fn local_name(&self) -> &str {
+        self.alias.as_deref().unwrap_or(&self.name)
+    }
+}
+
+impl fmt::Display for ImportItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if let Some(alias) = &self.alias {
+            write!(f, " as {}", alias)?;
+        }
+        Ok(())
+    }
+}
+
 /// Represents a statement in the AST
+///
+/// Like `Expr`, every variant carries the `Span` of its source text.
 #[derive(Debug, Clone, PartialEq)]
 pub enum Stmt {
     /// Expression statement, e.g., `foo()`
-    Expression(Expr),
+    Expression(Expr, Span),
 
-    /// Variable declaration, e.g., `let x = 5` or `let mut y: i32 = 10`
+    /// Variable declaration, e.g., `let x = 5`, `let mut y: i32 = 10` or `let (q, r) = f()`
     VarDeclaration {
-        name: String,
+        pattern: Pattern,
+        is_mutable: bool, // declared with `let mut`
         type_annotation: Option<TypeAnnotation>,
         initializer: Option<Expr>,
+        span: Span,
+    },
+
+    /// Constant declaration: `const N: i32 = 16;`, folded at compile time
+    ConstDeclaration {
+        name: String,
+        type_annotation: TypeAnnotation,
+        value: Expr,
+        span: Span,
     },
 
     /// Block of statements enclosed in braces: `{ ... }`
-    Block(Vec<Stmt>),
+    Block(Vec<Stmt>, Span),
 
-    /// Function declaration: `func name(params) -> return_type { body }`
+    /// Function declaration: `func name<T>(params) -> return_type { body }`
     FunctionDeclaration {
         name: String,
+        type_params: Vec<String>, // Empty for non-generic functions
         params: Vec<Parameter>,
         return_type: Option<TypeAnnotation>,
         body: Box<Stmt>, // Block statement
         attributes: Vec<Attribute>,
+        span: Span,
     },
 
-    /// Struct declaration: `struct Name { field1: Type1, field2: Type2 }`
+    /// Struct declaration: `struct Name<T> { field1: Type1, field2: T }`
     StructDeclaration {
         name: String,
+        type_params: Vec<String>, // Empty for non-generic structs
         fields: Vec<StructField>,
+        span: Span,
     },
 
     /// Enum declaration: `enum Name { Variant1, Variant2(Type) }`
     EnumDeclaration {
         name: String,
         variants: Vec<EnumVariant>,
+        span: Span,
+    },
+
+    /// Trait declaration: `trait Shape { func area(self) -> f32; }`
+    TraitDeclaration {
+        name: String,
+        methods: Vec<TraitMethod>,
+        span: Span,
+    },
+
+    /// Impl block: `impl Point { ... }` or `impl Shape for Circle { ... }`
+    /// `methods` holds the function declarations of the block; functions
+    /// without a `self` parameter are associated functions like `Point::new`.
+    ImplBlock {
+        trait_name: Option<String>, // None for inherent impls
+        type_name: String,
+        methods: Vec<Stmt>,
+        span: Span,
+    },
+
+    /// Block of foreign functions: `extern "C" { func png_read(path: string) -> i32; }`.
+    /// The functions are defined by libraries linked into the program.
+    ExternBlock {
+        abi: String,
+        functions: Vec<ExternFunction>,
+        span: Span,
     },
 
     /// Return statement: `return expr`
-    Return(Option<Expr>),
+    Return(Option<Expr>, Span),
 
     /// If statement: `if condition { then_branch } else { else_branch }`
     If {
         condition: Expr,
         then_branch: Box<Stmt>,
         else_branch: Option<Box<Stmt>>,
+        span: Span,
     },
 
     /// While loop: `while condition { body }`
-    While { condition: Expr, body: Box<Stmt> },
+    While {
+        condition: Expr,
+        body: Box<Stmt>,
+        span: Span,
+    },
 
     /// For loop: `for init; condition; increment { body }`
     For {
@@ -940,6 +1988,7 @@ pub enum Stmt {
         condition: Option<Expr>,
         increment: Option<Expr>,
         body: Box<Stmt>,
+        span: Span,
     },
 
     /// For-in loop: `for item in array { body }`
@@ -947,17 +1996,137 @@ pub enum Stmt {
         variable: String,
         iterable: Expr,
         body: Box<Stmt>,
+        span: Span,
+    },
+
+    /// Break out of the innermost loop, or the loop labelled `'label`: `break;`, `break 'outer;`
+    Break(Option<String>, Span),
+
+    /// Skip to the next iteration of the innermost or labelled loop: `continue 'outer;`
+    Continue(Option<String>, Span),
+
+    /// A labelled loop: `'outer: while (cond) { ... }`
+    Labeled {
+        label: String,
+        body: Box<Stmt>,
+        span: Span,
+    },
+
+    /// Import declaration: `import math::vec as v;`, `use math::vec::{dot, cross};`
+    /// or `from math::vec import dot;`. An empty `items` list imports the whole
+    /// module under `alias` (or the last path segment).
+    ImportDeclaration {
+        module_path: String,
+        items: Vec<ImportItem>,
+        alias: Option<String>,
+        span: Span,
     },
 }
 
+impl Stmt {
+    /// The source range of this statement
+    pub fn span(&self) -> Span {
+        match self {
+            Stmt::Expression(_, span)
+            | Stmt::Block(_, span)
+            | Stmt::Return(_, span)
+            | Stmt::Break(_, span)
+            | Stmt::Continue(_, span)
+            | Stmt::VarDeclaration { span, .. }
+            | Stmt::ConstDeclaration { span, .. }
+            | Stmt::FunctionDeclaration { span, .. }
+            | Stmt::StructDeclaration { span, .. }
+            | Stmt::EnumDeclaration { span, .. }
+            | Stmt::TraitDeclaration { span, .. }
+            | Stmt::ImplBlock { span, .. }
+            | Stmt::ExternBlock { span, .. }
+            | Stmt::If { span, .. }
+            | Stmt::While { span, .. }
+            | Stmt::For { span, .. }
+            | Stmt::ForIn { span, .. }
+            | Stmt::Labeled { span, .. }
+            | Stmt::ImportDeclaration { span, .. } => span.clone(),
+        }
+    }
+
+    fn collect_variable_names(&self, names: &mut Vec<String>) {
+        match self {
+            Stmt::Expression(expr, _) | Stmt::Return(Some(expr), _) => {
+                expr.collect_variable_names(names)
+            }
+            Stmt::VarDeclaration {
+                initializer: Some(expr),
+                ..
+            }
+            | Stmt::ConstDeclaration { value: expr, .. } => expr.collect_variable_names(names),
+            Stmt::Block(statements, _) => {
+                for stmt in statements {
+                    stmt.collect_variable_names(names);
+                }
+            }
+            Stmt::If {
+                condition,
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                condition.collect_variable_names(names);
+                then_branch.collect_variable_names(names);
+                if let Some(else_branch) = else_branch {
+                    else_branch.collect_variable_names(names);
+                }
+            }
+            Stmt::While {
+                condition, body, ..
+            } => {
+                condition.collect_variable_names(names);
+                body.collect_variable_names(names);
+            }
+            Stmt::For {
+                initializer,
+                condition,
+                increment,
+                body,
+                ..
+            } => {
+                if let Some(initializer) = initializer {
+                    initializer.collect_variable_names(names);
+                }
+                for expr in condition.iter().chain(increment.iter()) {
+                    expr.collect_variable_names(names);
+                }
+                body.collect_variable_names(names);
+            }
+            Stmt::ForIn { iterable, body, .. } => {
+                iterable.collect_variable_names(names);
+                body.collect_variable_names(names);
+            }
+            Stmt::Labeled { body, .. } => body.collect_variable_names(names),
+            // Nested declarations have their own scope
+            Stmt::VarDeclaration { .. }
+            | Stmt::Return(None, _)
+            | Stmt::Break(..)
+            | Stmt::Continue(..)
+            | Stmt::FunctionDeclaration { .. }
+            | Stmt::StructDeclaration { .. }
+            | Stmt::EnumDeclaration { .. }
+            | Stmt::TraitDeclaration { .. }
+            | Stmt::ImplBlock { .. }
+            | Stmt::ExternBlock { .. }
+            | Stmt::ImportDeclaration { .. } => {}
+        }
+    }
+}
+
 impl fmt::Display for Stmt {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Stmt::Expression(expr) => write!(f, "{};", expr),
+            Stmt::Expression(expr, _) => write!(f, "{};", expr),
             Stmt::VarDeclaration {
-                name,
+                pattern,
                 type_annotation,
                 initializer,
+                ..
             } => {
                 write!(f, "let ")?;
 
@@ -965,9 +2134,9 @@ impl fmt::Display for Stmt {
                     if type_ann.is_mutable {
                         write!(f, "mut ")?;
                     }
-                    write!(f, "{}: {}", name, type_ann.name)?;
+                    write!(f, "{}: {}", pattern, type_ann.name)?;
                 } else {
-                    write!(f, "{}", name)?;
+                    write!(f, "{}", pattern)?;
                 }
 
                 if let Some(init) = initializer {
@@ -976,7 +2145,7 @@ impl fmt::Display for Stmt {
 
                 write!(f, ";")
             }
-            Stmt::Block(statements) => {
+            Stmt::Block(statements, _) => {
                 writeln!(f, "{{")?;
                 for stmt in statements {
                     writeln!(f, "    {}", stmt)?;
@@ -985,16 +2154,22 @@ impl fmt::Display for Stmt {
             }
             Stmt::FunctionDeclaration {
                 name,
+                type_params,
                 params,
                 return_type,
                 body,
                 attributes,
+                ..
             } => {
                 // Display attributes first
                 for attr in attributes {
                     write!(f, "{}\n", attr)?;
                 }
-                write!(f, "func {}(", name)?;
+                write!(f, "func {}", name)?;
+                if !type_params.is_empty() {
+                    write!(f, "<{}>", type_params.join(", "))?;
+                }
+                write!(f, "(")?;
 
                 for (i, param) in params.iter().enumerate() {
                     if i > 0 {
@@ -1011,8 +2186,17 @@ impl fmt::Display for Stmt {
 
                 write!(f, " {}", body)
             }
-            Stmt::StructDeclaration { name, fields } => {
-                write!(f, "struct {} {{", name)?;
+            Stmt::StructDeclaration {
+                name,
+                type_params,
+                fields,
+                ..
+            } => {
+                write!(f, "struct {}", name)?;
+                if !type_params.is_empty() {
+                    write!(f, "<{}>", type_params.join(", "))?;
+                }
+                write!(f, " {{")?;
 
                 for (i, field) in fields.iter().enumerate() {
                     if i > 0 {
@@ -1023,7 +2207,7 @@ impl fmt::Display for Stmt {
 
                 write!(f, "}}")
             }
-            Stmt::EnumDeclaration { name, variants } => {
+            Stmt::EnumDeclaration { name, variants, .. } => {
                 write!(f, "enum {} {{", name)?;
 
                 for (i, variant) in variants.iter().enumerate() {
@@ -1035,7 +2219,36 @@ impl fmt::Display for Stmt {
 
                 write!(f, "}}")
             }
-            Stmt::Return(expr) => {
+            Stmt::TraitDeclaration { name, methods, .. } => {
+                write!(f, "trait {} {{", name)?;
+                for method in methods {
+                    write!(f, " {}", method)?;
+                }
+                write!(f, " }}")
+            }
+            Stmt::ImplBlock {
+                trait_name,
+                type_name,
+                methods,
+                ..
+            } => {
+                match trait_name {
+                    Some(trait_name) => writeln!(f, "impl {} for {} {{", trait_name, type_name)?,
+                    None => writeln!(f, "impl {} {{", type_name)?,
+                }
+                for method in methods {
+                    writeln!(f, "    {}", method)?;
+                }
+                write!(f, "}}")
+            }
+            Stmt::ExternBlock { abi, functions, .. } => {
+                write!(f, "extern \"{}\" {{", abi)?;
+                for function in functions {
+                    write!(f, " {}", function)?;
+                }
+                write!(f, " }}")
+            }
+            Stmt::Return(expr, _) => {
                 if let Some(e) = expr {
                     write!(f, "return {};", e)
                 } else {
@@ -1046,6 +2259,7 @@ impl fmt::Display for Stmt {
                 condition,
                 then_branch,
                 else_branch,
+                ..
             } => {
                 write!(f, "if {} {}", condition, then_branch)?;
 
@@ -1055,7 +2269,9 @@ impl fmt::Display for Stmt {
 
                 Ok(())
             }
-            Stmt::While { condition, body } => {
+            Stmt::While {
+                condition, body, ..
+            } => {
                 write!(f, "while {} {}", condition, body)
             }
             Stmt::For {
@@ -1063,6 +2279,7 @@ impl fmt::Display for Stmt {
                 condition,
                 increment,
                 body,
+                ..
             } => {
                 write!(f, "for ")?;
 
@@ -1088,9 +2305,48 @@ impl fmt::Display for Stmt {
                 variable,
                 iterable,
                 body,
+                ..
             } => {
                 write!(f, "for {} in {} {}", variable, iterable, body)
             }
+            Stmt::Break(label, _) => match label {
+                Some(label) => write!(f, "break '{};", label),
+                None => write!(f, "break;"),
+            },
+            Stmt::Continue(label, _) => match label {
+                Some(label) => write!(f, "continue '{};", label),
+                None => write!(f, "continue;"),
+            },
+            Stmt::Labeled { label, body, .. } => write!(f, "'{}: {}", label, body),
+            Stmt::ConstDeclaration {
+                name,
+                type_annotation,
+                value,
+                ..
+            } => write!(f, "const {}: {} = {};", name, type_annotation, value),
+            Stmt::ImportDeclaration {
+                module_path,
+                items,
+                alias,
+                ..
+            } => {
+                if items.is_empty() {
+                    write!(f, "import {}", module_path)?;
+                    if let Some(alias) = alias {
+                        write!(f, " as {}", alias)?;
+                    }
+                    return write!(f, ";");
+                }
+
+                write!(f, "use {}::{{", module_path)?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "}};")
+            }
         }
     }
 }
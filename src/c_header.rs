@@ -0,0 +1,169 @@
+//! C headers for libraries built with `--crate-type=cdylib` or `staticlib`.
+//!
+//! Every function marked `@export` is declared with the C types of its checked
+//! signature, so C and C++ programs can call into the library through the header.
+
+use crate::type_system::{EaType, ExportedFunction};
+
+/// The C spelling of `ty`, or `None` if the type has no C equivalent.
+///
+/// `()` is spelled `void`, which is only meaningful as a return type.
+pub fn c_type_name(ty: &EaType) -> Option<String> {
+    let name = match ty {
+        EaType::I8 => "int8_t",
+        EaType::I16 => "int16_t",
+        EaType::I32 => "int32_t",
+        EaType::I64 => "int64_t",
+        EaType::U8 => "uint8_t",
+        EaType::U16 => "uint16_t",
+        EaType::U32 => "uint32_t",
+        EaType::U64 => "uint64_t",
+        EaType::F32 => "float",
+        EaType::F64 => "double",
+        EaType::Bool => "bool",
+        EaType::Unit => "void",
+        EaType::Pointer { target, is_mutable } => {
+            if **target == EaType::Unit {
+                return None;
+            }
+            let target_name = c_type_name(target)?;
+            return Some(match (is_mutable, &**target) {
+                (true, _) => format!("{}*", target_name),
+                // `const` binds to the pointer it follows: `float* const*`
+                (false, EaType::Pointer { .. }) => format!("{} const*", target_name),
+                (false, _) => format!("const {}*", target_name),
+            });
+        }
+        _ => return None,
+    };
+    Some(name.to_string())
+}
+
+/// Generates the header declaring `exports` for the library `library_name`.
+pub fn generate_c_header(library_name: &str, exports: &[ExportedFunction]) -> String {
+    let guard = include_guard(library_name);
+    let mut header = String::new();
+
+    header.push_str(&format!(
+        "/* Generated by the Eä compiler for {}. Do not edit. */\n",
+        library_name
+    ));
+    header.push_str(&format!("#ifndef {}\n#define {}\n\n", guard, guard));
+    header.push_str("#include <stdbool.h>\n#include <stdint.h>\n\n");
+    header.push_str("#ifdef __cplusplus\nextern \"C\" {\n#endif\n\n");
+
+    for export in exports {
+        header.push_str(&function_declaration(export));
+        header.push('\n');
+    }
+    if !exports.is_empty() {
+        header.push('\n');
+    }
+
+    header.push_str("#ifdef __cplusplus\n}\n#endif\n\n");
+    header.push_str(&format!("#endif /* {} */\n", guard));
+    header
+}
+
+/// `float dot(const float* a, const float* b, int64_t len);`
+fn function_declaration(export: &ExportedFunction) -> String {
+    // Exported signatures are checked to have C types; see `TypeChecker::check_export`
+    let c_type = |ty: &EaType| c_type_name(ty).unwrap_or_else(|| "void".to_string());
+
+    let params = if export.params.is_empty() {
+        "void".to_string()
+    } else {
+        export
+            .params
+            .iter()
+            .map(|(name, ty)| format!("{} {}", c_type(ty), name))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    format!(
+        "{} {}({});",
+        c_type(&export.return_type),
+        export.symbol,
+        params
+    )
+}
+
+/// `kernels` -> `KERNELS_H`
+fn include_guard(library_name: &str) -> String {
+    let mut guard: String = library_name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if guard.starts_with(|c: char| c.is_ascii_digit()) {
+        guard.insert(0, '_');
+    }
+    guard + "_H"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pointer(target: EaType, is_mutable: bool) -> EaType {
+        EaType::Pointer {
+            target: Box::new(target),
+            is_mutable,
+        }
+    }
+
+    #[test]
+    fn test_c_type_names() {
+        assert_eq!(c_type_name(&EaType::I64).as_deref(), Some("int64_t"));
+        assert_eq!(c_type_name(&EaType::F32).as_deref(), Some("float"));
+        assert_eq!(
+            c_type_name(&pointer(EaType::F32, false)).as_deref(),
+            Some("const float*")
+        );
+        assert_eq!(
+            c_type_name(&pointer(EaType::U8, true)).as_deref(),
+            Some("uint8_t*")
+        );
+        assert_eq!(
+            c_type_name(&pointer(pointer(EaType::F32, true), false)).as_deref(),
+            Some("float* const*")
+        );
+
+        assert_eq!(c_type_name(&EaType::String), None);
+        assert_eq!(c_type_name(&EaType::Array(Box::new(EaType::F32))), None);
+        assert_eq!(c_type_name(&pointer(EaType::Unit, true)), None);
+    }
+
+    #[test]
+    fn test_generate_c_header() {
+        let exports = vec![
+            ExportedFunction {
+                symbol: "ea_dot".to_string(),
+                params: vec![
+                    ("a".to_string(), pointer(EaType::F32, false)),
+                    ("b".to_string(), pointer(EaType::F32, false)),
+                    ("len".to_string(), EaType::I64),
+                ],
+                return_type: EaType::F32,
+            },
+            ExportedFunction {
+                symbol: "reset".to_string(),
+                params: Vec::new(),
+                return_type: EaType::Unit,
+            },
+        ];
+
+        let header = generate_c_header("simd-kernels", &exports);
+        assert!(header.contains("#ifndef SIMD_KERNELS_H\n#define SIMD_KERNELS_H"));
+        assert!(header.contains("#include <stdint.h>"));
+        assert!(header.contains("extern \"C\" {"));
+        assert!(header.contains("float ea_dot(const float* a, const float* b, int64_t len);"));
+        assert!(header.contains("void reset(void);"));
+        assert!(header.ends_with("#endif /* SIMD_KERNELS_H */\n"));
+    }
+}
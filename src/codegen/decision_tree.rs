@@ -0,0 +1,526 @@
+//! Lowering of `match` arms into decision trees.
+//!
+//! Rather than testing the arms one after another, the arms are compiled
+//! into a tree that branches on one part of the matched value at a time, in
+//! the style of Maranget's "Compiling pattern matching to good decision
+//! trees". Every path through the tree tests each part of the value at most
+//! once, and tuples, structs, or-patterns and bindings cost no tests at all.
+
+use crate::ast::{Literal, MatchArm, Pattern};
+use std::cmp::Ordering;
+
+/// A step from a value to one of its parts.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Projection {
+    /// An element of a tuple
+    Element(usize),
+    /// A named field of a struct
+    Field(String),
+    /// A value carried by the enum variant the tree has already switched on
+    Payload(usize),
+}
+
+/// The path from the matched value to the part a test or binding looks at.
+pub type Occurrence = Vec<Projection>;
+
+/// A variable bound by an arm, with the part of the matched value it names.
+pub type Binding = (String, Occurrence);
+
+/// A test on a scalar part of the matched value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Test {
+    Equals(Literal),
+    InRange {
+        start: Literal,
+        end: Literal,
+        inclusive: bool,
+    },
+}
+
+/// A decision tree for a match expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Decision {
+    /// Runs `arm` with its variables bound
+    Leaf { arm: usize, bindings: Vec<Binding> },
+    /// Binds the variables of `arm`, then runs it if its guard holds
+    Guard {
+        arm: usize,
+        bindings: Vec<Binding>,
+        otherwise: Box<Decision>,
+    },
+    /// Branches on the variant of the enum at `occurrence`
+    Switch {
+        occurrence: Occurrence,
+        enum_name: String,
+        cases: Vec<(String, Decision)>,
+        default: Box<Decision>,
+    },
+    /// Branches on a test of the scalar at `occurrence`
+    Test {
+        occurrence: Occurrence,
+        test: Test,
+        then: Box<Decision>,
+        otherwise: Box<Decision>,
+    },
+    /// No arm matches
+    Fail,
+}
+
+/// An arm still to be matched: the parts of the value it tests and the
+/// variables it has bound so far.
+#[derive(Debug, Clone)]
+struct Row {
+    columns: Vec<(Occurrence, Pattern)>,
+    bindings: Vec<Binding>,
+    arm: usize,
+    guarded: bool,
+}
+
+/// Builds the decision tree for `arms`, which must be type checked.
+pub fn build(arms: &[MatchArm]) -> Decision {
+    let mut rows = Vec::new();
+    for (index, arm) in arms.iter().enumerate() {
+        let row = Row {
+            columns: vec![(Vec::new(), arm.pattern.clone())],
+            bindings: Vec::new(),
+            arm: index,
+            guarded: arm.guard.is_some(),
+        };
+        normalize(row, &mut rows);
+    }
+    compile(rows)
+}
+
+/// Reduces a row to columns that need a test: bindings and wildcards are
+/// dropped, tuples and structs are split into their parts and or-patterns
+/// into one row per alternative.
+fn normalize(mut row: Row, out: &mut Vec<Row>) {
+    let mut index = 0;
+    while index < row.columns.len() {
+        let (occurrence, pattern) = row.columns[index].clone();
+        match pattern {
+            Pattern::Wildcard => {
+                row.columns.remove(index);
+            }
+            Pattern::Variable(name) => {
+                row.bindings.push((name, occurrence));
+                row.columns.remove(index);
+            }
+            Pattern::Tuple { patterns } => {
+                let parts = patterns.into_iter().enumerate().map(|(element, pattern)| {
+                    (project(&occurrence, Projection::Element(element)), pattern)
+                });
+                row.columns.splice(index..=index, parts);
+            }
+            Pattern::Struct { fields, .. } => {
+                let parts = fields.into_iter().map(|field| {
+                    (
+                        project(&occurrence, Projection::Field(field.name)),
+                        field.pattern,
+                    )
+                });
+                row.columns.splice(index..=index, parts);
+            }
+            Pattern::Or(alternatives) => {
+                for alternative in alternatives {
+                    let mut alternative_row = row.clone();
+                    alternative_row.columns[index].1 = alternative;
+                    normalize(alternative_row, out);
+                }
+                return;
+            }
+            Pattern::Literal(_) | Pattern::Range { .. } | Pattern::EnumVariant { .. } => index += 1,
+        }
+    }
+    out.push(row);
+}
+
+fn project(occurrence: &Occurrence, projection: Projection) -> Occurrence {
+    let mut projected = occurrence.clone();
+    projected.push(projection);
+    projected
+}
+
+fn compile(rows: Vec<Row>) -> Decision {
+    let first = match rows.first() {
+        Some(first) => first,
+        None => return Decision::Fail,
+    };
+
+    // The first row matches once nothing is left to test
+    if first.columns.is_empty() {
+        let (arm, bindings) = (first.arm, first.bindings.clone());
+        if first.guarded {
+            return Decision::Guard {
+                arm,
+                bindings,
+                otherwise: Box::new(compile(rows[1..].to_vec())),
+            };
+        }
+        return Decision::Leaf { arm, bindings };
+    }
+
+    let (occurrence, pattern) = first.columns[0].clone();
+    match pattern {
+        Pattern::EnumVariant { enum_name, .. } => switch_on_variant(rows, occurrence, enum_name),
+        Pattern::Literal(literal) => test_scalar(rows, occurrence, Test::Equals(literal)),
+        Pattern::Range {
+            start,
+            end,
+            inclusive,
+        } => test_scalar(
+            rows,
+            occurrence,
+            Test::InRange {
+                start,
+                end,
+                inclusive,
+            },
+        ),
+        _ => unreachable!("normalized rows only test literals, ranges and variants"),
+    }
+}
+
+/// Splits `rows` on the variant of the enum at `occurrence`, with one case per
+/// variant the rows name and a default for the others.
+fn switch_on_variant(rows: Vec<Row>, occurrence: Occurrence, mut enum_name: String) -> Decision {
+    let mut variants: Vec<String> = Vec::new();
+    for row in &rows {
+        if let Some((
+            _,
+            Pattern::EnumVariant {
+                enum_name: name,
+                variant,
+                ..
+            },
+        )) = column(row, &occurrence)
+        {
+            if !variants.contains(variant) {
+                variants.push(variant.clone());
+            }
+            // `Ok(x)` style patterns may leave out the enum name
+            if enum_name.is_empty() {
+                enum_name = name.clone();
+            }
+        }
+    }
+
+    let cases = variants
+        .into_iter()
+        .map(|variant| {
+            let mut case_rows = Vec::new();
+            for row in &rows {
+                match column_index(row, &occurrence) {
+                    None => case_rows.push(row.clone()),
+                    Some(index) => {
+                        if let Pattern::EnumVariant {
+                            variant: row_variant,
+                            patterns,
+                            ..
+                        } = &row.columns[index].1
+                        {
+                            if *row_variant != variant {
+                                continue;
+                            }
+                            let mut case_row = row.clone();
+                            let payload = patterns.iter().enumerate().map(|(field, pattern)| {
+                                (
+                                    project(&occurrence, Projection::Payload(field)),
+                                    pattern.clone(),
+                                )
+                            });
+                            case_row.columns.splice(index..=index, payload);
+                            normalize(case_row, &mut case_rows);
+                        }
+                    }
+                }
+            }
+            (variant, compile(case_rows))
+        })
+        .collect();
+
+    let default_rows = rows
+        .into_iter()
+        .filter(|row| column_index(row, &occurrence).is_none())
+        .collect();
+
+    Decision::Switch {
+        occurrence,
+        enum_name,
+        cases,
+        default: Box::new(compile(default_rows)),
+    }
+}
+
+/// Splits `rows` on whether the scalar at `occurrence` passes `test`. Rows
+/// whose pattern neither follows from the outcome nor contradicts it keep
+/// their column, to be tested again further down.
+fn test_scalar(rows: Vec<Row>, occurrence: Occurrence, test: Test) -> Decision {
+    let mut then_rows = Vec::new();
+    let mut otherwise_rows = Vec::new();
+
+    for row in rows {
+        let index = match column_index(&row, &occurrence) {
+            Some(index) => index,
+            None => {
+                then_rows.push(row.clone());
+                otherwise_rows.push(row);
+                continue;
+            }
+        };
+        let pattern_test = match &row.columns[index].1 {
+            Pattern::Literal(literal) => Test::Equals(literal.clone()),
+            Pattern::Range {
+                start,
+                end,
+                inclusive,
+            } => Test::InRange {
+                start: start.clone(),
+                end: end.clone(),
+                inclusive: *inclusive,
+            },
+            _ => unreachable!("a scalar is only matched by literals and ranges"),
+        };
+
+        if implies(&test, &pattern_test) {
+            let mut matched = row.clone();
+            matched.columns.remove(index);
+            then_rows.push(matched);
+        } else if !disjoint(&test, &pattern_test) {
+            then_rows.push(row.clone());
+        }
+        if !implies(&pattern_test, &test) {
+            otherwise_rows.push(row);
+        }
+    }
+
+    Decision::Test {
+        occurrence,
+        test,
+        then: Box::new(compile(then_rows)),
+        otherwise: Box::new(compile(otherwise_rows)),
+    }
+}
+
+fn column<'a>(row: &'a Row, occurrence: &Occurrence) -> Option<&'a (Occurrence, Pattern)> {
+    row.columns.iter().find(|(at, _)| at == occurrence)
+}
+
+fn column_index(row: &Row, occurrence: &Occurrence) -> Option<usize> {
+    row.columns.iter().position(|(at, _)| at == occurrence)
+}
+
+/// Orders two literals of the same numeric type.
+fn compare(a: &Literal, b: &Literal) -> Option<Ordering> {
+    match (a, b) {
+        (Literal::Integer(a), Literal::Integer(b)) => Some(a.cmp(b)),
+        (Literal::Float(a), Literal::Float(b)) => a.partial_cmp(b),
+        _ => None,
+    }
+}
+
+/// The bounds of the values a test accepts, each with whether it is included.
+fn bounds(test: &Test) -> (&Literal, &Literal, bool) {
+    match test {
+        Test::Equals(value) => (value, value, true),
+        Test::InRange {
+            start,
+            end,
+            inclusive,
+        } => (start, end, *inclusive),
+    }
+}
+
+/// Whether every value passing `test` also passes `by`. Only claims what it
+/// can prove; unknown cases answer `false`.
+fn implies(test: &Test, by: &Test) -> bool {
+    if let (Test::Equals(a), Test::Equals(b)) = (test, by) {
+        return a == b;
+    }
+    let (start, end, inclusive) = bounds(test);
+    let (by_start, by_end, by_inclusive) = bounds(by);
+    let starts_inside = matches!(
+        compare(start, by_start),
+        Some(Ordering::Greater) | Some(Ordering::Equal)
+    );
+    let ends_inside = match compare(end, by_end) {
+        Some(Ordering::Less) => true,
+        Some(Ordering::Equal) => by_inclusive || !inclusive,
+        _ => false,
+    };
+    starts_inside && ends_inside
+}
+
+/// Whether no value passes both tests. Only claims what it can prove;
+/// unknown cases answer `false`.
+fn disjoint(a: &Test, b: &Test) -> bool {
+    if let (Test::Equals(a), Test::Equals(b)) = (a, b) {
+        if compare(a, b).is_none() {
+            // Strings and booleans are only ever compared for equality
+            return a != b;
+        }
+    }
+    let ends_before = |first: &Test, second: &Test| {
+        let (_, end, inclusive) = bounds(first);
+        let (start, _, _) = bounds(second);
+        match compare(end, start) {
+            Some(Ordering::Less) => true,
+            Some(Ordering::Equal) => !inclusive,
+            _ => false,
+        }
+    };
+    ends_before(a, b) || ends_before(b, a)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{Expr, FieldPattern};
+    use crate::lexer::Span;
+
+    fn arm(pattern: Pattern) -> MatchArm {
+        MatchArm {
+            pattern,
+            guard: None,
+            expression: Expr::Literal(Literal::Integer(0), Span::default()),
+        }
+    }
+
+    fn variant(name: &str, patterns: Vec<Pattern>) -> Pattern {
+        Pattern::EnumVariant {
+            enum_name: "Shape".to_string(),
+            variant: name.to_string(),
+            patterns,
+        }
+    }
+
+    fn range(start: i64, end: i64) -> Pattern {
+        Pattern::Range {
+            start: Literal::Integer(start),
+            end: Literal::Integer(end),
+            inclusive: true,
+        }
+    }
+
+    fn leaf(arm: usize) -> Decision {
+        Decision::Leaf {
+            arm,
+            bindings: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_variants_share_one_switch() {
+        let tree = build(&[
+            arm(variant("Circle", vec![Pattern::Variable("r".to_string())])),
+            arm(Pattern::Or(vec![
+                variant("Square", vec![Pattern::Wildcard]),
+                variant("Empty", Vec::new()),
+            ])),
+        ]);
+        assert_eq!(
+            tree,
+            Decision::Switch {
+                occurrence: Vec::new(),
+                enum_name: "Shape".to_string(),
+                cases: vec![
+                    (
+                        "Circle".to_string(),
+                        Decision::Leaf {
+                            arm: 0,
+                            bindings: vec![("r".to_string(), vec![Projection::Payload(0)])],
+                        }
+                    ),
+                    ("Square".to_string(), leaf(1)),
+                    ("Empty".to_string(), leaf(1)),
+                ],
+                default: Box::new(Decision::Fail),
+            }
+        );
+    }
+
+    #[test]
+    fn test_scalar_tests_are_not_repeated() {
+        // A value outside 1..=9 cannot be 5, so the second arm is only
+        // tested on the path where the range matched
+        let tree = build(&[
+            arm(Pattern::Tuple {
+                patterns: vec![range(1, 9), Pattern::Literal(Literal::Boolean(true))],
+            }),
+            arm(Pattern::Tuple {
+                patterns: vec![Pattern::Literal(Literal::Integer(5)), Pattern::Wildcard],
+            }),
+            arm(Pattern::Wildcard),
+        ]);
+        let first = vec![Projection::Element(0)];
+        let second = vec![Projection::Element(1)];
+        assert_eq!(
+            tree,
+            Decision::Test {
+                occurrence: first.clone(),
+                test: Test::InRange {
+                    start: Literal::Integer(1),
+                    end: Literal::Integer(9),
+                    inclusive: true,
+                },
+                then: Box::new(Decision::Test {
+                    occurrence: second,
+                    test: Test::Equals(Literal::Boolean(true)),
+                    then: Box::new(leaf(0)),
+                    otherwise: Box::new(Decision::Test {
+                        occurrence: first,
+                        test: Test::Equals(Literal::Integer(5)),
+                        then: Box::new(leaf(1)),
+                        otherwise: Box::new(leaf(2)),
+                    }),
+                }),
+                otherwise: Box::new(leaf(2)),
+            }
+        );
+    }
+
+    #[test]
+    fn test_struct_fields_and_guards() {
+        let mut guarded = arm(Pattern::Struct {
+            name: "Point".to_string(),
+            fields: vec![FieldPattern {
+                name: "x".to_string(),
+                pattern: Pattern::Variable("x".to_string()),
+            }],
+            has_rest: true,
+        });
+        guarded.guard = Some(Expr::Literal(Literal::Boolean(true), Span::default()));
+        let tree = build(&[guarded, arm(Pattern::Variable("other".to_string()))]);
+        assert_eq!(
+            tree,
+            Decision::Guard {
+                arm: 0,
+                bindings: vec![("x".to_string(), vec![Projection::Field("x".to_string())])],
+                otherwise: Box::new(Decision::Leaf {
+                    arm: 1,
+                    bindings: vec![("other".to_string(), Vec::new())],
+                }),
+            }
+        );
+    }
+
+    #[test]
+    fn test_range_relations() {
+        let range_test = |start: i64, end: i64, inclusive: bool| Test::InRange {
+            start: Literal::Integer(start),
+            end: Literal::Integer(end),
+            inclusive,
+        };
+        let five = Test::Equals(Literal::Integer(5));
+        assert!(implies(&five, &range_test(1, 9, true)));
+        assert!(!implies(&range_test(1, 9, true), &five));
+        assert!(implies(&range_test(2, 9, false), &range_test(1, 9, true)));
+        assert!(!implies(&range_test(2, 9, true), &range_test(1, 9, false)));
+        assert!(disjoint(&range_test(0, 5, false), &five));
+        assert!(!disjoint(&range_test(0, 5, true), &five));
+        assert!(disjoint(
+            &Test::Equals(Literal::String("a".to_string())),
+            &Test::Equals(Literal::String("b".to_string()))
+        ));
+    }
+}
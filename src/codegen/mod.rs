@@ -4,30 +4,66 @@
 //! This module is responsible for transforming the AST into LLVM IR,
 //! which can then be optimized and compiled to machine code.
 
+mod decision_tree;
+
+use self::decision_tree::{Decision, Projection, Test};
 use crate::ast::{
-    BinaryOp, Expr, Literal, Pattern, SIMDExpr, SIMDOperator, SIMDVectorType, Stmt, StructField,
-    StructFieldInit, TypeAnnotation, UnaryOp,
+    BinaryOp, Expr, ExternFunction, Literal, Pattern, SIMDExpr, SIMDOperator, SIMDVectorType, Stmt,
+    StructField, StructFieldInit, TypeAnnotation, UnaryOp,
 };
+use crate::comptime::{ComptimeEngine, ComptimeFunction, ComptimeValue};
 use crate::error::{CompileError, Result};
+use crate::lexer::Span;
 use crate::memory::{analyze_memory_regions, generate_memory_metadata};
 use crate::memory_profiler::{check_memory_limit, record_memory_usage, CompilationPhase};
 use crate::simd_advanced::{
     AdaptiveVectorizer, AdvancedSIMDCodegen, AdvancedSIMDOp, OptimizationHints,
 };
+use crate::type_system::hardware::{
+    llvm_target_features, target_clone_features, CompileTarget, HardwareDetector, SIMDFeature,
+    TARGET_CLONE_LEVELS,
+};
+use crate::type_system::{CheckedExpressions, TypeContext};
 // Removed unused import per DEVELOPMENT_PROCESS.md - no placeholder comments
 use inkwell::{
     basic_block::BasicBlock,
     builder::Builder,
     context::Context,
-    module::Module,
-    targets::{CodeModel, FileType, InitializationConfig, RelocMode, Target, TargetMachine},
+    module::{Linkage, Module},
+    targets::{
+        CodeModel, FileType, InitializationConfig, RelocMode, Target, TargetMachine, TargetTriple,
+    },
     types::{BasicType, BasicTypeEnum, StructType, VectorType},
-    values::{BasicValue, BasicValueEnum, FunctionValue, IntValue, PointerValue, VectorValue},
+    values::{
+        BasicMetadataValueEnum, BasicValue, BasicValueEnum, CallableValue, FunctionValue, IntValue,
+        PointerValue, VectorValue,
+    },
     AddressSpace, FloatPredicate, IntPredicate, OptimizationLevel,
 };
 use std::collections::HashMap;
 use std::path::Path;
 
+/// Options controlling the code generated for a whole program, set from the command line.
+#[derive(Debug, Clone)]
+pub struct CodegenOptions {
+    /// Check indices that are not constant against the array length at runtime
+    pub bounds_checks: bool,
+    /// Panic when integer `+`, `-` or `*` overflows instead of wrapping around
+    pub overflow_checks: bool,
+    /// The machine to generate code for
+    pub target: CompileTarget,
+}
+
+impl Default for CodegenOptions {
+    fn default() -> Self {
+        Self {
+            bounds_checks: true,
+            overflow_checks: false,
+            target: CompileTarget::default(),
+        }
+    }
+}
+
 /// Optimization configuration parsed from @optimize attributes
 #[derive(Debug, Clone)]
 struct OptimizationConfig {
@@ -82,6 +118,43 @@ enum BufferStrategy {
     Adaptive,
 }
 
+/// Branch targets of a loop that `break`/`continue` can jump to.
+#[derive(Debug, Clone)]
+struct LoopContext<'ctx> {
+    label: Option<String>,
+    break_block: BasicBlock<'ctx>,
+    continue_block: BasicBlock<'ctx>,
+}
+
+/// A generic function, emitted once per distinct set of type arguments it is called with.
+#[derive(Debug, Clone)]
+struct GenericFunction {
+    name: String,
+    type_params: Vec<String>,
+    params: Vec<crate::ast::Parameter>,
+    return_type: Option<TypeAnnotation>,
+    body: Box<Stmt>,
+    module: Option<String>, // declaring module of an imported function, whose names its body uses
+}
+
+/// A generic struct, laid out as a named LLVM struct `Name<Args>` per instantiation.
+#[derive(Debug, Clone)]
+struct GenericStruct {
+    type_params: Vec<String>,
+    fields: Vec<StructField>,
+}
+
+/// The names a module resolves its calls through. Kept once the module is
+/// generated, since its generic functions are emitted where they are called.
+#[derive(Debug, Clone)]
+struct ModuleScope {
+    module_prefix: Option<String>,
+    module_aliases: HashMap<String, String>,
+    import_targets: HashMap<String, String>,
+    symbol_aliases: HashMap<String, String>,
+    generic_functions: HashMap<String, GenericFunction>,
+}
+
 /// Code generator for the Eä programming language.
 pub struct CodeGenerator<'ctx> {
     context: &'ctx Context,
@@ -93,10 +166,45 @@ pub struct CodeGenerator<'ctx> {
     struct_fields: HashMap<String, HashMap<String, u32>>, // struct_name -> {field_name -> field_index}
     optimization_level: OptimizationLevel,
     current_optimization_config: Option<OptimizationConfig>,
+    options: CodegenOptions,
     jit_safe_mode: bool, // Disable SIMD features for JIT compatibility
     // Advanced SIMD integration
     advanced_simd_codegen: Option<AdvancedSIMDCodegen>,
     adaptive_vectorizer: Option<AdaptiveVectorizer>,
+    // Module system: mangling prefix of the module being generated and its imports
+    module_prefix: Option<String>,
+    module_aliases: HashMap<String, String>, // alias -> module path
+    import_targets: HashMap<String, String>, // import path -> module name, for the current module
+    root_module: Option<String>,
+    symbol_aliases: HashMap<String, String>, // local name -> mangled LLVM symbol
+    module_scopes: HashMap<String, ModuleScope>, // module name -> names of the generated module
+    // Enclosing loops (innermost last) and the label of the loop about to be generated
+    loop_stack: Vec<LoopContext<'ctx>>,
+    pending_loop_label: Option<String>,
+    // Generics: declarations are kept and monomorphized when first used
+    generic_functions: HashMap<String, GenericFunction>,
+    generic_structs: HashMap<String, GenericStruct>,
+    type_substitutions: HashMap<String, String>, // type parameter -> concrete type name
+    // Traits: methods of each implementing type, dispatched statically
+    methods: HashMap<String, HashMap<String, String>>, // type name -> method name -> LLVM symbol
+    // Constants: folded values, emitted as LLVM constants at each use
+    constants: HashMap<String, (BasicTypeEnum<'ctx>, ComptimeValue)>,
+    // `comptime func`s: run by the comptime engine, never emitted as LLVM functions
+    comptime_functions: HashMap<String, ComptimeFunction>,
+    // Closures: number generated so far, used to name their LLVM functions
+    closure_count: usize,
+    // Functions declared in `extern "C"` blocks, defined by linked libraries
+    extern_functions: Vec<String>,
+    // `@export` functions: module symbol -> C symbol they are emitted as
+    exported_symbols: HashMap<String, String>,
+    // What the type checker found out about the expressions of the module being
+    // compiled, such as unsigned integers and the type arguments of generic calls
+    expressions: CheckedExpressions,
+    module_expressions: HashMap<String, CheckedExpressions>,
+    // Generic function instance being generated, empty outside generic functions
+    current_instance: String,
+    // Target SIMD support, which picks the width of `simd<T>` vectors
+    hardware_detector: HardwareDetector,
 }
 
 impl<'ctx> CodeGenerator<'ctx> {
@@ -115,9 +223,31 @@ impl<'ctx> CodeGenerator<'ctx> {
             struct_fields: HashMap::new(),
             optimization_level: OptimizationLevel::Default,
             current_optimization_config: None,
+            options: CodegenOptions::default(),
             jit_safe_mode: true,         // Default for JIT compatibility
             advanced_simd_codegen: None, // Disabled for JIT safety
             adaptive_vectorizer: None,   // Disabled for JIT safety
+            module_prefix: None,
+            module_aliases: HashMap::new(),
+            import_targets: HashMap::new(),
+            root_module: None,
+            symbol_aliases: HashMap::new(),
+            module_scopes: HashMap::new(),
+            loop_stack: Vec::new(),
+            pending_loop_label: None,
+            generic_functions: HashMap::new(),
+            generic_structs: HashMap::new(),
+            type_substitutions: HashMap::new(),
+            methods: HashMap::new(),
+            constants: HashMap::new(),
+            comptime_functions: HashMap::new(),
+            closure_count: 0,
+            extern_functions: Vec::new(),
+            exported_symbols: HashMap::new(),
+            expressions: CheckedExpressions::default(),
+            module_expressions: HashMap::new(),
+            current_instance: String::new(),
+            hardware_detector: HardwareDetector::new(),
         };
 
         // Add minimal builtin functions for JIT compatibility
@@ -140,9 +270,31 @@ impl<'ctx> CodeGenerator<'ctx> {
             struct_fields: HashMap::new(),
             optimization_level: OptimizationLevel::Default,
             current_optimization_config: None,
+            options: CodegenOptions::default(),
             jit_safe_mode: false,        // Full features for static compilation
             advanced_simd_codegen: None, // Will be initialized after hardware detection
             adaptive_vectorizer: None,   // Will be initialized after hardware detection
+            module_prefix: None,
+            module_aliases: HashMap::new(),
+            import_targets: HashMap::new(),
+            root_module: None,
+            symbol_aliases: HashMap::new(),
+            module_scopes: HashMap::new(),
+            loop_stack: Vec::new(),
+            pending_loop_label: None,
+            generic_functions: HashMap::new(),
+            generic_structs: HashMap::new(),
+            type_substitutions: HashMap::new(),
+            methods: HashMap::new(),
+            constants: HashMap::new(),
+            comptime_functions: HashMap::new(),
+            closure_count: 0,
+            extern_functions: Vec::new(),
+            exported_symbols: HashMap::new(),
+            expressions: CheckedExpressions::default(),
+            module_expressions: HashMap::new(),
+            current_instance: String::new(),
+            hardware_detector: HardwareDetector::new(),
         };
 
         // Initialize advanced SIMD components for full compilation
@@ -159,6 +311,37 @@ impl<'ctx> CodeGenerator<'ctx> {
         codegen
     }
 
+    /// Sets the program-wide code generation options.
+    pub fn set_options(&mut self, options: CodegenOptions) {
+        // `simd<T>` is as wide as the vectors of the target, not of the host
+        self.hardware_detector = options.target.hardware_detector();
+        self.options = options;
+    }
+
+    /// Takes what code generation needs to know of the checked types from the
+    /// `context` the type checker returned for the program to compile.
+    pub fn set_type_context(&mut self, context: &TypeContext) {
+        self.expressions = context.expressions.clone();
+        self.module_expressions = context.module_expressions.clone();
+    }
+
+    /// The machine code is generated for.
+    pub fn target(&self) -> &CompileTarget {
+        &self.options.target
+    }
+
+    /// Whether the target triple is another than the host's.
+    pub fn is_cross_compiling(&self) -> bool {
+        match &self.options.target.triple {
+            Some(triple) => {
+                let triple = TargetMachine::normalize_triple(&TargetTriple::create(triple));
+                let host = TargetMachine::normalize_triple(&TargetMachine::get_default_triple());
+                triple.as_str() != host.as_str()
+            }
+            None => false,
+        }
+    }
+
     /// Set JIT safe mode - when false, enables full SIMD support for compilation
     pub fn set_jit_safe_mode(&mut self, safe_mode: bool) {
         self.jit_safe_mode = safe_mode;
@@ -4944,9 +5127,9 @@ impl<'ctx> CodeGenerator<'ctx> {
         // For simplicity, we'll use a fixed-size data field for now
         let data_type = self.context.i64_type(); // 64-bit union field
 
-        let enum_struct_type = self
-            .context
-            .struct_type(&[tag_type.into(), data_type.into()], false);
+        // Named, so impls on enums with the same layout stay apart
+        let enum_struct_type = self.context.opaque_struct_type(name);
+        enum_struct_type.set_body(&[tag_type.into(), data_type.into()], false);
 
         // Store the enum type for later use
         self.struct_types.insert(name.to_string(), enum_struct_type);
@@ -5018,6544 +5201,10154 @@ impl<'ctx> CodeGenerator<'ctx> {
         Ok(enum_value.into())
     }
 
-    /// Generates code for match expression
+    /// Generates code for a match expression. The arms are lowered into a
+    /// decision tree, so each part of the matched value is tested at most once
+    /// on the way to the arm that runs.
     fn generate_match_expression(
         &mut self,
         value: &Expr,
         arms: &[crate::ast::MatchArm],
     ) -> Result<BasicValueEnum<'ctx>> {
-        use crate::ast::{Pattern, Literal};
-
-        // Generate the value to match against
         let match_value = self.generate_expression(value)?;
-        
-        // Get the current function to add basic blocks
-        let current_function = self.builder.get_insert_block().unwrap().get_parent().unwrap();
-        
-        // Create basic blocks
-        let mut match_blocks = Vec::new();
-        let merge_block = self.context.append_basic_block(current_function, "match_merge");
-        
-        // Create PHI node for result values
-        let result_type = self.infer_match_result_type(arms)?;
-        
-        // Create blocks for each match arm
-        for (i, _) in arms.iter().enumerate() {
-            let block = self.context.append_basic_block(current_function, &format!("match_arm_{}", i));
-            match_blocks.push(block);
-        }
-        
-        // Create default block (for unmatched cases)
-        let default_block = self.context.append_basic_block(current_function, "match_default");
-        
-        // Generate comparison chain using conditional branches
-        let mut current_block = self.builder.get_insert_block().unwrap();
-        
-        for (i, arm) in arms.iter().enumerate() {
-            self.builder.position_at_end(current_block);
-            
-            match &arm.pattern {
-                Pattern::Literal(Literal::Integer(lit_val)) => {
-                    if let BasicValueEnum::IntValue(int_val) = match_value {
-                        let case_val = self.context.i32_type().const_int(*lit_val as u64, false);
-                        let condition = self.builder.build_int_compare(
-                            inkwell::IntPredicate::EQ,
-                            int_val,
-                            case_val,
-                            "match_cmp"
-                        ).unwrap();
-                        
-                        let next_test_block = if i < arms.len() - 1 {
-                            self.context.append_basic_block(current_function, &format!("match_test_{}", i + 1))
-                        } else {
-                            default_block
-                        };
-                        
-                        self.builder.build_conditional_branch(
-                            condition,
-                            match_blocks[i],
-                            next_test_block
-                        ).unwrap();
-                        
-                        current_block = next_test_block;
-                    }
-                }
-                Pattern::Wildcard => {
-                    // Wildcard matches anything - unconditional jump
-                    self.builder.build_unconditional_branch(match_blocks[i]).unwrap();
-                    break;
-                }
-                Pattern::EnumVariant { variant, .. } => {
-                    // For enum variants, check the tag field of the Result struct
-                    if let BasicValueEnum::PointerValue(result_ptr) = match_value {
-                        // Cast to our Result struct type
-                        let struct_type = self.context.struct_type(&[
-                            self.context.i32_type().into(),  // tag
-                            self.context.i64_type().into(),  // data
-                        ], false);
-                        let result_struct_ptr = self.builder.build_pointer_cast(
-                            result_ptr,
-                            struct_type.ptr_type(AddressSpace::default()),
-                            "pattern_result_cast"
-                        ).unwrap();
-                        
-                        // Get the tag field (index 0)
-                        let tag_ptr = self.builder.build_struct_gep(
-                            result_struct_ptr,
-                            0,
-                            "pattern_tag_ptr"
-                        ).unwrap();
-                        let tag_value = self.builder.build_load(
-                            tag_ptr,
-                            "pattern_tag_load"
-                        ).unwrap();
-                        
-                        // Check if tag matches the expected variant (Ok = 0, Err = 1)
-                        let expected_tag = if variant == "Ok" { 0 } else { 1 };
-                        let expected_tag_value = self.context.i32_type().const_int(expected_tag, false);
-                        let condition = self.builder.build_int_compare(
-                            inkwell::IntPredicate::EQ,
-                            tag_value.into_int_value(),
-                            expected_tag_value,
-                            "variant_match_cmp"
-                        ).unwrap();
-                        
-                        let next_test_block = if i < arms.len() - 1 {
-                            self.context.append_basic_block(current_function, &format!("match_test_{}", i + 1))
-                        } else {
-                            default_block
-                        };
-                        
-                        self.builder.build_conditional_branch(
-                            condition,
-                            match_blocks[i],
-                            next_test_block
-                        ).unwrap();
-                        
-                        current_block = next_test_block;
-                    }
-                }
-                _ => {
-                    // Other pattern types - skip for now
-                    continue;
-                }
+        let function = self
+            .builder
+            .get_insert_block()
+            .and_then(|block| block.get_parent())
+            .ok_or_else(|| {
+                CompileError::codegen_error(
+                    "Match expression outside of function context".to_string(),
+                    None,
+                )
+            })?;
+
+        let arm_blocks: Vec<BasicBlock<'ctx>> = (0..arms.len())
+            .map(|i| {
+                self.context
+                    .append_basic_block(function, &format!("match_arm_{}", i))
+            })
+            .collect();
+        let fail_block = self.context.append_basic_block(function, "match_fail");
+        let merge_block = self.context.append_basic_block(function, "match_merge");
+
+        // Each arm's pattern variables live in their own slots, stored by every
+        // leaf of the tree that leads to the arm
+        let decision = decision_tree::build(arms);
+        let mut slots = HashMap::new();
+        self.generate_decision(
+            &decision,
+            match_value,
+            arms,
+            &arm_blocks,
+            fail_block,
+            &mut slots,
+        )?;
+
+        let saved_variables = self.variables.clone();
+        let mut incoming = Vec::new();
+        for (index, arm) in arms.iter().enumerate() {
+            self.builder.position_at_end(arm_blocks[index]);
+            self.bind_match_variables(index, &slots);
+            let arm_value = self.generate_expression(&arm.expression)?;
+            let arm_end = self.builder.get_insert_block().unwrap();
+            if arm_end.get_terminator().is_none() {
+                incoming.push((arm_value, arm_end));
+                self.builder
+                    .build_unconditional_branch(merge_block)
+                    .unwrap();
             }
+            self.variables = saved_variables.clone();
         }
-        
-        // Generate code for each match arm
-        let mut result_values = Vec::new();
-        let mut result_blocks = Vec::new();
-        
-        for (i, arm) in arms.iter().enumerate() {
-            self.builder.position_at_end(match_blocks[i]);
-            
-            // Handle pattern variable binding
-            match &arm.pattern {
-                Pattern::EnumVariant { patterns, .. } if !patterns.is_empty() => {
-                    // Extract value from enum for variable binding
-                    if let Pattern::Variable(var_name) = &patterns[0] {
-                        // Extract the actual value from the Result enum struct
-                        if let BasicValueEnum::PointerValue(result_ptr) = match_value {
-                            // Cast to our Result struct type
-                            let struct_type = self.context.struct_type(&[
-                                self.context.i32_type().into(),  // tag
-                                self.context.i64_type().into(),  // data
-                            ], false);
-                            let result_struct_ptr = self.builder.build_pointer_cast(
-                                result_ptr,
-                                struct_type.ptr_type(AddressSpace::default()),
-                                "result_cast"
-                            ).unwrap();
-                            
-                            // Get the data field (index 1)
-                            let data_ptr = self.builder.build_struct_gep(
-                                result_struct_ptr,
-                                1,
-                                "data_ptr"
-                            ).unwrap();
-                            let data_value = self.builder.build_load(
-                                data_ptr,
-                                "data_load"
-                            ).unwrap();
-                            
-                            // Truncate i64 back to i32 (since we stored i32 extended to i64)
-                            let value = self.builder.build_int_truncate(
-                                data_value.into_int_value(),
-                                self.context.i32_type(),
-                                "value_truncate"
-                            ).unwrap();
-                            
-                            // Create variable for this scope
-                            let var_ptr = self.builder.build_alloca(self.context.i32_type(), var_name).unwrap();
-                            self.builder.build_store(var_ptr, value).unwrap();
-                            self.variables.insert(var_name.clone(), var_ptr);
-                        }
-                    }
-                }
-                _ => {}
+
+        // Reached only when no arm matches, which type checking allows for
+        // matches on numbers and strings without a `_` arm; execution then
+        // carries on after the match with a zero result
+        self.builder.position_at_end(fail_block);
+        let result_type = match incoming.first() {
+            Some((arm_value, _)) => arm_value.get_type(),
+            None => {
+                // Every arm returns or breaks out, so only unmatched values get here
+                self.builder
+                    .build_unconditional_branch(merge_block)
+                    .unwrap();
+                self.builder.position_at_end(merge_block);
+                return Ok(self.context.i32_type().const_zero().into());
             }
-            
-            // Generate the expression for this arm
-            let arm_result = self.generate_expression(&arm.expression)?;
-            result_values.push(arm_result);
-            result_blocks.push(self.builder.get_insert_block().unwrap());
-            
-            // Branch to merge block
-            self.builder.build_unconditional_branch(merge_block).unwrap();
-        }
-        
-        // Generate default case (should not be reached in well-typed programs)
-        self.builder.position_at_end(default_block);
-        let default_value = match result_type {
-            BasicTypeEnum::IntType(int_type) => int_type.const_int(0, false).into(),
-            _ => return Err(CompileError::codegen_error(
-                "Unsupported match result type".to_string(),
-                None,
-            )),
         };
-        result_values.push(default_value);
-        result_blocks.push(default_block);
-        self.builder.build_unconditional_branch(merge_block).unwrap();
-        
-        // Create PHI node in merge block
+        incoming.push((result_type.const_zero(), fail_block));
+        self.builder
+            .build_unconditional_branch(merge_block)
+            .unwrap();
+
         self.builder.position_at_end(merge_block);
         let phi = self.builder.build_phi(result_type, "match_result").unwrap();
-        
-        for (value, block) in result_values.iter().zip(result_blocks.iter()) {
-            phi.add_incoming(&[(&(*value), *block)]);
+        for (arm_value, block) in &incoming {
+            phi.add_incoming(&[(arm_value, *block)]);
         }
-        
-        Ok(phi.as_basic_value())
-    }
-    
-    /// Infer the result type of a match expression
-    fn infer_match_result_type(&self, arms: &[crate::ast::MatchArm]) -> Result<BasicTypeEnum<'ctx>> {
-        // For simplicity, assume i32 type - in a full implementation we'd do proper type inference
-        Ok(self.context.i32_type().into())
-    }
 
-    /// Sets the optimization level.
-    pub fn set_optimization_level(&mut self, level: OptimizationLevel) {
-        self.optimization_level = level;
+        Ok(phi.as_basic_value())
     }
 
-    /// Compiles the AST into LLVM IR.
-    pub fn compile_program(&mut self, program: &[Stmt]) -> Result<()> {
-        // Initialize target for the current machine
-        Self::initialize_native_target();
+    /// Emits the branches of a match decision tree at the builder's position.
+    fn generate_decision(
+        &mut self,
+        decision: &Decision,
+        match_value: BasicValueEnum<'ctx>,
+        arms: &[crate::ast::MatchArm],
+        arm_blocks: &[BasicBlock<'ctx>],
+        fail_block: BasicBlock<'ctx>,
+        slots: &mut HashMap<(usize, String), PointerValue<'ctx>>,
+    ) -> Result<()> {
+        let function = self
+            .builder
+            .get_insert_block()
+            .and_then(|block| block.get_parent())
+            .unwrap();
 
-        // Record initial memory usage for code generation
-        let initial_memory =
-            std::mem::size_of::<Module>() + program.len() * std::mem::size_of::<Stmt>();
-        record_memory_usage(
-            CompilationPhase::CodeGeneration,
-            initial_memory,
-            "Started code generation",
-        );
+        match decision {
+            Decision::Leaf { arm, bindings } => {
+                self.store_match_bindings(*arm, bindings, match_value, slots)?;
+                self.builder
+                    .build_unconditional_branch(arm_blocks[*arm])
+                    .unwrap();
+            }
+            Decision::Guard {
+                arm,
+                bindings,
+                otherwise,
+            } => {
+                self.store_match_bindings(*arm, bindings, match_value, slots)?;
+                let guard = arms[*arm].guard.as_ref().ok_or_else(|| {
+                    CompileError::codegen_error(format!("Match arm {} has no guard", arm), None)
+                })?;
 
-        // DEVELOPMENT_PROCESS.md: Real memory analysis integration
-        eprintln!("🧠 Performing memory region analysis...");
-        let memory_analysis = analyze_memory_regions(program);
-        let memory_metadata = generate_memory_metadata(&memory_analysis);
-        eprintln!("✅ Memory analysis: {} variables, {} metadata entries", 
-                 memory_analysis.variables.len(), memory_metadata.len());
+                let saved_variables = self.variables.clone();
+                self.bind_match_variables(*arm, slots);
+                let condition = self.generate_expression(guard);
+                self.variables = saved_variables;
+                let condition = self.convert_to_bool(condition?)?;
 
-        // Generate LLVM metadata for memory regions
-        self.generate_memory_metadata(&memory_metadata)?;
+                let otherwise_block = self
+                    .context
+                    .append_basic_block(function, "match_guard_failed");
+                self.builder
+                    .build_conditional_branch(condition, arm_blocks[*arm], otherwise_block)
+                    .unwrap();
+                self.builder.position_at_end(otherwise_block);
+                self.generate_decision(
+                    otherwise,
+                    match_value,
+                    arms,
+                    arm_blocks,
+                    fail_block,
+                    slots,
+                )?;
+            }
+            Decision::Switch {
+                occurrence,
+                enum_name,
+                cases,
+                default,
+            } => {
+                let enum_value = self.match_value_at(match_value, occurrence)?;
+                let tag = self
+                    .builder
+                    .build_extract_value(self.enum_struct_value(enum_value)?, 0, "match_tag")
+                    .unwrap()
+                    .into_int_value();
+
+                let mut case_blocks = Vec::new();
+                for (variant, _) in cases {
+                    let tag_value = self
+                        .context
+                        .i32_type()
+                        .const_int(self.variant_tag(enum_name, variant)?, false);
+                    let block = self
+                        .context
+                        .append_basic_block(function, &format!("match_{}", variant));
+                    case_blocks.push((tag_value, block));
+                }
+                let default_block = self.context.append_basic_block(function, "match_default");
+                self.builder
+                    .build_switch(tag, default_block, &case_blocks)
+                    .unwrap();
 
-        // Generate code for each statement in the program
-        for (i, stmt) in program.iter().enumerate() {
-            self.generate_statement(stmt)?;
+                for ((_, block), (_, case)) in case_blocks.iter().zip(cases) {
+                    self.builder.position_at_end(*block);
+                    self.generate_decision(case, match_value, arms, arm_blocks, fail_block, slots)?;
+                }
+                self.builder.position_at_end(default_block);
+                self.generate_decision(default, match_value, arms, arm_blocks, fail_block, slots)?;
+            }
+            Decision::Test {
+                occurrence,
+                test,
+                then,
+                otherwise,
+            } => {
+                let scalar = self.match_value_at(match_value, occurrence)?;
+                let condition = self.generate_pattern_test(scalar, test)?;
 
-            // Check memory usage periodically
-            if i % 25 == 0 {
-                let current_memory = std::mem::size_of::<Module>()
-                    + self.variables.len() * std::mem::size_of::<PointerValue>()
-                    + self.functions.len() * std::mem::size_of::<FunctionValue>();
-                record_memory_usage(
-                    CompilationPhase::CodeGeneration,
-                    current_memory,
-                    &format!(
-                        "Code generation progress: {}/{} statements",
-                        i + 1,
-                        program.len()
-                    ),
-                );
+                let then_block = self
+                    .context
+                    .append_basic_block(function, "match_test_passed");
+                let otherwise_block = self
+                    .context
+                    .append_basic_block(function, "match_test_failed");
+                self.builder
+                    .build_conditional_branch(condition, then_block, otherwise_block)
+                    .unwrap();
 
-                // Check memory limits
-                if let Err(e) = check_memory_limit() {
-                    return Err(CompileError::MemoryExhausted {
-                        phase: "code generation".to_string(),
-                        details: e.to_string(),
-                    });
-                }
+                self.builder.position_at_end(then_block);
+                self.generate_decision(then, match_value, arms, arm_blocks, fail_block, slots)?;
+                self.builder.position_at_end(otherwise_block);
+                self.generate_decision(
+                    otherwise,
+                    match_value,
+                    arms,
+                    arm_blocks,
+                    fail_block,
+                    slots,
+                )?;
+            }
+            Decision::Fail => {
+                self.builder.build_unconditional_branch(fail_block).unwrap();
             }
         }
-
-        // Code generation completed successfully
-
         Ok(())
     }
 
-    /// Initializes LLVM target for the current machine.
-    fn initialize_native_target() {
-        Target::initialize_native(&InitializationConfig::default())
-            .expect("Failed to initialize native target");
+    /// Stores the parts of the matched value that `arm`'s pattern binds into the
+    /// arm's variable slots, creating the slots on first use.
+    fn store_match_bindings(
+        &mut self,
+        arm: usize,
+        bindings: &[decision_tree::Binding],
+        match_value: BasicValueEnum<'ctx>,
+        slots: &mut HashMap<(usize, String), PointerValue<'ctx>>,
+    ) -> Result<()> {
+        let function = self
+            .builder
+            .get_insert_block()
+            .and_then(|block| block.get_parent())
+            .unwrap();
+
+        for (name, occurrence) in bindings {
+            let value = self.match_value_at(match_value, occurrence)?;
+            let slot = match slots.get(&(arm, name.clone())) {
+                Some(slot) => *slot,
+                None => {
+                    let slot = self.create_entry_block_alloca(function, name, value.get_type())?;
+                    slots.insert((arm, name.clone()), slot);
+                    slot
+                }
+            };
+            self.builder.build_store(slot, value).unwrap();
+        }
+        Ok(())
     }
 
-    /// Generates code for a statement.
-    fn generate_statement(&mut self, stmt: &Stmt) -> Result<()> {
-        match stmt {
-            Stmt::FunctionDeclaration {
-                name,
-                params,
-                return_type,
-                body,
-                attributes,
-            } => self.generate_function_declaration_with_attributes(
-                name,
-                params,
-                return_type,
-                body,
-                attributes,
-            ),
-            Stmt::VarDeclaration {
-                pattern,
-                type_annotation,
-                initializer,
-            } => self.generate_var_declaration_pattern(pattern, type_annotation, initializer),
-            Stmt::Expression(expr) => {
-                // Generate code for the expression but discard the result
-                self.generate_expression(expr)?;
-                Ok(())
+    /// Makes the variables bound by `arm`'s pattern visible to the code being generated.
+    fn bind_match_variables(
+        &mut self,
+        arm: usize,
+        slots: &HashMap<(usize, String), PointerValue<'ctx>>,
+    ) {
+        for ((slot_arm, name), slot) in slots {
+            if *slot_arm == arm {
+                self.variables.insert(name.clone(), *slot);
             }
-            Stmt::Return(expr) => self.generate_return(expr),
-            Stmt::Block(stmts) => {
-                for (i, stmt) in stmts.iter().enumerate() {
-                    self.generate_statement(stmt)?;
-
-                    // Check if we need to connect the current block to the next statement
-                    if i + 1 < stmts.len() {
-                        if let Some(current_block) = self.builder.get_insert_block() {
-                            if !self.block_has_terminator(current_block) {
-                                // Create a block for the next statement
-                                let function = current_block.get_parent().unwrap();
-                                let next_block =
-                                    self.context.append_basic_block(function, "next_stmt");
+        }
+    }
 
-                                // Branch from current block to next statement block
-                                self.builder
-                                    .build_unconditional_branch(next_block)
-                                    .map_err(|e| {
-                                        CompileError::codegen_error(
-                                            format!(
-                                                "Failed to build branch to next statement: {:?}",
-                                                e
-                                            ),
-                                            None,
-                                        )
-                                    })?;
-
-                                // Position at the next block for the next statement
-                                self.builder.position_at_end(next_block);
-                            }
-                        }
-                    }
+    /// The part of a matched value that `occurrence` points at.
+    fn match_value_at(
+        &self,
+        match_value: BasicValueEnum<'ctx>,
+        occurrence: &[Projection],
+    ) -> Result<BasicValueEnum<'ctx>> {
+        let mut value = match_value;
+        for projection in occurrence {
+            value = match projection {
+                Projection::Element(index) => {
+                    let tuple = self.matched_struct_value(value)?;
+                    self.builder
+                        .build_extract_value(
+                            tuple,
+                            *index as u32,
+                            &format!("match_element_{}", index),
+                        )
+                        .unwrap()
                 }
-                Ok(())
-            }
-            Stmt::If {
-                condition,
-                then_branch,
-                else_branch,
-            } => self.generate_if_statement(condition, then_branch, else_branch),
-            Stmt::While { condition, body } => self.generate_while_statement(condition, body),
-            Stmt::For {
-                initializer,
-                condition,
-                increment,
-                body,
-            } => self.generate_for_statement(initializer, condition, increment, body),
-            Stmt::ForIn {
-                variable,
-                iterable,
-                body,
-            } => self.generate_for_in_statement(variable, iterable, body),
-            Stmt::StructDeclaration { name, fields } => {
-                self.generate_struct_declaration(name, fields)
-            }
-            Stmt::EnumDeclaration { name, variants } => {
-                self.generate_enum_declaration(name, variants)
-            }
-            Stmt::Break | Stmt::Continue => {
-                // Break and Continue handled by control flow, not generated here
-                Ok(())
-            }
+                Projection::Field(field_name) => {
+                    let struct_value = self.matched_struct_value(value)?;
+                    let index = self
+                        .struct_field_index(struct_value.get_type(), field_name)
+                        .ok_or_else(|| {
+                            CompileError::codegen_error(
+                                format!("Unknown field '{}' in struct pattern", field_name),
+                                None,
+                            )
+                        })?;
+                    self.builder
+                        .build_extract_value(
+                            struct_value,
+                            index,
+                            &format!("match_field_{}", field_name),
+                        )
+                        .unwrap()
+                }
+                Projection::Payload(0) => {
+                    // Variants keep their value zero-extended in the i64 data field
+                    let data = self
+                        .builder
+                        .build_extract_value(self.enum_struct_value(value)?, 1, "match_payload")
+                        .unwrap()
+                        .into_int_value();
+                    self.builder
+                        .build_int_truncate(data, self.context.i32_type(), "match_payload_value")
+                        .unwrap()
+                        .into()
+                }
+                Projection::Payload(_) => {
+                    return Err(CompileError::codegen_error(
+                        "Matching enum variants that carry more than one value is not supported"
+                            .to_string(),
+                        None,
+                    ))
+                }
+            };
         }
+        Ok(value)
     }
 
-    /// Generates code for an if statement using LLVM basic blocks.
-    fn generate_if_statement(
-        &mut self,
-        condition: &Expr,
-        then_branch: &Box<Stmt>,
-        else_branch: &Option<Box<Stmt>>,
-    ) -> Result<()> {
-        let function = self
-            .builder
-            .get_insert_block()
-            .and_then(|block| block.get_parent())
-            .ok_or_else(|| {
-                CompileError::codegen_error(
-                    "If statement outside of function context".to_string(),
-                    None,
-                )
-            })?;
-
-        // Create basic blocks for the if statement
-        let then_block = self.context.append_basic_block(function, "if_then");
-        let else_block = if else_branch.is_some() {
-            Some(self.context.append_basic_block(function, "if_else"))
-        } else {
-            None
-        };
-        let merge_block = self.context.append_basic_block(function, "if_merge");
-
-        // Generate condition
-        let condition_value = self.generate_expression(condition)?;
-        let condition_bool = self.convert_to_bool(condition_value)?;
+    fn matched_struct_value(
+        &self,
+        value: BasicValueEnum<'ctx>,
+    ) -> Result<inkwell::values::StructValue<'ctx>> {
+        match value {
+            BasicValueEnum::StructValue(struct_value) => Ok(struct_value),
+            other => Err(CompileError::codegen_error(
+                format!("Cannot destructure a value of type {:?}", other.get_type()),
+                None,
+            )),
+        }
+    }
 
-        // Create conditional branch
-        match else_block {
-            Some(else_bb) => {
-                self.builder
-                    .build_conditional_branch(condition_bool, then_block, else_bb)
-                    .map_err(|e| {
-                        CompileError::codegen_error(
-                            format!("Failed to build conditional branch: {:?}", e),
-                            None,
-                        )
-                    })?;
-            }
-            None => {
-                self.builder
-                    .build_conditional_branch(condition_bool, then_block, merge_block)
-                    .map_err(|e| {
-                        CompileError::codegen_error(
-                            format!("Failed to build conditional branch: {:?}", e),
-                            None,
-                        )
-                    })?;
+    /// An enum value as its `{ i32 tag, i64 data }` struct, loaded first when
+    /// the value is a pointer to one, as `Result`s from the runtime are.
+    fn enum_struct_value(
+        &self,
+        value: BasicValueEnum<'ctx>,
+    ) -> Result<inkwell::values::StructValue<'ctx>> {
+        match value {
+            BasicValueEnum::StructValue(struct_value) => Ok(struct_value),
+            BasicValueEnum::PointerValue(pointer) => {
+                let enum_type = self.context.struct_type(
+                    &[
+                        self.context.i32_type().into(),
+                        self.context.i64_type().into(),
+                    ],
+                    false,
+                );
+                let enum_pointer = self
+                    .builder
+                    .build_pointer_cast(
+                        pointer,
+                        enum_type.ptr_type(AddressSpace::default()),
+                        "enum_cast",
+                    )
+                    .unwrap();
+                Ok(self
+                    .builder
+                    .build_load(enum_pointer, "enum_load")
+                    .unwrap()
+                    .into_struct_value())
             }
+            other => Err(CompileError::codegen_error(
+                format!(
+                    "Cannot match enum patterns against a value of type {:?}",
+                    other.get_type()
+                ),
+                None,
+            )),
         }
+    }
 
-        // Generate then branch
-        self.builder.position_at_end(then_block);
-        self.generate_statement(then_branch)?;
+    /// The tag `enum_name::variant` values carry.
+    fn variant_tag(&self, enum_name: &str, variant: &str) -> Result<u64> {
+        if let Some(index) = self
+            .struct_fields
+            .get(enum_name)
+            .and_then(|variants| variants.get(variant))
+        {
+            return Ok(*index as u64);
+        }
+        // `Result` is not declared in the program; its values use Ok = 0, Err = 1
+        match variant {
+            "Ok" => Ok(0),
+            "Err" => Ok(1),
+            _ => Err(CompileError::codegen_error(
+                format!("Unknown variant: {}::{}", enum_name, variant),
+                None,
+            )),
+        }
+    }
 
-        // Check if then branch has terminator
-        let then_has_terminator = self.block_has_terminator(then_block);
-        if !then_has_terminator {
-            self.builder
-                .build_unconditional_branch(merge_block)
-                .map_err(|e| {
+    /// Emits the comparison a decision tree test makes on a scalar part of the
+    /// matched value.
+    fn generate_pattern_test(
+        &self,
+        value: BasicValueEnum<'ctx>,
+        test: &Test,
+    ) -> Result<IntValue<'ctx>> {
+        match test {
+            Test::Equals(Literal::String(text)) => {
+                let string = match value {
+                    BasicValueEnum::PointerValue(string) => string,
+                    _ => {
+                        return Err(CompileError::codegen_error(
+                            "String patterns can only match strings".to_string(),
+                            None,
+                        ))
+                    }
+                };
+                let strcmp_function = self.functions.get("strcmp").copied().ok_or_else(|| {
                     CompileError::codegen_error(
-                        format!("Failed to build branch to merge: {:?}", e),
+                        "strcmp function not found in function table".to_string(),
                         None,
                     )
                 })?;
-        }
-
-        // Generate else branch if it exists
-        let else_has_terminator = if let Some(else_stmt) = else_branch {
-            let else_bb = else_block.unwrap();
-            self.builder.position_at_end(else_bb);
-            self.generate_statement(else_stmt)?;
-
-            // Check if else branch has terminator
-            let has_terminator = self.block_has_terminator(else_bb);
-            if !has_terminator {
-                self.builder
-                    .build_unconditional_branch(merge_block)
-                    .map_err(|e| {
-                        CompileError::codegen_error(
-                            format!("Failed to build branch to merge: {:?}", e),
-                            None,
-                        )
-                    })?;
+                let expected = self
+                    .builder
+                    .build_global_string_ptr(text, "pattern_string")
+                    .unwrap()
+                    .as_pointer_value();
+                let order = self
+                    .builder
+                    .build_call(
+                        strcmp_function,
+                        &[string.into(), expected.into()],
+                        "pattern_strcmp",
+                    )
+                    .unwrap()
+                    .try_as_basic_value()
+                    .left()
+                    .unwrap()
+                    .into_int_value();
+                Ok(self
+                    .builder
+                    .build_int_compare(
+                        IntPredicate::EQ,
+                        order,
+                        self.context.i32_type().const_zero(),
+                        "pattern_eq",
+                    )
+                    .unwrap())
+            }
+            Test::Equals(literal) => {
+                self.compare_with_literal(value, literal, IntPredicate::EQ, FloatPredicate::OEQ)
+            }
+            Test::InRange {
+                start,
+                end,
+                inclusive,
+            } => {
+                let above_start = self.compare_with_literal(
+                    value,
+                    start,
+                    IntPredicate::SGE,
+                    FloatPredicate::OGE,
+                )?;
+                let below_end = if *inclusive {
+                    self.compare_with_literal(value, end, IntPredicate::SLE, FloatPredicate::OLE)?
+                } else {
+                    self.compare_with_literal(value, end, IntPredicate::SLT, FloatPredicate::OLT)?
+                };
+                Ok(self
+                    .builder
+                    .build_and(above_start, below_end, "pattern_in_range")
+                    .unwrap())
             }
-            has_terminator
-        } else {
-            false
-        };
-
-        // Only continue with merge block if it's reachable
-        // If both branches have terminators, merge block is unreachable and should be removed
-        if then_has_terminator && else_has_terminator {
-            // DEVELOPMENT_PROCESS.md: Fix root cause - remove unreachable basic block
-            // This prevents LLVM IR validation errors for empty blocks
-            let _ = merge_block.remove_from_function();
-        } else {
-            self.builder.position_at_end(merge_block);
         }
-        Ok(())
     }
 
-    /// Generates code for an if expression that returns a value using phi nodes.
-    fn generate_if_expression(
-        &mut self,
-        condition: &Expr,
-        then_branch: &Box<Expr>,
-        else_branch: &Option<Box<Expr>>,
-    ) -> Result<BasicValueEnum<'ctx>> {
-        let function = self
-            .builder
-            .get_insert_block()
-            .and_then(|block| block.get_parent())
-            .ok_or_else(|| {
-                CompileError::codegen_error(
-                    "If expression outside of function context".to_string(),
-                    None,
+    /// Compares a scalar against a pattern literal, converting the literal to
+    /// the scalar's type.
+    fn compare_with_literal(
+        &self,
+        value: BasicValueEnum<'ctx>,
+        literal: &Literal,
+        int_predicate: IntPredicate,
+        float_predicate: FloatPredicate,
+    ) -> Result<IntValue<'ctx>> {
+        match (value, literal) {
+            (BasicValueEnum::IntValue(int_value), Literal::Integer(expected)) => Ok(self
+                .builder
+                .build_int_compare(
+                    int_predicate,
+                    int_value,
+                    int_value.get_type().const_int(*expected as u64, true),
+                    "pattern_cmp",
                 )
-            })?;
-
-        // Create basic blocks for the if expression
-        let then_block = self.context.append_basic_block(function, "if_then_expr");
-        let else_block = self.context.append_basic_block(function, "if_else_expr");
-        let merge_block = self.context.append_basic_block(function, "if_merge_expr");
-
-        // Generate condition
-        let condition_value = self.generate_expression(condition)?;
-        let condition_bool = self.convert_to_bool(condition_value)?;
-
-        // Create conditional branch
-        self.builder
-            .build_conditional_branch(condition_bool, then_block, else_block)
-            .map_err(|e| {
-                CompileError::codegen_error(
-                    format!("Failed to build conditional branch for if expression: {:?}", e),
-                    None,
+                .unwrap()),
+            (BasicValueEnum::IntValue(int_value), Literal::Boolean(expected)) => Ok(self
+                .builder
+                .build_int_compare(
+                    int_predicate,
+                    int_value,
+                    int_value.get_type().const_int(*expected as u64, false),
+                    "pattern_cmp",
                 )
-            })?;
-
-        // Generate then branch
-        self.builder.position_at_end(then_block);
-        let then_value = self.generate_expression(then_branch)?;
-        let then_block_final = self.builder.get_insert_block().unwrap();
-        
-        // Branch to merge block
-        self.builder
-            .build_unconditional_branch(merge_block)
-            .map_err(|e| {
-                CompileError::codegen_error(
-                    format!("Failed to build branch from then block: {:?}", e),
-                    None,
+                .unwrap()),
+            (BasicValueEnum::FloatValue(float_value), Literal::Float(expected)) => Ok(self
+                .builder
+                .build_float_compare(
+                    float_predicate,
+                    float_value,
+                    float_value.get_type().const_float(*expected),
+                    "pattern_cmp",
                 )
-            })?;
+                .unwrap()),
+            _ => Err(CompileError::codegen_error(
+                format!(
+                    "Cannot match pattern {} against a value of type {:?}",
+                    literal,
+                    value.get_type()
+                ),
+                None,
+            )),
+        }
+    }
 
-        // Generate else branch
-        self.builder.position_at_end(else_block);
-        let else_value = if let Some(else_expr) = else_branch {
-            self.generate_expression(else_expr)?
-        } else {
-            // If no else branch, return a default value of the same type as then branch
-            match then_value {
-                BasicValueEnum::IntValue(_) => self.context.i32_type().const_int(0, false).into(),
-                BasicValueEnum::FloatValue(_) => self.context.f32_type().const_float(0.0).into(),
-                BasicValueEnum::PointerValue(_) => {
-                    // For strings, return empty string
-                    let empty_str = self.context.const_string(b"", false);
-                    let global = self.module.add_global(empty_str.get_type(), None, "empty_str");
-                    global.set_initializer(&empty_str);
-                    global.as_pointer_value().into()
-                },
-                _ => then_value, // Use same value for other types
-            }
-        };
-        let else_block_final = self.builder.get_insert_block().unwrap();
-        
-        // Branch to merge block
-        self.builder
-            .build_unconditional_branch(merge_block)
-            .map_err(|e| {
-                CompileError::codegen_error(
-                    format!("Failed to build branch from else block: {:?}", e),
-                    None,
-                )
-            })?;
-
-        // Create phi node in merge block
-        self.builder.position_at_end(merge_block);
-        let phi = self.builder.build_phi(then_value.get_type(), "if_result")
-            .map_err(|e| {
-                CompileError::codegen_error(
-                    format!("Failed to build phi node: {:?}", e),
-                    None,
-                )
-            })?;
-
-        phi.add_incoming(&[(&then_value, then_block_final), (&else_value, else_block_final)]);
-        
-        Ok(phi.as_basic_value())
+    /// Sets the optimization level.
+    pub fn set_optimization_level(&mut self, level: OptimizationLevel) {
+        self.optimization_level = level;
     }
 
-    /// Generates code for a while loop using LLVM basic blocks.
-    fn generate_while_statement(&mut self, condition: &Expr, body: &Box<Stmt>) -> Result<()> {
-        let function = self
-            .builder
-            .get_insert_block()
-            .and_then(|block| block.get_parent())
-            .ok_or_else(|| {
-                CompileError::codegen_error(
-                    "While statement outside of function context".to_string(),
-                    None,
-                )
-            })?;
+    /// Compiles the AST into LLVM IR.
+    pub fn compile_program(&mut self, program: &[Stmt]) -> Result<()> {
+        // Initialize target for the current machine
+        Self::initialize_native_target();
 
-        // Create basic blocks for the while loop
-        let loop_cond_block = self.context.append_basic_block(function, "while_cond");
-        let loop_body_block = self.context.append_basic_block(function, "while_body");
-        let loop_end_block = self.context.append_basic_block(function, "while_end");
+        // Record initial memory usage for code generation
+        let initial_memory =
+            std::mem::size_of::<Module>() + program.len() * std::mem::size_of::<Stmt>();
+        record_memory_usage(
+            CompilationPhase::CodeGeneration,
+            initial_memory,
+            "Started code generation",
+        );
 
-        // Branch to condition check
-        self.builder
-            .build_unconditional_branch(loop_cond_block)
-            .map_err(|e| {
-                CompileError::codegen_error(
-                    format!("Failed to build branch to while condition: {:?}", e),
-                    None,
-                )
-            })?;
+        // DEVELOPMENT_PROCESS.md: Real memory analysis integration
+        eprintln!("🧠 Performing memory region analysis...");
+        let memory_analysis = analyze_memory_regions(program);
+        let memory_metadata = generate_memory_metadata(&memory_analysis);
+        eprintln!("✅ Memory analysis: {} variables, {} metadata entries", 
+                 memory_analysis.variables.len(), memory_metadata.len());
 
-        // Generate condition check
-        self.builder.position_at_end(loop_cond_block);
-        let condition_value = self.generate_expression(condition)?;
-        let condition_bool = self.convert_to_bool(condition_value)?;
+        // Generate LLVM metadata for memory regions
+        self.generate_memory_metadata(&memory_metadata)?;
 
-        // Conditional branch: if true go to body, if false go to end
-        self.builder
-            .build_conditional_branch(condition_bool, loop_body_block, loop_end_block)
-            .map_err(|e| {
-                CompileError::codegen_error(
-                    format!("Failed to build while conditional branch: {:?}", e),
-                    None,
-                )
-            })?;
+        // Generate code for each statement in the program
+        for (i, stmt) in program.iter().enumerate() {
+            self.generate_statement(stmt)?;
 
-        // Generate loop body
-        self.builder.position_at_end(loop_body_block);
-        self.generate_statement(body)?;
+            // Check memory usage periodically
+            if i % 25 == 0 {
+                let current_memory = std::mem::size_of::<Module>()
+                    + self.variables.len() * std::mem::size_of::<PointerValue>()
+                    + self.functions.len() * std::mem::size_of::<FunctionValue>();
+                record_memory_usage(
+                    CompilationPhase::CodeGeneration,
+                    current_memory,
+                    &format!(
+                        "Code generation progress: {}/{} statements",
+                        i + 1,
+                        program.len()
+                    ),
+                );
 
-        // Branch back to condition check if the current block doesn't already terminate
-        let current_block = self.builder.get_insert_block();
-        if let Some(block) = current_block {
-            if !self.block_has_terminator(block) {
-                self.builder
-                    .build_unconditional_branch(loop_cond_block)
-                    .map_err(|e| {
-                        CompileError::codegen_error(
-                            format!("Failed to build branch back to condition: {:?}", e),
-                            None,
-                        )
-                    })?;
+                // Check memory limits
+                if let Err(e) = check_memory_limit() {
+                    return Err(CompileError::MemoryExhausted {
+                        phase: "code generation".to_string(),
+                        details: e.to_string(),
+                    });
+                }
             }
         }
 
-        // Continue after the loop
-        self.builder.position_at_end(loop_end_block);
+        // Code generation completed successfully
+
         Ok(())
     }
 
-    /// Generates code for a for loop using LLVM basic blocks.
-    fn generate_for_statement(
+    /// Compiles a multi-file program into this single LLVM module.
+    ///
+    /// Modules must be in dependency order. Functions of non-root modules are
+    /// emitted under mangled names (see `module_resolver::mangle_symbol`) so
+    /// that equally named functions in different modules do not collide.
+    pub fn compile_modules(
         &mut self,
-        initializer: &Option<Box<Stmt>>,
-        condition: &Option<Expr>,
-        increment: &Option<Expr>,
-        body: &Box<Stmt>,
+        modules: &[crate::module_resolver::ResolvedModule],
     ) -> Result<()> {
-        let function = self
-            .builder
-            .get_insert_block()
-            .and_then(|block| block.get_parent())
-            .ok_or_else(|| {
-                CompileError::codegen_error(
-                    "For statement outside of function context".to_string(),
-                    None,
-                )
-            })?;
-
-        // Create basic blocks for the for loop
-        let loop_cond_block = self.context.append_basic_block(function, "for_cond");
-        let loop_body_block = self.context.append_basic_block(function, "for_body");
-        let loop_inc_block = self.context.append_basic_block(function, "for_inc");
-        let loop_end_block = self.context.append_basic_block(function, "for_end");
-
-        // Generate initializer
-        if let Some(init) = initializer {
-            self.generate_statement(init)?;
+        self.root_module = modules
+            .iter()
+            .find(|module| module.is_root)
+            .map(|module| module.name.clone());
+        for module in modules {
+            self.module_prefix = if module.is_root {
+                None
+            } else {
+                Some(module.name.clone())
+            };
+            self.module_aliases.clear();
+            self.symbol_aliases.clear();
+            self.generic_functions.clear();
+            self.import_targets = module.import_targets.clone();
+            self.expressions = self
+                .module_expressions
+                .get(&module.name)
+                .cloned()
+                .unwrap_or_default();
+
+            self.compile_program(&module.program)?;
+
+            let mut scope = self.scope();
+            for generic in scope.generic_functions.values_mut() {
+                generic.module.get_or_insert_with(|| module.name.clone());
+            }
+            self.module_scopes.insert(module.name.clone(), scope);
         }
 
-        // Branch to condition check
-        self.builder
-            .build_unconditional_branch(loop_cond_block)
-            .map_err(|e| {
-                CompileError::codegen_error(
-                    format!("Failed to build branch to for condition: {:?}", e),
-                    None,
-                )
-            })?;
+        self.module_prefix = None;
+        self.root_module = None;
+        self.module_aliases.clear();
+        self.symbol_aliases.clear();
+        self.generic_functions.clear();
+        self.import_targets.clear();
+        Ok(())
+    }
 
-        // Generate condition check
-        self.builder.position_at_end(loop_cond_block);
-        if let Some(cond) = condition {
-            let condition_value = self.generate_expression(cond)?;
-            let condition_bool = self.convert_to_bool(condition_value)?;
+    /// The names the module being generated resolves its calls through.
+    fn scope(&self) -> ModuleScope {
+        ModuleScope {
+            module_prefix: self.module_prefix.clone(),
+            module_aliases: self.module_aliases.clone(),
+            import_targets: self.import_targets.clone(),
+            symbol_aliases: self.symbol_aliases.clone(),
+            generic_functions: self.generic_functions.clone(),
+        }
+    }
 
-            // Conditional branch: if true go to body, if false go to end
-            self.builder
-                .build_conditional_branch(condition_bool, loop_body_block, loop_end_block)
-                .map_err(|e| {
-                    CompileError::codegen_error(
-                        format!("Failed to build for conditional branch: {:?}", e),
-                        None,
-                    )
-                })?;
-        } else {
-            // No condition means infinite loop, always go to body
-            self.builder
-                .build_unconditional_branch(loop_body_block)
-                .map_err(|e| {
-                    CompileError::codegen_error(
-                        format!("Failed to build unconditional branch to body: {:?}", e),
-                        None,
-                    )
-                })?;
+    /// Makes `scope` the names calls are resolved through and returns the ones that were.
+    fn replace_scope(&mut self, scope: ModuleScope) -> ModuleScope {
+        ModuleScope {
+            module_prefix: std::mem::replace(&mut self.module_prefix, scope.module_prefix),
+            module_aliases: std::mem::replace(&mut self.module_aliases, scope.module_aliases),
+            import_targets: std::mem::replace(&mut self.import_targets, scope.import_targets),
+            symbol_aliases: std::mem::replace(&mut self.symbol_aliases, scope.symbol_aliases),
+            generic_functions: std::mem::replace(
+                &mut self.generic_functions,
+                scope.generic_functions,
+            ),
         }
+    }
 
-        // Generate loop body
-        self.builder.position_at_end(loop_body_block);
-        self.generate_statement(body)?;
+    /// The generic functions declared in `module_name`, to be called from other modules.
+    fn module_generic_functions(&self, module_name: &str) -> Vec<(String, GenericFunction)> {
+        let Some(scope) = self.module_scopes.get(module_name) else {
+            return Vec::new();
+        };
+        scope
+            .generic_functions
+            .iter()
+            .filter(|(name, generic)| {
+                generic.module.as_deref() == Some(module_name) && **name == generic.name
+            })
+            .map(|(name, generic)| (name.clone(), generic.clone()))
+            .collect()
+    }
 
-        // After the body, check the current block and branch to increment
-        let current_block = self.builder.get_insert_block();
-        if let Some(current_block) = current_block {
-            if !self.block_has_terminator(current_block) {
-                self.builder
-                    .build_unconditional_branch(loop_inc_block)
-                    .map_err(|e| {
-                        CompileError::codegen_error(
-                            format!("Failed to build branch to increment: {:?}", e),
-                            None,
-                        )
-                    })?;
-            }
+    /// Registers the names an import brings into the current module.
+    fn generate_import_declaration(
+        &mut self,
+        module_path: &str,
+        items: &[crate::ast::ImportItem],
+        alias: &Option<String>,
+    ) -> Result<()> {
+        if items.is_empty() {
+            let alias = alias.clone().unwrap_or_else(|| {
+                module_path
+                    .rsplit("::")
+                    .next()
+                    .unwrap_or(module_path)
+                    .to_string()
+            });
+            let module_name =
+                crate::module_resolver::import_target(&self.import_targets, module_path)
+                    .to_string();
+            for (name, generic) in self.module_generic_functions(&module_name) {
+                self.generic_functions
+                    .insert(format!("{}::{}", alias, name), generic);
+            }
+            self.module_aliases.insert(alias, module_name);
+            return Ok(());
         }
 
-        // Generate increment
-        self.builder.position_at_end(loop_inc_block);
-        if let Some(inc) = increment {
-            self.generate_expression(inc)?;
+        let module_name =
+            crate::module_resolver::import_target(&self.import_targets, module_path).to_string();
+        let generic_functions: HashMap<_, _> = self
+            .module_generic_functions(&module_name)
+            .into_iter()
+            .collect();
+
+        for item in items {
+            let local_name = item.local_name().to_string();
+            if let Some(constant) = self.constants.get(&item.name).cloned() {
+                self.constants.insert(local_name, constant);
+            } else if let Some(struct_type) = self.struct_types.get(&item.name).copied() {
+                // Struct types are shared by name across the LLVM module
+                if let Some(fields) = self.struct_fields.get(&item.name).cloned() {
+                    self.struct_fields.insert(local_name.clone(), fields);
+                }
+                self.struct_types.insert(local_name, struct_type);
+            } else if self.extern_functions.contains(&item.name) {
+                // C functions keep their unmangled symbol in every module
+                self.symbol_aliases.insert(local_name, item.name.clone());
+            } else if let Some(generic) = generic_functions.get(&item.name) {
+                self.generic_functions.insert(local_name, generic.clone());
+            } else if let Some(generic) = self.generic_structs.get(&item.name).cloned() {
+                // Like struct types, generic structs are shared by name
+                self.generic_structs.insert(local_name, generic);
+            } else {
+                let symbol = self.module_symbol_name(&module_name, &item.name);
+                self.symbol_aliases.insert(local_name, symbol);
+            }
         }
 
-        // Branch back to condition check
-        self.builder
-            .build_unconditional_branch(loop_cond_block)
-            .map_err(|e| {
-                CompileError::codegen_error(
-                    format!("Failed to build branch back to condition: {:?}", e),
-                    None,
-                )
-            })?;
-
-        // Continue after the loop - this is where the next statement will be generated
-        self.builder.position_at_end(loop_end_block);
-
         Ok(())
     }
 
-    /// Generates code for a for-in statement that iterates over arrays.
-    fn generate_for_in_statement(
-        &mut self,
-        variable: &str,
-        iterable: &Expr,
-        body: &Box<Stmt>,
-    ) -> Result<()> {
-        let function = self
-            .builder
-            .get_insert_block()
-            .and_then(|block| block.get_parent())
-            .ok_or_else(|| {
-                CompileError::codegen_error(
-                    "For-in statement outside of function context".to_string(),
-                    None,
-                )
-            })?;
-
-        // Generate code for the array being iterated
-        let array_value = self.generate_expression(iterable)?;
-        let array_ptr = array_value.into_pointer_value();
-
-        // Create basic blocks for the for-in loop
-        let loop_cond_block = self.context.append_basic_block(function, "for_in_cond");
-        let loop_body_block = self.context.append_basic_block(function, "for_in_body");
-        let loop_inc_block = self.context.append_basic_block(function, "for_in_inc");
-        let loop_end_block = self.context.append_basic_block(function, "for_in_end");
-
-        // Create loop counter variable
-        let counter_ptr = self
-            .builder
-            .build_alloca(self.context.i32_type(), "counter")
-            .unwrap();
-        self.builder
-            .build_store(counter_ptr, self.context.i32_type().const_zero())
-            .unwrap();
-
-        // Get the actual array size from the iterable expression
-        let array_size = self.get_array_size(iterable)?;
-
-        // Branch to condition check
-        self.builder
-            .build_unconditional_branch(loop_cond_block)
-            .unwrap();
-
-        // Generate condition check: counter < array_size
-        self.builder.position_at_end(loop_cond_block);
-        let counter_val = self
-            .builder
-            .build_load(counter_ptr, "counter_load")
-            .unwrap()
-            .into_int_value();
-        let condition = self
-            .builder
-            .build_int_compare(
-                IntPredicate::ULT,
-                counter_val,
-                array_size,
-                "for_in_condition",
-            )
-            .unwrap();
-
-        // Conditional branch: if true go to body, if false go to end
-        self.builder
-            .build_conditional_branch(condition, loop_body_block, loop_end_block)
-            .unwrap();
-
-        // Generate loop body
-        self.builder.position_at_end(loop_body_block);
-
-        // Load the current element from the array
-        let current_counter = self
-            .builder
-            .build_load(counter_ptr, "counter_load")
-            .unwrap()
-            .into_int_value();
-        let element_ptr = unsafe {
-            self.builder
-                .build_gep(
-                    array_ptr,
-                    &[self.context.i32_type().const_zero(), current_counter],
-                    "element_ptr",
-                )
-                .unwrap()
-        };
-        let element_value = self
-            .builder
-            .build_load(element_ptr, "element_value")
-            .unwrap();
+    /// Returns the LLVM symbol a function defined in the current module is emitted as.
+    fn function_symbol_name(&self, name: &str) -> String {
+        match &self.module_prefix {
+            Some(prefix) if name != "main" => crate::module_resolver::mangle_symbol(prefix, name),
+            _ => name.to_string(),
+        }
+    }
 
-        // Create a variable for the loop variable and store the current element
-        let loop_var_ptr = self
-            .builder
-            .build_alloca(element_value.get_type(), variable)
-            .unwrap();
-        self.builder
-            .build_store(loop_var_ptr, element_value)
-            .unwrap();
+    /// Returns the symbol function `name` of module `module_name` was emitted as.
+    fn module_symbol_name(&self, module_name: &str, name: &str) -> String {
+        if self.root_module.as_deref() == Some(module_name) {
+            // The entry module keeps its names unmangled
+            return self.linked_symbol_name(name.to_string());
+        }
+        self.linked_symbol_name(crate::module_resolver::mangle_symbol(module_name, name))
+    }
 
-        // Add the loop variable to the variables map for the body
-        self.variables.insert(variable.to_string(), loop_var_ptr);
+    /// Returns the symbol a function of another module was emitted as: its
+    /// mangled name, or the C symbol it was exported under.
+    fn linked_symbol_name(&self, mangled_name: String) -> String {
+        match self.exported_symbols.get(&mangled_name) {
+            Some(symbol) => symbol.clone(),
+            None => mangled_name,
+        }
+    }
 
-        // Generate the loop body
-        self.generate_statement(body)?;
+    /// Resolves a called name through the current module's imports.
+    fn resolve_function_name(&self, name: &str) -> String {
+        self.symbol_aliases
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| name.to_string())
+    }
 
-        // Remove the loop variable from scope
-        self.variables.remove(variable);
+    /// Initializes LLVM target for the current machine.
+    fn initialize_native_target() {
+        Target::initialize_native(&InitializationConfig::default())
+            .expect("Failed to initialize native target");
+    }
 
-        // Branch to increment
-        self.builder
-            .build_unconditional_branch(loop_inc_block)
-            .unwrap();
+    /// Generates code for a statement.
+    fn generate_statement(&mut self, stmt: &Stmt) -> Result<()> {
+        self.generate_statement_node(stmt).map_err(|error| error.with_span(stmt.span()))
+    }
 
-        // Generate increment: counter++
-        self.builder.position_at_end(loop_inc_block);
-        let current_counter = self
-            .builder
-            .build_load(counter_ptr, "counter_load")
-            .unwrap()
-            .into_int_value();
-        let incremented_counter = self
-            .builder
-            .build_int_add(
-                current_counter,
-                self.context.i32_type().const_int(1, false),
-                "incremented_counter",
-            )
-            .unwrap();
-        self.builder
-            .build_store(counter_ptr, incremented_counter)
-            .unwrap();
+    fn generate_statement_node(&mut self, stmt: &Stmt) -> Result<()> {
+        match stmt {
+            Stmt::FunctionDeclaration {
+                name,
+                type_params,
+                params,
+                return_type,
+                body,
+                attributes,
+                ..
+            } => {
+                if crate::ast::is_comptime_function(attributes) {
+                    self.comptime_functions.insert(
+                        name.clone(),
+                        ComptimeFunction::from_declaration(name, params, return_type, body),
+                    );
+                    Ok(())
+                } else if type_params.is_empty() {
+                    self.generate_function_declaration_with_attributes(
+                        name,
+                        params,
+                        return_type,
+                        body,
+                        attributes,
+                    )
+                } else {
+                    // Emitted per instantiation from the call sites
+                    self.generic_functions.insert(
+                        name.clone(),
+                        GenericFunction {
+                            name: name.clone(),
+                            type_params: type_params.clone(),
+                            params: params.clone(),
+                            return_type: return_type.clone(),
+                            body: body.clone(),
+                            module: None,
+                        },
+                    );
+                    Ok(())
+                }
+            }
+            Stmt::VarDeclaration {
+                pattern,
+                type_annotation,
+                initializer,
+                ..
+            } => self.generate_var_declaration_pattern(pattern, type_annotation, initializer),
+            Stmt::Expression(expr, _) => {
+                // Generate code for the expression but discard the result
+                self.generate_expression(expr)?;
+                Ok(())
+            }
+            Stmt::Return(expr, _) => self.generate_return(expr),
+            Stmt::Block(stmts, _) => {
+                for (i, stmt) in stmts.iter().enumerate() {
+                    self.generate_statement(stmt)?;
 
-        // Branch back to condition check
-        self.builder
-            .build_unconditional_branch(loop_cond_block)
-            .unwrap();
+                    // Check if we need to connect the current block to the next statement
+                    if i + 1 < stmts.len() {
+                        if let Some(current_block) = self.builder.get_insert_block() {
+                            if !self.block_has_terminator(current_block) {
+                                // Create a block for the next statement
+                                let function = current_block.get_parent().unwrap();
+                                let next_block =
+                                    self.context.append_basic_block(function, "next_stmt");
 
-        // Continue after the loop
-        self.builder.position_at_end(loop_end_block);
-        Ok(())
-    }
+                                // Branch from current block to next statement block
+                                self.builder
+                                    .build_unconditional_branch(next_block)
+                                    .map_err(|e| {
+                                        CompileError::codegen_error(
+                                            format!(
+                                                "Failed to build branch to next statement: {:?}",
+                                                e
+                                            ),
+                                            None,
+                                        )
+                                    })?;
 
-    /// Get the size of an array from an iterable expression
-    fn get_array_size(&mut self, iterable: &Expr) -> Result<IntValue<'ctx>> {
-        match iterable {
-            // Handle array literals: [1, 2, 3] -> size = 3
-            Expr::Literal(Literal::Vector { elements, .. }) => {
-                Ok(self.context.i32_type().const_int(elements.len() as u64, false))
-            }
-            // Handle variables: get size from type information
-            Expr::Variable(name) => {
-                // Look up the variable to get its type
-                if let Some(var_ptr) = self.variables.get(name) {
-                    // The variable is a pointer to an array
-                    // Get the pointed-to type (the array type)
-                    let ptr_type = var_ptr.get_type();
-                    let pointee_type = ptr_type.get_element_type();
-                    match pointee_type {
-                        inkwell::types::AnyTypeEnum::ArrayType(arr_type) => {
-                            let size = arr_type.len();
-                            Ok(self.context.i32_type().const_int(size as u64, false))
-                        }
-                        _ => {
-                            Err(CompileError::codegen_error(
-                                format!("Variable '{}' points to non-array type", name),
-                                None,
-                            ))
+                                // Position at the next block for the next statement
+                                self.builder.position_at_end(next_block);
+                            }
                         }
                     }
+                }
+                Ok(())
+            }
+            Stmt::If {
+                condition,
+                then_branch,
+                else_branch,
+                ..
+            } => self.generate_if_statement(condition, then_branch, else_branch),
+            Stmt::While { condition, body, .. } => self.generate_while_statement(condition, body),
+            Stmt::For {
+                initializer,
+                condition,
+                increment,
+                body,
+                ..
+            } => self.generate_for_statement(initializer, condition, increment, body),
+            Stmt::ForIn {
+                variable,
+                iterable,
+                body,
+                ..
+            } => self.generate_for_in_statement(variable, iterable, body),
+            Stmt::StructDeclaration {
+                name,
+                type_params,
+                fields,
+                ..
+            } => {
+                if type_params.is_empty() {
+                    self.generate_struct_declaration(name, fields)
                 } else {
-                    Err(CompileError::codegen_error(
-                        format!("Undefined variable: {}", name),
+                    self.generic_structs.insert(
+                        name.clone(),
+                        GenericStruct {
+                            type_params: type_params.clone(),
+                            fields: fields.clone(),
+                        },
+                    );
+                    Ok(())
+                }
+            }
+            Stmt::EnumDeclaration { name, variants, .. } => {
+                self.generate_enum_declaration(name, variants)
+            }
+            Stmt::Break(label, _) => self.generate_loop_exit(label, true),
+            Stmt::Continue(label, _) => self.generate_loop_exit(label, false),
+            Stmt::Labeled { label, body, .. } => {
+                self.pending_loop_label = Some(label.clone());
+                self.generate_statement(body)
+            }
+            Stmt::ImportDeclaration {
+                module_path,
+                items,
+                alias,
+                ..
+            } => self.generate_import_declaration(module_path, items, alias),
+            // Traits only describe signatures; their impls carry the code
+            Stmt::TraitDeclaration { .. } => Ok(()),
+            Stmt::ConstDeclaration {
+                name,
+                type_annotation,
+                value,
+                ..
+            } => self.generate_const_declaration(name, type_annotation, value),
+            Stmt::ImplBlock {
+                trait_name,
+                type_name,
+                methods,
+                ..
+            } => self.generate_impl_block(trait_name, type_name, methods),
+            Stmt::ExternBlock { functions, .. } => self.generate_extern_block(functions),
+        }
+    }
+
+    /// Declares the functions of an `extern "C"` block. They are left undefined
+    /// in the module, for the linker or the JIT to resolve.
+    fn generate_extern_block(&mut self, functions: &[ExternFunction]) -> Result<()> {
+        for function in functions {
+            let mut param_types = Vec::new();
+            for param in &function.params {
+                param_types.push(self.resolve_type_annotation(&param.type_annotation)?.into());
+            }
+            let fn_type = match &function.return_type {
+                Some(type_ann) if type_ann.name != "()" => {
+                    self.resolve_type_annotation(type_ann)?.fn_type(&param_types, false)
+                }
+                _ => self.context.void_type().fn_type(&param_types, false),
+            };
+
+            let declared = match self.module.get_function(&function.name) {
+                // Builtins such as `puts` may already be declared with the same signature
+                Some(existing) if existing.get_type() == fn_type => existing,
+                Some(_) => {
+                    return Err(CompileError::codegen_error(
+                        format!(
+                            "Extern function '{}' conflicts with an existing declaration of a different type",
+                            function.name
+                        ),
                         None,
                     ))
                 }
-            }
-            // Handle other expression types (could be function calls, etc.)
-            _ => {
-                // For now, fall back to a runtime size detection or error
-                Err(CompileError::codegen_error(
-                    "Unsupported iterable type in for-in loop - only array literals and variables are supported".to_string(),
-                    None,
-                ))
+                None => self
+                    .module
+                    .add_function(&function.name, fn_type, Some(Linkage::External)),
+            };
+            self.functions.insert(function.name.clone(), declared);
+            if !self.extern_functions.contains(&function.name) {
+                self.extern_functions.push(function.name.clone());
             }
         }
+        Ok(())
     }
 
-    /// Converts a value to a boolean for use in conditional branches.
-    fn convert_to_bool(
-        &self,
-        value: BasicValueEnum<'ctx>,
-    ) -> Result<inkwell::values::IntValue<'ctx>> {
+    /// Folds a constant; uses of it are emitted as LLVM constants rather than loads.
+    fn generate_const_declaration(
+        &mut self,
+        name: &str,
+        type_annotation: &TypeAnnotation,
+        value: &Expr,
+    ) -> Result<()> {
+        let folded = self.fold_constant(value).map_err(|e| {
+            CompileError::codegen_error(
+                format!("Failed to evaluate constant '{}': {}", name, e),
+                None,
+            )
+        })?;
+        let llvm_type = self.resolve_type_annotation(type_annotation)?;
+
+        // A constant shadows an earlier local variable of the same name
+        self.variables.remove(name);
+        self.constants
+            .insert(name.to_string(), (llvm_type, folded));
+        Ok(())
+    }
+
+    /// Evaluates `expr` at compile time with the constants in scope.
+    fn fold_constant(
+        &self,
+        expr: &Expr,
+    ) -> std::result::Result<ComptimeValue, crate::comptime::ComptimeError> {
+        self.comptime_engine().evaluate_expression(expr)
+    }
+
+    /// A fresh engine that sees the constants in scope and every `comptime func`.
+    fn comptime_engine(&self) -> ComptimeEngine {
+        let mut engine = ComptimeEngine::new();
+        for (name, (_, value)) in &self.constants {
+            engine.define_value(name, value.clone());
+        }
+        for function in self.comptime_functions.values() {
+            engine.define_function(function.clone());
+        }
+        engine
+    }
+
+    /// Evaluates a `comptime { ... }` block and embeds the result in the module.
+    fn generate_comptime_block(&mut self, statements: &[Stmt]) -> Result<BasicValueEnum<'ctx>> {
+        let value = self
+            .comptime_engine()
+            .evaluate_block(statements)
+            .map_err(|e| {
+                CompileError::codegen_error(
+                    format!("Failed to evaluate comptime block: {}", e),
+                    None,
+                )
+            })?;
+        self.materialize_comptime_value(&value)
+    }
+
+    /// Emits a compile-time value. Scalars become immediates typed like literals;
+    /// arrays and lookup tables become private constant globals, used through
+    /// their address like other arrays.
+    fn materialize_comptime_value(&mut self, value: &ComptimeValue) -> Result<BasicValueEnum<'ctx>> {
         match value {
-            BasicValueEnum::IntValue(int_val) => {
-                if int_val.get_type() == self.context.bool_type() {
-                    Ok(int_val)
-                } else {
-                    // Convert integer to bool by comparing with 0
-                    let zero = int_val.get_type().const_zero();
-                    self.builder
-                        .build_int_compare(IntPredicate::NE, int_val, zero, "tobool")
-                        .map_err(|e| {
-                            CompileError::codegen_error(
-                                format!("Failed to convert to bool: {:?}", e),
-                                None,
-                            )
-                        })
-                }
+            ComptimeValue::Integer(n) => Ok(self.context.i32_type().const_int(*n as u64, true).into()),
+            ComptimeValue::Float(x) => Ok(self.context.f32_type().const_float(*x).into()),
+            ComptimeValue::Boolean(b) => {
+                Ok(self.context.bool_type().const_int(*b as u64, false).into())
             }
-            BasicValueEnum::FloatValue(float_val) => {
-                // Convert float to bool by comparing with 0.0
-                let zero = float_val.get_type().const_zero();
-                self.builder
-                    .build_float_compare(FloatPredicate::ONE, float_val, zero, "tobool")
-                    .map_err(|e| {
-                        CompileError::codegen_error(
-                            format!("Failed to convert float to bool: {:?}", e),
+            ComptimeValue::String(text) => self.generate_literal(&Literal::String(text.clone())),
+            _ => {
+                let elements = value.array_elements().ok_or_else(|| {
+                    CompileError::codegen_error(
+                        format!("Comptime value {:?} cannot be used at runtime", value),
+                        None,
+                    )
+                })?;
+                Ok(self.generate_comptime_table(elements)?.into())
+            }
+        }
+    }
+
+    /// Emits `elements` as a private constant array global and returns its address.
+    fn generate_comptime_table(&mut self, elements: &[ComptimeValue]) -> Result<PointerValue<'ctx>> {
+        let element_error = |element: &ComptimeValue| {
+            CompileError::codegen_error(
+                format!("Comptime arrays of {:?} cannot be used at runtime", element),
+                None,
+            )
+        };
+
+        let initializer: BasicValueEnum<'ctx> =
+            if elements.iter().any(|e| matches!(e, ComptimeValue::Float(_))) {
+                let float_type = self.context.f32_type();
+                let values = elements
+                    .iter()
+                    .map(|element| match element {
+                        ComptimeValue::Float(x) => Ok(float_type.const_float(*x)),
+                        ComptimeValue::Integer(n) => Ok(float_type.const_float(*n as f64)),
+                        other => Err(element_error(other)),
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                float_type.const_array(&values).into()
+            } else {
+                let int_type = match elements.first() {
+                    Some(ComptimeValue::Boolean(_)) => self.context.bool_type(),
+                    Some(ComptimeValue::Integer(_)) => self.context.i32_type(),
+                    Some(other) => return Err(element_error(other)),
+                    None => {
+                        return Err(CompileError::codegen_error(
+                            "Comptime arrays must not be empty".to_string(),
                             None,
-                        )
+                        ))
+                    }
+                };
+                let values = elements
+                    .iter()
+                    .map(|element| match element {
+                        ComptimeValue::Integer(n) => Ok(int_type.const_int(*n as u64, true)),
+                        ComptimeValue::Boolean(b) => Ok(int_type.const_int(*b as u64, false)),
+                        other => Err(element_error(other)),
                     })
+                    .collect::<Result<Vec<_>>>()?;
+                int_type.const_array(&values).into()
+            };
+
+        let global = self.module.add_global(
+            initializer.get_type(),
+            Some(AddressSpace::default()),
+            "comptime_table",
+        );
+        global.set_initializer(&initializer);
+        global.set_constant(true);
+        global.set_linkage(Linkage::Private);
+        global.set_unnamed_addr(true);
+        Ok(global.as_pointer_value())
+    }
+
+    /// Materializes the value of constant `name` with its declared LLVM type.
+    fn generate_constant(&mut self, name: &str) -> Result<BasicValueEnum<'ctx>> {
+        let (llvm_type, value) = self.constants.get(name).cloned().ok_or_else(|| {
+            CompileError::codegen_error(format!("Constant '{}' not found", name), None)
+        })?;
+
+        match (value, llvm_type) {
+            (ComptimeValue::Integer(n), BasicTypeEnum::IntType(int_type)) => {
+                Ok(int_type.const_int(n as u64, true).into())
             }
-            _ => Err(CompileError::codegen_error(
-                "Cannot convert value to boolean".to_string(),
+            (ComptimeValue::Boolean(b), BasicTypeEnum::IntType(int_type)) => {
+                Ok(int_type.const_int(b as u64, false).into())
+            }
+            (ComptimeValue::Integer(n), BasicTypeEnum::FloatType(float_type)) => {
+                Ok(float_type.const_float(n as f64).into())
+            }
+            (ComptimeValue::Float(x), BasicTypeEnum::FloatType(float_type)) => {
+                Ok(float_type.const_float(x).into())
+            }
+            (ComptimeValue::String(text), _) => self.generate_literal(&Literal::String(text)),
+            (value, _) => Err(CompileError::codegen_error(
+                format!(
+                    "Constant '{}' has a value that cannot be emitted as {:?}: {:?}",
+                    name, llvm_type, value
+                ),
                 None,
             )),
         }
     }
 
-    /// Checks if a basic block has a terminator instruction.
-    fn block_has_terminator(&self, block: BasicBlock<'ctx>) -> bool {
-        block.get_terminator().is_some()
+    /// Folds an array length or lane count, which must be a non-negative integer constant.
+    fn constant_length(&self, expr: &Expr) -> Result<usize> {
+        match self.fold_constant(expr) {
+            Ok(ComptimeValue::Integer(n)) if n >= 0 => Ok(n as usize),
+            Ok(other) => Err(CompileError::codegen_error(
+                format!("Array length '{}' must be a non-negative integer, got {:?}", expr, other),
+                None,
+            )),
+            Err(e) => Err(CompileError::codegen_error(
+                format!("Array length '{}' is not a compile-time constant: {}", expr, e),
+                None,
+            )),
+        }
     }
 
-    /// Checks if a basic block has no predecessor blocks (unreachable)
-    fn block_has_no_predecessors(&self, block: BasicBlock<'ctx>) -> bool {
-        // Check if any block in the function has a terminator that branches to this block
-        if let Some(parent_function) = block.get_parent() {
-            for predecessor_block in parent_function.get_basic_blocks() {
-                if let Some(terminator) = predecessor_block.get_terminator() {
-                    // Check if this terminator branches to our block
-                    let num_operands = terminator.get_num_operands();
-                    for i in 0..num_operands {
-                        if let Some(operand) = terminator.get_operand(i) {
-                            if let Some(operand_block) = operand.right() {
-                                if operand_block == block {
-                                    return false; // Found a predecessor
-                                }
-                            }
-                        }
-                    }
+    /// Replaces attribute identifiers that name integer or boolean constants with their values.
+    fn resolve_attribute_value(
+        &self,
+        value: &crate::ast::AttributeValue,
+    ) -> crate::ast::AttributeValue {
+        if let crate::ast::AttributeValue::Identifier(name) = value {
+            match self.constants.get(name) {
+                Some((_, ComptimeValue::Integer(n))) => {
+                    return crate::ast::AttributeValue::Integer(*n)
                 }
+                Some((_, ComptimeValue::Boolean(b))) => {
+                    return crate::ast::AttributeValue::Boolean(*b)
+                }
+                _ => {}
             }
         }
-        true // No predecessors found
+        value.clone()
     }
 
-    /// Generates code for a function declaration.
-    fn generate_function_declaration(
+    /// Emits the functions of an impl block as `Type::name` (inherent impls)
+    /// or `Type::Trait::name` (trait impls).
+    fn generate_impl_block(
         &mut self,
-        name: &str,
-        params: &[crate::ast::Parameter],
-        return_type: &Option<TypeAnnotation>,
-        body: &Box<Stmt>,
+        trait_name: &Option<String>,
+        type_name: &str,
+        methods: &[Stmt],
     ) -> Result<()> {
-        // Determine the return type
-        let return_llvm_type: Option<BasicTypeEnum> = match return_type {
-            Some(type_ann) => {
-                if type_ann.name == "()" {
-                    None // void type
-                } else {
-                    Some(self.type_annotation_to_llvm_type(type_ann)?)
-                }
-            }
-            None => {
-                // Special case: main function should return i32 by default
-                if name == "main" {
-                    Some(self.context.i32_type().into())
-                } else {
-                    None // void type for other functions
-                }
-            }
+        // Structs are keyed by their declared name, other types by the name of their LLVM type
+        let type_key = if self.struct_types.contains_key(type_name) {
+            type_name.to_string()
+        } else {
+            let self_annotation = TypeAnnotation {
+                name: type_name.to_string(),
+                is_mutable: false,
+            };
+            let self_type = self.resolve_type_annotation(&self_annotation)?;
+            self.llvm_type_name(self_type)
+                .unwrap_or_else(|| type_name.to_string())
         };
 
-        // Determine parameter types
-        let mut param_types = Vec::new();
-        
-        // Special handling for main function with CLI arguments
-        if name == "main" && params.len() == 1 && params[0].type_annotation.name == "Vec<String>" {
-            // main(args: Vec<String>) -> i32 - Generate standard C main signature
-            param_types.push(self.context.i32_type().into()); // argc
-            param_types.push(self.context.i8_type().ptr_type(AddressSpace::default()).ptr_type(AddressSpace::default()).into()); // argv
-        } else {
-            for param in params {
-                let param_type = self.type_annotation_to_llvm_type(&param.type_annotation)?;
-                param_types.push(param_type.into());
+        // Register every function first so methods can call each other
+        let mut symbols = Vec::new();
+        for method in methods {
+            if let Stmt::FunctionDeclaration { name, .. } = method {
+                let qualified_name = match trait_name {
+                    Some(trait_name) => format!("{}::{}::{}", type_key, trait_name, name),
+                    None => format!("{}::{}", type_key, name),
+                };
+                let symbol = self.function_symbol_name(&qualified_name);
+                self.methods
+                    .entry(type_key.clone())
+                    .or_default()
+                    .insert(name.clone(), symbol.clone());
+                symbols.push(symbol);
             }
         }
 
-        // Create the function type
-        let fn_type = match return_llvm_type {
-            Some(ret_type) => ret_type.fn_type(&param_types, false),
-            None => self.context.void_type().fn_type(&param_types, false),
-        };
+        let insert_block = self.builder.get_insert_block();
+        let saved_self = self
+            .type_substitutions
+            .insert("Self".to_string(), type_key.clone());
 
-        // Check if function already exists
-        let function = if let Some(existing_function) = self.functions.get(name) {
-            *existing_function
-        } else {
-            // Create the function
-            let function = self.module.add_function(name, fn_type, None);
-            // Add the function to our function map
-            self.functions.insert(name.to_string(), function);
-            function
-        };
+        let mut result = Ok(());
+        let declarations = methods.iter().filter_map(|method| match method {
+            Stmt::FunctionDeclaration {
+                params,
+                return_type,
+                body,
+                ..
+            } => Some((params, return_type, body)),
+            _ => None,
+        });
+        for ((params, return_type, body), symbol) in declarations.zip(symbols.iter()) {
+            result = self.generate_function_declaration(symbol, params, return_type, body);
+            if result.is_err() {
+                break;
+            }
+        }
 
-        // Create a new basic block for the function body only if it doesn't exist
-        let basic_block = if function.count_basic_blocks() == 0 {
-            self.context.append_basic_block(function, "entry")
-        } else {
-            function.get_first_basic_block().unwrap()
+        match saved_self {
+            Some(previous) => self.type_substitutions.insert("Self".to_string(), previous),
+            None => self.type_substitutions.remove("Self"),
         };
-        self.builder.position_at_end(basic_block);
-
-        // Create variable allocations for parameters
-        let old_variables = self.variables.clone();
-        self.variables.clear();
-
-        // Special handling for main function with CLI arguments
-        if name == "main" && params.len() == 1 && params[0].type_annotation.name == "Vec<String>" {
-            // For main(args: Vec<String>) -> i32, we get argc and argv
-            let argc = function.get_nth_param(0).ok_or_else(|| {
-                CompileError::codegen_error("Failed to get argc parameter".to_string(), None)
-            })?;
-            let argv = function.get_nth_param(1).ok_or_else(|| {
-                CompileError::codegen_error("Failed to get argv parameter".to_string(), None)
-            })?;
-
-            // Create alloca for the Vec<String> parameter (represented as argv)
-            let argv_alloca = self.create_entry_block_alloca(function, &params[0].name, argv.get_type())?;
-            self.builder.build_store(argv_alloca, argv).map_err(|e| {
-                CompileError::codegen_error(format!("Failed to store argv: {:?}", e), None)
-            })?;
-
-            // Also store argc as a hidden variable
-            let argc_alloca = self.create_entry_block_alloca(function, "__argc", argc.get_type())?;
-            self.builder.build_store(argc_alloca, argc).map_err(|e| {
-                CompileError::codegen_error(format!("Failed to store argc: {:?}", e), None)
-            })?;
-
-            // Add both to variable map
-            self.variables.insert(params[0].name.clone(), argv_alloca);
-            self.variables.insert("__argc".to_string(), argc_alloca);
-        } else {
-            for (i, param) in params.iter().enumerate() {
-                let param_value = function.get_nth_param(i as u32).ok_or_else(|| {
-                    CompileError::codegen_error(format!("Failed to get parameter {}", i), None)
-                })?;
-
-                // Allocate space on the stack for the parameter
-                let alloca =
-                    self.create_entry_block_alloca(function, &param.name, param_value.get_type())?;
+        if let Some(block) = insert_block {
+            self.builder.position_at_end(block);
+        }
+        result
+    }
 
-                // Store the parameter value
-                self.builder.build_store(alloca, param_value).map_err(|e| {
-                    CompileError::codegen_error(format!("Failed to store parameter: {:?}", e), None)
-                })?;
+    /// Generates `receiver.method(args)` or `Type::function(args)` for a
+    /// function of an impl block.
+    ///
+    /// Returns `None` when no impl provides the function, so the call is
+    /// handled as a builtin or module-qualified call instead.
+    fn generate_method_call(
+        &mut self,
+        receiver: &Expr,
+        method_name: &str,
+        args: &[Expr],
+    ) -> Result<Option<BasicValueEnum<'ctx>>> {
+        if !self
+            .methods
+            .values()
+            .any(|methods| methods.contains_key(method_name))
+        {
+            return Ok(None);
+        }
 
-                // Add the variable to our map
-                self.variables.insert(param.name.clone(), alloca);
+        // `Type::function(args)` calls the function with the arguments as written
+        if let Expr::Variable(name, _) = receiver {
+            if !self.variables.contains_key(name) {
+                let symbol = self
+                    .methods
+                    .get(name)
+                    .and_then(|methods| methods.get(method_name))
+                    .cloned();
+                return match symbol {
+                    Some(symbol) => self.build_method_call(&symbol, Vec::new(), args).map(Some),
+                    None => Ok(None),
+                };
             }
         }
 
-        // Generate code for the function body
-        if let Stmt::Block(stmts) = &**body {
-            for stmt in stmts {
-                self.generate_statement(stmt)?;
-            }
-        } else {
-            return Err(CompileError::codegen_error(
-                "Function body must be a block statement".to_string(),
-                None,
-            ));
+        let receiver_value = self.generate_expression(receiver)?;
+        let symbol = self
+            .llvm_type_name(receiver_value.get_type())
+            .and_then(|type_name| self.methods.get(&type_name)?.get(method_name).cloned());
+        match symbol {
+            Some(symbol) => self
+                .build_method_call(&symbol, vec![receiver_value.into()], args)
+                .map(Some),
+            None => Ok(None),
         }
+    }
 
-        // Critical fix: Ensure all basic blocks have terminating instructions
-        // This is required for valid LLVM IR - every block must end with a terminator
-        let current_insert_block = self.builder.get_insert_block();
-        if let Some(function_val) = self.functions.get(name) {
-            let function_val = *function_val;
+    fn build_method_call(
+        &mut self,
+        symbol: &str,
+        mut call_args: Vec<inkwell::values::BasicMetadataValueEnum<'ctx>>,
+        args: &[Expr],
+    ) -> Result<BasicValueEnum<'ctx>> {
+        let function = self.functions.get(symbol).copied().ok_or_else(|| {
+            CompileError::codegen_error(format!("Method '{}' not found", symbol), None)
+        })?;
 
-            for basic_block in function_val.get_basic_blocks() {
-                if !self.block_has_terminator(basic_block)
-                    && Some(basic_block) != current_insert_block
+        let params = function.get_params();
+        for (arg, param) in args.iter().zip(params.into_iter().skip(call_args.len())) {
+            // Integer literals are generated as i32; match them to the parameter width
+            let value = match (self.generate_expression(arg)?, param.get_type()) {
+                (BasicValueEnum::IntValue(int_value), BasicTypeEnum::IntType(int_type))
+                    if int_value.get_type() != int_type =>
                 {
-                    self.builder.position_at_end(basic_block);
-                    // Add unreachable instruction for blocks that don't have terminators
-                    // This satisfies LLVM IR requirements while allowing optimization passes
-                    // to remove unreachable code if needed
-                    self.builder.build_unreachable().map_err(|e| {
-                        CompileError::codegen_error(
-                            format!("Failed to build unreachable for block: {:?}", e),
-                            None,
-                        )
-                    })?;
+                    self.builder
+                        .build_int_cast(int_value, int_type, "method_arg")
+                        .map_err(|e| {
+                            CompileError::codegen_error(
+                                format!("Failed to convert argument: {:?}", e),
+                                None,
+                            )
+                        })?
+                        .into()
                 }
-            }
+                (value, _) => value,
+            };
+            call_args.push(value.into());
         }
 
-        // Restore position to the current block
-        if let Some(current_block) = current_insert_block {
-            self.builder.position_at_end(current_block);
+        let call = self
+            .builder
+            .build_call(function, &call_args, "method_call")
+            .map_err(|e| {
+                CompileError::codegen_error(format!("Failed to build method call: {:?}", e), None)
+            })?;
+
+        match call.try_as_basic_value().left() {
+            Some(return_value) => Ok(return_value),
+            // Method returns void - create a dummy value for statement context
+            None => Ok(self.context.i32_type().const_int(0, false).into()),
         }
+    }
 
-        // Add a return instruction if the current function block doesn't have one already
-        if let Some(current_block) = self.builder.get_insert_block() {
-            if !self.block_has_terminator(current_block) {
-                if return_llvm_type.is_none() {
-                    // Add a void return
-                    self.builder.build_return(None).map_err(|e| {
+    /// Generates code for an if statement using LLVM basic blocks.
+    fn generate_if_statement(
+        &mut self,
+        condition: &Expr,
+        then_branch: &Box<Stmt>,
+        else_branch: &Option<Box<Stmt>>,
+    ) -> Result<()> {
+        let function = self
+            .builder
+            .get_insert_block()
+            .and_then(|block| block.get_parent())
+            .ok_or_else(|| {
+                CompileError::codegen_error(
+                    "If statement outside of function context".to_string(),
+                    None,
+                )
+            })?;
+
+        // Create basic blocks for the if statement
+        let then_block = self.context.append_basic_block(function, "if_then");
+        let else_block = if else_branch.is_some() {
+            Some(self.context.append_basic_block(function, "if_else"))
+        } else {
+            None
+        };
+        let merge_block = self.context.append_basic_block(function, "if_merge");
+
+        // Generate condition
+        let condition_value = self.generate_expression(condition)?;
+        let condition_bool = self.convert_to_bool(condition_value)?;
+
+        // Create conditional branch
+        match else_block {
+            Some(else_bb) => {
+                self.builder
+                    .build_conditional_branch(condition_bool, then_block, else_bb)
+                    .map_err(|e| {
                         CompileError::codegen_error(
-                            format!("Failed to build return: {:?}", e),
+                            format!("Failed to build conditional branch: {:?}", e),
                             None,
                         )
                     })?;
-                } else if name == "main" && return_llvm_type.is_some() {
-                    // Main function should return 0 by default
-                    let zero = self.context.i32_type().const_int(0, false);
-                    self.builder.build_return(Some(&zero)).map_err(|e| {
+            }
+            None => {
+                self.builder
+                    .build_conditional_branch(condition_bool, then_block, merge_block)
+                    .map_err(|e| {
                         CompileError::codegen_error(
-                            format!("Failed to build return: {:?}", e),
+                            format!("Failed to build conditional branch: {:?}", e),
                             None,
                         )
                     })?;
-                } else {
-                    // This is an error - function should have a return value
-                    return Err(CompileError::codegen_error(
-                        format!("Function '{}' must return a value", name),
-                        None,
-                    ));
-                }
             }
         }
 
-        // Restore the previous variable map
-        self.variables = old_variables;
-
-        Ok(())
-    }
-
-    /// Generates code for a function declaration with attributes.
-    fn generate_function_declaration_with_attributes(
-        &mut self,
-        name: &str,
-        params: &[crate::ast::Parameter],
-        return_type: &Option<TypeAnnotation>,
-        body: &Box<Stmt>,
-        attributes: &[crate::ast::Attribute],
-    ) -> Result<()> {
-        // Process @optimize attributes for enhanced code generation
-        let optimization_config = self.parse_optimize_attributes(attributes);
+        // Generate then branch
+        self.builder.position_at_end(then_block);
+        self.generate_statement(then_branch)?;
 
-        // Generate the function with optimization config
-        self.generate_optimized_function_declaration(
-            name,
-            params,
-            return_type,
-            body,
-            &optimization_config,
-        )
-    }
+        // Check if then branch has terminator
+        let then_has_terminator = self.block_has_terminator(then_block);
+        if !then_has_terminator {
+            self.builder
+                .build_unconditional_branch(merge_block)
+                .map_err(|e| {
+                    CompileError::codegen_error(
+                        format!("Failed to build branch to merge: {:?}", e),
+                        None,
+                    )
+                })?;
+        }
 
-    /// Parses @optimize attributes to create optimization configuration
-    fn parse_optimize_attributes(
-        &self,
-        attributes: &[crate::ast::Attribute],
-    ) -> OptimizationConfig {
-        let mut config = OptimizationConfig::default();
+        // Generate else branch if it exists
+        let else_has_terminator = if let Some(else_stmt) = else_branch {
+            let else_bb = else_block.unwrap();
+            self.builder.position_at_end(else_bb);
+            self.generate_statement(else_stmt)?;
 
-        for attr in attributes {
-            if attr.name == "optimize" {
-                for param in &attr.params {
-                    match param.key.as_str() {
-                        "simd" => match &param.value {
-                            crate::ast::AttributeValue::Identifier(val) => {
-                                config.simd_strategy = match val.as_str() {
-                                    "auto" => SIMDStrategy::Auto,
-                                    "enabled" => SIMDStrategy::Enabled,
-                                    "disabled" => SIMDStrategy::Disabled,
-                                    _ => SIMDStrategy::Auto,
-                                };
-                            }
-                            crate::ast::AttributeValue::Boolean(true) => {
-                                config.simd_strategy = SIMDStrategy::Enabled;
-                            }
-                            crate::ast::AttributeValue::Boolean(false) => {
-                                config.simd_strategy = SIMDStrategy::Disabled;
-                            }
-                            _ => {}
-                        },
-                        "unroll" => match &param.value {
-                            crate::ast::AttributeValue::Identifier(val) => {
-                                config.unroll_strategy = match val.as_str() {
-                                    "adaptive" => UnrollStrategy::Adaptive,
-                                    "aggressive" => UnrollStrategy::Aggressive,
-                                    "conservative" => UnrollStrategy::Conservative,
-                                    "disabled" => UnrollStrategy::Disabled,
-                                    _ => UnrollStrategy::Adaptive,
-                                };
-                            }
-                            crate::ast::AttributeValue::Integer(n) => {
-                                config.unroll_factor = Some(*n as u32);
-                            }
-                            _ => {}
-                        },
-                        "algorithm" => match &param.value {
-                            crate::ast::AttributeValue::Identifier(val) => {
-                                config.algorithm_selection = match val.as_str() {
-                                    "adaptive" => AlgorithmSelection::Adaptive,
-                                    "parallel" => AlgorithmSelection::Parallel,
-                                    "sequential" => AlgorithmSelection::Sequential,
-                                    _ => AlgorithmSelection::Adaptive,
-                                };
-                            }
-                            _ => {}
-                        },
-                        "early_exit" => {
-                            if let crate::ast::AttributeValue::Boolean(val) = &param.value {
-                                config.early_exit = *val;
-                            }
-                        }
-                        "buffer" => match &param.value {
-                            crate::ast::AttributeValue::Identifier(val) => {
-                                config.buffer_strategy = match val.as_str() {
-                                    "vectorized" => BufferStrategy::Vectorized,
-                                    "consolidated" => BufferStrategy::Consolidated,
-                                    "adaptive" => BufferStrategy::Adaptive,
-                                    _ => BufferStrategy::Default,
-                                };
-                            }
-                            _ => {}
-                        },
-                        _ => {} // Ignore unknown attributes
-                    }
-                }
+            // Check if else branch has terminator
+            let has_terminator = self.block_has_terminator(else_bb);
+            if !has_terminator {
+                self.builder
+                    .build_unconditional_branch(merge_block)
+                    .map_err(|e| {
+                        CompileError::codegen_error(
+                            format!("Failed to build branch to merge: {:?}", e),
+                            None,
+                        )
+                    })?;
             }
-        }
+            has_terminator
+        } else {
+            false
+        };
 
-        config
+        // Only continue with merge block if it's reachable
+        // If both branches have terminators, merge block is unreachable and should be removed
+        if then_has_terminator && else_has_terminator {
+            // DEVELOPMENT_PROCESS.md: Fix root cause - remove unreachable basic block
+            // This prevents LLVM IR validation errors for empty blocks
+            let _ = merge_block.remove_from_function();
+        } else {
+            self.builder.position_at_end(merge_block);
+        }
+        Ok(())
     }
 
-    /// Generates optimized function declaration based on configuration
-    fn generate_optimized_function_declaration(
+    /// Generates code for an if expression that returns a value using phi nodes.
+    fn generate_if_expression(
         &mut self,
-        name: &str,
-        params: &[crate::ast::Parameter],
-        return_type: &Option<TypeAnnotation>,
-        body: &Box<Stmt>,
-        config: &OptimizationConfig,
-    ) -> Result<()> {
-        // Store the optimization config for use during code generation
-        let old_config = self.current_optimization_config.clone();
-        self.current_optimization_config = Some(config.clone());
+        condition: &Expr,
+        then_branch: &Box<Expr>,
+        else_branch: &Option<Box<Expr>>,
+    ) -> Result<BasicValueEnum<'ctx>> {
+        let function = self
+            .builder
+            .get_insert_block()
+            .and_then(|block| block.get_parent())
+            .ok_or_else(|| {
+                CompileError::codegen_error(
+                    "If expression outside of function context".to_string(),
+                    None,
+                )
+            })?;
 
-        // Generate the function
-        let result = self.generate_function_declaration(name, params, return_type, body);
+        // Create basic blocks for the if expression
+        let then_block = self.context.append_basic_block(function, "if_then_expr");
+        let else_block = self.context.append_basic_block(function, "if_else_expr");
+        let merge_block = self.context.append_basic_block(function, "if_merge_expr");
 
-        // Apply post-generation optimizations based on config
-        if result.is_ok() {
-            if let Some(function) = self.functions.get(name) {
-                self.apply_function_optimizations(*function, config)?;
+        // Generate condition
+        let condition_value = self.generate_expression(condition)?;
+        let condition_bool = self.convert_to_bool(condition_value)?;
+
+        // Create conditional branch
+        self.builder
+            .build_conditional_branch(condition_bool, then_block, else_block)
+            .map_err(|e| {
+                CompileError::codegen_error(
+                    format!("Failed to build conditional branch for if expression: {:?}", e),
+                    None,
+                )
+            })?;
+
+        // Generate then branch
+        self.builder.position_at_end(then_block);
+        let then_value = self.generate_expression(then_branch)?;
+        let then_block_final = self.builder.get_insert_block().unwrap();
+        
+        // Branch to merge block
+        self.builder
+            .build_unconditional_branch(merge_block)
+            .map_err(|e| {
+                CompileError::codegen_error(
+                    format!("Failed to build branch from then block: {:?}", e),
+                    None,
+                )
+            })?;
+
+        // Generate else branch
+        self.builder.position_at_end(else_block);
+        let else_value = if let Some(else_expr) = else_branch {
+            self.generate_expression(else_expr)?
+        } else {
+            // If no else branch, return a default value of the same type as then branch
+            match then_value {
+                BasicValueEnum::IntValue(_) => self.context.i32_type().const_int(0, false).into(),
+                BasicValueEnum::FloatValue(_) => self.context.f32_type().const_float(0.0).into(),
+                BasicValueEnum::PointerValue(_) => {
+                    // For strings, return empty string
+                    let empty_str = self.context.const_string(b"", false);
+                    let global = self.module.add_global(empty_str.get_type(), None, "empty_str");
+                    global.set_initializer(&empty_str);
+                    global.as_pointer_value().into()
+                },
+                _ => then_value, // Use same value for other types
             }
-        }
+        };
+        let else_block_final = self.builder.get_insert_block().unwrap();
+        
+        // Branch to merge block
+        self.builder
+            .build_unconditional_branch(merge_block)
+            .map_err(|e| {
+                CompileError::codegen_error(
+                    format!("Failed to build branch from else block: {:?}", e),
+                    None,
+                )
+            })?;
 
-        // Restore previous config
-        self.current_optimization_config = old_config;
+        // Create phi node in merge block
+        self.builder.position_at_end(merge_block);
+        let phi = self.builder.build_phi(then_value.get_type(), "if_result")
+            .map_err(|e| {
+                CompileError::codegen_error(
+                    format!("Failed to build phi node: {:?}", e),
+                    None,
+                )
+            })?;
 
-        result
+        phi.add_incoming(&[(&then_value, then_block_final), (&else_value, else_block_final)]);
+        
+        Ok(phi.as_basic_value())
     }
 
-    /// Applies optimizations to a generated function based on configuration
-    fn apply_function_optimizations(
-        &mut self,
-        function: FunctionValue<'ctx>,
-        config: &OptimizationConfig,
-    ) -> Result<()> {
-        // Apply SIMD optimizations
-        match config.simd_strategy {
-            SIMDStrategy::Enabled => {
-                self.optimize_simd_function(function)?;
-            }
-            SIMDStrategy::Auto => {
-                // Analyze function to determine if SIMD would be beneficial
-                if self.should_apply_simd_optimization(function) {
-                    self.optimize_simd_function(function)?;
-                }
-            }
-            SIMDStrategy::Disabled => {
-                // Skip SIMD optimizations
-            }
-        }
+    /// Generates code for a while loop using LLVM basic blocks.
+    fn generate_while_statement(&mut self, condition: &Expr, body: &Box<Stmt>) -> Result<()> {
+        let function = self
+            .builder
+            .get_insert_block()
+            .and_then(|block| block.get_parent())
+            .ok_or_else(|| {
+                CompileError::codegen_error(
+                    "While statement outside of function context".to_string(),
+                    None,
+                )
+            })?;
 
-        // Apply unroll optimizations
-        match config.unroll_strategy {
-            UnrollStrategy::Aggressive => {
-                self.apply_aggressive_unrolling(function, config.unroll_factor)?;
-            }
-            UnrollStrategy::Adaptive => {
-                self.apply_adaptive_unrolling(function)?;
-            }
-            UnrollStrategy::Conservative => {
-                self.apply_conservative_unrolling(function)?;
-            }
-            UnrollStrategy::Disabled => {
-                // Skip unroll optimizations
-            }
-        }
+        // Create basic blocks for the while loop
+        let loop_cond_block = self.context.append_basic_block(function, "while_cond");
+        let loop_body_block = self.context.append_basic_block(function, "while_body");
+        let loop_end_block = self.context.append_basic_block(function, "while_end");
+        let label = self.pending_loop_label.take();
 
-        // Apply other optimizations based on config
-        if config.early_exit {
-            self.apply_early_exit_optimizations(function)?;
-        }
+        // Branch to condition check
+        self.builder
+            .build_unconditional_branch(loop_cond_block)
+            .map_err(|e| {
+                CompileError::codegen_error(
+                    format!("Failed to build branch to while condition: {:?}", e),
+                    None,
+                )
+            })?;
 
-        Ok(())
-    }
+        // Generate condition check
+        self.builder.position_at_end(loop_cond_block);
+        let condition_value = self.generate_expression(condition)?;
+        let condition_bool = self.convert_to_bool(condition_value)?;
 
-    /// Determines if SIMD optimization should be applied automatically
-    fn should_apply_simd_optimization(&self, function: FunctionValue<'ctx>) -> bool {
-        // Analyze function to check for:
-        // 1. Vector operations
-        // 2. Array processing loops
-        // 3. Mathematical computations that benefit from SIMD
+        // Conditional branch: if true go to body, if false go to end
+        self.builder
+            .build_conditional_branch(condition_bool, loop_body_block, loop_end_block)
+            .map_err(|e| {
+                CompileError::codegen_error(
+                    format!("Failed to build while conditional branch: {:?}", e),
+                    None,
+                )
+            })?;
 
-        for basic_block in function.get_basic_blocks() {
-            for instruction in basic_block.get_instructions() {
-                if self.is_simd_instruction(&instruction) {
-                    return true;
-                }
+        // Generate loop body; `continue` re-checks the condition
+        self.builder.position_at_end(loop_body_block);
+        self.generate_loop_body(label, body, loop_end_block, loop_cond_block)?;
 
-                // Check for patterns that benefit from SIMD
-                if self.instruction_benefits_from_simd(&instruction) {
-                    return true;
-                }
+        // Branch back to condition check if the current block doesn't already terminate
+        let current_block = self.builder.get_insert_block();
+        if let Some(block) = current_block {
+            if !self.block_has_terminator(block) {
+                self.builder
+                    .build_unconditional_branch(loop_cond_block)
+                    .map_err(|e| {
+                        CompileError::codegen_error(
+                            format!("Failed to build branch back to condition: {:?}", e),
+                            None,
+                        )
+                    })?;
             }
         }
 
-        false
+        // Continue after the loop
+        self.builder.position_at_end(loop_end_block);
+        Ok(())
     }
 
-    /// Checks if an instruction would benefit from SIMD optimization
-    fn instruction_benefits_from_simd(
-        &self,
-        instruction: &inkwell::values::InstructionValue,
-    ) -> bool {
-        match instruction.get_opcode() {
-            // Arithmetic operations that can be vectorized
-            inkwell::values::InstructionOpcode::Add
-            | inkwell::values::InstructionOpcode::FAdd
-            | inkwell::values::InstructionOpcode::Sub
-            | inkwell::values::InstructionOpcode::FSub
-            | inkwell::values::InstructionOpcode::Mul
-            | inkwell::values::InstructionOpcode::FMul
-            | inkwell::values::InstructionOpcode::UDiv
-            | inkwell::values::InstructionOpcode::SDiv
-            | inkwell::values::InstructionOpcode::FDiv => {
-                // Check if instruction operates on data that could be vectorized
-                if let Some(operand) = instruction.get_operand(0) {
-                    if let Some(operand_value) = operand.left() {
-                        let ty = operand_value.get_type();
-                        // Float and integer operations on arrays/pointers can benefit from SIMD
-                        return ty.is_float_type() || ty.is_int_type() || ty.is_pointer_type();
-                    }
-                }
-                false
-            }
-            // Mathematical functions that have SIMD implementations
-            inkwell::values::InstructionOpcode::Call => {
-                // Simplified: assume mathematical function calls can benefit from SIMD
-                // In a full implementation, we would inspect the called function name
-                true
-            }
-            // Load/store operations in loops can benefit from vectorization
-            inkwell::values::InstructionOpcode::Load
-            | inkwell::values::InstructionOpcode::Store => true,
-            _ => false,
+    /// Generates code for a for loop using LLVM basic blocks.
+    fn generate_for_statement(
+        &mut self,
+        initializer: &Option<Box<Stmt>>,
+        condition: &Option<Expr>,
+        increment: &Option<Expr>,
+        body: &Box<Stmt>,
+    ) -> Result<()> {
+        let function = self
+            .builder
+            .get_insert_block()
+            .and_then(|block| block.get_parent())
+            .ok_or_else(|| {
+                CompileError::codegen_error(
+                    "For statement outside of function context".to_string(),
+                    None,
+                )
+            })?;
+
+        // Create basic blocks for the for loop
+        let loop_cond_block = self.context.append_basic_block(function, "for_cond");
+        let loop_body_block = self.context.append_basic_block(function, "for_body");
+        let loop_inc_block = self.context.append_basic_block(function, "for_inc");
+        let loop_end_block = self.context.append_basic_block(function, "for_end");
+        let label = self.pending_loop_label.take();
+
+        // Generate initializer
+        if let Some(init) = initializer {
+            self.generate_statement(init)?;
         }
-    }
 
-    /// Optimizes a function for SIMD operations
-    fn optimize_simd_function(&mut self, function: FunctionValue<'ctx>) -> Result<()> {
-        // Skip SIMD optimization in JIT safe mode to prevent segmentation faults
-        if self.jit_safe_mode {
-            return Ok(());
-        }
-
-        // Apply function-level SIMD attributes for LLVM optimization passes
-        let context = self.context;
-
-        // Add vectorization attributes
-        let vectorize_attr = context.create_string_attribute("vectorize", "true");
-        function.add_attribute(inkwell::attributes::AttributeLoc::Function, vectorize_attr);
-
-        let slp_vectorize_attr = context.create_string_attribute("slp-vectorize", "true");
-        function.add_attribute(
-            inkwell::attributes::AttributeLoc::Function,
-            slp_vectorize_attr,
-        );
-
-        let unroll_vectorize_attr = context.create_string_attribute("unroll-vectorize", "true");
-        function.add_attribute(
-            inkwell::attributes::AttributeLoc::Function,
-            unroll_vectorize_attr,
-        );
-
-        // Mark function for target-specific SIMD optimizations
-        self.mark_function_for_simd_optimization(function)?;
-
-        // Analyze loops and apply vectorization hints
-        self.apply_loop_vectorization_hints(function)?;
+        // Branch to condition check
+        self.builder
+            .build_unconditional_branch(loop_cond_block)
+            .map_err(|e| {
+                CompileError::codegen_error(
+                    format!("Failed to build branch to for condition: {:?}", e),
+                    None,
+                )
+            })?;
 
-        Ok(())
-    }
+        // Generate condition check
+        self.builder.position_at_end(loop_cond_block);
+        if let Some(cond) = condition {
+            let condition_value = self.generate_expression(cond)?;
+            let condition_bool = self.convert_to_bool(condition_value)?;
 
-    /// Marks function with target-specific SIMD optimization attributes
-    fn mark_function_for_simd_optimization(&mut self, function: FunctionValue<'ctx>) -> Result<()> {
-        // Skip SIMD features in JIT safe mode to prevent segmentation faults
-        if self.jit_safe_mode {
-            return Ok(());
+            // Conditional branch: if true go to body, if false go to end
+            self.builder
+                .build_conditional_branch(condition_bool, loop_body_block, loop_end_block)
+                .map_err(|e| {
+                    CompileError::codegen_error(
+                        format!("Failed to build for conditional branch: {:?}", e),
+                        None,
+                    )
+                })?;
+        } else {
+            // No condition means infinite loop, always go to body
+            self.builder
+                .build_unconditional_branch(loop_body_block)
+                .map_err(|e| {
+                    CompileError::codegen_error(
+                        format!("Failed to build unconditional branch to body: {:?}", e),
+                        None,
+                    )
+                })?;
         }
 
-        let context = self.context;
-
-        // Add target features for maximum SIMD support
-        let target_features_attr =
-            context.create_string_attribute("target-features", "+avx2,+sse4.2,+fma");
-        function.add_attribute(
-            inkwell::attributes::AttributeLoc::Function,
-            target_features_attr,
-        );
-
-        // Enable auto-vectorization for the function
-        let prefer_vector_width_attr =
-            context.create_string_attribute("prefer-vector-width", "256");
-        function.add_attribute(
-            inkwell::attributes::AttributeLoc::Function,
-            prefer_vector_width_attr,
-        );
-
-        Ok(())
-    }
+        // Generate loop body; `continue` jumps to the increment
+        self.builder.position_at_end(loop_body_block);
+        self.generate_loop_body(label, body, loop_end_block, loop_inc_block)?;
 
-    /// Applies loop vectorization hints to function basic blocks
-    fn apply_loop_vectorization_hints(&mut self, function: FunctionValue<'ctx>) -> Result<()> {
-        // Find loops in the function and add vectorization metadata
-        for basic_block in function.get_basic_blocks() {
-            if self.is_loop_block(basic_block) {
-                self.add_vectorization_metadata(basic_block)?;
+        // After the body, check the current block and branch to increment
+        let current_block = self.builder.get_insert_block();
+        if let Some(current_block) = current_block {
+            if !self.block_has_terminator(current_block) {
+                self.builder
+                    .build_unconditional_branch(loop_inc_block)
+                    .map_err(|e| {
+                        CompileError::codegen_error(
+                            format!("Failed to build branch to increment: {:?}", e),
+                            None,
+                        )
+                    })?;
             }
         }
-        Ok(())
-    }
 
-    /// Checks if a basic block represents a loop
-    fn is_loop_block(&self, block: BasicBlock<'ctx>) -> bool {
-        // Check if block has a backward branch (simple loop detection)
-        if let Some(terminator) = block.get_terminator() {
-            match terminator.get_opcode() {
-                inkwell::values::InstructionOpcode::Br => {
-                    // Conditional branch might be a loop
-                    terminator.get_num_operands() > 1
-                }
-                _ => false,
-            }
-        } else {
-            false
+        // Generate increment
+        self.builder.position_at_end(loop_inc_block);
+        if let Some(inc) = increment {
+            self.generate_expression(inc)?;
         }
-    }
 
-    /// Adds vectorization metadata to a loop block
-    fn add_vectorization_metadata(&mut self, _block: BasicBlock<'ctx>) -> Result<()> {
-        // In a real implementation, this would add LLVM metadata nodes
-        // to hint the optimizer about vectorization opportunities
-        // For now, we rely on function-level attributes
+        // Branch back to condition check
+        self.builder
+            .build_unconditional_branch(loop_cond_block)
+            .map_err(|e| {
+                CompileError::codegen_error(
+                    format!("Failed to build branch back to condition: {:?}", e),
+                    None,
+                )
+            })?;
+
+        // Continue after the loop - this is where the next statement will be generated
+        self.builder.position_at_end(loop_end_block);
+
         Ok(())
     }
 
-    /// Applies aggressive loop unrolling
-    fn apply_aggressive_unrolling(
+    /// Generates code for a for-in statement that iterates over arrays.
+    fn generate_for_in_statement(
         &mut self,
-        function: FunctionValue<'ctx>,
-        factor: Option<u32>,
+        variable: &str,
+        iterable: &Expr,
+        body: &Box<Stmt>,
     ) -> Result<()> {
-        let unroll_factor = factor.unwrap_or(8); // Default aggressive unroll factor
-        let context = self.context;
+        let function = self
+            .builder
+            .get_insert_block()
+            .and_then(|block| block.get_parent())
+            .ok_or_else(|| {
+                CompileError::codegen_error(
+                    "For-in statement outside of function context".to_string(),
+                    None,
+                )
+            })?;
 
-        // Add LLVM function attributes for aggressive unrolling
-        let unroll_count_attr =
-            context.create_string_attribute("unroll-count", &unroll_factor.to_string());
-        function.add_attribute(
-            inkwell::attributes::AttributeLoc::Function,
-            unroll_count_attr,
-        );
+        // Generate code for the array being iterated
+        let array_value = self.generate_expression(iterable)?;
+        let array_ptr = match array_value {
+            BasicValueEnum::PointerValue(pointer) => pointer,
+            // Arrays returned by value are iterated from a temporary
+            value => {
+                let alloca = self
+                    .builder
+                    .build_alloca(value.get_type(), "for_in_array")
+                    .map_err(|e| {
+                        CompileError::codegen_error(
+                            format!("Failed to allocate iterated array: {:?}", e),
+                            None,
+                        )
+                    })?;
+                self.builder.build_store(alloca, value).map_err(|e| {
+                    CompileError::codegen_error(
+                        format!("Failed to store iterated array: {:?}", e),
+                        None,
+                    )
+                })?;
+                alloca
+            }
+        };
 
-        // Enable full loop unrolling where beneficial
-        let unroll_full_attr = context.create_string_attribute("unroll-full", "true");
-        function.add_attribute(
-            inkwell::attributes::AttributeLoc::Function,
-            unroll_full_attr,
-        );
+        // Create basic blocks for the for-in loop
+        let loop_cond_block = self.context.append_basic_block(function, "for_in_cond");
+        let loop_body_block = self.context.append_basic_block(function, "for_in_body");
+        let loop_inc_block = self.context.append_basic_block(function, "for_in_inc");
+        let loop_end_block = self.context.append_basic_block(function, "for_in_end");
+        let label = self.pending_loop_label.take();
 
-        // Apply to all loops in the function
-        self.apply_unroll_attributes_to_loops(function, unroll_factor)?;
+        // Create loop counter variable
+        let counter_ptr = self
+            .builder
+            .build_alloca(self.context.i32_type(), "counter")
+            .unwrap();
+        self.builder
+            .build_store(counter_ptr, self.context.i32_type().const_zero())
+            .unwrap();
 
-        Ok(())
-    }
+        // The length of a fixed-size array is part of its type
+        let array_size = self.get_array_size(array_ptr)?;
 
-    /// Applies adaptive loop unrolling based on loop characteristics
-    fn apply_adaptive_unrolling(&mut self, function: FunctionValue<'ctx>) -> Result<()> {
-        // Analyze each loop and determine optimal unroll factor
-        for basic_block in function.get_basic_blocks() {
-            if self.is_loop_block(basic_block) {
-                let optimal_factor = self.calculate_optimal_unroll_factor(basic_block);
-                self.apply_unroll_attributes_to_loops(function, optimal_factor)?;
-            }
-        }
+        // Branch to condition check
+        self.builder
+            .build_unconditional_branch(loop_cond_block)
+            .unwrap();
 
-        // Enable adaptive unrolling hints
-        let context = self.context;
-        let unroll_enable_attr = context.create_string_attribute("unroll-enable", "true");
-        function.add_attribute(
-            inkwell::attributes::AttributeLoc::Function,
-            unroll_enable_attr,
-        );
+        // Generate condition check: counter < array_size
+        self.builder.position_at_end(loop_cond_block);
+        let counter_val = self
+            .builder
+            .build_load(counter_ptr, "counter_load")
+            .unwrap()
+            .into_int_value();
+        let condition = self
+            .builder
+            .build_int_compare(
+                IntPredicate::ULT,
+                counter_val,
+                array_size,
+                "for_in_condition",
+            )
+            .unwrap();
 
-        Ok(())
-    }
+        // Conditional branch: if true go to body, if false go to end
+        self.builder
+            .build_conditional_branch(condition, loop_body_block, loop_end_block)
+            .unwrap();
 
-    /// Applies conservative loop unrolling
-    fn apply_conservative_unrolling(&mut self, function: FunctionValue<'ctx>) -> Result<()> {
-        // Conservative unrolling: small factor, only for simple loops
-        let conservative_factor = 2;
-        let context = self.context;
+        // Generate loop body
+        self.builder.position_at_end(loop_body_block);
 
-        let unroll_count_attr =
-            context.create_string_attribute("unroll-count", &conservative_factor.to_string());
-        function.add_attribute(
-            inkwell::attributes::AttributeLoc::Function,
-            unroll_count_attr,
-        );
-
-        // Only unroll if profitable
-        let unroll_enable_attr = context.create_string_attribute("unroll-enable", "true");
-        function.add_attribute(
-            inkwell::attributes::AttributeLoc::Function,
-            unroll_enable_attr,
-        );
+        // Load the current element from the array
+        let current_counter = self
+            .builder
+            .build_load(counter_ptr, "counter_load")
+            .unwrap()
+            .into_int_value();
+        let element_ptr = unsafe {
+            self.builder
+                .build_gep(
+                    array_ptr,
+                    &[self.context.i32_type().const_zero(), current_counter],
+                    "element_ptr",
+                )
+                .unwrap()
+        };
+        let element_value = self
+            .builder
+            .build_load(element_ptr, "element_value")
+            .unwrap();
 
-        let unroll_threshold_attr = context.create_string_attribute("unroll-threshold", "150");
-        function.add_attribute(
-            inkwell::attributes::AttributeLoc::Function,
-            unroll_threshold_attr,
-        );
+        // Create a variable for the loop variable and store the current element
+        let loop_var_ptr = self
+            .builder
+            .build_alloca(element_value.get_type(), variable)
+            .unwrap();
+        self.builder
+            .build_store(loop_var_ptr, element_value)
+            .unwrap();
 
-        Ok(())
-    }
+        // Add the loop variable to the variables map for the body
+        self.variables.insert(variable.to_string(), loop_var_ptr);
 
-    /// Calculates optimal unroll factor for a specific loop
-    fn calculate_optimal_unroll_factor(&self, _block: BasicBlock<'ctx>) -> u32 {
-        // Simplified heuristic for optimal unroll factor
-        // In practice, this would analyze:
-        // - Loop body size
-        // - Memory access patterns
-        // - Register pressure
-        // - Target architecture capabilities
+        // Generate the loop body; `continue` jumps to the increment
+        self.generate_loop_body(label, body, loop_end_block, loop_inc_block)?;
 
-        // For now, return a reasonable default
-        4
-    }
+        // Remove the loop variable from scope
+        self.variables.remove(variable);
 
-    /// Applies unroll attributes to loops in the function
-    fn apply_unroll_attributes_to_loops(
-        &mut self,
-        function: FunctionValue<'ctx>,
-        factor: u32,
-    ) -> Result<()> {
-        // In LLVM, unroll attributes are typically applied via metadata
-        // This simplified version applies function-level hints
-        let context = self.context;
+        // Branch to increment unless the body already left the block (break/continue/return)
+        if let Some(current_block) = self.builder.get_insert_block() {
+            if !self.block_has_terminator(current_block) {
+                self.builder
+                    .build_unconditional_branch(loop_inc_block)
+                    .unwrap();
+            }
+        }
 
-        let unroll_count_attr =
-            context.create_string_attribute("unroll-count", &factor.to_string());
-        function.add_attribute(
-            inkwell::attributes::AttributeLoc::Function,
-            unroll_count_attr,
-        );
+        // Generate increment: counter++
+        self.builder.position_at_end(loop_inc_block);
+        let current_counter = self
+            .builder
+            .build_load(counter_ptr, "counter_load")
+            .unwrap()
+            .into_int_value();
+        let incremented_counter = self
+            .builder
+            .build_int_add(
+                current_counter,
+                self.context.i32_type().const_int(1, false),
+                "incremented_counter",
+            )
+            .unwrap();
+        self.builder
+            .build_store(counter_ptr, incremented_counter)
+            .unwrap();
 
-        // Enable pragma unroll
-        let unroll_pragma_attr = context.create_string_attribute("unroll-pragma", "true");
-        function.add_attribute(
-            inkwell::attributes::AttributeLoc::Function,
-            unroll_pragma_attr,
-        );
+        // Branch back to condition check
+        self.builder
+            .build_unconditional_branch(loop_cond_block)
+            .unwrap();
 
+        // Continue after the loop
+        self.builder.position_at_end(loop_end_block);
         Ok(())
     }
 
-    /// Applies early exit optimizations for better performance
-    fn apply_early_exit_optimizations(&mut self, function: FunctionValue<'ctx>) -> Result<()> {
-        let context = self.context;
+    /// Generates a loop body with the loop registered as a `break`/`continue` target.
+    fn generate_loop_body(
+        &mut self,
+        label: Option<String>,
+        body: &Stmt,
+        break_block: BasicBlock<'ctx>,
+        continue_block: BasicBlock<'ctx>,
+    ) -> Result<()> {
+        self.loop_stack.push(LoopContext {
+            label,
+            break_block,
+            continue_block,
+        });
+        let result = self.generate_statement(body);
+        self.loop_stack.pop();
+        result
+    }
 
-        // Add optimization hints for branch prediction and early exits
-        let branch_weight_attr = context.create_string_attribute("branch-weight", "true");
-        function.add_attribute(
-            inkwell::attributes::AttributeLoc::Function,
-            branch_weight_attr,
-        );
+    /// Generates `break` (`is_break`) or `continue` as a branch to the target loop's exit or latch.
+    fn generate_loop_exit(&mut self, label: &Option<String>, is_break: bool) -> Result<()> {
+        let keyword = if is_break { "break" } else { "continue" };
 
-        // Enable tail call optimization for recursive early exits
-        let tail_call_attr = context.create_string_attribute("tail-call-optimization", "true");
-        function.add_attribute(inkwell::attributes::AttributeLoc::Function, tail_call_attr);
+        let target = match label {
+            Some(label) => self
+                .loop_stack
+                .iter()
+                .rev()
+                .find(|loop_ctx| loop_ctx.label.as_deref() == Some(label.as_str())),
+            None => self.loop_stack.last(),
+        }
+        .ok_or_else(|| {
+            CompileError::codegen_error(
+                match label {
+                    Some(label) => format!("'{}' to unknown loop label '{}", keyword, label),
+                    None => format!("'{}' outside of a loop", keyword),
+                },
+                None,
+            )
+        })?;
+        let target_block = if is_break {
+            target.break_block
+        } else {
+            target.continue_block
+        };
 
-        // Apply early exit patterns to conditional blocks
-        self.optimize_conditional_blocks_for_early_exit(function)?;
+        self.builder
+            .build_unconditional_branch(target_block)
+            .map_err(|e| {
+                CompileError::codegen_error(
+                    format!("Failed to build branch for '{}': {:?}", keyword, e),
+                    None,
+                )
+            })?;
 
-        Ok(())
-    }
+        // Code after break/continue is unreachable; give it a block of its own so the
+        // branch stays the terminator. Unterminated blocks get `unreachable` when the
+        // function is finished.
+        let function = self
+            .builder
+            .get_insert_block()
+            .and_then(|block| block.get_parent())
+            .ok_or_else(|| {
+                CompileError::codegen_error(
+                    format!("'{}' outside of function context", keyword),
+                    None,
+                )
+            })?;
+        let after_block = self
+            .context
+            .append_basic_block(function, &format!("after_{}", keyword));
+        self.builder.position_at_end(after_block);
 
-    /// Optimizes conditional blocks for early exit patterns
-    fn optimize_conditional_blocks_for_early_exit(
-        &mut self,
-        function: FunctionValue<'ctx>,
-    ) -> Result<()> {
-        // Find conditional branches and optimize for likely/unlikely patterns
-        for basic_block in function.get_basic_blocks() {
-            if let Some(terminator) = basic_block.get_terminator() {
-                if terminator.get_opcode() == inkwell::values::InstructionOpcode::Br {
-                    // This is where we would add branch probability metadata
-                    // to hint to LLVM about likely early exits
-                    self.add_branch_probability_hints(terminator)?;
-                }
-            }
-        }
         Ok(())
     }
 
-    /// Adds branch probability hints for optimization
-    fn add_branch_probability_hints(
-        &mut self,
-        _instruction: inkwell::values::InstructionValue,
-    ) -> Result<()> {
-        // In a full implementation, this would add LLVM metadata for branch weights
-        // to optimize for common early-exit patterns
-        Ok(())
+    /// Get the size of the array `array_ptr` points to, `N` for `[T; N]`
+    fn get_array_size(&self, array_ptr: PointerValue<'ctx>) -> Result<IntValue<'ctx>> {
+        match array_ptr.get_type().get_element_type() {
+            inkwell::types::AnyTypeEnum::ArrayType(arr_type) => Ok(self
+                .context
+                .i32_type()
+                .const_int(arr_type.len() as u64, false)),
+            other => Err(CompileError::codegen_error(
+                format!(
+                    "Unsupported iterable type in for-in loop - only fixed-size arrays are supported, got {}",
+                    other.print_to_string()
+                ),
+                None,
+            )),
+        }
     }
 
-    /// Creates an allocation instruction in the entry block of the function.
-    fn create_entry_block_alloca(
+    /// Converts a value to a boolean for use in conditional branches.
+    fn convert_to_bool(
         &self,
-        function: FunctionValue<'ctx>,
-        name: &str,
-        ty: BasicTypeEnum<'ctx>,
-    ) -> Result<PointerValue<'ctx>> {
-        let builder = self.context.create_builder();
-        let entry = function.get_first_basic_block().unwrap();
-
-        match entry.get_first_instruction() {
-            Some(first_instr) => builder.position_before(&first_instr),
-            None => builder.position_at_end(entry),
+        value: BasicValueEnum<'ctx>,
+    ) -> Result<inkwell::values::IntValue<'ctx>> {
+        match value {
+            BasicValueEnum::IntValue(int_val) => {
+                if int_val.get_type() == self.context.bool_type() {
+                    Ok(int_val)
+                } else {
+                    // Convert integer to bool by comparing with 0
+                    let zero = int_val.get_type().const_zero();
+                    self.builder
+                        .build_int_compare(IntPredicate::NE, int_val, zero, "tobool")
+                        .map_err(|e| {
+                            CompileError::codegen_error(
+                                format!("Failed to convert to bool: {:?}", e),
+                                None,
+                            )
+                        })
+                }
+            }
+            BasicValueEnum::FloatValue(float_val) => {
+                // Convert float to bool by comparing with 0.0
+                let zero = float_val.get_type().const_zero();
+                self.builder
+                    .build_float_compare(FloatPredicate::ONE, float_val, zero, "tobool")
+                    .map_err(|e| {
+                        CompileError::codegen_error(
+                            format!("Failed to convert float to bool: {:?}", e),
+                            None,
+                        )
+                    })
+            }
+            _ => Err(CompileError::codegen_error(
+                "Cannot convert value to boolean".to_string(),
+                None,
+            )),
         }
+    }
 
-        builder.build_alloca(ty, name).map_err(|e| {
-            CompileError::codegen_error(format!("Failed to build alloca: {:?}", e), None)
-        })
+    /// Checks if a basic block has a terminator instruction.
+    fn block_has_terminator(&self, block: BasicBlock<'ctx>) -> bool {
+        block.get_terminator().is_some()
     }
 
-    /// Generates code for a variable declaration with pattern.
-    fn generate_var_declaration_pattern(
-        &mut self,
-        pattern: &Pattern,
-        type_annotation: &Option<TypeAnnotation>,
-        initializer: &Option<Expr>,
-    ) -> Result<()> {
-        match pattern {
-            Pattern::Variable(name) => {
-                // Simple variable pattern - delegate to existing method
-                self.generate_var_declaration(name, type_annotation, initializer)
-            }
-            Pattern::Tuple { patterns, .. } => {
-                // Tuple destructuring pattern
-                if let Some(init) = initializer {
-                    let tuple_value = self.generate_expression(init)?;
-                    
-                    // For each pattern in the tuple, extract the element and create a variable
-                    for (index, pattern) in patterns.iter().enumerate() {
-                        match pattern {
-                            Pattern::Variable(var_name) => {
-                                // Extract the tuple element
-                                let element_value = self.builder.build_extract_value(
-                                    tuple_value.into_struct_value(),
-                                    index as u32,
-                                    &format!("{}_element_{}", var_name, index),
-                                ).map_err(|e| CompileError::codegen_error(
-                                    format!("Failed to extract tuple element: {}", e),
-                                    None,
-                                ))?;
-                                
-                                // Create alloca for the variable
-                                let element_type = element_value.get_type();
-                                let alloca = self.builder.build_alloca(element_type, var_name)
-                                    .map_err(|e| CompileError::codegen_error(
-                                        format!("Failed to create alloca: {}", e),
-                                        None,
-                                    ))?;
-                                
-                                // Store the value
-                                self.builder.build_store(alloca, element_value)
-                                    .map_err(|e| CompileError::codegen_error(
-                                        format!("Failed to store value: {}", e),
-                                        None,
-                                    ))?;
-                                
-                                // Add to variables map
-                                self.variables.insert(var_name.clone(), alloca);
-                            }
-                            Pattern::Wildcard => {
-                                // Wildcard patterns don't bind variables, so just continue
-                            }
-                            _ => {
-                                return Err(CompileError::codegen_error(
-                                    "Only variable and wildcard patterns supported in tuple destructuring".to_string(),
-                                    None,
-                                ));
+    /// Checks if a basic block has no predecessor blocks (unreachable)
+    fn block_has_no_predecessors(&self, block: BasicBlock<'ctx>) -> bool {
+        // Check if any block in the function has a terminator that branches to this block
+        if let Some(parent_function) = block.get_parent() {
+            for predecessor_block in parent_function.get_basic_blocks() {
+                if let Some(terminator) = predecessor_block.get_terminator() {
+                    // Check if this terminator branches to our block
+                    let num_operands = terminator.get_num_operands();
+                    for i in 0..num_operands {
+                        if let Some(operand) = terminator.get_operand(i) {
+                            if let Some(operand_block) = operand.right() {
+                                if operand_block == block {
+                                    return false; // Found a predecessor
+                                }
                             }
                         }
                     }
-                    Ok(())
-                } else {
-                    Err(CompileError::codegen_error(
-                        "Tuple destructuring requires an initializer".to_string(),
-                        None,
-                    ))
                 }
             }
-            _ => {
-                // For now, only support variable and tuple patterns
-                Err(CompileError::codegen_error(
-                    "Complex patterns not yet supported in variable declarations".to_string(),
-                    None,
-                ))
-            }
         }
+        true // No predecessors found
     }
 
-    /// Generates code for a variable declaration.
-    fn generate_var_declaration(
+    /// Generates code for a function declaration.
+    fn generate_function_declaration(
         &mut self,
         name: &str,
-        type_annotation: &Option<TypeAnnotation>,
-        initializer: &Option<Expr>,
+        params: &[crate::ast::Parameter],
+        return_type: &Option<TypeAnnotation>,
+        body: &Box<Stmt>,
     ) -> Result<()> {
-        // Determine the variable type
-        let var_type = if let Some(type_ann) = type_annotation {
-            match type_ann.name.as_str() {
-                "i32" => self.context.i32_type().into(),
-                "i64" => self.context.i64_type().into(),
-                "f32" => self.context.f32_type().into(),
-                "f64" => self.context.f64_type().into(),
-                "bool" => self.context.bool_type().into(),
-                "string" => self
-                    .context
-                    .i8_type()
-                    .ptr_type(AddressSpace::default())
-                    .into(),
-                "Vec" => self
-                    .context
-                    .i8_type()
-                    .ptr_type(AddressSpace::default())
-                    .into(),
-                "Vec<i32>" => self
-                    .context
-                    .i8_type()
-                    .ptr_type(AddressSpace::default())
-                    .into(),
-                "HashMap" => self
-                    .context
-                    .i8_type()
-                    .ptr_type(AddressSpace::default())
-                    .into(),
-                "HashSet" => self
-                    .context
-                    .i8_type()
-                    .ptr_type(AddressSpace::default())
-                    .into(),
-                "String" => self
-                    .context
-                    .i8_type()
-                    .ptr_type(AddressSpace::default())
-                    .into(),
-                _ => {
-                    return Err(CompileError::codegen_error(
-                        format!("Unsupported variable type: {}", type_ann.name),
-                        None,
-                    ));
+        // Determine the return type
+        let return_llvm_type: Option<BasicTypeEnum> = match return_type {
+            Some(type_ann) => {
+                if type_ann.name == "()" {
+                    None // void type
+                } else {
+                    Some(self.resolve_type_annotation(type_ann)?)
+                }
+            }
+            None => {
+                // Special case: main function should return i32 by default
+                if name == "main" {
+                    Some(self.context.i32_type().into())
+                } else {
+                    None // void type for other functions
                 }
             }
-        } else if let Some(_init) = initializer {
-            // Skip type inference - we'll determine type when we generate the expression
-            // Use a placeholder type to avoid early generation
-            self.context.i32_type().into()  // Default to i32, will be corrected later
-        } else {
-            // No type annotation and no initializer - error
-            return Err(CompileError::codegen_error(
-                format!(
-                    "Variable '{}' needs either a type annotation or an initializer",
-                    name
-                ),
-                None,
-            ));
         };
 
-        // Allocate space on the stack for the variable
-        let function = self
-            .builder
-            .get_insert_block()
-            .and_then(|block| block.get_parent())
-            .ok_or_else(|| {
-                CompileError::codegen_error(
-                    "Variable declaration outside of function context".to_string(),
-                    None,
-                )
-            })?;
+        // Determine parameter types
+        let mut param_types = Vec::new();
+        
+        // Special handling for main function with CLI arguments
+        if name == "main" && params.len() == 1 && params[0].type_annotation.name == "Vec<String>" {
+            // main(args: Vec<String>) -> i32 - Generate standard C main signature
+            param_types.push(self.context.i32_type().into()); // argc
+            param_types.push(self.context.i8_type().ptr_type(AddressSpace::default()).ptr_type(AddressSpace::default()).into()); // argv
+        } else {
+            for param in params {
+                let param_type = self.resolve_type_annotation(&param.type_annotation)?;
+                param_types.push(param_type.into());
+            }
+        }
 
-        let alloca = self.create_entry_block_alloca(function, name, var_type)?;
+        // Create the function type
+        let fn_type = match return_llvm_type {
+            Some(ret_type) => ret_type.fn_type(&param_types, false),
+            None => self.context.void_type().fn_type(&param_types, false),
+        };
 
-        // Store the initial value if provided
-        if let Some(init) = initializer {
-            let init_value = self.generate_expression(init)?;
+        // Check if function already exists
+        let function = if let Some(existing_function) = self.functions.get(name) {
+            *existing_function
+        } else {
+            // Create the function
+            let function = self.module.add_function(name, fn_type, None);
+            // Add the function to our function map
+            self.functions.insert(name.to_string(), function);
+            function
+        };
 
-            // Update the variable type and allocation based on the actual value type
-            // This eliminates the need for dual expression generation
-            let actual_type = init_value.get_type();
-            let final_alloca = if var_type != actual_type {
-                // Create new allocation with correct type
-                let function = self.builder.get_insert_block().unwrap().get_parent().unwrap();
-                self.create_entry_block_alloca(function, name, actual_type)?
-            } else {
-                alloca
-            };
+        // Create a new basic block for the function body only if it doesn't exist
+        let basic_block = if function.count_basic_blocks() == 0 {
+            self.context.append_basic_block(function, "entry")
+        } else {
+            function.get_first_basic_block().unwrap()
+        };
+        self.builder.position_at_end(basic_block);
 
-            // For array literals, we need to handle them specially
-            // Array literals return pointers to arrays, but we shouldn't double-allocate
-            if let BasicValueEnum::PointerValue(ptr_val) = init_value {
-                // Check if this is pointing to an array type
-                if let inkwell::types::AnyTypeEnum::ArrayType(_) = ptr_val.get_type().get_element_type() {
-                    // For arrays, store the pointer directly instead of creating another alloca level
-                    self.variables.insert(name.to_string(), ptr_val);
-                    return Ok(());
-                }
-            }
+        // Create variable allocations for parameters
+        let old_variables = self.variables.clone();
+        let old_constants = self.constants.clone();
+        self.variables.clear();
 
-            self.builder.build_store(final_alloca, init_value).map_err(|e| {
-                CompileError::codegen_error(format!("Failed to store variable: {:?}", e), None)
+        // Special handling for main function with CLI arguments
+        if name == "main" && params.len() == 1 && params[0].type_annotation.name == "Vec<String>" {
+            // For main(args: Vec<String>) -> i32, we get argc and argv
+            let argc = function.get_nth_param(0).ok_or_else(|| {
+                CompileError::codegen_error("Failed to get argc parameter".to_string(), None)
+            })?;
+            let argv = function.get_nth_param(1).ok_or_else(|| {
+                CompileError::codegen_error("Failed to get argv parameter".to_string(), None)
             })?;
 
-            // Update the variable mapping with the final allocation
-            self.variables.insert(name.to_string(), final_alloca);
-            return Ok(());
-        }
+            // Create alloca for the Vec<String> parameter (represented as argv)
+            let argv_alloca = self.create_entry_block_alloca(function, &params[0].name, argv.get_type())?;
+            self.builder.build_store(argv_alloca, argv).map_err(|e| {
+                CompileError::codegen_error(format!("Failed to store argv: {:?}", e), None)
+            })?;
 
-        // Add the variable to our map
-        self.variables.insert(name.to_string(), alloca);
+            // Also store argc as a hidden variable
+            let argc_alloca = self.create_entry_block_alloca(function, "__argc", argc.get_type())?;
+            self.builder.build_store(argc_alloca, argc).map_err(|e| {
+                CompileError::codegen_error(format!("Failed to store argc: {:?}", e), None)
+            })?;
 
-        Ok(())
-    }
+            // Add both to variable map
+            self.variables.insert(params[0].name.clone(), argv_alloca);
+            self.variables.insert("__argc".to_string(), argc_alloca);
+        } else {
+            for (i, param) in params.iter().enumerate() {
+                let param_value = function.get_nth_param(i as u32).ok_or_else(|| {
+                    CompileError::codegen_error(format!("Failed to get parameter {}", i), None)
+                })?;
 
-    /// Generates code for a return statement.
-    fn generate_return(&mut self, expr: &Option<Expr>) -> Result<()> {
-        match expr {
-            Some(e) => {
-                let return_value = self.generate_expression(e)?;
-                self.builder
-                    .build_return(Some(&return_value))
-                    .map_err(|e| {
-                        CompileError::codegen_error(
-                            format!("Failed to build return: {:?}", e),
-                            None,
-                        )
-                    })?;
-            }
-            None => {
-                self.builder.build_return(None).map_err(|e| {
-                    CompileError::codegen_error(format!("Failed to build return: {:?}", e), None)
+                // Allocate space on the stack for the parameter
+                let alloca =
+                    self.create_entry_block_alloca(function, &param.name, param_value.get_type())?;
+
+                // Store the parameter value
+                self.builder.build_store(alloca, param_value).map_err(|e| {
+                    CompileError::codegen_error(format!("Failed to store parameter: {:?}", e), None)
                 })?;
+
+                // Add the variable to our map
+                self.variables.insert(param.name.clone(), alloca);
             }
         }
 
-        Ok(())
-    }
-
-    /// Generates code for an expression.
-    fn generate_expression(&mut self, expr: &Expr) -> Result<BasicValueEnum<'ctx>> {
-        match expr {
-            Expr::Literal(lit) => self.generate_literal(lit),
-            Expr::Variable(name) => self.generate_variable_access(name),
-            Expr::Binary(left, op, right) => self.generate_binary_expression(left, op, right),
-            Expr::Unary(op, expr) => self.generate_unary_expression(op, expr),
-            Expr::Call(callee, args) => self.generate_function_call(callee, args),
-            Expr::Grouping(expr) => self.generate_expression(expr),
-            Expr::SIMD(simd_expr) => {
-                // Comprehensive SIMD expression code generation
-                self.generate_simd_expression(simd_expr)
-            }
-            // For now, we'll add placeholders for other expression types
-            Expr::Index(array_expr, index_expr) => {
-                self.generate_array_index(array_expr, index_expr)
-            }
-            Expr::Slice { array, start, end } => self.generate_array_slice(array, start, end),
-            Expr::FieldAccess(struct_expr, field_name) => {
-                self.generate_field_access(struct_expr, field_name)
-            }
-            Expr::StructLiteral { name, fields } => self.generate_struct_literal(name, fields),
-            Expr::EnumLiteral {
-                enum_name,
-                variant,
-                args,
-            } => self.generate_enum_literal(enum_name, variant, args),
-            Expr::Match { value, arms } => self.generate_match_expression(value, arms),
-            Expr::Block(statements) => self.generate_block_expression(statements),
-            Expr::If { condition, then_branch, else_branch } => {
-                self.generate_if_expression(condition, then_branch, else_branch)
-            }
-            Expr::Cast { expr, target_type, .. } => {
-                // Simple cast - for now just generate the expression
-                self.generate_expression(expr)
-            }
-            Expr::Tuple { elements, .. } => {
-                // Generate first element for now
-                if !elements.is_empty() {
-                    self.generate_expression(&elements[0])
-                } else {
-                    Ok(self.context.i32_type().const_int(0, false).into())
-                }
+        // Generate code for the function body
+        if let Stmt::Block(stmts, _) = &**body {
+            for stmt in stmts {
+                self.generate_statement(stmt)?;
             }
+        } else {
+            return Err(CompileError::codegen_error(
+                "Function body must be a block statement".to_string(),
+                None,
+            ));
         }
-    }
 
-    /// Generates code for a block expression
-    fn generate_block_expression(
-        &mut self,
-        statements: &Vec<Stmt>,
-    ) -> Result<BasicValueEnum<'ctx>> {
-        let mut last_value = None;
+        // Critical fix: Ensure all basic blocks have terminating instructions
+        // This is required for valid LLVM IR - every block must end with a terminator
+        let current_insert_block = self.builder.get_insert_block();
+        if let Some(function_val) = self.functions.get(name) {
+            let function_val = *function_val;
 
-        // Generate code for all statements in the block
-        for stmt in statements {
-            match stmt {
-                Stmt::Expression(expr) => {
-                    // For expression statements, we might want to use the value
-                    last_value = Some(self.generate_expression(expr)?);
-                }
-                _ => {
-                    // For other statements, just generate their code
-                    self.generate_statement(stmt)?;
+            for basic_block in function_val.get_basic_blocks() {
+                if !self.block_has_terminator(basic_block)
+                    && Some(basic_block) != current_insert_block
+                {
+                    self.builder.position_at_end(basic_block);
+                    // Add unreachable instruction for blocks that don't have terminators
+                    // This satisfies LLVM IR requirements while allowing optimization passes
+                    // to remove unreachable code if needed
+                    self.builder.build_unreachable().map_err(|e| {
+                        CompileError::codegen_error(
+                            format!("Failed to build unreachable for block: {:?}", e),
+                            None,
+                        )
+                    })?;
                 }
             }
         }
 
-        // Return the last expression value, or unit if there was none
-        match last_value {
-            Some(value) => Ok(value),
-            None => {
-                // Return unit value (represented as i32 0 for now)
-                let int_type = self.context.i32_type();
-                let zero = int_type.const_int(0, false);
-                Ok(zero.into())
-            }
+        // Restore position to the current block
+        if let Some(current_block) = current_insert_block {
+            self.builder.position_at_end(current_block);
         }
-    }
 
-    /// Generates code for a literal value.
-    fn generate_literal(&mut self, literal: &Literal) -> Result<BasicValueEnum<'ctx>> {
-        match literal {
-            Literal::Integer(value) => {
-                let int_type = self.context.i32_type();
-                Ok(int_type.const_int(*value as u64, true).into())
-            }
-            Literal::TypedInteger { value, int_type } => {
-                use crate::ast::IntegerType;
-                match int_type {
-                    IntegerType::I8 => Ok(self.context.i8_type().const_int(*value as u64, true).into()),
-                    IntegerType::I16 => Ok(self.context.i16_type().const_int(*value as u64, true).into()),
-                    IntegerType::I32 => Ok(self.context.i32_type().const_int(*value as u64, true).into()),
-                    IntegerType::I64 => Ok(self.context.i64_type().const_int(*value as u64, true).into()),
-                    IntegerType::U8 => Ok(self.context.i8_type().const_int(*value as u64, false).into()),
-                    IntegerType::U16 => Ok(self.context.i16_type().const_int(*value as u64, false).into()),
-                    IntegerType::U32 => Ok(self.context.i32_type().const_int(*value as u64, false).into()),
-                    IntegerType::U64 => Ok(self.context.i64_type().const_int(*value as u64, false).into()),
-                }
-            }
-            Literal::Float(value) => {
-                let float_type = self.context.f32_type();
-                Ok(float_type.const_float(*value).into())
-            }
-            Literal::String(value) => {
-                // Create a global string constant
-                let string_value = self
-                    .builder
-                    .build_global_string_ptr(value, "string_literal")
-                    .map_err(|e| {
+        // Add a return instruction if the current function block doesn't have one already
+        if let Some(current_block) = self.builder.get_insert_block() {
+            if !self.block_has_terminator(current_block) {
+                if return_llvm_type.is_none() {
+                    // Add a void return
+                    self.builder.build_return(None).map_err(|e| {
                         CompileError::codegen_error(
-                            format!("Failed to build string: {:?}", e),
+                            format!("Failed to build return: {:?}", e),
+                            None,
+                        )
+                    })?;
+                } else if name == "main" && return_llvm_type.is_some() {
+                    // Main function should return 0 by default
+                    let zero = self.context.i32_type().const_int(0, false);
+                    self.builder.build_return(Some(&zero)).map_err(|e| {
+                        CompileError::codegen_error(
+                            format!("Failed to build return: {:?}", e),
                             None,
                         )
                     })?;
-                Ok(string_value.as_pointer_value().into())
-            }
-            Literal::Boolean(value) => {
-                let bool_type = self.context.bool_type();
-                Ok(bool_type.const_int(*value as u64, false).into())
-            }
-            Literal::Vector {
-                elements,
-                vector_type,
-            } => {
-                if let Some(vtype) = vector_type {
-                    // SIMD vector literal with type annotation
-                    let element_exprs: Vec<Expr> = elements
-                        .iter()
-                        .map(|lit| Expr::Literal(lit.clone()))
-                        .collect();
-
-                    let vector_val = self.generate_simd_vector_literal(&element_exprs, vtype)?;
-                    Ok(vector_val.into())
                 } else {
-                    // Regular array literal without SIMD type annotation
-                    self.generate_array_literal(elements)
+                    // This is an error - function should have a return value
+                    return Err(CompileError::codegen_error(
+                        format!("Function '{}' must return a value", name),
+                        None,
+                    ));
                 }
             }
         }
+
+        // Restore the previous variable map and drop the function's local constants
+        self.variables = old_variables;
+        self.constants = old_constants;
+
+        Ok(())
     }
 
-    /// Generates code for an array literal without SIMD type annotation.
-    fn generate_array_literal(&mut self, elements: &Vec<Literal>) -> Result<BasicValueEnum<'ctx>> {
-        if elements.is_empty() {
-            return Err(CompileError::codegen_error(
-                "Empty array literals are not yet supported".to_string(),
-                None,
-            ));
-        }
+    /// Generates code for a function declaration with attributes.
+    fn generate_function_declaration_with_attributes(
+        &mut self,
+        name: &str,
+        params: &[crate::ast::Parameter],
+        return_type: &Option<TypeAnnotation>,
+        body: &Box<Stmt>,
+        attributes: &[crate::ast::Attribute],
+    ) -> Result<()> {
+        // Process @optimize attributes for enhanced code generation
+        let optimization_config = self.parse_optimize_attributes(attributes);
 
-        // Generate the first element to determine the array type
-        // Use the same non-recursive logic to avoid infinite loops
-        let first_element: BasicValueEnum<'ctx> = match &elements[0] {
-            Literal::Integer(val) => {
-                let int_type = self.context.i32_type();
-                int_type.const_int(*val as u64, true).into()
-            }
-            Literal::TypedInteger { value, int_type } => {
-                use crate::ast::IntegerType;
-                match int_type {
-                    IntegerType::I8 => self.context.i8_type().const_int(*value as u64, true).into(),
-                    IntegerType::I16 => self.context.i16_type().const_int(*value as u64, true).into(),
-                    IntegerType::I32 => self.context.i32_type().const_int(*value as u64, true).into(),
-                    IntegerType::I64 => self.context.i64_type().const_int(*value as u64, true).into(),
-                    IntegerType::U8 => self.context.i8_type().const_int(*value as u64, false).into(),
-                    IntegerType::U16 => self.context.i16_type().const_int(*value as u64, false).into(),
-                    IntegerType::U32 => self.context.i32_type().const_int(*value as u64, false).into(),
-                    IntegerType::U64 => self.context.i64_type().const_int(*value as u64, false).into(),
-                }
-            }
-            Literal::Float(val) => {
-                let float_type = self.context.f32_type();
-                float_type.const_float(*val).into()
-            }
-            Literal::String(val) => {
-                let string_value = self
-                    .builder
-                    .build_global_string_ptr(val, "string_literal")
-                    .map_err(|e| {
-                        CompileError::codegen_error(
-                            format!("Failed to build string: {:?}", e),
-                            None,
-                        )
-                    })?;
-                string_value.as_pointer_value().into()
-            }
-            Literal::Boolean(val) => {
-                let bool_type = self.context.bool_type();
-                bool_type.const_int(*val as u64, false).into()
-            }
-            Literal::Vector { .. } => {
-                return Err(CompileError::codegen_error(
-                    "Nested array literals are not yet supported".to_string(),
-                    None,
-                ));
-            }
+        // Functions of imported modules are emitted under their mangled name,
+        // while calls inside the module keep using the source name. Exported
+        // functions are emitted under their stable C symbol instead.
+        let exported_symbol = crate::ast::exported_symbol(attributes, name);
+        let symbol_name = match &exported_symbol {
+            Some(symbol) => {
+                self.exported_symbols
+                    .insert(self.function_symbol_name(name), symbol.clone());
+                symbol.clone()
+            }
+            None => self.function_symbol_name(name),
         };
-        let element_type = first_element.get_type();
-        let array_size = elements.len() as u32;
+        if symbol_name != name {
+            self.symbol_aliases
+                .insert(name.to_string(), symbol_name.clone());
+        }
 
-        // Create an array type
-        let array_type = element_type.array_type(array_size);
+        if let Some(levels) = crate::ast::target_clones(attributes) {
+            self.generate_target_clones(
+                &symbol_name,
+                params,
+                return_type,
+                body,
+                &optimization_config,
+                &levels,
+            )?;
+        } else {
+            // Generate the function with optimization config
+            self.generate_optimized_function_declaration(
+                &symbol_name,
+                params,
+                return_type,
+                body,
+                &optimization_config,
+            )?;
+        }
 
-        // Allocate space for the array
-        let array_alloca = self
-            .builder
-            .build_alloca(array_type, "array_literal")
-            .map_err(|e| {
-                CompileError::codegen_error(format!("Failed to allocate array: {:?}", e), None)
-            })?;
+        if exported_symbol.is_some() {
+            self.mark_c_bools(&symbol_name)?;
+        }
+        Ok(())
+    }
 
-        // Initialize each element
-        for (i, element_literal) in elements.iter().enumerate() {
-            // For array literals, we should only have primitive literals, not nested arrays
-            let element_value: BasicValueEnum<'ctx> = match element_literal {
-                Literal::Integer(val) => {
-                    let int_type = self.context.i32_type();
-                    int_type.const_int(*val as u64, true).into()
-                }
-                Literal::TypedInteger { value, int_type } => {
-                    use crate::ast::IntegerType;
-                    match int_type {
-                        IntegerType::I8 => self.context.i8_type().const_int(*value as u64, true).into(),
-                        IntegerType::I16 => self.context.i16_type().const_int(*value as u64, true).into(),
-                        IntegerType::I32 => self.context.i32_type().const_int(*value as u64, true).into(),
-                        IntegerType::I64 => self.context.i64_type().const_int(*value as u64, true).into(),
-                        IntegerType::U8 => self.context.i8_type().const_int(*value as u64, false).into(),
-                        IntegerType::U16 => self.context.i16_type().const_int(*value as u64, false).into(),
-                        IntegerType::U32 => self.context.i32_type().const_int(*value as u64, false).into(),
-                        IntegerType::U64 => self.context.i64_type().const_int(*value as u64, false).into(),
-                    }
-                }
-                Literal::Float(val) => {
-                    let float_type = self.context.f32_type();
-                    float_type.const_float(*val).into()
-                }
-                Literal::String(val) => {
-                    let string_value = self
-                        .builder
-                        .build_global_string_ptr(val, "string_literal")
-                        .map_err(|e| {
-                            CompileError::codegen_error(
-                                format!("Failed to build string: {:?}", e),
-                                None,
-                            )
-                        })?;
-                    string_value.as_pointer_value().into()
-                }
-                Literal::Boolean(val) => {
-                    let bool_type = self.context.bool_type();
-                    bool_type.const_int(*val as u64, false).into()
-                }
-                Literal::Vector { .. } => {
-                    return Err(CompileError::codegen_error(
-                        "Nested array literals are not yet supported".to_string(),
-                        None,
-                    ));
-                }
-            };
+    /// Marks the `bool` parameters and result of an exported function `zeroext`.
+    /// C passes a `bool` as a whole byte or register holding 0 or 1, while LLVM
+    /// only defines the low bit of an `i1`.
+    fn mark_c_bools(&self, symbol: &str) -> Result<()> {
+        let function = *self.functions.get(symbol).ok_or_else(|| {
+            CompileError::codegen_error(format!("Function {} was not generated", symbol), None)
+        })?;
+        let zeroext = self.context.create_enum_attribute(
+            inkwell::attributes::Attribute::get_named_enum_kind_id("zeroext"),
+            0,
+        );
+        let is_bool = |ty: BasicTypeEnum<'ctx>| matches!(ty, BasicTypeEnum::IntType(int_type) if int_type.get_bit_width() == 1);
 
-            // Verify all elements have the same type
-            if element_value.get_type() != element_type {
-                return Err(CompileError::codegen_error(
-                    "All array elements must have the same type".to_string(),
-                    None,
-                ));
+        for (index, param) in function.get_params().into_iter().enumerate() {
+            if is_bool(param.get_type()) {
+                function.add_attribute(
+                    inkwell::attributes::AttributeLoc::Param(index as u32),
+                    zeroext,
+                );
             }
+        }
+        if function.get_type().get_return_type().is_some_and(is_bool) {
+            function.add_attribute(inkwell::attributes::AttributeLoc::Return, zeroext);
+        }
+        Ok(())
+    }
 
-            // Calculate element pointer using GEP
-            let element_ptr = unsafe {
-                self.builder
-                    .build_gep(
-                        array_alloca,
-                        &[
-                            self.context.i32_type().const_zero(),
-                            self.context.i32_type().const_int(i as u64, false),
-                        ],
-                        &format!("array_element_{}", i),
-                    )
-                    .map_err(|e| {
-                        CompileError::codegen_error(
-                            format!("Failed to build GEP for array element: {:?}", e),
-                            None,
-                        )
-                    })?
-            };
-
-            // Store the element value
-            self.builder
-                .build_store(element_ptr, element_value)
-                .map_err(|e| {
-                    CompileError::codegen_error(
-                        format!("Failed to store array element: {:?}", e),
-                        None,
-                    )
-                })?;
+    /// Generates a `@target_clones(avx512, avx2, sse4)` function as one clone per
+    /// feature level, compiled with that level's `target-features`, plus a
+    /// `.default` clone for CPUs that have none of them. The function itself
+    /// becomes a dispatcher that picks the best clone with CPUID on its first
+    /// call and jumps straight to it afterwards.
+    fn generate_target_clones(
+        &mut self,
+        name: &str,
+        params: &[crate::ast::Parameter],
+        return_type: &Option<TypeAnnotation>,
+        body: &Box<Stmt>,
+        config: &OptimizationConfig,
+        levels: &[String],
+    ) -> Result<()> {
+        // Declare the dispatcher first, so the clones can call the function recursively
+        let mut param_types = Vec::new();
+        for param in params {
+            param_types.push(self.resolve_type_annotation(&param.type_annotation)?.into());
+        }
+        let fn_type = match return_type {
+            Some(type_ann) if type_ann.name != "()" => self
+                .resolve_type_annotation(type_ann)?
+                .fn_type(&param_types, false),
+            _ => self.context.void_type().fn_type(&param_types, false),
+        };
+        let dispatcher = self.module.add_function(name, fn_type, None);
+        self.functions.insert(name.to_string(), dispatcher);
+
+        // Best level first, which is the order the resolver tries them in
+        let mut clones = Vec::new();
+        for level in TARGET_CLONE_LEVELS {
+            if !levels.iter().any(|listed| listed == level) {
+                continue;
+            }
+            // Levels were validated by the type checker
+            let features = target_clone_features(level).unwrap_or_default();
+            let clone = self.generate_target_clone(
+                &format!("{}.{}", name, level),
+                params,
+                return_type,
+                body,
+                config,
+                Some(llvm_target_features(&features)),
+            )?;
+            clones.push((clone, features));
         }
+        let default_clone = self.generate_target_clone(
+            &format!("{}.default", name),
+            params,
+            return_type,
+            body,
+            config,
+            None,
+        )?;
 
-        // Return the array as a pointer (this is how arrays are typically handled)
-        Ok(array_alloca.into())
+        let resolver = self.generate_clone_resolver(name, &clones, default_clone)?;
+        self.generate_clone_dispatcher(name, dispatcher, resolver)
     }
 
-    /// Generates code for a variable access.
-    fn generate_variable_access(&self, name: &str) -> Result<BasicValueEnum<'ctx>> {
-        if let Some(&ptr) = self.variables.get(name) {
-            // Check if this pointer is directly pointing to an array
-            // (arrays are stored as direct pointers, not pointer-to-pointer)
-            if let inkwell::types::AnyTypeEnum::ArrayType(_) = ptr.get_type().get_element_type() {
-                // For arrays, return the pointer directly (no load needed)
-                Ok(ptr.into())
-            } else {
-                // For regular variables, load the value
-                let load = self.builder.build_load(ptr, name).map_err(|e| {
-                    CompileError::codegen_error(format!("Failed to load variable: {:?}", e), None)
-                })?;
-                Ok(load)
-            }
-        } else {
-            Err(CompileError::codegen_error(
-                format!("Variable '{}' not found", name),
-                None,
-            ))
+    /// Generates one clone of a `@target_clones` function, compiled for
+    /// `target_features` or, given `None`, for the baseline target.
+    fn generate_target_clone(
+        &mut self,
+        clone_name: &str,
+        params: &[crate::ast::Parameter],
+        return_type: &Option<TypeAnnotation>,
+        body: &Box<Stmt>,
+        config: &OptimizationConfig,
+        target_features: Option<String>,
+    ) -> Result<FunctionValue<'ctx>> {
+        self.generate_optimized_function_declaration(
+            clone_name,
+            params,
+            return_type,
+            body,
+            config,
+        )?;
+        let clone = *self.functions.get(clone_name).ok_or_else(|| {
+            CompileError::codegen_error(format!("Clone {} was not generated", clone_name), None)
+        })?;
+
+        // SIMD optimization may have marked the clone for AVX2, but each clone
+        // must only use the features the resolver checked for
+        clone.remove_string_attribute(
+            inkwell::attributes::AttributeLoc::Function,
+            "target-features",
+        );
+        if let Some(target_features) = target_features {
+            let attribute = self
+                .context
+                .create_string_attribute("target-features", &target_features);
+            clone.add_attribute(inkwell::attributes::AttributeLoc::Function, attribute);
         }
+        clone.set_linkage(Linkage::Internal);
+        Ok(clone)
     }
 
-    /// Generates code for a binary expression.
-    fn generate_binary_expression(
+    /// Generates `{name}.resolver`, which returns the first of `clones` whose
+    /// features the CPU supports, or `default_clone` if there is none.
+    fn generate_clone_resolver(
         &mut self,
-        left: &Box<Expr>,
-        op: &BinaryOp,
-        right: &Box<Expr>,
-    ) -> Result<BasicValueEnum<'ctx>> {
-        // Handle assignment operations FIRST, before evaluating operands
-        match op {
-            BinaryOp::Assign => {
-                // Special case: handle assignment without evaluating left side as value
-                if let Expr::Variable(var_name) = &**left {
-                    let right_value = self.generate_expression(right)?;
-                    if let Some(&var_ptr) = self.variables.get(var_name) {
-                        self.builder
-                            .build_store(var_ptr, right_value)
-                            .map_err(|e| {
-                                CompileError::codegen_error(
-                                    format!("Failed to store assignment: {:?}", e),
-                                    None,
-                                )
-                            })?;
-                        Ok(right_value) // Return the assigned value
-                    } else {
-                        Err(CompileError::codegen_error(
-                            format!("Variable '{}' not found for assignment", var_name),
-                            None,
-                        ))
-                    }
-                } else {
-                    Err(CompileError::codegen_error(
-                        "Left side of assignment must be a variable".to_string(),
-                        None,
-                    ))
-                }
-            }
-            BinaryOp::PlusAssign => {
-                if let Expr::Variable(var_name) = &**left {
-                    let current_val = self.generate_variable_access(var_name)?;
-                    let right_val = self.generate_expression(right)?;
-
-                    // Handle both integer and float addition
-                    let sum = if let (
-                        BasicValueEnum::IntValue(curr_int),
-                        BasicValueEnum::IntValue(right_int),
-                    ) = (current_val, right_val)
-                    {
-                        self.builder
-                            .build_int_add(curr_int, right_int, "add_assign")
-                            .map(|v| v.into())
-                            .map_err(|e| {
-                                CompileError::codegen_error(
-                                    format!("Failed to build add_assign: {:?}", e),
-                                    None,
-                                )
-                            })?
-                    } else if let (
-                        BasicValueEnum::FloatValue(curr_float),
-                        BasicValueEnum::FloatValue(right_float),
-                    ) = (current_val, right_val)
-                    {
-                        self.builder
-                            .build_float_add(curr_float, right_float, "fadd_assign")
-                            .map(|v| v.into())
-                            .map_err(|e| {
-                                CompileError::codegen_error(
-                                    format!("Failed to build fadd_assign: {:?}", e),
-                                    None,
-                                )
-                            })?
-                    } else {
-                        return Err(CompileError::codegen_error(
-                            "Type mismatch in += operation".to_string(),
-                            None,
-                        ));
-                    };
+        name: &str,
+        clones: &[(FunctionValue<'ctx>, Vec<SIMDFeature>)],
+        default_clone: FunctionValue<'ctx>,
+    ) -> Result<FunctionValue<'ctx>> {
+        let build_error = |e: inkwell::builder::BuilderError| {
+            CompileError::codegen_error(format!("Failed to build clone resolver: {:?}", e), None)
+        };
+        let i32_type = self.context.i32_type();
+        let clone_ptr_type = default_clone.get_type().ptr_type(AddressSpace::default());
+        let resolver = self.module.add_function(
+            &format!("{}.resolver", name),
+            clone_ptr_type.fn_type(&[], false),
+            Some(Linkage::Internal),
+        );
+        let cpu_supports = self.get_or_generate_cpu_supports()?;
 
-                    if let Some(&var_ptr) = self.variables.get(var_name) {
-                        self.builder.build_store(var_ptr, sum).map_err(|e| {
+        let mut block = self.context.append_basic_block(resolver, "entry");
+        for (clone, features) in clones {
+            self.builder.position_at_end(block);
+            let mut supported = self.context.bool_type().const_all_ones();
+            for feature in features {
+                if let Some((leaf, register, bit)) = feature.cpuid_bit() {
+                    let args: Vec<BasicMetadataValueEnum> =
+                        [leaf, register, bit, feature.xcr0_mask()]
+                            .iter()
+                            .map(|value| i32_type.const_int(*value as u64, false).into())
+                            .collect();
+                    let has_feature = self
+                        .builder
+                        .build_call(cpu_supports, &args, "has_feature")
+                        .map_err(build_error)?
+                        .try_as_basic_value()
+                        .left()
+                        .ok_or_else(|| {
                             CompileError::codegen_error(
-                                format!("Failed to store add_assign: {:?}", e),
+                                "__ea_cpu_supports returned no value".to_string(),
                                 None,
                             )
-                        })?;
-                        Ok(sum)
-                    } else {
-                        Err(CompileError::codegen_error(
-                            format!("Variable '{}' not found for +=", var_name),
-                            None,
-                        ))
-                    }
-                } else {
-                    Err(CompileError::codegen_error(
-                        "Left side of += must be a variable".to_string(),
-                        None,
-                    ))
+                        })?
+                        .into_int_value();
+                    supported = self
+                        .builder
+                        .build_and(supported, has_feature, "supported")
+                        .map_err(build_error)?;
                 }
             }
-            BinaryOp::MinusAssign => {
-                if let Expr::Variable(var_name) = &**left {
-                    let current_val = self.generate_variable_access(var_name)?;
-                    let right_val = self.generate_expression(right)?;
 
-                    let diff = if let (
-                        BasicValueEnum::IntValue(curr_int),
-                        BasicValueEnum::IntValue(right_int),
-                    ) = (current_val, right_val)
-                    {
-                        self.builder
-                            .build_int_sub(curr_int, right_int, "sub_assign")
-                            .map(|v| v.into())
-                            .map_err(|e| {
-                                CompileError::codegen_error(
-                                    format!("Failed to build sub_assign: {:?}", e),
-                                    None,
-                                )
-                            })?
-                    } else if let (
-                        BasicValueEnum::FloatValue(curr_float),
-                        BasicValueEnum::FloatValue(right_float),
-                    ) = (current_val, right_val)
-                    {
-                        self.builder
-                            .build_float_sub(curr_float, right_float, "fsub_assign")
-                            .map(|v| v.into())
-                            .map_err(|e| {
-                                CompileError::codegen_error(
-                                    format!("Failed to build fsub_assign: {:?}", e),
-                                    None,
-                                )
-                            })?
-                    } else {
-                        return Err(CompileError::codegen_error(
-                            "Type mismatch in -= operation".to_string(),
-                            None,
-                        ));
-                    };
+            let selected_block = self.context.append_basic_block(resolver, "selected");
+            let next_block = self.context.append_basic_block(resolver, "next");
+            self.builder
+                .build_conditional_branch(supported, selected_block, next_block)
+                .map_err(build_error)?;
+            self.builder.position_at_end(selected_block);
+            self.builder
+                .build_return(Some(&clone.as_global_value().as_pointer_value()))
+                .map_err(build_error)?;
+            block = next_block;
+        }
 
-                    if let Some(&var_ptr) = self.variables.get(var_name) {
-                        self.builder.build_store(var_ptr, diff).map_err(|e| {
-                            CompileError::codegen_error(
-                                format!("Failed to store sub_assign: {:?}", e),
-                                None,
-                            )
-                        })?;
-                        Ok(diff)
-                    } else {
-                        Err(CompileError::codegen_error(
-                            format!("Variable '{}' not found for -=", var_name),
-                            None,
-                        ))
-                    }
-                } else {
-                    Err(CompileError::codegen_error(
-                        "Left side of -= must be a variable".to_string(),
-                        None,
-                    ))
-                }
-            }
-            BinaryOp::MultiplyAssign => {
-                if let Expr::Variable(var_name) = &**left {
-                    let current_val = self.generate_variable_access(var_name)?;
-                    let right_val = self.generate_expression(right)?;
+        self.builder.position_at_end(block);
+        self.builder
+            .build_return(Some(&default_clone.as_global_value().as_pointer_value()))
+            .map_err(build_error)?;
+        Ok(resolver)
+    }
 
-                    let product = if let (
-                        BasicValueEnum::IntValue(curr_int),
-                        BasicValueEnum::IntValue(right_int),
-                    ) = (current_val, right_val)
-                    {
-                        self.builder
-                            .build_int_mul(curr_int, right_int, "mul_assign")
-                            .map(|v| v.into())
-                            .map_err(|e| {
-                                CompileError::codegen_error(
-                                    format!("Failed to build mul_assign: {:?}", e),
-                                    None,
-                                )
-                            })?
-                    } else if let (
-                        BasicValueEnum::FloatValue(curr_float),
-                        BasicValueEnum::FloatValue(right_float),
-                    ) = (current_val, right_val)
-                    {
-                        self.builder
-                            .build_float_mul(curr_float, right_float, "fmul_assign")
-                            .map(|v| v.into())
-                            .map_err(|e| {
-                                CompileError::codegen_error(
-                                    format!("Failed to build fmul_assign: {:?}", e),
-                                    None,
-                                )
-                            })?
-                    } else {
-                        return Err(CompileError::codegen_error(
-                            "Type mismatch in *= operation".to_string(),
-                            None,
-                        ));
-                    };
+    /// Fills in the body of a `@target_clones` dispatcher: it calls the resolver
+    /// once, caches the chosen clone in `{name}.resolved`, and tail-calls the clone.
+    fn generate_clone_dispatcher(
+        &mut self,
+        name: &str,
+        dispatcher: FunctionValue<'ctx>,
+        resolver: FunctionValue<'ctx>,
+    ) -> Result<()> {
+        let build_error = |e: inkwell::builder::BuilderError| {
+            CompileError::codegen_error(format!("Failed to build clone dispatcher: {:?}", e), None)
+        };
+        let clone_type = dispatcher.get_type();
+        let clone_ptr_type = clone_type.ptr_type(AddressSpace::default());
 
-                    if let Some(&var_ptr) = self.variables.get(var_name) {
-                        self.builder.build_store(var_ptr, product).map_err(|e| {
-                            CompileError::codegen_error(
-                                format!("Failed to store mul_assign: {:?}", e),
-                                None,
-                            )
-                        })?;
-                        Ok(product)
-                    } else {
-                        Err(CompileError::codegen_error(
-                            format!("Variable '{}' not found for *=", var_name),
-                            None,
-                        ))
-                    }
-                } else {
-                    Err(CompileError::codegen_error(
-                        "Left side of *= must be a variable".to_string(),
-                        None,
-                    ))
-                }
-            }
-            BinaryOp::DivideAssign => {
-                if let Expr::Variable(var_name) = &**left {
-                    let current_val = self.generate_variable_access(var_name)?;
-                    let right_val = self.generate_expression(right)?;
+        let resolved = self.module.add_global(
+            clone_ptr_type,
+            Some(AddressSpace::default()),
+            &format!("{}.resolved", name),
+        );
+        resolved.set_linkage(Linkage::Internal);
+        resolved.set_initializer(&clone_ptr_type.const_null());
 
-                    let quotient = if let (
-                        BasicValueEnum::IntValue(curr_int),
-                        BasicValueEnum::IntValue(right_int),
-                    ) = (current_val, right_val)
-                    {
-                        self.builder
-                            .build_int_signed_div(curr_int, right_int, "div_assign")
-                            .map(|v| v.into())
-                            .map_err(|e| {
-                                CompileError::codegen_error(
-                                    format!("Failed to build div_assign: {:?}", e),
-                                    None,
-                                )
-                            })?
-                    } else if let (
-                        BasicValueEnum::FloatValue(curr_float),
-                        BasicValueEnum::FloatValue(right_float),
-                    ) = (current_val, right_val)
-                    {
-                        self.builder
-                            .build_float_div(curr_float, right_float, "fdiv_assign")
-                            .map(|v| v.into())
-                            .map_err(|e| {
-                                CompileError::codegen_error(
-                                    format!("Failed to build fdiv_assign: {:?}", e),
-                                    None,
-                                )
-                            })?
-                    } else {
-                        return Err(CompileError::codegen_error(
-                            "Type mismatch in /= operation".to_string(),
-                            None,
-                        ));
-                    };
+        let entry_block = self.context.append_basic_block(dispatcher, "entry");
+        let resolve_block = self.context.append_basic_block(dispatcher, "resolve");
+        let call_block = self.context.append_basic_block(dispatcher, "call");
 
-                    if let Some(&var_ptr) = self.variables.get(var_name) {
-                        self.builder.build_store(var_ptr, quotient).map_err(|e| {
-                            CompileError::codegen_error(
-                                format!("Failed to store div_assign: {:?}", e),
-                                None,
-                            )
-                        })?;
-                        Ok(quotient)
-                    } else {
-                        Err(CompileError::codegen_error(
-                            format!("Variable '{}' not found for /=", var_name),
-                            None,
-                        ))
-                    }
-                } else {
-                    Err(CompileError::codegen_error(
-                        "Left side of /= must be a variable".to_string(),
-                        None,
-                    ))
-                }
-            }
-            _ => {
-                // For all other operations, evaluate both operands first
-                let left_value = self.generate_expression(left)?;
-                let right_value = self.generate_expression(right)?;
+        // Threads racing on the first call all store the same clone, so a
+        // relaxed atomic is enough to avoid torn reads
+        self.builder.position_at_end(entry_block);
+        let cached = self
+            .builder
+            .build_load(resolved.as_pointer_value(), "cached")
+            .map_err(build_error)?
+            .into_pointer_value();
+        if let Some(load) = cached.as_instruction_value() {
+            load.set_atomic_ordering(inkwell::AtomicOrdering::Monotonic)
+                .and_then(|_| load.set_alignment(8))
+                .map_err(|e| {
+                    CompileError::codegen_error(format!("Failed to cache clone: {}", e), None)
+                })?;
+        }
+        let is_unresolved = self
+            .builder
+            .build_is_null(cached, "is_unresolved")
+            .map_err(build_error)?;
+        self.builder
+            .build_conditional_branch(is_unresolved, resolve_block, call_block)
+            .map_err(build_error)?;
 
-                // Handle String concatenation (+ operator)
-                if let (
-                    BasicValueEnum::PointerValue(left_ptr),
-                    BasicValueEnum::PointerValue(right_ptr),
-                ) = (left_value, right_value)
-                {
-                    if matches!(op, BinaryOp::Add) {
-                        // String concatenation: call string_concat function  
-                        // String concatenation: call string_concat function
-                        if let Some(&string_concat_function) = self.functions.get("string_concat") {
-                            let result = self
-                                .builder
-                                .build_call(
-                                    string_concat_function,
-                                    &[left_ptr.into(), right_ptr.into()],
-                                    "string_concat",
-                                )
-                                .map_err(|e| {
-                                    CompileError::codegen_error(
-                                        format!("Failed to build string concat: {:?}", e),
-                                        None,
-                                    )
-                                })?;
-                            if let Some(return_value) = result.try_as_basic_value().left() {
-                                return Ok(return_value);
-                            }
-                        } else {
-                            eprintln!("❌ string_concat function not found, creating fallback implementation");
-                            // Fallback: Create string_concat function using LLVM IR
-                            let string_type = self.context.i8_type().ptr_type(AddressSpace::default());
-                            let string_concat_type = string_type.fn_type(&[string_type.into(), string_type.into()], false);
-                            let string_concat_function = self.module.add_function("string_concat_fallback", string_concat_type, None);
-                            
-                            // Get malloc and strcpy functions
-                            let malloc_function = *self.functions.get("malloc").expect("malloc should be available");
-                            let strlen_function = *self.functions.get("strlen").expect("strlen should be available");
-                            
-                            // Get the current function and create basic blocks
-                            let current_function = self.builder.get_insert_block().unwrap().get_parent().unwrap();
-                            let entry_block = self.context.append_basic_block(string_concat_function, "entry");
-                            let old_block = self.builder.get_insert_block().unwrap();
-                            
-                            // Build the string concatenation function
-                            self.builder.position_at_end(entry_block);
-                            let left_param = string_concat_function.get_first_param().unwrap().into_pointer_value();
-                            let right_param = string_concat_function.get_nth_param(1).unwrap().into_pointer_value();
-                            
-                            // Get lengths
-                            let left_len = self.builder.build_call(strlen_function, &[left_param.into()], "left_len").unwrap().try_as_basic_value().left().unwrap().into_int_value();
-                            let right_len = self.builder.build_call(strlen_function, &[right_param.into()], "right_len").unwrap().try_as_basic_value().left().unwrap().into_int_value();
-                            let total_len = self.builder.build_int_add(left_len, right_len, "total_len").unwrap();
-                            let total_len_plus_null = self.builder.build_int_add(total_len, self.context.i32_type().const_int(1, false), "total_len_plus_null").unwrap();
-                            
-                            // Allocate memory
-                            let result_ptr = self.builder.build_call(malloc_function, &[total_len_plus_null.into()], "concat_result").unwrap().try_as_basic_value().left().unwrap().into_pointer_value();
-                            
-                            // Copy left string
-                            if let Some(strcpy_function) = self.functions.get("strcpy") {
-                                self.builder.build_call(*strcpy_function, &[result_ptr.into(), left_param.into()], "copy_left").unwrap();
-                                
-                                // Concatenate right string using strcat
-                                if let Some(strcat_function) = self.functions.get("strcat") {
-                                    self.builder.build_call(*strcat_function, &[result_ptr.into(), right_param.into()], "concat_right").unwrap();
-                                }
-                            }
-                            
-                            self.builder.build_return(Some(&result_ptr)).unwrap();
-                            
-                            // Return to the original block and call the fallback function
-                            self.builder.position_at_end(old_block);
-                            let result = self.builder.build_call(string_concat_function, &[left_ptr.into(), right_ptr.into()], "string_concat_fallback").unwrap();
-                            if let Some(return_value) = result.try_as_basic_value().left() {
-                                return Ok(return_value);
-                            }
-                        }
-                    } else if matches!(op, BinaryOp::Equal) {
-                        // String comparison: call string_equals function
-                        if let Some(&string_equals_function) = self.functions.get("string_equals_char") {
-                            let result = self
-                                .builder
-                                .build_call(
-                                    string_equals_function,
-                                    &[left_ptr.into(), right_ptr.into()],
-                                    "string_equals_char",
-                                )
-                                .map_err(|e| {
-                                    CompileError::codegen_error(
-                                        format!("Failed to build string equals: {:?}", e),
-                                        None,
-                                    )
-                                })?;
-                            if let Some(return_value) = result.try_as_basic_value().left() {
-                                return Ok(return_value);
-                            } else {
-                                eprintln!("❌ string_equals_char call failed to return basic value");
+        self.builder.position_at_end(resolve_block);
+        let found = self
+            .builder
+            .build_call(resolver, &[], "found")
+            .map_err(build_error)?
+            .try_as_basic_value()
+            .left()
+            .ok_or_else(|| {
+                CompileError::codegen_error("Clone resolver returned no value".to_string(), None)
+            })?
+            .into_pointer_value();
+        let store = self
+            .builder
+            .build_store(resolved.as_pointer_value(), found)
+            .map_err(build_error)?;
+        store
+            .set_atomic_ordering(inkwell::AtomicOrdering::Monotonic)
+            .and_then(|_| store.set_alignment(8))
+            .map_err(|e| {
+                CompileError::codegen_error(format!("Failed to cache clone: {}", e), None)
+            })?;
+        self.builder
+            .build_unconditional_branch(call_block)
+            .map_err(build_error)?;
+
+        self.builder.position_at_end(call_block);
+        let clone = self
+            .builder
+            .build_phi(clone_ptr_type, "clone")
+            .map_err(build_error)?;
+        clone.add_incoming(&[(&cached, entry_block), (&found, resolve_block)]);
+        let args: Vec<BasicMetadataValueEnum> = dispatcher
+            .get_param_iter()
+            .map(|param| param.into())
+            .collect();
+        let call = self
+            .builder
+            .build_call(
+                Self::function_pointer(clone.as_basic_value().into_pointer_value())?,
+                &args,
+                "result",
+            )
+            .map_err(build_error)?;
+        call.set_tail_call(true);
+        match call.try_as_basic_value().left() {
+            Some(result) => self.builder.build_return(Some(&result)),
+            None => self.builder.build_return(None),
+        }
+        .map_err(build_error)?;
+        Ok(())
+    }
+
+    /// Returns `__ea_cpu_supports(leaf, register, bit, xcr0_mask) -> bool`,
+    /// generating it on first use. It reports whether CPUID `leaf` sets `bit`
+    /// of `register` (eax = 0 to edx = 3) and, for a non-zero `xcr0_mask`,
+    /// whether the operating system saves the register state the feature needs.
+    fn get_or_generate_cpu_supports(&mut self) -> Result<FunctionValue<'ctx>> {
+        const NAME: &str = "__ea_cpu_supports";
+        if let Some(function) = self.functions.get(NAME) {
+            return Ok(*function);
+        }
+        let build_error = |e: inkwell::builder::BuilderError| {
+            CompileError::codegen_error(format!("Failed to build {}: {:?}", NAME, e), None)
+        };
+        let i32_type = self.context.i32_type();
+        let bool_type = self.context.bool_type();
+        let fn_type = bool_type.fn_type(
+            &[
+                i32_type.into(),
+                i32_type.into(),
+                i32_type.into(),
+                i32_type.into(),
+            ],
+            false,
+        );
+        let function = self
+            .module
+            .add_function(NAME, fn_type, Some(Linkage::Internal));
+        self.functions.insert(NAME.to_string(), function);
+        let param = |index: u32| function.get_nth_param(index).unwrap().into_int_value();
+        let (leaf, register, bit, xcr0_mask) = (param(0), param(1), param(2), param(3));
+
+        let entry_block = self.context.append_basic_block(function, "entry");
+        let query_block = self.context.append_basic_block(function, "query");
+        let check_os_block = self.context.append_basic_block(function, "check_os");
+        let os_state_block = self.context.append_basic_block(function, "os_state");
+        let xgetbv_block = self.context.append_basic_block(function, "xgetbv");
+        let supported_block = self.context.append_basic_block(function, "supported");
+        let unsupported_block = self.context.append_basic_block(function, "unsupported");
+        let zero = i32_type.const_zero();
+        let one = i32_type.const_int(1, false);
+
+        // Leaf 0 reports the highest leaf the CPU answers
+        self.builder.position_at_end(entry_block);
+        let [max_leaf, ..] = self.build_cpuid(zero)?;
+        let has_leaf = self
+            .builder
+            .build_int_compare(IntPredicate::ULE, leaf, max_leaf, "has_leaf")
+            .map_err(build_error)?;
+        self.builder
+            .build_conditional_branch(has_leaf, query_block, unsupported_block)
+            .map_err(build_error)?;
+
+        self.builder.position_at_end(query_block);
+        let [eax, ebx, ecx, edx] = self.build_cpuid(leaf)?;
+        let mut value = eax;
+        for (index, candidate) in [(1, ebx), (2, ecx), (3, edx)] {
+            let is_register = self
+                .builder
+                .build_int_compare(
+                    IntPredicate::EQ,
+                    register,
+                    i32_type.const_int(index, false),
+                    "is_register",
+                )
+                .map_err(build_error)?;
+            value = self
+                .builder
+                .build_select(is_register, candidate, value, "register")
+                .map_err(build_error)?
+                .into_int_value();
+        }
+        let shifted = self
+            .builder
+            .build_right_shift(value, bit, false, "shifted")
+            .map_err(build_error)?;
+        let bit_value = self
+            .builder
+            .build_and(shifted, one, "bit")
+            .map_err(build_error)?;
+        let bit_set = self
+            .builder
+            .build_int_compare(IntPredicate::NE, bit_value, zero, "bit_set")
+            .map_err(build_error)?;
+        self.builder
+            .build_conditional_branch(bit_set, check_os_block, unsupported_block)
+            .map_err(build_error)?;
+
+        self.builder.position_at_end(check_os_block);
+        let needs_os = self
+            .builder
+            .build_int_compare(IntPredicate::NE, xcr0_mask, zero, "needs_os")
+            .map_err(build_error)?;
+        self.builder
+            .build_conditional_branch(needs_os, os_state_block, supported_block)
+            .map_err(build_error)?;
+
+        // XGETBV may only run once the OS has enabled it: OSXSAVE, bit 27 of ecx in leaf 1
+        self.builder.position_at_end(os_state_block);
+        let [_, _, features, _] = self.build_cpuid(one)?;
+        let osxsave = self
+            .builder
+            .build_and(features, i32_type.const_int(1 << 27, false), "osxsave")
+            .map_err(build_error)?;
+        let has_osxsave = self
+            .builder
+            .build_int_compare(IntPredicate::NE, osxsave, zero, "has_osxsave")
+            .map_err(build_error)?;
+        self.builder
+            .build_conditional_branch(has_osxsave, xgetbv_block, unsupported_block)
+            .map_err(build_error)?;
+
+        self.builder.position_at_end(xgetbv_block);
+        let xgetbv_type = self
+            .context
+            .struct_type(&[i32_type.into(), i32_type.into()], false)
+            .fn_type(&[i32_type.into()], false);
+        let xgetbv = self.context.create_inline_asm(
+            xgetbv_type,
+            "xgetbv".to_string(),
+            "={ax},={dx},{cx}".to_string(),
+            true,
+            false,
+            None,
+            false,
+        );
+        let xcr0 = self
+            .builder
+            .build_call(Self::function_pointer(xgetbv)?, &[zero.into()], "xgetbv")
+            .map_err(build_error)?
+            .try_as_basic_value()
+            .left()
+            .ok_or_else(|| {
+                CompileError::codegen_error("xgetbv returned no value".to_string(), None)
+            })?
+            .into_struct_value();
+        let xcr0_low = self
+            .builder
+            .build_extract_value(xcr0, 0, "xcr0")
+            .map_err(build_error)?
+            .into_int_value();
+        let os_state = self
+            .builder
+            .build_and(xcr0_low, xcr0_mask, "os_state")
+            .map_err(build_error)?;
+        let os_saves_state = self
+            .builder
+            .build_int_compare(IntPredicate::EQ, os_state, xcr0_mask, "os_saves_state")
+            .map_err(build_error)?;
+        self.builder
+            .build_return(Some(&os_saves_state))
+            .map_err(build_error)?;
+
+        self.builder.position_at_end(supported_block);
+        self.builder
+            .build_return(Some(&bool_type.const_all_ones()))
+            .map_err(build_error)?;
+        self.builder.position_at_end(unsupported_block);
+        self.builder
+            .build_return(Some(&bool_type.const_zero()))
+            .map_err(build_error)?;
+
+        Ok(function)
+    }
+
+    /// Wraps a pointer to a function, such as a resolved clone or an inline asm
+    /// block, so it can be called.
+    fn function_pointer(pointer: PointerValue<'ctx>) -> Result<CallableValue<'ctx>> {
+        CallableValue::try_from(pointer).map_err(|_| {
+            CompileError::codegen_error("Called value is not a function pointer".to_string(), None)
+        })
+    }
+
+    /// Executes CPUID for `leaf` (sub-leaf 0) at the builder's position and
+    /// returns `[eax, ebx, ecx, edx]`.
+    fn build_cpuid(&self, leaf: IntValue<'ctx>) -> Result<[IntValue<'ctx>; 4]> {
+        let build_error = |e: inkwell::builder::BuilderError| {
+            CompileError::codegen_error(format!("Failed to build cpuid: {:?}", e), None)
+        };
+        let i32_type = self.context.i32_type();
+        let cpuid_type = self
+            .context
+            .struct_type(&[i32_type.into(); 4], false)
+            .fn_type(&[i32_type.into(), i32_type.into()], false);
+        let cpuid = self.context.create_inline_asm(
+            cpuid_type,
+            "cpuid".to_string(),
+            "={ax},={bx},={cx},={dx},{ax},{cx}".to_string(),
+            true,
+            false,
+            None,
+            false,
+        );
+        let registers = self
+            .builder
+            .build_call(
+                Self::function_pointer(cpuid)?,
+                &[leaf.into(), i32_type.const_zero().into()],
+                "cpuid",
+            )
+            .map_err(build_error)?
+            .try_as_basic_value()
+            .left()
+            .ok_or_else(|| {
+                CompileError::codegen_error("cpuid returned no value".to_string(), None)
+            })?
+            .into_struct_value();
+
+        let mut values = [i32_type.const_zero(); 4];
+        for (index, value) in values.iter_mut().enumerate() {
+            *value = self
+                .builder
+                .build_extract_value(registers, index as u32, "register")
+                .map_err(build_error)?
+                .into_int_value();
+        }
+        Ok(values)
+    }
+
+    /// Parses @optimize attributes to create optimization configuration
+    fn parse_optimize_attributes(
+        &self,
+        attributes: &[crate::ast::Attribute],
+    ) -> OptimizationConfig {
+        let mut config = OptimizationConfig::default();
+
+        for attr in attributes {
+            if attr.name == "optimize" {
+                for param in &attr.params {
+                    // Parameters may name constants, e.g. `@optimize(unroll: UNROLL)`
+                    let value = self.resolve_attribute_value(&param.value);
+                    match param.key.as_str() {
+                        "simd" => match &value {
+                            crate::ast::AttributeValue::Identifier(val) => {
+                                config.simd_strategy = match val.as_str() {
+                                    "auto" => SIMDStrategy::Auto,
+                                    "enabled" => SIMDStrategy::Enabled,
+                                    "disabled" => SIMDStrategy::Disabled,
+                                    _ => SIMDStrategy::Auto,
+                                };
                             }
-                        } else {
-                            return Err(CompileError::codegen_error(
-                                "string_equals_char function not found - check builtin function initialization".to_string(),
-                                None,
-                            ));
-                        }
-                    } else if matches!(op, BinaryOp::GreaterEqual | BinaryOp::LessEqual | BinaryOp::Greater | BinaryOp::Less | BinaryOp::NotEqual) {
-                        // String comparison operations: use strcmp and compare result
-                        
-                        // Use existing strcmp function from minimal builtins (it should already exist)
-                        let strcmp_function = self.functions.get("strcmp").copied().ok_or_else(|| {
-                            CompileError::codegen_error(
-                                "strcmp function not found in function table".to_string(),
-                                None,
-                            )
-                        })?;
-                        
-                        // Call strcmp(left, right)
-                        let strcmp_result = self
-                            .builder
-                            .build_call(
-                                strcmp_function,
-                                &[left_ptr.into(), right_ptr.into()],
-                                "strcmp_result",
-                            )
-                            .map_err(|e| {
-                                CompileError::codegen_error(
-                                    format!("Failed to build strcmp call: {:?}", e),
-                                    None,
-                                )
-                            })?;
-                        
-                        let strcmp_value = strcmp_result.try_as_basic_value().left().unwrap().into_int_value();
-                        let zero = self.context.i32_type().const_int(0, false);
-                        
-                        // Compare strcmp result based on operation
-                        let comparison_result = match op {
-                            BinaryOp::GreaterEqual => {
-                                // strcmp(a, b) >= 0 means a >= b
-                                self.builder.build_int_compare(
-                                    IntPredicate::SGE,
-                                    strcmp_value,
-                                    zero,
-                                    "string_ge",
-                                ).map_err(|e| {
-                                    CompileError::codegen_error(
-                                        format!("Failed to build string >= comparison: {:?}", e),
-                                        None,
-                                    )
-                                })?
+                            crate::ast::AttributeValue::Boolean(true) => {
+                                config.simd_strategy = SIMDStrategy::Enabled;
                             }
-                            BinaryOp::LessEqual => {
-                                // strcmp(a, b) <= 0 means a <= b
-                                self.builder.build_int_compare(
-                                    IntPredicate::SLE,
-                                    strcmp_value,
-                                    zero,
-                                    "string_le",
-                                ).map_err(|e| {
-                                    CompileError::codegen_error(
-                                        format!("Failed to build string <= comparison: {:?}", e),
-                                        None,
-                                    )
-                                })?
+                            crate::ast::AttributeValue::Boolean(false) => {
+                                config.simd_strategy = SIMDStrategy::Disabled;
                             }
-                            BinaryOp::Greater => {
-                                // strcmp(a, b) > 0 means a > b
-                                self.builder.build_int_compare(
-                                    IntPredicate::SGT,
-                                    strcmp_value,
-                                    zero,
-                                    "string_gt",
-                                ).map_err(|e| {
-                                    CompileError::codegen_error(
-                                        format!("Failed to build string > comparison: {:?}", e),
-                                        None,
-                                    )
-                                })?
+                            _ => {}
+                        },
+                        "unroll" => match &value {
+                            crate::ast::AttributeValue::Identifier(val) => {
+                                config.unroll_strategy = match val.as_str() {
+                                    "adaptive" => UnrollStrategy::Adaptive,
+                                    "aggressive" => UnrollStrategy::Aggressive,
+                                    "conservative" => UnrollStrategy::Conservative,
+                                    "disabled" => UnrollStrategy::Disabled,
+                                    _ => UnrollStrategy::Adaptive,
+                                };
                             }
-                            BinaryOp::Less => {
-                                // strcmp(a, b) < 0 means a < b
-                                self.builder.build_int_compare(
-                                    IntPredicate::SLT,
-                                    strcmp_value,
-                                    zero,
-                                    "string_lt",
-                                ).map_err(|e| {
-                                    CompileError::codegen_error(
-                                        format!("Failed to build string < comparison: {:?}", e),
-                                        None,
-                                    )
-                                })?
+                            crate::ast::AttributeValue::Integer(n) => {
+                                config.unroll_factor = Some(*n as u32);
                             }
-                            BinaryOp::NotEqual => {
-                                // strcmp(a, b) != 0 means a != b
-                                self.builder.build_int_compare(
-                                    IntPredicate::NE,
-                                    strcmp_value,
-                                    zero,
-                                    "string_ne",
-                                ).map_err(|e| {
-                                    CompileError::codegen_error(
-                                        format!("Failed to build string != comparison: {:?}", e),
-                                        None,
-                                    )
-                                })?
+                            _ => {}
+                        },
+                        "algorithm" => match &value {
+                            crate::ast::AttributeValue::Identifier(val) => {
+                                config.algorithm_selection = match val.as_str() {
+                                    "adaptive" => AlgorithmSelection::Adaptive,
+                                    "parallel" => AlgorithmSelection::Parallel,
+                                    "sequential" => AlgorithmSelection::Sequential,
+                                    _ => AlgorithmSelection::Adaptive,
+                                };
                             }
-                            _ => unreachable!(), // We already checked the operation types above
-                        };
-                        
-                        eprintln!("✅ String comparison {:?} completed successfully", op);
-                        return Ok(comparison_result.into());
+                            _ => {}
+                        },
+                        "early_exit" => {
+                            if let crate::ast::AttributeValue::Boolean(val) = &value {
+                                config.early_exit = *val;
+                            }
+                        }
+                        "buffer" => match &value {
+                            crate::ast::AttributeValue::Identifier(val) => {
+                                config.buffer_strategy = match val.as_str() {
+                                    "vectorized" => BufferStrategy::Vectorized,
+                                    "consolidated" => BufferStrategy::Consolidated,
+                                    "adaptive" => BufferStrategy::Adaptive,
+                                    _ => BufferStrategy::Default,
+                                };
+                            }
+                            _ => {}
+                        },
+                        _ => {} // Ignore unknown attributes
                     }
                 }
+            }
+        }
 
-                // Handle operations based on operand types
-                if let (BasicValueEnum::IntValue(left_int), BasicValueEnum::IntValue(right_int)) =
-                    (left_value, right_value)
-                {
-                    match op {
-                        BinaryOp::Add => {
-                            let result = self
-                                .builder
-                                .build_int_add(left_int, right_int, "add")
-                                .map_err(|e| {
-                                    CompileError::codegen_error(
-                                        format!("Failed to build add: {:?}", e),
-                                        None,
-                                    )
-                                })?;
-                            Ok(result.into())
-                        }
-                        BinaryOp::Subtract => {
-                            let result = self
-                                .builder
-                                .build_int_sub(left_int, right_int, "sub")
-                                .map_err(|e| {
-                                    CompileError::codegen_error(
-                                        format!("Failed to build sub: {:?}", e),
-                                        None,
-                                    )
-                                })?;
-                            Ok(result.into())
-                        }
-                        BinaryOp::Multiply => {
-                            let result = self
-                                .builder
-                                .build_int_mul(left_int, right_int, "mul")
-                                .map_err(|e| {
-                                    CompileError::codegen_error(
-                                        format!("Failed to build mul: {:?}", e),
-                                        None,
-                                    )
-                                })?;
-                            Ok(result.into())
-                        }
-                        BinaryOp::Divide => {
-                            let result = self
-                                .builder
-                                .build_int_signed_div(left_int, right_int, "div")
-                                .map_err(|e| {
-                                    CompileError::codegen_error(
-                                        format!("Failed to build div: {:?}", e),
-                                        None,
-                                    )
-                                })?;
-                            Ok(result.into())
-                        }
-                        BinaryOp::Less => {
-                            let result = self
-                                .builder
-                                .build_int_compare(IntPredicate::SLT, left_int, right_int, "cmp_lt")
-                                .map_err(|e| {
-                                    CompileError::codegen_error(
-                                        format!("Failed to build comparison: {:?}", e),
-                                        None,
-                                    )
-                                })?;
-                            Ok(result.into())
-                        }
-                        BinaryOp::LessEqual => {
-                            let result = self
-                                .builder
-                                .build_int_compare(IntPredicate::SLE, left_int, right_int, "cmp_le")
-                                .map_err(|e| {
-                                    CompileError::codegen_error(
-                                        format!("Failed to build comparison: {:?}", e),
-                                        None,
-                                    )
-                                })?;
-                            Ok(result.into())
-                        }
-                        BinaryOp::Greater => {
-                            let result = self
-                                .builder
-                                .build_int_compare(IntPredicate::SGT, left_int, right_int, "cmp_gt")
-                                .map_err(|e| {
-                                    CompileError::codegen_error(
-                                        format!("Failed to build comparison: {:?}", e),
-                                        None,
-                                    )
-                                })?;
-                            Ok(result.into())
-                        }
-                        BinaryOp::GreaterEqual => {
-                            let result = self
-                                .builder
-                                .build_int_compare(IntPredicate::SGE, left_int, right_int, "cmp_ge")
-                                .map_err(|e| {
-                                    CompileError::codegen_error(
-                                        format!("Failed to build comparison: {:?}", e),
-                                        None,
-                                    )
-                                })?;
-                            Ok(result.into())
-                        }
-                        BinaryOp::Equal => {
-                            // Handle type coercion for integer comparisons
-                            let left_type = left_int.get_type();
-                            let right_type = right_int.get_type();
+        config
+    }
 
-                            let (coerced_left, coerced_right) = if left_type.get_bit_width()
-                                != right_type.get_bit_width()
-                            {
-                                // Promote to the larger type
-                                if left_type.get_bit_width() > right_type.get_bit_width() {
-                                    // Promote right to left's type
-                                    let coerced_right = if left_type.get_bit_width() == 64
-                                        && right_type.get_bit_width() == 32
-                                    {
-                                        self.builder
-                                            .build_int_z_extend(
-                                                right_int,
-                                                left_type,
-                                                "promote_right",
-                                            )
-                                            .map_err(|e| {
-                                                CompileError::codegen_error(
-                                                    format!("Failed to promote integer: {:?}", e),
-                                                    None,
-                                                )
-                                            })?
-                                    } else {
-                                        return Err(CompileError::codegen_error(
-                                            format!(
-                                                "Unsupported integer type promotion: {} to {}",
-                                                right_type.get_bit_width(),
-                                                left_type.get_bit_width()
-                                            ),
-                                            None,
-                                        ));
-                                    };
-                                    (left_int, coerced_right)
-                                } else {
-                                    // Promote left to right's type
-                                    let coerced_left = if right_type.get_bit_width() == 64
-                                        && left_type.get_bit_width() == 32
-                                    {
-                                        self.builder
-                                            .build_int_z_extend(
-                                                left_int,
-                                                right_type,
-                                                "promote_left",
-                                            )
-                                            .map_err(|e| {
-                                                CompileError::codegen_error(
-                                                    format!("Failed to promote integer: {:?}", e),
-                                                    None,
-                                                )
-                                            })?
-                                    } else {
-                                        return Err(CompileError::codegen_error(
-                                            format!(
-                                                "Unsupported integer type promotion: {} to {}",
-                                                left_type.get_bit_width(),
-                                                right_type.get_bit_width()
-                                            ),
-                                            None,
-                                        ));
-                                    };
-                                    (coerced_left, right_int)
-                                }
-                            } else {
-                                (left_int, right_int)
-                            };
+    /// Generates optimized function declaration based on configuration
+    fn generate_optimized_function_declaration(
+        &mut self,
+        name: &str,
+        params: &[crate::ast::Parameter],
+        return_type: &Option<TypeAnnotation>,
+        body: &Box<Stmt>,
+        config: &OptimizationConfig,
+    ) -> Result<()> {
+        // Store the optimization config for use during code generation
+        let old_config = self.current_optimization_config.clone();
+        self.current_optimization_config = Some(config.clone());
 
-                            let result = self
-                                .builder
-                                .build_int_compare(
-                                    IntPredicate::EQ,
-                                    coerced_left,
-                                    coerced_right,
-                                    "cmp_eq",
-                                )
-                                .map_err(|e| {
-                                    CompileError::codegen_error(
-                                        format!("Failed to build comparison: {:?}", e),
-                                        None,
-                                    )
-                                })?;
-                            Ok(result.into())
-                        }
-                        BinaryOp::NotEqual => {
-                            let result = self
-                                .builder
-                                .build_int_compare(IntPredicate::NE, left_int, right_int, "cmp_ne")
-                                .map_err(|e| {
-                                    CompileError::codegen_error(
-                                        format!("Failed to build comparison: {:?}", e),
-                                        None,
-                                    )
-                                })?;
-                            Ok(result.into())
-                        }
-                        BinaryOp::Modulo => {
-                            let result = self
-                                .builder
-                                .build_int_signed_rem(left_int, right_int, "mod")
-                                .map_err(|e| {
-                                    CompileError::codegen_error(
-                                        format!("Failed to build mod: {:?}", e),
-                                        None,
-                                    )
-                                })?;
-                            Ok(result.into())
-                        }
-                        BinaryOp::And => {
-                            // Convert to boolean (i1) if needed, then perform logical AND
-                            let left_bool = if left_int.get_type().get_bit_width() == 1 {
-                                left_int
-                            } else {
-                                // Convert non-zero to true, zero to false
-                                let zero = self.context.i32_type().const_int(0, false);
-                                self.builder
-                                    .build_int_compare(IntPredicate::NE, left_int, zero, "to_bool")
-                                    .map_err(|e| {
-                                        CompileError::codegen_error(
-                                            format!("Failed to convert to bool: {:?}", e),
-                                            None,
-                                        )
-                                    })?
-                            };
+        // Generate the function
+        let result = self.generate_function_declaration(name, params, return_type, body);
 
-                            let right_bool = if right_int.get_type().get_bit_width() == 1 {
-                                right_int
-                            } else {
-                                // Convert non-zero to true, zero to false
-                                let zero = self.context.i32_type().const_int(0, false);
-                                self.builder
-                                    .build_int_compare(IntPredicate::NE, right_int, zero, "to_bool")
-                                    .map_err(|e| {
-                                        CompileError::codegen_error(
-                                            format!("Failed to convert to bool: {:?}", e),
-                                            None,
-                                        )
-                                    })?
-                            };
+        // Apply post-generation optimizations based on config
+        if result.is_ok() {
+            if let Some(function) = self.functions.get(name) {
+                self.apply_function_optimizations(*function, config)?;
+            }
+        }
 
-                            let result = self
-                                .builder
-                                .build_and(left_bool, right_bool, "and")
-                                .map_err(|e| {
-                                    CompileError::codegen_error(
-                                        format!("Failed to build logical AND: {:?}", e),
-                                        None,
-                                    )
-                                })?;
-                            Ok(result.into())
-                        }
-                        BinaryOp::Or => {
-                            // Convert to boolean (i1) if needed, then perform logical OR
-                            let left_bool = if left_int.get_type().get_bit_width() == 1 {
-                                left_int
-                            } else {
-                                // Convert non-zero to true, zero to false
-                                let zero = self.context.i32_type().const_int(0, false);
-                                self.builder
-                                    .build_int_compare(IntPredicate::NE, left_int, zero, "to_bool")
-                                    .map_err(|e| {
-                                        CompileError::codegen_error(
-                                            format!("Failed to convert to bool: {:?}", e),
-                                            None,
-                                        )
-                                    })?
-                            };
+        // Restore previous config
+        self.current_optimization_config = old_config;
 
-                            let right_bool = if right_int.get_type().get_bit_width() == 1 {
-                                right_int
-                            } else {
-                                // Convert non-zero to true, zero to false
-                                let zero = self.context.i32_type().const_int(0, false);
-                                self.builder
-                                    .build_int_compare(IntPredicate::NE, right_int, zero, "to_bool")
-                                    .map_err(|e| {
-                                        CompileError::codegen_error(
-                                            format!("Failed to convert to bool: {:?}", e),
-                                            None,
-                                        )
-                                    })?
-                            };
+        result
+    }
 
-                            let result = self
-                                .builder
-                                .build_or(left_bool, right_bool, "or")
-                                .map_err(|e| {
-                                    CompileError::codegen_error(
-                                        format!("Failed to build logical OR: {:?}", e),
-                                        None,
-                                    )
-                                })?;
-                            Ok(result.into())
-                        }
-                        // For other operations, we'll return an error for now
-                        _ => Err(CompileError::codegen_error(
-                            format!("Binary operation {:?} not yet implemented for integers", op),
-                            None,
-                        )),
+    /// Applies optimizations to a generated function based on configuration
+    fn apply_function_optimizations(
+        &mut self,
+        function: FunctionValue<'ctx>,
+        config: &OptimizationConfig,
+    ) -> Result<()> {
+        // Apply SIMD optimizations
+        match config.simd_strategy {
+            SIMDStrategy::Enabled => {
+                self.optimize_simd_function(function)?;
+            }
+            SIMDStrategy::Auto => {
+                // Analyze function to determine if SIMD would be beneficial
+                if self.should_apply_simd_optimization(function) {
+                    self.optimize_simd_function(function)?;
+                }
+            }
+            SIMDStrategy::Disabled => {
+                // Skip SIMD optimizations
+            }
+        }
+
+        // Apply unroll optimizations
+        match config.unroll_strategy {
+            UnrollStrategy::Aggressive => {
+                self.apply_aggressive_unrolling(function, config.unroll_factor)?;
+            }
+            UnrollStrategy::Adaptive => {
+                self.apply_adaptive_unrolling(function)?;
+            }
+            UnrollStrategy::Conservative => {
+                self.apply_conservative_unrolling(function)?;
+            }
+            UnrollStrategy::Disabled => {
+                // Skip unroll optimizations
+            }
+        }
+
+        // Apply other optimizations based on config
+        if config.early_exit {
+            self.apply_early_exit_optimizations(function)?;
+        }
+
+        Ok(())
+    }
+
+    /// Determines if SIMD optimization should be applied automatically
+    fn should_apply_simd_optimization(&self, function: FunctionValue<'ctx>) -> bool {
+        // Analyze function to check for:
+        // 1. Vector operations
+        // 2. Array processing loops
+        // 3. Mathematical computations that benefit from SIMD
+
+        for basic_block in function.get_basic_blocks() {
+            for instruction in basic_block.get_instructions() {
+                if self.is_simd_instruction(&instruction) {
+                    return true;
+                }
+
+                // Check for patterns that benefit from SIMD
+                if self.instruction_benefits_from_simd(&instruction) {
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Checks if an instruction would benefit from SIMD optimization
+    fn instruction_benefits_from_simd(
+        &self,
+        instruction: &inkwell::values::InstructionValue,
+    ) -> bool {
+        match instruction.get_opcode() {
+            // Arithmetic operations that can be vectorized
+            inkwell::values::InstructionOpcode::Add
+            | inkwell::values::InstructionOpcode::FAdd
+            | inkwell::values::InstructionOpcode::Sub
+            | inkwell::values::InstructionOpcode::FSub
+            | inkwell::values::InstructionOpcode::Mul
+            | inkwell::values::InstructionOpcode::FMul
+            | inkwell::values::InstructionOpcode::UDiv
+            | inkwell::values::InstructionOpcode::SDiv
+            | inkwell::values::InstructionOpcode::FDiv => {
+                // Check if instruction operates on data that could be vectorized
+                if let Some(operand) = instruction.get_operand(0) {
+                    if let Some(operand_value) = operand.left() {
+                        let ty = operand_value.get_type();
+                        // Float and integer operations on arrays/pointers can benefit from SIMD
+                        return ty.is_float_type() || ty.is_int_type() || ty.is_pointer_type();
                     }
-                } else if let (
-                    BasicValueEnum::FloatValue(left_float),
-                    BasicValueEnum::FloatValue(right_float),
-                ) = (left_value, right_value)
-                {
-                    match op {
-                        BinaryOp::Add => {
-                            let result = self
-                                .builder
-                                .build_float_add(left_float, right_float, "fadd")
-                                .map_err(|e| {
-                                    CompileError::codegen_error(
-                                        format!("Failed to build fadd: {:?}", e),
-                                        None,
-                                    )
-                                })?;
-                            Ok(result.into())
-                        }
-                        BinaryOp::Subtract => {
-                            let result = self
-                                .builder
-                                .build_float_sub(left_float, right_float, "fsub")
-                                .map_err(|e| {
-                                    CompileError::codegen_error(
-                                        format!("Failed to build fsub: {:?}", e),
-                                        None,
-                                    )
-                                })?;
-                            Ok(result.into())
-                        }
-                        BinaryOp::Multiply => {
-                            let result = self
-                                .builder
-                                .build_float_mul(left_float, right_float, "fmul")
-                                .map_err(|e| {
-                                    CompileError::codegen_error(
-                                        format!("Failed to build fmul: {:?}", e),
-                                        None,
-                                    )
-                                })?;
-                            Ok(result.into())
-                        }
-                        BinaryOp::Divide => {
-                            let result = self
-                                .builder
-                                .build_float_div(left_float, right_float, "fdiv")
-                                .map_err(|e| {
-                                    CompileError::codegen_error(
-                                        format!("Failed to build fdiv: {:?}", e),
-                                        None,
-                                    )
-                                })?;
-                            Ok(result.into())
-                        }
-                        BinaryOp::Less => {
-                            let result = self
-                                .builder
-                                .build_float_compare(
-                                    FloatPredicate::OLT,
-                                    left_float,
-                                    right_float,
-                                    "fcmp_lt",
-                                )
-                                .map_err(|e| {
-                                    CompileError::codegen_error(
-                                        format!("Failed to build float comparison: {:?}", e),
-                                        None,
-                                    )
-                                })?;
-                            Ok(result.into())
-                        }
-                        BinaryOp::LessEqual => {
-                            let result = self
-                                .builder
-                                .build_float_compare(
-                                    FloatPredicate::OLE,
-                                    left_float,
-                                    right_float,
-                                    "fcmp_le",
-                                )
-                                .map_err(|e| {
-                                    CompileError::codegen_error(
-                                        format!("Failed to build float comparison: {:?}", e),
-                                        None,
-                                    )
-                                })?;
-                            Ok(result.into())
-                        }
-                        BinaryOp::Greater => {
-                            let result = self
-                                .builder
-                                .build_float_compare(
-                                    FloatPredicate::OGT,
-                                    left_float,
-                                    right_float,
-                                    "fcmp_gt",
-                                )
-                                .map_err(|e| {
-                                    CompileError::codegen_error(
-                                        format!("Failed to build float comparison: {:?}", e),
-                                        None,
-                                    )
-                                })?;
-                            Ok(result.into())
-                        }
-                        BinaryOp::GreaterEqual => {
-                            let result = self
-                                .builder
-                                .build_float_compare(
-                                    FloatPredicate::OGE,
-                                    left_float,
-                                    right_float,
-                                    "fcmp_ge",
-                                )
-                                .map_err(|e| {
-                                    CompileError::codegen_error(
-                                        format!("Failed to build float comparison: {:?}", e),
-                                        None,
-                                    )
-                                })?;
-                            Ok(result.into())
-                        }
-                        BinaryOp::Equal => {
-                            let result = self
-                                .builder
-                                .build_float_compare(
-                                    FloatPredicate::OEQ,
-                                    left_float,
-                                    right_float,
-                                    "fcmp_eq",
-                                )
-                                .map_err(|e| {
-                                    CompileError::codegen_error(
-                                        format!("Failed to build float comparison: {:?}", e),
-                                        None,
-                                    )
-                                })?;
-                            Ok(result.into())
-                        }
-                        BinaryOp::NotEqual => {
-                            let result = self
-                                .builder
-                                .build_float_compare(
-                                    FloatPredicate::ONE,
-                                    left_float,
-                                    right_float,
-                                    "fcmp_ne",
-                                )
-                                .map_err(|e| {
-                                    CompileError::codegen_error(
-                                        format!("Failed to build float comparison: {:?}", e),
-                                        None,
-                                    )
-                                })?;
-                            Ok(result.into())
-                        }
-                        BinaryOp::And => {
-                            // Convert floats to boolean: non-zero is true, zero/NaN is false
-                            let zero = self.context.f64_type().const_float(0.0);
-                            let left_bool = self
-                                .builder
-                                .build_float_compare(
-                                    FloatPredicate::ONE,
-                                    left_float,
-                                    zero,
-                                    "float_to_bool",
-                                )
-                                .map_err(|e| {
-                                    CompileError::codegen_error(
-                                        format!("Failed to convert float to bool: {:?}", e),
-                                        None,
-                                    )
-                                })?;
-                            let right_bool = self
-                                .builder
-                                .build_float_compare(
-                                    FloatPredicate::ONE,
-                                    right_float,
-                                    zero,
-                                    "float_to_bool",
-                                )
-                                .map_err(|e| {
-                                    CompileError::codegen_error(
-                                        format!("Failed to convert float to bool: {:?}", e),
-                                        None,
-                                    )
-                                })?;
+                }
+                false
+            }
+            // Mathematical functions that have SIMD implementations
+            inkwell::values::InstructionOpcode::Call => {
+                // Simplified: assume mathematical function calls can benefit from SIMD
+                // In a full implementation, we would inspect the called function name
+                true
+            }
+            // Load/store operations in loops can benefit from vectorization
+            inkwell::values::InstructionOpcode::Load
+            | inkwell::values::InstructionOpcode::Store => true,
+            _ => false,
+        }
+    }
 
-                            let result = self
-                                .builder
-                                .build_and(left_bool, right_bool, "float_and")
-                                .map_err(|e| {
-                                    CompileError::codegen_error(
-                                        format!("Failed to build logical AND for floats: {:?}", e),
-                                        None,
-                                    )
-                                })?;
-                            Ok(result.into())
-                        }
-                        BinaryOp::Or => {
-                            // Convert floats to boolean: non-zero is true, zero/NaN is false
-                            let zero = self.context.f64_type().const_float(0.0);
-                            let left_bool = self
-                                .builder
-                                .build_float_compare(
-                                    FloatPredicate::ONE,
-                                    left_float,
-                                    zero,
-                                    "float_to_bool",
-                                )
-                                .map_err(|e| {
-                                    CompileError::codegen_error(
-                                        format!("Failed to convert float to bool: {:?}", e),
-                                        None,
-                                    )
-                                })?;
-                            let right_bool = self
-                                .builder
-                                .build_float_compare(
-                                    FloatPredicate::ONE,
-                                    right_float,
-                                    zero,
-                                    "float_to_bool",
-                                )
-                                .map_err(|e| {
-                                    CompileError::codegen_error(
-                                        format!("Failed to convert float to bool: {:?}", e),
-                                        None,
-                                    )
-                                })?;
+    /// Optimizes a function for SIMD operations
+    fn optimize_simd_function(&mut self, function: FunctionValue<'ctx>) -> Result<()> {
+        // Skip SIMD optimization in JIT safe mode to prevent segmentation faults
+        if self.jit_safe_mode {
+            return Ok(());
+        }
 
-                            let result = self
-                                .builder
-                                .build_or(left_bool, right_bool, "float_or")
-                                .map_err(|e| {
-                                CompileError::codegen_error(
-                                    format!("Failed to build logical OR for floats: {:?}", e),
-                                    None,
-                                )
-                            })?;
-                            Ok(result.into())
-                        }
-                        BinaryOp::Modulo => {
-                            // Implement floating-point remainder using frem instruction
-                            let result = self
-                                .builder
-                                .build_float_rem(left_float, right_float, "frem")
-                                .map_err(|e| {
-                                    CompileError::codegen_error(
-                                        format!("Failed to build float remainder: {:?}", e),
-                                        None,
-                                    )
-                                })?;
-                            Ok(result.into())
-                        }
-                        // For other operations, we'll return an error for now
-                        _ => Err(CompileError::codegen_error(
-                            format!("Binary operation {:?} not yet implemented for floats", op),
-                            None,
-                        )),
-                    }
-                } else if matches!(op, BinaryOp::Add) {
-                    eprintln!("🔍 Codegen: Binary Add operation detected");
-                    eprintln!("🔍 Codegen: Left value type: {:?}, Right value type: {:?}", left_value, right_value);
-                    eprintln!("🔍 Codegen: Checking string+int condition: left_ptr={}, right_int={}, left_int={}, right_ptr={}", 
-                        matches!(left_value, BasicValueEnum::PointerValue(_)), 
-                        matches!(right_value, BasicValueEnum::IntValue(_)), 
-                        matches!(left_value, BasicValueEnum::IntValue(_)), 
-                        matches!(right_value, BasicValueEnum::PointerValue(_)));
-                    
-                    if (matches!(left_value, BasicValueEnum::PointerValue(_)) && matches!(right_value, BasicValueEnum::IntValue(_))) || (matches!(left_value, BasicValueEnum::IntValue(_)) && matches!(right_value, BasicValueEnum::PointerValue(_))) {
-                    // Handle string + integer concatenation
-                    eprintln!("🔍 String + Integer concatenation detected: {:?}", op);
-                    
-                    // Determine which operand is string and which is integer
-                    let (string_value, int_value) = if let (BasicValueEnum::PointerValue(str_ptr), BasicValueEnum::IntValue(int_val)) = (left_value, right_value) {
-                        (str_ptr, int_val)
-                    } else if let (BasicValueEnum::IntValue(int_val), BasicValueEnum::PointerValue(str_ptr)) = (left_value, right_value) {
-                        (str_ptr, int_val)
-                    } else {
-                        unreachable!()
-                    };
-                    
-                    // Convert integer to string using i32_to_string function
-                    let i32_to_string_function = self.functions.get("i32_to_string").copied().ok_or_else(|| {
-                        CompileError::codegen_error(
-                            "i32_to_string function not found in function table".to_string(),
-                            None,
-                        )
-                    })?;
-                    
-                    let int_as_string = self.builder.build_call(
-                        i32_to_string_function,
-                        &[int_value.into()],
-                        "int_to_str"
-                    ).map_err(|e| {
-                        CompileError::codegen_error(
-                            format!("Failed to call i32_to_string: {:?}", e),
-                            None,
-                        )
-                    })?.try_as_basic_value().left().ok_or_else(|| {
-                        CompileError::codegen_error(
-                            "i32_to_string did not return a value".to_string(),
-                            None,
-                        )
-                    })?;
-                    
-                    // Use string_concat function to concatenate string + converted integer
-                    let string_concat_function = self.functions.get("string_concat").copied().ok_or_else(|| {
-                        CompileError::codegen_error(
-                            "string_concat function not found in function table".to_string(),
-                            None,
-                        )
-                    })?;
-                    
-                    // Determine correct order for concatenation based on original operand order
-                    let (first_arg, second_arg) = if matches!(left_value, BasicValueEnum::PointerValue(_)) {
-                        // String + Integer: concat(string, int_as_string)
-                        (string_value.into(), int_as_string)
-                    } else {
-                        // Integer + String: concat(int_as_string, string)  
-                        (int_as_string, string_value.into())
-                    };
-                    
-                    let result = self.builder.build_call(
-                        string_concat_function,
-                        &[first_arg.into(), second_arg.into()],
-                        "string_int_concat"
-                    ).map_err(|e| {
-                        CompileError::codegen_error(
-                            format!("Failed to call string_concat: {:?}", e),
-                            None,
-                        )
-                    })?.try_as_basic_value().left().ok_or_else(|| {
-                        CompileError::codegen_error(
-                            "string_concat did not return a value".to_string(),
-                            None,
-                        )
-                    })?;
-                    
-                    eprintln!("✅ String + Integer concatenation successful");
-                    Ok(result)
-                    } else {
-                        // Not string + integer, continue to mixed types error
-                        eprintln!("❌ Mixed types in binary operation: {:?}, left: {:?}, right: {:?}", op, left_value.get_type(), right_value.get_type());
-                        Err(CompileError::codegen_error(
-                            format!("Mixed types in binary operation not yet supported: {:?} between {:?} and {:?}", op, left_value.get_type(), right_value.get_type()),
-                            None,
-                        ))
-                    }
-                } else {
-                    // Handle mixed integer types with coercion
-                    if let (
-                        BasicValueEnum::IntValue(left_int),
-                        BasicValueEnum::IntValue(right_int),
-                    ) = (left_value, right_value)
-                    {
-                        // Get the types and promote to larger type if needed
-                        let left_type = left_int.get_type();
-                        let right_type = right_int.get_type();
+        // Apply function-level SIMD attributes for LLVM optimization passes
+        let context = self.context;
 
-                        let (coerced_left, coerced_right) = if left_type.get_bit_width()
-                            != right_type.get_bit_width()
-                        {
-                            // Promote to the larger type
-                            if left_type.get_bit_width() > right_type.get_bit_width() {
-                                // Promote right to left's type
-                                let coerced_right = if left_type.get_bit_width() == 64
-                                    && right_type.get_bit_width() == 32
-                                {
-                                    self.builder
-                                        .build_int_z_extend(right_int, left_type, "promote_right")
-                                        .map_err(|e| {
-                                            CompileError::codegen_error(
-                                                format!("Failed to promote integer: {:?}", e),
-                                                None,
-                                            )
-                                        })?
-                                } else {
-                                    self.builder
-                                        .build_int_truncate(right_int, left_type, "truncate_right")
-                                        .map_err(|e| {
-                                            CompileError::codegen_error(
-                                                format!("Failed to truncate integer: {:?}", e),
-                                                None,
-                                            )
-                                        })?
-                                };
-                                (left_int, coerced_right)
-                            } else {
-                                // Promote left to right's type
-                                let coerced_left = if right_type.get_bit_width() == 64
-                                    && left_type.get_bit_width() == 32
-                                {
-                                    self.builder
-                                        .build_int_z_extend(left_int, right_type, "promote_left")
-                                        .map_err(|e| {
-                                            CompileError::codegen_error(
-                                                format!("Failed to promote integer: {:?}", e),
-                                                None,
-                                            )
-                                        })?
-                                } else {
-                                    self.builder
-                                        .build_int_truncate(left_int, right_type, "truncate_left")
-                                        .map_err(|e| {
-                                            CompileError::codegen_error(
-                                                format!("Failed to truncate integer: {:?}", e),
-                                                None,
-                                            )
-                                        })?
-                                };
-                                (coerced_left, right_int)
-                            }
-                        } else {
-                            (left_int, right_int)
-                        };
+        // Add vectorization attributes
+        let vectorize_attr = context.create_string_attribute("vectorize", "true");
+        function.add_attribute(inkwell::attributes::AttributeLoc::Function, vectorize_attr);
 
-                        // Now perform the operation with coerced types
-                        match op {
-                            BinaryOp::Equal => {
-                                let result = self
-                                    .builder
-                                    .build_int_compare(
-                                        IntPredicate::EQ,
-                                        coerced_left,
-                                        coerced_right,
-                                        "cmp_eq",
-                                    )
-                                    .map_err(|e| {
-                                        CompileError::codegen_error(
-                                            format!("Failed to build comparison: {:?}", e),
-                                            None,
-                                        )
-                                    })?;
-                                Ok(result.into())
-                            }
-                            BinaryOp::NotEqual => {
-                                let result = self
-                                    .builder
-                                    .build_int_compare(
-                                        IntPredicate::NE,
-                                        coerced_left,
-                                        coerced_right,
-                                        "cmp_ne",
-                                    )
-                                    .map_err(|e| {
-                                        CompileError::codegen_error(
-                                            format!("Failed to build comparison: {:?}", e),
-                                            None,
-                                        )
-                                    })?;
-                                Ok(result.into())
-                            }
-                            BinaryOp::Less => {
-                                let result = self
-                                    .builder
-                                    .build_int_compare(
-                                        IntPredicate::SLT,
-                                        coerced_left,
-                                        coerced_right,
-                                        "cmp_lt",
-                                    )
-                                    .map_err(|e| {
-                                        CompileError::codegen_error(
-                                            format!("Failed to build comparison: {:?}", e),
-                                            None,
-                                        )
-                                    })?;
-                                Ok(result.into())
-                            }
-                            BinaryOp::LessEqual => {
-                                let result = self
-                                    .builder
-                                    .build_int_compare(
-                                        IntPredicate::SLE,
-                                        coerced_left,
-                                        coerced_right,
-                                        "cmp_le",
-                                    )
-                                    .map_err(|e| {
-                                        CompileError::codegen_error(
-                                            format!("Failed to build comparison: {:?}", e),
-                                            None,
-                                        )
-                                    })?;
-                                Ok(result.into())
-                            }
-                            BinaryOp::Greater => {
-                                let result = self
-                                    .builder
-                                    .build_int_compare(
-                                        IntPredicate::SGT,
-                                        coerced_left,
-                                        coerced_right,
-                                        "cmp_gt",
-                                    )
-                                    .map_err(|e| {
-                                        CompileError::codegen_error(
-                                            format!("Failed to build comparison: {:?}", e),
-                                            None,
-                                        )
-                                    })?;
-                                Ok(result.into())
-                            }
-                            BinaryOp::GreaterEqual => {
-                                let result = self
-                                    .builder
-                                    .build_int_compare(
-                                        IntPredicate::SGE,
-                                        coerced_left,
-                                        coerced_right,
-                                        "cmp_ge",
-                                    )
-                                    .map_err(|e| {
-                                        CompileError::codegen_error(
-                                            format!("Failed to build comparison: {:?}", e),
-                                            None,
-                                        )
-                                    })?;
-                                Ok(result.into())
-                            }
-                            BinaryOp::Add => {
-                                let result = self
-                                    .builder
-                                    .build_int_add(coerced_left, coerced_right, "add")
-                                    .map_err(|e| {
-                                        CompileError::codegen_error(
-                                            format!("Failed to build add: {:?}", e),
-                                            None,
-                                        )
-                                    })?;
-                                Ok(result.into())
-                            }
-                            BinaryOp::Subtract => {
-                                let result = self
-                                    .builder
-                                    .build_int_sub(coerced_left, coerced_right, "sub")
-                                    .map_err(|e| {
-                                        CompileError::codegen_error(
-                                            format!("Failed to build sub: {:?}", e),
-                                            None,
-                                        )
-                                    })?;
-                                Ok(result.into())
-                            }
-                            BinaryOp::Multiply => {
-                                let result = self
-                                    .builder
-                                    .build_int_mul(coerced_left, coerced_right, "mul")
-                                    .map_err(|e| {
-                                        CompileError::codegen_error(
-                                            format!("Failed to build mul: {:?}", e),
-                                            None,
-                                        )
-                                    })?;
-                                Ok(result.into())
-                            }
-                            BinaryOp::Divide => {
-                                let result = self
-                                    .builder
-                                    .build_int_signed_div(coerced_left, coerced_right, "div")
-                                    .map_err(|e| {
-                                        CompileError::codegen_error(
-                                            format!("Failed to build div: {:?}", e),
-                                            None,
-                                        )
-                                    })?;
-                                Ok(result.into())
-                            }
-                            BinaryOp::Modulo => {
-                                let result = self
-                                    .builder
-                                    .build_int_signed_rem(coerced_left, coerced_right, "rem")
-                                    .map_err(|e| {
-                                        CompileError::codegen_error(
-                                            format!("Failed to build rem: {:?}", e),
-                                            None,
-                                        )
-                                    })?;
-                                Ok(result.into())
-                            }
-                            _ => Err(CompileError::codegen_error(
-                                format!(
-                                    "Binary operation {:?} not yet implemented for mixed integers",
-                                    op
-                                ),
-                                None,
-                            )),
-                        }
-                    } else {
-                        // Truly mixed types (e.g., int and float)
-                        eprintln!("❌ Mixed types in binary operation: {:?}, left: {:?}, right: {:?}", op, left_value.get_type(), right_value.get_type());
-                        Err(CompileError::codegen_error(
-                            format!("Mixed types in binary operation not yet supported: {:?} between {:?} and {:?}", op, left_value.get_type(), right_value.get_type()),
-                            None,
-                        ))
-                    }
+        let slp_vectorize_attr = context.create_string_attribute("slp-vectorize", "true");
+        function.add_attribute(
+            inkwell::attributes::AttributeLoc::Function,
+            slp_vectorize_attr,
+        );
+
+        let unroll_vectorize_attr = context.create_string_attribute("unroll-vectorize", "true");
+        function.add_attribute(
+            inkwell::attributes::AttributeLoc::Function,
+            unroll_vectorize_attr,
+        );
+
+        // Mark function for target-specific SIMD optimizations
+        self.mark_function_for_simd_optimization(function)?;
+
+        // Analyze loops and apply vectorization hints
+        self.apply_loop_vectorization_hints(function)?;
+
+        Ok(())
+    }
+
+    /// Marks function with target-specific SIMD optimization attributes
+    fn mark_function_for_simd_optimization(&mut self, function: FunctionValue<'ctx>) -> Result<()> {
+        // Skip SIMD features in JIT safe mode to prevent segmentation faults
+        if self.jit_safe_mode {
+            return Ok(());
+        }
+
+        let context = self.context;
+
+        // Add target features for maximum SIMD support
+        let target_features = self.options.target.llvm_features();
+        if !target_features.is_empty() {
+            let target_features_attr =
+                context.create_string_attribute("target-features", &target_features);
+            function.add_attribute(
+                inkwell::attributes::AttributeLoc::Function,
+                target_features_attr,
+            );
+        }
+
+        // Enable auto-vectorization for the function with AVX's 256-bit vectors
+        if self.hardware_detector.target_arch() == "x86_64" {
+            let prefer_vector_width_attr =
+                context.create_string_attribute("prefer-vector-width", "256");
+            function.add_attribute(
+                inkwell::attributes::AttributeLoc::Function,
+                prefer_vector_width_attr,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Applies loop vectorization hints to function basic blocks
+    fn apply_loop_vectorization_hints(&mut self, function: FunctionValue<'ctx>) -> Result<()> {
+        // Find loops in the function and add vectorization metadata
+        for basic_block in function.get_basic_blocks() {
+            if self.is_loop_block(basic_block) {
+                self.add_vectorization_metadata(basic_block)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks if a basic block represents a loop
+    fn is_loop_block(&self, block: BasicBlock<'ctx>) -> bool {
+        // Check if block has a backward branch (simple loop detection)
+        if let Some(terminator) = block.get_terminator() {
+            match terminator.get_opcode() {
+                inkwell::values::InstructionOpcode::Br => {
+                    // Conditional branch might be a loop
+                    terminator.get_num_operands() > 1
                 }
+                _ => false,
             }
+        } else {
+            false
         }
     }
 
-    /// Generates code for a unary expression.
-    fn generate_unary_expression(
+    /// Adds vectorization metadata to a loop block
+    fn add_vectorization_metadata(&mut self, _block: BasicBlock<'ctx>) -> Result<()> {
+        // In a real implementation, this would add LLVM metadata nodes
+        // to hint the optimizer about vectorization opportunities
+        // For now, we rely on function-level attributes
+        Ok(())
+    }
+
+    /// Applies aggressive loop unrolling
+    fn apply_aggressive_unrolling(
         &mut self,
-        op: &UnaryOp,
-        expr: &Box<Expr>,
-    ) -> Result<BasicValueEnum<'ctx>> {
-        let expr_value = self.generate_expression(expr)?;
+        function: FunctionValue<'ctx>,
+        factor: Option<u32>,
+    ) -> Result<()> {
+        let unroll_factor = factor.unwrap_or(8); // Default aggressive unroll factor
+        let context = self.context;
 
-        match op {
-            UnaryOp::Negate => {
-                if let BasicValueEnum::IntValue(int_value) = expr_value {
-                    let result = self.builder.build_int_neg(int_value, "neg").map_err(|e| {
-                        CompileError::codegen_error(format!("Failed to build neg: {:?}", e), None)
-                    })?;
-                    Ok(result.into())
-                } else if let BasicValueEnum::FloatValue(float_value) = expr_value {
-                    let result =
-                        self.builder
-                            .build_float_neg(float_value, "fneg")
-                            .map_err(|e| {
-                                CompileError::codegen_error(
-                                    format!("Failed to build fneg: {:?}", e),
-                                    None,
-                                )
-                            })?;
-                    Ok(result.into())
-                } else {
-                    Err(CompileError::codegen_error(
-                        "Negation only supported for numeric types".to_string(),
-                        None,
-                    ))
-                }
-            }
-            UnaryOp::Not => {
-                if let BasicValueEnum::IntValue(int_value) = expr_value {
-                    let result = self.builder.build_not(int_value, "not").map_err(|e| {
-                        CompileError::codegen_error(format!("Failed to build not: {:?}", e), None)
-                    })?;
-                    Ok(result.into())
-                } else {
-                    Err(CompileError::codegen_error(
-                        "Logical not only supported for boolean types".to_string(),
-                        None,
-                    ))
-                }
-            }
-            UnaryOp::Reference => {
-                // Create a reference (pointer) to the value
-                // For now, we'll allocate the value on the stack and return the pointer
-                let current_function = self.builder.get_insert_block().unwrap().get_parent().unwrap();
-                let alloca = self.create_entry_block_alloca(
-                    current_function, 
-                    "ref_temp", 
-                    expr_value.get_type()
-                )?;
-                self.builder.build_store(alloca, expr_value).map_err(|e| {
-                    CompileError::codegen_error(
-                        format!("Failed to store value for reference: {:?}", e),
-                        None,
-                    )
-                })?;
-                Ok(alloca.into())
+        // Add LLVM function attributes for aggressive unrolling
+        let unroll_count_attr =
+            context.create_string_attribute("unroll-count", &unroll_factor.to_string());
+        function.add_attribute(
+            inkwell::attributes::AttributeLoc::Function,
+            unroll_count_attr,
+        );
+
+        // Enable full loop unrolling where beneficial
+        let unroll_full_attr = context.create_string_attribute("unroll-full", "true");
+        function.add_attribute(
+            inkwell::attributes::AttributeLoc::Function,
+            unroll_full_attr,
+        );
+
+        // Apply to all loops in the function
+        self.apply_unroll_attributes_to_loops(function, unroll_factor)?;
+
+        Ok(())
+    }
+
+    /// Applies adaptive loop unrolling based on loop characteristics
+    fn apply_adaptive_unrolling(&mut self, function: FunctionValue<'ctx>) -> Result<()> {
+        // Analyze each loop and determine optimal unroll factor
+        for basic_block in function.get_basic_blocks() {
+            if self.is_loop_block(basic_block) {
+                let optimal_factor = self.calculate_optimal_unroll_factor(basic_block);
+                self.apply_unroll_attributes_to_loops(function, optimal_factor)?;
             }
-            // For other unary operations, we'll return an error for now
-            _ => Err(CompileError::codegen_error(
-                format!("Unary operation {:?} not yet implemented", op),
-                None,
-            )),
         }
+
+        // Enable adaptive unrolling hints
+        let context = self.context;
+        let unroll_enable_attr = context.create_string_attribute("unroll-enable", "true");
+        function.add_attribute(
+            inkwell::attributes::AttributeLoc::Function,
+            unroll_enable_attr,
+        );
+
+        Ok(())
     }
 
-    /// Generates code for a function call.
-    fn generate_function_call(
-        &mut self,
-        callee: &Box<Expr>,
-        args: &[Expr],
-    ) -> Result<BasicValueEnum<'ctx>> {
-        // Handle both direct function calls and module-scoped calls (Vec::new, HashMap::new)
-        let function_name = match &**callee {
-            // Direct function call: func_name()
-            Expr::Variable(func_name) => func_name.clone(),
+    /// Applies conservative loop unrolling
+    fn apply_conservative_unrolling(&mut self, function: FunctionValue<'ctx>) -> Result<()> {
+        // Conservative unrolling: small factor, only for simple loops
+        let conservative_factor = 2;
+        let context = self.context;
 
-            // Module-scoped function call: Vec::new(), HashMap::new()
-            // OR method call on instance: vec.push(), vec.len()
-            Expr::FieldAccess(base_expr, method_name) => {
-                if let Expr::Variable(module_name) = &**base_expr {
-                    // Check if this is a static method call (Vec::new) or instance method call (vec.push)
-                    // Static calls have uppercase first letter, instance calls have lowercase
-                    if module_name.chars().next().unwrap().is_uppercase() {
-                        // Static method call: Vec::new(), HashMap::new()
-                        // Map to runtime function names
-                        match (module_name.as_str(), method_name.as_str()) {
-                            ("Vec", "new") => "vec_new".to_string(),
-                            ("HashMap", "new") => "hashmap_new".to_string(),
-                            ("HashSet", "new") => "HashSet_new".to_string(),
-                            ("String", "new") => "string_new".to_string(),
-                            ("String", "from") => "string_from".to_string(),
-                            ("File", "open") => "file_open".to_string(),
-                            ("File", "create") => "file_create".to_string(),
-                            ("File", "exists") => "file_exists".to_string(),
-                            ("File", "size") => "file_size".to_string(),
-                            ("File", "delete") => "file_delete".to_string(),
-                            ("File", "write") => "file_write".to_string(),
-                            ("File", "readline") => "file_read_line".to_string(),
-                            ("File", "read_all") => "file_read_all".to_string(),
-                            ("File", "close") => "file_close".to_string(),
-                            ("Package", "new") => "Package_new".to_string(),
-                            ("PackageManager", "new") => "PackageManager_new".to_string(),
-                            ("BuildConfig", "new") => "BuildConfig_new".to_string(),
-                            ("BenchmarkConfig", "new") => "BenchmarkConfig_new".to_string(),
-                            _ => format!("{}::{}", module_name, method_name),
-                        }
-                    } else {
-                        // Instance method call: vec.push(), vec.len()
-                        // Generate the object value and pass it as first argument
-                        let object_value = self.generate_expression(base_expr)?;
+        let unroll_count_attr =
+            context.create_string_attribute("unroll-count", &conservative_factor.to_string());
+        function.add_attribute(
+            inkwell::attributes::AttributeLoc::Function,
+            unroll_count_attr,
+        );
 
-                        // Get the method function
-                        // Map Vec and HashMap method names to runtime function names
-                        // For now, we'll determine the type based on the variable name pattern
-                        // This is a simplified approach - in a full implementation, we'd use type information
-                        let method_func_name = if let Expr::Variable(var_name) = &**base_expr {
-                            if var_name.contains("set") || var_name.starts_with("set") {
-                                // HashSet methods
-                                match method_name.as_str() {
-                                    "insert" => "HashSet_insert".to_string(),
-                                    "contains" => "HashSet_contains".to_string(),
-                                    "remove" => "HashSet_remove".to_string(),
-                                    "len" => "HashSet_len".to_string(),
-                                    "is_empty" => "HashSet_is_empty".to_string(),
-                                    "clear" => "HashSet_clear".to_string(),
-                                    _ => format!("HashSet::{}", method_name),
-                                }
-                            } else if var_name.contains("map") || var_name.starts_with("hash") {
-                                // HashMap methods
-                                match method_name.as_str() {
-                                    "insert" => "hashmap_insert".to_string(),
-                                    "get" => "hashmap_get".to_string(),
-                                    "len" => "hashmap_len".to_string(),
-                                    "contains_key" => "hashmap_contains_key".to_string(),
-                                    "remove" => "hashmap_remove".to_string(),
-                                    "is_empty" => "hashmap_is_empty".to_string(),
-                                    "clear" => "hashmap_clear".to_string(),
-                                    _ => format!("HashMap::{}", method_name),
-                                }
-                            } else if var_name.contains("string") || var_name.starts_with("s") {
-                                // String methods
-                                match method_name.as_str() {
-                                    "len" => "string_len".to_string(),
-                                    "push_str" => "string_push_str".to_string(),
-                                    "as_str" => "string_as_str".to_string(),
-                                    "clone" => "string_clone".to_string(),
-                                    "substring" => "string_substring".to_string(),
-                                    "find" => "string_find".to_string(),
-                                    "replace" => "string_replace".to_string(),
-                                    "to_uppercase" => "string_to_uppercase".to_string(),
-                                    "to_lowercase" => "string_to_lowercase".to_string(),
-                                    "trim" => "string_trim".to_string(),
-                                    _ => format!("String::{}", method_name),
-                                }
-                            } else if var_name.contains("package") || var_name.starts_with("package") {
-                                // Package methods
-                                match method_name.as_str() {
-                                    "add_dependency" => "Package_add_dependency".to_string(),
-                                    "set_performance_requirements" => "Package_set_performance_requirements".to_string(),
-                                    _ => format!("Package::{}", method_name),
-                                }
-                            } else if var_name.contains("resolver") || var_name.contains("manager") {
-                                // PackageManager methods
-                                match method_name.as_str() {
-                                    "resolve_dependencies" => "PackageManager_resolve_dependencies".to_string(),
-                                    "build_package" => "PackageManager_build_package".to_string(),
-                                    "run_benchmarks" => "PackageManager_run_benchmarks".to_string(),
-                                    _ => format!("PackageManager::{}", method_name),
-                                }
-                            } else if var_name.contains("build_config") || var_name.starts_with("build") {
-                                // BuildConfig methods
-                                match method_name.as_str() {
-                                    "add_target" => "BuildConfig_add_target".to_string(),
-                                    "set_optimization" => "BuildConfig_set_optimization".to_string(),
-                                    _ => format!("BuildConfig::{}", method_name),
-                                }
-                            } else if var_name.contains("benchmark_config") || var_name.starts_with("benchmark") {
-                                // BenchmarkConfig methods
-                                match method_name.as_str() {
-                                    "set_iterations" => "BenchmarkConfig_set_iterations".to_string(),
-                                    "set_timeout" => "BenchmarkConfig_set_timeout".to_string(),
-                                    _ => format!("BenchmarkConfig::{}", method_name),
-                                }
-                            } else if var_name.contains("resolution") {
-                                // DependencyResolution methods
-                                match method_name.as_str() {
-                                    "count" => "DependencyResolution_count".to_string(),
-                                    _ => format!("DependencyResolution::{}", method_name),
-                                }
-                            } else if var_name.contains("result") && (var_name.contains("build") || var_name.contains("cached")) {
-                                // BuildResult methods
-                                match method_name.as_str() {
-                                    "compilation_time_ms" => "BuildResult_compilation_time_ms".to_string(),
-                                    "peak_memory_mb" => "BuildResult_peak_memory_mb".to_string(),
-                                    "cache_hit_rate" => "BuildResult_cache_hit_rate".to_string(),
-                                    "performance_gain" => "BuildResult_performance_gain".to_string(),
-                                    "from_cache" => "BuildResult_from_cache".to_string(),
-                                    _ => format!("BuildResult::{}", method_name),
-                                }
-                            } else {
-                                // Vec methods (default)
-                                match method_name.as_str() {
-                                    "push" => "vec_push".to_string(),
-                                    "len" => "vec_len".to_string(),
-                                    "get" => "vec_get".to_string(),
-                                    "pop" => "vec_pop".to_string(),
-                                    "capacity" => "vec_capacity".to_string(),
-                                    "is_empty" => "vec_is_empty".to_string(),
-                                    "clear" => "vec_clear".to_string(),
-                                    _ => format!("Vec::{}", method_name),
-                                }
-                            }
-                        } else {
-                            // Default to Vec methods for other expressions
-                            match method_name.as_str() {
-                                "push" => "vec_push".to_string(),
-                                "len" => "vec_len".to_string(),
-                                "get" => "vec_get".to_string(),
-                                "pop" => "vec_pop".to_string(),
-                                "capacity" => "vec_capacity".to_string(),
-                                "is_empty" => "vec_is_empty".to_string(),
-                                "clear" => "vec_clear".to_string(),
-                                _ => format!("Vec::{}", method_name),
-                            }
-                        };
-                        if let Some(&function) = self.functions.get(&method_func_name) {
-                            // Generate arguments, with object as first argument
-                            // For Vec methods that expect double pointers (i8**), we need to pass the address of the variable
-                            let first_arg = if method_func_name.starts_with("vec_") && 
-                                            (method_func_name == "vec_get" || method_func_name == "vec_len" || method_func_name == "vec_push" || method_func_name == "vec_pop") {
-                                // These Vec functions expect i8** (pointer to Vec pointer)
-                                if let Expr::Variable(var_name) = &**base_expr {
-                                    if let Some(&var_ptr) = self.variables.get(var_name) {
-                                        // Pass the address of the variable (i8**) instead of the loaded value (i8*)
-                                        var_ptr.into()
-                                    } else {
-                                        return Err(CompileError::codegen_error(
-                                            format!("Variable '{}' not found", var_name),
-                                            None,
-                                        ));
-                                    }
-                                } else {
-                                    // For complex expressions, use the loaded value (this may still cause issues)
-                                    object_value.into()
-                                }
-                            } else {
-                                // For other methods, use the loaded value as before
-                                object_value.into()
-                            };
-                            
-                            let mut arg_values = vec![first_arg];
-                            for arg in args {
-                                let arg_value = self.generate_expression(arg)?;
-                                arg_values.push(arg_value.into());
-                            }
+        // Only unroll if profitable
+        let unroll_enable_attr = context.create_string_attribute("unroll-enable", "true");
+        function.add_attribute(
+            inkwell::attributes::AttributeLoc::Function,
+            unroll_enable_attr,
+        );
 
-                            // Build the method call
-                            let call = self
-                                .builder
-                                .build_call(function, &arg_values, "method_call")
-                                .map_err(|e| {
-                                    CompileError::codegen_error(
-                                        format!("Failed to build method call: {:?}", e),
-                                        None,
-                                    )
-                                })?;
+        let unroll_threshold_attr = context.create_string_attribute("unroll-threshold", "150");
+        function.add_attribute(
+            inkwell::attributes::AttributeLoc::Function,
+            unroll_threshold_attr,
+        );
 
-                            // Handle special return value processing for Vec and HashMap methods
-                            if let Some(return_value) = call.try_as_basic_value().left() {
-                                // Check if this is a HashMap method based on the function name
-                                if method_func_name.starts_with("hashmap_") {
-                                    // HashMap methods
-                                    match method_name.as_str() {
-                                        "get" => {
-                                            // hashmap_get returns i32 directly (0 if not found)
-                                            if let BasicValueEnum::IntValue(value) = return_value {
-                                                return Ok(value.into());
-                                            }
-                                            return Ok(return_value);
-                                        }
-                                        "len" => {
-                                            // hashmap_len returns i32, no conversion needed
-                                            if let BasicValueEnum::IntValue(len_i32) = return_value
-                                            {
-                                                return Ok(len_i32.into());
-                                            }
-                                            return Ok(return_value);
-                                        }
-                                        "contains_key" | "remove" => {
-                                            // hashmap_contains_key and hashmap_remove return i32 (0 or 1)
-                                            if let BasicValueEnum::IntValue(result) = return_value {
-                                                return Ok(result.into());
-                                            }
-                                            return Ok(return_value);
-                                        }
-                                        "insert" => {
-                                            // hashmap_insert returns void, create a dummy success value
-                                            let success_value =
-                                                self.context.i32_type().const_int(1, false);
-                                            return Ok(success_value.into());
-                                        }
-                                        _ => {
-                                            return Ok(return_value);
-                                        }
-                                    }
-                                } else if method_func_name.starts_with("HashSet_") {
-                                    // HashSet methods
-                                    match method_name.as_str() {
-                                        "contains" | "remove" | "insert" => {
-                                            // HashSet methods return bool (0 or 1)
-                                            if let BasicValueEnum::IntValue(result) = return_value {
-                                                return Ok(result.into());
-                                            }
-                                            return Ok(return_value);
-                                        }
-                                        "len" => {
-                                            // HashSet_len returns i32, no conversion needed
-                                            if let BasicValueEnum::IntValue(len_i32) = return_value
-                                            {
-                                                return Ok(len_i32.into());
-                                            }
-                                            return Ok(return_value);
-                                        }
-                                        "is_empty" => {
-                                            // HashSet_is_empty returns bool (0 or 1)
-                                            if let BasicValueEnum::IntValue(result) = return_value {
-                                                return Ok(result.into());
-                                            }
-                                            return Ok(return_value);
-                                        }
-                                        "clear" => {
-                                            // HashSet_clear returns void, create a dummy success value
-                                            let success_value =
-                                                self.context.i32_type().const_int(1, false);
-                                            return Ok(success_value.into());
-                                        }
-                                        _ => {
-                                            return Ok(return_value);
-                                        }
-                                    }
-                                } else {
-                                    // Vec methods
-                                    match method_name.as_str() {
-                                        "len" => {
-                                            // vec_len returns i32, no conversion needed
-                                            if let BasicValueEnum::IntValue(len_i32) = return_value
-                                            {
-                                                return Ok(len_i32.into());
-                                            }
-                                            return Ok(return_value);
-                                        }
-                                        "get" => {
-                                            // For Vec<string>, vec_get should return the string pointer directly
-                                            // For Vec<i32>, vec_get should dereference to get the integer value
-                                            // Since we don't have runtime type information, we'll assume Vec<string> 
-                                            // based on the variable name pattern or return the pointer directly
-                                            
-                                            // Check if this is likely a Vec<string> based on variable name
-                                            let is_string_vec = if let Expr::Variable(var_name) = &**base_expr {
-                                                var_name.contains("args") || var_name.contains("string") || var_name.ends_with("_args")
-                                            } else {
-                                                false
-                                            };
-                                            
-                                            if is_string_vec {
-                                                // For Vec<string>, return the pointer directly (it's already a string pointer)
-                                                return Ok(return_value);
-                                            } else {
-                                                // vec_get returns pointer, dereference to get value for integer vectors
-                                                if let BasicValueEnum::PointerValue(ptr) = return_value
-                                                {
-                                                    // Check if pointer is null first
-                                                    let null_ptr = self
-                                                        .context
-                                                        .i8_type()
-                                                        .ptr_type(AddressSpace::default())
-                                                        .const_null();
-                                                    let is_null = self.builder.build_int_compare(
-                                                    IntPredicate::EQ,
-                                                    ptr,
-                                                    null_ptr,
-                                                    "is_null"
-                                                ).map_err(|e| CompileError::codegen_error(
-                                                    format!("Failed to check null pointer: {:?}", e), None))?;
+        Ok(())
+    }
 
-                                                    // Create conditional to return 0 if null, otherwise dereference
-                                                    let current_function = self
-                                                        .builder
-                                                        .get_insert_block()
-                                                        .unwrap()
-                                                        .get_parent()
-                                                        .unwrap();
-                                                    let then_block = self.context.append_basic_block(
-                                                        current_function,
-                                                        "null_case",
-                                                    );
-                                                    let else_block = self.context.append_basic_block(
-                                                        current_function,
-                                                        "valid_case",
-                                                    );
-                                                    let merge_block = self
-                                                        .context
-                                                        .append_basic_block(current_function, "merge");
+    /// Calculates optimal unroll factor for a specific loop
+    fn calculate_optimal_unroll_factor(&self, _block: BasicBlock<'ctx>) -> u32 {
+        // Simplified heuristic for optimal unroll factor
+        // In practice, this would analyze:
+        // - Loop body size
+        // - Memory access patterns
+        // - Register pressure
+        // - Target architecture capabilities
 
-                                                    self.builder
-                                                        .build_conditional_branch(
-                                                            is_null, then_block, else_block,
-                                                        )
-                                                        .map_err(|e| {
-                                                            CompileError::codegen_error(
-                                                                format!(
-                                                                    "Failed to build conditional: {:?}",
-                                                                    e
-                                                                ),
-                                                                None,
-                                                            )
-                                                        })?;
+        // For now, return a reasonable default
+        4
+    }
 
-                                                    // Null case: return 0
-                                                    self.builder.position_at_end(then_block);
-                                                    let zero =
-                                                        self.context.i32_type().const_int(0, false);
-                                                    self.builder
-                                                        .build_unconditional_branch(merge_block)
-                                                        .map_err(|e| {
-                                                            CompileError::codegen_error(
-                                                                format!(
-                                                                    "Failed to build branch: {:?}",
-                                                                    e
-                                                                ),
-                                                                None,
-                                                            )
-                                                        })?;
+    /// Applies unroll attributes to loops in the function
+    fn apply_unroll_attributes_to_loops(
+        &mut self,
+        function: FunctionValue<'ctx>,
+        factor: u32,
+    ) -> Result<()> {
+        // In LLVM, unroll attributes are typically applied via metadata
+        // This simplified version applies function-level hints
+        let context = self.context;
 
-                                                    // Valid case: dereference pointer
-                                                    self.builder.position_at_end(else_block);
+        let unroll_count_attr =
+            context.create_string_attribute("unroll-count", &factor.to_string());
+        function.add_attribute(
+            inkwell::attributes::AttributeLoc::Function,
+            unroll_count_attr,
+        );
 
-                                                    // Cast the i8* pointer to i32* and load the i32 value
-                                                    let i32_ptr = self.builder.build_pointer_cast(
-                                                    ptr,
-                                                    self.context.i32_type().ptr_type(AddressSpace::default()),
-                                                    "i32_ptr"
-                                                ).map_err(|e| CompileError::codegen_error(
-                                                    format!("Failed to cast pointer to i32*: {:?}", e), None))?;
+        // Enable pragma unroll
+        let unroll_pragma_attr = context.create_string_attribute("unroll-pragma", "true");
+        function.add_attribute(
+            inkwell::attributes::AttributeLoc::Function,
+            unroll_pragma_attr,
+        );
 
-                                                    let value = self.builder.build_load(i32_ptr, "deref_value")
-                                                    .map_err(|e| CompileError::codegen_error(
-                                                        format!("Failed to dereference pointer: {:?}", e), None))?;
+        Ok(())
+    }
 
-                                                self.builder
-                                                    .build_unconditional_branch(merge_block)
-                                                    .map_err(|e| {
-                                                        CompileError::codegen_error(
-                                                            format!(
-                                                                "Failed to build branch: {:?}",
-                                                                e
-                                                            ),
-                                                            None,
-                                                        )
-                                                    })?;
+    /// Applies early exit optimizations for better performance
+    fn apply_early_exit_optimizations(&mut self, function: FunctionValue<'ctx>) -> Result<()> {
+        let context = self.context;
 
-                                                // Merge block: PHI node to select result
-                                                self.builder.position_at_end(merge_block);
-                                                let phi = self
-                                                    .builder
-                                                    .build_phi(
-                                                        self.context.i32_type(),
-                                                        "get_result",
-                                                    )
-                                                    .map_err(|e| {
-                                                        CompileError::codegen_error(
-                                                            format!("Failed to build phi: {:?}", e),
-                                                            None,
-                                                        )
-                                                    })?;
-                                                phi.add_incoming(&[
-                                                    (&zero, then_block),
-                                                    (&value, else_block),
-                                                ]);
+        // Add optimization hints for branch prediction and early exits
+        let branch_weight_attr = context.create_string_attribute("branch-weight", "true");
+        function.add_attribute(
+            inkwell::attributes::AttributeLoc::Function,
+            branch_weight_attr,
+        );
 
-                                                return Ok(phi.as_basic_value());
-                                                }
-                                                return Ok(return_value);
-                                            }
-                                        }
-                                        "push" => {
-                                            // vec_push returns i32 success indicator, which is fine
-                                            return Ok(return_value);
-                                        }
-                                        _ => {
-                                            return Ok(return_value);
-                                        }
-                                    }
-                                }
-                            } else {
-                                let dummy_value = self.context.i32_type().const_int(0, false);
-                                return Ok(dummy_value.into());
-                            }
-                        } else {
-                            return Err(CompileError::codegen_error(
-                                format!("Method '{}' not found", method_name),
-                                None,
-                            ));
-                        }
-                    }
-                } else {
-                    return Err(CompileError::codegen_error(
-                        "Complex expressions not supported in method calls".to_string(),
-                        None,
-                    ));
-                }
-            }
+        // Enable tail call optimization for recursive early exits
+        let tail_call_attr = context.create_string_attribute("tail-call-optimization", "true");
+        function.add_attribute(inkwell::attributes::AttributeLoc::Function, tail_call_attr);
 
-            _ => {
-                return Err(CompileError::codegen_error(
-                    "Unsupported function call syntax".to_string(),
-                    None,
-                ));
-            }
-        };
+        // Apply early exit patterns to conditional blocks
+        self.optimize_conditional_blocks_for_early_exit(function)?;
 
-        // Look up the function in the function table
-        if let Some(&function) = self.functions.get(&function_name) {
-            // Generate code for each argument
-            let mut arg_values = Vec::new();
-            for arg in args {
-                let arg_value = self.generate_expression(arg)?;
-                arg_values.push(arg_value);
-            }
+        Ok(())
+    }
 
-            // In JIT mode, check for functions with vector parameters
-            if self.jit_safe_mode && self.should_skip_function_in_jit(&function_name, &arg_values) {
-                return Err(CompileError::codegen_error(
-                    format!(
-                        "JIT Mode Error: Function '{}' has vector parameters which aren't supported by LLVM JIT.\n\
-                        \n\
-                        SIMD functions work correctly in normal compilation mode.\n\
-                        To use this function:\n\
-                        \n\
-                        1. Compile normally: ./target/release/ea your_program.ea\n\
-                        2. Or compile to LLVM IR: ./target/release/ea --emit-llvm your_program.ea\n\
-                        \n\
-                        JIT mode (--run) is intended for simple programs and testing.\n\
-                        For SIMD operations, use the normal compilation pipeline.",
-                        function_name
-                    ),
-                    None,
-                ));
+    /// Optimizes conditional blocks for early exit patterns
+    fn optimize_conditional_blocks_for_early_exit(
+        &mut self,
+        function: FunctionValue<'ctx>,
+    ) -> Result<()> {
+        // Find conditional branches and optimize for likely/unlikely patterns
+        for basic_block in function.get_basic_blocks() {
+            if let Some(terminator) = basic_block.get_terminator() {
+                if terminator.get_opcode() == inkwell::values::InstructionOpcode::Br {
+                    // This is where we would add branch probability metadata
+                    // to hint to LLVM about likely early exits
+                    self.add_branch_probability_hints(terminator)?;
+                }
             }
+        }
+        Ok(())
+    }
 
-            // Build the function call
-            let call = self
-                .builder
-                .build_call(
-                    function,
-                    &arg_values.iter().map(|v| (*v).into()).collect::<Vec<_>>(),
-                    "call",
-                )
-                .map_err(|e| {
-                    CompileError::codegen_error(format!("Failed to build call: {:?}", e), None)
-                })?;
-
-            // Get the return value (if any)
-            if let Some(return_value) = call.try_as_basic_value().left() {
-                Ok(return_value)
-            } else {
-                // Function returns void - create a dummy value for statement context
-                let dummy_value = self.context.i32_type().const_int(0, false);
-                Ok(dummy_value.into())
-            }
-        } else {
-            Err(CompileError::codegen_error(
-                format!("Function '{}' not found", function_name),
-                None,
-            ))
-        }
+    /// Adds branch probability hints for optimization
+    fn add_branch_probability_hints(
+        &mut self,
+        _instruction: inkwell::values::InstructionValue,
+    ) -> Result<()> {
+        // In a full implementation, this would add LLVM metadata for branch weights
+        // to optimize for common early-exit patterns
+        Ok(())
     }
 
-    /// Writes the generated LLVM IR to a file.
-    pub fn write_ir_to_file(&self, filename: &str) -> Result<()> {
-        if self.module.print_to_file(filename).is_err() {
-            return Err(CompileError::codegen_error(
-                format!("Failed to write IR to file '{}'", filename),
-                None,
-            ));
+    /// Creates an allocation instruction in the entry block of the function.
+    fn create_entry_block_alloca(
+        &self,
+        function: FunctionValue<'ctx>,
+        name: &str,
+        ty: BasicTypeEnum<'ctx>,
+    ) -> Result<PointerValue<'ctx>> {
+        let builder = self.context.create_builder();
+        let entry = function.get_first_basic_block().unwrap();
+
+        match entry.get_first_instruction() {
+            Some(first_instr) => builder.position_before(&first_instr),
+            None => builder.position_at_end(entry),
         }
-        Ok(())
-    }
 
-    /// Returns the generated LLVM IR as a string.
-    pub fn emit_ir(&self) -> String {
-        self.module.print_to_string().to_string()
+        builder.build_alloca(ty, name).map_err(|e| {
+            CompileError::codegen_error(format!("Failed to build alloca: {:?}", e), None)
+        })
     }
 
-    /// Returns a reference to the LLVM module for JIT execution.
-    pub fn get_module(&self) -> &Module<'ctx> {
-        &self.module
+    /// Generates code for a variable declaration with pattern.
+    fn generate_var_declaration_pattern(
+        &mut self,
+        pattern: &Pattern,
+        type_annotation: &Option<TypeAnnotation>,
+        initializer: &Option<Expr>,
+    ) -> Result<()> {
+        match pattern {
+            Pattern::Variable(name) => {
+                // Simple variable pattern - delegate to existing method
+                self.generate_var_declaration(name, type_annotation, initializer)
+            }
+            Pattern::Tuple { .. } => {
+                // Tuple destructuring pattern
+                let init = initializer.as_ref().ok_or_else(|| {
+                    CompileError::codegen_error(
+                        "Tuple destructuring requires an initializer".to_string(),
+                        None,
+                    )
+                })?;
+                let tuple_value = self.generate_expression(init)?;
+                self.bind_tuple_pattern(pattern, tuple_value)
+            }
+            Pattern::Wildcard => {
+                // Evaluated for its side effects only
+                if let Some(init) = initializer {
+                    self.generate_expression(init)?;
+                }
+                Ok(())
+            }
+            _ => {
+                // For now, only support variable and tuple patterns
+                Err(CompileError::codegen_error(
+                    "Complex patterns not yet supported in variable declarations".to_string(),
+                    None,
+                ))
+            }
+        }
     }
 
-    /// Compiles the module to an object file.
-    pub fn compile_to_object_file(&self, filename: &str) -> Result<()> {
-        let triple = TargetMachine::get_default_triple();
-        let target = Target::from_triple(&triple).map_err(|e| {
-            CompileError::codegen_error(format!("Failed to create target: {}", e), None)
-        })?;
-
-        let machine = target
-            .create_target_machine(
-                &triple,
-                "x86-64",
-                "+avx2",
-                self.optimization_level,
-                RelocMode::Default,
-                CodeModel::Default,
-            )
-            .ok_or_else(|| {
-                CompileError::codegen_error("Failed to create target machine".to_string(), None)
-            })?;
-
-        let result = machine.write_to_file(&self.module, FileType::Object, Path::new(filename));
-
-        if let Err(e) = result {
-            return Err(CompileError::codegen_error(
-                format!("Failed to write object file: {}", e),
+    /// Binds the variables of a destructuring `let`, extracting tuple elements
+    /// into stack slots of their own.
+    fn bind_tuple_pattern(&mut self, pattern: &Pattern, value: BasicValueEnum<'ctx>) -> Result<()> {
+        match pattern {
+            Pattern::Variable(var_name) => {
+                let function = self
+                    .builder
+                    .get_insert_block()
+                    .and_then(|block| block.get_parent())
+                    .ok_or_else(|| {
+                        CompileError::codegen_error(
+                            "Variable declaration outside of function context".to_string(),
+                            None,
+                        )
+                    })?;
+                let alloca = self.create_entry_block_alloca(function, var_name, value.get_type())?;
+                self.builder.build_store(alloca, value).map_err(|e| {
+                    CompileError::codegen_error(format!("Failed to store variable: {:?}", e), None)
+                })?;
+                self.variables.insert(var_name.clone(), alloca);
+                Ok(())
+            }
+            Pattern::Wildcard => Ok(()),
+            Pattern::Tuple { patterns } => {
+                for (index, pattern) in patterns.iter().enumerate() {
+                    let element_value = self
+                        .builder
+                        .build_extract_value(value.into_struct_value(), index as u32, &format!("tuple_{}", index))
+                        .map_err(|e| {
+                            CompileError::codegen_error(
+                                format!("Failed to extract tuple element: {:?}", e),
+                                None,
+                            )
+                        })?;
+                    self.bind_tuple_pattern(pattern, element_value)?;
+                }
+                Ok(())
+            }
+            _ => Err(CompileError::codegen_error(
+                format!("Pattern '{}' cannot be used in a let binding", pattern),
                 None,
-            ));
+            )),
         }
-
-        Ok(())
     }
 
-    /// Generates code for array indexing operations.
-    fn generate_array_index(
+    /// Generates code for a variable declaration.
+    fn generate_var_declaration(
         &mut self,
-        array_expr: &Box<Expr>,
-        index_expr: &Box<Expr>,
-    ) -> Result<BasicValueEnum<'ctx>> {
-        // Generate the array expression
-        let array_value = self.generate_expression(array_expr)?;
-
-        // Generate the index expression - this should be an integer
-        let index_value = self.generate_expression(index_expr)?;
-
-        // Ensure the index is an integer type
-        let index_int = if index_value.is_int_value() {
-            index_value.into_int_value()
+        name: &str,
+        type_annotation: &Option<TypeAnnotation>,
+        initializer: &Option<Expr>,
+    ) -> Result<()> {
+        // Determine the variable type
+        let var_type = if let Some(type_ann) = type_annotation {
+            match type_ann.name.as_str() {
+                "i32" => self.context.i32_type().into(),
+                "i64" => self.context.i64_type().into(),
+                "f32" => self.context.f32_type().into(),
+                "f64" => self.context.f64_type().into(),
+                "bool" => self.context.bool_type().into(),
+                "string" => self
+                    .context
+                    .i8_type()
+                    .ptr_type(AddressSpace::default())
+                    .into(),
+                "Vec" => self
+                    .context
+                    .i8_type()
+                    .ptr_type(AddressSpace::default())
+                    .into(),
+                "Vec<i32>" => self
+                    .context
+                    .i8_type()
+                    .ptr_type(AddressSpace::default())
+                    .into(),
+                "HashMap" => self
+                    .context
+                    .i8_type()
+                    .ptr_type(AddressSpace::default())
+                    .into(),
+                "HashSet" => self
+                    .context
+                    .i8_type()
+                    .ptr_type(AddressSpace::default())
+                    .into(),
+                "String" => self
+                    .context
+                    .i8_type()
+                    .ptr_type(AddressSpace::default())
+                    .into(),
+                // Structs, SIMD vectors and type parameters of generic functions
+                _ => self.resolve_type_annotation(type_ann)?,
+            }
+        } else if let Some(_init) = initializer {
+            // Skip type inference - we'll determine type when we generate the expression
+            // Use a placeholder type to avoid early generation
+            self.context.i32_type().into()  // Default to i32, will be corrected later
         } else {
+            // No type annotation and no initializer - error
             return Err(CompileError::codegen_error(
-                "Array index must be an integer".to_string(),
+                format!(
+                    "Variable '{}' needs either a type annotation or an initializer",
+                    name
+                ),
                 None,
             ));
         };
 
-        // Check if this is a SIMD vector indexing operation
-        if array_value.is_vector_value() {
-            // For SIMD vectors, use extractelement instruction
-            let vector_value = array_value.into_vector_value();
-            
-            let extracted_element = self
-                .builder
-                .build_extract_element(vector_value, index_int, "simd_extract")
-                .map_err(|e| {
-                    CompileError::codegen_error(
-                        format!("Failed to extract SIMD vector element: {:?}", e),
-                        None,
-                    )
-                })?;
+        // Allocate space on the stack for the variable
+        let function = self
+            .builder
+            .get_insert_block()
+            .and_then(|block| block.get_parent())
+            .ok_or_else(|| {
+                CompileError::codegen_error(
+                    "Variable declaration outside of function context".to_string(),
+                    None,
+                )
+            })?;
 
-            Ok(extracted_element)
-        } else {
-            // For regular arrays, use GEP instruction
-            let array_ptr = if array_value.is_pointer_value() {
-                array_value.into_pointer_value()
+        let alloca = self.create_entry_block_alloca(function, name, var_type)?;
+
+        // Store the initial value if provided
+        if let Some(init) = initializer {
+            let init_value = self.generate_expression(init)?;
+
+            // Update the variable type and allocation based on the actual value type
+            // This eliminates the need for dual expression generation
+            let actual_type = init_value.get_type();
+            let final_alloca = if var_type != actual_type {
+                // Create new allocation with correct type
+                let function = self.builder.get_insert_block().unwrap().get_parent().unwrap();
+                self.create_entry_block_alloca(function, name, actual_type)?
             } else {
-                // If array value is not a pointer, we need to allocate space and store it
-                // This handles cases like function returns or literal arrays
-                let array_type = array_value.get_type();
-                let alloca = self.builder.build_alloca(array_type, "array_temp_alloca")
-                    .map_err(|e| CompileError::codegen_error(
-                        format!("Failed to create temporary allocation for array: {:?}", e),
-                        None,
-                    ))?;
-                
-                // Store the array value in the allocated space
-                self.builder.build_store(alloca, array_value)
-                    .map_err(|e| CompileError::codegen_error(
-                        format!("Failed to store array value: {:?}", e),
-                        None,
-                    ))?;
-                
                 alloca
             };
 
-            // Build GEP (Get Element Pointer) instruction for array indexing
-            // This calculates the address of array[index]
-            let element_ptr = unsafe {
-                self.builder
-                    .build_gep(
-                        array_ptr,
-                        &[self.context.i32_type().const_zero(), index_int],
-                        "array_index_gep",
-                    )
-                    .map_err(|e| {
-                        CompileError::codegen_error(
-                            format!("Failed to build GEP instruction: {:?}", e),
-                            None,
-                        )
-                    })?
-            };
+            // For array literals, we need to handle them specially
+            // Array literals return pointers to arrays, but we shouldn't double-allocate
+            if let BasicValueEnum::PointerValue(ptr_val) = init_value {
+                // Check if this is pointing to an array type
+                if let inkwell::types::AnyTypeEnum::ArrayType(_) = ptr_val.get_type().get_element_type() {
+                    // For arrays, store the pointer directly instead of creating another alloca level
+                    self.variables.insert(name.to_string(), ptr_val);
+                    return Ok(());
+                }
+            }
 
-            // Load the value from the calculated address
-            let loaded_value = self
-                .builder
-                .build_load(element_ptr, "array_element_load")
-                .map_err(|e| {
-                    CompileError::codegen_error(format!("Failed to load array element: {:?}", e), None)
-                })?;
+            self.builder.build_store(final_alloca, init_value).map_err(|e| {
+                CompileError::codegen_error(format!("Failed to store variable: {:?}", e), None)
+            })?;
 
-            Ok(loaded_value)
+            // Update the variable mapping with the final allocation
+            self.variables.insert(name.to_string(), final_alloca);
+            return Ok(());
         }
-    }
-
-    /// Generates code for array slicing: array[start:end]
-    fn generate_array_slice(
-        &mut self,
-        array_expr: &Box<Expr>,
-        start_expr: &Box<Expr>,
-        end_expr: &Box<Expr>,
-    ) -> Result<BasicValueEnum<'ctx>> {
-        // Generate the array expression
-        let array_value = self.generate_expression(array_expr)?;
-        let start_value = self.generate_expression(start_expr)?;
-        let end_value = self.generate_expression(end_expr)?;
-
-        // Ensure indices are integers
-        let start_int = start_value.into_int_value();
-        let end_int = end_value.into_int_value();
-
-        // Get array pointer
-        let array_ptr = array_value.into_pointer_value();
-
-        // Calculate slice length
-        let _slice_length = self
-            .builder
-            .build_int_sub(end_int, start_int, "slice_length")
-            .unwrap();
 
-        // For now, create a pointer offset to simulate slicing
-        // This creates a new pointer that points to the start of the slice
-        let slice_start_ptr = unsafe {
-            self.builder
-                .build_gep(
-                    array_ptr,
-                    &[self.context.i32_type().const_zero(), start_int],
-                    "slice_start_ptr",
-                )
-                .unwrap()
-        };
+        // Add the variable to our map
+        self.variables.insert(name.to_string(), alloca);
 
-        // Return the slice start pointer
-        // Note: This is a simplified implementation that returns a pointer to the slice start
-        // A full implementation would create a new array with proper bounds
-        Ok(slice_start_ptr.into())
+        Ok(())
     }
 
-    /// Converts SIMD vector type to LLVM vector type.
-    fn simd_type_to_llvm(&self, simd_type: &SIMDVectorType) -> Result<VectorType<'ctx>> {
-        match simd_type {
-            // Float vectors
-            SIMDVectorType::F32x2 => Ok(self.context.f32_type().vec_type(2)),
-            SIMDVectorType::F32x4 => Ok(self.context.f32_type().vec_type(4)),
-            SIMDVectorType::F32x8 => Ok(self.context.f32_type().vec_type(8)),
-            SIMDVectorType::F32x16 => Ok(self.context.f32_type().vec_type(16)),
-            SIMDVectorType::F64x2 => Ok(self.context.f64_type().vec_type(2)),
-            SIMDVectorType::F64x4 => Ok(self.context.f64_type().vec_type(4)),
-            SIMDVectorType::F64x8 => Ok(self.context.f64_type().vec_type(8)),
-
-            // Integer vectors
-            SIMDVectorType::I32x2 => Ok(self.context.i32_type().vec_type(2)),
-            SIMDVectorType::I32x4 => Ok(self.context.i32_type().vec_type(4)),
-            SIMDVectorType::I32x8 => Ok(self.context.i32_type().vec_type(8)),
-            SIMDVectorType::I32x16 => Ok(self.context.i32_type().vec_type(16)),
-            SIMDVectorType::I64x2 => Ok(self.context.i64_type().vec_type(2)),
-            SIMDVectorType::I64x4 => Ok(self.context.i64_type().vec_type(4)),
-            SIMDVectorType::I64x8 => Ok(self.context.i64_type().vec_type(8)),
-            SIMDVectorType::I16x4 => Ok(self.context.i16_type().vec_type(4)),
-            SIMDVectorType::I16x8 => Ok(self.context.i16_type().vec_type(8)),
-            SIMDVectorType::I16x16 => Ok(self.context.i16_type().vec_type(16)),
-            SIMDVectorType::I16x32 => Ok(self.context.i16_type().vec_type(32)),
-            SIMDVectorType::I8x8 => Ok(self.context.i8_type().vec_type(8)),
-            SIMDVectorType::I8x16 => Ok(self.context.i8_type().vec_type(16)),
-            SIMDVectorType::I8x32 => Ok(self.context.i8_type().vec_type(32)),
-            SIMDVectorType::I8x64 => Ok(self.context.i8_type().vec_type(64)),
+    /// Generates code for a return statement.
+    fn generate_return(&mut self, expr: &Option<Expr>) -> Result<()> {
+        match expr {
+            Some(e) => {
+                let return_value = self.generate_expression(e)?;
+                self.builder
+                    .build_return(Some(&return_value))
+                    .map_err(|e| {
+                        CompileError::codegen_error(
+                            format!("Failed to build return: {:?}", e),
+                            None,
+                        )
+                    })?;
+            }
+            None => {
+                self.builder.build_return(None).map_err(|e| {
+                    CompileError::codegen_error(format!("Failed to build return: {:?}", e), None)
+                })?;
+            }
+        }
 
-            // Unsigned integer vectors (LLVM treats as signed)
-            SIMDVectorType::U32x4 => Ok(self.context.i32_type().vec_type(4)),
-            SIMDVectorType::U32x8 => Ok(self.context.i32_type().vec_type(8)),
-            SIMDVectorType::U16x8 => Ok(self.context.i16_type().vec_type(8)),
-            SIMDVectorType::U16x16 => Ok(self.context.i16_type().vec_type(16)),
-            SIMDVectorType::U8x4 => Ok(self.context.i8_type().vec_type(4)),
-            SIMDVectorType::U8x8 => Ok(self.context.i8_type().vec_type(8)),
-            SIMDVectorType::U8x16 => Ok(self.context.i8_type().vec_type(16)),
-            SIMDVectorType::U8x32 => Ok(self.context.i8_type().vec_type(32)),
+        Ok(())
+    }
 
-            // Mask types (represented as integer vectors in LLVM)
-            SIMDVectorType::Mask8 => Ok(self.context.bool_type().vec_type(8)),
-            SIMDVectorType::Mask16 => Ok(self.context.bool_type().vec_type(16)),
-            SIMDVectorType::Mask32 => Ok(self.context.bool_type().vec_type(32)),
-            SIMDVectorType::Mask64 => Ok(self.context.bool_type().vec_type(64)),
-        }
+    /// Generates code for an expression.
+    fn generate_expression(&mut self, expr: &Expr) -> Result<BasicValueEnum<'ctx>> {
+        self.generate_expression_node(expr).map_err(|error| error.with_span(expr.span()))
     }
 
-    /// Generates a SIMD element with the correct type for the target vector.
-    fn generate_simd_element(
-        &mut self,
-        element: &Expr,
-        vector_type: &SIMDVectorType,
-    ) -> Result<BasicValueEnum<'ctx>> {
-        match element {
-            Expr::Literal(Literal::Float(value)) => {
-                // Generate float with the correct precision for the vector type
-                match vector_type.element_type() {
-                    "f32" => {
-                        let float_type = self.context.f32_type();
-                        Ok(float_type.const_float(*value).into())
-                    }
-                    "f64" => {
-                        let float_type = self.context.f64_type();
-                        Ok(float_type.const_float(*value).into())
-                    }
-                    _ => Err(CompileError::codegen_error(
-                        format!(
-                            "Cannot use float literal in {} vector",
-                            vector_type.element_type()
-                        ),
-                        None,
-                    )),
-                }
+    fn generate_expression_node(&mut self, expr: &Expr) -> Result<BasicValueEnum<'ctx>> {
+        match expr {
+            Expr::Literal(lit, _) => self.generate_literal(lit),
+            Expr::Variable(name, _)
+                if !self.variables.contains_key(name) && self.constants.contains_key(name) =>
+            {
+                self.generate_constant(name)
+            }
+            Expr::Variable(name, _)
+                if !self.variables.contains_key(name)
+                    && self.functions.contains_key(&self.resolve_function_name(name)) =>
+            {
+                self.generate_function_value(name)
+            }
+            Expr::Variable(name, _) => self.generate_variable_access(name),
+            Expr::Binary(left, op, right, span) => {
+                self.generate_binary_expression(left, op, right, span)
+            }
+            Expr::Unary(op, expr, _) => self.generate_unary_expression(op, expr),
+            Expr::Call(callee, args, span) => self.generate_function_call(callee, args, span),
+            Expr::Grouping(expr, _) => self.generate_expression(expr),
+            Expr::SIMD(simd_expr, _) => {
+                // Comprehensive SIMD expression code generation
+                self.generate_simd_expression(simd_expr)
             }
-            Expr::Literal(Literal::Integer(value)) => {
-                // Generate integer with the correct width for the vector type
-                match vector_type.element_type() {
-                    "i8" => {
-                        let int_type = self.context.i8_type();
-                        Ok(int_type.const_int(*value as u64, true).into())
-                    }
-                    "i16" => {
-                        let int_type = self.context.i16_type();
-                        Ok(int_type.const_int(*value as u64, true).into())
-                    }
-                    "i32" => {
-                        let int_type = self.context.i32_type();
-                        Ok(int_type.const_int(*value as u64, true).into())
-                    }
-                    "i64" => {
-                        let int_type = self.context.i64_type();
-                        Ok(int_type.const_int(*value as u64, true).into())
-                    }
-                    "u8" | "u16" | "u32" => {
-                        // For unsigned types, use the corresponding signed type in LLVM
-                        // The actual signed/unsigned interpretation happens at the operation level
-                        match vector_type.element_type() {
-                            "u8" => Ok(self
-                                .context
-                                .i8_type()
-                                .const_int(*value as u64, false)
-                                .into()),
-                            "u16" => Ok(self
-                                .context
-                                .i16_type()
-                                .const_int(*value as u64, false)
-                                .into()),
-                            "u32" => Ok(self
-                                .context
-                                .i32_type()
-                                .const_int(*value as u64, false)
-                                .into()),
-                            _ => unreachable!(),
-                        }
-                    }
-                    _ => Err(CompileError::codegen_error(
-                        format!(
-                            "Cannot use integer literal in {} vector",
-                            vector_type.element_type()
-                        ),
-                        None,
-                    )),
-                }
+            // For now, we'll add placeholders for other expression types
+            Expr::Index(array_expr, index_expr, _) => {
+                self.generate_array_index(array_expr, index_expr)
             }
-            _ => {
-                // For non-literal elements, generate normally and let type checking handle compatibility
-                self.generate_expression(element)
+            Expr::Slice { array, start, end, .. } => self.generate_array_slice(array, start, end),
+            Expr::FieldAccess(struct_expr, field_name, _) => {
+                self.generate_field_access(struct_expr, field_name)
+            }
+            Expr::StructLiteral { name, fields, span } => {
+                self.generate_struct_literal(name, fields, span)
+            }
+            Expr::EnumLiteral {
+                enum_name,
+                variant,
+                args,
+                ..
+            } => self.generate_enum_literal(enum_name, variant, args),
+            Expr::Match { value, arms, .. } => self.generate_match_expression(value, arms),
+            Expr::Block(statements, _) => self.generate_block_expression(statements),
+            Expr::If { condition, then_branch, else_branch } => {
+                self.generate_if_expression(condition, then_branch, else_branch)
+            }
+            Expr::Cast { expr, target_type, .. } => {
+                // Simple cast - for now just generate the expression
+                self.generate_expression(expr)
+            }
+            Expr::Tuple { elements, .. } => self.generate_tuple_literal(elements),
+            Expr::Closure {
+                params,
+                return_type,
+                body,
+                span,
+            } => self.generate_closure(params, return_type, body, span),
+            Expr::ArrayRepeat {
+                value,
+                count,
+                vector_type,
+                ..
+            } => {
+                let count = self.constant_length(count)?;
+                let expanded = crate::ast::expand_array_repeat(value, count, vector_type)
+                    .ok_or_else(|| {
+                        CompileError::codegen_error(
+                            format!("Repeated array value '{}' must be a literal", value),
+                            None,
+                        )
+                    })?;
+                self.generate_expression(&expanded)
             }
+            Expr::Comptime(statements, _) => self.generate_comptime_block(statements),
+            // Pointer operations are only gated by the type checker
+            Expr::Unsafe(statements, _) => self.generate_block_expression(statements),
         }
     }
 
-    /// Generates code for SIMD vector literal.
-    fn generate_simd_vector_literal(
+    /// Generates code for a block expression
+    fn generate_block_expression(
         &mut self,
-        elements: &[Expr],
-        vector_type: &SIMDVectorType,
-    ) -> Result<VectorValue<'ctx>> {
-        // Generate values for each element with correct type for the vector
-        let mut element_values = Vec::new();
-        for element in elements {
-            let element_value = self.generate_simd_element(element, vector_type)?;
-            element_values.push(element_value);
-        }
+        statements: &Vec<Stmt>,
+    ) -> Result<BasicValueEnum<'ctx>> {
+        let mut last_value = None;
 
-        // Validate element count matches vector width
-        if element_values.len() != vector_type.width() {
-            return Err(CompileError::codegen_error(
-                format!(
-                    "Vector literal element count mismatch: {} expected {}, got {}",
-                    vector_type,
-                    vector_type.width(),
-                    element_values.len()
-                ),
-                None,
-            ));
+        // Generate code for all statements in the block
+        for stmt in statements {
+            match stmt {
+                Stmt::Expression(expr, _) => {
+                    // For expression statements, we might want to use the value
+                    last_value = Some(self.generate_expression(expr)?);
+                }
+                _ => {
+                    // For other statements, just generate their code
+                    self.generate_statement(stmt)?;
+                }
+            }
         }
 
-        // Create LLVM vector constant based on element type
-        match vector_type.element_type() {
-            "f32" | "f64" => {
-                // For float vectors, create a constant vector
-                if let Some(first_val) = element_values.first() {
-                    if let BasicValueEnum::FloatValue(_float_val) = first_val {
-                        let const_vals: Vec<_> = element_values
-                            .iter()
-                            .filter_map(|v| {
-                                if let BasicValueEnum::FloatValue(fv) = v {
-                                    Some(*fv)
-                                } else {
-                                    None
-                                }
-                            })
-                            .collect();
+        // Return the last expression value, or unit if there was none
+        match last_value {
+            Some(value) => Ok(value),
+            None => {
+                // Return unit value (represented as i32 0 for now)
+                let int_type = self.context.i32_type();
+                let zero = int_type.const_int(0, false);
+                Ok(zero.into())
+            }
+        }
+    }
 
-                        if const_vals.len() == element_values.len() {
-                            Ok(VectorType::const_vector(&const_vals))
-                        } else {
-                            // Handle mixed constant/runtime elements using efficient vector construction
-                            self.generate_mixed_vector_from_elements(&element_values, vector_type)
-                        }
-                    } else {
-                        Err(CompileError::codegen_error(
-                            "Expected float values for float vector".to_string(),
-                            None,
-                        ))
-                    }
-                } else {
-                    Err(CompileError::codegen_error(
-                        "Empty vector literal".to_string(),
-                        None,
-                    ))
+    /// Generates code for a literal value.
+    fn generate_literal(&mut self, literal: &Literal) -> Result<BasicValueEnum<'ctx>> {
+        match literal {
+            Literal::Integer(value) => {
+                let int_type = self.context.i32_type();
+                Ok(int_type.const_int(*value as u64, true).into())
+            }
+            Literal::TypedInteger { value, int_type } => {
+                use crate::ast::IntegerType;
+                match int_type {
+                    IntegerType::I8 => Ok(self.context.i8_type().const_int(*value as u64, true).into()),
+                    IntegerType::I16 => Ok(self.context.i16_type().const_int(*value as u64, true).into()),
+                    IntegerType::I32 => Ok(self.context.i32_type().const_int(*value as u64, true).into()),
+                    IntegerType::I64 => Ok(self.context.i64_type().const_int(*value as u64, true).into()),
+                    IntegerType::U8 => Ok(self.context.i8_type().const_int(*value as u64, false).into()),
+                    IntegerType::U16 => Ok(self.context.i16_type().const_int(*value as u64, false).into()),
+                    IntegerType::U32 => Ok(self.context.i32_type().const_int(*value as u64, false).into()),
+                    IntegerType::U64 => Ok(self.context.i64_type().const_int(*value as u64, false).into()),
                 }
             }
-            _ => {
-                // For integer and boolean vectors
-                if let Some(first_val) = element_values.first() {
-                    if let BasicValueEnum::IntValue(_int_val) = first_val {
-                        let const_vals: Vec<_> = element_values
-                            .iter()
-                            .filter_map(|v| {
-                                if let BasicValueEnum::IntValue(iv) = v {
-                                    Some(*iv)
-                                } else {
-                                    None
-                                }
-                            })
-                            .collect();
-
-                        if const_vals.len() == element_values.len() {
-                            Ok(VectorType::const_vector(&const_vals))
-                        } else {
-                            // Handle mixed constant/runtime elements using efficient vector construction
-                            self.generate_mixed_vector_from_elements(&element_values, vector_type)
-                        }
-                    } else {
-                        Err(CompileError::codegen_error(
-                            "Expected integer values for integer vector".to_string(),
+            Literal::Float(value) => {
+                let float_type = self.context.f32_type();
+                Ok(float_type.const_float(*value).into())
+            }
+            Literal::String(value) => {
+                // Create a global string constant
+                let string_value = self
+                    .builder
+                    .build_global_string_ptr(value, "string_literal")
+                    .map_err(|e| {
+                        CompileError::codegen_error(
+                            format!("Failed to build string: {:?}", e),
                             None,
-                        ))
-                    }
+                        )
+                    })?;
+                Ok(string_value.as_pointer_value().into())
+            }
+            Literal::Boolean(value) => {
+                let bool_type = self.context.bool_type();
+                Ok(bool_type.const_int(*value as u64, false).into())
+            }
+            Literal::Vector {
+                elements,
+                vector_type,
+            } => {
+                if let Some(vtype) = vector_type {
+                    // SIMD vector literal with type annotation
+                    let element_exprs: Vec<Expr> = elements
+                        .iter()
+                        .map(|lit| Expr::Literal(lit.clone(), Span::default()))
+                        .collect();
+
+                    let vector_val = self.generate_simd_vector_literal(&element_exprs, vtype)?;
+                    Ok(vector_val.into())
                 } else {
-                    Err(CompileError::codegen_error(
-                        "Empty vector literal".to_string(),
-                        None,
-                    ))
+                    // Regular array literal without SIMD type annotation
+                    self.generate_array_literal(elements)
                 }
             }
         }
     }
 
-    /// Generates a vector from mixed constant/runtime elements using efficient memory-based construction.
-    /// This avoids the problematic insertelement chain that can cause LLVM optimization failures.
-    fn generate_mixed_vector_from_elements(
-        &mut self,
-        element_values: &[BasicValueEnum<'ctx>],
-        vector_type: &SIMDVectorType,
-    ) -> Result<VectorValue<'ctx>> {
-        // Get the LLVM vector type
-        let llvm_vector_type = self.simd_type_to_llvm(vector_type)?;
-        
-        // Get the element type
-        let element_type: inkwell::types::BasicTypeEnum = match vector_type.element_type() {
-            "f32" => self.context.f32_type().into(),
-            "f64" => self.context.f64_type().into(),
-            "i8" | "u8" => self.context.i8_type().into(),
-            "i16" | "u16" => self.context.i16_type().into(),
-            "i32" | "u32" => self.context.i32_type().into(),
-            "i64" | "u64" => self.context.i64_type().into(),
-            _ => return Err(CompileError::codegen_error(
-                format!("Unsupported vector element type: {}", vector_type.element_type()),
+    /// Generates code for an array literal without SIMD type annotation.
+    fn generate_array_literal(&mut self, elements: &Vec<Literal>) -> Result<BasicValueEnum<'ctx>> {
+        if elements.is_empty() {
+            return Err(CompileError::codegen_error(
+                "Empty array literals are not yet supported".to_string(),
                 None,
-            )),
-        };
-        
-        // Allocate temporary array for the vector elements
-        let array_type = element_type.array_type(element_values.len() as u32);
-        let array_alloca = self.builder.build_alloca(array_type, "temp_vector_array")
-            .map_err(|e| CompileError::codegen_error(format!("Failed to allocate vector array: {:?}", e), None))?;
-        
-        // Store each element in the array
-        for (i, element_value) in element_values.iter().enumerate() {
-            let index = self.context.i32_type().const_int(i as u64, false);
-            let element_ptr = unsafe {
-                self.builder.build_gep(array_alloca, &[self.context.i32_type().const_zero(), index], &format!("elem_ptr_{}", i))
-                    .map_err(|e| CompileError::codegen_error(format!("Failed to get element pointer: {:?}", e), None))?
-            };
-            
-            self.builder.build_store(element_ptr, *element_value)
-                .map_err(|e| CompileError::codegen_error(format!("Failed to store element: {:?}", e), None))?;
-        }
-        
-        // Cast the array pointer to a vector pointer
-        let vector_ptr_type = llvm_vector_type.ptr_type(AddressSpace::default());
-        let vector_ptr = self.builder.build_bitcast(array_alloca, vector_ptr_type, "vector_ptr")
-            .map_err(|e| CompileError::codegen_error(format!("Failed to bitcast to vector pointer: {:?}", e), None))?;
-        
-        // Load the vector from memory - this is much more efficient than insertelement chains
-        if let BasicValueEnum::PointerValue(ptr) = vector_ptr {
-            let vector_value = self.builder.build_load(ptr, "vector_load")
-                .map_err(|e| CompileError::codegen_error(format!("Failed to load vector: {:?}", e), None))?;
-                
-            match vector_value {
-                BasicValueEnum::VectorValue(v) => Ok(v),
-                _ => Err(CompileError::codegen_error("Vector load did not produce vector value".to_string(), None)),
-            }
-        } else {
-            Err(CompileError::codegen_error("Bitcast did not produce pointer".to_string(), None))
+            ));
         }
-    }
-
-    /// Generates code for SIMD element-wise operations.
-    fn generate_simd_elementwise(
-        &mut self,
-        left: &Expr,
-        operator: &SIMDOperator,
-        right: &Expr,
-    ) -> Result<BasicValueEnum<'ctx>> {
-        let left_val = self.generate_expression(left)?;
-        let right_val = self.generate_expression(right)?;
 
-        // Extract vector values
-        let (left_vec, right_vec) = match (left_val, right_val) {
-            (BasicValueEnum::VectorValue(lv), BasicValueEnum::VectorValue(rv)) => (lv, rv),
-            _ => {
-                return Err(CompileError::codegen_error(
-                    "Element-wise operations require vector operands".to_string(),
-                    None,
-                ))
+        // Generate the first element to determine the array type
+        // Use the same non-recursive logic to avoid infinite loops
+        let first_element: BasicValueEnum<'ctx> = match &elements[0] {
+            Literal::Integer(val) => {
+                let int_type = self.context.i32_type();
+                int_type.const_int(*val as u64, true).into()
             }
-        };
-
-        // Generate the appropriate LLVM instruction based on operator
-        let result = match operator {
-            // Arithmetic operations
-            SIMDOperator::DotAdd => {
-                if left_vec.get_type().get_element_type().is_float_type() {
-                    self.builder
-                        .build_float_add(left_vec, right_vec, "simd_fadd")
-                        .map(|v| v.into())
-                } else {
-                    self.builder
-                        .build_int_add(left_vec, right_vec, "simd_add")
-                        .map(|v| v.into())
+            Literal::TypedInteger { value, int_type } => {
+                use crate::ast::IntegerType;
+                match int_type {
+                    IntegerType::I8 => self.context.i8_type().const_int(*value as u64, true).into(),
+                    IntegerType::I16 => self.context.i16_type().const_int(*value as u64, true).into(),
+                    IntegerType::I32 => self.context.i32_type().const_int(*value as u64, true).into(),
+                    IntegerType::I64 => self.context.i64_type().const_int(*value as u64, true).into(),
+                    IntegerType::U8 => self.context.i8_type().const_int(*value as u64, false).into(),
+                    IntegerType::U16 => self.context.i16_type().const_int(*value as u64, false).into(),
+                    IntegerType::U32 => self.context.i32_type().const_int(*value as u64, false).into(),
+                    IntegerType::U64 => self.context.i64_type().const_int(*value as u64, false).into(),
                 }
             }
-            SIMDOperator::DotSubtract => {
-                if left_vec.get_type().get_element_type().is_float_type() {
-                    self.builder
-                        .build_float_sub(left_vec, right_vec, "simd_fsub")
-                        .map(|v| v.into())
-                } else {
-                    self.builder
-                        .build_int_sub(left_vec, right_vec, "simd_sub")
-                        .map(|v| v.into())
-                }
+            Literal::Float(val) => {
+                let float_type = self.context.f32_type();
+                float_type.const_float(*val).into()
             }
-            SIMDOperator::DotMultiply => {
-                if left_vec.get_type().get_element_type().is_float_type() {
-                    self.builder
-                        .build_float_mul(left_vec, right_vec, "simd_fmul")
-                        .map(|v| v.into())
-                } else {
-                    self.builder
-                        .build_int_mul(left_vec, right_vec, "simd_mul")
-                        .map(|v| v.into())
-                }
+            Literal::String(val) => {
+                let string_value = self
+                    .builder
+                    .build_global_string_ptr(val, "string_literal")
+                    .map_err(|e| {
+                        CompileError::codegen_error(
+                            format!("Failed to build string: {:?}", e),
+                            None,
+                        )
+                    })?;
+                string_value.as_pointer_value().into()
             }
-            SIMDOperator::DotDivide => {
-                if left_vec.get_type().get_element_type().is_float_type() {
-                    self.builder
-                        .build_float_div(left_vec, right_vec, "simd_fdiv")
-                        .map(|v| v.into())
-                } else {
-                    self.builder
-                        .build_int_signed_div(left_vec, right_vec, "simd_sdiv")
-                        .map(|v| v.into())
-                }
+            Literal::Boolean(val) => {
+                let bool_type = self.context.bool_type();
+                bool_type.const_int(*val as u64, false).into()
+            }
+            Literal::Vector { .. } => {
+                return Err(CompileError::codegen_error(
+                    "Nested array literals are not yet supported".to_string(),
+                    None,
+                ));
             }
+        };
+        let element_type = first_element.get_type();
+        let array_size = elements.len() as u32;
 
-            // Bitwise operations
-            SIMDOperator::DotAnd => self
-                .builder
-                .build_and(left_vec, right_vec, "simd_and")
-                .map(|v| v.into()),
-            SIMDOperator::DotOr => self
-                .builder
-                .build_or(left_vec, right_vec, "simd_or")
-                .map(|v| v.into()),
-            SIMDOperator::DotXor => self
-                .builder
-                .build_xor(left_vec, right_vec, "simd_xor")
-                .map(|v| v.into()),
+        // Create an array type
+        let array_type = element_type.array_type(array_size);
 
-            // Comparison operations
-            SIMDOperator::DotEqual => {
-                if left_vec.get_type().get_element_type().is_float_type() {
-                    self.builder
-                        .build_float_compare(
-                            FloatPredicate::OEQ,
-                            left_vec,
-                            right_vec,
-                            "simd_fcmp_eq",
-                        )
-                        .map(|v| v.into())
-                } else {
-                    self.builder
-                        .build_int_compare(IntPredicate::EQ, left_vec, right_vec, "simd_icmp_eq")
-                        .map(|v| v.into())
+        // Allocate space for the array
+        let array_alloca = self
+            .builder
+            .build_alloca(array_type, "array_literal")
+            .map_err(|e| {
+                CompileError::codegen_error(format!("Failed to allocate array: {:?}", e), None)
+            })?;
+
+        // Initialize each element
+        for (i, element_literal) in elements.iter().enumerate() {
+            // For array literals, we should only have primitive literals, not nested arrays
+            let element_value: BasicValueEnum<'ctx> = match element_literal {
+                Literal::Integer(val) => {
+                    let int_type = self.context.i32_type();
+                    int_type.const_int(*val as u64, true).into()
                 }
-            }
-            SIMDOperator::DotNotEqual => {
-                if left_vec.get_type().get_element_type().is_float_type() {
-                    self.builder
-                        .build_float_compare(
-                            FloatPredicate::ONE,
-                            left_vec,
-                            right_vec,
-                            "simd_fcmp_ne",
-                        )
-                        .map(|v| v.into())
-                } else {
-                    self.builder
-                        .build_int_compare(IntPredicate::NE, left_vec, right_vec, "simd_icmp_ne")
-                        .map(|v| v.into())
+                Literal::TypedInteger { value, int_type } => {
+                    use crate::ast::IntegerType;
+                    match int_type {
+                        IntegerType::I8 => self.context.i8_type().const_int(*value as u64, true).into(),
+                        IntegerType::I16 => self.context.i16_type().const_int(*value as u64, true).into(),
+                        IntegerType::I32 => self.context.i32_type().const_int(*value as u64, true).into(),
+                        IntegerType::I64 => self.context.i64_type().const_int(*value as u64, true).into(),
+                        IntegerType::U8 => self.context.i8_type().const_int(*value as u64, false).into(),
+                        IntegerType::U16 => self.context.i16_type().const_int(*value as u64, false).into(),
+                        IntegerType::U32 => self.context.i32_type().const_int(*value as u64, false).into(),
+                        IntegerType::U64 => self.context.i64_type().const_int(*value as u64, false).into(),
+                    }
                 }
-            }
-            SIMDOperator::DotLess => {
-                if left_vec.get_type().get_element_type().is_float_type() {
-                    self.builder
-                        .build_float_compare(
-                            FloatPredicate::OLT,
-                            left_vec,
-                            right_vec,
-                            "simd_fcmp_lt",
-                        )
-                        .map(|v| v.into())
-                } else {
-                    self.builder
-                        .build_int_compare(IntPredicate::SLT, left_vec, right_vec, "simd_icmp_slt")
-                        .map(|v| v.into())
+                Literal::Float(val) => {
+                    let float_type = self.context.f32_type();
+                    float_type.const_float(*val).into()
                 }
-            }
-            SIMDOperator::DotGreater => {
-                if left_vec.get_type().get_element_type().is_float_type() {
-                    self.builder
-                        .build_float_compare(
-                            FloatPredicate::OGT,
-                            left_vec,
-                            right_vec,
-                            "simd_fcmp_gt",
-                        )
-                        .map(|v| v.into())
-                } else {
-                    self.builder
-                        .build_int_compare(IntPredicate::SGT, left_vec, right_vec, "simd_icmp_sgt")
-                        .map(|v| v.into())
+                Literal::String(val) => {
+                    let string_value = self
+                        .builder
+                        .build_global_string_ptr(val, "string_literal")
+                        .map_err(|e| {
+                            CompileError::codegen_error(
+                                format!("Failed to build string: {:?}", e),
+                                None,
+                            )
+                        })?;
+                    string_value.as_pointer_value().into()
                 }
-            }
-            SIMDOperator::DotLessEqual => {
-                if left_vec.get_type().get_element_type().is_float_type() {
-                    self.builder
-                        .build_float_compare(
-                            FloatPredicate::OLE,
-                            left_vec,
-                            right_vec,
-                            "simd_fcmp_le",
-                        )
-                        .map(|v| v.into())
-                } else {
-                    self.builder
-                        .build_int_compare(IntPredicate::SLE, left_vec, right_vec, "simd_icmp_sle")
-                        .map(|v| v.into())
+                Literal::Boolean(val) => {
+                    let bool_type = self.context.bool_type();
+                    bool_type.const_int(*val as u64, false).into()
                 }
-            }
-            SIMDOperator::DotGreaterEqual => {
-                if left_vec.get_type().get_element_type().is_float_type() {
-                    self.builder
-                        .build_float_compare(
-                            FloatPredicate::OGE,
-                            left_vec,
-                            right_vec,
-                            "simd_fcmp_ge",
-                        )
-                        .map(|v| v.into())
-                } else {
-                    self.builder
-                        .build_int_compare(IntPredicate::SGE, left_vec, right_vec, "simd_icmp_sge")
-                        .map(|v| v.into())
+                Literal::Vector { .. } => {
+                    return Err(CompileError::codegen_error(
+                        "Nested array literals are not yet supported".to_string(),
+                        None,
+                    ));
                 }
+            };
+
+            // Verify all elements have the same type
+            if element_value.get_type() != element_type {
+                return Err(CompileError::codegen_error(
+                    "All array elements must have the same type".to_string(),
+                    None,
+                ));
             }
-        };
 
-        result.map_err(|_| {
-            CompileError::codegen_error(
-                format!("Failed to generate SIMD {:?} operation", operator),
-                None,
-            )
-        })
-    }
+            // Calculate element pointer using GEP
+            let element_ptr = unsafe {
+                self.builder
+                    .build_gep(
+                        array_alloca,
+                        &[
+                            self.context.i32_type().const_zero(),
+                            self.context.i32_type().const_int(i as u64, false),
+                        ],
+                        &format!("array_element_{}", i),
+                    )
+                    .map_err(|e| {
+                        CompileError::codegen_error(
+                            format!("Failed to build GEP for array element: {:?}", e),
+                            None,
+                        )
+                    })?
+            };
 
-    /// Attempts to generate advanced SIMD code using hardware-specific optimizations
-    fn try_generate_advanced_simd_expression(
+            // Store the element value
+            self.builder
+                .build_store(element_ptr, element_value)
+                .map_err(|e| {
+                    CompileError::codegen_error(
+                        format!("Failed to store array element: {:?}", e),
+                        None,
+                    )
+                })?;
+        }
+
+        // Return the array as a pointer (this is how arrays are typically handled)
+        Ok(array_alloca.into())
+    }
+
+    /// Generates code for a variable access.
+    fn generate_variable_access(&self, name: &str) -> Result<BasicValueEnum<'ctx>> {
+        if let Some(&ptr) = self.variables.get(name) {
+            // Check if this pointer is directly pointing to an array
+            // (arrays are stored as direct pointers, not pointer-to-pointer)
+            if let inkwell::types::AnyTypeEnum::ArrayType(_) = ptr.get_type().get_element_type() {
+                // For arrays, return the pointer directly (no load needed)
+                Ok(ptr.into())
+            } else {
+                // For regular variables, load the value
+                let load = self.builder.build_load(ptr, name).map_err(|e| {
+                    CompileError::codegen_error(format!("Failed to load variable: {:?}", e), None)
+                })?;
+                Ok(load)
+            }
+        } else {
+            Err(CompileError::codegen_error(
+                format!("Variable '{}' not found", name),
+                None,
+            ))
+        }
+    }
+
+    /// Generates code for a binary expression.
+    fn generate_binary_expression(
         &mut self,
-        simd_expr: &SIMDExpr,
+        left: &Box<Expr>,
+        op: &BinaryOp,
+        right: &Box<Expr>,
+        span: &Span,
     ) -> Result<BasicValueEnum<'ctx>> {
-        match simd_expr {
-            SIMDExpr::ElementWise {
-                left,
-                operator,
-                right,
-                ..
-            } => {
-                // Convert to AdvancedSIMDOp and generate optimized code
-                let advanced_op = match operator {
-                    SIMDOperator::DotAdd => AdvancedSIMDOp::Add {
-                        predicated: false,
-                        saturating: false,
-                    },
-                    SIMDOperator::DotMultiply => AdvancedSIMDOp::Multiply {
-                        fused: true,
-                        accumulate: false,
-                    },
-                    SIMDOperator::DotSubtract => AdvancedSIMDOp::Add {
-                        predicated: false,
-                        saturating: false,
-                    }, // Will negate second operand
-                    SIMDOperator::DotDivide => AdvancedSIMDOp::Divide {
-                        precise: true,
-                        approximate: false,
-                    },
-                    _ => {
-                        return Err(CompileError::codegen_error(
-                            "Advanced SIMD operation not supported for this operator".to_string(),
+        // Handle assignment operations FIRST, before evaluating operands
+        match op {
+            BinaryOp::Assign => {
+                // Special case: handle assignment without evaluating left side as value
+                if let Expr::Variable(var_name, _) = &**left {
+                    let right_value = self.generate_expression(right)?;
+                    if let Some(&var_ptr) = self.variables.get(var_name) {
+                        self.builder
+                            .build_store(var_ptr, right_value)
+                            .map_err(|e| {
+                                CompileError::codegen_error(
+                                    format!("Failed to store assignment: {:?}", e),
+                                    None,
+                                )
+                            })?;
+                        Ok(right_value) // Return the assigned value
+                    } else {
+                        Err(CompileError::codegen_error(
+                            format!("Variable '{}' not found for assignment", var_name),
                             None,
                         ))
                     }
-                };
-
-                // Get vector type from left operand
-                let left_val = self.generate_expression(left)?;
-                let vector_type = self.infer_simd_vector_type_from_value(&left_val)?;
-
-                // Create optimization hints
-                let optimization_hints = OptimizationHints {
-                    prefer_throughput: true,
-                    minimize_latency: false,
-                    optimize_for_size: false,
-                    cache_blocking: false,
-                    loop_unrolling: 4,
-                    vectorization_factor: vector_type.width(),
-                };
-
-                // Generate advanced SIMD code
-                let generated_code = if let Some(ref mut advanced_simd) = self.advanced_simd_codegen
-                {
-                    advanced_simd.generate_simd_code(
-                        &advanced_op,
-                        &vector_type,
-                        &optimization_hints,
-                    )
+                } else if let Expr::Unary(UnaryOp::Deref, pointer, _) = &**left {
+                    // Store through a raw pointer: *p = value
+                    let target_ptr = self.generate_expression(pointer)?.into_pointer_value();
+                    let right_value = self.generate_expression(right)?;
+                    self.builder
+                        .build_store(target_ptr, right_value)
+                        .map_err(|e| {
+                            CompileError::codegen_error(
+                                format!("Failed to store through pointer: {:?}", e),
+                                None,
+                            )
+                        })?;
+                    Ok(right_value)
                 } else {
-                    return Err(CompileError::codegen_error(
-                        "Advanced SIMD not available".to_string(),
-                        None,
-                    ));
-                };
-
-                match generated_code {
-                    Ok(generated_code) => {
-                        // Convert generated instructions to LLVM IR
-                        self.emit_advanced_simd_instructions(&generated_code, left, right)
-                    }
-                    Err(_) => Err(CompileError::codegen_error(
-                        "Failed to generate advanced SIMD code".to_string(),
+                    Err(CompileError::codegen_error(
+                        "Left side of assignment must be a variable".to_string(),
                         None,
-                    )),
+                    ))
                 }
             }
-            SIMDExpr::Reduction {
-                vector, operation, ..
-            } => {
-                // Generate advanced reduction using tree reduction if beneficial
-                let vector_val = self.generate_expression(vector)?;
-                let vector_type = self.infer_simd_vector_type_from_value(&vector_val)?;
-
-                let reduce_op = match operation {
-                    crate::ast::ReductionOp::Sum => crate::simd_advanced::ReduceOp::Sum,
-                    crate::ast::ReductionOp::Product => crate::simd_advanced::ReduceOp::Product,
-                    crate::ast::ReductionOp::Min => crate::simd_advanced::ReduceOp::Min,
-                    crate::ast::ReductionOp::Max => crate::simd_advanced::ReduceOp::Max,
-                    crate::ast::ReductionOp::And => crate::simd_advanced::ReduceOp::And,
-                    crate::ast::ReductionOp::Or => crate::simd_advanced::ReduceOp::Or,
-                    crate::ast::ReductionOp::Xor => crate::simd_advanced::ReduceOp::Xor,
-                    crate::ast::ReductionOp::Any => crate::simd_advanced::ReduceOp::Or, // Any is logical OR
-                    crate::ast::ReductionOp::All => crate::simd_advanced::ReduceOp::And, // All is logical AND
-                };
-
-                let advanced_op = AdvancedSIMDOp::Reduce {
-                    operation: reduce_op,
-                    tree: true,
-                };
-                let optimization_hints = OptimizationHints {
-                    prefer_throughput: true,
-                    minimize_latency: false,
-                    optimize_for_size: false,
-                    cache_blocking: false,
-                    loop_unrolling: 1,
-                    vectorization_factor: vector_type.width(),
-                };
+            BinaryOp::PlusAssign => {
+                if let Expr::Variable(var_name, _) = &**left {
+                    let current_val = self.generate_variable_access(var_name)?;
+                    let right_val = self.generate_expression(right)?;
 
-                let generated_code = if let Some(ref mut advanced_simd) = self.advanced_simd_codegen
-                {
-                    advanced_simd.generate_simd_code(
-                        &advanced_op,
-                        &vector_type,
-                        &optimization_hints,
-                    )
-                } else {
-                    return Err(CompileError::codegen_error(
-                        "Advanced SIMD not available".to_string(),
-                        None,
-                    ));
-                };
+                    // Handle both integer and float addition
+                    let sum = if let (
+                        BasicValueEnum::IntValue(curr_int),
+                        BasicValueEnum::IntValue(right_int),
+                    ) = (current_val, right_val)
+                    {
+                        let is_unsigned = self.is_unsigned_expression(&left.span());
+                        self.build_int_arithmetic(
+                            op,
+                            curr_int,
+                            right_int,
+                            is_unsigned,
+                            "add_assign",
+                        )?
+                        .into()
+                    } else if let (
+                        BasicValueEnum::FloatValue(curr_float),
+                        BasicValueEnum::FloatValue(right_float),
+                    ) = (current_val, right_val)
+                    {
+                        self.builder
+                            .build_float_add(curr_float, right_float, "fadd_assign")
+                            .map(|v| v.into())
+                            .map_err(|e| {
+                                CompileError::codegen_error(
+                                    format!("Failed to build fadd_assign: {:?}", e),
+                                    None,
+                                )
+                            })?
+                    } else {
+                        return Err(CompileError::codegen_error(
+                            "Type mismatch in += operation".to_string(),
+                            None,
+                        ));
+                    };
 
-                match generated_code {
-                    Ok(generated_code) => {
-                        self.emit_advanced_reduction_instructions(&generated_code, &vector_val)
+                    if let Some(&var_ptr) = self.variables.get(var_name) {
+                        self.builder.build_store(var_ptr, sum).map_err(|e| {
+                            CompileError::codegen_error(
+                                format!("Failed to store add_assign: {:?}", e),
+                                None,
+                            )
+                        })?;
+                        Ok(sum)
+                    } else {
+                        Err(CompileError::codegen_error(
+                            format!("Variable '{}' not found for +=", var_name),
+                            None,
+                        ))
                     }
-                    Err(_) => Err(CompileError::codegen_error(
-                        "Failed to generate advanced reduction".to_string(),
+                } else {
+                    Err(CompileError::codegen_error(
+                        "Left side of += must be a variable".to_string(),
                         None,
-                    )),
+                    ))
                 }
             }
-            _ => Err(CompileError::codegen_error(
-                "Advanced SIMD generation not implemented for this expression type".to_string(),
-                None,
-            )),
-        }
-    }
-
-    /// Generates code for a complete SIMD expression.
-    fn generate_simd_expression(&mut self, simd_expr: &SIMDExpr) -> Result<BasicValueEnum<'ctx>> {
-        // Generate full SIMD operations - the JIT execution engine should be properly configured
-        // to handle these operations
-        
-        // Use advanced SIMD code generation if available
-        if self.advanced_simd_codegen.is_some() {
-            if let Ok(result) = self.try_generate_advanced_simd_expression(simd_expr) {
-                return Ok(result);
-            }
-        }
+            BinaryOp::MinusAssign => {
+                if let Expr::Variable(var_name, _) = &**left {
+                    let current_val = self.generate_variable_access(var_name)?;
+                    let right_val = self.generate_expression(right)?;
 
-        // Fallback to basic SIMD implementation
-        match simd_expr {
-            SIMDExpr::VectorLiteral {
-                elements,
-                vector_type,
-                ..
-            } => {
-                if let Some(vtype) = vector_type {
-                    let vector_val = self.generate_simd_vector_literal(elements, vtype)?;
-                    Ok(vector_val.into())
+                    let diff = if let (
+                        BasicValueEnum::IntValue(curr_int),
+                        BasicValueEnum::IntValue(right_int),
+                    ) = (current_val, right_val)
+                    {
+                        let is_unsigned = self.is_unsigned_expression(&left.span());
+                        self.build_int_arithmetic(
+                            op,
+                            curr_int,
+                            right_int,
+                            is_unsigned,
+                            "sub_assign",
+                        )?
+                        .into()
+                    } else if let (
+                        BasicValueEnum::FloatValue(curr_float),
+                        BasicValueEnum::FloatValue(right_float),
+                    ) = (current_val, right_val)
+                    {
+                        self.builder
+                            .build_float_sub(curr_float, right_float, "fsub_assign")
+                            .map(|v| v.into())
+                            .map_err(|e| {
+                                CompileError::codegen_error(
+                                    format!("Failed to build fsub_assign: {:?}", e),
+                                    None,
+                                )
+                            })?
+                    } else {
+                        return Err(CompileError::codegen_error(
+                            "Type mismatch in -= operation".to_string(),
+                            None,
+                        ));
+                    };
+
+                    if let Some(&var_ptr) = self.variables.get(var_name) {
+                        self.builder.build_store(var_ptr, diff).map_err(|e| {
+                            CompileError::codegen_error(
+                                format!("Failed to store sub_assign: {:?}", e),
+                                None,
+                            )
+                        })?;
+                        Ok(diff)
+                    } else {
+                        Err(CompileError::codegen_error(
+                            format!("Variable '{}' not found for -=", var_name),
+                            None,
+                        ))
+                    }
                 } else {
                     Err(CompileError::codegen_error(
-                        "Vector literal must have explicit type annotation".to_string(),
+                        "Left side of -= must be a variable".to_string(),
                         None,
                     ))
                 }
             }
-            SIMDExpr::ElementWise {
-                left,
-                operator,
-                right,
-                ..
-            } => self.generate_simd_elementwise(left, operator, right),
-            SIMDExpr::Broadcast {
-                value, target_type, ..
-            } => {
-                // Generate scalar value
-                let scalar_val = self.generate_expression(value)?;
+            BinaryOp::MultiplyAssign => {
+                if let Expr::Variable(var_name, _) = &**left {
+                    let current_val = self.generate_variable_access(var_name)?;
+                    let right_val = self.generate_expression(right)?;
 
-                // Create vector by broadcasting scalar
-                let vector_width = target_type.width();
-                let _vector_vals = vec![scalar_val; vector_width];
+                    let product = if let (
+                        BasicValueEnum::IntValue(curr_int),
+                        BasicValueEnum::IntValue(right_int),
+                    ) = (current_val, right_val)
+                    {
+                        let is_unsigned = self.is_unsigned_expression(&left.span());
+                        self.build_int_arithmetic(
+                            op,
+                            curr_int,
+                            right_int,
+                            is_unsigned,
+                            "mul_assign",
+                        )?
+                        .into()
+                    } else if let (
+                        BasicValueEnum::FloatValue(curr_float),
+                        BasicValueEnum::FloatValue(right_float),
+                    ) = (current_val, right_val)
+                    {
+                        self.builder
+                            .build_float_mul(curr_float, right_float, "fmul_assign")
+                            .map(|v| v.into())
+                            .map_err(|e| {
+                                CompileError::codegen_error(
+                                    format!("Failed to build fmul_assign: {:?}", e),
+                                    None,
+                                )
+                            })?
+                    } else {
+                        return Err(CompileError::codegen_error(
+                            "Type mismatch in *= operation".to_string(),
+                            None,
+                        ));
+                    };
 
-                // Create constant vector
-                match scalar_val {
-                    BasicValueEnum::FloatValue(fv) => {
-                        let const_vals = vec![fv; vector_width];
-                        Ok(VectorType::const_vector(&const_vals).into())
-                    }
-                    BasicValueEnum::IntValue(iv) => {
-                        let const_vals = vec![iv; vector_width];
-                        Ok(VectorType::const_vector(&const_vals).into())
+                    if let Some(&var_ptr) = self.variables.get(var_name) {
+                        self.builder.build_store(var_ptr, product).map_err(|e| {
+                            CompileError::codegen_error(
+                                format!("Failed to store mul_assign: {:?}", e),
+                                None,
+                            )
+                        })?;
+                        Ok(product)
+                    } else {
+                        Err(CompileError::codegen_error(
+                            format!("Variable '{}' not found for *=", var_name),
+                            None,
+                        ))
                     }
-                    _ => Err(CompileError::codegen_error(
-                        "Unsupported scalar type for broadcast".to_string(),
+                } else {
+                    Err(CompileError::codegen_error(
+                        "Left side of *= must be a variable".to_string(),
                         None,
-                    )),
+                    ))
                 }
             }
-            SIMDExpr::Swizzle {
-                vector, pattern, ..
-            } => self.generate_simd_swizzle(vector, pattern),
-            SIMDExpr::Reduction {
-                vector, operation, ..
-            } => {
-                let vector_val = self.generate_expression(vector)?;
-
-                if let BasicValueEnum::VectorValue(vec_val) = vector_val {
-                    // For now, implement simple horizontal reduction by extracting and adding elements
-                    // Note: Currently using element-wise reduction; target-specific horizontal instructions could be added later
-                    let vector_type = vec_val.get_type();
-                    let element_count = vector_type.get_size();
+            BinaryOp::DivideAssign => {
+                if let Expr::Variable(var_name, _) = &**left {
+                    let current_val = self.generate_variable_access(var_name)?;
+                    let right_val = self.generate_expression(right)?;
 
-                    if element_count == 0 {
+                    let quotient = if let (
+                        BasicValueEnum::IntValue(curr_int),
+                        BasicValueEnum::IntValue(right_int),
+                    ) = (current_val, right_val)
+                    {
+                        self.builder
+                            .build_int_signed_div(curr_int, right_int, "div_assign")
+                            .map(|v| v.into())
+                            .map_err(|e| {
+                                CompileError::codegen_error(
+                                    format!("Failed to build div_assign: {:?}", e),
+                                    None,
+                                )
+                            })?
+                    } else if let (
+                        BasicValueEnum::FloatValue(curr_float),
+                        BasicValueEnum::FloatValue(right_float),
+                    ) = (current_val, right_val)
+                    {
+                        self.builder
+                            .build_float_div(curr_float, right_float, "fdiv_assign")
+                            .map(|v| v.into())
+                            .map_err(|e| {
+                                CompileError::codegen_error(
+                                    format!("Failed to build fdiv_assign: {:?}", e),
+                                    None,
+                                )
+                            })?
+                    } else {
                         return Err(CompileError::codegen_error(
-                            "Cannot reduce empty vector".to_string(),
+                            "Type mismatch in /= operation".to_string(),
                             None,
                         ));
-                    }
+                    };
 
-                    // Extract first element as accumulator
-                    let zero_index = self.context.i32_type().const_int(0, false);
-                    let mut accumulator = self
-                        .builder
-                        .build_extract_element(vec_val, zero_index, "extract_0")
-                        .map_err(|_| {
+                    if let Some(&var_ptr) = self.variables.get(var_name) {
+                        self.builder.build_store(var_ptr, quotient).map_err(|e| {
                             CompileError::codegen_error(
-                                "Failed to extract vector element".to_string(),
+                                format!("Failed to store div_assign: {:?}", e),
                                 None,
                             )
                         })?;
+                        Ok(quotient)
+                    } else {
+                        Err(CompileError::codegen_error(
+                            format!("Variable '{}' not found for /=", var_name),
+                            None,
+                        ))
+                    }
+                } else {
+                    Err(CompileError::codegen_error(
+                        "Left side of /= must be a variable".to_string(),
+                        None,
+                    ))
+                }
+            }
+            _ => {
+                // For all other operations, evaluate both operands first
+                let left_value = self.generate_expression(left)?;
+                let right_value = self.generate_expression(right)?;
 
-                    // Reduce remaining elements
-                    for i in 1..element_count {
-                        let index = self.context.i32_type().const_int(i as u64, false);
-                        let element = self
-                            .builder
-                            .build_extract_element(vec_val, index, &format!("extract_{}", i))
-                            .map_err(|_| {
-                                CompileError::codegen_error(
-                                    "Failed to extract vector element".to_string(),
-                                    None,
+                // Handle String concatenation (+ operator)
+                if let (
+                    BasicValueEnum::PointerValue(left_ptr),
+                    BasicValueEnum::PointerValue(right_ptr),
+                ) = (left_value, right_value)
+                {
+                    if matches!(op, BinaryOp::Add) {
+                        // String concatenation: call string_concat function  
+                        // String concatenation: call string_concat function
+                        if let Some(&string_concat_function) = self.functions.get("string_concat") {
+                            let result = self
+                                .builder
+                                .build_call(
+                                    string_concat_function,
+                                    &[left_ptr.into(), right_ptr.into()],
+                                    "string_concat",
                                 )
-                            })?;
-
-                        accumulator = match operation {
-                            crate::ast::ReductionOp::Sum => {
-                                if vector_type.get_element_type().is_float_type() {
-                                    if let (
-                                        BasicValueEnum::FloatValue(acc),
-                                        BasicValueEnum::FloatValue(elem),
-                                    ) = (accumulator, element)
-                                    {
-                                        self.builder
-                                            .build_float_add(acc, elem, "reduce_add")
-                                            .map(|v| v.into())
-                                            .map_err(|_| {
-                                                CompileError::codegen_error(
-                                                    "Failed to build float add".to_string(),
-                                                    None,
-                                                )
-                                            })?
-                                    } else {
-                                        return Err(CompileError::codegen_error(
-                                            "Type mismatch in reduction".to_string(),
-                                            None,
-                                        ));
-                                    }
-                                } else {
-                                    if let (
-                                        BasicValueEnum::IntValue(acc),
-                                        BasicValueEnum::IntValue(elem),
-                                    ) = (accumulator, element)
-                                    {
-                                        self.builder
-                                            .build_int_add(acc, elem, "reduce_add")
-                                            .map(|v| v.into())
-                                            .map_err(|_| {
-                                                CompileError::codegen_error(
-                                                    "Failed to build int add".to_string(),
-                                                    None,
-                                                )
-                                            })?
-                                    } else {
-                                        return Err(CompileError::codegen_error(
-                                            "Type mismatch in reduction".to_string(),
-                                            None,
-                                        ));
-                                    }
-                                }
+                                .map_err(|e| {
+                                    CompileError::codegen_error(
+                                        format!("Failed to build string concat: {:?}", e),
+                                        None,
+                                    )
+                                })?;
+                            if let Some(return_value) = result.try_as_basic_value().left() {
+                                return Ok(return_value);
                             }
-                            crate::ast::ReductionOp::Product => {
-                                if vector_type.get_element_type().is_float_type() {
-                                    if let (
-                                        BasicValueEnum::FloatValue(acc),
-                                        BasicValueEnum::FloatValue(elem),
-                                    ) = (accumulator, element)
-                                    {
-                                        self.builder
-                                            .build_float_mul(acc, elem, "reduce_mul")
-                                            .map(|v| v.into())
-                                            .map_err(|_| {
-                                                CompileError::codegen_error(
-                                                    "Failed to build float mul".to_string(),
-                                                    None,
-                                                )
-                                            })?
-                                    } else {
-                                        return Err(CompileError::codegen_error(
-                                            "Type mismatch in reduction".to_string(),
-                                            None,
-                                        ));
-                                    }
-                                } else {
-                                    if let (
-                                        BasicValueEnum::IntValue(acc),
-                                        BasicValueEnum::IntValue(elem),
-                                    ) = (accumulator, element)
-                                    {
-                                        self.builder
-                                            .build_int_mul(acc, elem, "reduce_mul")
-                                            .map(|v| v.into())
-                                            .map_err(|_| {
-                                                CompileError::codegen_error(
-                                                    "Failed to build int mul".to_string(),
-                                                    None,
-                                                )
-                                            })?
-                                    } else {
-                                        return Err(CompileError::codegen_error(
-                                            "Type mismatch in reduction".to_string(),
-                                            None,
-                                        ));
-                                    }
+                        } else {
+                            eprintln!("❌ string_concat function not found, creating fallback implementation");
+                            // Fallback: Create string_concat function using LLVM IR
+                            let string_type = self.context.i8_type().ptr_type(AddressSpace::default());
+                            let string_concat_type = string_type.fn_type(&[string_type.into(), string_type.into()], false);
+                            let string_concat_function = self.module.add_function("string_concat_fallback", string_concat_type, None);
+                            
+                            // Get malloc and strcpy functions
+                            let malloc_function = *self.functions.get("malloc").expect("malloc should be available");
+                            let strlen_function = *self.functions.get("strlen").expect("strlen should be available");
+                            
+                            // Get the current function and create basic blocks
+                            let current_function = self.builder.get_insert_block().unwrap().get_parent().unwrap();
+                            let entry_block = self.context.append_basic_block(string_concat_function, "entry");
+                            let old_block = self.builder.get_insert_block().unwrap();
+                            
+                            // Build the string concatenation function
+                            self.builder.position_at_end(entry_block);
+                            let left_param = string_concat_function.get_first_param().unwrap().into_pointer_value();
+                            let right_param = string_concat_function.get_nth_param(1).unwrap().into_pointer_value();
+                            
+                            // Get lengths
+                            let left_len = self.builder.build_call(strlen_function, &[left_param.into()], "left_len").unwrap().try_as_basic_value().left().unwrap().into_int_value();
+                            let right_len = self.builder.build_call(strlen_function, &[right_param.into()], "right_len").unwrap().try_as_basic_value().left().unwrap().into_int_value();
+                            let total_len = self.builder.build_int_add(left_len, right_len, "total_len").unwrap();
+                            let total_len_plus_null = self.builder.build_int_add(total_len, self.context.i32_type().const_int(1, false), "total_len_plus_null").unwrap();
+                            
+                            // Allocate memory
+                            let result_ptr = self.builder.build_call(malloc_function, &[total_len_plus_null.into()], "concat_result").unwrap().try_as_basic_value().left().unwrap().into_pointer_value();
+                            
+                            // Copy left string
+                            if let Some(strcpy_function) = self.functions.get("strcpy") {
+                                self.builder.build_call(*strcpy_function, &[result_ptr.into(), left_param.into()], "copy_left").unwrap();
+                                
+                                // Concatenate right string using strcat
+                                if let Some(strcat_function) = self.functions.get("strcat") {
+                                    self.builder.build_call(*strcat_function, &[result_ptr.into(), right_param.into()], "concat_right").unwrap();
                                 }
                             }
-                            crate::ast::ReductionOp::Min => {
-                                if vector_type.get_element_type().is_float_type() {
-                                    if let (
-                                        BasicValueEnum::FloatValue(acc),
-                                        BasicValueEnum::FloatValue(elem),
-                                    ) = (accumulator, element)
-                                    {
-                                        // Use fcmp + select for min
-                                        let cmp = self
-                                            .builder
-                                            .build_float_compare(
-                                                inkwell::FloatPredicate::OLT,
-                                                acc,
-                                                elem,
-                                                "min_cmp",
-                                            )
-                                            .map_err(|_| {
-                                                CompileError::codegen_error(
-                                                    "Failed to build float compare".to_string(),
-                                                    None,
-                                                )
-                                            })?;
-                                        self.builder
-                                            .build_select(cmp, acc, elem, "reduce_min")
-                                            .map(|v| v.into())
-                                            .map_err(|_| {
-                                                CompileError::codegen_error(
-                                                    "Failed to build select".to_string(),
-                                                    None,
-                                                )
-                                            })?
-                                    } else {
-                                        return Err(CompileError::codegen_error(
-                                            "Type mismatch in reduction".to_string(),
-                                            None,
-                                        ));
-                                    }
-                                } else {
-                                    if let (
-                                        BasicValueEnum::IntValue(acc),
-                                        BasicValueEnum::IntValue(elem),
-                                    ) = (accumulator, element)
-                                    {
-                                        // Use icmp + select for min
-                                        let cmp = self
-                                            .builder
-                                            .build_int_compare(
-                                                inkwell::IntPredicate::SLT,
-                                                acc,
-                                                elem,
-                                                "min_cmp",
-                                            )
-                                            .map_err(|_| {
-                                                CompileError::codegen_error(
-                                                    "Failed to build int compare".to_string(),
-                                                    None,
-                                                )
-                                            })?;
-                                        self.builder
-                                            .build_select(cmp, acc, elem, "reduce_min")
-                                            .map(|v| v.into())
-                                            .map_err(|_| {
-                                                CompileError::codegen_error(
-                                                    "Failed to build select".to_string(),
-                                                    None,
-                                                )
-                                            })?
-                                    } else {
-                                        return Err(CompileError::codegen_error(
-                                            "Type mismatch in reduction".to_string(),
-                                            None,
-                                        ));
-                                    }
-                                }
+                            
+                            self.builder.build_return(Some(&result_ptr)).unwrap();
+                            
+                            // Return to the original block and call the fallback function
+                            self.builder.position_at_end(old_block);
+                            let result = self.builder.build_call(string_concat_function, &[left_ptr.into(), right_ptr.into()], "string_concat_fallback").unwrap();
+                            if let Some(return_value) = result.try_as_basic_value().left() {
+                                return Ok(return_value);
                             }
-                            crate::ast::ReductionOp::Max => {
-                                if vector_type.get_element_type().is_float_type() {
-                                    if let (
-                                        BasicValueEnum::FloatValue(acc),
-                                        BasicValueEnum::FloatValue(elem),
-                                    ) = (accumulator, element)
-                                    {
-                                        // Use fcmp + select for max
-                                        let cmp = self
-                                            .builder
-                                            .build_float_compare(
-                                                inkwell::FloatPredicate::OGT,
-                                                acc,
-                                                elem,
-                                                "max_cmp",
-                                            )
-                                            .map_err(|_| {
-                                                CompileError::codegen_error(
-                                                    "Failed to build float compare".to_string(),
-                                                    None,
-                                                )
-                                            })?;
-                                        self.builder
-                                            .build_select(cmp, acc, elem, "reduce_max")
-                                            .map(|v| v.into())
-                                            .map_err(|_| {
-                                                CompileError::codegen_error(
-                                                    "Failed to build select".to_string(),
-                                                    None,
-                                                )
-                                            })?
-                                    } else {
-                                        return Err(CompileError::codegen_error(
-                                            "Type mismatch in reduction".to_string(),
-                                            None,
-                                        ));
-                                    }
-                                } else {
-                                    if let (
-                                        BasicValueEnum::IntValue(acc),
-                                        BasicValueEnum::IntValue(elem),
-                                    ) = (accumulator, element)
-                                    {
-                                        // Use icmp + select for max
-                                        let cmp = self
-                                            .builder
-                                            .build_int_compare(
-                                                inkwell::IntPredicate::SGT,
-                                                acc,
-                                                elem,
-                                                "max_cmp",
-                                            )
-                                            .map_err(|_| {
-                                                CompileError::codegen_error(
-                                                    "Failed to build int compare".to_string(),
-                                                    None,
-                                                )
-                                            })?;
-                                        self.builder
-                                            .build_select(cmp, acc, elem, "reduce_max")
-                                            .map(|v| v.into())
-                                            .map_err(|_| {
-                                                CompileError::codegen_error(
-                                                    "Failed to build select".to_string(),
-                                                    None,
-                                                )
-                                            })?
-                                    } else {
-                                        return Err(CompileError::codegen_error(
-                                            "Type mismatch in reduction".to_string(),
-                                            None,
-                                        ));
-                                    }
-                                }
+                        }
+                    } else if matches!(op, BinaryOp::Equal) {
+                        // String comparison: call string_equals function
+                        if let Some(&string_equals_function) = self.functions.get("string_equals_char") {
+                            let result = self
+                                .builder
+                                .build_call(
+                                    string_equals_function,
+                                    &[left_ptr.into(), right_ptr.into()],
+                                    "string_equals_char",
+                                )
+                                .map_err(|e| {
+                                    CompileError::codegen_error(
+                                        format!("Failed to build string equals: {:?}", e),
+                                        None,
+                                    )
+                                })?;
+                            if let Some(return_value) = result.try_as_basic_value().left() {
+                                return Ok(return_value);
+                            } else {
+                                eprintln!("❌ string_equals_char call failed to return basic value");
                             }
-                            crate::ast::ReductionOp::And => {
-                                if let (
-                                    BasicValueEnum::IntValue(acc),
-                                    BasicValueEnum::IntValue(elem),
-                                ) = (accumulator, element)
-                                {
-                                    // Bitwise AND for integer types
-                                    self.builder
-                                        .build_and(acc, elem, "reduce_and")
-                                        .map(|v| v.into())
-                                        .map_err(|_| {
-                                            CompileError::codegen_error(
-                                                "Failed to build bitwise and".to_string(),
-                                                None,
-                                            )
-                                        })?
-                                } else {
-                                    return Err(CompileError::codegen_error(
-                                        "And reduction only supports integer types".to_string(),
+                        } else {
+                            return Err(CompileError::codegen_error(
+                                "string_equals_char function not found - check builtin function initialization".to_string(),
+                                None,
+                            ));
+                        }
+                    } else if matches!(op, BinaryOp::GreaterEqual | BinaryOp::LessEqual | BinaryOp::Greater | BinaryOp::Less | BinaryOp::NotEqual) {
+                        // String comparison operations: use strcmp and compare result
+                        
+                        // Use existing strcmp function from minimal builtins (it should already exist)
+                        let strcmp_function = self.functions.get("strcmp").copied().ok_or_else(|| {
+                            CompileError::codegen_error(
+                                "strcmp function not found in function table".to_string(),
+                                None,
+                            )
+                        })?;
+                        
+                        // Call strcmp(left, right)
+                        let strcmp_result = self
+                            .builder
+                            .build_call(
+                                strcmp_function,
+                                &[left_ptr.into(), right_ptr.into()],
+                                "strcmp_result",
+                            )
+                            .map_err(|e| {
+                                CompileError::codegen_error(
+                                    format!("Failed to build strcmp call: {:?}", e),
+                                    None,
+                                )
+                            })?;
+                        
+                        let strcmp_value = strcmp_result.try_as_basic_value().left().unwrap().into_int_value();
+                        let zero = self.context.i32_type().const_int(0, false);
+                        
+                        // Compare strcmp result based on operation
+                        let comparison_result = match op {
+                            BinaryOp::GreaterEqual => {
+                                // strcmp(a, b) >= 0 means a >= b
+                                self.builder.build_int_compare(
+                                    IntPredicate::SGE,
+                                    strcmp_value,
+                                    zero,
+                                    "string_ge",
+                                ).map_err(|e| {
+                                    CompileError::codegen_error(
+                                        format!("Failed to build string >= comparison: {:?}", e),
                                         None,
-                                    ));
-                                }
+                                    )
+                                })?
                             }
-                            crate::ast::ReductionOp::Or => {
-                                if let (
-                                    BasicValueEnum::IntValue(acc),
-                                    BasicValueEnum::IntValue(elem),
-                                ) = (accumulator, element)
-                                {
-                                    // Bitwise OR for integer types
-                                    self.builder
-                                        .build_or(acc, elem, "reduce_or")
-                                        .map(|v| v.into())
-                                        .map_err(|_| {
-                                            CompileError::codegen_error(
-                                                "Failed to build bitwise or".to_string(),
-                                                None,
-                                            )
-                                        })?
-                                } else {
-                                    return Err(CompileError::codegen_error(
-                                        "Or reduction only supports integer types".to_string(),
+                            BinaryOp::LessEqual => {
+                                // strcmp(a, b) <= 0 means a <= b
+                                self.builder.build_int_compare(
+                                    IntPredicate::SLE,
+                                    strcmp_value,
+                                    zero,
+                                    "string_le",
+                                ).map_err(|e| {
+                                    CompileError::codegen_error(
+                                        format!("Failed to build string <= comparison: {:?}", e),
                                         None,
-                                    ));
-                                }
+                                    )
+                                })?
                             }
-                            crate::ast::ReductionOp::Xor => {
-                                if let (
-                                    BasicValueEnum::IntValue(acc),
-                                    BasicValueEnum::IntValue(elem),
-                                ) = (accumulator, element)
-                                {
-                                    // Bitwise XOR for integer types
-                                    self.builder
-                                        .build_xor(acc, elem, "reduce_xor")
-                                        .map(|v| v.into())
-                                        .map_err(|_| {
-                                            CompileError::codegen_error(
-                                                "Failed to build bitwise xor".to_string(),
-                                                None,
-                                            )
-                                        })?
-                                } else {
-                                    return Err(CompileError::codegen_error(
-                                        "Xor reduction only supports integer types".to_string(),
+                            BinaryOp::Greater => {
+                                // strcmp(a, b) > 0 means a > b
+                                self.builder.build_int_compare(
+                                    IntPredicate::SGT,
+                                    strcmp_value,
+                                    zero,
+                                    "string_gt",
+                                ).map_err(|e| {
+                                    CompileError::codegen_error(
+                                        format!("Failed to build string > comparison: {:?}", e),
+                                        None,
+                                    )
+                                })?
+                            }
+                            BinaryOp::Less => {
+                                // strcmp(a, b) < 0 means a < b
+                                self.builder.build_int_compare(
+                                    IntPredicate::SLT,
+                                    strcmp_value,
+                                    zero,
+                                    "string_lt",
+                                ).map_err(|e| {
+                                    CompileError::codegen_error(
+                                        format!("Failed to build string < comparison: {:?}", e),
+                                        None,
+                                    )
+                                })?
+                            }
+                            BinaryOp::NotEqual => {
+                                // strcmp(a, b) != 0 means a != b
+                                self.builder.build_int_compare(
+                                    IntPredicate::NE,
+                                    strcmp_value,
+                                    zero,
+                                    "string_ne",
+                                ).map_err(|e| {
+                                    CompileError::codegen_error(
+                                        format!("Failed to build string != comparison: {:?}", e),
+                                        None,
+                                    )
+                                })?
+                            }
+                            _ => unreachable!(), // We already checked the operation types above
+                        };
+                        
+                        eprintln!("✅ String comparison {:?} completed successfully", op);
+                        return Ok(comparison_result.into());
+                    }
+                }
+
+                // Handle operations based on operand types
+                if let (BasicValueEnum::IntValue(left_int), BasicValueEnum::IntValue(right_int)) =
+                    (left_value, right_value)
+                {
+                    match op {
+                        BinaryOp::Add => {
+                            let is_unsigned = self.is_unsigned_expression(span);
+                            let result = self.build_int_arithmetic(
+                                op,
+                                left_int,
+                                right_int,
+                                is_unsigned,
+                                "add",
+                            )?;
+                            Ok(result.into())
+                        }
+                        BinaryOp::Subtract => {
+                            let is_unsigned = self.is_unsigned_expression(span);
+                            let result = self.build_int_arithmetic(
+                                op,
+                                left_int,
+                                right_int,
+                                is_unsigned,
+                                "sub",
+                            )?;
+                            Ok(result.into())
+                        }
+                        BinaryOp::Multiply => {
+                            let is_unsigned = self.is_unsigned_expression(span);
+                            let result = self.build_int_arithmetic(
+                                op,
+                                left_int,
+                                right_int,
+                                is_unsigned,
+                                "mul",
+                            )?;
+                            Ok(result.into())
+                        }
+                        BinaryOp::Divide => {
+                            let result = self
+                                .builder
+                                .build_int_signed_div(left_int, right_int, "div")
+                                .map_err(|e| {
+                                    CompileError::codegen_error(
+                                        format!("Failed to build div: {:?}", e),
+                                        None,
+                                    )
+                                })?;
+                            Ok(result.into())
+                        }
+                        BinaryOp::Less => {
+                            let result = self
+                                .builder
+                                .build_int_compare(IntPredicate::SLT, left_int, right_int, "cmp_lt")
+                                .map_err(|e| {
+                                    CompileError::codegen_error(
+                                        format!("Failed to build comparison: {:?}", e),
+                                        None,
+                                    )
+                                })?;
+                            Ok(result.into())
+                        }
+                        BinaryOp::LessEqual => {
+                            let result = self
+                                .builder
+                                .build_int_compare(IntPredicate::SLE, left_int, right_int, "cmp_le")
+                                .map_err(|e| {
+                                    CompileError::codegen_error(
+                                        format!("Failed to build comparison: {:?}", e),
+                                        None,
+                                    )
+                                })?;
+                            Ok(result.into())
+                        }
+                        BinaryOp::Greater => {
+                            let result = self
+                                .builder
+                                .build_int_compare(IntPredicate::SGT, left_int, right_int, "cmp_gt")
+                                .map_err(|e| {
+                                    CompileError::codegen_error(
+                                        format!("Failed to build comparison: {:?}", e),
+                                        None,
+                                    )
+                                })?;
+                            Ok(result.into())
+                        }
+                        BinaryOp::GreaterEqual => {
+                            let result = self
+                                .builder
+                                .build_int_compare(IntPredicate::SGE, left_int, right_int, "cmp_ge")
+                                .map_err(|e| {
+                                    CompileError::codegen_error(
+                                        format!("Failed to build comparison: {:?}", e),
+                                        None,
+                                    )
+                                })?;
+                            Ok(result.into())
+                        }
+                        BinaryOp::Equal => {
+                            // Handle type coercion for integer comparisons
+                            let left_type = left_int.get_type();
+                            let right_type = right_int.get_type();
+
+                            let (coerced_left, coerced_right) = if left_type.get_bit_width()
+                                != right_type.get_bit_width()
+                            {
+                                // Promote to the larger type
+                                if left_type.get_bit_width() > right_type.get_bit_width() {
+                                    // Promote right to left's type
+                                    let coerced_right = if left_type.get_bit_width() == 64
+                                        && right_type.get_bit_width() == 32
+                                    {
+                                        self.builder
+                                            .build_int_z_extend(
+                                                right_int,
+                                                left_type,
+                                                "promote_right",
+                                            )
+                                            .map_err(|e| {
+                                                CompileError::codegen_error(
+                                                    format!("Failed to promote integer: {:?}", e),
+                                                    None,
+                                                )
+                                            })?
+                                    } else {
+                                        return Err(CompileError::codegen_error(
+                                            format!(
+                                                "Unsupported integer type promotion: {} to {}",
+                                                right_type.get_bit_width(),
+                                                left_type.get_bit_width()
+                                            ),
+                                            None,
+                                        ));
+                                    };
+                                    (left_int, coerced_right)
+                                } else {
+                                    // Promote left to right's type
+                                    let coerced_left = if right_type.get_bit_width() == 64
+                                        && left_type.get_bit_width() == 32
+                                    {
+                                        self.builder
+                                            .build_int_z_extend(
+                                                left_int,
+                                                right_type,
+                                                "promote_left",
+                                            )
+                                            .map_err(|e| {
+                                                CompileError::codegen_error(
+                                                    format!("Failed to promote integer: {:?}", e),
+                                                    None,
+                                                )
+                                            })?
+                                    } else {
+                                        return Err(CompileError::codegen_error(
+                                            format!(
+                                                "Unsupported integer type promotion: {} to {}",
+                                                left_type.get_bit_width(),
+                                                right_type.get_bit_width()
+                                            ),
+                                            None,
+                                        ));
+                                    };
+                                    (coerced_left, right_int)
+                                }
+                            } else {
+                                (left_int, right_int)
+                            };
+
+                            let result = self
+                                .builder
+                                .build_int_compare(
+                                    IntPredicate::EQ,
+                                    coerced_left,
+                                    coerced_right,
+                                    "cmp_eq",
+                                )
+                                .map_err(|e| {
+                                    CompileError::codegen_error(
+                                        format!("Failed to build comparison: {:?}", e),
+                                        None,
+                                    )
+                                })?;
+                            Ok(result.into())
+                        }
+                        BinaryOp::NotEqual => {
+                            let result = self
+                                .builder
+                                .build_int_compare(IntPredicate::NE, left_int, right_int, "cmp_ne")
+                                .map_err(|e| {
+                                    CompileError::codegen_error(
+                                        format!("Failed to build comparison: {:?}", e),
+                                        None,
+                                    )
+                                })?;
+                            Ok(result.into())
+                        }
+                        BinaryOp::Modulo => {
+                            let result = self
+                                .builder
+                                .build_int_signed_rem(left_int, right_int, "mod")
+                                .map_err(|e| {
+                                    CompileError::codegen_error(
+                                        format!("Failed to build mod: {:?}", e),
+                                        None,
+                                    )
+                                })?;
+                            Ok(result.into())
+                        }
+                        BinaryOp::And => {
+                            // Convert to boolean (i1) if needed, then perform logical AND
+                            let left_bool = if left_int.get_type().get_bit_width() == 1 {
+                                left_int
+                            } else {
+                                // Convert non-zero to true, zero to false
+                                let zero = self.context.i32_type().const_int(0, false);
+                                self.builder
+                                    .build_int_compare(IntPredicate::NE, left_int, zero, "to_bool")
+                                    .map_err(|e| {
+                                        CompileError::codegen_error(
+                                            format!("Failed to convert to bool: {:?}", e),
+                                            None,
+                                        )
+                                    })?
+                            };
+
+                            let right_bool = if right_int.get_type().get_bit_width() == 1 {
+                                right_int
+                            } else {
+                                // Convert non-zero to true, zero to false
+                                let zero = self.context.i32_type().const_int(0, false);
+                                self.builder
+                                    .build_int_compare(IntPredicate::NE, right_int, zero, "to_bool")
+                                    .map_err(|e| {
+                                        CompileError::codegen_error(
+                                            format!("Failed to convert to bool: {:?}", e),
+                                            None,
+                                        )
+                                    })?
+                            };
+
+                            let result = self
+                                .builder
+                                .build_and(left_bool, right_bool, "and")
+                                .map_err(|e| {
+                                    CompileError::codegen_error(
+                                        format!("Failed to build logical AND: {:?}", e),
+                                        None,
+                                    )
+                                })?;
+                            Ok(result.into())
+                        }
+                        BinaryOp::Or => {
+                            // Convert to boolean (i1) if needed, then perform logical OR
+                            let left_bool = if left_int.get_type().get_bit_width() == 1 {
+                                left_int
+                            } else {
+                                // Convert non-zero to true, zero to false
+                                let zero = self.context.i32_type().const_int(0, false);
+                                self.builder
+                                    .build_int_compare(IntPredicate::NE, left_int, zero, "to_bool")
+                                    .map_err(|e| {
+                                        CompileError::codegen_error(
+                                            format!("Failed to convert to bool: {:?}", e),
+                                            None,
+                                        )
+                                    })?
+                            };
+
+                            let right_bool = if right_int.get_type().get_bit_width() == 1 {
+                                right_int
+                            } else {
+                                // Convert non-zero to true, zero to false
+                                let zero = self.context.i32_type().const_int(0, false);
+                                self.builder
+                                    .build_int_compare(IntPredicate::NE, right_int, zero, "to_bool")
+                                    .map_err(|e| {
+                                        CompileError::codegen_error(
+                                            format!("Failed to convert to bool: {:?}", e),
+                                            None,
+                                        )
+                                    })?
+                            };
+
+                            let result = self
+                                .builder
+                                .build_or(left_bool, right_bool, "or")
+                                .map_err(|e| {
+                                    CompileError::codegen_error(
+                                        format!("Failed to build logical OR: {:?}", e),
+                                        None,
+                                    )
+                                })?;
+                            Ok(result.into())
+                        }
+                        // For other operations, we'll return an error for now
+                        _ => Err(CompileError::codegen_error(
+                            format!("Binary operation {:?} not yet implemented for integers", op),
+                            None,
+                        )),
+                    }
+                } else if let (
+                    BasicValueEnum::FloatValue(left_float),
+                    BasicValueEnum::FloatValue(right_float),
+                ) = (left_value, right_value)
+                {
+                    match op {
+                        BinaryOp::Add => {
+                            let result = self
+                                .builder
+                                .build_float_add(left_float, right_float, "fadd")
+                                .map_err(|e| {
+                                    CompileError::codegen_error(
+                                        format!("Failed to build fadd: {:?}", e),
+                                        None,
+                                    )
+                                })?;
+                            Ok(result.into())
+                        }
+                        BinaryOp::Subtract => {
+                            let result = self
+                                .builder
+                                .build_float_sub(left_float, right_float, "fsub")
+                                .map_err(|e| {
+                                    CompileError::codegen_error(
+                                        format!("Failed to build fsub: {:?}", e),
+                                        None,
+                                    )
+                                })?;
+                            Ok(result.into())
+                        }
+                        BinaryOp::Multiply => {
+                            let result = self
+                                .builder
+                                .build_float_mul(left_float, right_float, "fmul")
+                                .map_err(|e| {
+                                    CompileError::codegen_error(
+                                        format!("Failed to build fmul: {:?}", e),
+                                        None,
+                                    )
+                                })?;
+                            Ok(result.into())
+                        }
+                        BinaryOp::Divide => {
+                            let result = self
+                                .builder
+                                .build_float_div(left_float, right_float, "fdiv")
+                                .map_err(|e| {
+                                    CompileError::codegen_error(
+                                        format!("Failed to build fdiv: {:?}", e),
+                                        None,
+                                    )
+                                })?;
+                            Ok(result.into())
+                        }
+                        BinaryOp::Less => {
+                            let result = self
+                                .builder
+                                .build_float_compare(
+                                    FloatPredicate::OLT,
+                                    left_float,
+                                    right_float,
+                                    "fcmp_lt",
+                                )
+                                .map_err(|e| {
+                                    CompileError::codegen_error(
+                                        format!("Failed to build float comparison: {:?}", e),
+                                        None,
+                                    )
+                                })?;
+                            Ok(result.into())
+                        }
+                        BinaryOp::LessEqual => {
+                            let result = self
+                                .builder
+                                .build_float_compare(
+                                    FloatPredicate::OLE,
+                                    left_float,
+                                    right_float,
+                                    "fcmp_le",
+                                )
+                                .map_err(|e| {
+                                    CompileError::codegen_error(
+                                        format!("Failed to build float comparison: {:?}", e),
+                                        None,
+                                    )
+                                })?;
+                            Ok(result.into())
+                        }
+                        BinaryOp::Greater => {
+                            let result = self
+                                .builder
+                                .build_float_compare(
+                                    FloatPredicate::OGT,
+                                    left_float,
+                                    right_float,
+                                    "fcmp_gt",
+                                )
+                                .map_err(|e| {
+                                    CompileError::codegen_error(
+                                        format!("Failed to build float comparison: {:?}", e),
+                                        None,
+                                    )
+                                })?;
+                            Ok(result.into())
+                        }
+                        BinaryOp::GreaterEqual => {
+                            let result = self
+                                .builder
+                                .build_float_compare(
+                                    FloatPredicate::OGE,
+                                    left_float,
+                                    right_float,
+                                    "fcmp_ge",
+                                )
+                                .map_err(|e| {
+                                    CompileError::codegen_error(
+                                        format!("Failed to build float comparison: {:?}", e),
+                                        None,
+                                    )
+                                })?;
+                            Ok(result.into())
+                        }
+                        BinaryOp::Equal => {
+                            let result = self
+                                .builder
+                                .build_float_compare(
+                                    FloatPredicate::OEQ,
+                                    left_float,
+                                    right_float,
+                                    "fcmp_eq",
+                                )
+                                .map_err(|e| {
+                                    CompileError::codegen_error(
+                                        format!("Failed to build float comparison: {:?}", e),
+                                        None,
+                                    )
+                                })?;
+                            Ok(result.into())
+                        }
+                        BinaryOp::NotEqual => {
+                            let result = self
+                                .builder
+                                .build_float_compare(
+                                    FloatPredicate::ONE,
+                                    left_float,
+                                    right_float,
+                                    "fcmp_ne",
+                                )
+                                .map_err(|e| {
+                                    CompileError::codegen_error(
+                                        format!("Failed to build float comparison: {:?}", e),
+                                        None,
+                                    )
+                                })?;
+                            Ok(result.into())
+                        }
+                        BinaryOp::And => {
+                            // Convert floats to boolean: non-zero is true, zero/NaN is false
+                            let zero = self.context.f64_type().const_float(0.0);
+                            let left_bool = self
+                                .builder
+                                .build_float_compare(
+                                    FloatPredicate::ONE,
+                                    left_float,
+                                    zero,
+                                    "float_to_bool",
+                                )
+                                .map_err(|e| {
+                                    CompileError::codegen_error(
+                                        format!("Failed to convert float to bool: {:?}", e),
+                                        None,
+                                    )
+                                })?;
+                            let right_bool = self
+                                .builder
+                                .build_float_compare(
+                                    FloatPredicate::ONE,
+                                    right_float,
+                                    zero,
+                                    "float_to_bool",
+                                )
+                                .map_err(|e| {
+                                    CompileError::codegen_error(
+                                        format!("Failed to convert float to bool: {:?}", e),
+                                        None,
+                                    )
+                                })?;
+
+                            let result = self
+                                .builder
+                                .build_and(left_bool, right_bool, "float_and")
+                                .map_err(|e| {
+                                    CompileError::codegen_error(
+                                        format!("Failed to build logical AND for floats: {:?}", e),
+                                        None,
+                                    )
+                                })?;
+                            Ok(result.into())
+                        }
+                        BinaryOp::Or => {
+                            // Convert floats to boolean: non-zero is true, zero/NaN is false
+                            let zero = self.context.f64_type().const_float(0.0);
+                            let left_bool = self
+                                .builder
+                                .build_float_compare(
+                                    FloatPredicate::ONE,
+                                    left_float,
+                                    zero,
+                                    "float_to_bool",
+                                )
+                                .map_err(|e| {
+                                    CompileError::codegen_error(
+                                        format!("Failed to convert float to bool: {:?}", e),
+                                        None,
+                                    )
+                                })?;
+                            let right_bool = self
+                                .builder
+                                .build_float_compare(
+                                    FloatPredicate::ONE,
+                                    right_float,
+                                    zero,
+                                    "float_to_bool",
+                                )
+                                .map_err(|e| {
+                                    CompileError::codegen_error(
+                                        format!("Failed to convert float to bool: {:?}", e),
+                                        None,
+                                    )
+                                })?;
+
+                            let result = self
+                                .builder
+                                .build_or(left_bool, right_bool, "float_or")
+                                .map_err(|e| {
+                                CompileError::codegen_error(
+                                    format!("Failed to build logical OR for floats: {:?}", e),
+                                    None,
+                                )
+                            })?;
+                            Ok(result.into())
+                        }
+                        BinaryOp::Modulo => {
+                            // Implement floating-point remainder using frem instruction
+                            let result = self
+                                .builder
+                                .build_float_rem(left_float, right_float, "frem")
+                                .map_err(|e| {
+                                    CompileError::codegen_error(
+                                        format!("Failed to build float remainder: {:?}", e),
+                                        None,
+                                    )
+                                })?;
+                            Ok(result.into())
+                        }
+                        // For other operations, we'll return an error for now
+                        _ => Err(CompileError::codegen_error(
+                            format!("Binary operation {:?} not yet implemented for floats", op),
+                            None,
+                        )),
+                    }
+                } else if matches!(op, BinaryOp::Add) {
+                    eprintln!("🔍 Codegen: Binary Add operation detected");
+                    eprintln!("🔍 Codegen: Left value type: {:?}, Right value type: {:?}", left_value, right_value);
+                    eprintln!("🔍 Codegen: Checking string+int condition: left_ptr={}, right_int={}, left_int={}, right_ptr={}", 
+                        matches!(left_value, BasicValueEnum::PointerValue(_)), 
+                        matches!(right_value, BasicValueEnum::IntValue(_)), 
+                        matches!(left_value, BasicValueEnum::IntValue(_)), 
+                        matches!(right_value, BasicValueEnum::PointerValue(_)));
+                    
+                    if (matches!(left_value, BasicValueEnum::PointerValue(_)) && matches!(right_value, BasicValueEnum::IntValue(_))) || (matches!(left_value, BasicValueEnum::IntValue(_)) && matches!(right_value, BasicValueEnum::PointerValue(_))) {
+                    // Handle string + integer concatenation
+                    eprintln!("🔍 String + Integer concatenation detected: {:?}", op);
+                    
+                    // Determine which operand is string and which is integer
+                    let (string_value, int_value) = if let (BasicValueEnum::PointerValue(str_ptr), BasicValueEnum::IntValue(int_val)) = (left_value, right_value) {
+                        (str_ptr, int_val)
+                    } else if let (BasicValueEnum::IntValue(int_val), BasicValueEnum::PointerValue(str_ptr)) = (left_value, right_value) {
+                        (str_ptr, int_val)
+                    } else {
+                        unreachable!()
+                    };
+                    
+                    // Convert integer to string using i32_to_string function
+                    let i32_to_string_function = self.functions.get("i32_to_string").copied().ok_or_else(|| {
+                        CompileError::codegen_error(
+                            "i32_to_string function not found in function table".to_string(),
+                            None,
+                        )
+                    })?;
+                    
+                    let int_as_string = self.builder.build_call(
+                        i32_to_string_function,
+                        &[int_value.into()],
+                        "int_to_str"
+                    ).map_err(|e| {
+                        CompileError::codegen_error(
+                            format!("Failed to call i32_to_string: {:?}", e),
+                            None,
+                        )
+                    })?.try_as_basic_value().left().ok_or_else(|| {
+                        CompileError::codegen_error(
+                            "i32_to_string did not return a value".to_string(),
+                            None,
+                        )
+                    })?;
+                    
+                    // Use string_concat function to concatenate string + converted integer
+                    let string_concat_function = self.functions.get("string_concat").copied().ok_or_else(|| {
+                        CompileError::codegen_error(
+                            "string_concat function not found in function table".to_string(),
+                            None,
+                        )
+                    })?;
+                    
+                    // Determine correct order for concatenation based on original operand order
+                    let (first_arg, second_arg) = if matches!(left_value, BasicValueEnum::PointerValue(_)) {
+                        // String + Integer: concat(string, int_as_string)
+                        (string_value.into(), int_as_string)
+                    } else {
+                        // Integer + String: concat(int_as_string, string)  
+                        (int_as_string, string_value.into())
+                    };
+                    
+                    let result = self.builder.build_call(
+                        string_concat_function,
+                        &[first_arg.into(), second_arg.into()],
+                        "string_int_concat"
+                    ).map_err(|e| {
+                        CompileError::codegen_error(
+                            format!("Failed to call string_concat: {:?}", e),
+                            None,
+                        )
+                    })?.try_as_basic_value().left().ok_or_else(|| {
+                        CompileError::codegen_error(
+                            "string_concat did not return a value".to_string(),
+                            None,
+                        )
+                    })?;
+                    
+                    eprintln!("✅ String + Integer concatenation successful");
+                    Ok(result)
+                    } else {
+                        // Not string + integer, continue to mixed types error
+                        eprintln!("❌ Mixed types in binary operation: {:?}, left: {:?}, right: {:?}", op, left_value.get_type(), right_value.get_type());
+                        Err(CompileError::codegen_error(
+                            format!("Mixed types in binary operation not yet supported: {:?} between {:?} and {:?}", op, left_value.get_type(), right_value.get_type()),
+                            None,
+                        ))
+                    }
+                } else {
+                    // Handle mixed integer types with coercion
+                    if let (
+                        BasicValueEnum::IntValue(left_int),
+                        BasicValueEnum::IntValue(right_int),
+                    ) = (left_value, right_value)
+                    {
+                        // Get the types and promote to larger type if needed
+                        let left_type = left_int.get_type();
+                        let right_type = right_int.get_type();
+
+                        let (coerced_left, coerced_right) = if left_type.get_bit_width()
+                            != right_type.get_bit_width()
+                        {
+                            // Promote to the larger type
+                            if left_type.get_bit_width() > right_type.get_bit_width() {
+                                // Promote right to left's type
+                                let coerced_right = if left_type.get_bit_width() == 64
+                                    && right_type.get_bit_width() == 32
+                                {
+                                    self.builder
+                                        .build_int_z_extend(right_int, left_type, "promote_right")
+                                        .map_err(|e| {
+                                            CompileError::codegen_error(
+                                                format!("Failed to promote integer: {:?}", e),
+                                                None,
+                                            )
+                                        })?
+                                } else {
+                                    self.builder
+                                        .build_int_truncate(right_int, left_type, "truncate_right")
+                                        .map_err(|e| {
+                                            CompileError::codegen_error(
+                                                format!("Failed to truncate integer: {:?}", e),
+                                                None,
+                                            )
+                                        })?
+                                };
+                                (left_int, coerced_right)
+                            } else {
+                                // Promote left to right's type
+                                let coerced_left = if right_type.get_bit_width() == 64
+                                    && left_type.get_bit_width() == 32
+                                {
+                                    self.builder
+                                        .build_int_z_extend(left_int, right_type, "promote_left")
+                                        .map_err(|e| {
+                                            CompileError::codegen_error(
+                                                format!("Failed to promote integer: {:?}", e),
+                                                None,
+                                            )
+                                        })?
+                                } else {
+                                    self.builder
+                                        .build_int_truncate(left_int, right_type, "truncate_left")
+                                        .map_err(|e| {
+                                            CompileError::codegen_error(
+                                                format!("Failed to truncate integer: {:?}", e),
+                                                None,
+                                            )
+                                        })?
+                                };
+                                (coerced_left, right_int)
+                            }
+                        } else {
+                            (left_int, right_int)
+                        };
+
+                        // Now perform the operation with coerced types
+                        match op {
+                            BinaryOp::Equal => {
+                                let result = self
+                                    .builder
+                                    .build_int_compare(
+                                        IntPredicate::EQ,
+                                        coerced_left,
+                                        coerced_right,
+                                        "cmp_eq",
+                                    )
+                                    .map_err(|e| {
+                                        CompileError::codegen_error(
+                                            format!("Failed to build comparison: {:?}", e),
+                                            None,
+                                        )
+                                    })?;
+                                Ok(result.into())
+                            }
+                            BinaryOp::NotEqual => {
+                                let result = self
+                                    .builder
+                                    .build_int_compare(
+                                        IntPredicate::NE,
+                                        coerced_left,
+                                        coerced_right,
+                                        "cmp_ne",
+                                    )
+                                    .map_err(|e| {
+                                        CompileError::codegen_error(
+                                            format!("Failed to build comparison: {:?}", e),
+                                            None,
+                                        )
+                                    })?;
+                                Ok(result.into())
+                            }
+                            BinaryOp::Less => {
+                                let result = self
+                                    .builder
+                                    .build_int_compare(
+                                        IntPredicate::SLT,
+                                        coerced_left,
+                                        coerced_right,
+                                        "cmp_lt",
+                                    )
+                                    .map_err(|e| {
+                                        CompileError::codegen_error(
+                                            format!("Failed to build comparison: {:?}", e),
+                                            None,
+                                        )
+                                    })?;
+                                Ok(result.into())
+                            }
+                            BinaryOp::LessEqual => {
+                                let result = self
+                                    .builder
+                                    .build_int_compare(
+                                        IntPredicate::SLE,
+                                        coerced_left,
+                                        coerced_right,
+                                        "cmp_le",
+                                    )
+                                    .map_err(|e| {
+                                        CompileError::codegen_error(
+                                            format!("Failed to build comparison: {:?}", e),
+                                            None,
+                                        )
+                                    })?;
+                                Ok(result.into())
+                            }
+                            BinaryOp::Greater => {
+                                let result = self
+                                    .builder
+                                    .build_int_compare(
+                                        IntPredicate::SGT,
+                                        coerced_left,
+                                        coerced_right,
+                                        "cmp_gt",
+                                    )
+                                    .map_err(|e| {
+                                        CompileError::codegen_error(
+                                            format!("Failed to build comparison: {:?}", e),
+                                            None,
+                                        )
+                                    })?;
+                                Ok(result.into())
+                            }
+                            BinaryOp::GreaterEqual => {
+                                let result = self
+                                    .builder
+                                    .build_int_compare(
+                                        IntPredicate::SGE,
+                                        coerced_left,
+                                        coerced_right,
+                                        "cmp_ge",
+                                    )
+                                    .map_err(|e| {
+                                        CompileError::codegen_error(
+                                            format!("Failed to build comparison: {:?}", e),
+                                            None,
+                                        )
+                                    })?;
+                                Ok(result.into())
+                            }
+                            BinaryOp::Add => {
+                                let result = self
+                                    .builder
+                                    .build_int_add(coerced_left, coerced_right, "add")
+                                    .map_err(|e| {
+                                        CompileError::codegen_error(
+                                            format!("Failed to build add: {:?}", e),
+                                            None,
+                                        )
+                                    })?;
+                                Ok(result.into())
+                            }
+                            BinaryOp::Subtract => {
+                                let result = self
+                                    .builder
+                                    .build_int_sub(coerced_left, coerced_right, "sub")
+                                    .map_err(|e| {
+                                        CompileError::codegen_error(
+                                            format!("Failed to build sub: {:?}", e),
+                                            None,
+                                        )
+                                    })?;
+                                Ok(result.into())
+                            }
+                            BinaryOp::Multiply => {
+                                let result = self
+                                    .builder
+                                    .build_int_mul(coerced_left, coerced_right, "mul")
+                                    .map_err(|e| {
+                                        CompileError::codegen_error(
+                                            format!("Failed to build mul: {:?}", e),
+                                            None,
+                                        )
+                                    })?;
+                                Ok(result.into())
+                            }
+                            BinaryOp::Divide => {
+                                let result = self
+                                    .builder
+                                    .build_int_signed_div(coerced_left, coerced_right, "div")
+                                    .map_err(|e| {
+                                        CompileError::codegen_error(
+                                            format!("Failed to build div: {:?}", e),
+                                            None,
+                                        )
+                                    })?;
+                                Ok(result.into())
+                            }
+                            BinaryOp::Modulo => {
+                                let result = self
+                                    .builder
+                                    .build_int_signed_rem(coerced_left, coerced_right, "rem")
+                                    .map_err(|e| {
+                                        CompileError::codegen_error(
+                                            format!("Failed to build rem: {:?}", e),
+                                            None,
+                                        )
+                                    })?;
+                                Ok(result.into())
+                            }
+                            _ => Err(CompileError::codegen_error(
+                                format!(
+                                    "Binary operation {:?} not yet implemented for mixed integers",
+                                    op
+                                ),
+                                None,
+                            )),
+                        }
+                    } else {
+                        // Truly mixed types (e.g., int and float)
+                        eprintln!("❌ Mixed types in binary operation: {:?}, left: {:?}, right: {:?}", op, left_value.get_type(), right_value.get_type());
+                        Err(CompileError::codegen_error(
+                            format!("Mixed types in binary operation not yet supported: {:?} between {:?} and {:?}", op, left_value.get_type(), right_value.get_type()),
+                            None,
+                        ))
+                    }
+                }
+            }
+        }
+    }
+
+    /// Generates code for a unary expression.
+    fn generate_unary_expression(
+        &mut self,
+        op: &UnaryOp,
+        expr: &Box<Expr>,
+    ) -> Result<BasicValueEnum<'ctx>> {
+        // A reference to a variable points at the variable itself, so writes
+        // through a pointer made from it are visible to the variable
+        if let (UnaryOp::Reference, Expr::Variable(name, _)) = (op, &**expr) {
+            if let Some(&var_ptr) = self.variables.get(name) {
+                return self.decay_array_pointer(var_ptr);
+            }
+        }
+
+        let expr_value = self.generate_expression(expr)?;
+
+        match op {
+            UnaryOp::Negate => {
+                if let BasicValueEnum::IntValue(int_value) = expr_value {
+                    let result = self.builder.build_int_neg(int_value, "neg").map_err(|e| {
+                        CompileError::codegen_error(format!("Failed to build neg: {:?}", e), None)
+                    })?;
+                    Ok(result.into())
+                } else if let BasicValueEnum::FloatValue(float_value) = expr_value {
+                    let result =
+                        self.builder
+                            .build_float_neg(float_value, "fneg")
+                            .map_err(|e| {
+                                CompileError::codegen_error(
+                                    format!("Failed to build fneg: {:?}", e),
+                                    None,
+                                )
+                            })?;
+                    Ok(result.into())
+                } else {
+                    Err(CompileError::codegen_error(
+                        "Negation only supported for numeric types".to_string(),
+                        None,
+                    ))
+                }
+            }
+            UnaryOp::Not => {
+                if let BasicValueEnum::IntValue(int_value) = expr_value {
+                    let result = self.builder.build_not(int_value, "not").map_err(|e| {
+                        CompileError::codegen_error(format!("Failed to build not: {:?}", e), None)
+                    })?;
+                    Ok(result.into())
+                } else {
+                    Err(CompileError::codegen_error(
+                        "Logical not only supported for boolean types".to_string(),
+                        None,
+                    ))
+                }
+            }
+            UnaryOp::Reference => {
+                // Arrays are already held by pointer
+                if let BasicValueEnum::PointerValue(ptr) = expr_value {
+                    if ptr.get_type().get_element_type().is_array_type() {
+                        return self.decay_array_pointer(ptr);
+                    }
+                }
+
+                // Create a reference (pointer) to the value
+                // For now, we'll allocate the value on the stack and return the pointer
+                let current_function = self.builder.get_insert_block().unwrap().get_parent().unwrap();
+                let alloca = self.create_entry_block_alloca(
+                    current_function, 
+                    "ref_temp", 
+                    expr_value.get_type()
+                )?;
+                self.builder.build_store(alloca, expr_value).map_err(|e| {
+                    CompileError::codegen_error(
+                        format!("Failed to store value for reference: {:?}", e),
+                        None,
+                    )
+                })?;
+                Ok(alloca.into())
+            }
+            UnaryOp::Deref => {
+                if let BasicValueEnum::PointerValue(ptr) = expr_value {
+                    self.builder.build_load(ptr, "deref").map_err(|e| {
+                        CompileError::codegen_error(format!("Failed to build deref: {:?}", e), None)
+                    })
+                } else {
+                    Err(CompileError::codegen_error(
+                        "Dereference only supported for pointer types".to_string(),
+                        None,
+                    ))
+                }
+            }
+        }
+    }
+
+    /// A pointer to an array `[N x T]*` decays to a pointer to its first element `T*`;
+    /// any other pointer is returned unchanged.
+    fn decay_array_pointer(&self, ptr: PointerValue<'ctx>) -> Result<BasicValueEnum<'ctx>> {
+        if !ptr.get_type().get_element_type().is_array_type() {
+            return Ok(ptr.into());
+        }
+        let zero = self.context.i32_type().const_zero();
+        let element_ptr = unsafe {
+            self.builder
+                .build_in_bounds_gep(ptr, &[zero, zero], "array_decay")
+                .map_err(|e| {
+                    CompileError::codegen_error(format!("Failed to decay array: {:?}", e), None)
+                })?
+        };
+        Ok(element_ptr.into())
+    }
+
+    /// Generates `p.offset(n)`: the pointer `n` elements past `p`.
+    fn generate_pointer_offset(
+        &mut self,
+        pointer: PointerValue<'ctx>,
+        args: &[Expr],
+    ) -> Result<BasicValueEnum<'ctx>> {
+        let count = self.generate_expression(&args[0])?.into_int_value();
+        let offset_ptr = unsafe {
+            self.builder
+                .build_in_bounds_gep(pointer, &[count], "offset")
+                .map_err(|e| {
+                    CompileError::codegen_error(format!("Failed to build offset: {:?}", e), None)
+                })?
+        };
+        Ok(offset_ptr.into())
+    }
+
+    /// Generates code for a function call.
+    fn generate_function_call(
+        &mut self,
+        callee: &Box<Expr>,
+        args: &[Expr],
+        span: &Span,
+    ) -> Result<BasicValueEnum<'ctx>> {
+        if let Expr::Variable(func_name, _) = &**callee {
+            if let Some(generic) = self.generic_functions.get(func_name).cloned() {
+                return self.generate_generic_function_call(func_name, &generic, args, span);
+            }
+            // Builtins unless a function or value of the same name shadows them
+            if crate::ast::OVERFLOW_ARITHMETIC_BUILTINS.contains(&func_name.as_str())
+                && !self.functions.contains_key(&self.resolve_function_name(func_name))
+                && !self.variables.contains_key(func_name)
+            {
+                return self.generate_overflow_arithmetic_builtin(func_name, args, span);
+            }
+        }
+        if let Expr::FieldAccess(receiver, method_name, _) = &**callee {
+            // `module::function(args)` of a generic function of an imported module
+            if let Expr::Variable(module_alias, _) = &**receiver {
+                let qualified_name = format!("{}::{}", module_alias, method_name);
+                if let Some(generic) = self.generic_functions.get(&qualified_name).cloned() {
+                    return self.generate_generic_function_call(
+                        &qualified_name,
+                        &generic,
+                        args,
+                        span,
+                    );
+                }
+            }
+            if let Some(value) = self.generate_method_call(receiver, method_name, args)? {
+                return Ok(value);
+            }
+            if method_name == "offset" && args.len() == 1 {
+                if let BasicValueEnum::PointerValue(pointer) = self.generate_expression(receiver)? {
+                    return self.generate_pointer_offset(pointer, args);
+                }
+            }
+            if let ("map" | "filter" | "reduce", Expr::Variable(var_name, _)) =
+                (method_name.as_str(), &**receiver)
+            {
+                if let Some(&vec_ptr) = self.variables.get(var_name) {
+                    return self.generate_vec_closure_method(vec_ptr, method_name, args);
+                }
+            }
+        }
+
+        // Calls through a closure or function value: a local variable, or any other expression
+        let calls_closure = match &**callee {
+            Expr::Variable(name, _) => self.variables.get(name).map_or(false, |ptr| {
+                BasicTypeEnum::try_from(ptr.get_type().get_element_type())
+                    .map_or(false, |ty| self.closure_code_type(ty).is_some())
+            }),
+            Expr::FieldAccess(..) => false,
+            _ => true,
+        };
+        if calls_closure {
+            let closure = self.generate_expression(callee)?;
+            let mut arg_values = Vec::new();
+            for arg in args {
+                arg_values.push(self.generate_expression(arg)?);
+            }
+            return self.call_closure(closure, arg_values);
+        }
+
+        // Handle both direct function calls and module-scoped calls (Vec::new, HashMap::new)
+        let function_name = match &**callee {
+            // Direct function call: func_name()
+            Expr::Variable(func_name, _) => self.resolve_function_name(func_name),
+
+            // Module-scoped function call: Vec::new(), HashMap::new()
+            // OR method call on instance: vec.push(), vec.len()
+            Expr::FieldAccess(base_expr, method_name, _) => {
+                if let Expr::Variable(module_name, _) = &**base_expr {
+                    // Check if this is a static method call (Vec::new) or instance method call (vec.push)
+                    // Static calls have uppercase first letter, instance calls have lowercase
+                    if let Some(module_path) = self.module_aliases.get(module_name) {
+                        // Qualified call into an imported module: math::dot()
+                        self.module_symbol_name(module_path, method_name)
+                    } else if module_name.chars().next().unwrap().is_uppercase() {
+                        // Static method call: Vec::new(), HashMap::new()
+                        // Map to runtime function names
+                        match (module_name.as_str(), method_name.as_str()) {
+                            ("Vec", "new") => "vec_new".to_string(),
+                            ("HashMap", "new") => "hashmap_new".to_string(),
+                            ("HashSet", "new") => "HashSet_new".to_string(),
+                            ("String", "new") => "string_new".to_string(),
+                            ("String", "from") => "string_from".to_string(),
+                            ("File", "open") => "file_open".to_string(),
+                            ("File", "create") => "file_create".to_string(),
+                            ("File", "exists") => "file_exists".to_string(),
+                            ("File", "size") => "file_size".to_string(),
+                            ("File", "delete") => "file_delete".to_string(),
+                            ("File", "write") => "file_write".to_string(),
+                            ("File", "readline") => "file_read_line".to_string(),
+                            ("File", "read_all") => "file_read_all".to_string(),
+                            ("File", "close") => "file_close".to_string(),
+                            ("Package", "new") => "Package_new".to_string(),
+                            ("PackageManager", "new") => "PackageManager_new".to_string(),
+                            ("BuildConfig", "new") => "BuildConfig_new".to_string(),
+                            ("BenchmarkConfig", "new") => "BenchmarkConfig_new".to_string(),
+                            _ => format!("{}::{}", module_name, method_name),
+                        }
+                    } else {
+                        // Instance method call: vec.push(), vec.len()
+                        // Generate the object value and pass it as first argument
+                        let object_value = self.generate_expression(base_expr)?;
+
+                        // Get the method function
+                        // Map Vec and HashMap method names to runtime function names
+                        // For now, we'll determine the type based on the variable name pattern
+                        // This is a simplified approach - in a full implementation, we'd use type information
+                        let method_func_name = if let Expr::Variable(var_name, _) = &**base_expr {
+                            if var_name.contains("set") || var_name.starts_with("set") {
+                                // HashSet methods
+                                match method_name.as_str() {
+                                    "insert" => "HashSet_insert".to_string(),
+                                    "contains" => "HashSet_contains".to_string(),
+                                    "remove" => "HashSet_remove".to_string(),
+                                    "len" => "HashSet_len".to_string(),
+                                    "is_empty" => "HashSet_is_empty".to_string(),
+                                    "clear" => "HashSet_clear".to_string(),
+                                    _ => format!("HashSet::{}", method_name),
+                                }
+                            } else if var_name.contains("map") || var_name.starts_with("hash") {
+                                // HashMap methods
+                                match method_name.as_str() {
+                                    "insert" => "hashmap_insert".to_string(),
+                                    "get" => "hashmap_get".to_string(),
+                                    "len" => "hashmap_len".to_string(),
+                                    "contains_key" => "hashmap_contains_key".to_string(),
+                                    "remove" => "hashmap_remove".to_string(),
+                                    "is_empty" => "hashmap_is_empty".to_string(),
+                                    "clear" => "hashmap_clear".to_string(),
+                                    _ => format!("HashMap::{}", method_name),
+                                }
+                            } else if var_name.contains("string") || var_name.starts_with("s") {
+                                // String methods
+                                match method_name.as_str() {
+                                    "len" => "string_len".to_string(),
+                                    "push_str" => "string_push_str".to_string(),
+                                    "as_str" => "string_as_str".to_string(),
+                                    "clone" => "string_clone".to_string(),
+                                    "substring" => "string_substring".to_string(),
+                                    "find" => "string_find".to_string(),
+                                    "replace" => "string_replace".to_string(),
+                                    "to_uppercase" => "string_to_uppercase".to_string(),
+                                    "to_lowercase" => "string_to_lowercase".to_string(),
+                                    "trim" => "string_trim".to_string(),
+                                    _ => format!("String::{}", method_name),
+                                }
+                            } else if var_name.contains("package") || var_name.starts_with("package") {
+                                // Package methods
+                                match method_name.as_str() {
+                                    "add_dependency" => "Package_add_dependency".to_string(),
+                                    "set_performance_requirements" => "Package_set_performance_requirements".to_string(),
+                                    _ => format!("Package::{}", method_name),
+                                }
+                            } else if var_name.contains("resolver") || var_name.contains("manager") {
+                                // PackageManager methods
+                                match method_name.as_str() {
+                                    "resolve_dependencies" => "PackageManager_resolve_dependencies".to_string(),
+                                    "build_package" => "PackageManager_build_package".to_string(),
+                                    "run_benchmarks" => "PackageManager_run_benchmarks".to_string(),
+                                    _ => format!("PackageManager::{}", method_name),
+                                }
+                            } else if var_name.contains("build_config") || var_name.starts_with("build") {
+                                // BuildConfig methods
+                                match method_name.as_str() {
+                                    "add_target" => "BuildConfig_add_target".to_string(),
+                                    "set_optimization" => "BuildConfig_set_optimization".to_string(),
+                                    _ => format!("BuildConfig::{}", method_name),
+                                }
+                            } else if var_name.contains("benchmark_config") || var_name.starts_with("benchmark") {
+                                // BenchmarkConfig methods
+                                match method_name.as_str() {
+                                    "set_iterations" => "BenchmarkConfig_set_iterations".to_string(),
+                                    "set_timeout" => "BenchmarkConfig_set_timeout".to_string(),
+                                    _ => format!("BenchmarkConfig::{}", method_name),
+                                }
+                            } else if var_name.contains("resolution") {
+                                // DependencyResolution methods
+                                match method_name.as_str() {
+                                    "count" => "DependencyResolution_count".to_string(),
+                                    _ => format!("DependencyResolution::{}", method_name),
+                                }
+                            } else if var_name.contains("result") && (var_name.contains("build") || var_name.contains("cached")) {
+                                // BuildResult methods
+                                match method_name.as_str() {
+                                    "compilation_time_ms" => "BuildResult_compilation_time_ms".to_string(),
+                                    "peak_memory_mb" => "BuildResult_peak_memory_mb".to_string(),
+                                    "cache_hit_rate" => "BuildResult_cache_hit_rate".to_string(),
+                                    "performance_gain" => "BuildResult_performance_gain".to_string(),
+                                    "from_cache" => "BuildResult_from_cache".to_string(),
+                                    _ => format!("BuildResult::{}", method_name),
+                                }
+                            } else {
+                                // Vec methods (default)
+                                match method_name.as_str() {
+                                    "push" => "vec_push".to_string(),
+                                    "len" => "vec_len".to_string(),
+                                    "get" => "vec_get".to_string(),
+                                    "pop" => "vec_pop".to_string(),
+                                    "capacity" => "vec_capacity".to_string(),
+                                    "is_empty" => "vec_is_empty".to_string(),
+                                    "clear" => "vec_clear".to_string(),
+                                    _ => format!("Vec::{}", method_name),
+                                }
+                            }
+                        } else {
+                            // Default to Vec methods for other expressions
+                            match method_name.as_str() {
+                                "push" => "vec_push".to_string(),
+                                "len" => "vec_len".to_string(),
+                                "get" => "vec_get".to_string(),
+                                "pop" => "vec_pop".to_string(),
+                                "capacity" => "vec_capacity".to_string(),
+                                "is_empty" => "vec_is_empty".to_string(),
+                                "clear" => "vec_clear".to_string(),
+                                _ => format!("Vec::{}", method_name),
+                            }
+                        };
+                        if let Some(&function) = self.functions.get(&method_func_name) {
+                            // Generate arguments, with object as first argument
+                            // For Vec methods that expect double pointers (i8**), we need to pass the address of the variable
+                            let first_arg = if method_func_name.starts_with("vec_") && 
+                                            (method_func_name == "vec_get" || method_func_name == "vec_len" || method_func_name == "vec_push" || method_func_name == "vec_pop") {
+                                // These Vec functions expect i8** (pointer to Vec pointer)
+                                if let Expr::Variable(var_name, _) = &**base_expr {
+                                    if let Some(&var_ptr) = self.variables.get(var_name) {
+                                        // Pass the address of the variable (i8**) instead of the loaded value (i8*)
+                                        var_ptr.into()
+                                    } else {
+                                        return Err(CompileError::codegen_error(
+                                            format!("Variable '{}' not found", var_name),
+                                            None,
+                                        ));
+                                    }
+                                } else {
+                                    // For complex expressions, use the loaded value (this may still cause issues)
+                                    object_value.into()
+                                }
+                            } else {
+                                // For other methods, use the loaded value as before
+                                object_value.into()
+                            };
+                            
+                            let mut arg_values = vec![first_arg];
+                            for arg in args {
+                                let arg_value = self.generate_expression(arg)?;
+                                arg_values.push(arg_value.into());
+                            }
+
+                            // Build the method call
+                            let call = self
+                                .builder
+                                .build_call(function, &arg_values, "method_call")
+                                .map_err(|e| {
+                                    CompileError::codegen_error(
+                                        format!("Failed to build method call: {:?}", e),
+                                        None,
+                                    )
+                                })?;
+
+                            // Handle special return value processing for Vec and HashMap methods
+                            if let Some(return_value) = call.try_as_basic_value().left() {
+                                // Check if this is a HashMap method based on the function name
+                                if method_func_name.starts_with("hashmap_") {
+                                    // HashMap methods
+                                    match method_name.as_str() {
+                                        "get" => {
+                                            // hashmap_get returns i32 directly (0 if not found)
+                                            if let BasicValueEnum::IntValue(value) = return_value {
+                                                return Ok(value.into());
+                                            }
+                                            return Ok(return_value);
+                                        }
+                                        "len" => {
+                                            // hashmap_len returns i32, no conversion needed
+                                            if let BasicValueEnum::IntValue(len_i32) = return_value
+                                            {
+                                                return Ok(len_i32.into());
+                                            }
+                                            return Ok(return_value);
+                                        }
+                                        "contains_key" | "remove" => {
+                                            // hashmap_contains_key and hashmap_remove return i32 (0 or 1)
+                                            if let BasicValueEnum::IntValue(result) = return_value {
+                                                return Ok(result.into());
+                                            }
+                                            return Ok(return_value);
+                                        }
+                                        "insert" => {
+                                            // hashmap_insert returns void, create a dummy success value
+                                            let success_value =
+                                                self.context.i32_type().const_int(1, false);
+                                            return Ok(success_value.into());
+                                        }
+                                        _ => {
+                                            return Ok(return_value);
+                                        }
+                                    }
+                                } else if method_func_name.starts_with("HashSet_") {
+                                    // HashSet methods
+                                    match method_name.as_str() {
+                                        "contains" | "remove" | "insert" => {
+                                            // HashSet methods return bool (0 or 1)
+                                            if let BasicValueEnum::IntValue(result) = return_value {
+                                                return Ok(result.into());
+                                            }
+                                            return Ok(return_value);
+                                        }
+                                        "len" => {
+                                            // HashSet_len returns i32, no conversion needed
+                                            if let BasicValueEnum::IntValue(len_i32) = return_value
+                                            {
+                                                return Ok(len_i32.into());
+                                            }
+                                            return Ok(return_value);
+                                        }
+                                        "is_empty" => {
+                                            // HashSet_is_empty returns bool (0 or 1)
+                                            if let BasicValueEnum::IntValue(result) = return_value {
+                                                return Ok(result.into());
+                                            }
+                                            return Ok(return_value);
+                                        }
+                                        "clear" => {
+                                            // HashSet_clear returns void, create a dummy success value
+                                            let success_value =
+                                                self.context.i32_type().const_int(1, false);
+                                            return Ok(success_value.into());
+                                        }
+                                        _ => {
+                                            return Ok(return_value);
+                                        }
+                                    }
+                                } else {
+                                    // Vec methods
+                                    match method_name.as_str() {
+                                        "len" => {
+                                            // vec_len returns i32, no conversion needed
+                                            if let BasicValueEnum::IntValue(len_i32) = return_value
+                                            {
+                                                return Ok(len_i32.into());
+                                            }
+                                            return Ok(return_value);
+                                        }
+                                        "get" => {
+                                            // For Vec<string>, vec_get should return the string pointer directly
+                                            // For Vec<i32>, vec_get should dereference to get the integer value
+                                            // Since we don't have runtime type information, we'll assume Vec<string> 
+                                            // based on the variable name pattern or return the pointer directly
+                                            
+                                            // Check if this is likely a Vec<string> based on variable name
+                                            let is_string_vec = if let Expr::Variable(var_name, _) = &**base_expr {
+                                                var_name.contains("args") || var_name.contains("string") || var_name.ends_with("_args")
+                                            } else {
+                                                false
+                                            };
+                                            
+                                            if is_string_vec {
+                                                // For Vec<string>, return the pointer directly (it's already a string pointer)
+                                                return Ok(return_value);
+                                            } else {
+                                                // vec_get returns pointer, dereference to get value for integer vectors
+                                                if let BasicValueEnum::PointerValue(ptr) = return_value
+                                                {
+                                                    // Check if pointer is null first
+                                                    let null_ptr = self
+                                                        .context
+                                                        .i8_type()
+                                                        .ptr_type(AddressSpace::default())
+                                                        .const_null();
+                                                    let is_null = self.builder.build_int_compare(
+                                                    IntPredicate::EQ,
+                                                    ptr,
+                                                    null_ptr,
+                                                    "is_null"
+                                                ).map_err(|e| CompileError::codegen_error(
+                                                    format!("Failed to check null pointer: {:?}", e), None))?;
+
+                                                    // Create conditional to return 0 if null, otherwise dereference
+                                                    let current_function = self
+                                                        .builder
+                                                        .get_insert_block()
+                                                        .unwrap()
+                                                        .get_parent()
+                                                        .unwrap();
+                                                    let then_block = self.context.append_basic_block(
+                                                        current_function,
+                                                        "null_case",
+                                                    );
+                                                    let else_block = self.context.append_basic_block(
+                                                        current_function,
+                                                        "valid_case",
+                                                    );
+                                                    let merge_block = self
+                                                        .context
+                                                        .append_basic_block(current_function, "merge");
+
+                                                    self.builder
+                                                        .build_conditional_branch(
+                                                            is_null, then_block, else_block,
+                                                        )
+                                                        .map_err(|e| {
+                                                            CompileError::codegen_error(
+                                                                format!(
+                                                                    "Failed to build conditional: {:?}",
+                                                                    e
+                                                                ),
+                                                                None,
+                                                            )
+                                                        })?;
+
+                                                    // Null case: return 0
+                                                    self.builder.position_at_end(then_block);
+                                                    let zero =
+                                                        self.context.i32_type().const_int(0, false);
+                                                    self.builder
+                                                        .build_unconditional_branch(merge_block)
+                                                        .map_err(|e| {
+                                                            CompileError::codegen_error(
+                                                                format!(
+                                                                    "Failed to build branch: {:?}",
+                                                                    e
+                                                                ),
+                                                                None,
+                                                            )
+                                                        })?;
+
+                                                    // Valid case: dereference pointer
+                                                    self.builder.position_at_end(else_block);
+
+                                                    // Cast the i8* pointer to i32* and load the i32 value
+                                                    let i32_ptr = self.builder.build_pointer_cast(
+                                                    ptr,
+                                                    self.context.i32_type().ptr_type(AddressSpace::default()),
+                                                    "i32_ptr"
+                                                ).map_err(|e| CompileError::codegen_error(
+                                                    format!("Failed to cast pointer to i32*: {:?}", e), None))?;
+
+                                                    let value = self.builder.build_load(i32_ptr, "deref_value")
+                                                    .map_err(|e| CompileError::codegen_error(
+                                                        format!("Failed to dereference pointer: {:?}", e), None))?;
+
+                                                self.builder
+                                                    .build_unconditional_branch(merge_block)
+                                                    .map_err(|e| {
+                                                        CompileError::codegen_error(
+                                                            format!(
+                                                                "Failed to build branch: {:?}",
+                                                                e
+                                                            ),
+                                                            None,
+                                                        )
+                                                    })?;
+
+                                                // Merge block: PHI node to select result
+                                                self.builder.position_at_end(merge_block);
+                                                let phi = self
+                                                    .builder
+                                                    .build_phi(
+                                                        self.context.i32_type(),
+                                                        "get_result",
+                                                    )
+                                                    .map_err(|e| {
+                                                        CompileError::codegen_error(
+                                                            format!("Failed to build phi: {:?}", e),
+                                                            None,
+                                                        )
+                                                    })?;
+                                                phi.add_incoming(&[
+                                                    (&zero, then_block),
+                                                    (&value, else_block),
+                                                ]);
+
+                                                return Ok(phi.as_basic_value());
+                                                }
+                                                return Ok(return_value);
+                                            }
+                                        }
+                                        "push" => {
+                                            // vec_push returns i32 success indicator, which is fine
+                                            return Ok(return_value);
+                                        }
+                                        _ => {
+                                            return Ok(return_value);
+                                        }
+                                    }
+                                }
+                            } else {
+                                let dummy_value = self.context.i32_type().const_int(0, false);
+                                return Ok(dummy_value.into());
+                            }
+                        } else {
+                            return Err(CompileError::codegen_error(
+                                format!("Method '{}' not found", method_name),
+                                None,
+                            ));
+                        }
+                    }
+                } else {
+                    return Err(CompileError::codegen_error(
+                        "Complex expressions not supported in method calls".to_string(),
+                        None,
+                    ));
+                }
+            }
+
+            _ => {
+                return Err(CompileError::codegen_error(
+                    "Unsupported function call syntax".to_string(),
+                    None,
+                ));
+            }
+        };
+
+        // Look up the function in the function table
+        if let Some(&function) = self.functions.get(&function_name) {
+            // Generate code for each argument
+            let param_types = function.get_type().get_param_types();
+            let mut arg_values = Vec::new();
+            for (i, arg) in args.iter().enumerate() {
+                let mut arg_value = self.generate_expression(arg)?;
+                // Fixed-size arrays are passed by value, but array expressions yield their address
+                if let (BasicValueEnum::PointerValue(pointer), Some(BasicTypeEnum::ArrayType(_))) =
+                    (arg_value, param_types.get(i))
+                {
+                    arg_value = self.builder.build_load(pointer, "array_arg").map_err(|e| {
+                        CompileError::codegen_error(
+                            format!("Failed to load array argument: {:?}", e),
+                            None,
+                        )
+                    })?;
+                }
+                // Integer arguments are widened like the type checker's promotions, e.g. to i64
+                if let Some(&param_type) = param_types.get(i) {
+                    arg_value = self.coerce_int_value(arg_value, param_type)?;
+                }
+                arg_values.push(arg_value);
+            }
+
+            // In JIT mode, check for functions with vector parameters
+            if self.jit_safe_mode && self.should_skip_function_in_jit(&function_name, &arg_values) {
+                return Err(CompileError::codegen_error(
+                    format!(
+                        "JIT Mode Error: Function '{}' has vector parameters which aren't supported by LLVM JIT.\n\
+                        \n\
+                        SIMD functions work correctly in normal compilation mode.\n\
+                        To use this function:\n\
+                        \n\
+                        1. Compile normally: ./target/release/ea your_program.ea\n\
+                        2. Or compile to LLVM IR: ./target/release/ea --emit-llvm your_program.ea\n\
+                        \n\
+                        JIT mode (--run) is intended for simple programs and testing.\n\
+                        For SIMD operations, use the normal compilation pipeline.",
+                        function_name
+                    ),
+                    None,
+                ));
+            }
+
+            // Build the function call
+            let call = self
+                .builder
+                .build_call(
+                    function,
+                    &arg_values.iter().map(|v| (*v).into()).collect::<Vec<_>>(),
+                    "call",
+                )
+                .map_err(|e| {
+                    CompileError::codegen_error(format!("Failed to build call: {:?}", e), None)
+                })?;
+
+            // Get the return value (if any)
+            if let Some(return_value) = call.try_as_basic_value().left() {
+                Ok(return_value)
+            } else {
+                // Function returns void - create a dummy value for statement context
+                let dummy_value = self.context.i32_type().const_int(0, false);
+                Ok(dummy_value.into())
+            }
+        } else {
+            Err(CompileError::codegen_error(
+                format!("Function '{}' not found", function_name),
+                None,
+            ))
+        }
+    }
+
+    /// Writes the generated LLVM IR to a file.
+    pub fn write_ir_to_file(&self, filename: &str) -> Result<()> {
+        if self.module.print_to_file(filename).is_err() {
+            return Err(CompileError::codegen_error(
+                format!("Failed to write IR to file '{}'", filename),
+                None,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the generated LLVM IR as a string.
+    pub fn emit_ir(&self) -> String {
+        self.module.print_to_string().to_string()
+    }
+
+    /// Returns a reference to the LLVM module for JIT execution.
+    pub fn get_module(&self) -> &Module<'ctx> {
+        &self.module
+    }
+
+    /// Names of the functions declared in `extern "C"` blocks, which the JIT
+    /// must resolve against the loaded libraries.
+    pub fn extern_functions(&self) -> &[String] {
+        &self.extern_functions
+    }
+
+    /// Compiles the module to an object file.
+    pub fn compile_to_object_file(&self, filename: &str) -> Result<()> {
+        self.write_native_file(filename, FileType::Object)
+    }
+
+    /// Compiles the module to a target assembly file.
+    pub fn compile_to_assembly_file(&self, filename: &str) -> Result<()> {
+        self.write_native_file(filename, FileType::Assembly)
+    }
+
+    fn write_native_file(&self, filename: &str, file_type: FileType) -> Result<()> {
+        let machine = self.create_target_machine()?;
+        self.module.set_triple(&machine.get_triple());
+        self.module
+            .set_data_layout(&machine.get_target_data().get_data_layout());
+        let result = machine.write_to_file(&self.module, file_type, Path::new(filename));
+
+        if let Err(e) = result {
+            return Err(CompileError::codegen_error(
+                format!("Failed to write '{}': {}", filename, e),
+                None,
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Target machine for ahead-of-time output, for the host unless `--target` was given.
+    fn create_target_machine(&self) -> Result<TargetMachine> {
+        let target_options = &self.options.target;
+        let triple = match &target_options.triple {
+            Some(triple) => {
+                Target::initialize_all(&InitializationConfig::default());
+                TargetTriple::create(triple)
+            }
+            None => {
+                Self::initialize_native_target();
+                TargetMachine::get_default_triple()
+            }
+        };
+        let target = Target::from_triple(&triple).map_err(|e| {
+            CompileError::codegen_error(
+                format!(
+                    "Failed to create target {}: {}",
+                    triple.as_str().to_string_lossy(),
+                    e
+                ),
+                None,
+            )
+        })?;
+
+        // Position independent, so objects link into the PIE executables `cc` produces by default
+        target
+            .create_target_machine(
+                &triple,
+                &target_options.llvm_cpu(),
+                &target_options.llvm_features(),
+                self.optimization_level,
+                RelocMode::PIC,
+                CodeModel::Default,
+            )
+            .ok_or_else(|| {
+                CompileError::codegen_error("Failed to create target machine".to_string(), None)
+            })
+    }
+
+    /// Generates code for array indexing operations.
+    fn generate_array_index(
+        &mut self,
+        array_expr: &Box<Expr>,
+        index_expr: &Box<Expr>,
+    ) -> Result<BasicValueEnum<'ctx>> {
+        // Generate the array expression
+        let array_value = self.generate_expression(array_expr)?;
+
+        // Generate the index expression - this should be an integer
+        let index_value = self.generate_expression(index_expr)?;
+
+        // Ensure the index is an integer type
+        let index_int = if index_value.is_int_value() {
+            index_value.into_int_value()
+        } else {
+            return Err(CompileError::codegen_error(
+                "Array index must be an integer".to_string(),
+                None,
+            ));
+        };
+
+        // Check if this is a SIMD vector indexing operation
+        if array_value.is_vector_value() {
+            // For SIMD vectors, use extractelement instruction
+            let vector_value = array_value.into_vector_value();
+            
+            let extracted_element = self
+                .builder
+                .build_extract_element(vector_value, index_int, "simd_extract")
+                .map_err(|e| {
+                    CompileError::codegen_error(
+                        format!("Failed to extract SIMD vector element: {:?}", e),
+                        None,
+                    )
+                })?;
+
+            Ok(extracted_element)
+        } else {
+            // For regular arrays, use GEP instruction
+            let array_ptr = if array_value.is_pointer_value() {
+                array_value.into_pointer_value()
+            } else {
+                // If array value is not a pointer, we need to allocate space and store it
+                // This handles cases like function returns or literal arrays
+                let array_type = array_value.get_type();
+                let alloca = self.builder.build_alloca(array_type, "array_temp_alloca")
+                    .map_err(|e| CompileError::codegen_error(
+                        format!("Failed to create temporary allocation for array: {:?}", e),
+                        None,
+                    ))?;
+                
+                // Store the array value in the allocated space
+                self.builder.build_store(alloca, array_value)
+                    .map_err(|e| CompileError::codegen_error(
+                        format!("Failed to store array value: {:?}", e),
+                        None,
+                    ))?;
+                
+                alloca
+            };
+
+            if self.options.bounds_checks {
+                if let inkwell::types::AnyTypeEnum::ArrayType(array_type) =
+                    array_ptr.get_type().get_element_type()
+                {
+                    // Only constant indices known to be in range can skip the check
+                    let in_bounds = index_int
+                        .get_zero_extended_constant()
+                        .is_some_and(|index| index < array_type.len() as u64);
+                    if !in_bounds {
+                        self.generate_bounds_check(index_int, array_type.len())?;
+                    }
+                }
+            }
+
+            // Build GEP (Get Element Pointer) instruction for array indexing
+            // This calculates the address of array[index]
+            let element_ptr = unsafe {
+                self.builder
+                    .build_gep(
+                        array_ptr,
+                        &[self.context.i32_type().const_zero(), index_int],
+                        "array_index_gep",
+                    )
+                    .map_err(|e| {
+                        CompileError::codegen_error(
+                            format!("Failed to build GEP instruction: {:?}", e),
+                            None,
+                        )
+                    })?
+            };
+
+            // Load the value from the calculated address
+            let loaded_value = self
+                .builder
+                .build_load(element_ptr, "array_element_load")
+                .map_err(|e| {
+                    CompileError::codegen_error(format!("Failed to load array element: {:?}", e), None)
+                })?;
+
+            Ok(loaded_value)
+        }
+    }
+
+    /// Panics with an out of bounds message unless `0 <= index < length`.
+    fn generate_bounds_check(&mut self, index: IntValue<'ctx>, length: u32) -> Result<()> {
+        let function = self
+            .builder
+            .get_insert_block()
+            .and_then(|block| block.get_parent())
+            .ok_or_else(|| {
+                CompileError::codegen_error(
+                    "Array index outside of function context".to_string(),
+                    None,
+                )
+            })?;
+
+        // Negative indices wrap around to large unsigned ones, so one comparison does
+        let length_value = index.get_type().const_int(length as u64, false);
+        let in_bounds = self
+            .builder
+            .build_int_compare(IntPredicate::ULT, index, length_value, "in_bounds")
+            .map_err(|e| {
+                CompileError::codegen_error(format!("Failed to build bounds check: {:?}", e), None)
+            })?;
+        let ok_block = self.context.append_basic_block(function, "bounds_ok");
+        let panic_block = self.context.append_basic_block(function, "bounds_panic");
+        self.builder
+            .build_conditional_branch(in_bounds, ok_block, panic_block)
+            .map_err(|e| {
+                CompileError::codegen_error(format!("Failed to build bounds check: {:?}", e), None)
+            })?;
+
+        self.builder.position_at_end(panic_block);
+        let i64_type = self.context.i64_type();
+        let wide_index = self
+            .builder
+            .build_int_s_extend_or_bit_cast(index, i64_type, "wide_index")
+            .map_err(|e| {
+                CompileError::codegen_error(format!("Failed to extend index: {:?}", e), None)
+            })?;
+        self.generate_panic(
+            "panic: index out of bounds: the length is %lld but the index is %lld\n",
+            &[
+                i64_type.const_int(length as u64, false).into(),
+                wide_index.into(),
+            ],
+        )?;
+
+        self.builder.position_at_end(ok_block);
+        Ok(())
+    }
+
+    /// Prints `format` with `args` through `printf` and exits with the exit code of
+    /// a panic. Leaves the current block terminated.
+    fn generate_panic(
+        &mut self,
+        format: &str,
+        args: &[BasicMetadataValueEnum<'ctx>],
+    ) -> Result<()> {
+        let printf_function = *self.functions.get("printf").ok_or_else(|| {
+            CompileError::codegen_error("printf function not declared".to_string(), None)
+        })?;
+        let message = self
+            .builder
+            .build_global_string_ptr(format, "panic_message")
+            .map_err(|e| {
+                CompileError::codegen_error(format!("Failed to build string: {:?}", e), None)
+            })?;
+        let mut printf_args = vec![message.as_pointer_value().into()];
+        printf_args.extend_from_slice(args);
+        self.builder
+            .build_call(printf_function, &printf_args, "panic_printf")
+            .map_err(|e| {
+                CompileError::codegen_error(format!("Failed to call printf: {:?}", e), None)
+            })?;
+
+        // Exit rather than abort so the message is flushed; 101 is the exit code of a panic
+        let exit_function = self.module.get_function("exit").unwrap_or_else(|| {
+            let exit_type = self
+                .context
+                .void_type()
+                .fn_type(&[self.context.i32_type().into()], false);
+            self.module.add_function("exit", exit_type, None)
+        });
+        self.builder
+            .build_call(
+                exit_function,
+                &[self.context.i32_type().const_int(101, false).into()],
+                "",
+            )
+            .map_err(|e| {
+                CompileError::codegen_error(format!("Failed to call exit: {:?}", e), None)
+            })?;
+        self.builder.build_unreachable().map_err(|e| {
+            CompileError::codegen_error(format!("Failed to build unreachable: {:?}", e), None)
+        })?;
+        Ok(())
+    }
+
+    /// Builds integer `+`, `-` or `*` (also as `+=`, `-=`, `*=`). With overflow
+    /// checks enabled the operation goes through `llvm.sadd.with.overflow` (or
+    /// its unsigned and sub/mul relatives) and panics if the result does not fit.
+    fn build_int_arithmetic(
+        &mut self,
+        op: &BinaryOp,
+        left: IntValue<'ctx>,
+        right: IntValue<'ctx>,
+        is_unsigned: bool,
+        name: &str,
+    ) -> Result<IntValue<'ctx>> {
+        let build_error = |e: inkwell::builder::BuilderError| {
+            CompileError::codegen_error(format!("Failed to build {}: {:?}", name, e), None)
+        };
+        let (operation, verb) = match op {
+            BinaryOp::Add | BinaryOp::PlusAssign => ("add", "add"),
+            BinaryOp::Subtract | BinaryOp::MinusAssign => ("sub", "subtract"),
+            _ => ("mul", "multiply"),
+        };
+
+        if !self.options.overflow_checks {
+            return match operation {
+                "add" => self.builder.build_int_add(left, right, name),
+                "sub" => self.builder.build_int_sub(left, right, name),
+                _ => self.builder.build_int_mul(left, right, name),
+            }
+            .map_err(build_error);
+        }
+
+        let int_type = left.get_type();
+        let intrinsic_name = format!(
+            "llvm.{}{}.with.overflow.i{}",
+            if is_unsigned { "u" } else { "s" },
+            operation,
+            int_type.get_bit_width()
+        );
+        let result_type = self
+            .context
+            .struct_type(&[int_type.into(), self.context.bool_type().into()], false);
+        let intrinsic = self.get_or_declare_intrinsic(
+            &intrinsic_name,
+            &[int_type.into(), int_type.into()],
+            result_type.into(),
+        )?;
+        let result = self
+            .builder
+            .build_call(intrinsic, &[left.into(), right.into()], name)
+            .map_err(build_error)?
+            .try_as_basic_value()
+            .left()
+            .ok_or_else(|| {
+                CompileError::codegen_error(format!("{} returned no value", intrinsic_name), None)
+            })?
+            .into_struct_value();
+        let value = self
+            .builder
+            .build_extract_value(result, 0, name)
+            .map_err(build_error)?
+            .into_int_value();
+        let overflowed = self
+            .builder
+            .build_extract_value(result, 1, "overflowed")
+            .map_err(build_error)?
+            .into_int_value();
+
+        let function = self
+            .builder
+            .get_insert_block()
+            .and_then(|block| block.get_parent())
+            .ok_or_else(|| {
+                CompileError::codegen_error(
+                    "Arithmetic outside of function context".to_string(),
+                    None,
+                )
+            })?;
+        let ok_block = self.context.append_basic_block(function, "overflow_ok");
+        let panic_block = self.context.append_basic_block(function, "overflow_panic");
+        self.builder
+            .build_conditional_branch(overflowed, panic_block, ok_block)
+            .map_err(build_error)?;
+
+        self.builder.position_at_end(panic_block);
+        self.generate_panic(&format!("panic: attempt to {} with overflow\n", verb), &[])?;
+
+        self.builder.position_at_end(ok_block);
+        Ok(value)
+    }
+
+    /// Generates `wrapping_add(a, b)` and the other overflow arithmetic builtins.
+    ///
+    /// Wrapping operations are plain LLVM arithmetic, which is never checked;
+    /// saturating ones map to `llvm.sadd.sat`/`llvm.uadd.sat` (and `sub`), which
+    /// lower to `paddsb`/`paddusb` and friends for SIMD vectors.
+    fn generate_overflow_arithmetic_builtin(
+        &mut self,
+        func_name: &str,
+        args: &[Expr],
+        span: &Span,
+    ) -> Result<BasicValueEnum<'ctx>> {
+        if args.len() != 2 {
+            return Err(CompileError::codegen_error(
+                format!("{}() takes exactly 2 arguments", func_name),
+                None,
+            ));
+        }
+        let is_unsigned = self.is_unsigned_expression(span);
+        let left = self.generate_expression(&args[0])?;
+        let right = self.generate_expression(&args[1])?;
+        let build_error = |e: inkwell::builder::BuilderError| {
+            CompileError::codegen_error(format!("Failed to build {}: {:?}", func_name, e), None)
+        };
+
+        let (kind, operation) = func_name.split_once('_').unwrap_or(("wrapping", func_name));
+        if kind == "wrapping" {
+            return match (left, right) {
+                (BasicValueEnum::IntValue(left), BasicValueEnum::IntValue(right)) => {
+                    // Literals are i32; bring the right operand to the left one's width
+                    let right = self
+                        .builder
+                        .build_int_cast_sign_flag(right, left.get_type(), !is_unsigned, "operand")
+                        .map_err(build_error)?;
+                    match operation {
+                        "add" => self.builder.build_int_add(left, right, func_name),
+                        "sub" => self.builder.build_int_sub(left, right, func_name),
+                        _ => self.builder.build_int_mul(left, right, func_name),
+                    }
+                    .map(|value| value.into())
+                    .map_err(build_error)
+                }
+                (BasicValueEnum::VectorValue(left), BasicValueEnum::VectorValue(right)) => {
+                    match operation {
+                        "add" => self.builder.build_int_add(left, right, func_name),
+                        "sub" => self.builder.build_int_sub(left, right, func_name),
+                        _ => self.builder.build_int_mul(left, right, func_name),
+                    }
+                    .map(|value| value.into())
+                    .map_err(build_error)
+                }
+                _ => Err(CompileError::codegen_error(
+                    format!("{}() requires integer operands", func_name),
+                    None,
+                )),
+            };
+        }
+
+        let sign = if is_unsigned { "u" } else { "s" };
+        let (operand_type, right): (BasicTypeEnum<'ctx>, BasicValueEnum<'ctx>) = match (left, right)
+        {
+            (BasicValueEnum::IntValue(left), BasicValueEnum::IntValue(right)) => {
+                let right = self
+                    .builder
+                    .build_int_cast_sign_flag(right, left.get_type(), !is_unsigned, "operand")
+                    .map_err(build_error)?;
+                (left.get_type().into(), right.into())
+            }
+            (BasicValueEnum::VectorValue(left), BasicValueEnum::VectorValue(_)) => {
+                (left.get_type().into(), right)
+            }
+            _ => {
+                return Err(CompileError::codegen_error(
+                    format!("{}() requires integer operands", func_name),
+                    None,
+                ))
+            }
+        };
+        let type_suffix = match operand_type {
+            BasicTypeEnum::VectorType(vector_type) => format!(
+                "v{}i{}",
+                vector_type.get_size(),
+                vector_type.get_element_type().into_int_type().get_bit_width()
+            ),
+            other => format!("i{}", other.into_int_type().get_bit_width()),
+        };
+        let intrinsic_name = format!("llvm.{}{}.sat.{}", sign, operation, type_suffix);
+        let intrinsic = self.get_or_declare_intrinsic(
+            &intrinsic_name,
+            &[operand_type, operand_type],
+            operand_type,
+        )?;
+        self.builder
+            .build_call(intrinsic, &[left.into(), right.into()], func_name)
+            .map_err(build_error)?
+            .try_as_basic_value()
+            .left()
+            .ok_or_else(|| {
+                CompileError::codegen_error(format!("{} returned no value", intrinsic_name), None)
+            })
+    }
+
+    /// Whether the type checker found the expression at `span` to produce
+    /// unsigned integers. LLVM integers carry no sign, so this picks the
+    /// unsigned form of overflow checks and saturation.
+    fn is_unsigned_expression(&self, span: &Span) -> bool {
+        self.expressions
+            .unsigned
+            .contains(&(self.current_instance.clone(), span.range()))
+    }
+
+    /// Generates code for array slicing: array[start:end]
+    fn generate_array_slice(
+        &mut self,
+        array_expr: &Box<Expr>,
+        start_expr: &Box<Expr>,
+        end_expr: &Box<Expr>,
+    ) -> Result<BasicValueEnum<'ctx>> {
+        // Generate the array expression
+        let array_value = self.generate_expression(array_expr)?;
+        let start_value = self.generate_expression(start_expr)?;
+        let end_value = self.generate_expression(end_expr)?;
+
+        // Ensure indices are integers
+        let start_int = start_value.into_int_value();
+        let end_int = end_value.into_int_value();
+
+        // Get array pointer
+        let array_ptr = array_value.into_pointer_value();
+
+        // Calculate slice length
+        let _slice_length = self
+            .builder
+            .build_int_sub(end_int, start_int, "slice_length")
+            .unwrap();
+
+        // For now, create a pointer offset to simulate slicing
+        // This creates a new pointer that points to the start of the slice
+        let slice_start_ptr = unsafe {
+            self.builder
+                .build_gep(
+                    array_ptr,
+                    &[self.context.i32_type().const_zero(), start_int],
+                    "slice_start_ptr",
+                )
+                .unwrap()
+        };
+
+        // Return the slice start pointer
+        // Note: This is a simplified implementation that returns a pointer to the slice start
+        // A full implementation would create a new array with proper bounds
+        Ok(slice_start_ptr.into())
+    }
+
+    /// Converts SIMD vector type to LLVM vector type.
+    fn simd_type_to_llvm(&self, simd_type: &SIMDVectorType) -> Result<VectorType<'ctx>> {
+        match simd_type {
+            // Float vectors
+            SIMDVectorType::F32x2 => Ok(self.context.f32_type().vec_type(2)),
+            SIMDVectorType::F32x4 => Ok(self.context.f32_type().vec_type(4)),
+            SIMDVectorType::F32x8 => Ok(self.context.f32_type().vec_type(8)),
+            SIMDVectorType::F32x16 => Ok(self.context.f32_type().vec_type(16)),
+            SIMDVectorType::F64x2 => Ok(self.context.f64_type().vec_type(2)),
+            SIMDVectorType::F64x4 => Ok(self.context.f64_type().vec_type(4)),
+            SIMDVectorType::F64x8 => Ok(self.context.f64_type().vec_type(8)),
+
+            // Integer vectors
+            SIMDVectorType::I32x2 => Ok(self.context.i32_type().vec_type(2)),
+            SIMDVectorType::I32x4 => Ok(self.context.i32_type().vec_type(4)),
+            SIMDVectorType::I32x8 => Ok(self.context.i32_type().vec_type(8)),
+            SIMDVectorType::I32x16 => Ok(self.context.i32_type().vec_type(16)),
+            SIMDVectorType::I64x2 => Ok(self.context.i64_type().vec_type(2)),
+            SIMDVectorType::I64x4 => Ok(self.context.i64_type().vec_type(4)),
+            SIMDVectorType::I64x8 => Ok(self.context.i64_type().vec_type(8)),
+            SIMDVectorType::I16x4 => Ok(self.context.i16_type().vec_type(4)),
+            SIMDVectorType::I16x8 => Ok(self.context.i16_type().vec_type(8)),
+            SIMDVectorType::I16x16 => Ok(self.context.i16_type().vec_type(16)),
+            SIMDVectorType::I16x32 => Ok(self.context.i16_type().vec_type(32)),
+            SIMDVectorType::I8x8 => Ok(self.context.i8_type().vec_type(8)),
+            SIMDVectorType::I8x16 => Ok(self.context.i8_type().vec_type(16)),
+            SIMDVectorType::I8x32 => Ok(self.context.i8_type().vec_type(32)),
+            SIMDVectorType::I8x64 => Ok(self.context.i8_type().vec_type(64)),
+
+            // Unsigned integer vectors (LLVM treats as signed)
+            SIMDVectorType::U32x4 => Ok(self.context.i32_type().vec_type(4)),
+            SIMDVectorType::U32x8 => Ok(self.context.i32_type().vec_type(8)),
+            SIMDVectorType::U16x8 => Ok(self.context.i16_type().vec_type(8)),
+            SIMDVectorType::U16x16 => Ok(self.context.i16_type().vec_type(16)),
+            SIMDVectorType::U8x4 => Ok(self.context.i8_type().vec_type(4)),
+            SIMDVectorType::U8x8 => Ok(self.context.i8_type().vec_type(8)),
+            SIMDVectorType::U8x16 => Ok(self.context.i8_type().vec_type(16)),
+            SIMDVectorType::U8x32 => Ok(self.context.i8_type().vec_type(32)),
+
+            // Mask types (represented as integer vectors in LLVM)
+            SIMDVectorType::Mask8 => Ok(self.context.bool_type().vec_type(8)),
+            SIMDVectorType::Mask16 => Ok(self.context.bool_type().vec_type(16)),
+            SIMDVectorType::Mask32 => Ok(self.context.bool_type().vec_type(32)),
+            SIMDVectorType::Mask64 => Ok(self.context.bool_type().vec_type(64)),
+        }
+    }
+
+    /// Generates a SIMD element with the correct type for the target vector.
+    fn generate_simd_element(
+        &mut self,
+        element: &Expr,
+        vector_type: &SIMDVectorType,
+    ) -> Result<BasicValueEnum<'ctx>> {
+        match element {
+            Expr::Literal(Literal::Float(value), _) => {
+                // Generate float with the correct precision for the vector type
+                match vector_type.element_type() {
+                    "f32" => {
+                        let float_type = self.context.f32_type();
+                        Ok(float_type.const_float(*value).into())
+                    }
+                    "f64" => {
+                        let float_type = self.context.f64_type();
+                        Ok(float_type.const_float(*value).into())
+                    }
+                    _ => Err(CompileError::codegen_error(
+                        format!(
+                            "Cannot use float literal in {} vector",
+                            vector_type.element_type()
+                        ),
+                        None,
+                    )),
+                }
+            }
+            Expr::Literal(Literal::Integer(value), _) => {
+                // Generate integer with the correct width for the vector type
+                match vector_type.element_type() {
+                    "i8" => {
+                        let int_type = self.context.i8_type();
+                        Ok(int_type.const_int(*value as u64, true).into())
+                    }
+                    "i16" => {
+                        let int_type = self.context.i16_type();
+                        Ok(int_type.const_int(*value as u64, true).into())
+                    }
+                    "i32" => {
+                        let int_type = self.context.i32_type();
+                        Ok(int_type.const_int(*value as u64, true).into())
+                    }
+                    "i64" => {
+                        let int_type = self.context.i64_type();
+                        Ok(int_type.const_int(*value as u64, true).into())
+                    }
+                    "u8" | "u16" | "u32" => {
+                        // For unsigned types, use the corresponding signed type in LLVM
+                        // The actual signed/unsigned interpretation happens at the operation level
+                        match vector_type.element_type() {
+                            "u8" => Ok(self
+                                .context
+                                .i8_type()
+                                .const_int(*value as u64, false)
+                                .into()),
+                            "u16" => Ok(self
+                                .context
+                                .i16_type()
+                                .const_int(*value as u64, false)
+                                .into()),
+                            "u32" => Ok(self
+                                .context
+                                .i32_type()
+                                .const_int(*value as u64, false)
+                                .into()),
+                            _ => unreachable!(),
+                        }
+                    }
+                    _ => Err(CompileError::codegen_error(
+                        format!(
+                            "Cannot use integer literal in {} vector",
+                            vector_type.element_type()
+                        ),
+                        None,
+                    )),
+                }
+            }
+            _ => {
+                // For non-literal elements, generate normally and let type checking handle compatibility
+                self.generate_expression(element)
+            }
+        }
+    }
+
+    /// Generates code for SIMD vector literal.
+    fn generate_simd_vector_literal(
+        &mut self,
+        elements: &[Expr],
+        vector_type: &SIMDVectorType,
+    ) -> Result<VectorValue<'ctx>> {
+        // Generate values for each element with correct type for the vector
+        let mut element_values = Vec::new();
+        for element in elements {
+            let element_value = self.generate_simd_element(element, vector_type)?;
+            element_values.push(element_value);
+        }
+
+        // Validate element count matches vector width
+        if element_values.len() != vector_type.width() {
+            return Err(CompileError::codegen_error(
+                format!(
+                    "Vector literal element count mismatch: {} expected {}, got {}",
+                    vector_type,
+                    vector_type.width(),
+                    element_values.len()
+                ),
+                None,
+            ));
+        }
+
+        // Create LLVM vector constant based on element type
+        match vector_type.element_type() {
+            "f32" | "f64" => {
+                // For float vectors, create a constant vector
+                if let Some(first_val) = element_values.first() {
+                    if let BasicValueEnum::FloatValue(_float_val) = first_val {
+                        let const_vals: Vec<_> = element_values
+                            .iter()
+                            .filter_map(|v| {
+                                if let BasicValueEnum::FloatValue(fv) = v {
+                                    Some(*fv)
+                                } else {
+                                    None
+                                }
+                            })
+                            .collect();
+
+                        if const_vals.len() == element_values.len() {
+                            Ok(VectorType::const_vector(&const_vals))
+                        } else {
+                            // Handle mixed constant/runtime elements using efficient vector construction
+                            self.generate_mixed_vector_from_elements(&element_values, vector_type)
+                        }
+                    } else {
+                        Err(CompileError::codegen_error(
+                            "Expected float values for float vector".to_string(),
+                            None,
+                        ))
+                    }
+                } else {
+                    Err(CompileError::codegen_error(
+                        "Empty vector literal".to_string(),
+                        None,
+                    ))
+                }
+            }
+            _ => {
+                // For integer and boolean vectors
+                if let Some(first_val) = element_values.first() {
+                    if let BasicValueEnum::IntValue(_int_val) = first_val {
+                        let const_vals: Vec<_> = element_values
+                            .iter()
+                            .filter_map(|v| {
+                                if let BasicValueEnum::IntValue(iv) = v {
+                                    Some(*iv)
+                                } else {
+                                    None
+                                }
+                            })
+                            .collect();
+
+                        if const_vals.len() == element_values.len() {
+                            Ok(VectorType::const_vector(&const_vals))
+                        } else {
+                            // Handle mixed constant/runtime elements using efficient vector construction
+                            self.generate_mixed_vector_from_elements(&element_values, vector_type)
+                        }
+                    } else {
+                        Err(CompileError::codegen_error(
+                            "Expected integer values for integer vector".to_string(),
+                            None,
+                        ))
+                    }
+                } else {
+                    Err(CompileError::codegen_error(
+                        "Empty vector literal".to_string(),
+                        None,
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Generates a vector from mixed constant/runtime elements using efficient memory-based construction.
+    /// This avoids the problematic insertelement chain that can cause LLVM optimization failures.
+    fn generate_mixed_vector_from_elements(
+        &mut self,
+        element_values: &[BasicValueEnum<'ctx>],
+        vector_type: &SIMDVectorType,
+    ) -> Result<VectorValue<'ctx>> {
+        // Get the LLVM vector type
+        let llvm_vector_type = self.simd_type_to_llvm(vector_type)?;
+        
+        // Get the element type
+        let element_type: inkwell::types::BasicTypeEnum = match vector_type.element_type() {
+            "f32" => self.context.f32_type().into(),
+            "f64" => self.context.f64_type().into(),
+            "i8" | "u8" => self.context.i8_type().into(),
+            "i16" | "u16" => self.context.i16_type().into(),
+            "i32" | "u32" => self.context.i32_type().into(),
+            "i64" | "u64" => self.context.i64_type().into(),
+            _ => return Err(CompileError::codegen_error(
+                format!("Unsupported vector element type: {}", vector_type.element_type()),
+                None,
+            )),
+        };
+        
+        // Allocate temporary array for the vector elements
+        let array_type = element_type.array_type(element_values.len() as u32);
+        let array_alloca = self.builder.build_alloca(array_type, "temp_vector_array")
+            .map_err(|e| CompileError::codegen_error(format!("Failed to allocate vector array: {:?}", e), None))?;
+        
+        // Store each element in the array
+        for (i, element_value) in element_values.iter().enumerate() {
+            let index = self.context.i32_type().const_int(i as u64, false);
+            let element_ptr = unsafe {
+                self.builder.build_gep(array_alloca, &[self.context.i32_type().const_zero(), index], &format!("elem_ptr_{}", i))
+                    .map_err(|e| CompileError::codegen_error(format!("Failed to get element pointer: {:?}", e), None))?
+            };
+            
+            self.builder.build_store(element_ptr, *element_value)
+                .map_err(|e| CompileError::codegen_error(format!("Failed to store element: {:?}", e), None))?;
+        }
+        
+        // Cast the array pointer to a vector pointer
+        let vector_ptr_type = llvm_vector_type.ptr_type(AddressSpace::default());
+        let vector_ptr = self.builder.build_bitcast(array_alloca, vector_ptr_type, "vector_ptr")
+            .map_err(|e| CompileError::codegen_error(format!("Failed to bitcast to vector pointer: {:?}", e), None))?;
+        
+        // Load the vector from memory - this is much more efficient than insertelement chains
+        if let BasicValueEnum::PointerValue(ptr) = vector_ptr {
+            let vector_value = self.builder.build_load(ptr, "vector_load")
+                .map_err(|e| CompileError::codegen_error(format!("Failed to load vector: {:?}", e), None))?;
+                
+            match vector_value {
+                BasicValueEnum::VectorValue(v) => Ok(v),
+                _ => Err(CompileError::codegen_error("Vector load did not produce vector value".to_string(), None)),
+            }
+        } else {
+            Err(CompileError::codegen_error("Bitcast did not produce pointer".to_string(), None))
+        }
+    }
+
+    /// Generates code for SIMD element-wise operations.
+    fn generate_simd_elementwise(
+        &mut self,
+        left: &Expr,
+        operator: &SIMDOperator,
+        right: &Expr,
+    ) -> Result<BasicValueEnum<'ctx>> {
+        let left_val = self.generate_expression(left)?;
+        let right_val = self.generate_expression(right)?;
+
+        // Extract vector values
+        let (left_vec, right_vec) = match (left_val, right_val) {
+            (BasicValueEnum::VectorValue(lv), BasicValueEnum::VectorValue(rv)) => (lv, rv),
+            _ => {
+                return Err(CompileError::codegen_error(
+                    "Element-wise operations require vector operands".to_string(),
+                    None,
+                ))
+            }
+        };
+
+        // Generate the appropriate LLVM instruction based on operator
+        let result = match operator {
+            // Arithmetic operations
+            SIMDOperator::DotAdd => {
+                if left_vec.get_type().get_element_type().is_float_type() {
+                    self.builder
+                        .build_float_add(left_vec, right_vec, "simd_fadd")
+                        .map(|v| v.into())
+                } else {
+                    self.builder
+                        .build_int_add(left_vec, right_vec, "simd_add")
+                        .map(|v| v.into())
+                }
+            }
+            SIMDOperator::DotSubtract => {
+                if left_vec.get_type().get_element_type().is_float_type() {
+                    self.builder
+                        .build_float_sub(left_vec, right_vec, "simd_fsub")
+                        .map(|v| v.into())
+                } else {
+                    self.builder
+                        .build_int_sub(left_vec, right_vec, "simd_sub")
+                        .map(|v| v.into())
+                }
+            }
+            SIMDOperator::DotMultiply => {
+                if left_vec.get_type().get_element_type().is_float_type() {
+                    self.builder
+                        .build_float_mul(left_vec, right_vec, "simd_fmul")
+                        .map(|v| v.into())
+                } else {
+                    self.builder
+                        .build_int_mul(left_vec, right_vec, "simd_mul")
+                        .map(|v| v.into())
+                }
+            }
+            SIMDOperator::DotDivide => {
+                if left_vec.get_type().get_element_type().is_float_type() {
+                    self.builder
+                        .build_float_div(left_vec, right_vec, "simd_fdiv")
+                        .map(|v| v.into())
+                } else {
+                    self.builder
+                        .build_int_signed_div(left_vec, right_vec, "simd_sdiv")
+                        .map(|v| v.into())
+                }
+            }
+
+            // Bitwise operations
+            SIMDOperator::DotAnd => self
+                .builder
+                .build_and(left_vec, right_vec, "simd_and")
+                .map(|v| v.into()),
+            SIMDOperator::DotOr => self
+                .builder
+                .build_or(left_vec, right_vec, "simd_or")
+                .map(|v| v.into()),
+            SIMDOperator::DotXor => self
+                .builder
+                .build_xor(left_vec, right_vec, "simd_xor")
+                .map(|v| v.into()),
+
+            // Comparison operations
+            SIMDOperator::DotEqual => {
+                if left_vec.get_type().get_element_type().is_float_type() {
+                    self.builder
+                        .build_float_compare(
+                            FloatPredicate::OEQ,
+                            left_vec,
+                            right_vec,
+                            "simd_fcmp_eq",
+                        )
+                        .map(|v| v.into())
+                } else {
+                    self.builder
+                        .build_int_compare(IntPredicate::EQ, left_vec, right_vec, "simd_icmp_eq")
+                        .map(|v| v.into())
+                }
+            }
+            SIMDOperator::DotNotEqual => {
+                if left_vec.get_type().get_element_type().is_float_type() {
+                    self.builder
+                        .build_float_compare(
+                            FloatPredicate::ONE,
+                            left_vec,
+                            right_vec,
+                            "simd_fcmp_ne",
+                        )
+                        .map(|v| v.into())
+                } else {
+                    self.builder
+                        .build_int_compare(IntPredicate::NE, left_vec, right_vec, "simd_icmp_ne")
+                        .map(|v| v.into())
+                }
+            }
+            SIMDOperator::DotLess => {
+                if left_vec.get_type().get_element_type().is_float_type() {
+                    self.builder
+                        .build_float_compare(
+                            FloatPredicate::OLT,
+                            left_vec,
+                            right_vec,
+                            "simd_fcmp_lt",
+                        )
+                        .map(|v| v.into())
+                } else {
+                    self.builder
+                        .build_int_compare(IntPredicate::SLT, left_vec, right_vec, "simd_icmp_slt")
+                        .map(|v| v.into())
+                }
+            }
+            SIMDOperator::DotGreater => {
+                if left_vec.get_type().get_element_type().is_float_type() {
+                    self.builder
+                        .build_float_compare(
+                            FloatPredicate::OGT,
+                            left_vec,
+                            right_vec,
+                            "simd_fcmp_gt",
+                        )
+                        .map(|v| v.into())
+                } else {
+                    self.builder
+                        .build_int_compare(IntPredicate::SGT, left_vec, right_vec, "simd_icmp_sgt")
+                        .map(|v| v.into())
+                }
+            }
+            SIMDOperator::DotLessEqual => {
+                if left_vec.get_type().get_element_type().is_float_type() {
+                    self.builder
+                        .build_float_compare(
+                            FloatPredicate::OLE,
+                            left_vec,
+                            right_vec,
+                            "simd_fcmp_le",
+                        )
+                        .map(|v| v.into())
+                } else {
+                    self.builder
+                        .build_int_compare(IntPredicate::SLE, left_vec, right_vec, "simd_icmp_sle")
+                        .map(|v| v.into())
+                }
+            }
+            SIMDOperator::DotGreaterEqual => {
+                if left_vec.get_type().get_element_type().is_float_type() {
+                    self.builder
+                        .build_float_compare(
+                            FloatPredicate::OGE,
+                            left_vec,
+                            right_vec,
+                            "simd_fcmp_ge",
+                        )
+                        .map(|v| v.into())
+                } else {
+                    self.builder
+                        .build_int_compare(IntPredicate::SGE, left_vec, right_vec, "simd_icmp_sge")
+                        .map(|v| v.into())
+                }
+            }
+        };
+
+        result.map_err(|_| {
+            CompileError::codegen_error(
+                format!("Failed to generate SIMD {:?} operation", operator),
+                None,
+            )
+        })
+    }
+
+    /// Attempts to generate advanced SIMD code using hardware-specific optimizations
+    fn try_generate_advanced_simd_expression(
+        &mut self,
+        simd_expr: &SIMDExpr,
+    ) -> Result<BasicValueEnum<'ctx>> {
+        match simd_expr {
+            SIMDExpr::ElementWise {
+                left,
+                operator,
+                right,
+                ..
+            } => {
+                // Convert to AdvancedSIMDOp and generate optimized code
+                let advanced_op = match operator {
+                    SIMDOperator::DotAdd => AdvancedSIMDOp::Add {
+                        predicated: false,
+                        saturating: false,
+                    },
+                    SIMDOperator::DotMultiply => AdvancedSIMDOp::Multiply {
+                        fused: true,
+                        accumulate: false,
+                    },
+                    SIMDOperator::DotSubtract => AdvancedSIMDOp::Add {
+                        predicated: false,
+                        saturating: false,
+                    }, // Will negate second operand
+                    SIMDOperator::DotDivide => AdvancedSIMDOp::Divide {
+                        precise: true,
+                        approximate: false,
+                    },
+                    _ => {
+                        return Err(CompileError::codegen_error(
+                            "Advanced SIMD operation not supported for this operator".to_string(),
+                            None,
+                        ))
+                    }
+                };
+
+                // Get vector type from left operand
+                let left_val = self.generate_expression(left)?;
+                let vector_type = self.infer_simd_vector_type_from_value(&left_val)?;
+
+                // Create optimization hints
+                let optimization_hints = OptimizationHints {
+                    prefer_throughput: true,
+                    minimize_latency: false,
+                    optimize_for_size: false,
+                    cache_blocking: false,
+                    loop_unrolling: 4,
+                    vectorization_factor: vector_type.width(),
+                };
+
+                // Generate advanced SIMD code
+                let generated_code = if let Some(ref mut advanced_simd) = self.advanced_simd_codegen
+                {
+                    advanced_simd.generate_simd_code(
+                        &advanced_op,
+                        &vector_type,
+                        &optimization_hints,
+                    )
+                } else {
+                    return Err(CompileError::codegen_error(
+                        "Advanced SIMD not available".to_string(),
+                        None,
+                    ));
+                };
+
+                match generated_code {
+                    Ok(generated_code) => {
+                        // Convert generated instructions to LLVM IR
+                        self.emit_advanced_simd_instructions(&generated_code, left, right)
+                    }
+                    Err(_) => Err(CompileError::codegen_error(
+                        "Failed to generate advanced SIMD code".to_string(),
+                        None,
+                    )),
+                }
+            }
+            SIMDExpr::Reduction {
+                vector, operation, ..
+            } => {
+                // Generate advanced reduction using tree reduction if beneficial
+                let vector_val = self.generate_expression(vector)?;
+                let vector_type = self.infer_simd_vector_type_from_value(&vector_val)?;
+
+                let reduce_op = match operation {
+                    crate::ast::ReductionOp::Sum => crate::simd_advanced::ReduceOp::Sum,
+                    crate::ast::ReductionOp::Product => crate::simd_advanced::ReduceOp::Product,
+                    crate::ast::ReductionOp::Min => crate::simd_advanced::ReduceOp::Min,
+                    crate::ast::ReductionOp::Max => crate::simd_advanced::ReduceOp::Max,
+                    crate::ast::ReductionOp::And => crate::simd_advanced::ReduceOp::And,
+                    crate::ast::ReductionOp::Or => crate::simd_advanced::ReduceOp::Or,
+                    crate::ast::ReductionOp::Xor => crate::simd_advanced::ReduceOp::Xor,
+                    crate::ast::ReductionOp::Any => crate::simd_advanced::ReduceOp::Or, // Any is logical OR
+                    crate::ast::ReductionOp::All => crate::simd_advanced::ReduceOp::And, // All is logical AND
+                };
+
+                let advanced_op = AdvancedSIMDOp::Reduce {
+                    operation: reduce_op,
+                    tree: true,
+                };
+                let optimization_hints = OptimizationHints {
+                    prefer_throughput: true,
+                    minimize_latency: false,
+                    optimize_for_size: false,
+                    cache_blocking: false,
+                    loop_unrolling: 1,
+                    vectorization_factor: vector_type.width(),
+                };
+
+                let generated_code = if let Some(ref mut advanced_simd) = self.advanced_simd_codegen
+                {
+                    advanced_simd.generate_simd_code(
+                        &advanced_op,
+                        &vector_type,
+                        &optimization_hints,
+                    )
+                } else {
+                    return Err(CompileError::codegen_error(
+                        "Advanced SIMD not available".to_string(),
+                        None,
+                    ));
+                };
+
+                match generated_code {
+                    Ok(generated_code) => {
+                        self.emit_advanced_reduction_instructions(&generated_code, &vector_val)
+                    }
+                    Err(_) => Err(CompileError::codegen_error(
+                        "Failed to generate advanced reduction".to_string(),
+                        None,
+                    )),
+                }
+            }
+            _ => Err(CompileError::codegen_error(
+                "Advanced SIMD generation not implemented for this expression type".to_string(),
+                None,
+            )),
+        }
+    }
+
+    /// Generates code for a complete SIMD expression.
+    fn generate_simd_expression(&mut self, simd_expr: &SIMDExpr) -> Result<BasicValueEnum<'ctx>> {
+        // Generate full SIMD operations - the JIT execution engine should be properly configured
+        // to handle these operations
+        
+        // Use advanced SIMD code generation if available
+        if self.advanced_simd_codegen.is_some() {
+            if let Ok(result) = self.try_generate_advanced_simd_expression(simd_expr) {
+                return Ok(result);
+            }
+        }
+
+        // Fallback to basic SIMD implementation
+        match simd_expr {
+            SIMDExpr::VectorLiteral {
+                elements,
+                vector_type,
+                ..
+            } => {
+                if let Some(vtype) = vector_type {
+                    let vector_val = self.generate_simd_vector_literal(elements, vtype)?;
+                    Ok(vector_val.into())
+                } else {
+                    Err(CompileError::codegen_error(
+                        "Vector literal must have explicit type annotation".to_string(),
+                        None,
+                    ))
+                }
+            }
+            SIMDExpr::ElementWise {
+                left,
+                operator,
+                right,
+                ..
+            } => self.generate_simd_elementwise(left, operator, right),
+            SIMDExpr::Broadcast {
+                value, target_type, ..
+            } => {
+                // Generate scalar value
+                let scalar_val = self.generate_expression(value)?;
+
+                // Create vector by broadcasting scalar
+                let vector_width = target_type.width();
+                let _vector_vals = vec![scalar_val; vector_width];
+
+                // Create constant vector
+                match scalar_val {
+                    BasicValueEnum::FloatValue(fv) => {
+                        let const_vals = vec![fv; vector_width];
+                        Ok(VectorType::const_vector(&const_vals).into())
+                    }
+                    BasicValueEnum::IntValue(iv) => {
+                        let const_vals = vec![iv; vector_width];
+                        Ok(VectorType::const_vector(&const_vals).into())
+                    }
+                    _ => Err(CompileError::codegen_error(
+                        "Unsupported scalar type for broadcast".to_string(),
+                        None,
+                    )),
+                }
+            }
+            SIMDExpr::Swizzle {
+                vector, pattern, ..
+            } => self.generate_simd_swizzle(vector, pattern),
+            SIMDExpr::Reduction {
+                vector, operation, ..
+            } => {
+                let vector_val = self.generate_expression(vector)?;
+
+                if let BasicValueEnum::VectorValue(vec_val) = vector_val {
+                    // For now, implement simple horizontal reduction by extracting and adding elements
+                    // Note: Currently using element-wise reduction; target-specific horizontal instructions could be added later
+                    let vector_type = vec_val.get_type();
+                    let element_count = vector_type.get_size();
+
+                    if element_count == 0 {
+                        return Err(CompileError::codegen_error(
+                            "Cannot reduce empty vector".to_string(),
+                            None,
+                        ));
+                    }
+
+                    // Extract first element as accumulator
+                    let zero_index = self.context.i32_type().const_int(0, false);
+                    let mut accumulator = self
+                        .builder
+                        .build_extract_element(vec_val, zero_index, "extract_0")
+                        .map_err(|_| {
+                            CompileError::codegen_error(
+                                "Failed to extract vector element".to_string(),
+                                None,
+                            )
+                        })?;
+
+                    // Reduce remaining elements
+                    for i in 1..element_count {
+                        let index = self.context.i32_type().const_int(i as u64, false);
+                        let element = self
+                            .builder
+                            .build_extract_element(vec_val, index, &format!("extract_{}", i))
+                            .map_err(|_| {
+                                CompileError::codegen_error(
+                                    "Failed to extract vector element".to_string(),
+                                    None,
+                                )
+                            })?;
+
+                        accumulator = match operation {
+                            crate::ast::ReductionOp::Sum => {
+                                if vector_type.get_element_type().is_float_type() {
+                                    if let (
+                                        BasicValueEnum::FloatValue(acc),
+                                        BasicValueEnum::FloatValue(elem),
+                                    ) = (accumulator, element)
+                                    {
+                                        self.builder
+                                            .build_float_add(acc, elem, "reduce_add")
+                                            .map(|v| v.into())
+                                            .map_err(|_| {
+                                                CompileError::codegen_error(
+                                                    "Failed to build float add".to_string(),
+                                                    None,
+                                                )
+                                            })?
+                                    } else {
+                                        return Err(CompileError::codegen_error(
+                                            "Type mismatch in reduction".to_string(),
+                                            None,
+                                        ));
+                                    }
+                                } else {
+                                    if let (
+                                        BasicValueEnum::IntValue(acc),
+                                        BasicValueEnum::IntValue(elem),
+                                    ) = (accumulator, element)
+                                    {
+                                        self.builder
+                                            .build_int_add(acc, elem, "reduce_add")
+                                            .map(|v| v.into())
+                                            .map_err(|_| {
+                                                CompileError::codegen_error(
+                                                    "Failed to build int add".to_string(),
+                                                    None,
+                                                )
+                                            })?
+                                    } else {
+                                        return Err(CompileError::codegen_error(
+                                            "Type mismatch in reduction".to_string(),
+                                            None,
+                                        ));
+                                    }
+                                }
+                            }
+                            crate::ast::ReductionOp::Product => {
+                                if vector_type.get_element_type().is_float_type() {
+                                    if let (
+                                        BasicValueEnum::FloatValue(acc),
+                                        BasicValueEnum::FloatValue(elem),
+                                    ) = (accumulator, element)
+                                    {
+                                        self.builder
+                                            .build_float_mul(acc, elem, "reduce_mul")
+                                            .map(|v| v.into())
+                                            .map_err(|_| {
+                                                CompileError::codegen_error(
+                                                    "Failed to build float mul".to_string(),
+                                                    None,
+                                                )
+                                            })?
+                                    } else {
+                                        return Err(CompileError::codegen_error(
+                                            "Type mismatch in reduction".to_string(),
+                                            None,
+                                        ));
+                                    }
+                                } else {
+                                    if let (
+                                        BasicValueEnum::IntValue(acc),
+                                        BasicValueEnum::IntValue(elem),
+                                    ) = (accumulator, element)
+                                    {
+                                        self.builder
+                                            .build_int_mul(acc, elem, "reduce_mul")
+                                            .map(|v| v.into())
+                                            .map_err(|_| {
+                                                CompileError::codegen_error(
+                                                    "Failed to build int mul".to_string(),
+                                                    None,
+                                                )
+                                            })?
+                                    } else {
+                                        return Err(CompileError::codegen_error(
+                                            "Type mismatch in reduction".to_string(),
+                                            None,
+                                        ));
+                                    }
+                                }
+                            }
+                            crate::ast::ReductionOp::Min => {
+                                if vector_type.get_element_type().is_float_type() {
+                                    if let (
+                                        BasicValueEnum::FloatValue(acc),
+                                        BasicValueEnum::FloatValue(elem),
+                                    ) = (accumulator, element)
+                                    {
+                                        // Use fcmp + select for min
+                                        let cmp = self
+                                            .builder
+                                            .build_float_compare(
+                                                inkwell::FloatPredicate::OLT,
+                                                acc,
+                                                elem,
+                                                "min_cmp",
+                                            )
+                                            .map_err(|_| {
+                                                CompileError::codegen_error(
+                                                    "Failed to build float compare".to_string(),
+                                                    None,
+                                                )
+                                            })?;
+                                        self.builder
+                                            .build_select(cmp, acc, elem, "reduce_min")
+                                            .map(|v| v.into())
+                                            .map_err(|_| {
+                                                CompileError::codegen_error(
+                                                    "Failed to build select".to_string(),
+                                                    None,
+                                                )
+                                            })?
+                                    } else {
+                                        return Err(CompileError::codegen_error(
+                                            "Type mismatch in reduction".to_string(),
+                                            None,
+                                        ));
+                                    }
+                                } else {
+                                    if let (
+                                        BasicValueEnum::IntValue(acc),
+                                        BasicValueEnum::IntValue(elem),
+                                    ) = (accumulator, element)
+                                    {
+                                        // Use icmp + select for min
+                                        let cmp = self
+                                            .builder
+                                            .build_int_compare(
+                                                inkwell::IntPredicate::SLT,
+                                                acc,
+                                                elem,
+                                                "min_cmp",
+                                            )
+                                            .map_err(|_| {
+                                                CompileError::codegen_error(
+                                                    "Failed to build int compare".to_string(),
+                                                    None,
+                                                )
+                                            })?;
+                                        self.builder
+                                            .build_select(cmp, acc, elem, "reduce_min")
+                                            .map(|v| v.into())
+                                            .map_err(|_| {
+                                                CompileError::codegen_error(
+                                                    "Failed to build select".to_string(),
+                                                    None,
+                                                )
+                                            })?
+                                    } else {
+                                        return Err(CompileError::codegen_error(
+                                            "Type mismatch in reduction".to_string(),
+                                            None,
+                                        ));
+                                    }
+                                }
+                            }
+                            crate::ast::ReductionOp::Max => {
+                                if vector_type.get_element_type().is_float_type() {
+                                    if let (
+                                        BasicValueEnum::FloatValue(acc),
+                                        BasicValueEnum::FloatValue(elem),
+                                    ) = (accumulator, element)
+                                    {
+                                        // Use fcmp + select for max
+                                        let cmp = self
+                                            .builder
+                                            .build_float_compare(
+                                                inkwell::FloatPredicate::OGT,
+                                                acc,
+                                                elem,
+                                                "max_cmp",
+                                            )
+                                            .map_err(|_| {
+                                                CompileError::codegen_error(
+                                                    "Failed to build float compare".to_string(),
+                                                    None,
+                                                )
+                                            })?;
+                                        self.builder
+                                            .build_select(cmp, acc, elem, "reduce_max")
+                                            .map(|v| v.into())
+                                            .map_err(|_| {
+                                                CompileError::codegen_error(
+                                                    "Failed to build select".to_string(),
+                                                    None,
+                                                )
+                                            })?
+                                    } else {
+                                        return Err(CompileError::codegen_error(
+                                            "Type mismatch in reduction".to_string(),
+                                            None,
+                                        ));
+                                    }
+                                } else {
+                                    if let (
+                                        BasicValueEnum::IntValue(acc),
+                                        BasicValueEnum::IntValue(elem),
+                                    ) = (accumulator, element)
+                                    {
+                                        // Use icmp + select for max
+                                        let cmp = self
+                                            .builder
+                                            .build_int_compare(
+                                                inkwell::IntPredicate::SGT,
+                                                acc,
+                                                elem,
+                                                "max_cmp",
+                                            )
+                                            .map_err(|_| {
+                                                CompileError::codegen_error(
+                                                    "Failed to build int compare".to_string(),
+                                                    None,
+                                                )
+                                            })?;
+                                        self.builder
+                                            .build_select(cmp, acc, elem, "reduce_max")
+                                            .map(|v| v.into())
+                                            .map_err(|_| {
+                                                CompileError::codegen_error(
+                                                    "Failed to build select".to_string(),
+                                                    None,
+                                                )
+                                            })?
+                                    } else {
+                                        return Err(CompileError::codegen_error(
+                                            "Type mismatch in reduction".to_string(),
+                                            None,
+                                        ));
+                                    }
+                                }
+                            }
+                            crate::ast::ReductionOp::And => {
+                                if let (
+                                    BasicValueEnum::IntValue(acc),
+                                    BasicValueEnum::IntValue(elem),
+                                ) = (accumulator, element)
+                                {
+                                    // Bitwise AND for integer types
+                                    self.builder
+                                        .build_and(acc, elem, "reduce_and")
+                                        .map(|v| v.into())
+                                        .map_err(|_| {
+                                            CompileError::codegen_error(
+                                                "Failed to build bitwise and".to_string(),
+                                                None,
+                                            )
+                                        })?
+                                } else {
+                                    return Err(CompileError::codegen_error(
+                                        "And reduction only supports integer types".to_string(),
+                                        None,
+                                    ));
+                                }
+                            }
+                            crate::ast::ReductionOp::Or => {
+                                if let (
+                                    BasicValueEnum::IntValue(acc),
+                                    BasicValueEnum::IntValue(elem),
+                                ) = (accumulator, element)
+                                {
+                                    // Bitwise OR for integer types
+                                    self.builder
+                                        .build_or(acc, elem, "reduce_or")
+                                        .map(|v| v.into())
+                                        .map_err(|_| {
+                                            CompileError::codegen_error(
+                                                "Failed to build bitwise or".to_string(),
+                                                None,
+                                            )
+                                        })?
+                                } else {
+                                    return Err(CompileError::codegen_error(
+                                        "Or reduction only supports integer types".to_string(),
+                                        None,
+                                    ));
+                                }
+                            }
+                            crate::ast::ReductionOp::Xor => {
+                                if let (
+                                    BasicValueEnum::IntValue(acc),
+                                    BasicValueEnum::IntValue(elem),
+                                ) = (accumulator, element)
+                                {
+                                    // Bitwise XOR for integer types
+                                    self.builder
+                                        .build_xor(acc, elem, "reduce_xor")
+                                        .map(|v| v.into())
+                                        .map_err(|_| {
+                                            CompileError::codegen_error(
+                                                "Failed to build bitwise xor".to_string(),
+                                                None,
+                                            )
+                                        })?
+                                } else {
+                                    return Err(CompileError::codegen_error(
+                                        "Xor reduction only supports integer types".to_string(),
+                                        None,
+                                    ));
+                                }
+                            }
+                            crate::ast::ReductionOp::Any => {
+                                if let (
+                                    BasicValueEnum::IntValue(acc),
+                                    BasicValueEnum::IntValue(elem),
+                                ) = (accumulator, element)
+                                {
+                                    // Any is logical OR (for boolean vectors represented as integers)
+                                    self.builder
+                                        .build_or(acc, elem, "reduce_any")
+                                        .map(|v| v.into())
+                                        .map_err(|_| {
+                                            CompileError::codegen_error(
+                                                "Failed to build logical or for any".to_string(),
+                                                None,
+                                            )
+                                        })?
+                                } else {
+                                    return Err(CompileError::codegen_error(
+                                        "Any reduction only supports integer types".to_string(),
+                                        None,
+                                    ));
+                                }
+                            }
+                            crate::ast::ReductionOp::All => {
+                                if let (
+                                    BasicValueEnum::IntValue(acc),
+                                    BasicValueEnum::IntValue(elem),
+                                ) = (accumulator, element)
+                                {
+                                    // All is logical AND (for boolean vectors represented as integers)
+                                    self.builder
+                                        .build_and(acc, elem, "reduce_all")
+                                        .map(|v| v.into())
+                                        .map_err(|_| {
+                                            CompileError::codegen_error(
+                                                "Failed to build logical and for all".to_string(),
+                                                None,
+                                            )
+                                        })?
+                                } else {
+                                    return Err(CompileError::codegen_error(
+                                        "All reduction only supports integer types".to_string(),
+                                        None,
+                                    ));
+                                }
+                            }
+                        };
+                    }
+
+                    Ok(accumulator)
+                } else {
+                    Err(CompileError::codegen_error(
+                        "Reduction requires vector operand".to_string(),
+                        None,
+                    ))
+                }
+            }
+            SIMDExpr::DotProduct { left, right, .. } => {
+                // Generate dot product: sum of element-wise multiplication
+                let left_val = self.generate_expression(left)?;
+                let right_val = self.generate_expression(right)?;
+
+                if let (
+                    BasicValueEnum::VectorValue(left_vec),
+                    BasicValueEnum::VectorValue(right_vec),
+                ) = (left_val, right_val)
+                {
+                    // First, do element-wise multiplication
+                    let product = if left_vec.get_type().get_element_type().is_float_type() {
+                        self.builder
+                            .build_float_mul(left_vec, right_vec, "dot_mul")
+                            .map_err(|_| {
+                                CompileError::codegen_error(
+                                    "Failed to build vector multiply".to_string(),
+                                    None,
+                                )
+                            })?
+                    } else {
+                        self.builder
+                            .build_int_mul(left_vec, right_vec, "dot_mul")
+                            .map_err(|_| {
+                                CompileError::codegen_error(
+                                    "Failed to build vector multiply".to_string(),
+                                    None,
+                                )
+                            })?
+                    };
+
+                    // Then reduce with sum
+                    let vector_type = product.get_type();
+                    let element_count = vector_type.get_size();
+
+                    if element_count == 0 {
+                        return Err(CompileError::codegen_error(
+                            "Cannot compute dot product of empty vectors".to_string(),
+                            None,
+                        ));
+                    }
+
+                    // Extract first element as accumulator
+                    let zero_index = self.context.i32_type().const_int(0, false);
+                    let mut accumulator = self
+                        .builder
+                        .build_extract_element(product, zero_index, "extract_0")
+                        .map_err(|_| {
+                            CompileError::codegen_error(
+                                "Failed to extract vector element".to_string(),
+                                None,
+                            )
+                        })?;
+
+                    // Sum remaining elements
+                    for i in 1..element_count {
+                        let index = self.context.i32_type().const_int(i as u64, false);
+                        let element = self
+                            .builder
+                            .build_extract_element(product, index, &format!("extract_{}", i))
+                            .map_err(|_| {
+                                CompileError::codegen_error(
+                                    "Failed to extract vector element".to_string(),
+                                    None,
+                                )
+                            })?;
+
+                        accumulator = if vector_type.get_element_type().is_float_type() {
+                            if let (
+                                BasicValueEnum::FloatValue(acc),
+                                BasicValueEnum::FloatValue(elem),
+                            ) = (accumulator, element)
+                            {
+                                self.builder
+                                    .build_float_add(acc, elem, "dot_add")
+                                    .map(|v| v.into())
+                                    .map_err(|_| {
+                                        CompileError::codegen_error(
+                                            "Failed to build float add".to_string(),
+                                            None,
+                                        )
+                                    })?
+                            } else {
+                                return Err(CompileError::codegen_error(
+                                    "Type mismatch in dot product".to_string(),
+                                    None,
+                                ));
+                            }
+                        } else {
+                            if let (BasicValueEnum::IntValue(acc), BasicValueEnum::IntValue(elem)) =
+                                (accumulator, element)
+                            {
+                                self.builder
+                                    .build_int_add(acc, elem, "dot_add")
+                                    .map(|v| v.into())
+                                    .map_err(|_| {
+                                        CompileError::codegen_error(
+                                            "Failed to build int add".to_string(),
+                                            None,
+                                        )
+                                    })?
+                            } else {
+                                return Err(CompileError::codegen_error(
+                                    "Type mismatch in dot product".to_string(),
+                                    None,
+                                ));
+                            }
+                        };
+                    }
+
+                    Ok(accumulator)
+                } else {
+                    Err(CompileError::codegen_error(
+                        "Dot product requires two vector operands".to_string(),
+                        None,
+                    ))
+                }
+            }
+            SIMDExpr::VectorLoad {
+                address,
+                vector_type,
+                alignment,
+                ..
+            } => self.generate_vector_load(address, vector_type, *alignment),
+            SIMDExpr::VectorStore {
+                address,
+                vector,
+                alignment,
+                ..
+            } => {
+                // Generate vector store to memory
+                let address_val = self.generate_expression(address)?;
+                let vector_val = self.generate_expression(vector)?;
+
+                if let (BasicValueEnum::PointerValue(ptr), BasicValueEnum::VectorValue(vec)) =
+                    (address_val, vector_val)
+                {
+                    // Cast pointer to vector pointer type if needed
+                    let vector_ptr = if ptr.get_type().get_element_type() != vec.get_type().into() {
+                        self.builder
+                            .build_bitcast(
+                                ptr,
+                                vec.get_type().ptr_type(AddressSpace::default()),
+                                "vector_ptr_cast",
+                            )
+                            .map_err(|_| {
+                                CompileError::codegen_error(
+                                    "Failed to cast pointer for vector store".to_string(),
+                                    None,
+                                )
+                            })?
+                            .into_pointer_value()
+                    } else {
+                        ptr
+                    };
+
+                    // Set alignment based on parameter or infer from vector type
+                    let align_bytes = alignment.unwrap_or_else(|| {
+                        // A pointer into a buffer of elements is only guaranteed element alignment
+                        if !ptr.get_type().get_element_type().is_vector_type() {
+                            return self.element_alignment(vec.get_type().get_element_type());
+                        }
+                        // Infer alignment from vector width
+                        let element_count = vec.get_type().get_size();
+                        let element_size = if vec.get_type().get_element_type().is_float_type() {
+                            // Assume f32 for now, could be enhanced later
+                            4
+                        } else {
+                            vec.get_type()
+                                .get_element_type()
+                                .into_int_type()
+                                .get_bit_width()
+                                / 8
+                        };
+                        let total_bytes = element_count * element_size;
+                        // Use natural alignment for common SIMD sizes
+                        if total_bytes >= 32 {
+                            32
+                        } else if total_bytes >= 16 {
+                            16
+                        } else {
+                            total_bytes
+                        }
+                    });
+
+                    // Build the store instruction
+                    let store_inst = self.builder.build_store(vector_ptr, vec).map_err(|_| {
+                        CompileError::codegen_error(
+                            "Failed to build vector store".to_string(),
+                            None,
+                        )
+                    })?;
+
+                    // Set alignment
+                    store_inst.set_alignment(align_bytes).map_err(|_| {
+                        CompileError::codegen_error(
+                            "Failed to set store alignment".to_string(),
+                            None,
+                        )
+                    })?;
+
+                    // Vector store returns void
+                    Ok(self.context.i32_type().const_int(0, false).into())
+                } else {
+                    Err(CompileError::codegen_error(
+                        "Vector store requires pointer address and vector value".to_string(),
+                        None,
+                    ))
+                }
+            }
+            SIMDExpr::Select {
+                mask,
+                if_true,
+                if_false,
+                ..
+            } => {
+                let if_true = self.generate_vector_operand(if_true, "select")?;
+                let if_false = self.generate_vector_operand(if_false, "select")?;
+                let mask = self.generate_simd_mask(mask, if_true.get_type().get_size())?;
+                self.builder
+                    .build_select(mask, if_true, if_false, "simd_select")
+                    .map_err(|e| {
+                        CompileError::codegen_error(format!("Failed to build select: {:?}", e), None)
+                    })
+            }
+            SIMDExpr::MaskedLoad {
+                address,
+                mask,
+                passthru,
+                ..
+            } => self.generate_masked_load(address, mask, passthru),
+            SIMDExpr::MaskedStore {
+                address,
+                vector,
+                mask,
+                ..
+            } => self.generate_masked_store(address, vector, mask),
+            SIMDExpr::Gather { base, indices, .. } => self.generate_gather(base, indices),
+            SIMDExpr::Scatter {
+                base,
+                indices,
+                vector,
+                ..
+            } => self.generate_scatter(base, indices, vector),
+            SIMDExpr::Shuffle {
+                left, right, mask, ..
+            } => self.generate_shuffle(left, right, mask),
+            SIMDExpr::NativeVectorLoad {
+                address,
+                element_type,
+                alignment,
+                ..
+            } => {
+                let vector_type = self.native_vector_type(element_type)?;
+                self.generate_vector_load(address, &vector_type, *alignment)
+            }
+            SIMDExpr::Lanes { element_type, .. } => {
+                let lanes = self.native_vector_type(element_type)?.width();
+                Ok(self
+                    .context
+                    .i32_type()
+                    .const_int(lanes as u64, false)
+                    .into())
+            }
+        }
+    }
+
+    /// Generates a load of `vector_type` from the pointer `address`.
+    fn generate_vector_load(
+        &mut self,
+        address: &Expr,
+        vector_type: &SIMDVectorType,
+        alignment: Option<u32>,
+    ) -> Result<BasicValueEnum<'ctx>> {
+        let address_val = self.generate_expression(address)?;
+
+        if let BasicValueEnum::PointerValue(ptr) = address_val {
+            // Get the LLVM vector type
+            let llvm_vector_type = self.simd_type_to_llvm(vector_type)?;
+
+            // Cast pointer to vector pointer type if needed
+            let vector_ptr = if ptr.get_type().get_element_type() != llvm_vector_type.into() {
+                self.builder
+                    .build_bitcast(
+                        ptr,
+                        llvm_vector_type.ptr_type(AddressSpace::default()),
+                        "vector_ptr_cast",
+                    )
+                    .map_err(|_| {
+                        CompileError::codegen_error(
+                            "Failed to cast pointer for vector load".to_string(),
+                            None,
+                        )
+                    })?
+                    .into_pointer_value()
+            } else {
+                ptr
+            };
+
+            // Set alignment based on parameter or default; a pointer into a
+            // buffer of elements is only guaranteed element alignment
+            let align_bytes = alignment.unwrap_or_else(|| {
+                if ptr.get_type().get_element_type().is_vector_type() {
+                    self.get_default_alignment(vector_type)
+                } else {
+                    self.element_alignment(llvm_vector_type.get_element_type())
+                }
+            });
+
+            // Build the load instruction
+            let load_inst = self
+                .builder
+                .build_load(vector_ptr, "vector_load")
+                .map_err(|_| {
+                    CompileError::codegen_error("Failed to build vector load".to_string(), None)
+                })?;
+
+            // Set alignment
+            if let BasicValueEnum::VectorValue(vec_val) = load_inst {
+                if let Some(load_inst) = vec_val.as_instruction_value() {
+                    load_inst.set_alignment(align_bytes).map_err(|_| {
+                        CompileError::codegen_error(
+                            "Failed to set load alignment".to_string(),
+                            None,
+                        )
+                    })?;
+                }
+            }
+
+            Ok(load_inst)
+        } else {
+            Err(CompileError::codegen_error(
+                "Vector load requires pointer address".to_string(),
+                None,
+            ))
+        }
+    }
+
+    /// Generates `masked_load(address, mask, passthru)` as `llvm.masked.load`:
+    /// lanes whose mask bit is clear are not read and come from `passthru`.
+    fn generate_masked_load(
+        &mut self,
+        address: &Expr,
+        mask: &Expr,
+        passthru: &Expr,
+    ) -> Result<BasicValueEnum<'ctx>> {
+        let passthru = self.generate_vector_operand(passthru, "masked_load")?;
+        let vector_type = passthru.get_type();
+        let vector_ptr = self.generate_vector_address(address, vector_type, "masked_load")?;
+        let mask = self.generate_simd_mask(mask, vector_type.get_size())?;
+
+        let suffix = self.vector_intrinsic_suffix(vector_type);
+        let intrinsic_name = format!("llvm.masked.load.{}.p0{}", suffix, suffix);
+        let i32_type = self.context.i32_type();
+        let intrinsic = self.get_or_declare_intrinsic(
+            &intrinsic_name,
+            &[
+                vector_ptr.get_type().into(),
+                i32_type.into(),
+                mask.get_type().into(),
+                vector_type.into(),
+            ],
+            vector_type.into(),
+        )?;
+
+        let alignment = self.element_alignment(vector_type.get_element_type());
+        self.builder
+            .build_call(
+                intrinsic,
+                &[
+                    vector_ptr.into(),
+                    i32_type.const_int(alignment as u64, false).into(),
+                    mask.into(),
+                    passthru.into(),
+                ],
+                "masked_load",
+            )
+            .map_err(|e| {
+                CompileError::codegen_error(format!("Failed to build masked load: {:?}", e), None)
+            })?
+            .try_as_basic_value()
+            .left()
+            .ok_or_else(|| {
+                CompileError::codegen_error("Masked load returned no value".to_string(), None)
+            })
+    }
+
+    /// Generates `masked_store(address, vector, mask)` as `llvm.masked.store`:
+    /// memory behind lanes whose mask bit is clear is left untouched.
+    fn generate_masked_store(
+        &mut self,
+        address: &Expr,
+        vector: &Expr,
+        mask: &Expr,
+    ) -> Result<BasicValueEnum<'ctx>> {
+        let vector = self.generate_vector_operand(vector, "masked_store")?;
+        let vector_type = vector.get_type();
+        let vector_ptr = self.generate_vector_address(address, vector_type, "masked_store")?;
+        let mask = self.generate_simd_mask(mask, vector_type.get_size())?;
+
+        let suffix = self.vector_intrinsic_suffix(vector_type);
+        let intrinsic_name = format!("llvm.masked.store.{}.p0{}", suffix, suffix);
+        let i32_type = self.context.i32_type();
+        let intrinsic = self.get_or_declare_void_intrinsic(
+            &intrinsic_name,
+            &[
+                vector_type.into(),
+                vector_ptr.get_type().into(),
+                i32_type.into(),
+                mask.get_type().into(),
+            ],
+        );
+
+        let alignment = self.element_alignment(vector_type.get_element_type());
+        self.builder
+            .build_call(
+                intrinsic,
+                &[
+                    vector.into(),
+                    vector_ptr.into(),
+                    i32_type.const_int(alignment as u64, false).into(),
+                    mask.into(),
+                ],
+                "",
+            )
+            .map_err(|e| {
+                CompileError::codegen_error(format!("Failed to build masked store: {:?}", e), None)
+            })?;
+
+        // Like a vector store, a masked store has no value
+        Ok(self.context.i32_type().const_int(0, false).into())
+    }
+
+    fn generate_vector_operand(
+        &mut self,
+        expr: &Expr,
+        operation: &str,
+    ) -> Result<VectorValue<'ctx>> {
+        match self.generate_expression(expr)? {
+            BasicValueEnum::VectorValue(vector) => Ok(vector),
+            _ => Err(CompileError::codegen_error(
+                format!("{}() requires SIMD vector operands", operation),
+                None,
+            )),
+        }
+    }
+
+    /// Generates `address` as a pointer to a whole `vector_type`, as the masked
+    /// memory intrinsics expect.
+    fn generate_vector_address(
+        &mut self,
+        address: &Expr,
+        vector_type: VectorType<'ctx>,
+        operation: &str,
+    ) -> Result<PointerValue<'ctx>> {
+        let ptr = match self.generate_expression(address)? {
+            BasicValueEnum::PointerValue(ptr) => ptr,
+            _ => {
+                return Err(CompileError::codegen_error(
+                    format!("{}() requires pointer address", operation),
+                    None,
+                ))
+            }
+        };
+        if ptr.get_type().get_element_type() == vector_type.into() {
+            return Ok(ptr);
+        }
+        self.builder
+            .build_bitcast(
+                ptr,
+                vector_type.ptr_type(AddressSpace::default()),
+                "vector_ptr_cast",
+            )
+            .map(|value| value.into_pointer_value())
+            .map_err(|e| {
+                CompileError::codegen_error(format!("Failed to cast pointer: {:?}", e), None)
+            })
+    }
+
+    /// Generates a mask operand as `<lanes x i1>`. Comparisons already produce
+    /// that; integer and float vectors select the lanes that are not zero.
+    fn generate_simd_mask(&mut self, mask: &Expr, lanes: u32) -> Result<VectorValue<'ctx>> {
+        let mask = self.generate_vector_operand(mask, "mask")?;
+        let mask_type = mask.get_type();
+        if mask_type.get_size() != lanes {
+            return Err(CompileError::codegen_error(
+                format!(
+                    "Mask has {} lanes but the vector has {}",
+                    mask_type.get_size(),
+                    lanes
+                ),
+                None,
+            ));
+        }
+
+        let build_error = |e: inkwell::builder::BuilderError| {
+            CompileError::codegen_error(format!("Failed to build mask: {:?}", e), None)
+        };
+        match mask_type.get_element_type() {
+            BasicTypeEnum::IntType(int_type) if int_type.get_bit_width() == 1 => Ok(mask),
+            BasicTypeEnum::IntType(_) => self
+                .builder
+                .build_int_compare(IntPredicate::NE, mask, mask_type.const_zero(), "mask")
+                .map_err(build_error),
+            _ => self
+                .builder
+                .build_float_compare(FloatPredicate::UNE, mask, mask_type.const_zero(), "mask")
+                .map_err(build_error),
+        }
+    }
+
+    /// Generates `gather(base, indices)` as `llvm.masked.gather` with every lane
+    /// enabled: lane `i` reads `base[indices[i]]`.
+    fn generate_gather(&mut self, base: &Expr, indices: &Expr) -> Result<BasicValueEnum<'ctx>> {
+        let pointers = self.generate_lane_pointers(base, indices, "gather")?;
+        let lanes = pointers.get_type().get_size();
+        let element_type = self.lane_pointer_element_type(pointers)?;
+        let vector_type = match element_type {
+            BasicTypeEnum::IntType(int_type) => int_type.vec_type(lanes),
+            BasicTypeEnum::FloatType(float_type) => float_type.vec_type(lanes),
+            _ => {
+                return Err(CompileError::codegen_error(
+                    "gather() requires a pointer to numbers".to_string(),
+                    None,
+                ))
+            }
+        };
+
+        let intrinsic_name = format!(
+            "llvm.masked.gather.{}.v{}p0{}",
+            self.vector_intrinsic_suffix(vector_type),
+            lanes,
+            self.scalar_intrinsic_suffix(element_type)
+        );
+        let i32_type = self.context.i32_type();
+        let mask = self.all_lanes_mask(lanes);
+        let intrinsic = self.get_or_declare_intrinsic(
+            &intrinsic_name,
+            &[
+                pointers.get_type().into(),
+                i32_type.into(),
+                mask.get_type().into(),
+                vector_type.into(),
+            ],
+            vector_type.into(),
+        )?;
+
+        let alignment = self.element_alignment(element_type);
+        self.builder
+            .build_call(
+                intrinsic,
+                &[
+                    pointers.into(),
+                    i32_type.const_int(alignment as u64, false).into(),
+                    mask.into(),
+                    vector_type.get_undef().into(),
+                ],
+                "gather",
+            )
+            .map_err(|e| {
+                CompileError::codegen_error(format!("Failed to build gather: {:?}", e), None)
+            })?
+            .try_as_basic_value()
+            .left()
+            .ok_or_else(|| {
+                CompileError::codegen_error("Gather returned no value".to_string(), None)
+            })
+    }
+
+    /// Generates `scatter(base, indices, vector)` as `llvm.masked.scatter` with
+    /// every lane enabled: lane `i` is written to `base[indices[i]]`.
+    fn generate_scatter(
+        &mut self,
+        base: &Expr,
+        indices: &Expr,
+        vector: &Expr,
+    ) -> Result<BasicValueEnum<'ctx>> {
+        let pointers = self.generate_lane_pointers(base, indices, "scatter")?;
+        let lanes = pointers.get_type().get_size();
+        let element_type = self.lane_pointer_element_type(pointers)?;
+        let vector = self.generate_vector_operand(vector, "scatter")?;
+
+        let intrinsic_name = format!(
+            "llvm.masked.scatter.{}.v{}p0{}",
+            self.vector_intrinsic_suffix(vector.get_type()),
+            lanes,
+            self.scalar_intrinsic_suffix(element_type)
+        );
+        let i32_type = self.context.i32_type();
+        let mask = self.all_lanes_mask(lanes);
+        let intrinsic = self.get_or_declare_void_intrinsic(
+            &intrinsic_name,
+            &[
+                vector.get_type().into(),
+                pointers.get_type().into(),
+                i32_type.into(),
+                mask.get_type().into(),
+            ],
+        );
+
+        let alignment = self.element_alignment(element_type);
+        self.builder
+            .build_call(
+                intrinsic,
+                &[
+                    vector.into(),
+                    pointers.into(),
+                    i32_type.const_int(alignment as u64, false).into(),
+                    mask.into(),
+                ],
+                "",
+            )
+            .map_err(|e| {
+                CompileError::codegen_error(format!("Failed to build scatter: {:?}", e), None)
+            })?;
+
+        // Like a vector store, a scatter has no value
+        Ok(self.context.i32_type().const_int(0, false).into())
+    }
+
+    /// Generates `shuffle(a, b, mask)` as a `shufflevector` with the constant mask.
+    fn generate_shuffle(
+        &mut self,
+        left: &Expr,
+        right: &Expr,
+        mask: &Expr,
+    ) -> Result<BasicValueEnum<'ctx>> {
+        let left = self.generate_vector_operand(left, "shuffle")?;
+        let right = self.generate_vector_operand(right, "shuffle")?;
+        let indices = crate::ast::constant_lane_indices(mask).ok_or_else(|| {
+            CompileError::codegen_error(
+                "shuffle() mask must be a constant array of lane indices".to_string(),
+                None,
+            )
+        })?;
+
+        let i32_type = self.context.i32_type();
+        let mask_values: Vec<_> = indices
+            .iter()
+            .map(|&index| i32_type.const_int(index as u64, false))
+            .collect();
+        self.builder
+            .build_shuffle_vector(
+                left,
+                right,
+                VectorType::const_vector(&mask_values),
+                "shuffle",
+            )
+            .map(|value| value.into())
+            .map_err(|e| {
+                CompileError::codegen_error(format!("Failed to build shuffle: {:?}", e), None)
+            })
+    }
+
+    /// Generates one pointer per lane of `indices` for the gather and scatter
+    /// intrinsics: lane `i` holds `base` offset by `indices[i]` elements.
+    fn generate_lane_pointers(
+        &mut self,
+        base: &Expr,
+        indices: &Expr,
+        operation: &str,
+    ) -> Result<VectorValue<'ctx>> {
+        let base = match self.generate_expression(base)? {
+            BasicValueEnum::PointerValue(ptr) => ptr,
+            _ => {
+                return Err(CompileError::codegen_error(
+                    format!("{}() requires pointer base", operation),
+                    None,
+                ))
+            }
+        };
+        let indices = self.generate_vector_operand(indices, operation)?;
+        let lanes = indices.get_type().get_size();
+        let build_error = |e: inkwell::builder::BuilderError| {
+            CompileError::codegen_error(format!("Failed to build {}: {:?}", operation, e), None)
+        };
+
+        let mut pointers = base.get_type().vec_type(lanes).get_undef();
+        for lane in 0..lanes {
+            let lane_index = self.context.i32_type().const_int(lane as u64, false);
+            let index = self
+                .builder
+                .build_extract_element(indices, lane_index, "lane_index")
+                .map_err(build_error)?
+                .into_int_value();
+            let pointer = unsafe { self.builder.build_in_bounds_gep(base, &[index], "lane_ptr") }
+                .map_err(build_error)?;
+            pointers = self
+                .builder
+                .build_insert_element(pointers, pointer, lane_index, "lane_ptrs")
+                .map_err(build_error)?;
+        }
+        Ok(pointers)
+    }
+
+    /// The element type the lane pointers of a gather or scatter point to.
+    fn lane_pointer_element_type(
+        &self,
+        pointers: VectorValue<'ctx>,
+    ) -> Result<BasicTypeEnum<'ctx>> {
+        let pointer_type = pointers.get_type().get_element_type().into_pointer_type();
+        BasicTypeEnum::try_from(pointer_type.get_element_type()).map_err(|_| {
+            CompileError::codegen_error(
+                "Gather and scatter require a pointer to numbers".to_string(),
+                None,
+            )
+        })
+    }
+
+    /// A `<lanes x i1>` mask with every lane set.
+    fn all_lanes_mask(&self, lanes: u32) -> VectorValue<'ctx> {
+        let set = self.context.bool_type().const_all_ones();
+        VectorType::const_vector(&vec![set; lanes as usize])
+    }
+
+    /// Declares an intrinsic that returns nothing, such as `llvm.masked.store`,
+    /// which `get_or_declare_intrinsic` cannot since it needs a return type.
+    fn get_or_declare_void_intrinsic(
+        &mut self,
+        intrinsic_name: &str,
+        param_types: &[BasicTypeEnum<'ctx>],
+    ) -> FunctionValue<'ctx> {
+        if let Some(function) = self.functions.get(intrinsic_name) {
+            return *function;
+        }
+        let metadata_types: Vec<_> = param_types.iter().map(|t| (*t).into()).collect();
+        let fn_type = self.context.void_type().fn_type(&metadata_types, false);
+        let function = self.module.add_function(intrinsic_name, fn_type, None);
+        self.functions.insert(intrinsic_name.to_string(), function);
+        function
+    }
+
+    /// The overloaded-intrinsic suffix of a vector type: `v4f32`, `v16i8`.
+    fn vector_intrinsic_suffix(&self, vector_type: VectorType<'ctx>) -> String {
+        format!(
+            "v{}{}",
+            vector_type.get_size(),
+            self.scalar_intrinsic_suffix(vector_type.get_element_type())
+        )
+    }
+
+    /// The overloaded-intrinsic suffix of a number type: `f32`, `i8`.
+    fn scalar_intrinsic_suffix(&self, scalar_type: BasicTypeEnum<'ctx>) -> String {
+        match scalar_type {
+            BasicTypeEnum::IntType(int_type) => format!("i{}", int_type.get_bit_width()),
+            BasicTypeEnum::FloatType(float_type) if float_type == self.context.f64_type() => {
+                "f64".to_string()
+            }
+            _ => "f32".to_string(),
+        }
+    }
+
+    /// Generates code for SIMD swizzle operations.
+    fn generate_simd_swizzle(
+        &mut self,
+        vector: &Expr,
+        pattern: &crate::ast::SwizzlePattern,
+    ) -> Result<BasicValueEnum<'ctx>> {
+        let vector_val = self.generate_expression(vector)?;
+
+        if let BasicValueEnum::VectorValue(vec_val) = vector_val {
+            let vector_type = vec_val.get_type();
+            let element_count = vector_type.get_size();
+
+            // Convert swizzle pattern to indices
+            let indices = self.swizzle_pattern_to_indices(pattern, element_count as usize)?;
+
+            // Create shuffle mask
+            let mask_values: Vec<_> = indices
+                .iter()
+                .map(|&i| self.context.i32_type().const_int(i as u64, false))
+                .collect();
+
+            // Create the shuffle mask vector
+            let mask_vector = VectorType::const_vector(&mask_values);
+
+            // Generate shuffle instruction
+            self.builder
+                .build_shuffle_vector(
+                    vec_val,
+                    vec_val, // Use same vector for both operands
+                    mask_vector,
+                    "swizzle",
+                )
+                .map(|v| v.into())
+                .map_err(|_| {
+                    CompileError::codegen_error(
+                        "Failed to generate swizzle operation".to_string(),
+                        None,
+                    )
+                })
+        } else {
+            Err(CompileError::codegen_error(
+                "Swizzle requires vector operand".to_string(),
+                None,
+            ))
+        }
+    }
+
+    /// Converts swizzle pattern to element indices.
+    fn swizzle_pattern_to_indices(
+        &self,
+        pattern: &crate::ast::SwizzlePattern,
+        vector_width: usize,
+    ) -> Result<Vec<u32>> {
+        use crate::ast::SwizzlePattern;
+
+        match pattern {
+            SwizzlePattern::Named(name) => {
+                let mut indices = Vec::new();
+                for ch in name.chars() {
+                    let index = match ch {
+                        'x' => 0,
+                        'y' => 1,
+                        'z' => 2,
+                        'w' => 3,
+                        _ => {
+                            return Err(CompileError::codegen_error(
+                                format!("Invalid swizzle component: {}", ch),
+                                None,
+                            ))
+                        }
+                    };
+
+                    if index >= vector_width as u32 {
+                        return Err(CompileError::codegen_error(
+                            format!(
+                                "Swizzle index {} out of bounds for vector width {}",
+                                index, vector_width
+                            ),
+                            None,
+                        ));
+                    }
+
+                    indices.push(index);
+                }
+
+                if indices.is_empty() {
+                    return Err(CompileError::codegen_error(
+                        "Empty named swizzle pattern".to_string(),
+                        None,
+                    ));
+                }
+
+                Ok(indices)
+            }
+            SwizzlePattern::Range { start, end } => {
+                if *start >= *end || *end > vector_width {
+                    return Err(CompileError::codegen_error(
+                        format!(
+                            "Invalid range [{}:{}] for vector width {}",
+                            start, end, vector_width
+                        ),
+                        None,
+                    ));
+                }
+
+                let indices: Vec<u32> = (*start..*end).map(|i| i as u32).collect();
+                Ok(indices)
+            }
+            SwizzlePattern::Indices(index_list) => {
+                for &index in index_list {
+                    if index >= vector_width {
+                        return Err(CompileError::codegen_error(
+                            format!(
+                                "Swizzle index {} out of bounds for vector width {}",
+                                index, vector_width
+                            ),
+                            None,
+                        ));
+                    }
+                }
+
+                if index_list.is_empty() {
+                    return Err(CompileError::codegen_error(
+                        "Empty index list in swizzle pattern".to_string(),
+                        None,
+                    ));
+                }
+
+                let indices: Vec<u32> = index_list.iter().map(|&i| i as u32).collect();
+                Ok(indices)
+            }
+        }
+    }
+
+    /// Generates optimized reduction operations using tree-based algorithms.
+    fn generate_optimized_reduction(
+        &mut self,
+        vector: VectorValue<'ctx>,
+        operation: &crate::ast::ReductionOp,
+    ) -> Result<BasicValueEnum<'ctx>> {
+        let vector_type = vector.get_type();
+        let element_count = vector_type.get_size();
+
+        // Use tree-based reduction for larger vectors, linear for smaller ones
+        if element_count > 4 {
+            self.generate_tree_reduction(vector, operation)
+        } else {
+            self.generate_linear_reduction(vector, operation)
+        }
+    }
+
+    /// Generates tree-based reduction for efficient parallel reduction.
+    fn generate_tree_reduction(
+        &mut self,
+        vector: VectorValue<'ctx>,
+        operation: &crate::ast::ReductionOp,
+    ) -> Result<BasicValueEnum<'ctx>> {
+        let vector_type = vector.get_type();
+        let element_count = vector_type.get_size();
+
+        if element_count == 0 {
+            return Err(CompileError::codegen_error(
+                "Cannot reduce empty vector".to_string(),
+                None,
+            ));
+        }
+
+        if element_count == 1 {
+            // Single element - just extract it
+            let zero_index = self.context.i32_type().const_int(0, false);
+            return self
+                .builder
+                .build_extract_element(vector, zero_index, "single_element")
+                .map_err(|_| {
+                    CompileError::codegen_error(
+                        "Failed to extract single element".to_string(),
+                        None,
+                    )
+                });
+        }
+
+        // Tree reduction: repeatedly split vector in half and combine
+        let mut current_vector = vector;
+        let mut current_width = element_count;
+
+        while current_width > 1 {
+            let half_width = current_width / 2;
+            let remaining = current_width % 2;
+
+            // Create shuffle masks for the two halves
+            let left_mask: Vec<_> = (0..half_width)
+                .map(|i| self.context.i32_type().const_int(i as u64, false))
+                .collect();
+            let right_mask: Vec<_> = (half_width..current_width - remaining)
+                .map(|i| self.context.i32_type().const_int(i as u64, false))
+                .collect();
+
+            if left_mask.len() == right_mask.len() && !left_mask.is_empty() {
+                // Create vector halves
+                let left_mask_vec = VectorType::const_vector(&left_mask);
+                let right_mask_vec = VectorType::const_vector(&right_mask);
+
+                let left_half = self
+                    .builder
+                    .build_shuffle_vector(
+                        current_vector,
+                        current_vector,
+                        left_mask_vec,
+                        "left_half",
+                    )
+                    .map_err(|_| {
+                        CompileError::codegen_error("Failed to create left half".to_string(), None)
+                    })?;
+
+                let right_half = self
+                    .builder
+                    .build_shuffle_vector(
+                        current_vector,
+                        current_vector,
+                        right_mask_vec,
+                        "right_half",
+                    )
+                    .map_err(|_| {
+                        CompileError::codegen_error("Failed to create right half".to_string(), None)
+                    })?;
+
+                // Combine the two halves
+                current_vector = match operation {
+                    crate::ast::ReductionOp::Sum => {
+                        if vector_type.get_element_type().is_float_type() {
+                            self.builder
+                                .build_float_add(left_half, right_half, "tree_add")
+                        } else {
+                            self.builder
+                                .build_int_add(left_half, right_half, "tree_add")
+                        }
+                    }
+                    crate::ast::ReductionOp::Product => {
+                        if vector_type.get_element_type().is_float_type() {
+                            self.builder
+                                .build_float_mul(left_half, right_half, "tree_mul")
+                        } else {
+                            self.builder
+                                .build_int_mul(left_half, right_half, "tree_mul")
+                        }
+                    }
+                    _ => {
+                        return Err(CompileError::codegen_error(
+                            format!("Tree reduction for {:?} not implemented", operation),
+                            None,
+                        ))
+                    }
+                }
+                .map_err(|_| {
+                    CompileError::codegen_error(
+                        "Failed to build tree reduction operation".to_string(),
+                        None,
+                    )
+                })?;
+
+                current_width = half_width;
+            } else {
+                // Fallback to linear reduction for odd sizes
+                return self.generate_linear_reduction(current_vector, operation);
+            }
+        }
+
+        // Extract the final result
+        let zero_index = self.context.i32_type().const_int(0, false);
+        self.builder
+            .build_extract_element(current_vector, zero_index, "tree_result")
+            .map_err(|_| {
+                CompileError::codegen_error(
+                    "Failed to extract tree reduction result".to_string(),
+                    None,
+                )
+            })
+    }
+
+    /// Generates linear reduction for small vectors.
+    fn generate_linear_reduction(
+        &mut self,
+        vector: VectorValue<'ctx>,
+        operation: &crate::ast::ReductionOp,
+    ) -> Result<BasicValueEnum<'ctx>> {
+        let vector_type = vector.get_type();
+        let element_count = vector_type.get_size();
+
+        if element_count == 0 {
+            return Err(CompileError::codegen_error(
+                "Cannot reduce empty vector".to_string(),
+                None,
+            ));
+        }
+
+        // Extract first element as accumulator
+        let zero_index = self.context.i32_type().const_int(0, false);
+        let mut accumulator = self
+            .builder
+            .build_extract_element(vector, zero_index, "linear_acc_0")
+            .map_err(|_| {
+                CompileError::codegen_error("Failed to extract first element".to_string(), None)
+            })?;
+
+        // Linearly reduce remaining elements
+        for i in 1..element_count {
+            let index = self.context.i32_type().const_int(i as u64, false);
+            let element = self
+                .builder
+                .build_extract_element(vector, index, &format!("linear_elem_{}", i))
+                .map_err(|_| {
+                    CompileError::codegen_error(
+                        "Failed to extract vector element".to_string(),
+                        None,
+                    )
+                })?;
+
+            accumulator = match operation {
+                crate::ast::ReductionOp::Sum => {
+                    if vector_type.get_element_type().is_float_type() {
+                        if let (BasicValueEnum::FloatValue(acc), BasicValueEnum::FloatValue(elem)) =
+                            (accumulator, element)
+                        {
+                            self.builder
+                                .build_float_add(acc, elem, "linear_add")
+                                .map(|v| v.into())
+                                .map_err(|_| {
+                                    CompileError::codegen_error(
+                                        "Failed to build linear add".to_string(),
+                                        None,
+                                    )
+                                })?
+                        } else {
+                            return Err(CompileError::codegen_error(
+                                "Type mismatch in linear reduction".to_string(),
+                                None,
+                            ));
+                        }
+                    } else {
+                        if let (BasicValueEnum::IntValue(acc), BasicValueEnum::IntValue(elem)) =
+                            (accumulator, element)
+                        {
+                            self.builder
+                                .build_int_add(acc, elem, "linear_add")
+                                .map(|v| v.into())
+                                .map_err(|_| {
+                                    CompileError::codegen_error(
+                                        "Failed to build linear add".to_string(),
                                         None,
-                                    ));
-                                }
-                            }
-                            crate::ast::ReductionOp::Any => {
-                                if let (
-                                    BasicValueEnum::IntValue(acc),
-                                    BasicValueEnum::IntValue(elem),
-                                ) = (accumulator, element)
-                                {
-                                    // Any is logical OR (for boolean vectors represented as integers)
-                                    self.builder
-                                        .build_or(acc, elem, "reduce_any")
-                                        .map(|v| v.into())
-                                        .map_err(|_| {
-                                            CompileError::codegen_error(
-                                                "Failed to build logical or for any".to_string(),
-                                                None,
-                                            )
-                                        })?
-                                } else {
-                                    return Err(CompileError::codegen_error(
-                                        "Any reduction only supports integer types".to_string(),
+                                    )
+                                })?
+                        } else {
+                            return Err(CompileError::codegen_error(
+                                "Type mismatch in linear reduction".to_string(),
+                                None,
+                            ));
+                        }
+                    }
+                }
+                crate::ast::ReductionOp::Product => {
+                    if vector_type.get_element_type().is_float_type() {
+                        if let (BasicValueEnum::FloatValue(acc), BasicValueEnum::FloatValue(elem)) =
+                            (accumulator, element)
+                        {
+                            self.builder
+                                .build_float_mul(acc, elem, "linear_mul")
+                                .map(|v| v.into())
+                                .map_err(|_| {
+                                    CompileError::codegen_error(
+                                        "Failed to build linear mul".to_string(),
                                         None,
-                                    ));
-                                }
-                            }
-                            crate::ast::ReductionOp::All => {
-                                if let (
-                                    BasicValueEnum::IntValue(acc),
-                                    BasicValueEnum::IntValue(elem),
-                                ) = (accumulator, element)
-                                {
-                                    // All is logical AND (for boolean vectors represented as integers)
-                                    self.builder
-                                        .build_and(acc, elem, "reduce_all")
-                                        .map(|v| v.into())
-                                        .map_err(|_| {
-                                            CompileError::codegen_error(
-                                                "Failed to build logical and for all".to_string(),
-                                                None,
-                                            )
-                                        })?
-                                } else {
-                                    return Err(CompileError::codegen_error(
-                                        "All reduction only supports integer types".to_string(),
+                                    )
+                                })?
+                        } else {
+                            return Err(CompileError::codegen_error(
+                                "Type mismatch in linear reduction".to_string(),
+                                None,
+                            ));
+                        }
+                    } else {
+                        if let (BasicValueEnum::IntValue(acc), BasicValueEnum::IntValue(elem)) =
+                            (accumulator, element)
+                        {
+                            self.builder
+                                .build_int_mul(acc, elem, "linear_mul")
+                                .map(|v| v.into())
+                                .map_err(|_| {
+                                    CompileError::codegen_error(
+                                        "Failed to build linear mul".to_string(),
                                         None,
-                                    ));
-                                }
-                            }
-                        };
+                                    )
+                                })?
+                        } else {
+                            return Err(CompileError::codegen_error(
+                                "Type mismatch in linear reduction".to_string(),
+                                None,
+                            ));
+                        }
                     }
-
-                    Ok(accumulator)
-                } else {
-                    Err(CompileError::codegen_error(
-                        "Reduction requires vector operand".to_string(),
+                }
+                _ => {
+                    return Err(CompileError::codegen_error(
+                        format!("Linear reduction for {:?} not implemented", operation),
                         None,
-                    ))
+                    ));
                 }
-            }
-            SIMDExpr::DotProduct { left, right, .. } => {
-                // Generate dot product: sum of element-wise multiplication
-                let left_val = self.generate_expression(left)?;
-                let right_val = self.generate_expression(right)?;
-
-                if let (
-                    BasicValueEnum::VectorValue(left_vec),
-                    BasicValueEnum::VectorValue(right_vec),
-                ) = (left_val, right_val)
-                {
-                    // First, do element-wise multiplication
-                    let product = if left_vec.get_type().get_element_type().is_float_type() {
-                        self.builder
-                            .build_float_mul(left_vec, right_vec, "dot_mul")
-                            .map_err(|_| {
-                                CompileError::codegen_error(
-                                    "Failed to build vector multiply".to_string(),
-                                    None,
-                                )
-                            })?
-                    } else {
-                        self.builder
-                            .build_int_mul(left_vec, right_vec, "dot_mul")
-                            .map_err(|_| {
-                                CompileError::codegen_error(
-                                    "Failed to build vector multiply".to_string(),
-                                    None,
-                                )
-                            })?
-                    };
+            };
+        }
 
-                    // Then reduce with sum
-                    let vector_type = product.get_type();
-                    let element_count = vector_type.get_size();
+        Ok(accumulator)
+    }
 
-                    if element_count == 0 {
-                        return Err(CompileError::codegen_error(
-                            "Cannot compute dot product of empty vectors".to_string(),
-                            None,
-                        ));
+    /// Checks if an instruction is a SIMD operation.
+    fn is_simd_instruction(&self, instruction: &inkwell::values::InstructionValue) -> bool {
+        // Check if instruction operates on vector types
+        match instruction.get_opcode() {
+            inkwell::values::InstructionOpcode::Add
+            | inkwell::values::InstructionOpcode::FAdd
+            | inkwell::values::InstructionOpcode::Sub
+            | inkwell::values::InstructionOpcode::FSub
+            | inkwell::values::InstructionOpcode::Mul
+            | inkwell::values::InstructionOpcode::FMul
+            | inkwell::values::InstructionOpcode::UDiv
+            | inkwell::values::InstructionOpcode::SDiv
+            | inkwell::values::InstructionOpcode::FDiv
+            | inkwell::values::InstructionOpcode::And
+            | inkwell::values::InstructionOpcode::Or
+            | inkwell::values::InstructionOpcode::Xor => {
+                // Check if operands are vector types
+                if let Some(operand) = instruction.get_operand(0) {
+                    if let Some(operand_value) = operand.left() {
+                        return operand_value.get_type().is_vector_type();
                     }
+                }
+                false
+            }
+            inkwell::values::InstructionOpcode::ShuffleVector
+            | inkwell::values::InstructionOpcode::ExtractElement
+            | inkwell::values::InstructionOpcode::InsertElement => true,
+            _ => false,
+        }
+    }
 
-                    // Extract first element as accumulator
-                    let zero_index = self.context.i32_type().const_int(0, false);
-                    let mut accumulator = self
-                        .builder
-                        .build_extract_element(product, zero_index, "extract_0")
-                        .map_err(|_| {
-                            CompileError::codegen_error(
-                                "Failed to extract vector element".to_string(),
-                                None,
-                            )
-                        })?;
-
-                    // Sum remaining elements
-                    for i in 1..element_count {
-                        let index = self.context.i32_type().const_int(i as u64, false);
-                        let element = self
-                            .builder
-                            .build_extract_element(product, index, &format!("extract_{}", i))
-                            .map_err(|_| {
-                                CompileError::codegen_error(
-                                    "Failed to extract vector element".to_string(),
-                                    None,
-                                )
-                            })?;
+    /// Enables auto-vectorization optimization for the module.
+    pub fn enable_auto_vectorization(&mut self) -> Result<()> {
+        // Add global metadata to enable LLVM's auto-vectorization
+        let _context = self.context;
 
-                        accumulator = if vector_type.get_element_type().is_float_type() {
-                            if let (
-                                BasicValueEnum::FloatValue(acc),
-                                BasicValueEnum::FloatValue(elem),
-                            ) = (accumulator, element)
-                            {
-                                self.builder
-                                    .build_float_add(acc, elem, "dot_add")
-                                    .map(|v| v.into())
-                                    .map_err(|_| {
-                                        CompileError::codegen_error(
-                                            "Failed to build float add".to_string(),
-                                            None,
-                                        )
-                                    })?
-                            } else {
-                                return Err(CompileError::codegen_error(
-                                    "Type mismatch in dot product".to_string(),
-                                    None,
-                                ));
-                            }
-                        } else {
-                            if let (BasicValueEnum::IntValue(acc), BasicValueEnum::IntValue(elem)) =
-                                (accumulator, element)
-                            {
-                                self.builder
-                                    .build_int_add(acc, elem, "dot_add")
-                                    .map(|v| v.into())
-                                    .map_err(|_| {
-                                        CompileError::codegen_error(
-                                            "Failed to build int add".to_string(),
-                                            None,
-                                        )
-                                    })?
-                            } else {
-                                return Err(CompileError::codegen_error(
-                                    "Type mismatch in dot product".to_string(),
-                                    None,
-                                ));
-                            }
-                        };
-                    }
+        // Enable loop vectorization through module flags
+        // Note: These are global hints that affect the LLVM optimizer behavior
 
-                    Ok(accumulator)
-                } else {
-                    Err(CompileError::codegen_error(
-                        "Dot product requires two vector operands".to_string(),
-                        None,
-                    ))
-                }
-            }
-            SIMDExpr::VectorLoad {
-                address,
-                vector_type,
-                alignment,
-                ..
-            } => {
-                // Generate vector load from memory
-                let address_val = self.generate_expression(address)?;
+        // In practice, vectorization is typically enabled via optimization passes
+        // rather than global metadata. This is a conceptual implementation.
 
-                if let BasicValueEnum::PointerValue(ptr) = address_val {
-                    // Get the LLVM vector type
-                    let llvm_vector_type = self.simd_type_to_llvm(vector_type)?;
+        // The actual auto-vectorization happens during LLVM optimization passes
+        // which can be enabled when compiling the module to object code
 
-                    // Cast pointer to vector pointer type if needed
-                    let vector_ptr = if ptr.get_type().get_element_type() != llvm_vector_type.into()
-                    {
-                        self.builder
-                            .build_bitcast(
-                                ptr,
-                                llvm_vector_type.ptr_type(AddressSpace::default()),
-                                "vector_ptr_cast",
-                            )
-                            .map_err(|_| {
-                                CompileError::codegen_error(
-                                    "Failed to cast pointer for vector load".to_string(),
-                                    None,
-                                )
-                            })?
-                            .into_pointer_value()
-                    } else {
-                        ptr
-                    };
+        Ok(())
+    }
 
-                    // Set alignment based on parameter or default
-                    let align_bytes = alignment.unwrap_or(self.get_default_alignment(vector_type));
+    /// Generates optimized SIMD memory operations with alignment hints.
+    fn generate_simd_load_aligned(
+        &mut self,
+        address: inkwell::values::PointerValue<'ctx>,
+        vector_type: &SIMDVectorType,
+    ) -> Result<VectorValue<'ctx>> {
+        let _llvm_type = self.simd_type_to_llvm(vector_type)?;
 
-                    // Build the load instruction
-                    let load_inst =
-                        self.builder
-                            .build_load(vector_ptr, "vector_load")
-                            .map_err(|_| {
-                                CompileError::codegen_error(
-                                    "Failed to build vector load".to_string(),
-                                    None,
-                                )
-                            })?;
+        // Create aligned load with appropriate alignment
+        let alignment = self.get_simd_alignment(vector_type);
+        let load_inst = self
+            .builder
+            .build_load(address, "simd_load_aligned")
+            .map_err(|e| {
+                CompileError::codegen_error(format!("Failed to build aligned load: {:?}", e), None)
+            })?;
 
-                    // Set alignment
-                    if let BasicValueEnum::VectorValue(vec_val) = load_inst {
-                        if let Some(load_inst) = vec_val.as_instruction_value() {
-                            load_inst.set_alignment(align_bytes).map_err(|_| {
-                                CompileError::codegen_error(
-                                    "Failed to set load alignment".to_string(),
-                                    None,
-                                )
-                            })?;
-                        }
-                    }
+        // Set alignment hint for better optimization
+        if let Some(inst) = load_inst.as_instruction_value() {
+            inst.set_alignment(alignment).map_err(|e| {
+                CompileError::codegen_error(format!("Failed to set load alignment: {:?}", e), None)
+            })?;
+        }
 
-                    Ok(load_inst)
-                } else {
-                    Err(CompileError::codegen_error(
-                        "Vector load requires pointer address".to_string(),
-                        None,
-                    ))
-                }
-            }
-            SIMDExpr::VectorStore {
-                address,
-                vector,
-                alignment,
-                ..
-            } => {
-                // Generate vector store to memory
-                let address_val = self.generate_expression(address)?;
-                let vector_val = self.generate_expression(vector)?;
+        // Convert to vector value
+        if let inkwell::values::BasicValueEnum::VectorValue(vec_val) = load_inst {
+            Ok(vec_val)
+        } else {
+            Err(CompileError::codegen_error(
+                "Load result is not a vector value".to_string(),
+                None,
+            ))
+        }
+    }
 
-                if let (BasicValueEnum::PointerValue(ptr), BasicValueEnum::VectorValue(vec)) =
-                    (address_val, vector_val)
-                {
-                    // Cast pointer to vector pointer type if needed
-                    let vector_ptr = if ptr.get_type().get_element_type() != vec.get_type().into() {
-                        self.builder
-                            .build_bitcast(
-                                ptr,
-                                vec.get_type().ptr_type(AddressSpace::default()),
-                                "vector_ptr_cast",
-                            )
-                            .map_err(|_| {
-                                CompileError::codegen_error(
-                                    "Failed to cast pointer for vector store".to_string(),
-                                    None,
-                                )
-                            })?
-                            .into_pointer_value()
-                    } else {
-                        ptr
-                    };
+    /// Generates optimized SIMD memory store with alignment hints.
+    fn generate_simd_store_aligned(
+        &mut self,
+        vector: VectorValue<'ctx>,
+        address: inkwell::values::PointerValue<'ctx>,
+        vector_type: &SIMDVectorType,
+    ) -> Result<()> {
+        // Create aligned store with appropriate alignment
+        let alignment = self.get_simd_alignment(vector_type);
+        let store_inst = self.builder.build_store(address, vector).map_err(|e| {
+            CompileError::codegen_error(format!("Failed to build aligned store: {:?}", e), None)
+        })?;
 
-                    // Set alignment based on parameter or infer from vector type
-                    let align_bytes = alignment.unwrap_or_else(|| {
-                        // Infer alignment from vector width
-                        let element_count = vec.get_type().get_size();
-                        let element_size = if vec.get_type().get_element_type().is_float_type() {
-                            // Assume f32 for now, could be enhanced later
-                            4
-                        } else {
-                            vec.get_type()
-                                .get_element_type()
-                                .into_int_type()
-                                .get_bit_width()
-                                / 8
-                        };
-                        let total_bytes = element_count * element_size;
-                        // Use natural alignment for common SIMD sizes
-                        if total_bytes >= 32 {
-                            32
-                        } else if total_bytes >= 16 {
-                            16
-                        } else {
-                            total_bytes
-                        }
-                    });
+        // Set alignment hint for better optimization
+        store_inst.set_alignment(alignment).map_err(|e| {
+            CompileError::codegen_error(format!("Failed to set store alignment: {:?}", e), None)
+        })?;
 
-                    // Build the store instruction
-                    let store_inst = self.builder.build_store(vector_ptr, vec).map_err(|_| {
-                        CompileError::codegen_error(
-                            "Failed to build vector store".to_string(),
-                            None,
-                        )
-                    })?;
+        Ok(())
+    }
 
-                    // Set alignment
-                    store_inst.set_alignment(align_bytes).map_err(|_| {
-                        CompileError::codegen_error(
-                            "Failed to set store alignment".to_string(),
-                            None,
-                        )
-                    })?;
+    /// Gets the appropriate memory alignment for a SIMD vector type.
+    fn get_simd_alignment(&self, vector_type: &SIMDVectorType) -> u32 {
+        match vector_type {
+            // 128-bit vectors (16-byte alignment)
+            SIMDVectorType::F32x4
+            | SIMDVectorType::F64x2
+            | SIMDVectorType::I32x4
+            | SIMDVectorType::I64x2
+            | SIMDVectorType::I16x8
+            | SIMDVectorType::I8x16
+            | SIMDVectorType::U32x4
+            | SIMDVectorType::U16x8
+            | SIMDVectorType::U8x16 => 16,
 
-                    // Vector store returns void
-                    Ok(self.context.i32_type().const_int(0, false).into())
-                } else {
-                    Err(CompileError::codegen_error(
-                        "Vector store requires pointer address and vector value".to_string(),
-                        None,
-                    ))
-                }
-            }
+            // 256-bit vectors (32-byte alignment)
+            SIMDVectorType::F32x8
+            | SIMDVectorType::F64x4
+            | SIMDVectorType::I32x8
+            | SIMDVectorType::I64x4
+            | SIMDVectorType::I16x16
+            | SIMDVectorType::I8x32
+            | SIMDVectorType::U32x8
+            | SIMDVectorType::U16x16
+            | SIMDVectorType::U8x32 => 32,
+
+            // 512-bit vectors (64-byte alignment)
+            SIMDVectorType::F32x16
+            | SIMDVectorType::F64x8
+            | SIMDVectorType::I32x16
+            | SIMDVectorType::I64x8
+            | SIMDVectorType::I16x32
+            | SIMDVectorType::I8x64 => 64,
+
+            // Smaller vectors (8-byte alignment)
+            SIMDVectorType::F32x2
+            | SIMDVectorType::I32x2
+            | SIMDVectorType::I16x4
+            | SIMDVectorType::U8x8
+            | SIMDVectorType::I8x8 => 8,
+
+            // Very small vectors (4-byte alignment)
+            SIMDVectorType::U8x4 => 4,
+
+            // Mask types (match the underlying vector size they represent)
+            SIMDVectorType::Mask8 => 8,
+            SIMDVectorType::Mask16 => 16,
+            SIMDVectorType::Mask32 => 32,
+            SIMDVectorType::Mask64 => 64,
         }
     }
 
-    /// Generates code for SIMD swizzle operations.
-    fn generate_simd_swizzle(
-        &mut self,
-        vector: &Expr,
-        pattern: &crate::ast::SwizzlePattern,
-    ) -> Result<BasicValueEnum<'ctx>> {
-        let vector_val = self.generate_expression(vector)?;
+    /// Adds vectorization hints to loops containing SIMD operations.
+    pub fn add_vectorization_hints(&mut self, _function: FunctionValue<'ctx>) -> Result<()> {
+        // This would typically be called after generating a function with loops
+        // Add metadata to encourage vectorization of loops containing SIMD operations
 
-        if let BasicValueEnum::VectorValue(vec_val) = vector_val {
-            let vector_type = vec_val.get_type();
-            let element_count = vector_type.get_size();
+        let context = self.context;
 
-            // Convert swizzle pattern to indices
-            let indices = self.swizzle_pattern_to_indices(pattern, element_count as usize)?;
+        // Create vectorization metadata
+        let _vectorize_enable = context.metadata_node(&[
+            context.metadata_string("llvm.loop.vectorize.enable").into(),
+            context.bool_type().const_int(1, false).into(),
+        ]);
+
+        let _vectorize_width = context.metadata_node(&[
+            context.metadata_string("llvm.loop.vectorize.width").into(),
+            context.i32_type().const_int(8, false).into(),
+        ]);
 
-            // Create shuffle mask
-            let mask_values: Vec<_> = indices
-                .iter()
-                .map(|&i| self.context.i32_type().const_int(i as u64, false))
-                .collect();
+        // Note: In a real implementation, these would be attached to specific loop blocks
+        // This is a simplified version for demonstration
 
-            // Create the shuffle mask vector
-            let mask_vector = VectorType::const_vector(&mask_values);
+        Ok(())
+    }
 
-            // Generate shuffle instruction
-            self.builder
-                .build_shuffle_vector(
-                    vec_val,
-                    vec_val, // Use same vector for both operands
-                    mask_vector,
-                    "swizzle",
-                )
-                .map(|v| v.into())
-                .map_err(|_| {
-                    CompileError::codegen_error(
-                        "Failed to generate swizzle operation".to_string(),
-                        None,
-                    )
-                })
-        } else {
-            Err(CompileError::codegen_error(
-                "Swizzle requires vector operand".to_string(),
-                None,
-            ))
+    /// Get default memory alignment for a SIMD vector type
+    /// Natural alignment of a scalar vector element, in bytes.
+    fn element_alignment(&self, element_type: BasicTypeEnum<'ctx>) -> u32 {
+        match element_type {
+            BasicTypeEnum::FloatType(float_type) if float_type == self.context.f64_type() => 8,
+            BasicTypeEnum::IntType(int_type) => (int_type.get_bit_width() / 8).max(1),
+            _ => 4,
         }
     }
 
-    /// Converts swizzle pattern to element indices.
-    fn swizzle_pattern_to_indices(
-        &self,
-        pattern: &crate::ast::SwizzlePattern,
-        vector_width: usize,
-    ) -> Result<Vec<u32>> {
-        use crate::ast::SwizzlePattern;
+    fn get_default_alignment(&self, vector_type: &SIMDVectorType) -> u32 {
+        match vector_type {
+            // 32-bit float vectors
+            SIMDVectorType::F32x2 => 8,   // 2 * 4 bytes
+            SIMDVectorType::F32x4 => 16,  // 4 * 4 bytes (SSE alignment)
+            SIMDVectorType::F32x8 => 32,  // 8 * 4 bytes (AVX alignment)
+            SIMDVectorType::F32x16 => 64, // 16 * 4 bytes (AVX-512 alignment)
 
-        match pattern {
-            SwizzlePattern::Named(name) => {
-                let mut indices = Vec::new();
-                for ch in name.chars() {
-                    let index = match ch {
-                        'x' => 0,
-                        'y' => 1,
-                        'z' => 2,
-                        'w' => 3,
-                        _ => {
-                            return Err(CompileError::codegen_error(
-                                format!("Invalid swizzle component: {}", ch),
-                                None,
-                            ))
-                        }
-                    };
+            // 64-bit float vectors
+            SIMDVectorType::F64x2 => 16, // 2 * 8 bytes (SSE alignment)
+            SIMDVectorType::F64x4 => 32, // 4 * 8 bytes (AVX alignment)
+            SIMDVectorType::F64x8 => 64, // 8 * 8 bytes (AVX-512 alignment)
 
-                    if index >= vector_width as u32 {
-                        return Err(CompileError::codegen_error(
-                            format!(
-                                "Swizzle index {} out of bounds for vector width {}",
-                                index, vector_width
-                            ),
-                            None,
-                        ));
-                    }
+            // 32-bit integer vectors
+            SIMDVectorType::I32x2 => 8,
+            SIMDVectorType::I32x4 | SIMDVectorType::U32x4 => 16, // SSE alignment
+            SIMDVectorType::I32x8 | SIMDVectorType::U32x8 => 32, // AVX alignment
+            SIMDVectorType::I32x16 => 64, // AVX-512 alignment
 
-                    indices.push(index);
-                }
+            // 64-bit integer vectors
+            SIMDVectorType::I64x2 => 16, // SSE alignment
+            SIMDVectorType::I64x4 => 32, // AVX alignment
+            SIMDVectorType::I64x8 => 64, // AVX-512 alignment
 
-                if indices.is_empty() {
-                    return Err(CompileError::codegen_error(
-                        "Empty named swizzle pattern".to_string(),
-                        None,
-                    ));
-                }
+            // 16-bit integer vectors
+            SIMDVectorType::I16x4 => 8, // 4 * 2 bytes
+            SIMDVectorType::I16x8 | SIMDVectorType::U16x8 => 16, // SSE alignment
+            SIMDVectorType::I16x16 | SIMDVectorType::U16x16 => 32, // AVX alignment
+            SIMDVectorType::I16x32 => 64, // AVX-512 alignment
 
-                Ok(indices)
-            }
-            SwizzlePattern::Range { start, end } => {
-                if *start >= *end || *end > vector_width {
-                    return Err(CompileError::codegen_error(
-                        format!(
-                            "Invalid range [{}:{}] for vector width {}",
-                            start, end, vector_width
-                        ),
-                        None,
-                    ));
-                }
+            // 8-bit integer vectors
+            SIMDVectorType::U8x4 => 4, // 4 * 1 bytes
+            SIMDVectorType::I8x8 | SIMDVectorType::U8x8 => 8, // 8 * 1 bytes
+            SIMDVectorType::I8x16 | SIMDVectorType::U8x16 => 16, // SSE alignment
+            SIMDVectorType::I8x32 | SIMDVectorType::U8x32 => 32, // AVX alignment
+            SIMDVectorType::I8x64 => 64, // AVX-512 alignment
 
-                let indices: Vec<u32> = (*start..*end).map(|i| i as u32).collect();
-                Ok(indices)
-            }
-            SwizzlePattern::Indices(index_list) => {
-                for &index in index_list {
-                    if index >= vector_width {
-                        return Err(CompileError::codegen_error(
-                            format!(
-                                "Swizzle index {} out of bounds for vector width {}",
-                                index, vector_width
-                            ),
-                            None,
-                        ));
-                    }
-                }
+            // Mask vectors
+            SIMDVectorType::Mask8 => 1,
+            SIMDVectorType::Mask16 => 2,
+            SIMDVectorType::Mask32 => 4,
+            SIMDVectorType::Mask64 => 8,
+        }
+    }
 
-                if index_list.is_empty() {
-                    return Err(CompileError::codegen_error(
-                        "Empty index list in swizzle pattern".to_string(),
-                        None,
-                    ));
-                }
+    /// Generates code for a struct declaration.
+    fn generate_struct_declaration(&mut self, name: &str, fields: &[StructField]) -> Result<()> {
+        // Convert field types from TypeAnnotation to LLVM BasicTypeEnum
+        let mut field_types = Vec::new();
+        let mut field_map = HashMap::new();
 
-                let indices: Vec<u32> = index_list.iter().map(|&i| i as u32).collect();
-                Ok(indices)
-            }
+        for (index, field) in fields.iter().enumerate() {
+            let llvm_type = self.resolve_type_annotation(&field.type_annotation)?;
+            field_types.push(llvm_type);
+            field_map.insert(field.name.clone(), index as u32);
         }
+
+        // Named, so structs with the same layout stay distinct types
+        let struct_type = self.context.opaque_struct_type(name);
+        struct_type.set_body(&field_types, false);
+
+        // Store the struct type and field mapping for later use
+        self.struct_types.insert(name.to_string(), struct_type);
+        self.struct_fields.insert(name.to_string(), field_map);
+
+        Ok(())
     }
 
-    /// Generates optimized reduction operations using tree-based algorithms.
-    fn generate_optimized_reduction(
+    /// Generates code for a struct literal.
+    fn generate_struct_literal(
         &mut self,
-        vector: VectorValue<'ctx>,
-        operation: &crate::ast::ReductionOp,
+        name: &str,
+        fields: &[StructFieldInit],
+        span: &Span,
     ) -> Result<BasicValueEnum<'ctx>> {
-        let vector_type = vector.get_type();
-        let element_count = vector_type.get_size();
+        let mut field_values = Vec::new();
+        for field_init in fields {
+            field_values.push(self.generate_expression(&field_init.value)?);
+        }
 
-        // Use tree-based reduction for larger vectors, linear for smaller ones
-        if element_count > 4 {
-            self.generate_tree_reduction(vector, operation)
+        let struct_name = if self.generic_structs.contains_key(name) {
+            let type_args = self.checked_type_arguments(name, span)?;
+            let instance_name = crate::ast::generic_instance_name(name, &type_args);
+            self.ensure_struct_instance(&instance_name)?;
+            instance_name
         } else {
-            self.generate_linear_reduction(vector, operation)
-        }
-    }
+            name.to_string()
+        };
 
-    /// Generates tree-based reduction for efficient parallel reduction.
-    fn generate_tree_reduction(
-        &mut self,
-        vector: VectorValue<'ctx>,
-        operation: &crate::ast::ReductionOp,
-    ) -> Result<BasicValueEnum<'ctx>> {
-        let vector_type = vector.get_type();
-        let element_count = vector_type.get_size();
+        // Get the struct type
+        let struct_type = *self.struct_types.get(&struct_name).ok_or_else(|| {
+            CompileError::codegen_error(format!("Unknown struct type: {}", name), None)
+        })?;
 
-        if element_count == 0 {
-            return Err(CompileError::codegen_error(
-                "Cannot reduce empty vector".to_string(),
-                None,
-            ));
-        }
+        // Create an undef struct value to start with
+        let mut struct_value = struct_type.get_undef();
 
-        if element_count == 1 {
-            // Single element - just extract it
-            let zero_index = self.context.i32_type().const_int(0, false);
-            return self
+        // Fill in the struct fields at their declared positions
+        for (position, (field_init, field_value)) in fields.iter().zip(field_values).enumerate() {
+            let field_index = self
+                .struct_fields
+                .get(&struct_name)
+                .and_then(|indices| indices.get(&field_init.name).copied())
+                .unwrap_or(position as u32);
+            struct_value = self
                 .builder
-                .build_extract_element(vector, zero_index, "single_element")
-                .map_err(|_| {
-                    CompileError::codegen_error(
-                        "Failed to extract single element".to_string(),
-                        None,
-                    )
-                });
+                .build_insert_value(
+                    struct_value,
+                    field_value,
+                    field_index,
+                    &format!("field_{}", field_init.name),
+                )
+                .unwrap()
+                .into_struct_value();
         }
 
-        // Tree reduction: repeatedly split vector in half and combine
-        let mut current_vector = vector;
-        let mut current_width = element_count;
-
-        while current_width > 1 {
-            let half_width = current_width / 2;
-            let remaining = current_width % 2;
+        Ok(struct_value.into())
+    }
 
-            // Create shuffle masks for the two halves
-            let left_mask: Vec<_> = (0..half_width)
-                .map(|i| self.context.i32_type().const_int(i as u64, false))
-                .collect();
-            let right_mask: Vec<_> = (half_width..current_width - remaining)
-                .map(|i| self.context.i32_type().const_int(i as u64, false))
-                .collect();
+    /// Generates a tuple literal as a value of an anonymous LLVM struct type.
+    fn generate_tuple_literal(&mut self, elements: &[Expr]) -> Result<BasicValueEnum<'ctx>> {
+        let mut element_values = Vec::new();
+        for element in elements {
+            element_values.push(self.generate_expression(element)?);
+        }
 
-            if left_mask.len() == right_mask.len() && !left_mask.is_empty() {
-                // Create vector halves
-                let left_mask_vec = VectorType::const_vector(&left_mask);
-                let right_mask_vec = VectorType::const_vector(&right_mask);
+        let element_types: Vec<BasicTypeEnum<'ctx>> =
+            element_values.iter().map(|value| value.get_type()).collect();
+        let mut tuple_value = self.context.struct_type(&element_types, false).get_undef();
 
-                let left_half = self
-                    .builder
-                    .build_shuffle_vector(
-                        current_vector,
-                        current_vector,
-                        left_mask_vec,
-                        "left_half",
-                    )
-                    .map_err(|_| {
-                        CompileError::codegen_error("Failed to create left half".to_string(), None)
-                    })?;
+        for (index, element_value) in element_values.into_iter().enumerate() {
+            tuple_value = self
+                .builder
+                .build_insert_value(tuple_value, element_value, index as u32, &format!("tuple_{}", index))
+                .map_err(|e| {
+                    CompileError::codegen_error(format!("Failed to build tuple: {:?}", e), None)
+                })?
+                .into_struct_value();
+        }
 
-                let right_half = self
-                    .builder
-                    .build_shuffle_vector(
-                        current_vector,
-                        current_vector,
-                        right_mask_vec,
-                        "right_half",
-                    )
-                    .map_err(|_| {
-                        CompileError::codegen_error("Failed to create right half".to_string(), None)
-                    })?;
+        Ok(tuple_value.into())
+    }
 
-                // Combine the two halves
-                current_vector = match operation {
-                    crate::ast::ReductionOp::Sum => {
-                        if vector_type.get_element_type().is_float_type() {
-                            self.builder
-                                .build_float_add(left_half, right_half, "tree_add")
-                        } else {
-                            self.builder
-                                .build_int_add(left_half, right_half, "tree_add")
-                        }
-                    }
-                    crate::ast::ReductionOp::Product => {
-                        if vector_type.get_element_type().is_float_type() {
-                            self.builder
-                                .build_float_mul(left_half, right_half, "tree_mul")
-                        } else {
-                            self.builder
-                                .build_int_mul(left_half, right_half, "tree_mul")
-                        }
-                    }
-                    _ => {
-                        return Err(CompileError::codegen_error(
-                            format!("Tree reduction for {:?} not implemented", operation),
-                            None,
-                        ))
-                    }
-                }
-                .map_err(|_| {
-                    CompileError::codegen_error(
-                        "Failed to build tree reduction operation".to_string(),
-                        None,
-                    )
-                })?;
+    /// The LLVM type of a closure value: a pointer to its code, which takes the
+    /// environment as a hidden first parameter, paired with that environment.
+    fn closure_type(
+        &self,
+        param_types: &[BasicTypeEnum<'ctx>],
+        return_type: Option<BasicTypeEnum<'ctx>>,
+    ) -> StructType<'ctx> {
+        let env_type = self.context.i8_type().ptr_type(AddressSpace::default());
+        let mut code_params: Vec<inkwell::types::BasicMetadataTypeEnum<'ctx>> =
+            vec![env_type.into()];
+        code_params.extend(
+            param_types
+                .iter()
+                .map(|param_type| inkwell::types::BasicMetadataTypeEnum::from(*param_type)),
+        );
+        let code_type = match return_type {
+            Some(return_type) => return_type.fn_type(&code_params, false),
+            None => self.context.void_type().fn_type(&code_params, false),
+        };
+        self.context.struct_type(
+            &[
+                code_type.ptr_type(AddressSpace::default()).into(),
+                env_type.into(),
+            ],
+            false,
+        )
+    }
 
-                current_width = half_width;
-            } else {
-                // Fallback to linear reduction for odd sizes
-                return self.generate_linear_reduction(current_vector, operation);
+    /// The type of the code of a closure value, or `None` if `ty` is not a closure.
+    fn closure_code_type(
+        &self,
+        ty: BasicTypeEnum<'ctx>,
+    ) -> Option<inkwell::types::FunctionType<'ctx>> {
+        let struct_type = match ty {
+            BasicTypeEnum::StructType(struct_type) if struct_type.count_fields() == 2 => {
+                struct_type
             }
+            _ => return None,
+        };
+        match struct_type.get_field_type_at_index(0)? {
+            BasicTypeEnum::PointerType(code_ptr) => match code_ptr.get_element_type() {
+                inkwell::types::AnyTypeEnum::FunctionType(code_type) => Some(code_type),
+                _ => None,
+            },
+            _ => None,
         }
-
-        // Extract the final result
-        let zero_index = self.context.i32_type().const_int(0, false);
-        self.builder
-            .build_extract_element(current_vector, zero_index, "tree_result")
-            .map_err(|_| {
-                CompileError::codegen_error(
-                    "Failed to extract tree reduction result".to_string(),
-                    None,
-                )
-            })
     }
 
-    /// Generates linear reduction for small vectors.
-    fn generate_linear_reduction(
+    /// Lowers a closure to a function that takes its environment as a hidden first
+    /// parameter. Captured variables are copied into the environment, which is
+    /// owned by the function that creates the closure:
+    ///
+    /// - It lives in that function's stack frame, so the closure may be called
+    ///   until the function returns. Evaluating the closure expression again, as
+    ///   in a loop, reuses the environment.
+    /// - If the function can hand closures to its caller, by returning them or
+    ///   storing them through a pointer parameter, the environment is allocated
+    ///   on the heap instead. Closures have no destructor, so it is never freed.
+    fn generate_closure(
         &mut self,
-        vector: VectorValue<'ctx>,
-        operation: &crate::ast::ReductionOp,
+        params: &[crate::ast::Parameter],
+        return_type: &Option<TypeAnnotation>,
+        body: &Expr,
+        span: &Span,
     ) -> Result<BasicValueEnum<'ctx>> {
-        let vector_type = vector.get_type();
-        let element_count = vector_type.get_size();
+        // Captures are the enclosing variables the body refers to, except shadowed ones
+        let captures: Vec<(String, PointerValue<'ctx>)> = body
+            .variable_names()
+            .into_iter()
+            .filter(|name| !params.iter().any(|param| &param.name == name))
+            .filter_map(|name| self.variables.get(&name).map(|&ptr| (name, ptr)))
+            .collect();
+        let mut capture_types = Vec::new();
+        for (name, ptr) in &captures {
+            let capture_type =
+                BasicTypeEnum::try_from(ptr.get_type().get_element_type()).map_err(|_| {
+                    CompileError::codegen_error(format!("Cannot capture variable '{}'", name), None)
+                })?;
+            capture_types.push(capture_type);
+        }
+        let env_type = self.context.struct_type(&capture_types, false);
 
-        if element_count == 0 {
-            return Err(CompileError::codegen_error(
-                "Cannot reduce empty vector".to_string(),
-                None,
-            ));
+        let mut param_types = Vec::new();
+        for param in params {
+            param_types.push(self.resolve_type_annotation(&param.type_annotation)?);
         }
+        let caller_block = self.builder.get_insert_block().unwrap();
+        // Without `-> T` the result has the type the type checker found for the body
+        let result_type = match return_type {
+            Some(type_ann) => type_ann.clone(),
+            None => {
+                let key = (self.current_instance.clone(), span.range());
+                let name = self.expressions.closure_results.get(&key).ok_or_else(|| {
+                    CompileError::codegen_error(
+                        "No result type was inferred for closure".to_string(),
+                        None,
+                    )
+                })?;
+                TypeAnnotation {
+                    name: name.clone(),
+                    is_mutable: false,
+                }
+            }
+        };
+        let result_type = match result_type.name.as_str() {
+            "()" => None,
+            _ => Some(self.resolve_type_annotation(&result_type)?),
+        };
 
-        // Extract first element as accumulator
-        let zero_index = self.context.i32_type().const_int(0, false);
-        let mut accumulator = self
-            .builder
-            .build_extract_element(vector, zero_index, "linear_acc_0")
-            .map_err(|_| {
-                CompileError::codegen_error("Failed to extract first element".to_string(), None)
-            })?;
+        let closure_type = self.closure_type(&param_types, result_type);
+        let code_type = self.closure_code_type(closure_type.into()).unwrap();
+        self.closure_count += 1;
+        let code = self.module.add_function(
+            &format!("__closure_{}", self.closure_count),
+            code_type,
+            Some(Linkage::Internal),
+        );
+        let value = self.generate_closure_body(code, params, &captures, env_type, body)?;
+        if !self.block_has_terminator(self.builder.get_insert_block().unwrap()) {
+            let value = match result_type {
+                Some(result_type) => Some(self.coerce_int_value(value, result_type)?),
+                None => None,
+            };
+            self.builder
+                .build_return(value.as_ref().map(|value| value as &dyn BasicValue))
+                .map_err(|e| {
+                    CompileError::codegen_error(format!("Failed to build return: {:?}", e), None)
+                })?;
+        }
+        self.builder.position_at_end(caller_block);
 
-        // Linearly reduce remaining elements
-        for i in 1..element_count {
-            let index = self.context.i32_type().const_int(i as u64, false);
-            let element = self
-                .builder
-                .build_extract_element(vector, index, &format!("linear_elem_{}", i))
-                .map_err(|_| {
+        // Copy the captured variables into the environment
+        let env_ptr_type = self.context.i8_type().ptr_type(AddressSpace::default());
+        let env = if captures.is_empty() {
+            env_ptr_type.const_null()
+        } else {
+            let function = caller_block.get_parent().unwrap();
+            let env = if self.can_pass_closures_out(function) {
+                self.builder
+                    .build_malloc(env_type, "closure_env")
+                    .map_err(|e| {
+                        CompileError::codegen_error(
+                            format!("Failed to allocate closure environment: {:?}", e),
+                            None,
+                        )
+                    })?
+            } else {
+                self.create_entry_block_alloca(function, "closure_env", env_type.into())?
+            };
+            for (index, (name, ptr)) in captures.iter().enumerate() {
+                let value = self.builder.build_load(*ptr, name).map_err(|e| {
+                    CompileError::codegen_error(format!("Failed to load variable: {:?}", e), None)
+                })?;
+                let slot = self
+                    .builder
+                    .build_struct_gep(env, index as u32, name)
+                    .map_err(|e| {
+                        CompileError::codegen_error(
+                            format!("Failed to capture '{}': {:?}", name, e),
+                            None,
+                        )
+                    })?;
+                self.builder.build_store(slot, value).map_err(|e| {
                     CompileError::codegen_error(
-                        "Failed to extract vector element".to_string(),
+                        format!("Failed to capture '{}': {:?}", name, e),
                         None,
                     )
                 })?;
+            }
+            self.builder
+                .build_pointer_cast(env, env_ptr_type, "closure_env")
+                .map_err(|e| {
+                    CompileError::codegen_error(
+                        format!("Failed to cast closure environment: {:?}", e),
+                        None,
+                    )
+                })?
+        };
 
-            accumulator = match operation {
-                crate::ast::ReductionOp::Sum => {
-                    if vector_type.get_element_type().is_float_type() {
-                        if let (BasicValueEnum::FloatValue(acc), BasicValueEnum::FloatValue(elem)) =
-                            (accumulator, element)
-                        {
-                            self.builder
-                                .build_float_add(acc, elem, "linear_add")
-                                .map(|v| v.into())
-                                .map_err(|_| {
-                                    CompileError::codegen_error(
-                                        "Failed to build linear add".to_string(),
-                                        None,
-                                    )
-                                })?
-                        } else {
-                            return Err(CompileError::codegen_error(
-                                "Type mismatch in linear reduction".to_string(),
-                                None,
-                            ));
-                        }
-                    } else {
-                        if let (BasicValueEnum::IntValue(acc), BasicValueEnum::IntValue(elem)) =
-                            (accumulator, element)
-                        {
-                            self.builder
-                                .build_int_add(acc, elem, "linear_add")
-                                .map(|v| v.into())
-                                .map_err(|_| {
-                                    CompileError::codegen_error(
-                                        "Failed to build linear add".to_string(),
-                                        None,
-                                    )
-                                })?
-                        } else {
-                            return Err(CompileError::codegen_error(
-                                "Type mismatch in linear reduction".to_string(),
-                                None,
-                            ));
-                        }
-                    }
-                }
-                crate::ast::ReductionOp::Product => {
-                    if vector_type.get_element_type().is_float_type() {
-                        if let (BasicValueEnum::FloatValue(acc), BasicValueEnum::FloatValue(elem)) =
-                            (accumulator, element)
-                        {
-                            self.builder
-                                .build_float_mul(acc, elem, "linear_mul")
-                                .map(|v| v.into())
-                                .map_err(|_| {
-                                    CompileError::codegen_error(
-                                        "Failed to build linear mul".to_string(),
-                                        None,
-                                    )
-                                })?
-                        } else {
-                            return Err(CompileError::codegen_error(
-                                "Type mismatch in linear reduction".to_string(),
-                                None,
-                            ));
-                        }
-                    } else {
-                        if let (BasicValueEnum::IntValue(acc), BasicValueEnum::IntValue(elem)) =
-                            (accumulator, element)
-                        {
-                            self.builder
-                                .build_int_mul(acc, elem, "linear_mul")
-                                .map(|v| v.into())
-                                .map_err(|_| {
-                                    CompileError::codegen_error(
-                                        "Failed to build linear mul".to_string(),
-                                        None,
-                                    )
-                                })?
-                        } else {
-                            return Err(CompileError::codegen_error(
-                                "Type mismatch in linear reduction".to_string(),
+        self.build_closure_value(closure_type, code, env)
+    }
+
+    /// Generates the body of a closure into `function` and returns the body's value,
+    /// leaving the builder at the end of the body.
+    fn generate_closure_body(
+        &mut self,
+        function: FunctionValue<'ctx>,
+        params: &[crate::ast::Parameter],
+        captures: &[(String, PointerValue<'ctx>)],
+        env_type: StructType<'ctx>,
+        body: &Expr,
+    ) -> Result<BasicValueEnum<'ctx>> {
+        let entry = self.context.append_basic_block(function, "entry");
+        self.builder.position_at_end(entry);
+
+        let caller_variables = std::mem::take(&mut self.variables);
+        // `break` and `continue` cannot leave the closure body
+        let caller_loops = std::mem::take(&mut self.loop_stack);
+
+        let result = (|| {
+            let env = function.get_nth_param(0).unwrap().into_pointer_value();
+            if !captures.is_empty() {
+                let env = self
+                    .builder
+                    .build_pointer_cast(env, env_type.ptr_type(AddressSpace::default()), "env")
+                    .map_err(|e| {
+                        CompileError::codegen_error(
+                            format!("Failed to cast closure environment: {:?}", e),
+                            None,
+                        )
+                    })?;
+                for (index, (name, _)) in captures.iter().enumerate() {
+                    let slot = self
+                        .builder
+                        .build_struct_gep(env, index as u32, name)
+                        .map_err(|e| {
+                            CompileError::codegen_error(
+                                format!("Failed to load capture '{}': {:?}", name, e),
                                 None,
-                            ));
-                        }
-                    }
-                }
-                _ => {
-                    return Err(CompileError::codegen_error(
-                        format!("Linear reduction for {:?} not implemented", operation),
-                        None,
-                    ));
+                            )
+                        })?;
+                    self.variables.insert(name.clone(), slot);
                 }
-            };
-        }
+            }
+
+            for (i, param) in params.iter().enumerate() {
+                let param_value = function.get_nth_param(i as u32 + 1).ok_or_else(|| {
+                    CompileError::codegen_error(format!("Failed to get parameter {}", i), None)
+                })?;
+                let alloca =
+                    self.create_entry_block_alloca(function, &param.name, param_value.get_type())?;
+                self.builder.build_store(alloca, param_value).map_err(|e| {
+                    CompileError::codegen_error(format!("Failed to store parameter: {:?}", e), None)
+                })?;
+                self.variables.insert(param.name.clone(), alloca);
+            }
 
-        Ok(accumulator)
+            self.generate_expression(body)
+        })();
+
+        self.variables = caller_variables;
+        self.loop_stack = caller_loops;
+        result
     }
 
-    /// Checks if an instruction is a SIMD operation.
-    fn is_simd_instruction(&self, instruction: &inkwell::values::InstructionValue) -> bool {
-        // Check if instruction operates on vector types
-        match instruction.get_opcode() {
-            inkwell::values::InstructionOpcode::Add
-            | inkwell::values::InstructionOpcode::FAdd
-            | inkwell::values::InstructionOpcode::Sub
-            | inkwell::values::InstructionOpcode::FSub
-            | inkwell::values::InstructionOpcode::Mul
-            | inkwell::values::InstructionOpcode::FMul
-            | inkwell::values::InstructionOpcode::UDiv
-            | inkwell::values::InstructionOpcode::SDiv
-            | inkwell::values::InstructionOpcode::FDiv
-            | inkwell::values::InstructionOpcode::And
-            | inkwell::values::InstructionOpcode::Or
-            | inkwell::values::InstructionOpcode::Xor => {
-                // Check if operands are vector types
-                if let Some(operand) = instruction.get_operand(0) {
-                    if let Some(operand_value) = operand.left() {
-                        return operand_value.get_type().is_vector_type();
-                    }
+    /// Whether `function` can hand closures it creates to its caller: through its
+    /// result, or through a pointer parameter to something holding closures.
+    fn can_pass_closures_out(&self, function: FunctionValue<'ctx>) -> bool {
+        let returns_closures = function
+            .get_type()
+            .get_return_type()
+            .map_or(false, |return_type| self.holds_closures(return_type));
+        returns_closures
+            || function.get_params().into_iter().any(|param| match param {
+                BasicValueEnum::PointerValue(pointer) => {
+                    BasicTypeEnum::try_from(pointer.get_type().get_element_type())
+                        .map_or(false, |target| self.holds_closures(target))
                 }
-                false
+                _ => false,
+            })
+    }
+
+    /// Whether values of `ty` are or contain closures.
+    fn holds_closures(&self, ty: BasicTypeEnum<'ctx>) -> bool {
+        if self.closure_code_type(ty).is_some() {
+            return true;
+        }
+        match ty {
+            BasicTypeEnum::StructType(struct_type) => struct_type
+                .get_field_types()
+                .into_iter()
+                .any(|field_type| self.holds_closures(field_type)),
+            BasicTypeEnum::ArrayType(array_type) => {
+                self.holds_closures(array_type.get_element_type())
             }
-            inkwell::values::InstructionOpcode::ShuffleVector
-            | inkwell::values::InstructionOpcode::ExtractElement
-            | inkwell::values::InstructionOpcode::InsertElement => true,
             _ => false,
         }
     }
 
-    /// Enables auto-vectorization optimization for the module.
-    pub fn enable_auto_vectorization(&mut self) -> Result<()> {
-        // Add global metadata to enable LLVM's auto-vectorization
-        let _context = self.context;
+    fn build_closure_value(
+        &self,
+        closure_type: StructType<'ctx>,
+        code: FunctionValue<'ctx>,
+        env: PointerValue<'ctx>,
+    ) -> Result<BasicValueEnum<'ctx>> {
+        let mut closure = closure_type.get_undef();
+        for (index, field) in [code.as_global_value().as_pointer_value(), env]
+            .into_iter()
+            .enumerate()
+        {
+            closure = self
+                .builder
+                .build_insert_value(closure, field, index as u32, "closure")
+                .map_err(|e| {
+                    CompileError::codegen_error(format!("Failed to build closure: {:?}", e), None)
+                })?
+                .into_struct_value();
+        }
+        Ok(closure.into())
+    }
 
-        // Enable loop vectorization through module flags
-        // Note: These are global hints that affect the LLVM optimizer behavior
+    /// A named function used as a value. It is wrapped in a closure whose code
+    /// ignores the environment and forwards its arguments to the function.
+    fn generate_function_value(&mut self, name: &str) -> Result<BasicValueEnum<'ctx>> {
+        let function = *self
+            .functions
+            .get(&self.resolve_function_name(name))
+            .unwrap();
+        let param_types: Vec<BasicTypeEnum<'ctx>> = function
+            .get_params()
+            .iter()
+            .map(|param| param.get_type())
+            .collect();
+        let closure_type = self.closure_type(&param_types, function.get_type().get_return_type());
+        let env_ptr_type = self.context.i8_type().ptr_type(AddressSpace::default());
+
+        let wrapper_name = format!("__fn_value_{}", function.get_name().to_string_lossy());
+        let wrapper = match self.module.get_function(&wrapper_name) {
+            Some(wrapper) => wrapper,
+            None => {
+                let code_type = self.closure_code_type(closure_type.into()).unwrap();
+                let wrapper =
+                    self.module
+                        .add_function(&wrapper_name, code_type, Some(Linkage::Internal));
+                let caller_block = self.builder.get_insert_block();
+                self.builder
+                    .position_at_end(self.context.append_basic_block(wrapper, "entry"));
 
-        // In practice, vectorization is typically enabled via optimization passes
-        // rather than global metadata. This is a conceptual implementation.
+                let args: Vec<inkwell::values::BasicMetadataValueEnum<'ctx>> = wrapper
+                    .get_params()
+                    .into_iter()
+                    .skip(1)
+                    .map(|param| param.into())
+                    .collect();
+                let call = self
+                    .builder
+                    .build_call(function, &args, "forward")
+                    .map_err(|e| {
+                        CompileError::codegen_error(format!("Failed to build call: {:?}", e), None)
+                    })?;
+                let result = call.try_as_basic_value().left();
+                self.builder
+                    .build_return(result.as_ref().map(|value| value as &dyn BasicValue))
+                    .map_err(|e| {
+                        CompileError::codegen_error(
+                            format!("Failed to build return: {:?}", e),
+                            None,
+                        )
+                    })?;
 
-        // The actual auto-vectorization happens during LLVM optimization passes
-        // which can be enabled when compiling the module to object code
+                if let Some(block) = caller_block {
+                    self.builder.position_at_end(block);
+                }
+                wrapper
+            }
+        };
 
-        Ok(())
+        self.build_closure_value(closure_type, wrapper, env_ptr_type.const_null())
     }
 
-    /// Generates optimized SIMD memory operations with alignment hints.
-    fn generate_simd_load_aligned(
+    /// Calls a closure value with already generated arguments.
+    fn call_closure(
         &mut self,
-        address: inkwell::values::PointerValue<'ctx>,
-        vector_type: &SIMDVectorType,
-    ) -> Result<VectorValue<'ctx>> {
-        let _llvm_type = self.simd_type_to_llvm(vector_type)?;
-
-        // Create aligned load with appropriate alignment
-        let alignment = self.get_simd_alignment(vector_type);
-        let load_inst = self
+        closure: BasicValueEnum<'ctx>,
+        args: Vec<BasicValueEnum<'ctx>>,
+    ) -> Result<BasicValueEnum<'ctx>> {
+        let code_type = self.closure_code_type(closure.get_type()).ok_or_else(|| {
+            CompileError::codegen_error("Called value is not a function".to_string(), None)
+        })?;
+        let closure = closure.into_struct_value();
+        let code = self
             .builder
-            .build_load(address, "simd_load_aligned")
+            .build_extract_value(closure, 0, "closure_code")
             .map_err(|e| {
-                CompileError::codegen_error(format!("Failed to build aligned load: {:?}", e), None)
+                CompileError::codegen_error(format!("Failed to call closure: {:?}", e), None)
+            })?
+            .into_pointer_value();
+        let env = self
+            .builder
+            .build_extract_value(closure, 1, "closure_env")
+            .map_err(|e| {
+                CompileError::codegen_error(format!("Failed to call closure: {:?}", e), None)
             })?;
 
-        // Set alignment hint for better optimization
-        if let Some(inst) = load_inst.as_instruction_value() {
-            inst.set_alignment(alignment).map_err(|e| {
-                CompileError::codegen_error(format!("Failed to set load alignment: {:?}", e), None)
-            })?;
+        let mut call_args: Vec<inkwell::values::BasicMetadataValueEnum<'ctx>> = vec![env.into()];
+        for (arg, param_type) in args
+            .into_iter()
+            .zip(code_type.get_param_types().into_iter().skip(1))
+        {
+            call_args.push(self.coerce_int_value(arg, param_type)?.into());
         }
 
-        // Convert to vector value
-        if let inkwell::values::BasicValueEnum::VectorValue(vec_val) = load_inst {
-            Ok(vec_val)
-        } else {
-            Err(CompileError::codegen_error(
-                "Load result is not a vector value".to_string(),
-                None,
-            ))
+        let code = CallableValue::try_from(code).map_err(|_| {
+            CompileError::codegen_error("Called value is not a function".to_string(), None)
+        })?;
+        let call = self
+            .builder
+            .build_call(code, &call_args, "closure_call")
+            .map_err(|e| {
+                CompileError::codegen_error(format!("Failed to call closure: {:?}", e), None)
+            })?;
+        match call.try_as_basic_value().left() {
+            Some(value) => Ok(value),
+            // Returns nothing: a dummy value for statement context
+            None => Ok(self.context.i32_type().const_int(0, false).into()),
         }
     }
 
-    /// Generates optimized SIMD memory store with alignment hints.
-    fn generate_simd_store_aligned(
-        &mut self,
-        vector: VectorValue<'ctx>,
-        address: inkwell::values::PointerValue<'ctx>,
-        vector_type: &SIMDVectorType,
-    ) -> Result<()> {
-        // Create aligned store with appropriate alignment
-        let alignment = self.get_simd_alignment(vector_type);
-        let store_inst = self.builder.build_store(address, vector).map_err(|e| {
-            CompileError::codegen_error(format!("Failed to build aligned store: {:?}", e), None)
-        })?;
-
-        // Set alignment hint for better optimization
-        store_inst.set_alignment(alignment).map_err(|e| {
-            CompileError::codegen_error(format!("Failed to set store alignment: {:?}", e), None)
-        })?;
-
-        Ok(())
+    /// Integer literals are generated as i32; this widens or narrows them to `ty`.
+    fn coerce_int_value(
+        &self,
+        value: BasicValueEnum<'ctx>,
+        ty: BasicTypeEnum<'ctx>,
+    ) -> Result<BasicValueEnum<'ctx>> {
+        match (value, ty) {
+            (BasicValueEnum::IntValue(int_value), BasicTypeEnum::IntType(int_type))
+                if int_value.get_type() != int_type =>
+            {
+                Ok(self
+                    .builder
+                    .build_int_cast(int_value, int_type, "int_cast")
+                    .map_err(|e| {
+                        CompileError::codegen_error(
+                            format!("Failed to convert value: {:?}", e),
+                            None,
+                        )
+                    })?
+                    .into())
+            }
+            (value, _) => Ok(value),
+        }
     }
 
-    /// Gets the appropriate memory alignment for a SIMD vector type.
-    fn get_simd_alignment(&self, vector_type: &SIMDVectorType) -> u32 {
-        match vector_type {
-            // 128-bit vectors (16-byte alignment)
-            SIMDVectorType::F32x4
-            | SIMDVectorType::F64x2
-            | SIMDVectorType::I32x4
-            | SIMDVectorType::I64x2
-            | SIMDVectorType::I16x8
-            | SIMDVectorType::I8x16
-            | SIMDVectorType::U32x4
-            | SIMDVectorType::U16x8
-            | SIMDVectorType::U8x16 => 16,
-
-            // 256-bit vectors (32-byte alignment)
-            SIMDVectorType::F32x8
-            | SIMDVectorType::F64x4
-            | SIMDVectorType::I32x8
-            | SIMDVectorType::I64x4
-            | SIMDVectorType::I16x16
-            | SIMDVectorType::I8x32
-            | SIMDVectorType::U32x8
-            | SIMDVectorType::U16x16
-            | SIMDVectorType::U8x32 => 32,
-
-            // 512-bit vectors (64-byte alignment)
-            SIMDVectorType::F32x16
-            | SIMDVectorType::F64x8
-            | SIMDVectorType::I32x16
-            | SIMDVectorType::I64x8
-            | SIMDVectorType::I16x32
-            | SIMDVectorType::I8x64 => 64,
+    /// Generates `vec.map(f)`, `vec.filter(f)` and `vec.reduce(initial, f)` as a
+    /// loop over the Vec runtime that calls the closure once per element.
+    fn generate_vec_closure_method(
+        &mut self,
+        vec_ptr: PointerValue<'ctx>,
+        method_name: &str,
+        args: &[Expr],
+    ) -> Result<BasicValueEnum<'ctx>> {
+        let (initial, closure) = match (method_name, args) {
+            ("reduce", [initial, closure]) => (Some(initial), closure),
+            ("map" | "filter", [closure]) => (None, closure),
+            _ => {
+                return Err(CompileError::codegen_error(
+                    format!(
+                        "Vec::{}() called with {} arguments",
+                        method_name,
+                        args.len()
+                    ),
+                    None,
+                ))
+            }
+        };
+        let runtime = |name: &str| {
+            self.functions.get(name).copied().ok_or_else(|| {
+                CompileError::codegen_error(
+                    format!("Vec runtime function '{}' not found", name),
+                    None,
+                )
+            })
+        };
+        let (vec_new, vec_push, vec_len, vec_get) = (
+            runtime("vec_new")?,
+            runtime("vec_push")?,
+            runtime("vec_len")?,
+            runtime("vec_get")?,
+        );
 
-            // Smaller vectors (8-byte alignment)
-            SIMDVectorType::F32x2
-            | SIMDVectorType::I32x2
-            | SIMDVectorType::I16x4
-            | SIMDVectorType::U8x8
-            | SIMDVectorType::I8x8 => 8,
+        let closure = self.generate_expression(closure)?;
+        let function = self
+            .builder
+            .get_insert_block()
+            .unwrap()
+            .get_parent()
+            .unwrap();
+        let i32_type = self.context.i32_type();
+        let codegen_error = |e: inkwell::builder::BuilderError| {
+            CompileError::codegen_error(
+                format!("Failed to build Vec::{}(): {:?}", method_name, e),
+                None,
+            )
+        };
 
-            // Very small vectors (4-byte alignment)
-            SIMDVectorType::U8x4 => 4,
+        // The accumulator of `reduce`, or the Vec being built by `map` and `filter`
+        let result_ptr = match initial {
+            Some(initial) => {
+                let initial = self.generate_expression(initial)?;
+                let accumulator_type = self
+                    .closure_code_type(closure.get_type())
+                    .and_then(|code_type| code_type.get_param_types().get(1).copied())
+                    .unwrap_or(initial.get_type());
+                let initial = self.coerce_int_value(initial, accumulator_type)?;
+                let accumulator =
+                    self.create_entry_block_alloca(function, "accumulator", accumulator_type)?;
+                self.builder
+                    .build_store(accumulator, initial)
+                    .map_err(codegen_error)?;
+                accumulator
+            }
+            None => {
+                let new_vec = self
+                    .builder
+                    .build_call(vec_new, &[], "result_vec")
+                    .map_err(codegen_error)?
+                    .try_as_basic_value()
+                    .left()
+                    .unwrap();
+                let result_vec =
+                    self.create_entry_block_alloca(function, "result_vec", new_vec.get_type())?;
+                self.builder
+                    .build_store(result_vec, new_vec)
+                    .map_err(codegen_error)?;
+                result_vec
+            }
+        };
 
-            // Mask types (match the underlying vector size they represent)
-            SIMDVectorType::Mask8 => 8,
-            SIMDVectorType::Mask16 => 16,
-            SIMDVectorType::Mask32 => 32,
-            SIMDVectorType::Mask64 => 64,
-        }
-    }
+        let index_ptr = self.create_entry_block_alloca(function, "index", i32_type.into())?;
+        self.builder
+            .build_store(index_ptr, i32_type.const_zero())
+            .map_err(codegen_error)?;
+        let len = self
+            .builder
+            .build_call(vec_len, &[vec_ptr.into()], "len")
+            .map_err(codegen_error)?
+            .try_as_basic_value()
+            .left()
+            .unwrap()
+            .into_int_value();
 
-    /// Adds vectorization hints to loops containing SIMD operations.
-    pub fn add_vectorization_hints(&mut self, _function: FunctionValue<'ctx>) -> Result<()> {
-        // This would typically be called after generating a function with loops
-        // Add metadata to encourage vectorization of loops containing SIMD operations
+        let cond_block = self.context.append_basic_block(function, "vec_loop_cond");
+        let body_block = self.context.append_basic_block(function, "vec_loop_body");
+        let next_block = self.context.append_basic_block(function, "vec_loop_next");
+        let done_block = self.context.append_basic_block(function, "vec_loop_done");
+        self.builder
+            .build_unconditional_branch(cond_block)
+            .map_err(codegen_error)?;
 
-        let context = self.context;
+        self.builder.position_at_end(cond_block);
+        let index = self
+            .builder
+            .build_load(index_ptr, "index")
+            .map_err(codegen_error)?
+            .into_int_value();
+        let in_bounds = self
+            .builder
+            .build_int_compare(IntPredicate::SLT, index, len, "in_bounds")
+            .map_err(codegen_error)?;
+        self.builder
+            .build_conditional_branch(in_bounds, body_block, done_block)
+            .map_err(codegen_error)?;
 
-        // Create vectorization metadata
-        let _vectorize_enable = context.metadata_node(&[
-            context.metadata_string("llvm.loop.vectorize.enable").into(),
-            context.bool_type().const_int(1, false).into(),
-        ]);
+        // The runtime stores i32 elements
+        self.builder.position_at_end(body_block);
+        let element_ptr = self
+            .builder
+            .build_call(vec_get, &[vec_ptr.into(), index.into()], "element_ptr")
+            .map_err(codegen_error)?
+            .try_as_basic_value()
+            .left()
+            .unwrap()
+            .into_pointer_value();
+        let element_ptr = self
+            .builder
+            .build_pointer_cast(
+                element_ptr,
+                i32_type.ptr_type(AddressSpace::default()),
+                "element_ptr",
+            )
+            .map_err(codegen_error)?;
+        let element = self
+            .builder
+            .build_load(element_ptr, "element")
+            .map_err(codegen_error)?;
 
-        let _vectorize_width = context.metadata_node(&[
-            context.metadata_string("llvm.loop.vectorize.width").into(),
-            context.i32_type().const_int(8, false).into(),
-        ]);
+        match method_name {
+            "map" => {
+                let mapped = self.call_closure(closure, vec![element])?;
+                if !mapped.is_int_value() {
+                    return Err(CompileError::codegen_error(
+                        "Vec::map() can only produce integer elements".to_string(),
+                        None,
+                    ));
+                }
+                let mapped = self.coerce_int_value(mapped, i32_type.into())?;
+                self.builder
+                    .build_call(vec_push, &[result_ptr.into(), mapped.into()], "")
+                    .map_err(codegen_error)?;
+                self.builder
+                    .build_unconditional_branch(next_block)
+                    .map_err(codegen_error)?;
+            }
+            "filter" => {
+                let keep = self.call_closure(closure, vec![element])?.into_int_value();
+                let keep_block = self.context.append_basic_block(function, "vec_filter_keep");
+                self.builder
+                    .build_conditional_branch(keep, keep_block, next_block)
+                    .map_err(codegen_error)?;
+                self.builder.position_at_end(keep_block);
+                self.builder
+                    .build_call(vec_push, &[result_ptr.into(), element.into()], "")
+                    .map_err(codegen_error)?;
+                self.builder
+                    .build_unconditional_branch(next_block)
+                    .map_err(codegen_error)?;
+            }
+            _ => {
+                let accumulator = self
+                    .builder
+                    .build_load(result_ptr, "accumulator")
+                    .map_err(codegen_error)?;
+                let reduced = self.call_closure(closure, vec![accumulator, element])?;
+                let reduced = self.coerce_int_value(reduced, accumulator.get_type())?;
+                self.builder
+                    .build_store(result_ptr, reduced)
+                    .map_err(codegen_error)?;
+                self.builder
+                    .build_unconditional_branch(next_block)
+                    .map_err(codegen_error)?;
+            }
+        }
 
-        // Note: In a real implementation, these would be attached to specific loop blocks
-        // This is a simplified version for demonstration
+        self.builder.position_at_end(next_block);
+        let next_index = self
+            .builder
+            .build_int_add(index, i32_type.const_int(1, false), "next_index")
+            .map_err(codegen_error)?;
+        self.builder
+            .build_store(index_ptr, next_index)
+            .map_err(codegen_error)?;
+        self.builder
+            .build_unconditional_branch(cond_block)
+            .map_err(codegen_error)?;
 
-        Ok(())
+        self.builder.position_at_end(done_block);
+        self.builder
+            .build_load(result_ptr, "vec_result")
+            .map_err(codegen_error)
     }
 
-    /// Get default memory alignment for a SIMD vector type
-    fn get_default_alignment(&self, vector_type: &SIMDVectorType) -> u32 {
-        match vector_type {
-            // 32-bit float vectors
-            SIMDVectorType::F32x2 => 8,   // 2 * 4 bytes
-            SIMDVectorType::F32x4 => 16,  // 4 * 4 bytes (SSE alignment)
-            SIMDVectorType::F32x8 => 32,  // 8 * 4 bytes (AVX alignment)
-            SIMDVectorType::F32x16 => 64, // 16 * 4 bytes (AVX-512 alignment)
+    /// Generates code for field access.
+    fn generate_field_access(
+        &mut self,
+        struct_expr: &Expr,
+        field_name: &str,
+    ) -> Result<BasicValueEnum<'ctx>> {
+        let struct_value = self.generate_expression(struct_expr)?;
 
-            // 64-bit float vectors
-            SIMDVectorType::F64x2 => 16, // 2 * 8 bytes (SSE alignment)
-            SIMDVectorType::F64x4 => 32, // 4 * 8 bytes (AVX alignment)
-            SIMDVectorType::F64x8 => 64, // 8 * 8 bytes (AVX-512 alignment)
+        // Tuple fields are numbered: `pair.0`
+        if let Ok(index) = field_name.parse::<u32>() {
+            return self
+                .builder
+                .build_extract_value(struct_value.into_struct_value(), index, &format!("tuple_{}", index))
+                .map_err(|e| {
+                    CompileError::codegen_error(
+                        format!("Failed to extract tuple field {}: {:?}", index, e),
+                        None,
+                    )
+                });
+        }
 
-            // 32-bit integer vectors
-            SIMDVectorType::I32x2 => 8,
-            SIMDVectorType::I32x4 | SIMDVectorType::U32x4 => 16, // SSE alignment
-            SIMDVectorType::I32x8 | SIMDVectorType::U32x8 => 32, // AVX alignment
-            SIMDVectorType::I32x16 => 64, // AVX-512 alignment
+        let struct_type = struct_value.into_struct_value().get_type();
+        let field_index = self
+            .struct_field_index(struct_type, field_name)
+            .ok_or_else(|| {
+                CompileError::codegen_error(
+                    format!("Unknown field '{}' in field access", field_name),
+                    None,
+                )
+            })?;
 
-            // 64-bit integer vectors
-            SIMDVectorType::I64x2 => 16, // SSE alignment
-            SIMDVectorType::I64x4 => 32, // AVX alignment
-            SIMDVectorType::I64x8 => 64, // AVX-512 alignment
+        let field_value = self
+            .builder
+            .build_extract_value(
+                struct_value.into_struct_value(),
+                field_index,
+                &format!("field_{}", field_name),
+            )
+            .unwrap();
 
-            // 16-bit integer vectors
-            SIMDVectorType::I16x4 => 8, // 4 * 2 bytes
-            SIMDVectorType::I16x8 | SIMDVectorType::U16x8 => 16, // SSE alignment
-            SIMDVectorType::I16x16 | SIMDVectorType::U16x16 => 32, // AVX alignment
-            SIMDVectorType::I16x32 => 64, // AVX-512 alignment
+        Ok(field_value)
+    }
 
-            // 8-bit integer vectors
-            SIMDVectorType::U8x4 => 4, // 4 * 1 bytes
-            SIMDVectorType::I8x8 | SIMDVectorType::U8x8 => 8, // 8 * 1 bytes
-            SIMDVectorType::I8x16 | SIMDVectorType::U8x16 => 16, // SSE alignment
-            SIMDVectorType::I8x32 | SIMDVectorType::U8x32 => 32, // AVX alignment
-            SIMDVectorType::I8x64 => 64, // AVX-512 alignment
+    /// The index of `field_name` in the declaration of the struct `struct_type`.
+    fn struct_field_index(&self, struct_type: StructType<'ctx>, field_name: &str) -> Option<u32> {
+        let struct_name = self.struct_type_name(struct_type)?;
+        self.struct_fields
+            .get(&struct_name)?
+            .get(field_name)
+            .copied()
+    }
 
-            // Mask vectors
-            SIMDVectorType::Mask8 => 1,
-            SIMDVectorType::Mask16 => 2,
-            SIMDVectorType::Mask32 => 4,
-            SIMDVectorType::Mask64 => 8,
-        }
+    /// Converts a TypeAnnotation to an LLVM type, substituting the type parameters of
+    /// the instantiation being generated and laying out generic structs on first use.
+    fn resolve_type_annotation(
+        &mut self,
+        type_annotation: &TypeAnnotation,
+    ) -> Result<BasicTypeEnum<'ctx>> {
+        let name = crate::ast::substitute_type_params(&type_annotation.name, &self.type_substitutions);
+        self.ensure_struct_instance(&name)?;
+        self.type_annotation_to_llvm_type(&TypeAnnotation {
+            name,
+            is_mutable: type_annotation.is_mutable,
+        })
     }
 
-    /// Generates code for a struct declaration.
-    fn generate_struct_declaration(&mut self, name: &str, fields: &[StructField]) -> Result<()> {
-        // Convert field types from TypeAnnotation to LLVM BasicTypeEnum
+    /// Lays out `Name<Args>` if it names a not yet instantiated generic struct.
+    fn ensure_struct_instance(&mut self, name: &str) -> Result<()> {
+        if self.struct_types.contains_key(name) {
+            return Ok(());
+        }
+        let (base, type_args) = match crate::ast::split_generic_type_name(name) {
+            Some(parts) => parts,
+            None => return Ok(()),
+        };
+        let generic = match self.generic_structs.get(base) {
+            Some(generic) => generic.clone(),
+            None => return Ok(()),
+        };
+        if type_args.len() != generic.type_params.len() {
+            return Err(CompileError::codegen_error(
+                format!(
+                    "Struct '{}' expects {} type arguments, got {}",
+                    base,
+                    generic.type_params.len(),
+                    type_args.len()
+                ),
+                None,
+            ));
+        }
+
+        // Named, so instances stay distinct from structs with the same layout
+        let struct_type = self.context.opaque_struct_type(name);
+        self.struct_types.insert(name.to_string(), struct_type);
+
+        let substitutions = generic.type_params.iter().cloned().zip(type_args).collect();
+        let saved_substitutions = std::mem::replace(&mut self.type_substitutions, substitutions);
         let mut field_types = Vec::new();
         let mut field_map = HashMap::new();
-
-        for (index, field) in fields.iter().enumerate() {
-            let llvm_type = self.type_annotation_to_llvm_type(&field.type_annotation)?;
-            field_types.push(llvm_type);
-            field_map.insert(field.name.clone(), index as u32);
+        let mut result = Ok(());
+        for (index, field) in generic.fields.iter().enumerate() {
+            match self.resolve_type_annotation(&field.type_annotation) {
+                Ok(llvm_type) => {
+                    field_types.push(llvm_type);
+                    field_map.insert(field.name.clone(), index as u32);
+                }
+                Err(e) => {
+                    result = Err(e);
+                    break;
+                }
+            }
         }
+        self.type_substitutions = saved_substitutions;
+        result?;
 
-        // Create LLVM struct type
-        let struct_type = self.context.struct_type(&field_types, false);
-
-        // Store the struct type and field mapping for later use
-        self.struct_types.insert(name.to_string(), struct_type);
+        struct_type.set_body(&field_types, false);
         self.struct_fields.insert(name.to_string(), field_map);
-
         Ok(())
     }
 
-    /// Generates code for a struct literal.
-    fn generate_struct_literal(
+    /// Generates a call to a generic function, instantiating it for the type
+    /// arguments the type checker inferred for the call.
+    fn generate_generic_function_call(
         &mut self,
         name: &str,
-        fields: &[StructFieldInit],
+        generic: &GenericFunction,
+        args: &[Expr],
+        span: &Span,
     ) -> Result<BasicValueEnum<'ctx>> {
-        // Get the struct type
-        let struct_type = self.struct_types.get(name).ok_or_else(|| {
-            CompileError::codegen_error(format!("Unknown struct type: {}", name), None)
-        })?;
+        if args.len() != generic.params.len() {
+            return Err(CompileError::codegen_error(
+                format!(
+                    "Function '{}' expects {} arguments, got {}",
+                    name,
+                    generic.params.len(),
+                    args.len()
+                ),
+                None,
+            ));
+        }
 
-        // Create an undef struct value to start with
-        let mut struct_value = struct_type.get_undef();
+        let mut arg_values = Vec::new();
+        for arg in args {
+            arg_values.push(self.generate_expression(arg)?);
+        }
 
-        // Fill in the struct fields
-        for (field_index, field_init) in fields.iter().enumerate() {
-            let field_value = self.generate_expression(&field_init.value)?;
-            struct_value = self
-                .builder
-                .build_insert_value(
-                    struct_value,
-                    field_value,
-                    field_index as u32,
-                    &format!("field_{}", field_init.name),
-                )
-                .unwrap()
-                .into_struct_value();
+        let type_args = self.checked_type_arguments(name, span)?;
+        let function = self.instantiate_generic_function(name, generic, &type_args)?;
+
+        // Integer literals were generated as i32; match them to the instantiated parameters
+        let mut call_args: Vec<inkwell::values::BasicMetadataValueEnum> = Vec::new();
+        for (value, param) in arg_values.into_iter().zip(function.get_params()) {
+            let value = match (value, param.get_type()) {
+                (BasicValueEnum::IntValue(int_value), BasicTypeEnum::IntType(int_type))
+                    if int_value.get_type() != int_type =>
+                {
+                    self.builder
+                        .build_int_cast(int_value, int_type, "generic_arg")
+                        .map_err(|e| {
+                            CompileError::codegen_error(
+                                format!("Failed to convert argument: {:?}", e),
+                                None,
+                            )
+                        })?
+                        .into()
+                }
+                _ => value,
+            };
+            call_args.push(value.into());
         }
 
-        Ok(struct_value.into())
+        let call = self
+            .builder
+            .build_call(function, &call_args, "call")
+            .map_err(|e| {
+                CompileError::codegen_error(format!("Failed to build call: {:?}", e), None)
+            })?;
+
+        match call.try_as_basic_value().left() {
+            Some(return_value) => Ok(return_value),
+            // Function returns void - create a dummy value for statement context
+            None => Ok(self.context.i32_type().const_int(0, false).into()),
+        }
     }
 
-    /// Generates code for field access.
-    fn generate_field_access(
-        &mut self,
-        struct_expr: &Expr,
-        field_name: &str,
-    ) -> Result<BasicValueEnum<'ctx>> {
-        let struct_value = self.generate_expression(struct_expr)?;
+    /// The type arguments the type checker inferred for the use of the generic
+    /// `item` at `span`.
+    ///
+    /// LLVM types lose signedness and function types, so they are not inferred again here.
+    fn checked_type_arguments(&self, item: &str, span: &Span) -> Result<Vec<String>> {
+        self.expressions
+            .type_arguments
+            .get(&(self.current_instance.clone(), span.range()))
+            .cloned()
+            .ok_or_else(|| {
+                CompileError::codegen_error(
+                    format!("No type arguments were inferred for '{}'", item),
+                    None,
+                )
+            })
+    }
 
-        // Try to find the field index (simplified version - should use type info)
-        let field_index = match field_name {
-            "x" => 0,        // Point.x
-            "y" => 1,        // Point.y
-            "top_left" => 0, // Rectangle.top_left
-            "width" => 1,    // Rectangle.width
-            "height" => 2,   // Rectangle.height
-            _ => 0,
+    /// Returns the function for `name<type_args>`, generating it on first use.
+    fn instantiate_generic_function(
+        &mut self,
+        name: &str,
+        generic: &GenericFunction,
+        type_args: &[String],
+    ) -> Result<FunctionValue<'ctx>> {
+        // Instances of imported functions are named after their declaring module,
+        // and emitted with the names of that module
+        let (base_name, declaring_scope) = match &generic.module {
+            Some(module) => (
+                format!("{}::{}", module, generic.name),
+                self.module_scopes.get(module).cloned(),
+            ),
+            None => (name.to_string(), None),
         };
+        let instance_name = crate::ast::generic_instance_name(&base_name, type_args);
+        let caller_scope = declaring_scope.map(|scope| self.replace_scope(scope));
+        let symbol =
+            self.function_symbol_name(&crate::ast::generic_instance_name(&generic.name, type_args));
+        let result = match self.functions.get(&symbol) {
+            Some(&function) => Ok(function),
+            None => self.generate_function_instance(&instance_name, &symbol, generic, type_args),
+        };
+        if let Some(scope) = caller_scope {
+            self.replace_scope(scope);
+        }
+        result
+    }
 
-        let field_value = self
-            .builder
-            .build_extract_value(
-                struct_value.into_struct_value(),
-                field_index,
-                &format!("field_{}", field_name),
+    /// Emits `symbol`, the instance of `generic` for `type_args`.
+    fn generate_function_instance(
+        &mut self,
+        instance_name: &str,
+        symbol: &str,
+        generic: &GenericFunction,
+        type_args: &[String],
+    ) -> Result<FunctionValue<'ctx>> {
+        // Emit the instance as a function of its own, then resume at the call site
+        let call_site = self.builder.get_insert_block();
+        let caller_loops = std::mem::take(&mut self.loop_stack);
+        let substitutions = generic
+            .type_params
+            .iter()
+            .cloned()
+            .zip(type_args.iter().cloned())
+            .collect();
+        let saved_substitutions = std::mem::replace(&mut self.type_substitutions, substitutions);
+        let saved_instance =
+            std::mem::replace(&mut self.current_instance, instance_name.to_string());
+
+        let result = self.generate_function_declaration(
+            symbol,
+            &generic.params,
+            &generic.return_type,
+            &generic.body,
+        );
+
+        self.current_instance = saved_instance;
+        self.type_substitutions = saved_substitutions;
+        self.loop_stack = caller_loops;
+        if let Some(block) = call_site {
+            self.builder.position_at_end(block);
+        }
+        result?;
+
+        self.functions.get(symbol).copied().ok_or_else(|| {
+            CompileError::codegen_error(
+                format!("Failed to instantiate generic function '{}'", instance_name),
+                None,
             )
-            .unwrap();
+        })
+    }
 
-        Ok(field_value)
+    /// Names the Eä type of an LLVM type, for finding the impl of a receiver.
+    fn llvm_type_name(&self, llvm_type: BasicTypeEnum<'ctx>) -> Option<String> {
+        match llvm_type {
+            BasicTypeEnum::IntType(int_type) => match int_type.get_bit_width() {
+                1 => Some("bool".to_string()),
+                8 => Some("i8".to_string()),
+                16 => Some("i16".to_string()),
+                32 => Some("i32".to_string()),
+                64 => Some("i64".to_string()),
+                _ => None,
+            },
+            BasicTypeEnum::FloatType(float_type) => {
+                if float_type == self.context.f32_type() {
+                    Some("f32".to_string())
+                } else if float_type == self.context.f64_type() {
+                    Some("f64".to_string())
+                } else {
+                    None
+                }
+            }
+            BasicTypeEnum::VectorType(vector_type) => {
+                let element = self.llvm_type_name(vector_type.get_element_type())?;
+                Some(format!("{}x{}", element, vector_type.get_size()))
+            }
+            BasicTypeEnum::PointerType(pointer_type)
+                if pointer_type == self.context.i8_type().ptr_type(AddressSpace::default()) =>
+            {
+                Some("string".to_string())
+            }
+            BasicTypeEnum::StructType(struct_type) => self.struct_type_name(struct_type),
+            _ => None,
+        }
+    }
+
+    /// The declared name of a struct or enum type; `None` for tuples and closures.
+    fn struct_type_name(&self, struct_type: StructType<'ctx>) -> Option<String> {
+        let llvm_name = struct_type.get_name()?.to_str().ok()?;
+        if self.struct_types.get(llvm_name) == Some(&struct_type) {
+            return Some(llvm_name.to_string());
+        }
+        // LLVM renames a type whose name is already taken in the context
+        self.struct_types
+            .iter()
+            .filter(|(_, ty)| **ty == struct_type)
+            .map(|(name, _)| name)
+            .min()
+            .cloned()
+    }
+
+    /// The vector type `simd<element_type>` resolves to on the target, as the type
+    /// checker resolved it.
+    fn native_vector_type(&self, element_type: &str) -> Result<SIMDVectorType> {
+        self.hardware_detector
+            .native_vector_type(element_type)
+            .ok_or_else(|| {
+                CompileError::codegen_error(
+                    format!(
+                        "simd<{}> has no vector width on target architecture {}",
+                        element_type,
+                        self.hardware_detector.target_arch()
+                    ),
+                    None,
+                )
+            })
     }
 
     /// Converts a TypeAnnotation to an LLVM type.
@@ -11623,41 +15416,67 @@ impl<'ctx> CodeGenerator<'ctx> {
                 .ptr_type(AddressSpace::default())
                 .into()),
             _ => {
-                // Check if it's a tuple type (starts with '(' and ends with ')')
-                if type_annotation.name.starts_with('(') && type_annotation.name.ends_with(')') {
-                    // Parse tuple type: "(i32, i32)" -> [i32, i32]
-                    let inner = &type_annotation.name[1..type_annotation.name.len()-1];
-                    let element_types: Vec<&str> = inner.split(',').map(|s| s.trim()).collect();
-                    
+                // Lane-width-agnostic vectors take the target's widest width: "simd<f32>" -> <8 x float>
+                if let Some(element_name) =
+                    crate::ast::split_native_vector_type_name(&type_annotation.name)
+                {
+                    let vector_type = self.native_vector_type(&element_name)?;
+                    self.type_annotation_to_llvm_type(&TypeAnnotation {
+                        name: vector_type.to_string(),
+                        is_mutable: false,
+                    })
+                }
+                // Tuple types lower to anonymous structs: "(i32, (f32, bool))" -> { i32, { float, i1 } }
+                else if let Some(element_names) = crate::ast::split_tuple_type_name(&type_annotation.name) {
                     let mut llvm_element_types = Vec::new();
-                    for element_type_str in element_types {
-                        match element_type_str {
-                            "i8" => llvm_element_types.push(self.context.i8_type().into()),
-                            "i16" => llvm_element_types.push(self.context.i16_type().into()),
-                            "i32" => llvm_element_types.push(self.context.i32_type().into()),
-                            "i64" => llvm_element_types.push(self.context.i64_type().into()),
-                            "u8" => llvm_element_types.push(self.context.i8_type().into()),
-                            "u16" => llvm_element_types.push(self.context.i16_type().into()),
-                            "u32" => llvm_element_types.push(self.context.i32_type().into()),
-                            "u64" => llvm_element_types.push(self.context.i64_type().into()),
-                            "f32" => llvm_element_types.push(self.context.f32_type().into()),
-                            "f64" => llvm_element_types.push(self.context.f64_type().into()),
-                            "bool" => llvm_element_types.push(self.context.bool_type().into()),
-                            "string" => llvm_element_types.push(self.context.i8_type().ptr_type(AddressSpace::default()).into()),
-                            "Vec<i32>" => llvm_element_types.push(self.context.i8_type().ptr_type(AddressSpace::default()).into()),
-                            "Vec<String>" | "Vec<string>" => llvm_element_types.push(self.context.i8_type().ptr_type(AddressSpace::default()).ptr_type(AddressSpace::default()).into()),
-                            _ => {
-                                return Err(CompileError::codegen_error(
-                                    format!("Unsupported type in tuple: {}", element_type_str),
-                                    None,
-                                ));
-                            }
-                        }
+                    for name in element_names {
+                        llvm_element_types.push(self.type_annotation_to_llvm_type(&TypeAnnotation {
+                            name,
+                            is_mutable: false,
+                        })?);
                     }
-                    
-                    // Create an anonymous struct type for the tuple
-                    let tuple_struct = self.context.struct_type(&llvm_element_types, false);
-                    Ok(tuple_struct.into())
+                    Ok(self.context.struct_type(&llvm_element_types, false).into())
+                }
+                // Raw pointers lower to LLVM pointers: "*mut f32" -> float*
+                else if let Some((target_name, _)) =
+                    crate::ast::split_pointer_type_name(&type_annotation.name)
+                {
+                    let target_type = self.type_annotation_to_llvm_type(&TypeAnnotation {
+                        name: target_name,
+                        is_mutable: false,
+                    })?;
+                    Ok(target_type.ptr_type(AddressSpace::default()).into())
+                }
+                // Fixed-size arrays lower to LLVM arrays: "[f32; 64]" -> [64 x float]
+                else if let Some((element_name, length)) =
+                    crate::ast::split_array_type_name(&type_annotation.name)
+                {
+                    let element_type = self.type_annotation_to_llvm_type(&TypeAnnotation {
+                        name: element_name,
+                        is_mutable: false,
+                    })?;
+                    let length = self.constant_length(&crate::ast::array_length_expr(&length))?;
+                    Ok(element_type.array_type(length as u32).into())
+                }
+                // Function types lower to closures: "func(f32) -> f32" -> { float (i8*, float)*, i8* }
+                else if let Some((param_names, return_name)) =
+                    crate::ast::split_function_type_name(&type_annotation.name)
+                {
+                    let mut param_types = Vec::new();
+                    for name in param_names {
+                        param_types.push(self.type_annotation_to_llvm_type(&TypeAnnotation {
+                            name,
+                            is_mutable: false,
+                        })?);
+                    }
+                    let return_type = match return_name.as_str() {
+                        "()" => None,
+                        _ => Some(self.type_annotation_to_llvm_type(&TypeAnnotation {
+                            name: return_name,
+                            is_mutable: false,
+                        })?),
+                    };
+                    Ok(self.closure_type(&param_types, return_type).into())
                 }
                 // Check if it's a struct type
                 else if let Some(struct_type) = self.struct_types.get(&type_annotation.name) {
@@ -12078,17 +15897,17 @@ impl<'ctx> CodeGenerator<'ctx> {
     /// Check if an expression contains a function call that returns a vector type
     fn expression_contains_vector_function_call(&self, expr: &Expr) -> bool {
         match expr {
-            Expr::Call(_, _) => {
+            Expr::Call(..) => {
                 // For simplicity, assume all function calls might return vectors
                 // In a complete implementation, we'd check the function's return type
                 true
             }
-            Expr::Binary(left, _, right) => {
+            Expr::Binary(left, _, right, _) => {
                 self.expression_contains_vector_function_call(left) ||
                 self.expression_contains_vector_function_call(right)
             }
-            Expr::Unary(_, expr) => self.expression_contains_vector_function_call(expr),
-            Expr::Grouping(expr) => self.expression_contains_vector_function_call(expr),
+            Expr::Unary(_, expr, _) => self.expression_contains_vector_function_call(expr),
+            Expr::Grouping(expr, _) => self.expression_contains_vector_function_call(expr),
             _ => false,
         }
     }
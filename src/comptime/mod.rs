@@ -6,8 +6,9 @@
 //! - Automatic optimization selection based on data characteristics
 //! - Performance guarantees through static analysis
 
-use crate::ast::{Expr, Literal, Stmt, TypeAnnotation};
-use crate::type_system::{EaType, TypeContext};
+use crate::ast::{BinaryOp, Expr, Literal, Parameter, Pattern, Stmt, TypeAnnotation};
+use crate::lexer::Span;
+use crate::type_system::TypeContext;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
@@ -26,6 +27,23 @@ pub struct ComptimeEngine {
     stats: ComptimeStats,
     /// Optimization database
     optimization_db: OptimizationDatabase,
+    /// Pending `return`, `break` or `continue` of the statement being executed
+    control: Option<ControlFlow>,
+    /// Nesting depth of compile-time function calls
+    call_depth: usize,
+}
+
+/// Upper bound on iterations of a single compile-time loop
+const MAX_LOOP_ITERATIONS: u64 = 10_000_000;
+/// Upper bound on nested compile-time function calls
+const MAX_CALL_DEPTH: usize = 256;
+
+/// Non-local control flow raised by a statement and consumed by the enclosing loop or call
+#[derive(Debug, Clone)]
+enum ControlFlow {
+    Return(ComptimeValue),
+    Break,
+    Continue,
 }
 
 /// Values that can be computed at compile time
@@ -75,6 +93,17 @@ pub enum ComptimeValue {
     },
 }
 
+impl ComptimeValue {
+    /// Elements of values that are laid out as a flat array: arrays and lookup tables.
+    pub fn array_elements(&self) -> Option<&[ComptimeValue]> {
+        match self {
+            ComptimeValue::Array(elements) => Some(elements),
+            ComptimeValue::LookupTable { data, .. } => Some(data),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AlgorithmType {
     Sort,
@@ -141,12 +170,42 @@ pub enum LookupTableType {
 #[derive(Debug, Clone)]
 pub struct ComptimeFunction {
     pub name: String,
-    pub parameters: Vec<(String, EaType)>,
-    pub return_type: EaType,
+    pub parameters: Vec<Parameter>,
+    pub return_type: Option<TypeAnnotation>,
     pub body: Vec<Stmt>,
     pub attributes: ComptimeAttributes,
 }
 
+impl ComptimeFunction {
+    /// Builds the compile-time form of a `comptime func` declaration.
+    pub fn from_declaration(
+        name: &str,
+        parameters: &[Parameter],
+        return_type: &Option<TypeAnnotation>,
+        body: &Stmt,
+    ) -> Self {
+        let body = match body {
+            Stmt::Block(statements, _) => statements.clone(),
+            other => vec![other.clone()],
+        };
+
+        Self {
+            name: name.to_string(),
+            parameters: parameters.to_vec(),
+            return_type: return_type.clone(),
+            body,
+            attributes: ComptimeAttributes {
+                pure: true,
+                deterministic: true,
+                memoizable: true,
+                complexity_bounds: None,
+                simd_friendly: false,
+                parallelizable: false,
+            },
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ComptimeAttributes {
     pub pure: bool,          // No side effects
@@ -294,9 +353,48 @@ impl ComptimeEngine {
             // memory_manager: placeholder removed
             stats: ComptimeStats::default(),
             optimization_db: OptimizationDatabase::new(),
+            control: None,
+            call_depth: 0,
+        }
+    }
+
+    /// Makes a named value, e.g. an already folded constant, visible to later evaluations
+    pub fn define_value(&mut self, name: &str, value: ComptimeValue) {
+        self.values.insert(name.to_string(), value);
+    }
+
+    /// Makes a `comptime func` callable from later evaluations
+    pub fn define_function(&mut self, function: ComptimeFunction) {
+        self.functions.insert(function.name.clone(), function);
+    }
+
+    /// Evaluates the statements of a `comptime { ... }` block.
+    ///
+    /// The block's value is the value of its last statement, or the value of
+    /// a `return` executed inside it.
+    pub fn evaluate_block(&mut self, statements: &[Stmt]) -> Result<ComptimeValue, ComptimeError> {
+        let value = self.execute_block(statements)?;
+        match self.control.take() {
+            Some(ControlFlow::Return(value)) => Ok(value),
+            Some(_) => Err(ComptimeError::CompilationError(
+                "'break' or 'continue' outside of a loop".to_string(),
+            )),
+            None => Ok(value),
         }
     }
 
+    /// Executes statements until one of them raises control flow.
+    fn execute_block(&mut self, statements: &[Stmt]) -> Result<ComptimeValue, ComptimeError> {
+        let mut last_value = ComptimeValue::Integer(0); // Unit value equivalent
+        for stmt in statements {
+            last_value = self.execute_statement(stmt)?;
+            if self.control.is_some() {
+                break;
+            }
+        }
+        Ok(last_value)
+    }
+
     /// Execute a statement at compile time
     pub fn execute_statement(&mut self, stmt: &Stmt) -> Result<ComptimeValue, ComptimeError> {
         let start_time = Instant::now();
@@ -304,51 +402,37 @@ impl ComptimeEngine {
 
         let result = match stmt {
             Stmt::VarDeclaration {
-                name,
+                pattern,
                 type_annotation: _,
                 initializer,
+                ..
             } => {
-                if let Some(value) = initializer {
-                    let computed_value = self.evaluate_expression(value)?;
-                    self.values.insert(name.clone(), computed_value.clone());
-                    Ok(computed_value)
-                } else {
+                let computed_value = match initializer {
+                    Some(value) => self.evaluate_expression(value)?,
                     // Uninitialized variable gets a default value
-                    let default_value = ComptimeValue::Integer(0);
-                    self.values.insert(name.clone(), default_value.clone());
-                    Ok(default_value)
-                }
+                    None => ComptimeValue::Integer(0),
+                };
+                self.bind_pattern(pattern, computed_value.clone())?;
+                Ok(computed_value)
             }
 
-            Stmt::Expression(expr) => self.evaluate_expression(expr),
-
-            Stmt::Block(statements) => {
-                let mut last_value = ComptimeValue::Integer(0); // Unit value equivalent
-                for stmt in statements {
-                    last_value = self.execute_statement(stmt)?;
-                }
-                Ok(last_value)
+            Stmt::ConstDeclaration { name, value, .. } => {
+                let computed_value = self.evaluate_expression(value)?;
+                self.values.insert(name.clone(), computed_value.clone());
+                Ok(computed_value)
             }
 
+            Stmt::Expression(expr, _) => self.evaluate_expression(expr),
+
+            Stmt::Block(statements, _) => self.execute_block(statements),
+
             Stmt::If {
                 condition,
                 then_branch,
                 else_branch,
+                ..
             } => {
-                let condition_value = self.evaluate_expression(condition)?;
-
-                let condition_bool = match condition_value {
-                    ComptimeValue::Boolean(b) => b,
-                    ComptimeValue::Integer(i) => i != 0,
-                    ComptimeValue::Float(f) => f != 0.0,
-                    _ => {
-                        return Err(ComptimeError::TypeMismatch(
-                            "Condition must be boolean or numeric".to_string(),
-                        ))
-                    }
-                };
-
-                if condition_bool {
+                if self.evaluate_condition(condition)? {
                     self.execute_statement(then_branch)
                 } else if let Some(else_stmt) = else_branch {
                     self.execute_statement(else_stmt)
@@ -357,12 +441,94 @@ impl ComptimeEngine {
                 }
             }
 
-            Stmt::Return(expr) => {
-                if let Some(expr) = expr {
-                    self.evaluate_expression(expr)
-                } else {
-                    Ok(ComptimeValue::Integer(0)) // Unit value
+            Stmt::While { condition, body, .. } => {
+                let mut iterations = 0;
+                while self.evaluate_condition(condition)? {
+                    Self::count_iteration(&mut iterations)?;
+                    self.execute_statement(body)?;
+                    if self.finish_iteration() {
+                        break;
+                    }
+                }
+                Ok(ComptimeValue::Integer(0))
+            }
+
+            Stmt::For {
+                initializer,
+                condition,
+                increment,
+                body,
+                ..
+            } => {
+                if let Some(initializer) = initializer {
+                    self.execute_statement(initializer)?;
                 }
+                let mut iterations = 0;
+                loop {
+                    if let Some(condition) = condition {
+                        if !self.evaluate_condition(condition)? {
+                            break;
+                        }
+                    }
+                    Self::count_iteration(&mut iterations)?;
+                    self.execute_statement(body)?;
+                    if self.finish_iteration() {
+                        break;
+                    }
+                    if let Some(increment) = increment {
+                        self.evaluate_expression(increment)?;
+                    }
+                }
+                Ok(ComptimeValue::Integer(0))
+            }
+
+            Stmt::ForIn {
+                variable,
+                iterable,
+                body,
+                ..
+            } => {
+                let elements = match self.evaluate_expression(iterable)? {
+                    ComptimeValue::SIMDVector { elements, .. } => elements,
+                    other => other.array_elements().map(<[_]>::to_vec).ok_or_else(|| {
+                        ComptimeError::TypeMismatch(format!("Cannot iterate over {:?}", other))
+                    })?,
+                };
+                for element in elements {
+                    self.values.insert(variable.clone(), element);
+                    self.execute_statement(body)?;
+                    if self.finish_iteration() {
+                        break;
+                    }
+                }
+                Ok(ComptimeValue::Integer(0))
+            }
+
+            Stmt::Break(None, _) => {
+                self.control = Some(ControlFlow::Break);
+                Ok(ComptimeValue::Integer(0))
+            }
+
+            Stmt::Continue(None, _) => {
+                self.control = Some(ControlFlow::Continue);
+                Ok(ComptimeValue::Integer(0))
+            }
+
+            Stmt::Break(Some(label), _) | Stmt::Continue(Some(label), _) => Err(
+                ComptimeError::UnsupportedExpression(format!(
+                    "labeled loop control '{}' at compile time",
+                    label
+                )),
+            ),
+
+            Stmt::Return(expr, _) => {
+                let value = if let Some(expr) = expr {
+                    self.evaluate_expression(expr)?
+                } else {
+                    ComptimeValue::Integer(0) // Unit value
+                };
+                self.control = Some(ControlFlow::Return(value.clone()));
+                Ok(value)
             }
 
             _ => {
@@ -376,41 +542,113 @@ impl ComptimeEngine {
         result
     }
 
+    /// Evaluates a loop or `if` condition.
+    fn evaluate_condition(&mut self, condition: &Expr) -> Result<bool, ComptimeError> {
+        match self.evaluate_expression(condition)? {
+            ComptimeValue::Boolean(b) => Ok(b),
+            ComptimeValue::Integer(i) => Ok(i != 0),
+            ComptimeValue::Float(f) => Ok(f != 0.0),
+            _ => Err(ComptimeError::TypeMismatch(
+                "Condition must be boolean or numeric".to_string(),
+            )),
+        }
+    }
+
+    /// Bounds compile-time loops so a non-terminating loop fails the build instead of hanging it.
+    fn count_iteration(iterations: &mut u64) -> Result<(), ComptimeError> {
+        *iterations += 1;
+        if *iterations > MAX_LOOP_ITERATIONS {
+            return Err(ComptimeError::ExecutionTimeout);
+        }
+        Ok(())
+    }
+
+    /// Consumes `break`/`continue` after a loop body; returns true if the loop must stop.
+    fn finish_iteration(&mut self) -> bool {
+        match self.control {
+            Some(ControlFlow::Break) => {
+                self.control = None;
+                true
+            }
+            Some(ControlFlow::Continue) => {
+                self.control = None;
+                false
+            }
+            Some(ControlFlow::Return(_)) => true,
+            None => false,
+        }
+    }
+
     /// Evaluate an expression at compile time
     pub fn evaluate_expression(&mut self, expr: &Expr) -> Result<ComptimeValue, ComptimeError> {
         match expr {
-            Expr::Literal(lit) => match lit {
-                Literal::Integer(value) => Ok(ComptimeValue::Integer(*value)),
-                Literal::Float(value) => Ok(ComptimeValue::Float(*value)),
-                Literal::Boolean(value) => Ok(ComptimeValue::Boolean(*value)),
-                Literal::String(value) => Ok(ComptimeValue::String(value.clone())),
-                Literal::Vector {
-                    elements,
-                    vector_type,
-                } => {
-                    let computed_elements: Result<Vec<_>, _> = elements
-                        .iter()
-                        .map(|elem| self.evaluate_literal(elem))
-                        .collect();
-                    let computed_elements = computed_elements?;
-
-                    Ok(ComptimeValue::SIMDVector {
-                        elements: computed_elements,
-                        vector_type: vector_type
-                            .as_ref()
-                            .map(|vt| format!("{:?}", vt))
-                            .unwrap_or_default(),
-                        width: elements.len(),
-                    })
+            Expr::Literal(lit, _) => self.evaluate_literal(lit),
+
+            Expr::Grouping(inner, _) => self.evaluate_expression(inner),
+
+            Expr::Comptime(statements, _) => self.evaluate_block(statements),
+
+            Expr::Block(statements, _) => self.execute_block(statements),
+
+            Expr::Tuple { elements, .. } => {
+                let mut values = Vec::new();
+                for element in elements {
+                    values.push(self.evaluate_expression(element)?);
                 }
+                Ok(ComptimeValue::Tuple(values))
+            }
+
+            Expr::FieldAccess(object, field, _) => match self.evaluate_expression(object)? {
+                ComptimeValue::Tuple(elements) => field
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|index| elements.get(index).cloned())
+                    .ok_or_else(|| {
+                        ComptimeError::CompilationError(format!("Tuple has no field '{}'", field))
+                    }),
+                other => Err(ComptimeError::TypeMismatch(format!(
+                    "Cannot access field '{}' of {:?}",
+                    field, other
+                ))),
             },
 
-            Expr::Variable(name) => self.values.get(name).cloned().ok_or_else(|| {
+            Expr::ArrayRepeat {
+                value,
+                count,
+                vector_type: None,
+                ..
+            } => {
+                let value = self.evaluate_expression(value)?;
+                match self.evaluate_expression(count)? {
+                    ComptimeValue::Integer(count) if count >= 0 => {
+                        Ok(ComptimeValue::Array(vec![value; count as usize]))
+                    }
+                    other => Err(ComptimeError::TypeMismatch(format!(
+                        "Array length must be a non-negative integer, got {:?}",
+                        other
+                    ))),
+                }
+            }
+
+            Expr::Binary(target, operator, value, _)
+                if matches!(
+                    operator,
+                    BinaryOp::Assign
+                        | BinaryOp::PlusAssign
+                        | BinaryOp::MinusAssign
+                        | BinaryOp::MultiplyAssign
+                        | BinaryOp::DivideAssign
+                ) =>
+            {
+                self.evaluate_assignment(target, operator, value)
+            }
+
+            Expr::Variable(name, _) => self.values.get(name).cloned().ok_or_else(|| {
                 ComptimeError::UnknownFunction(format!("Variable '{}' not found", name))
             }),
 
-            Expr::Call(func, args) => {
-                if let Expr::Variable(name) = func.as_ref() {
+            Expr::Call(func, args, _) => {
+                if let Expr::Variable(name, _) = func.as_ref() {
                     self.evaluate_function_call(name, args)
                 } else {
                     Err(ComptimeError::UnsupportedExpression(
@@ -419,18 +657,18 @@ impl ComptimeEngine {
                 }
             }
 
-            Expr::Binary(left, operator, right) => {
+            Expr::Binary(left, operator, right, _) => {
                 let left_val = self.evaluate_expression(left)?;
                 let right_val = self.evaluate_expression(right)?;
-                self.evaluate_binary_operation(&left_val, &format!("{:?}", operator), &right_val)
+                self.evaluate_binary_operation(&left_val, &operator.to_string(), &right_val)
             }
 
-            Expr::Unary(operator, operand) => {
+            Expr::Unary(operator, operand, _) => {
                 let operand_val = self.evaluate_expression(operand)?;
-                self.evaluate_unary_operation(&format!("{:?}", operator), &operand_val)
+                self.evaluate_unary_operation(&operator.to_string(), &operand_val)
             }
 
-            Expr::Index(array, index) => {
+            Expr::Index(array, index, _) => {
                 let array_val = self.evaluate_expression(array)?;
                 let index_val = self.evaluate_expression(index)?;
 
@@ -444,7 +682,8 @@ impl ComptimeEngine {
                 };
 
                 match array_val {
-                    ComptimeValue::Array(elements) => {
+                    ComptimeValue::Array(elements)
+                    | ComptimeValue::LookupTable { data: elements, .. } => {
                         elements.get(index_num).cloned().ok_or_else(|| {
                             ComptimeError::CompilationError("Array index out of bounds".to_string())
                         })
@@ -466,6 +705,164 @@ impl ComptimeEngine {
         }
     }
 
+    /// Binds the variables of a `let` pattern, destructuring tuple values.
+    fn bind_pattern(
+        &mut self,
+        pattern: &Pattern,
+        value: ComptimeValue,
+    ) -> Result<(), ComptimeError> {
+        match (pattern, value) {
+            (Pattern::Variable(name), value) => {
+                self.values.insert(name.clone(), value);
+                Ok(())
+            }
+            (Pattern::Wildcard, _) => Ok(()),
+            (Pattern::Tuple { patterns }, ComptimeValue::Tuple(elements))
+                if patterns.len() == elements.len() =>
+            {
+                for (pattern, element) in patterns.iter().zip(elements) {
+                    self.bind_pattern(pattern, element)?;
+                }
+                Ok(())
+            }
+            (pattern, value) => Err(ComptimeError::TypeMismatch(format!(
+                "Cannot bind {:?} to pattern '{}'",
+                value, pattern
+            ))),
+        }
+    }
+
+    /// Assigns to a variable or an element of an array variable: `x = v`, `t[i] += v`.
+    fn evaluate_assignment(
+        &mut self,
+        target: &Expr,
+        operator: &BinaryOp,
+        value: &Expr,
+    ) -> Result<ComptimeValue, ComptimeError> {
+        let mut new_value = self.evaluate_expression(value)?;
+        let compound = match operator {
+            BinaryOp::PlusAssign => Some("+"),
+            BinaryOp::MinusAssign => Some("-"),
+            BinaryOp::MultiplyAssign => Some("*"),
+            BinaryOp::DivideAssign => Some("/"),
+            _ => None,
+        };
+
+        match target {
+            Expr::Variable(name, _) => {
+                if let Some(compound) = compound {
+                    let current = self.evaluate_expression(target)?;
+                    new_value = self.evaluate_binary_operation(&current, compound, &new_value)?;
+                }
+                self.values.insert(name.clone(), new_value.clone());
+                Ok(new_value)
+            }
+            Expr::Index(array, index, _) => {
+                let name = match array.as_ref() {
+                    Expr::Variable(name, _) => name,
+                    _ => {
+                        return Err(ComptimeError::UnsupportedExpression(format!(
+                            "Cannot assign to {}",
+                            target
+                        )))
+                    }
+                };
+                let index = match self.evaluate_expression(index)? {
+                    ComptimeValue::Integer(i) if i >= 0 => i as usize,
+                    _ => {
+                        return Err(ComptimeError::TypeMismatch(
+                            "Index must be a non-negative integer".to_string(),
+                        ))
+                    }
+                };
+
+                let mut array_value = self.evaluate_expression(array)?;
+                let elements = match &mut array_value {
+                    ComptimeValue::Array(elements)
+                    | ComptimeValue::LookupTable { data: elements, .. }
+                    | ComptimeValue::SIMDVector { elements, .. } => elements,
+                    _ => {
+                        return Err(ComptimeError::TypeMismatch(format!(
+                            "Cannot index into '{}'",
+                            name
+                        )))
+                    }
+                };
+                let slot = elements.get_mut(index).ok_or_else(|| {
+                    ComptimeError::CompilationError("Array index out of bounds".to_string())
+                })?;
+                if let Some(compound) = compound {
+                    new_value = self.evaluate_binary_operation(slot, compound, &new_value)?;
+                }
+                *slot = new_value.clone();
+
+                self.values.insert(name.clone(), array_value);
+                Ok(new_value)
+            }
+            _ => Err(ComptimeError::UnsupportedExpression(format!(
+                "Cannot assign to {}",
+                target
+            ))),
+        }
+    }
+
+    /// Calls a `comptime func` with the caller's values plus its parameters in scope.
+    fn call_comptime_function(
+        &mut self,
+        function: &ComptimeFunction,
+        args: &[Expr],
+    ) -> Result<ComptimeValue, ComptimeError> {
+        if args.len() != function.parameters.len() {
+            return Err(ComptimeError::CompilationError(format!(
+                "Function '{}' expects {} arguments, got {}",
+                function.name,
+                function.parameters.len(),
+                args.len()
+            )));
+        }
+        if self.call_depth >= MAX_CALL_DEPTH {
+            return Err(ComptimeError::CompilationError(format!(
+                "Compile-time call depth of {} exceeded in '{}'",
+                MAX_CALL_DEPTH, function.name
+            )));
+        }
+
+        let mut arguments = Vec::with_capacity(args.len());
+        for (param, arg) in function.parameters.iter().zip(args) {
+            let value = self.evaluate_expression(arg)?;
+            arguments.push(Self::coerce_to_annotation(value, &param.type_annotation));
+        }
+
+        let saved_values = self.values.clone();
+        for (param, value) in function.parameters.iter().zip(arguments) {
+            self.values.insert(param.name.clone(), value);
+        }
+
+        self.call_depth += 1;
+        let result = self.execute_block(&function.body);
+        self.call_depth -= 1;
+        let control = self.control.take();
+        self.values = saved_values;
+        result?;
+
+        let value = match control {
+            Some(ControlFlow::Return(value)) => value,
+            _ => ComptimeValue::Integer(0), // Unit value
+        };
+        Ok(match &function.return_type {
+            Some(return_type) => Self::coerce_to_annotation(value, return_type),
+            None => value,
+        })
+    }
+
+    /// Converts integers passed or returned as floats, as the type checker allows.
+    fn coerce_to_annotation(value: ComptimeValue, annotation: &TypeAnnotation) -> ComptimeValue {
+        match (value, annotation.name.as_str()) {
+            (ComptimeValue::Integer(n), "f32" | "f64") => ComptimeValue::Float(n as f64),
+            (value, _) => value,
+        }
+    }
+
     /// Evaluate unary operations
     fn evaluate_unary_operation(
         &mut self,
@@ -514,6 +911,11 @@ impl ComptimeEngine {
                     .collect();
                 let computed_elements = computed_elements?;
 
+                // Without a SIMD type the literal is a plain array
+                if vector_type.is_none() {
+                    return Ok(ComptimeValue::Array(computed_elements));
+                }
+
                 Ok(ComptimeValue::SIMDVector {
                     elements: computed_elements,
                     vector_type: vector_type
@@ -635,12 +1037,17 @@ impl ComptimeEngine {
                 // Generate unrolled statements
                 for i in 0..*count {
                     let stmt = Stmt::VarDeclaration {
-                        name: format!("unrolled_var_{}", i),
+                        pattern: Pattern::Variable(format!("unrolled_var_{}", i)),
+                        is_mutable: false,
                         type_annotation: Some(TypeAnnotation {
                             name: "i32".to_string(),
                             is_mutable: false,
                         }),
-                        initializer: Some(Expr::Literal(Literal::Integer(i as i64))),
+                        initializer: Some(Expr::Literal(
+                            Literal::Integer(i as i64),
+                            Span::default(),
+                        )),
+                        span: Span::default(),
                     };
                     specialized_statements.push(stmt);
                 }
@@ -652,15 +1059,20 @@ impl ComptimeEngine {
             if let Some(ComptimeValue::Integer(width)) = parameters.get("vector_width") {
                 // Generate vectorized operations
                 let stmt = Stmt::VarDeclaration {
-                    name: "vectorized_op".to_string(),
+                    pattern: Pattern::Variable("vectorized_op".to_string()),
+                    is_mutable: false,
                     type_annotation: Some(TypeAnnotation {
                         name: format!("f32x{}", width),
                         is_mutable: false,
                     }),
-                    initializer: Some(Expr::Literal(Literal::Vector {
-                        elements: vec![Literal::Float(1.0); *width as usize],
-                        vector_type: None,
-                    })),
+                    initializer: Some(Expr::Literal(
+                        Literal::Vector {
+                            elements: vec![Literal::Float(1.0); *width as usize],
+                            vector_type: None,
+                        },
+                        Span::default(),
+                    )),
+                    span: Span::default(),
                 };
                 specialized_statements.push(stmt);
             }
@@ -672,12 +1084,14 @@ impl ComptimeEngine {
             for (param_name, param_value) in parameters {
                 if let ComptimeValue::Integer(value) = param_value {
                     let stmt = Stmt::VarDeclaration {
-                        name: format!("const_{}", param_name),
+                        pattern: Pattern::Variable(format!("const_{}", param_name)),
+                        is_mutable: false,
                         type_annotation: Some(TypeAnnotation {
                             name: "i32".to_string(),
                             is_mutable: false,
                         }),
-                        initializer: Some(Expr::Literal(Literal::Integer(*value))),
+                        initializer: Some(Expr::Literal(Literal::Integer(*value), Span::default())),
+                        span: Span::default(),
                     };
                     specialized_statements.push(stmt);
                 }
@@ -972,7 +1386,10 @@ impl ComptimeEngine {
                 Ok(ComptimeValue::Float(result))
             }
 
-            _ => Err(ComptimeError::UnknownFunction(name.to_string())),
+            _ => match self.functions.get(name).cloned() {
+                Some(function) => self.call_comptime_function(&function, args),
+                None => Err(ComptimeError::UnknownFunction(name.to_string())),
+            },
         }
     }
 
@@ -1700,7 +2117,7 @@ mod tests {
     #[test]
     fn test_integer_evaluation() {
         let mut engine = ComptimeEngine::new();
-        let expr = Expr::Literal(Literal::Integer(42));
+        let expr = Expr::Literal(Literal::Integer(42), Span::default());
         let result = engine.evaluate_expression(&expr).unwrap();
 
         match result {
@@ -1709,6 +2126,115 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_constant_folding() {
+        let mut engine = ComptimeEngine::new();
+        engine.define_value("LANES", ComptimeValue::Integer(8));
+
+        // -(LANES * 2) + 1
+        let span = Span::default();
+        let expr = Expr::Binary(
+            Box::new(Expr::Unary(
+                crate::ast::UnaryOp::Negate,
+                Box::new(Expr::Grouping(
+                    Box::new(Expr::Binary(
+                        Box::new(Expr::Variable("LANES".to_string(), span.clone())),
+                        crate::ast::BinaryOp::Multiply,
+                        Box::new(Expr::Literal(Literal::Integer(2), span.clone())),
+                        span.clone(),
+                    )),
+                    span.clone(),
+                )),
+                span.clone(),
+            )),
+            crate::ast::BinaryOp::Add,
+            Box::new(Expr::Literal(Literal::Integer(1), span.clone())),
+            span,
+        );
+
+        match engine.evaluate_expression(&expr).unwrap() {
+            ComptimeValue::Integer(value) => assert_eq!(value, -15),
+            other => panic!("Expected integer value, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_comptime_function_call() {
+        let program = crate::parse(
+            "comptime func factorial(n: i32) -> i32 {
+                let mut result: i32 = 1;
+                for (let mut i: i32 = 2; i <= n; i += 1) {
+                    result *= i;
+                }
+                return result;
+            }
+            func main() -> i32 {
+                return comptime { let table = [0; 4]; table[1] = factorial(5); table[1] + 1; };
+            }",
+        )
+        .unwrap();
+
+        let mut engine = ComptimeEngine::new();
+        if let Stmt::FunctionDeclaration {
+            name,
+            params,
+            return_type,
+            body,
+            ..
+        } = &program[0]
+        {
+            engine.define_function(ComptimeFunction::from_declaration(
+                name,
+                params,
+                return_type,
+                body,
+            ));
+        }
+
+        let block = match &program[1] {
+            Stmt::FunctionDeclaration { body, .. } => match body.as_ref() {
+                Stmt::Block(stmts, _) => match &stmts[0] {
+                    Stmt::Return(Some(expr), _) => expr.clone(),
+                    other => panic!("Expected return, got {:?}", other),
+                },
+                other => panic!("Expected block, got {:?}", other),
+            },
+            other => panic!("Expected function, got {:?}", other),
+        };
+
+        match engine.evaluate_expression(&block).unwrap() {
+            ComptimeValue::Integer(value) => assert_eq!(value, 121),
+            other => panic!("Expected integer value, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_comptime_tuples() {
+        let program = crate::parse(
+            "let x = comptime { let pair = (6, (7, true)); let (a, (b, _)) = pair; (a * b, pair.1.1); };",
+        )
+        .unwrap();
+        let block = match &program[0] {
+            Stmt::VarDeclaration {
+                initializer: Some(expr),
+                ..
+            } => expr.clone(),
+            other => panic!("Expected variable declaration, got {:?}", other),
+        };
+
+        let mut engine = ComptimeEngine::new();
+        match engine.evaluate_expression(&block).unwrap() {
+            ComptimeValue::Tuple(elements) => match elements.as_slice() {
+                [ComptimeValue::Integer(product), ComptimeValue::Boolean(flag)] => {
+                    assert_eq!(*product, 42);
+                    assert!(*flag);
+                }
+                other => panic!("Unexpected tuple elements {:?}", other),
+            },
+            other => panic!("Expected tuple value, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_lookup_table_generation() {
         let mut engine = ComptimeEngine::new();
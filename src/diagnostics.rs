@@ -0,0 +1,751 @@
+//! Compiler diagnostics: error codes, labelled source snippets and suggestions.
+//!
+//! A [`Diagnostic`] is built from a [`CompileError`] and rendered either as a
+//! rustc-style snippet with carets under the offending code, or as one JSON
+//! object per line for CI annotations (`--error-format=json`).
+
+use crate::ast::Stmt;
+use crate::error::{CompileError, TypeErrorKind};
+use crate::lexer::{Position, Span};
+use crate::parser::{Parser, KEYWORDS};
+use serde_json::{json, Value};
+use std::fmt::{self, Write};
+
+/// Stable diagnostic codes, so tooling can match on the kind of error rather than its wording.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    /// E0001: a character sequence that is not a valid token
+    InvalidToken,
+    /// E0002: source that does not match the grammar
+    SyntaxError,
+    /// E0003: a type mismatch or other type checking failure
+    TypeMismatch,
+    /// E0004: use of an undeclared variable
+    UndefinedVariable,
+    /// E0005: call of an undeclared function
+    UndefinedFunction,
+    /// E0006: use of an undeclared struct, enum, trait or type
+    UndefinedType,
+    /// E0007: an unknown field, method or enum variant
+    UnknownMember,
+    /// E0008: a failure while generating LLVM IR or native code
+    CodeGeneration,
+    /// E0009: a compilation resource limit was exceeded
+    ResourceExhausted,
+}
+
+impl ErrorCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::InvalidToken => "E0001",
+            ErrorCode::SyntaxError => "E0002",
+            ErrorCode::TypeMismatch => "E0003",
+            ErrorCode::UndefinedVariable => "E0004",
+            ErrorCode::UndefinedFunction => "E0005",
+            ErrorCode::UndefinedType => "E0006",
+            ErrorCode::UnknownMember => "E0007",
+            ErrorCode::CodeGeneration => "E0008",
+            ErrorCode::ResourceExhausted => "E0009",
+        }
+    }
+
+    /// The code of a type checking error of kind `kind`.
+    fn for_type_error(kind: TypeErrorKind) -> Self {
+        match kind {
+            TypeErrorKind::Mismatch => ErrorCode::TypeMismatch,
+            TypeErrorKind::UndefinedVariable => ErrorCode::UndefinedVariable,
+            TypeErrorKind::UndefinedFunction => ErrorCode::UndefinedFunction,
+            TypeErrorKind::UndefinedType => ErrorCode::UndefinedType,
+            TypeErrorKind::UnknownMember => ErrorCode::UnknownMember,
+        }
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// How serious a diagnostic is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+    Note,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Note => "note",
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A message attached to a source range. The primary label marks the
+/// offending code with `^`; secondary labels add context with `-`.
+#[derive(Debug, Clone)]
+pub struct Label {
+    pub span: Span,
+    pub message: String,
+    pub primary: bool,
+}
+
+impl Label {
+    pub fn primary(span: Span, message: String) -> Self {
+        Self {
+            span,
+            message,
+            primary: true,
+        }
+    }
+
+    pub fn secondary(span: Span, message: String) -> Self {
+        Self {
+            span,
+            message,
+            primary: false,
+        }
+    }
+}
+
+/// A single reported problem, with everything needed to render it.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub code: Option<ErrorCode>,
+    pub severity: Severity,
+    pub message: String,
+    pub labels: Vec<Label>,
+    pub notes: Vec<String>,
+    pub help: Vec<String>,
+}
+
+impl Diagnostic {
+    pub fn new(severity: Severity, message: String) -> Self {
+        Self {
+            code: None,
+            severity,
+            message,
+            labels: Vec::new(),
+            notes: Vec::new(),
+            help: Vec::new(),
+        }
+    }
+
+    pub fn error(message: String) -> Self {
+        Self::new(Severity::Error, message)
+    }
+
+    pub fn with_code(mut self, code: ErrorCode) -> Self {
+        self.code = Some(code);
+        self
+    }
+
+    /// Adds a label; labels without a known source location are dropped.
+    pub fn with_label(mut self, label: Label) -> Self {
+        if label.span.is_known() {
+            self.labels.push(label);
+        }
+        self
+    }
+
+    pub fn with_note(mut self, note: String) -> Self {
+        self.notes.push(note);
+        self
+    }
+
+    pub fn with_help(mut self, help: String) -> Self {
+        self.help.push(help);
+        self
+    }
+
+    /// Builds the diagnostic for `error`. `source` is the text the error's
+    /// positions refer to; it sizes the underline of errors that only carry
+    /// a start position.
+    pub fn from_error(error: &CompileError, source: &str) -> Self {
+        match error {
+            CompileError::LexError { message, position } => Diagnostic::error(message.clone())
+                .with_code(ErrorCode::InvalidToken)
+                .with_label(Label::primary(token_span(source, position), String::new())),
+            CompileError::ParseError { message, position } => {
+                let mut diagnostic = Diagnostic::error(message.clone())
+                    .with_code(ErrorCode::SyntaxError)
+                    .with_label(Label::primary(token_span(source, position), String::new()));
+                if let Some(keyword) = mistyped_keyword(source, position) {
+                    diagnostic = diagnostic.with_help(format!("did you mean `{}`?", keyword));
+                }
+                for suggestion in Parser::suggest_fixes(error) {
+                    diagnostic = match suggestion.suggested_fix {
+                        Some(fix) => diagnostic.with_help(fix),
+                        None => diagnostic.with_note(suggestion.message),
+                    };
+                }
+                diagnostic
+            }
+            CompileError::TypeError {
+                message,
+                position,
+                end,
+                kind,
+            } => {
+                let code = ErrorCode::for_type_error(*kind);
+                let label = match code {
+                    ErrorCode::UndefinedVariable
+                    | ErrorCode::UndefinedFunction
+                    | ErrorCode::UndefinedType => "not found in this scope",
+                    ErrorCode::UnknownMember => "not found",
+                    _ => "",
+                };
+                Diagnostic::error(message.clone())
+                    .with_code(code)
+                    .with_label(Label::primary(
+                        error_span(source, position, end),
+                        label.to_string(),
+                    ))
+            }
+            CompileError::CodeGenError {
+                message,
+                position,
+                end,
+            } => {
+                let diagnostic =
+                    Diagnostic::error(message.clone()).with_code(ErrorCode::CodeGeneration);
+                match position {
+                    Some(position) => diagnostic.with_label(Label::primary(
+                        error_span(source, position, end),
+                        String::new(),
+                    )),
+                    None => diagnostic,
+                }
+            }
+            CompileError::MemoryExhausted { phase, details } => {
+                Diagnostic::error(format!("memory exhausted during {}", phase))
+                    .with_code(ErrorCode::ResourceExhausted)
+                    .with_note(details.clone())
+            }
+        }
+    }
+
+    /// Builds the diagnostic for a problem that does not stop compilation.
+    pub fn warning_from_error(error: &CompileError, source: &str) -> Self {
+        let mut diagnostic = Self::from_error(error, source);
+        diagnostic.severity = Severity::Warning;
+        diagnostic.code = None;
+        diagnostic
+    }
+
+    /// For an undefined name, suggests the closest name `program` declares
+    /// and points at its declaration.
+    pub fn suggest_declared_names(self, program: &[Stmt]) -> Self {
+        let namespace = match self.code {
+            Some(ErrorCode::UndefinedVariable) => Namespace::Value,
+            Some(ErrorCode::UndefinedFunction) => Namespace::Function,
+            Some(ErrorCode::UndefinedType) => Namespace::Type,
+            _ => return self,
+        };
+        let name = match quoted_name(&self.message) {
+            Some(name) => name.to_string(),
+            None => return self,
+        };
+
+        let mut declarations = Vec::new();
+        collect_declarations(program, &mut declarations);
+        let candidates = declarations
+            .iter()
+            .filter(|declaration| declaration.namespace == namespace);
+        let similar = match did_you_mean(&name, candidates.clone().map(|d| d.name.as_str())) {
+            Some(similar) => similar.to_string(),
+            None => return self,
+        };
+
+        let mut diagnostic = self;
+        if let Some(declaration) = candidates.into_iter().find(|d| d.name == similar) {
+            diagnostic = diagnostic.with_label(Label::secondary(
+                declaration.span.clone(),
+                format!("`{}` declared here", similar),
+            ));
+        }
+        diagnostic.with_help(format!("did you mean `{}`?", similar))
+    }
+
+    /// Renders the diagnostic rustc-style, quoting the lines of `source` its
+    /// labels point at. An empty `filename` leaves out the location line.
+    pub fn render(&self, filename: &str, source: &str) -> String {
+        let mut out = match self.code {
+            Some(code) => format!("{}[{}]: {}\n", self.severity, code, self.message),
+            None => format!("{}: {}\n", self.severity, self.message),
+        };
+
+        let lines: Vec<&str> = source.lines().collect();
+        let mut labels: Vec<&Label> = self
+            .labels
+            .iter()
+            .filter(|label| label.span.is_known() && label.span.start.line <= lines.len())
+            .collect();
+        labels.sort_by_key(|label| (label.span.start.line, label.span.start.column));
+
+        let gutter = labels
+            .iter()
+            .map(|label| label.span.start.line.to_string().len())
+            .max()
+            .unwrap_or(0);
+        let pad = " ".repeat(gutter);
+
+        let location = self
+            .labels
+            .iter()
+            .find(|label| label.primary)
+            .or_else(|| self.labels.first());
+        if let Some(label) = location.filter(|_| !filename.is_empty()) {
+            let start = &label.span.start;
+            writeln!(
+                out,
+                "{}--> {}:{}:{}",
+                pad, filename, start.line, start.column
+            )
+            .unwrap();
+        }
+
+        if !labels.is_empty() {
+            writeln!(out, "{} |", pad).unwrap();
+            let mut previous_line: Option<usize> = None;
+            for label in &labels {
+                let line_number = label.span.start.line;
+                let text = lines[line_number - 1];
+                if previous_line != Some(line_number) {
+                    if previous_line.map_or(false, |previous| line_number > previous + 1) {
+                        writeln!(out, "...").unwrap();
+                    }
+                    writeln!(out, "{:>width$} | {}", line_number, text, width = gutter).unwrap();
+                    previous_line = Some(line_number);
+                }
+                let (indent, width) = underline(text, &label.span);
+                let mark = if label.primary { "^" } else { "-" };
+                let underline = format!(
+                    "{} | {}{} {}",
+                    pad,
+                    indent,
+                    mark.repeat(width),
+                    label.message
+                );
+                writeln!(out, "{}", underline.trim_end()).unwrap();
+            }
+            if !self.notes.is_empty() || !self.help.is_empty() {
+                writeln!(out, "{} |", pad).unwrap();
+            }
+        }
+
+        for note in &self.notes {
+            writeln!(out, "{} = note: {}", pad, note).unwrap();
+        }
+        for help in &self.help {
+            writeln!(out, "{} = help: {}", pad, help).unwrap();
+        }
+        out
+    }
+
+    /// The diagnostic as a JSON object, including its human-readable rendering.
+    pub fn to_json(&self, filename: &str, source: &str) -> Value {
+        let labels: Vec<Value> = self
+            .labels
+            .iter()
+            .map(|label| {
+                json!({
+                    "primary": label.primary,
+                    "message": label.message,
+                    "start": { "line": label.span.start.line, "column": label.span.start.column },
+                    "end": { "line": label.span.end.line, "column": label.span.end.column },
+                })
+            })
+            .collect();
+        json!({
+            "code": self.code.map(|code| code.as_str()),
+            "severity": self.severity.as_str(),
+            "message": self.message,
+            "file": filename,
+            "labels": labels,
+            "notes": self.notes,
+            "help": self.help,
+            "rendered": self.render(filename, source),
+        })
+    }
+}
+
+/// Output format for diagnostics, selected with `--error-format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorFormat {
+    /// Source snippets with carets, for people
+    #[default]
+    Human,
+    /// One JSON object per diagnostic and line, for CI annotations
+    Json,
+}
+
+impl ErrorFormat {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "human" => Some(ErrorFormat::Human),
+            "json" => Some(ErrorFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Renders every diagnostic in `format`. The human format ends with a
+/// summary line when errors were reported.
+pub fn render_report(
+    diagnostics: &[Diagnostic],
+    filename: &str,
+    source: &str,
+    format: ErrorFormat,
+) -> String {
+    let mut out = String::new();
+    match format {
+        ErrorFormat::Human => {
+            for diagnostic in diagnostics {
+                out.push_str(&diagnostic.render(filename, source));
+                out.push('\n');
+            }
+            let errors = diagnostics
+                .iter()
+                .filter(|diagnostic| diagnostic.severity == Severity::Error)
+                .count();
+            if errors > 0 {
+                let plural = if errors == 1 { "" } else { "s" };
+                writeln!(
+                    out,
+                    "error: aborting due to {} previous error{}",
+                    errors, plural
+                )
+                .unwrap();
+            }
+        }
+        ErrorFormat::Json => {
+            for diagnostic in diagnostics {
+                writeln!(out, "{}", diagnostic.to_json(filename, source)).unwrap();
+            }
+        }
+    }
+    out
+}
+
+/// The candidate closest to `name` by edit distance, if it is close enough
+/// to be a plausible typo.
+pub fn did_you_mean<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let threshold = std::cmp::max(1, (name.chars().count() + 2) / 3);
+    candidates
+        .into_iter()
+        .filter(|candidate| *candidate != name)
+        .map(|candidate| (Parser::levenshtein_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= threshold)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Which kind of name a declaration introduces
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Namespace {
+    Value,
+    Function,
+    Type,
+}
+
+struct Declaration {
+    name: String,
+    namespace: Namespace,
+    span: Span,
+}
+
+fn collect_declarations(statements: &[Stmt], declarations: &mut Vec<Declaration>) {
+    let mut declare = |name: &str, namespace, span: &Span| {
+        declarations.push(Declaration {
+            name: name.to_string(),
+            namespace,
+            span: span.clone(),
+        })
+    };
+    let mut nested = Vec::new();
+    for stmt in statements {
+        match stmt {
+            Stmt::VarDeclaration { pattern, span, .. } => {
+                for name in pattern.bound_names() {
+                    declare(name, Namespace::Value, span);
+                }
+            }
+            Stmt::ConstDeclaration { name, span, .. } => declare(name, Namespace::Value, span),
+            Stmt::FunctionDeclaration {
+                name,
+                params,
+                body,
+                span,
+                ..
+            } => {
+                declare(name, Namespace::Function, span);
+                for param in params {
+                    // Parameters carry no span of their own
+                    declare(&param.name, Namespace::Value, &Span::default());
+                }
+                nested.push(body.as_ref());
+            }
+            Stmt::StructDeclaration { name, span, .. }
+            | Stmt::EnumDeclaration { name, span, .. }
+            | Stmt::TraitDeclaration { name, span, .. } => declare(name, Namespace::Type, span),
+            Stmt::ExternBlock {
+                functions, span, ..
+            } => {
+                for function in functions {
+                    declare(&function.name, Namespace::Function, span);
+                }
+            }
+            Stmt::ForIn {
+                variable,
+                body,
+                span,
+                ..
+            } => {
+                declare(variable, Namespace::Value, span);
+                nested.push(body.as_ref());
+            }
+            Stmt::If {
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                nested.push(then_branch.as_ref());
+                nested.extend(else_branch.as_deref());
+            }
+            Stmt::For {
+                initializer, body, ..
+            } => {
+                nested.extend(initializer.as_deref());
+                nested.push(body.as_ref());
+            }
+            Stmt::While { body, .. } | Stmt::Labeled { body, .. } => nested.push(body.as_ref()),
+            Stmt::Block(statements, _) => nested.extend(statements.iter()),
+            Stmt::ImplBlock { methods, .. } => nested.extend(methods.iter()),
+            _ => {}
+        }
+    }
+    for stmt in nested {
+        collect_declarations(std::slice::from_ref(stmt), declarations);
+    }
+}
+
+/// The first `'quoted'` name in an error message
+fn quoted_name(message: &str) -> Option<&str> {
+    let start = message.find('\'')? + 1;
+    let end = start + message[start..].find('\'')?;
+    Some(&message[start..end])
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// The span of the token starting at `position`: a whole identifier or
+/// number, otherwise a single character.
+fn token_span(source: &str, position: &Position) -> Span {
+    if !position.is_known() {
+        return Span::default();
+    }
+    let rest = source
+        .lines()
+        .nth(position.line - 1)
+        .and_then(|line| line.get(position.column.saturating_sub(1)..))
+        .unwrap_or("");
+    let length = match rest.chars().next() {
+        Some(c) if is_identifier_char(c) => rest
+            .chars()
+            .take_while(|c| is_identifier_char(*c))
+            .map(char::len_utf8)
+            .sum(),
+        Some(c) => c.len_utf8(),
+        None => 1,
+    };
+    Span::new(
+        position.clone(),
+        Position::new(
+            position.line,
+            position.column + length,
+            position.offset + length,
+        ),
+    )
+}
+
+fn error_span(source: &str, position: &Position, end: &Option<Position>) -> Span {
+    match end {
+        Some(end) => Span::new(position.clone(), end.clone()),
+        None => token_span(source, position),
+    }
+}
+
+/// A keyword the user probably meant on the line of a syntax error: the word
+/// at the error, or the word that starts the line, where a mistyped `func`
+/// or `return` leaves an identifier the parser cannot continue from.
+fn mistyped_keyword(source: &str, position: &Position) -> Option<&'static str> {
+    if !position.is_known() {
+        return None;
+    }
+    let line = source.lines().nth(position.line - 1)?;
+    let first_word = line
+        .trim_start()
+        .split(|c: char| !is_identifier_char(c))
+        .next();
+    let error_word = line
+        .get(position.column.saturating_sub(1)..)
+        .and_then(|rest| rest.split(|c: char| !is_identifier_char(c)).next());
+
+    [error_word, first_word]
+        .into_iter()
+        .flatten()
+        .filter(|word| {
+            word.starts_with(|c: char| c.is_alphabetic() || c == '_') && !KEYWORDS.contains(word)
+        })
+        .find_map(|word| did_you_mean(word, KEYWORDS.iter().copied()))
+}
+
+/// Indentation and width, in characters, of the underline for `span` on `text`.
+/// Spans that continue past the line are underlined to its end.
+fn underline(text: &str, span: &Span) -> (String, usize) {
+    let start = span.start.column.saturating_sub(1).min(text.len());
+    let prefix = text.get(..start).unwrap_or(text);
+    let indent = prefix
+        .chars()
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+
+    let end = if span.end.line == span.start.line && span.end.column > span.start.column {
+        (span.end.column - 1).min(text.len())
+    } else {
+        text.len()
+    };
+    let width = text
+        .get(start..end)
+        .map(|underlined| underlined.chars().count())
+        .unwrap_or(0);
+    (indent, width.max(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_error(
+        kind: TypeErrorKind,
+        message: &str,
+        start: (usize, usize),
+        end: (usize, usize),
+    ) -> CompileError {
+        CompileError::TypeError {
+            message: message.to_string(),
+            position: Position::new(start.0, start.1, 0),
+            end: Some(Position::new(end.0, end.1, 0)),
+            kind,
+        }
+    }
+
+    #[test]
+    fn test_render_snippet_with_suggestion() {
+        let source = "func main() -> i32 {\n    let counter = 1;\n    return countr + 1;\n}\n";
+        let program = crate::parse(source).unwrap();
+        let error = type_error(
+            TypeErrorKind::UndefinedVariable,
+            "Variable 'countr' not found",
+            (3, 12),
+            (3, 18),
+        );
+
+        let rendered = Diagnostic::from_error(&error, source)
+            .suggest_declared_names(&program)
+            .render("main.ea", source);
+
+        assert_eq!(
+            rendered,
+            "error[E0004]: Variable 'countr' not found\n \
+             --> main.ea:3:12\n  \
+             |\n\
+             2 |     let counter = 1;\n  \
+             |     ---------------- `counter` declared here\n\
+             3 |     return countr + 1;\n  \
+             |            ^^^^^^ not found in this scope\n  \
+             |\n  \
+             = help: did you mean `counter`?\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_error_suggests_keyword() {
+        let source = "fucn main() -> i32 {\n    return 0;\n}\n";
+        let error = crate::parse(source).unwrap_err();
+        let diagnostic = Diagnostic::from_error(&error, source);
+
+        assert_eq!(diagnostic.code, Some(ErrorCode::SyntaxError));
+        assert!(diagnostic
+            .help
+            .contains(&"did you mean `func`?".to_string()));
+        assert_eq!(diagnostic.labels.len(), 1);
+        assert!(diagnostic.labels[0].primary);
+    }
+
+    #[test]
+    fn test_json_format() {
+        let source = "let x = 1;\n";
+        let error = type_error(TypeErrorKind::Mismatch, "Type mismatch", (1, 9), (1, 10));
+        let report = render_report(
+            &[Diagnostic::from_error(&error, source)],
+            "x.ea",
+            source,
+            ErrorFormat::Json,
+        );
+
+        let value: Value = serde_json::from_str(report.trim_end()).unwrap();
+        assert_eq!(value["code"], "E0003");
+        assert_eq!(value["severity"], "error");
+        assert_eq!(value["file"], "x.ea");
+        assert_eq!(value["labels"][0]["start"]["column"], 9);
+        assert_eq!(value["labels"][0]["end"]["column"], 10);
+        assert_eq!(ErrorFormat::parse("json"), Some(ErrorFormat::Json));
+        assert_eq!(ErrorFormat::parse("xml"), None);
+    }
+
+    #[test]
+    fn test_type_error_codes() {
+        let code = |source: &str| {
+            let error = crate::compile_to_ast(source).unwrap_err();
+            Diagnostic::from_error(&error, source).code
+        };
+
+        assert_eq!(
+            code("func main() -> i32 {\n    return missing(1);\n}\n"),
+            Some(ErrorCode::UndefinedFunction)
+        );
+        assert_eq!(
+            code("struct P {\n    x: i32,\n}\nfunc main() -> i32 {\n    let p = P { x: 1 };\n    return p.y;\n}\n"),
+            Some(ErrorCode::UnknownMember)
+        );
+        assert_eq!(
+            code("func main() -> i32 {\n    return true;\n}\n"),
+            Some(ErrorCode::TypeMismatch)
+        );
+    }
+
+    #[test]
+    fn test_did_you_mean() {
+        let names = ["counter", "total", "x"];
+        assert_eq!(did_you_mean("countr", names), Some("counter"));
+        assert_eq!(did_you_mean("totl", names), Some("total"));
+        assert_eq!(did_you_mean("velocity", names), None);
+    }
+}
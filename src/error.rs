@@ -1,6 +1,6 @@
 //! Error handling for the Eä compiler.
 
-use crate::lexer::Position;
+use crate::lexer::{Position, Span};
 use std::fmt;
 
 /// Result type for compiler operations.
@@ -13,17 +13,38 @@ pub enum CompileError {
     LexError { message: String, position: Position },
     /// Parsing error
     ParseError { message: String, position: Position },
-    /// Type checking error
-    TypeError { message: String, position: Position },
+    /// Type checking error; `end` closes the offending source range when it is known
+    TypeError {
+        message: String,
+        position: Position,
+        end: Option<Position>,
+        kind: TypeErrorKind,
+    },
     /// Code generation error
     CodeGenError {
         message: String,
         position: Option<Position>,
+        end: Option<Position>,
     },
     /// Memory exhaustion error
     MemoryExhausted { phase: String, details: String },
 }
 
+/// What a type error is about, which picks its diagnostic code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeErrorKind {
+    /// A type mismatch or other type checking failure
+    Mismatch,
+    /// Use of an undeclared variable
+    UndefinedVariable,
+    /// Call of an undeclared function
+    UndefinedFunction,
+    /// Use of an undeclared struct, enum, trait or type
+    UndefinedType,
+    /// An unknown field, method or enum variant
+    UnknownMember,
+}
+
 impl CompileError {
     /// Creates a new lexical error
     pub fn lex_error(message: String, position: Position) -> Self {
@@ -37,12 +58,76 @@ impl CompileError {
 
     /// Creates a new type error
     pub fn type_error(message: String, position: Position) -> Self {
-        Self::TypeError { message, position }
+        Self::type_error_of_kind(TypeErrorKind::Mismatch, message, position)
+    }
+
+    /// Creates a type error for the use of an undeclared variable
+    pub fn undefined_variable_error(message: String, position: Position) -> Self {
+        Self::type_error_of_kind(TypeErrorKind::UndefinedVariable, message, position)
+    }
+
+    /// Creates a type error for the call of an undeclared function
+    pub fn undefined_function_error(message: String, position: Position) -> Self {
+        Self::type_error_of_kind(TypeErrorKind::UndefinedFunction, message, position)
+    }
+
+    /// Creates a type error for the use of an undeclared struct, enum, trait or type
+    pub fn undefined_type_error(message: String, position: Position) -> Self {
+        Self::type_error_of_kind(TypeErrorKind::UndefinedType, message, position)
+    }
+
+    /// Creates a type error for an unknown field, method or enum variant
+    pub fn unknown_member_error(message: String, position: Position) -> Self {
+        Self::type_error_of_kind(TypeErrorKind::UnknownMember, message, position)
+    }
+
+    fn type_error_of_kind(kind: TypeErrorKind, message: String, position: Position) -> Self {
+        Self::TypeError {
+            message,
+            position,
+            end: None,
+            kind,
+        }
     }
 
     /// Creates a new code generation error
     pub fn codegen_error(message: String, position: Option<Position>) -> Self {
-        Self::CodeGenError { message, position }
+        Self::CodeGenError {
+            message,
+            position,
+            end: None,
+        }
+    }
+
+    /// Locates a type or code generation error at `span` if it has no source
+    /// location yet, so the innermost node that reports it wins.
+    pub fn with_span(self, span: Span) -> Self {
+        if !span.is_known() {
+            return self;
+        }
+        match self {
+            Self::TypeError {
+                message,
+                position,
+                kind,
+                ..
+            } if !position.is_known() => Self::TypeError {
+                message,
+                position: span.start,
+                end: Some(span.end),
+                kind,
+            },
+            Self::CodeGenError {
+                message,
+                position: None,
+                ..
+            } => Self::CodeGenError {
+                message,
+                position: Some(span.start),
+                end: Some(span.end),
+            },
+            other => other,
+        }
     }
 
     /// Creates a new memory exhaustion error
@@ -68,14 +153,18 @@ impl fmt::Display for CompileError {
                     position.line, position.column, message
                 )
             }
-            CompileError::TypeError { message, position } => {
+            CompileError::TypeError {
+                message, position, ..
+            } => {
                 write!(
                     f,
                     "Type error at {}:{}: {}",
                     position.line, position.column, message
                 )
             }
-            CompileError::CodeGenError { message, position } => {
+            CompileError::CodeGenError {
+                message, position, ..
+            } => {
                 if let Some(pos) = position {
                     write!(
                         f,
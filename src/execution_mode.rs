@@ -107,7 +107,7 @@ fn analyze_statement(stmt: &Stmt, analysis: &mut ComplexityAnalysis, depth: usiz
     analysis.max_recursion_depth = analysis.max_recursion_depth.max(depth);
     
     match stmt {
-        Stmt::FunctionDeclaration { name: _, params, return_type: _, body, attributes: _ } => {
+        Stmt::FunctionDeclaration { params, body, .. } => {
             analysis.function_count += 1;
             
             // Check if function has vector parameters (indicates SIMD function calls)
@@ -121,7 +121,7 @@ fn analyze_statement(stmt: &Stmt, analysis: &mut ComplexityAnalysis, depth: usiz
             analyze_statement(body, analysis, depth + 1);
         }
         
-        Stmt::If { condition, then_branch, else_branch } => {
+        Stmt::If { condition, then_branch, else_branch, .. } => {
             analyze_expression(condition, analysis);
             
             // Analyze branches
@@ -137,7 +137,7 @@ fn analyze_statement(stmt: &Stmt, analysis: &mut ComplexityAnalysis, depth: usiz
             }
         }
         
-        Stmt::While { condition, body } => {
+        Stmt::While { condition, body, .. } => {
             analyze_expression(condition, analysis);
             
             // While loops are complex control flow
@@ -146,7 +146,7 @@ fn analyze_statement(stmt: &Stmt, analysis: &mut ComplexityAnalysis, depth: usiz
             analyze_statement(body, analysis, depth + 1);
         }
         
-        Stmt::For { initializer, condition, increment, body } => {
+        Stmt::For { initializer, condition, increment, body, .. } => {
             if let Some(init) = initializer {
                 analyze_statement(init, analysis, depth + 1);
             }
@@ -165,7 +165,7 @@ fn analyze_statement(stmt: &Stmt, analysis: &mut ComplexityAnalysis, depth: usiz
             analysis.complex_control_flow = true;
         }
         
-        Stmt::ForIn { variable: _, iterable, body } => {
+        Stmt::ForIn { variable: _, iterable, body, .. } => {
             analyze_expression(iterable, analysis);
             analyze_statement(body, analysis, depth + 1);
             
@@ -173,7 +173,7 @@ fn analyze_statement(stmt: &Stmt, analysis: &mut ComplexityAnalysis, depth: usiz
             analysis.complex_control_flow = true;
         }
         
-        Stmt::VarDeclaration { pattern: _, type_annotation, initializer } => {
+        Stmt::VarDeclaration { pattern: _, type_annotation, initializer, .. } => {
             if let Some(type_ann) = type_annotation {
                 if is_vector_type(type_ann) {
                     analysis.has_simd_operations = true;
@@ -184,26 +184,41 @@ fn analyze_statement(stmt: &Stmt, analysis: &mut ComplexityAnalysis, depth: usiz
             }
         }
         
-        Stmt::Block(statements) => {
+        Stmt::Block(statements, _) => {
             for stmt in statements {
                 analyze_statement(stmt, analysis, depth + 1);
             }
         }
         
-        Stmt::Expression(expr) => {
+        Stmt::Expression(expr, _) => {
             analyze_expression(expr, analysis);
         }
         
-        Stmt::Return(Some(expr)) => {
+        Stmt::Return(Some(expr), _) => {
             analyze_expression(expr, analysis);
         }
         
-        Stmt::Return(None) | Stmt::Break | Stmt::Continue => {
+        Stmt::Return(None, _) | Stmt::Break(_, _) | Stmt::Continue(_, _) => {
             // Simple control flow statements, no additional analysis needed
         }
+
+        Stmt::Labeled { body, .. } => {
+            analyze_statement(body, analysis, depth);
+        }
+
+        Stmt::ImplBlock { methods, .. } => {
+            for method in methods {
+                analyze_statement(method, analysis, depth);
+            }
+        }
         
-        Stmt::StructDeclaration { .. } | Stmt::EnumDeclaration { .. } => {
-            // Type declarations don't affect execution complexity for our purposes
+        Stmt::StructDeclaration { .. }
+        | Stmt::EnumDeclaration { .. }
+        | Stmt::TraitDeclaration { .. }
+        | Stmt::ExternBlock { .. }
+        | Stmt::ConstDeclaration { .. }
+        | Stmt::ImportDeclaration { .. } => {
+            // Type declarations and imports don't affect execution complexity for our purposes
         }
     }
 }
@@ -211,7 +226,7 @@ fn analyze_statement(stmt: &Stmt, analysis: &mut ComplexityAnalysis, depth: usiz
 /// Analyze expressions for complexity markers
 fn analyze_expression(expr: &Expr, analysis: &mut ComplexityAnalysis) {
     match expr {
-        Expr::Call(callee, args) => {
+        Expr::Call(callee, args, _) => {
             analyze_expression(callee, analysis);
             
             // Check for function calls with vector arguments
@@ -223,58 +238,61 @@ fn analyze_expression(expr: &Expr, analysis: &mut ComplexityAnalysis) {
             }
         }
         
-        Expr::SIMD(simd_expr) => {
+        Expr::SIMD(simd_expr, _) => {
             analysis.has_simd_operations = true;
             analyze_simd_expression(simd_expr, analysis);
         }
         
-        Expr::Binary(left, _op, right) => {
+        Expr::Binary(left, _op, right, _) => {
             analyze_expression(left, analysis);
             analyze_expression(right, analysis);
         }
         
-        Expr::Unary(_op, operand) => {
+        Expr::Unary(_op, operand, _) => {
             analyze_expression(operand, analysis);
         }
         
-        Expr::Variable(_) | Expr::Literal(_) => {
+        Expr::Variable(_, _) | Expr::Literal(_, _) => {
             // Simple expressions, no complexity
         }
         
-        Expr::Grouping(expr) => {
+        Expr::Grouping(expr, _) => {
             analyze_expression(expr, analysis);
         }
         
-        Expr::Index(array, index) => {
+        Expr::Index(array, index, _) => {
             analyze_expression(array, analysis);
             analyze_expression(index, analysis);
         }
         
-        Expr::Slice { array, start, end } => {
+        Expr::Slice { array, start, end, .. } => {
             analyze_expression(array, analysis);
             analyze_expression(start, analysis);
             analyze_expression(end, analysis);
         }
         
-        Expr::FieldAccess(object, _field) => {
+        Expr::FieldAccess(object, _field, _) => {
             analyze_expression(object, analysis);
         }
         
-        Expr::StructLiteral { name: _, fields } => {
+        Expr::StructLiteral { name: _, fields, .. } => {
             for field in fields {
                 analyze_expression(&field.value, analysis);
             }
         }
         
-        Expr::EnumLiteral { enum_name: _, variant: _, args } => {
+        Expr::EnumLiteral { enum_name: _, variant: _, args, .. } => {
             for arg in args {
                 analyze_expression(arg, analysis);
             }
         }
         
-        Expr::Match { value, arms } => {
+        Expr::Match { value, arms, .. } => {
             analyze_expression(value, analysis);
             for arm in arms {
+                if let Some(guard) = &arm.guard {
+                    analyze_expression(guard, analysis);
+                }
                 analyze_expression(&arm.expression, analysis);
             }
         }
@@ -287,7 +305,7 @@ fn analyze_expression(expr: &Expr, analysis: &mut ComplexityAnalysis) {
             }
         }
         
-        Expr::Block(statements) => {
+        Expr::Block(statements, _) | Expr::Unsafe(statements, _) => {
             for stmt in statements {
                 analyze_statement(stmt, analysis, 0);
             }
@@ -297,11 +315,25 @@ fn analyze_expression(expr: &Expr, analysis: &mut ComplexityAnalysis) {
             analyze_expression(expr, analysis);
         }
         
-        Expr::Tuple { elements, position: _ } => {
+        Expr::Tuple { elements, .. } => {
             for element in elements {
                 analyze_expression(element, analysis);
             }
         }
+
+        Expr::Closure { body, .. } => {
+            analyze_expression(body, analysis);
+        }
+
+        Expr::ArrayRepeat { value, vector_type, .. } => {
+            if vector_type.is_some() {
+                analysis.has_simd_operations = true;
+            }
+            analyze_expression(value, analysis);
+        }
+
+        // Evaluated while compiling, so it adds no runtime work
+        Expr::Comptime(_, _) => {}
     }
 }
 
@@ -346,6 +378,47 @@ fn analyze_simd_expression(simd_expr: &SIMDExpr, analysis: &mut ComplexityAnalys
             analyze_expression(address, analysis);
             analyze_expression(vector, analysis);
         }
+        
+        SIMDExpr::Select { mask, if_true, if_false, position: _ } => {
+            analyze_expression(mask, analysis);
+            analyze_expression(if_true, analysis);
+            analyze_expression(if_false, analysis);
+        }
+        
+        SIMDExpr::MaskedLoad { address, mask, passthru, position: _ } => {
+            analyze_expression(address, analysis);
+            analyze_expression(mask, analysis);
+            analyze_expression(passthru, analysis);
+        }
+        
+        SIMDExpr::MaskedStore { address, vector, mask, position: _ } => {
+            analyze_expression(address, analysis);
+            analyze_expression(vector, analysis);
+            analyze_expression(mask, analysis);
+        }
+        
+        SIMDExpr::Gather { base, indices, position: _ } => {
+            analyze_expression(base, analysis);
+            analyze_expression(indices, analysis);
+        }
+        
+        SIMDExpr::Scatter { base, indices, vector, position: _ } => {
+            analyze_expression(base, analysis);
+            analyze_expression(indices, analysis);
+            analyze_expression(vector, analysis);
+        }
+        
+        SIMDExpr::Shuffle { left, right, mask, position: _ } => {
+            analyze_expression(left, analysis);
+            analyze_expression(right, analysis);
+            analyze_expression(mask, analysis);
+        }
+        
+        SIMDExpr::NativeVectorLoad { address, element_type: _, alignment: _, position: _ } => {
+            analyze_expression(address, analysis);
+        }
+        
+        SIMDExpr::Lanes { element_type: _, position: _ } => {}
     }
 }
 
@@ -356,21 +429,22 @@ fn is_vector_type(type_annotation: &TypeAnnotation) -> bool {
     name.ends_with("x4") || name.ends_with("x8") || name.ends_with("x16") || name.ends_with("x32") ||
     name.starts_with("f32x") || name.starts_with("f64x") || name.starts_with("i32x") || 
     name.starts_with("i64x") || name.starts_with("u8x") || name.starts_with("u16x") || 
-    name.starts_with("u32x") || name.starts_with("u64x")
+    name.starts_with("u32x") || name.starts_with("u64x") ||
+    crate::ast::split_native_vector_type_name(name).is_some()
 }
 
 /// Heuristic to detect if an expression is likely to produce a vector value
 fn is_likely_vector_expression(expr: &Expr) -> bool {
     match expr {
-        Expr::SIMD(_) => true,
-        Expr::Variable(name) => {
+        Expr::SIMD(_, _) => true,
+        Expr::Variable(name, _) => {
             // Common vector variable naming patterns
             name.contains("vec") || name.contains("simd") || name.ends_with("x4") || name.ends_with("x8") || name.ends_with("x16")
         }
-        Expr::Call(callee, _args) => {
+        Expr::Call(callee, _args, _) => {
             // Check if the callee is a function that commonly returns vectors
             match callee.as_ref() {
-                Expr::Variable(name) => {
+                Expr::Variable(name, _) => {
                     name.contains("horizontal") || name.contains("simd") || name.contains("vector")
                 }
                 _ => false,
@@ -384,12 +458,14 @@ fn is_likely_vector_expression(expr: &Expr) -> bool {
 mod tests {
     use super::*;
     use crate::ast::{Literal, Parameter};
+    use crate::lexer::Span;
     
     #[test]
     fn test_simple_program_analysis() {
         let statements = vec![
             Stmt::FunctionDeclaration {
                 name: "main".to_string(),
+                type_params: vec![],
                 params: vec![],
                 return_type: Some(TypeAnnotation {
                     name: "i32".to_string(),
@@ -397,12 +473,14 @@ mod tests {
                 }),
                 body: Box::new(Stmt::Block(vec![
                     Stmt::Expression(Expr::Call(
-                        Box::new(Expr::Variable("println".to_string())),
-                        vec![Expr::Literal(Literal::String("Hello".to_string()))]
-                    )),
-                    Stmt::Return(Some(Expr::Literal(Literal::Integer(0))))
-                ])),
+                        Box::new(Expr::Variable("println".to_string(), Span::default())),
+                        vec![Expr::Literal(Literal::String("Hello".to_string()), Span::default())],
+                        Span::default(),
+                    ), Span::default()),
+                    Stmt::Return(Some(Expr::Literal(Literal::Integer(0), Span::default())), Span::default())
+                ], Span::default())),
                 attributes: vec![],
+                span: Span::default(),
             }
         ];
         
@@ -418,6 +496,7 @@ mod tests {
         let statements = vec![
             Stmt::FunctionDeclaration {
                 name: "simd_func".to_string(),
+                type_params: vec![],
                 params: vec![
                     Parameter {
                         name: "vec".to_string(),
@@ -432,9 +511,10 @@ mod tests {
                     is_mutable: false,
                 }),
                 body: Box::new(Stmt::Block(vec![
-                    Stmt::Return(Some(Expr::Literal(Literal::Float(1.0))))
-                ])),
+                    Stmt::Return(Some(Expr::Literal(Literal::Float(1.0), Span::default())), Span::default())
+                ], Span::default())),
                 attributes: vec![],
+                span: Span::default(),
             }
         ];
         
@@ -449,6 +529,7 @@ mod tests {
         let statements = vec![
             Stmt::FunctionDeclaration {
                 name: "complex".to_string(),
+                type_params: vec![],
                 params: vec![],
                 return_type: Some(TypeAnnotation {
                     name: "i32".to_string(),
@@ -456,22 +537,25 @@ mod tests {
                 }),
                 body: Box::new(Stmt::Block(vec![
                     Stmt::While {
-                        condition: Expr::Literal(Literal::Boolean(true)),
+                        condition: Expr::Literal(Literal::Boolean(true), Span::default()),
                         body: Box::new(Stmt::Block(vec![
                             Stmt::If {
-                                condition: Expr::Literal(Literal::Boolean(true)),
+                                condition: Expr::Literal(Literal::Boolean(true), Span::default()),
                                 then_branch: Box::new(Stmt::Block(vec![
-                                    Stmt::Expression(Expr::Literal(Literal::Integer(1))),
-                                    Stmt::Expression(Expr::Literal(Literal::Integer(2))),
-                                    Stmt::Expression(Expr::Literal(Literal::Integer(3))),
-                                    Stmt::Expression(Expr::Literal(Literal::Integer(4))),
-                                ])),
+                                    Stmt::Expression(Expr::Literal(Literal::Integer(1), Span::default()), Span::default()),
+                                    Stmt::Expression(Expr::Literal(Literal::Integer(2), Span::default()), Span::default()),
+                                    Stmt::Expression(Expr::Literal(Literal::Integer(3), Span::default()), Span::default()),
+                                    Stmt::Expression(Expr::Literal(Literal::Integer(4), Span::default()), Span::default()),
+                                ], Span::default())),
                                 else_branch: None,
+                                span: Span::default(),
                             }
-                        ])),
+                        ], Span::default())),
+                        span: Span::default(),
                     }
-                ])),
+                ], Span::default())),
                 attributes: vec![],
+                span: Span::default(),
             }
         ];
         
@@ -3,7 +3,7 @@
 
 use crate::error::{CompileError, Result};
 use crate::jit_cache::with_jit_cache;
-use crate::jit_execution::{execute_jit_program, map_essential_symbols};
+use crate::jit_execution::{execute_jit_program, map_essential_symbols, map_extern_symbols};
 use crate::memory_profiler::get_current_memory_usage;
 use crate::{codegen, compile_to_ast};
 use inkwell::context::Context;
@@ -37,9 +37,10 @@ pub fn jit_execute_cached(source: &str, module_name: &str) -> Result<i32> {
 
     if let Some(_cached_jit) = cache_result {
         // Fast path: recompile and execute immediately since we have cached metadata
-        let (program, _type_context) = compile_to_ast(source)?;
+        let (program, type_context) = compile_to_ast(source)?;
         let context = Context::create();
         let mut codegen = codegen::CodeGenerator::new(&context, module_name);
+        codegen.set_type_context(&type_context);
         codegen.compile_program(&program)?;
 
         // Initialize LLVM targets for cached execution too
@@ -68,6 +69,7 @@ pub fn jit_execute_cached(source: &str, module_name: &str) -> Result<i32> {
 
         // Reuse cached symbol mappings
         let _symbol_table = map_essential_symbols(&execution_engine, &codegen)?;
+        map_extern_symbols(&execution_engine, &codegen, &[])?;
 
         // Execute the program
         let exit_code = execute_jit_program(&execution_engine, &codegen)?;
@@ -80,10 +82,11 @@ pub fn jit_execute_cached(source: &str, module_name: &str) -> Result<i32> {
     let compilation_start = Instant::now();
     let memory_start = get_current_memory_usage();
 
-    let (program, _type_context) = compile_to_ast(source)?;
+    let (program, type_context) = compile_to_ast(source)?;
 
     let context = Context::create();
     let mut codegen = codegen::CodeGenerator::new(&context, module_name);
+    codegen.set_type_context(&type_context);
     codegen.compile_program(&program)?;
 
     // Create execution engine for JIT compilation with proper target configuration
@@ -115,6 +118,8 @@ pub fn jit_execute_cached(source: &str, module_name: &str) -> Result<i32> {
 
     // Map essential symbols for JIT execution
     let symbol_table = map_essential_symbols(&execution_engine, &codegen)?;
+    // No libraries are given here, so `extern "C"` functions come from the process
+    map_extern_symbols(&execution_engine, &codegen, &[])?;
 
     // Execute the program and measure performance
     let exec_start = Instant::now();
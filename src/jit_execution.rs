@@ -4,7 +4,9 @@
 use crate::codegen::CodeGenerator;
 use crate::error::{CompileError, Result};
 use crate::jit_cache::CachedJIT;
+use crate::linker::LinkOptions;
 use inkwell::execution_engine::{ExecutionEngine, JitFunction};
+use libloading::Library;
 use std::collections::HashMap;
 
 /// Execute a cached JIT compilation result
@@ -1193,6 +1195,82 @@ pub fn map_essential_symbols(
     Ok(symbol_table)
 }
 
+/// Load the shared libraries given with `--link-lib`. They must stay loaded
+/// while the JIT-compiled program runs.
+pub fn load_link_libraries(options: &LinkOptions) -> Result<Vec<Library>> {
+    options
+        .shared_library_paths()
+        .iter()
+        .map(|path| {
+            unsafe { Library::new(path) }.map_err(|e| {
+                CompileError::codegen_error(
+                    format!("Failed to load library '{}': {}", path.display(), e),
+                    None,
+                )
+            })
+        })
+        .collect()
+}
+
+/// Map the program's `extern "C"` functions to their definitions in `libraries`
+pub fn map_extern_symbols(
+    execution_engine: &ExecutionEngine,
+    codegen: &CodeGenerator,
+    libraries: &[Library],
+) -> Result<HashMap<String, usize>> {
+    let symbol_table = resolve_extern_symbols(codegen.extern_functions(), libraries)?;
+    for (name, address) in &symbol_table {
+        if let Some(function) = codegen.get_module().get_function(name) {
+            execution_engine.add_global_mapping(&function, *address);
+        }
+    }
+    Ok(symbol_table)
+}
+
+/// Find the address of each of `names` in `libraries`, falling back to the
+/// symbols already loaded into the compiler process (libc, libm).
+pub fn resolve_extern_symbols(
+    names: &[String],
+    libraries: &[Library],
+) -> Result<HashMap<String, usize>> {
+    let process = current_process();
+    let mut addresses = HashMap::new();
+    for name in names {
+        let address = libraries.iter().chain(process.iter()).find_map(|library| {
+            let symbol = unsafe { library.get::<*const ()>(name.as_bytes()) }.ok()?;
+            Some(*symbol as usize)
+        });
+        match address {
+            Some(address) => {
+                addresses.insert(name.clone(), address);
+            }
+            None => {
+                return Err(CompileError::codegen_error(
+                    format!(
+                        "Undefined extern function '{}': not found in any library given with --link-lib",
+                        name
+                    ),
+                    None,
+                ))
+            }
+        }
+    }
+    Ok(addresses)
+}
+
+/// The compiler process itself, as a library to look symbols up in
+#[cfg(unix)]
+fn current_process() -> Option<Library> {
+    Some(libloading::os::unix::Library::this().into())
+}
+
+#[cfg(windows)]
+fn current_process() -> Option<Library> {
+    libloading::os::windows::Library::this()
+        .ok()
+        .map(Library::from)
+}
+
 /// Execute a JIT-compiled program
 pub fn execute_jit_program(
     execution_engine: &ExecutionEngine,
@@ -1388,4 +1466,16 @@ mod tests {
         // Basic sanity check: if there are hits and misses, they should sum to total lookups
         assert_eq!(stats.total_lookups, stats.cache_hits + stats.cache_misses);
     }
+
+    #[test]
+    fn test_resolve_extern_symbols() {
+        // Without --link-lib, C library functions resolve against the compiler process
+        let addresses = resolve_extern_symbols(&["abs".to_string()], &[]).unwrap();
+        assert_eq!(addresses["abs"], libc::abs as *const () as usize);
+
+        let error = resolve_extern_symbols(&["ea_no_such_function".to_string()], &[]).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("Undefined extern function 'ea_no_such_function'"));
+    }
 }
@@ -10,7 +10,9 @@ use crate::error::CompileError;
 use crate::memory_profiler::{check_memory_limit, record_memory_usage, CompilationPhase};
 
 /// Position information for tokens
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Lines and columns start at 1; the default `0:0` position means "unknown".
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct Position {
     pub line: usize,
     pub column: usize,
@@ -25,6 +27,46 @@ impl Position {
             offset,
         }
     }
+
+    /// Whether this is a real source location rather than the `0:0` placeholder
+    pub fn is_known(&self) -> bool {
+        self.line > 0
+    }
+}
+
+/// A source range, from the start of a node's first token to the end of its last token
+#[derive(Debug, Clone, Default)]
+pub struct Span {
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Span {
+    pub fn new(start: Position, end: Position) -> Self {
+        Self { start, end }
+    }
+
+    /// The range from the start of `self` to the end of `other`
+    pub fn to(&self, other: &Span) -> Span {
+        Span::new(self.start.clone(), other.end.clone())
+    }
+
+    /// Whether this span points into the source (nodes built by the compiler have none)
+    pub fn is_known(&self) -> bool {
+        self.start.is_known()
+    }
+
+    /// The byte offsets covered, which tell the nodes of one source file apart
+    pub fn range(&self) -> std::ops::Range<usize> {
+        self.start.offset..self.end.offset
+    }
+}
+
+/// Spans never take part in equality, so AST nodes compare structurally
+impl PartialEq for Span {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
 }
 
 /// A token with position information
@@ -43,6 +85,28 @@ impl Token {
             position,
         }
     }
+
+    /// Source range of the token
+    pub fn span(&self) -> Span {
+        Span::new(self.position.clone(), self.end_position())
+    }
+
+    /// Position just past the last character of the token
+    pub fn end_position(&self) -> Position {
+        let offset = self.position.offset + self.lexeme.len();
+        match self.lexeme.rfind('\n') {
+            Some(last_newline) => Position::new(
+                self.position.line + self.lexeme.matches('\n').count(),
+                self.lexeme.len() - last_newline,
+                offset,
+            ),
+            None => Position::new(
+                self.position.line,
+                self.position.column + self.lexeme.len(),
+                offset,
+            ),
+        }
+    }
 }
 
 /// Token types for the Eä language with SIMD extensions
@@ -81,6 +145,20 @@ pub enum TokenKind {
     Enum,
     #[token("match")]
     Match,
+    #[token("break")]
+    Break,
+    #[token("continue")]
+    Continue,
+    #[token("trait")]
+    Trait,
+    #[token("impl")]
+    Impl,
+    #[token("comptime")]
+    Comptime,
+    #[token("unsafe")]
+    Unsafe,
+    #[token("extern")]
+    Extern,
 
     // === Memory Management Keywords ===
     #[token("mem_region")]
@@ -267,6 +345,8 @@ pub enum TokenKind {
     Not,
     #[token("&")]
     Ampersand,
+    #[token("|")]
+    Pipe,
     #[token("+=")]
     PlusAssign,
     #[token("-=")]
@@ -336,6 +416,10 @@ pub enum TokenKind {
     FatArrow,
     #[token(".")]
     Dot,
+    #[token("..")]
+    DotDot,
+    #[token("..=")]
+    DotDotEq,
 
     // === Literals ===
     // Hexadecimal integers
@@ -379,6 +463,10 @@ pub enum TokenKind {
     #[regex(r"[a-zA-Z_][a-zA-Z0-9_]*", |lex| lex.slice().to_string())]
     Identifier(String),
 
+    // Loop labels: 'outer (stored without the leading quote)
+    #[regex(r"'[a-zA-Z_][a-zA-Z0-9_]*", |lex| lex.slice()[1..].to_string())]
+    Label(String),
+
     // Comments (skip them)
     #[regex(r"//[^\n]*", logos::skip)]
     #[regex(r"/\*([^*]|\*[^/])*\*/", logos::skip)]
@@ -450,6 +538,7 @@ impl std::hash::Hash for TokenKind {
             TokenKind::SimdLiteral(s) => s.hash(state),
             TokenKind::StringLiteral(s) => s.hash(state),
             TokenKind::Identifier(s) => s.hash(state),
+            TokenKind::Label(s) => s.hash(state),
             _ => {} // Other variants have no data to hash
         }
     }
@@ -474,6 +563,13 @@ impl fmt::Display for TokenKind {
             TokenKind::In => "in",
             TokenKind::Enum => "enum",
             TokenKind::Match => "match",
+            TokenKind::Break => "break",
+            TokenKind::Continue => "continue",
+            TokenKind::Trait => "trait",
+            TokenKind::Impl => "impl",
+            TokenKind::Comptime => "comptime",
+            TokenKind::Unsafe => "unsafe",
+            TokenKind::Extern => "extern",
 
             // Memory management
             TokenKind::MemRegion => "mem_region",
@@ -565,6 +661,7 @@ impl fmt::Display for TokenKind {
             TokenKind::Or => "||",
             TokenKind::Not => "!",
             TokenKind::Ampersand => "&",
+            TokenKind::Pipe => "|",
             TokenKind::PlusAssign => "+=",
             TokenKind::MinusAssign => "-=",
             TokenKind::StarAssign => "*=",
@@ -599,6 +696,8 @@ impl fmt::Display for TokenKind {
             TokenKind::Arrow => "->",
             TokenKind::FatArrow => "=>",
             TokenKind::Dot => ".",
+            TokenKind::DotDot => "..",
+            TokenKind::DotDotEq => "..=",
 
             // Literals with values
             TokenKind::Integer(n) => return write!(f, "{}", n),
@@ -606,6 +705,7 @@ impl fmt::Display for TokenKind {
             TokenKind::SimdLiteral(s) => return write!(f, "{}", s),
             TokenKind::StringLiteral(s) => return write!(f, "\"{}\"", s),
             TokenKind::Identifier(s) => return write!(f, "{}", s),
+            TokenKind::Label(s) => return write!(f, "'{}", s),
 
             // Standard library imports
             TokenKind::Import => "import",
@@ -679,6 +779,13 @@ impl<'source> Lexer<'source> {
             "in" => Some(TokenKind::In),
             "enum" => Some(TokenKind::Enum),
             "match" => Some(TokenKind::Match),
+            "break" => Some(TokenKind::Break),
+            "continue" => Some(TokenKind::Continue),
+            "trait" => Some(TokenKind::Trait),
+            "impl" => Some(TokenKind::Impl),
+            "comptime" => Some(TokenKind::Comptime),
+            "unsafe" => Some(TokenKind::Unsafe),
+            "extern" => Some(TokenKind::Extern),
 
             // Memory management
             "mem_region" => Some(TokenKind::MemRegion),
@@ -1053,4 +1160,34 @@ mod tests {
         assert_eq!(tokens[3].kind, TokenKind::Identifier("value".to_string()));
         assert_eq!(tokens[4].kind, TokenKind::Eof);
     }
+
+    #[test]
+    fn test_break_continue_and_labels() {
+        let mut lexer = Lexer::new("'outer: while break 'outer; continue");
+        let tokens = lexer.tokenize_all().unwrap();
+
+        assert_eq!(tokens[0].kind, TokenKind::Label("outer".to_string()));
+        assert_eq!(tokens[1].kind, TokenKind::Colon);
+        assert_eq!(tokens[2].kind, TokenKind::While);
+        assert_eq!(tokens[3].kind, TokenKind::Break);
+        assert_eq!(tokens[4].kind, TokenKind::Label("outer".to_string()));
+        assert_eq!(tokens[5].kind, TokenKind::Semicolon);
+        assert_eq!(tokens[6].kind, TokenKind::Continue);
+    }
+
+    #[test]
+    fn test_range_pattern_tokens() {
+        let mut lexer = Lexer::new("1..=9 0..10 1.5..=2.5");
+        let tokens = lexer.tokenize_all().unwrap();
+
+        assert_eq!(tokens[0].kind, TokenKind::Integer(1));
+        assert_eq!(tokens[1].kind, TokenKind::DotDotEq);
+        assert_eq!(tokens[2].kind, TokenKind::Integer(9));
+        assert_eq!(tokens[3].kind, TokenKind::Integer(0));
+        assert_eq!(tokens[4].kind, TokenKind::DotDot);
+        assert_eq!(tokens[5].kind, TokenKind::Integer(10));
+        assert_eq!(tokens[6].kind, TokenKind::Float(1.5));
+        assert_eq!(tokens[7].kind, TokenKind::DotDotEq);
+        assert_eq!(tokens[8].kind, TokenKind::Float(2.5));
+    }
 }
@@ -52,6 +52,11 @@ pub fn is_keyword(token: &TokenKind) -> bool {
             | TokenKind::False
             | TokenKind::While
             | TokenKind::For
+            | TokenKind::Break
+            | TokenKind::Continue
+            | TokenKind::Trait
+            | TokenKind::Impl
+            | TokenKind::Comptime
     )
 }
 
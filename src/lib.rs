@@ -6,11 +6,12 @@
 
 pub mod ast;
 pub mod config;
+pub mod diagnostics;
 pub mod error;
 pub mod execution_mode;
 pub mod lexer;
-pub mod string_interner;
 pub mod parser;
+pub mod string_interner;
 pub mod type_system;
 
 // Conditionally include codegen module if LLVM feature is enabled
@@ -27,9 +28,18 @@ pub mod jit_batch_symbols;
 #[cfg(feature = "lsp")]
 pub mod lsp;
 
+// Module resolution for multi-file programs
+pub mod module_resolver;
+
 // Package management system
 pub mod package;
 
+// Ahead-of-time object/assembly/executable output
+pub mod linker;
+
+// C headers declaring the `@export` functions of library builds
+pub mod c_header;
+
 // Zero-cost memory management system
 pub mod memory;
 
@@ -89,10 +99,11 @@ extern "C" {
 }
 
 // Re-export commonly used types
-pub use config::{CompilerConfig, get_config, init_config, set_config};
+pub use config::{get_config, init_config, set_config, CompilerConfig};
+pub use diagnostics::{Diagnostic, ErrorFormat};
 pub use error::{CompileError, Result};
-pub use lexer::{Lexer, Position, Token, TokenKind};
-pub use type_system::{EaType, FunctionType, TypeChecker, TypeContext};
+pub use lexer::{Lexer, Position, Span, Token, TokenKind};
+pub use type_system::{EaType, ExportedFunction, FunctionType, TypeChecker, TypeContext};
 
 // Re-export JIT cache functionality
 pub use jit_cache::{with_jit_cache, initialize_default_jit_cache, JITCacheConfig, JITCacheStats};
@@ -118,20 +129,114 @@ pub fn parse(source: &str) -> Result<Vec<ast::Stmt>> {
     parser.parse_program()
 }
 
+/// Parse a source string, returning every syntax error the parser recovered
+/// from rather than only the first
+pub fn parse_with_errors(source: &str) -> std::result::Result<Vec<ast::Stmt>, Vec<CompileError>> {
+    let tokens = tokenize(source).map_err(|error| vec![error])?;
+    let mut parser = parser::Parser::new(tokens);
+    parser.parse_program().map_err(|error| {
+        let mut errors: Vec<CompileError> = Vec::new();
+        for collected in parser.get_errors() {
+            // Recovery can report the same problem more than once
+            if !errors.iter().any(|seen| seen.to_string() == collected.to_string()) {
+                errors.push(collected.clone());
+            }
+        }
+        if errors.is_empty() {
+            errors.push(error);
+        }
+        errors
+    })
+}
+
 /// Type check a parsed AST
 pub fn type_check(program: &[ast::Stmt]) -> Result<TypeContext> {
     let mut type_checker = TypeChecker::new();
     type_checker.check_program(program)
 }
 
+/// Type check a parsed AST, returning every type error found rather than only
+/// the first
+pub fn type_check_with_errors(
+    program: &[ast::Stmt],
+) -> std::result::Result<TypeContext, Vec<CompileError>> {
+    let mut type_checker = TypeChecker::new();
+    type_checker
+        .check_program(program)
+        .map_err(|_| type_checker.get_errors().to_vec())
+}
+
+/// Type check a parsed AST like `type_check_with_errors`, also returning the
+/// warnings found, such as unreachable match arms
+pub fn type_check_with_warnings(
+    program: &[ast::Stmt],
+) -> std::result::Result<(TypeContext, Vec<CompileError>), Vec<CompileError>> {
+    type_check_for_target(program, &type_system::hardware::CompileTarget::default())
+}
+
+/// Type check a parsed AST like `type_check_with_warnings`, with the SIMD types
+/// that are legal on `target` rather than on the host
+pub fn type_check_for_target(
+    program: &[ast::Stmt],
+    target: &type_system::hardware::CompileTarget,
+) -> std::result::Result<(TypeContext, Vec<CompileError>), Vec<CompileError>> {
+    let mut type_checker = TypeChecker::for_compile_target(target);
+    match type_checker.check_program(program) {
+        Ok(context) => Ok((context, type_checker.get_warnings().to_vec())),
+        Err(_) => Err(type_checker.get_errors().to_vec()),
+    }
+}
+
 /// Complete compilation pipeline: source -> tokens -> AST -> type checking -> memory analysis
 pub fn compile_to_ast(source: &str) -> Result<(Vec<ast::Stmt>, TypeContext)> {
+    compile_to_ast_for_target(source, &type_system::hardware::CompileTarget::default())
+}
+
+/// Compilation pipeline like `compile_to_ast`, type checking for `target`
+pub fn compile_to_ast_for_target(
+    source: &str,
+    target: &type_system::hardware::CompileTarget,
+) -> Result<(Vec<ast::Stmt>, TypeContext)> {
     let program = parse(source)?;
-    let type_context = type_check(&program)?;
+    let type_context = TypeChecker::for_compile_target(target).check_program(&program)?;
     let _memory_analysis = memory::analyze_memory_regions(&program);
     Ok((program, type_context))
 }
 
+/// Type check a multi-file program, giving each module its own namespace
+pub fn type_check_modules(modules: &[module_resolver::ResolvedModule]) -> Result<TypeContext> {
+    let mut type_checker = TypeChecker::new();
+    type_checker.check_modules(modules)
+}
+
+/// Multi-file compilation pipeline: resolve imports starting at `entry`, parse
+/// every module and type check them in dependency order
+pub fn compile_modules_to_ast(
+    entry: &std::path::Path,
+    extra_files: &[std::path::PathBuf],
+) -> Result<(Vec<module_resolver::ResolvedModule>, TypeContext)> {
+    compile_modules_to_ast_for_target(
+        entry,
+        extra_files,
+        &type_system::hardware::CompileTarget::default(),
+    )
+}
+
+/// Multi-file compilation pipeline like `compile_modules_to_ast`, type checking
+/// for `target`
+pub fn compile_modules_to_ast_for_target(
+    entry: &std::path::Path,
+    extra_files: &[std::path::PathBuf],
+    target: &type_system::hardware::CompileTarget,
+) -> Result<(Vec<module_resolver::ResolvedModule>, TypeContext)> {
+    let modules = module_resolver::load_modules(entry, extra_files)?;
+    let type_context = TypeChecker::for_compile_target(target).check_modules(&modules)?;
+    for module in &modules {
+        let _memory_analysis = memory::analyze_memory_regions(&module.program);
+    }
+    Ok((modules, type_context))
+}
+
 /// Streaming compilation pipeline for large programs
 pub fn compile_to_ast_streaming(
     source: &str,
@@ -144,10 +249,11 @@ pub fn compile_to_ast_streaming(
 pub fn compile_to_llvm(source: &str, module_name: &str) -> Result<()> {
     use inkwell::context::Context;
 
-    let (program, _type_context) = compile_to_ast(source)?;
+    let (program, type_context) = compile_to_ast(source)?;
     let pooled_context = crate::llvm_context_pool::PooledContext::acquire();
     let context = pooled_context.context();
     let mut codegen = codegen::CodeGenerator::new_full(&context, module_name);
+    codegen.set_type_context(&type_context);
     codegen.compile_program(&program)?;
 
     let mut optimizer = llvm_optimization::LLVMOptimizer::with_config(
@@ -181,16 +287,181 @@ pub fn compile_to_llvm(source: &str, module_name: &str) -> Result<()> {
     Ok(())
 }
 
+/// Compile a resolved multi-file program into a single LLVM module
+///
+/// `type_context` is the context `compile_modules_to_ast` returned for `modules`.
+#[cfg(feature = "llvm")]
+pub fn compile_modules_to_llvm(
+    modules: &[module_resolver::ResolvedModule],
+    type_context: &TypeContext,
+    module_name: &str,
+) -> Result<()> {
+    let pooled_context = crate::llvm_context_pool::PooledContext::acquire();
+    let context = pooled_context.context();
+    let mut codegen = codegen::CodeGenerator::new_full(context, module_name);
+    codegen.set_type_context(type_context);
+    codegen.compile_modules(modules)?;
+
+    let mut optimizer =
+        llvm_optimization::LLVMOptimizer::with_config(llvm_optimization::apply_emit_llvm_preset());
+    optimizer.optimize_module(codegen.get_module())?;
+    let ir_filename = format!("{}.ll", module_name);
+    codegen.write_ir_to_file(&ir_filename)?;
+
+    eprintln!(
+        "🎉 LLVM compilation of {} modules completed successfully",
+        modules.len()
+    );
+    Ok(())
+}
+
+/// Compile source ahead of time to native output (object, assembly and/or executable)
+///
+/// With a library `--crate-type` the linked output is a library instead, and a C
+/// header declaring its `@export` functions is written next to it.
+///
+/// Returns the paths that were written (object, assembly, executable or library, header).
+#[cfg(feature = "llvm")]
+pub fn compile_to_native(
+    source: &str,
+    output: &std::path::Path,
+    emit: &[linker::EmitKind],
+    codegen_options: &codegen::CodegenOptions,
+    options: &linker::LinkOptions,
+) -> Result<Vec<std::path::PathBuf>> {
+    let (program, type_context) = compile_to_ast_for_target(source, &codegen_options.target)?;
+    let pooled_context = crate::llvm_context_pool::PooledContext::acquire();
+    let context = pooled_context.context();
+    let module_name = native_module_name(output);
+    let mut codegen = codegen::CodeGenerator::new_full(context, &module_name);
+    codegen.set_options(codegen_options.clone());
+    codegen.set_type_context(&type_context);
+    codegen.compile_program(&program)?;
+
+    write_native_outputs(&codegen, &type_context.exports, output, emit, options)
+}
+
+/// Compile a resolved multi-file program ahead of time to native output
+///
+/// `type_context` is the context `compile_modules_to_ast_for_target` returned for
+/// `modules` and the target in `codegen_options`; the C header of a library
+/// declares the exports of all modules it lists.
+#[cfg(feature = "llvm")]
+pub fn compile_modules_to_native(
+    modules: &[module_resolver::ResolvedModule],
+    type_context: &TypeContext,
+    output: &std::path::Path,
+    emit: &[linker::EmitKind],
+    codegen_options: &codegen::CodegenOptions,
+    options: &linker::LinkOptions,
+) -> Result<Vec<std::path::PathBuf>> {
+    let pooled_context = crate::llvm_context_pool::PooledContext::acquire();
+    let context = pooled_context.context();
+    let module_name = native_module_name(output);
+    let mut codegen = codegen::CodeGenerator::new_full(context, &module_name);
+    codegen.set_options(codegen_options.clone());
+    codegen.set_type_context(type_context);
+    codegen.compile_modules(modules)?;
+
+    write_native_outputs(&codegen, &type_context.exports, output, emit, options)
+}
+
+#[cfg(feature = "llvm")]
+fn native_module_name(output: &std::path::Path) -> String {
+    output
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("main")
+        .to_string()
+}
+
+#[cfg(feature = "llvm")]
+fn write_native_outputs(
+    codegen: &codegen::CodeGenerator,
+    exports: &[ExportedFunction],
+    output: &std::path::Path,
+    emit: &[linker::EmitKind],
+    options: &linker::LinkOptions,
+) -> Result<Vec<std::path::PathBuf>> {
+    use linker::{CrateType, EmitKind};
+
+    let mut optimizer = llvm_optimization::LLVMOptimizer::with_config(
+        llvm_optimization::apply_emit_llvm_preset().with_target(codegen.target()),
+    );
+    optimizer.optimize_module(codegen.get_module())?;
+
+    // The C runtime linked into executables and libraries is built for the host
+    if emit.contains(&EmitKind::Executable) && codegen.is_cross_compiling() {
+        return Err(CompileError::codegen_error(
+            format!(
+                "Cannot link for target {}: the runtime is only built for the host; use --emit=obj or --emit=asm and link with the target's toolchain",
+                codegen.target().triple.as_deref().unwrap_or_default()
+            ),
+            None,
+        ));
+    }
+
+    let mut written = Vec::new();
+    for kind in [EmitKind::Object, EmitKind::Assembly] {
+        if emit.contains(&kind) {
+            let path = kind.output_path(output);
+            let filename = path.to_string_lossy();
+            match kind {
+                EmitKind::Object => codegen.compile_to_object_file(&filename)?,
+                _ => codegen.compile_to_assembly_file(&filename)?,
+            }
+            written.push(path);
+        }
+    }
+
+    if emit.contains(&EmitKind::Executable) {
+        // Link against the object we just wrote, or a temporary one if it wasn't requested
+        let object_dir = tempfile::tempdir().map_err(|e| {
+            CompileError::codegen_error(format!("Failed to create temporary directory: {}", e), None)
+        })?;
+        let object = if emit.contains(&EmitKind::Object) {
+            EmitKind::Object.output_path(output)
+        } else {
+            let object = object_dir.path().join(format!("{}.o", native_module_name(output)));
+            codegen.compile_to_object_file(&object.to_string_lossy())?;
+            object
+        };
+
+        let linked = options.crate_type.output_path(output);
+        match options.crate_type {
+            CrateType::Bin => linker::link_executable(&[object], &linked, options)?,
+            CrateType::Cdylib => linker::link_shared_library(&[object], &linked, options)?,
+            CrateType::Staticlib => linker::archive_static_library(&[object], &linked)?,
+        }
+        written.push(linked);
+    }
+
+    if options.crate_type.is_library() {
+        let header_path = options.crate_type.header_path(output);
+        let header = c_header::generate_c_header(&native_module_name(output), exports);
+        std::fs::write(&header_path, header).map_err(|e| {
+            CompileError::codegen_error(
+                format!("Failed to write header '{}': {}", header_path.display(), e),
+                None,
+            )
+        })?;
+        written.push(header_path);
+    }
+
+    Ok(written)
+}
+
 /// Compile to LLVM IR with minimal standard library for static linking
 #[cfg(feature = "llvm")]
 pub fn compile_to_llvm_minimal(source: &str, module_name: &str) -> Result<()> {
     use inkwell::context::Context;
 
-    let (program, _type_context) = compile_to_ast(source)?;
+    let (program, type_context) = compile_to_ast(source)?;
 
     let pooled_context = crate::llvm_context_pool::PooledContext::acquire();
     let context = pooled_context.context();
     let mut codegen = codegen::CodeGenerator::new_full(context, module_name);
+    codegen.set_type_context(&type_context);
     codegen.compile_program(&program)?;
 
     // Write LLVM IR to file for inspection
@@ -209,13 +480,14 @@ pub fn diagnose_jit_execution(source: &str, module_name: &str) -> Result<String>
     let mut diagnostics = String::new();
 
     // Parse and type check
-    let (program, _type_context) = compile_to_ast(source)?;
+    let (program, type_context) = compile_to_ast(source)?;
     diagnostics.push_str("✅ Parsing and type checking successful\n");
 
     // Generate LLVM IR
     let pooled_context = crate::llvm_context_pool::PooledContext::acquire();
     let context = pooled_context.context();
     let mut codegen = codegen::CodeGenerator::new(context, module_name);
+    codegen.set_type_context(&type_context);
     codegen.compile_program(&program)?;
     diagnostics.push_str("✅ LLVM IR generation successful\n");
 
@@ -359,8 +631,14 @@ pub fn diagnose_jit_execution(source: &str, module_name: &str) -> Result<String>
 }
 
 /// Smart execution strategy that automatically chooses between JIT and compilation
+///
+/// `extern "C"` functions are resolved against the libraries in `link_options`.
 #[cfg(feature = "llvm")]
-pub fn smart_execute(source: &str, module_name: &str) -> Result<i32> {
+pub fn smart_execute(
+    source: &str,
+    module_name: &str,
+    link_options: &linker::LinkOptions,
+) -> Result<i32> {
     use crate::execution_mode::{analyze_execution_complexity, ExecutionMode};
     
     // 1. Analyze program complexity to determine execution method
@@ -371,58 +649,101 @@ pub fn smart_execute(source: &str, module_name: &str) -> Result<i32> {
     match execution_mode {
         ExecutionMode::JitSafe => {
             eprintln!("⚡ JIT execution (fast) - {}", analysis.execution_reason());
-            jit_execute(source, module_name)
+            jit_execute(source, module_name, link_options)
         }
         ExecutionMode::JitRisky => {
             eprintln!("🔄 Trying JIT execution... - {}", analysis.execution_reason());
-            match jit_execute(source, module_name) {
+            match jit_execute(source, module_name, link_options) {
                 Ok(result) => {
                     eprintln!("✅ JIT execution successful");
                     Ok(result)
                 }
                 Err(_) => {
                     eprintln!("⚠️  JIT limitations encountered, using compiled execution...");
-                    compile_and_execute(source, module_name)
+                    compile_and_execute(source, module_name, link_options)
                 }
             }
         }
         ExecutionMode::CompileRequired => {
             eprintln!("🔧 Compiled execution - {}", analysis.execution_reason());
-            compile_and_execute(source, module_name)
+            compile_and_execute(source, module_name, link_options)
         }
     }
 }
 
 /// Compile to native executable and execute
 #[cfg(feature = "llvm")]
-pub fn compile_and_execute(source: &str, module_name: &str) -> Result<i32> {
-    use std::process::Command;
-    use std::io::Write;
-    use tempfile::NamedTempFile;
-    
+pub fn compile_and_execute(
+    source: &str,
+    module_name: &str,
+    link_options: &linker::LinkOptions,
+) -> Result<i32> {
     eprintln!("🔧 Compiling to LLVM IR...");
     
     // 1. Use the standard compilation pipeline (same as --emit-llvm)
-    let (program, _type_context) = compile_to_ast(source)?;
+    let (program, type_context) = compile_to_ast(source)?;
     let pooled_context = crate::llvm_context_pool::PooledContext::acquire();
     let context = pooled_context.context();
     
     // Use the standard new() method and disable JIT safe mode for full SIMD support
     let mut codegen = codegen::CodeGenerator::new(context, module_name);
     codegen.set_jit_safe_mode(false); // Enable full SIMD compilation
+    codegen.set_type_context(&type_context);
     codegen.compile_program(&program)?;
 
     let mut optimizer = llvm_optimization::LLVMOptimizer::with_config(
         llvm_optimization::apply_emit_llvm_preset()
     );
     optimizer.optimize_module(codegen.get_module())?;
-    
+
+    execute_ir_with_lli(
+        &codegen.get_module().print_to_string().to_string(),
+        link_options,
+    )
+}
+
+/// Compile a resolved multi-file program and execute it with lli
+///
+/// `type_context` is the context `compile_modules_to_ast` returned for `modules`.
+#[cfg(feature = "llvm")]
+pub fn compile_modules_and_execute(
+    modules: &[module_resolver::ResolvedModule],
+    type_context: &TypeContext,
+    module_name: &str,
+    link_options: &linker::LinkOptions,
+) -> Result<i32> {
+    eprintln!("🔧 Compiling {} modules to LLVM IR...", modules.len());
+
+    let pooled_context = crate::llvm_context_pool::PooledContext::acquire();
+    let context = pooled_context.context();
+    let mut codegen = codegen::CodeGenerator::new(context, module_name);
+    codegen.set_jit_safe_mode(false);
+    codegen.set_type_context(type_context);
+    codegen.compile_modules(modules)?;
+
+    let mut optimizer =
+        llvm_optimization::LLVMOptimizer::with_config(llvm_optimization::apply_emit_llvm_preset());
+    optimizer.optimize_module(codegen.get_module())?;
+
+    execute_ir_with_lli(
+        &codegen.get_module().print_to_string().to_string(),
+        link_options,
+    )
+}
+
+/// Write LLVM IR to a temporary file and execute it with lli, loading the
+/// libraries of `link_options` for its `extern "C"` functions
+#[cfg(feature = "llvm")]
+fn execute_ir_with_lli(ir_content: &str, link_options: &linker::LinkOptions) -> Result<i32> {
+    use std::io::Write;
+    use std::process::Command;
+    use tempfile::NamedTempFile;
+
     // 2. Write LLVM IR to temporary file
     let mut temp_ll_file = NamedTempFile::new()
         .map_err(|e| crate::error::CompileError::codegen_error(
             format!("Failed to create temporary IR file: {}", e), None))?;
     
-    let ir_content = codegen.get_module().print_to_string().to_string();
     temp_ll_file.write_all(ir_content.as_bytes())
         .map_err(|e| crate::error::CompileError::codegen_error(
             format!("Failed to write IR to temp file: {}", e), None))?;
@@ -432,7 +753,11 @@ pub fn compile_and_execute(source: &str, module_name: &str) -> Result<i32> {
     eprintln!("✅ LLVM IR generated, executing with lli...");
     
     // 3. Execute LLVM IR using lli (LLVM interpreter)
-    let output = Command::new("lli")
+    let mut command = Command::new("lli");
+    for library in link_options.shared_library_paths() {
+        command.arg(format!("-load={}", library.display()));
+    }
+    let output = command
         .arg(ll_path)
         .output()
         .map_err(|e| crate::error::CompileError::codegen_error(
@@ -459,7 +784,11 @@ pub fn compile_and_execute(source: &str, module_name: &str) -> Result<i32> {
 
 /// JIT compile and execute a program immediately with caching
 #[cfg(feature = "llvm")]
-pub fn jit_execute(source: &str, module_name: &str) -> Result<i32> {
+pub fn jit_execute(
+    source: &str,
+    module_name: &str,
+    link_options: &linker::LinkOptions,
+) -> Result<i32> {
     use inkwell::context::Context;
     use inkwell::execution_engine::JitFunction;
     use inkwell::OptimizationLevel;
@@ -493,11 +822,12 @@ pub fn jit_execute(source: &str, module_name: &str) -> Result<i32> {
     let _compilation_start = Instant::now();
     let _memory_start = memory_profiler::get_current_memory_usage();
 
-    let (program, _type_context) = compile_to_ast(source)?;
+    let (program, type_context) = compile_to_ast(source)?;
 
     let pooled_context = crate::llvm_context_pool::PooledContext::acquire();
     let context = pooled_context.context();
     let mut codegen = codegen::CodeGenerator::new(context, module_name);
+    codegen.set_type_context(&type_context);
     codegen.compile_program(&program)?;
 
     // Configure target features for SIMD support before creating JIT engine
@@ -664,6 +994,11 @@ pub fn jit_execute(source: &str, module_name: &str) -> Result<i32> {
         }
     }
 
+    // Resolve `extern "C"` functions against the libraries given with --link-lib;
+    // they stay loaded until the program has run
+    let libraries = jit_execution::load_link_libraries(link_options)?;
+    jit_execution::map_extern_symbols(&execution_engine, &codegen, &libraries)?;
+
     // CRITICAL DEBUG: Check if we reach global mapping
     eprintln!("🚨 DEBUG: About to start global mapping...");
 
@@ -1004,4 +1339,1229 @@ mod tests {
         let result = compile_to_ast(source);
         assert!(result.is_ok(), "Scoping should work correctly");
     }
+
+    #[test]
+    fn test_generic_type_checking() {
+        let source = r#"
+            struct Pair<T> {
+                first: T,
+                second: T,
+            }
+
+            func sum<T>(a: T, b: T) -> T {
+                return a + b;
+            }
+
+            func swap<T>(p: Pair<T>) -> Pair<T> {
+                return Pair { first: p.second, second: p.first };
+            }
+
+            func main() -> () {
+                let total: i32 = sum(1, 2);
+                let scaled: f32 = sum(1.5, 2.5);
+                let p: Pair<i32> = swap(Pair { first: 1, second: 2 });
+                let first: i32 = p.first;
+                return;
+            }
+        "#;
+        compile_to_ast(source).unwrap();
+
+        // Calls are checked against the instantiated signature
+        let wrong_result = source.replace("let total: i32", "let total: bool");
+        let error = compile_to_ast(&wrong_result).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("Type mismatch in variable 'total'"),
+            "{}",
+            error
+        );
+
+        let conflicting = r#"
+            func sum<T>(a: T, b: T) -> T {
+                return a + b;
+            }
+
+            func main() -> () {
+                let x = sum(true, 1.5);
+                return;
+            }
+        "#;
+        let error = compile_to_ast(conflicting).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("Conflicting types for type parameter 'T' of 'sum': bool and f32"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_trait_type_checking() {
+        let source = r#"
+            trait Shape {
+                func area(self) -> f32;
+                func scaled(self, k: f32) -> Self;
+            }
+
+            struct Circle {
+                r: f32,
+            }
+
+            impl Shape for Circle {
+                func area(self) -> f32 {
+                    return self.r * self.r * 3.14;
+                }
+                func scaled(self, k: f32) -> Circle {
+                    return Circle { r: self.r * k };
+                }
+            }
+
+            func area_of<T>(shape: T) -> f32 {
+                return shape.area();
+            }
+
+            func main() -> () {
+                let c = Circle { r: 2.0 };
+                let a: f32 = c.scaled(2.0).area();
+                let b: f32 = area_of(c);
+                return;
+            }
+        "#;
+        let (_, context) = compile_to_ast(source).unwrap();
+        assert_eq!(
+            context.functions["Circle::scaled"].to_string(),
+            "func(Circle, f32) -> Circle"
+        );
+
+        let missing_method = r#"
+            trait Shape {
+                func area(self) -> f32;
+            }
+            struct Square { side: f32 }
+            impl Shape for Square { }
+        "#;
+        let error = compile_to_ast(missing_method).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("Missing method 'area' in impl of trait 'Shape' for 'Square'"),
+            "{}",
+            error
+        );
+
+        let unknown_trait = r#"
+            struct Square { side: f32 }
+            impl Drawable for Square {
+                func draw(self) -> () { return; }
+            }
+        "#;
+        let error = compile_to_ast(unknown_trait).unwrap_err();
+        assert!(
+            error.to_string().contains("Unknown trait 'Drawable'"),
+            "{}",
+            error
+        );
+
+        let unimplemented_call = r#"
+            trait Shape {
+                func area(self) -> f32;
+            }
+            struct Square { side: f32 }
+            func main() -> () {
+                let s = Square { side: 1.0 };
+                let a = s.area();
+                return;
+            }
+        "#;
+        let error = compile_to_ast(unimplemented_call).unwrap_err();
+        assert!(
+            error.to_string().contains("has no method 'area'"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_impl_method_type_checking() {
+        let source = r#"
+            struct Point {
+                x: f32,
+                y: f32,
+            }
+
+            impl Point {
+                func new(x: f32, y: f32) -> Point {
+                    return Point { x: x, y: y };
+                }
+                func length_squared(self) -> f32 {
+                    return self.x * self.x + self.y * self.y;
+                }
+                func offset(self, dx: f32) -> Self {
+                    return Point::new(self.x + dx, self.y);
+                }
+            }
+
+            func main() -> () {
+                let p = Point::new(3.0, 4.0);
+                let l: f32 = p.offset(1.0).length_squared();
+                return;
+            }
+        "#;
+        let (_, context) = compile_to_ast(source).unwrap();
+        assert_eq!(
+            context.functions["Point::new"].to_string(),
+            "func(f32, f32) -> Point"
+        );
+        // `Self` resolves to the implementing type
+        assert_eq!(
+            context.functions["Point::offset"].to_string(),
+            "func(Point, f32) -> Point"
+        );
+
+        let free_self = r#"
+            func length(self) -> f32 {
+                return 0.0;
+            }
+        "#;
+        let error = compile_to_ast(free_self).unwrap_err();
+        assert!(
+            error.to_string().contains(
+                "'self' parameter of function 'length' is only allowed in methods of impl blocks"
+            ),
+            "{}",
+            error
+        );
+
+        let associated_as_method = r#"
+            struct Point { x: f32 }
+            impl Point {
+                func new(x: f32) -> Point { return Point { x: x }; }
+            }
+            func main() -> () {
+                let p = Point::new(1.0);
+                let q = p.new(2.0);
+                return;
+            }
+        "#;
+        let error = compile_to_ast(associated_as_method).unwrap_err();
+        assert!(
+            error.to_string().contains("has no method 'new'"),
+            "{}",
+            error
+        );
+
+        let primitive_impl = r#"
+            impl i32 {
+                func double(self) -> i32 { return self * 2; }
+            }
+        "#;
+        let error = compile_to_ast(primitive_impl).unwrap_err();
+        assert!(
+            error.to_string().contains(
+                "Inherent impl for 'i32' requires a struct or enum declared in this program"
+            ),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_const_type_checking() {
+        let source = r#"
+            const LANES: i32 = 4;
+            const SCALE: f32 = 0.5;
+            const UNROLL: i32 = LANES * 2;
+
+            @optimize(unroll: UNROLL)
+            func scale(v: f32x4) -> f32x4 {
+                let factors = [SCALE; LANES]f32x4;
+                return v .* factors;
+            }
+
+            func main() -> () {
+                const LIMIT: i32 = (UNROLL + 1) * 2;
+                let zeros = [0; LIMIT];
+                let v = scale([1.0, 2.0, 3.0, 4.0]f32x4);
+                return;
+            }
+        "#;
+        let (_, context) = compile_to_ast(source).unwrap();
+        assert_eq!(
+            context.functions["scale"].to_string(),
+            "func(f32x4) -> f32x4"
+        );
+        assert!(matches!(
+            context.constants["UNROLL"],
+            crate::comptime::ComptimeValue::Integer(8)
+        ));
+
+        let assigned = r#"
+            const N: i32 = 4;
+            func main() -> () {
+                N = 5;
+                return;
+            }
+        "#;
+        let error = compile_to_ast(assigned).unwrap_err();
+        assert!(
+            error.to_string().contains("Cannot assign to constant 'N'"),
+            "{}",
+            error
+        );
+
+        let not_constant = r#"
+            func five() -> i32 { return 5; }
+            const N: i32 = five();
+        "#;
+        let error = compile_to_ast(not_constant).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("Value of constant 'N' is not a compile-time constant"),
+            "{}",
+            error
+        );
+
+        let wrong_lanes = r#"
+            const LANES: i32 = 8;
+            func main() -> () {
+                let v = [1.0; LANES]f32x4;
+                return;
+            }
+        "#;
+        let error = compile_to_ast(wrong_lanes).unwrap_err();
+        assert!(
+            error.to_string().contains("f32x4 has 4 elements, got 8"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_comptime_type_checking() {
+        let source = r#"
+            comptime func square(x: i32) -> i32 {
+                return x * x;
+            }
+
+            comptime func sum_of_squares(n: i32) -> i32 {
+                let mut total: i32 = 0;
+                for (let mut i: i32 = 1; i <= n; i += 1) {
+                    total += square(i);
+                }
+                return total;
+            }
+
+            const TOTAL: i32 = sum_of_squares(4);
+
+            func lookup(i: i32) -> f32 {
+                let sin_table = comptime { generate_lookup_table("sin", 0.0, 6.283185, 256); };
+                return sin_table[i];
+            }
+
+            func main() -> i32 {
+                let squares = comptime {
+                    let table = [0; 8];
+                    for (let mut i: i32 = 0; i < 8; i += 1) { table[i] = square(i); }
+                    table;
+                };
+                return squares[3] + TOTAL + comptime { square(2); };
+            }
+        "#;
+        let (_, context) = compile_to_ast(source).unwrap();
+        assert!(matches!(
+            context.constants["TOTAL"],
+            crate::comptime::ComptimeValue::Integer(30)
+        ));
+
+        let runtime_call = r#"
+            comptime func square(x: i32) -> i32 { return x * x; }
+            func main() -> i32 { return square(3); }
+        "#;
+        let error = compile_to_ast(runtime_call).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("comptime function 'square' can only be called in a comptime block"),
+            "{}",
+            error
+        );
+
+        let runtime_value = r#"
+            func main() -> i32 {
+                let n = 3;
+                return comptime { n * 2; };
+            }
+        "#;
+        let error = compile_to_ast(runtime_value).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("Failed to evaluate comptime block"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_tuple_type_checking() {
+        let source = r#"
+            func divmod(a: i32, b: i32) -> (i32, i32) {
+                return (a / b, a % b);
+            }
+
+            func main() -> i32 {
+                let (q, r) = divmod(17, 5);
+                let pair: (i32, f32) = (q, 2.5);
+                let nested = (pair, true);
+                let (_, flag) = nested;
+                let scale: f32 = nested.0.1;
+                if (flag) {
+                    return pair.0 + r;
+                }
+                return q;
+            }
+        "#;
+        let (_, context) = compile_to_ast(source).unwrap();
+        assert_eq!(
+            context.functions["divmod"].to_string(),
+            "func(i32, i32) -> (i32, i32)"
+        );
+
+        let wrong_arity = r#"
+            func main() -> () {
+                let (a, b, c) = (1, 2);
+                return;
+            }
+        "#;
+        let error = compile_to_ast(wrong_arity).unwrap_err();
+        assert!(
+            error.to_string().contains(
+                "Cannot destructure a value of type (i64, i64) into a tuple of 3 elements"
+            ),
+            "{}",
+            error
+        );
+
+        let missing_field = r#"
+            func main() -> () {
+                let pair = (1, 2);
+                let x = pair.2;
+                return;
+            }
+        "#;
+        let error = compile_to_ast(missing_field).unwrap_err();
+        assert!(error.to_string().contains("has no field '2'"), "{}", error);
+
+        let mismatched = r#"
+            func main() -> () {
+                let pair: (i32, bool) = (1, 2);
+                return;
+            }
+        "#;
+        let error = compile_to_ast(mismatched).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("Type mismatch in variable 'pair'"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_closure_type_checking() {
+        let source = r#"
+            func double(x: i32) -> i32 {
+                return x * 2;
+            }
+
+            func apply(f: func(i32) -> i32, x: i32) -> i32 {
+                return f(x);
+            }
+
+            func make_adder(offset: i32) -> func(i32) -> i32 {
+                return |x: i32| -> i32 { x + offset };
+            }
+
+            func main() -> i32 {
+                let threshold = 10;
+                let above = |x: i32| x > threshold;
+                let add_five = make_adder(5);
+                let values = Vec::new();
+                values.push(4);
+                values.push(12);
+                let doubled = values.map(double);
+                let large = doubled.filter(above);
+                let total: i32 = large.reduce(0, |acc: i32, x: i32| acc + x);
+                if (above(total)) {
+                    return apply(add_five, total);
+                }
+                return (|| { threshold })();
+            }
+        "#;
+        let (_, context) = compile_to_ast(source).unwrap();
+        assert_eq!(
+            context.functions["make_adder"].to_string(),
+            "func(i32) -> func(i32) -> i32"
+        );
+
+        let wrong_argument = r#"
+            func main() -> () {
+                let negate = |flag: bool| !flag;
+                let result = negate(1.5);
+                return;
+            }
+        "#;
+        let error = compile_to_ast(wrong_argument).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("Argument 1 of function 'negate': expected Bool, got F32"),
+            "{}",
+            error
+        );
+
+        let wrong_return = r#"
+            func main() -> () {
+                let f = |x: i32| -> bool { x + 1 };
+                return;
+            }
+        "#;
+        let error = compile_to_ast(wrong_return).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("Closure declared to return bool, but its body has type i32"),
+            "{}",
+            error
+        );
+
+        let filter_needs_bool = r#"
+            func main() -> () {
+                let values = Vec::new();
+                let odd = values.filter(|x: i32| x % 2);
+                return;
+            }
+        "#;
+        let error = compile_to_ast(filter_needs_bool).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("Vec::filter() expects a function returning bool, got i32"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_match_exhaustiveness() {
+        let shapes = r#"
+            enum Shape {
+                Circle(f32),
+                Square(f32),
+                Empty,
+            }
+
+            func area(shape: Shape) -> f32 {
+                return match shape {
+                    Shape::Circle(r) => r * r * 3.14,
+                    Shape::Square(side) => side * side,
+                    Shape::Empty => 0.0,
+                };
+            }
+        "#;
+        let program = parse(shapes).unwrap();
+        let (_, warnings) = type_check_with_warnings(&program).unwrap();
+        assert!(warnings.is_empty(), "{:?}", warnings);
+
+        let missing_variant = shapes.replace("Shape::Square(side) => side * side,", "");
+        let error = compile_to_ast(&missing_variant).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("missing variants [Shape::Square(_)]"),
+            "{}",
+            error
+        );
+
+        // A binding catches every variant it follows
+        let catch_all = shapes.replace("Shape::Empty => 0.0,", "other => 0.0,");
+        let program = parse(&catch_all).unwrap();
+        let (_, warnings) = type_check_with_warnings(&program).unwrap();
+        assert!(warnings.is_empty(), "{:?}", warnings);
+
+        let unreachable = shapes.replace("Shape::Empty => 0.0,", "Shape::Empty => 0.0, _ => 1.0,");
+        let program = parse(&unreachable).unwrap();
+        let (_, warnings) = type_check_with_warnings(&program).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].to_string().contains("Unreachable match arm"));
+
+        // Integers cannot all be listed, so a missing `_` only warns
+        let integers = r#"
+            func describe(n: i32) -> i32 {
+                return match n {
+                    0 => 10,
+                    1 => 20,
+                };
+            }
+        "#;
+        let program = parse(integers).unwrap();
+        let (_, warnings) = type_check_with_warnings(&program).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].to_string().contains("patterns [_] not covered"));
+    }
+
+    #[test]
+    fn test_rich_patterns() {
+        let source = r#"
+            struct Point {
+                x: i32,
+                y: i32,
+            }
+
+            func classify(p: Point, n: i32) -> i32 {
+                let quadrant = match p {
+                    Point { x: 0, y: 0 } => 0,
+                    Point { x, y } if x > 0 && y > 0 => 1,
+                    Point { x: 0, .. } | Point { y: 0, .. } => 2,
+                    _ => 3,
+                };
+                let size = match n {
+                    0 => 0,
+                    1..=9 => 1,
+                    10..100 => 2,
+                    _ => 3,
+                };
+                return quadrant + size;
+            }
+        "#;
+        let program = parse(source).unwrap();
+        let (_, warnings) = type_check_with_warnings(&program).unwrap();
+        assert!(warnings.is_empty(), "{:?}", warnings);
+
+        let mismatched_or = source.replace("Point { x: 0, .. } |", "Point { x, .. } |");
+        let error = compile_to_ast(&mismatched_or).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("Variable 'x' is not bound in every alternative"),
+            "{}",
+            error
+        );
+
+        let missing_fields = source.replace("Point { x: 0, .. } |", "Point { x: 0 } |");
+        let error = compile_to_ast(&missing_fields).unwrap_err();
+        assert!(
+            error.to_string().contains("add `..` to ignore them"),
+            "{}",
+            error
+        );
+
+        let integer_guard = source.replace("if x > 0 && y > 0", "if x");
+        let error = compile_to_ast(&integer_guard).unwrap_err();
+        assert!(
+            error.to_string().contains("Match guard must be a bool"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_fixed_size_arrays() {
+        let source = r#"
+            const SIZE: i32 = 4;
+
+            func sum(values: [f32; SIZE]) -> f32 {
+                let mut total = 0.0;
+                for value in values {
+                    total = total + value;
+                }
+                return total;
+            }
+
+            func main() -> f32 {
+                let weights: [f32; 4] = [0.5, 1.5, 2.5, 3.5];
+                let i = 2;
+                return sum(weights) + weights[3] + weights[i];
+            }
+        "#;
+        let (_, context) = compile_to_ast(source).unwrap();
+        // The length constant is folded into the parameter type
+        assert_eq!(
+            context.functions["sum"].to_string(),
+            "func([f32; 4]) -> f32"
+        );
+
+        // Constant indices are checked against the length in the type
+        let out_of_bounds = source.replace("weights[3]", "weights[SIZE]");
+        let error = compile_to_ast(&out_of_bounds).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("Array index 4 out of bounds for array of length 4"),
+            "{}",
+            error
+        );
+
+        let wrong_length =
+            source.replace("[f32; 4] = [0.5, 1.5, 2.5, 3.5]", "[f32; 4] = [0.5, 1.5]");
+        let error = compile_to_ast(&wrong_length).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("Type mismatch in variable 'weights'"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_raw_pointers_and_unsafe() {
+        let source = r#"
+            func copy4(dst: *mut f32, src: *f32) {
+                unsafe {
+                    let v = load_vector(src, f32x4);
+                    store_vector(dst, v);
+                }
+            }
+
+            func main() -> f32 {
+                let values: [f32; 8] = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+                let mut out: [f32; 8] = [0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+                let src: *f32 = &values;
+                let dst: *mut f32 = &out;
+                copy4(dst, src);
+                let second = unsafe { *src.offset(1); };
+                unsafe {
+                    *dst = second;
+                }
+                return second;
+            }
+        "#;
+        let (_, context) = compile_to_ast(source).unwrap();
+        assert_eq!(
+            context.functions["copy4"].to_string(),
+            "func(*mut f32, *f32) -> ()"
+        );
+
+        // Pointer operations are rejected outside `unsafe`
+        let deref_outside = source.replace("unsafe { *src.offset(1); }", "*src");
+        let error = compile_to_ast(&deref_outside).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("Dereferencing a raw pointer requires an unsafe block"),
+            "{}",
+            error
+        );
+
+        let offset_outside = source.replace("unsafe { *src.offset(1); }", "src.offset(1)");
+        let error = compile_to_ast(&offset_outside).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("Pointer arithmetic requires an unsafe block"),
+            "{}",
+            error
+        );
+
+        // Writes need a `*mut` pointer
+        let write_through_const = source.replace("*dst = second", "*src = second");
+        let error = compile_to_ast(&write_through_const).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("Cannot assign through immutable pointer *f32"),
+            "{}",
+            error
+        );
+
+        // Only references to `let mut` variables become `*mut` pointers
+        let immutable_target = source.replace("let mut out", "let out");
+        let error = compile_to_ast(&immutable_target).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("Type mismatch in variable 'dst'"),
+            "{}",
+            error
+        );
+
+        let load_wrong_type = source.replace("load_vector(src, f32x4)", "load_vector(src, i32x4)");
+        let error = compile_to_ast(&load_wrong_type).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("Vector load through *f32 does not match vector element type i32"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_extern_c_blocks() {
+        let source = r#"
+            extern "C" {
+                func labs(x: i64) -> i64;
+                func memset(dst: *mut u8, value: i32, count: i64) -> *mut u8;
+            }
+
+            func main() -> i64 {
+                return labs(0 - 42);
+            }
+        "#;
+        let (_, context) = compile_to_ast(source).unwrap();
+        assert_eq!(
+            context.functions["memset"].to_string(),
+            "func(*mut u8, i32, i64) -> *mut u8"
+        );
+
+        // Extern signatures are checked like any other function
+        let wrong_argument = source.replace("labs(0 - 42)", "labs(true)");
+        let error = compile_to_ast(&wrong_argument).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("Argument 1 of function 'labs': expected I64, got Bool"),
+            "{}",
+            error
+        );
+
+        let undeclared = source.replace("func labs(x: i64) -> i64;", "");
+        let error = compile_to_ast(&undeclared).unwrap_err();
+        assert!(
+            error.to_string().contains("Function 'labs' not found"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_export_attribute() {
+        let source = r#"
+            @export(name: "ea_sum")
+            func sum(values: *f32, len: i64) -> f32 {
+                let total: f32 = 0.0;
+                for (let i: i64 = 0; i < len; i += 1) {
+                    total = total + unsafe { *values.offset(i); };
+                }
+                return total;
+            }
+
+            @export
+            func clear(out: *mut f32) {
+                unsafe {
+                    *out = 0.0;
+                }
+            }
+
+            func helper() -> i32 {
+                return 1;
+            }
+        "#;
+        let (_program, context) = compile_to_ast(source).unwrap();
+        let symbols: Vec<&str> = context
+            .exports
+            .iter()
+            .map(|export| export.symbol.as_str())
+            .collect();
+        assert_eq!(symbols, vec!["ea_sum", "clear"]);
+        assert_eq!(
+            context.exports[0].params[1],
+            ("len".to_string(), EaType::I64)
+        );
+        assert_eq!(context.exports[1].return_type, EaType::Unit);
+
+        // Exported signatures must have C equivalents
+        let string_param = source.replace(
+            "func helper() -> i32",
+            "@export func helper(s: string) -> i32",
+        );
+        let error = compile_to_ast(&string_param).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("parameter 's' has type string, which has no C equivalent"),
+            "{}",
+            error
+        );
+
+        let duplicate = source.replace("@export\n", "@export(name: \"ea_sum\")\n");
+        let error = compile_to_ast(&duplicate).unwrap_err();
+        assert!(
+            error.to_string().contains("Symbol 'ea_sum' is exported more than once"),
+            "{}",
+            error
+        );
+
+        let bad_symbol = source.replace("\"ea_sum\"", "\"ea-sum\"");
+        let error = compile_to_ast(&bad_symbol).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("'ea-sum' is not a valid C symbol name"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_masked_simd_operations() {
+        let source = r#"
+            func clamp_tail(dst: *mut f32, src: *f32, limit: f32x4) {
+                unsafe {
+                    let positions = [0, 1, 2, 3]i32x4;
+                    let in_range = positions .< [3, 3, 3, 3]i32x4;
+                    let values = masked_load(src, in_range, [0.0, 0.0, 0.0, 0.0]f32x4);
+                    let clamped = select(values .> limit, limit, values);
+                    masked_store(dst, clamped, in_range);
+                }
+            }
+        "#;
+        let (_, context) = compile_to_ast(source).unwrap();
+        assert_eq!(
+            context.functions["clamp_tail"].to_string(),
+            "func(*mut f32, *f32, f32x4) -> ()"
+        );
+
+        // The mask needs one lane per vector lane
+        let wide_mask = source.replace(
+            "select(values .> limit, limit, values)",
+            "select([1, 0]i32x2, limit, values)",
+        );
+        let error = compile_to_ast(&wide_mask).unwrap_err();
+        assert!(
+            error.to_string().contains("select() mask has 2 lanes but f32x4 has 4"),
+            "{}",
+            error
+        );
+
+        let mixed_types = source.replace("limit, values)", "limit, positions)");
+        let error = compile_to_ast(&mixed_types).unwrap_err();
+        assert!(
+            error.to_string().contains(
+                "select() requires two SIMD vectors of the same type, got f32x4 and i32x4"
+            ),
+            "{}",
+            error
+        );
+
+        let const_store = source.replace("masked_store(dst,", "masked_store(src,");
+        let error = compile_to_ast(&const_store).unwrap_err();
+        assert!(
+            error.to_string().contains("masked_store() requires a *mut pointer"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_gather_scatter_shuffle() {
+        let source = r#"
+            func transpose(dst: *mut f32, src: *f32, a: f32x4, b: f32x4) -> f32x4 {
+                unsafe {
+                    let rows = [0, 4, 8, 12]i32x4;
+                    let column = gather(src, rows);
+                    scatter(dst, rows, column);
+                }
+                return shuffle(a, b, [0, 4, 1, 5]);
+            }
+        "#;
+        let (_, context) = compile_to_ast(source).unwrap();
+        assert_eq!(
+            context.functions["transpose"].to_string(),
+            "func(*mut f32, *f32, f32x4, f32x4) -> f32x4"
+        );
+
+        // Two f32x4 operands have lanes 0..=7
+        let out_of_range = source.replace("[0, 4, 1, 5]", "[0, 4, 1, 8]");
+        let error = compile_to_ast(&out_of_range).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("shuffle() lane index 8 is out of range for two f32x4 vectors"),
+            "{}",
+            error
+        );
+
+        let runtime_mask = source.replace("[0, 4, 1, 5]", "rows");
+        let error = compile_to_ast(&runtime_mask).unwrap_err();
+        assert!(
+            error.to_string().contains("shuffle() mask must be a constant"),
+            "{}",
+            error
+        );
+
+        let const_scatter = source.replace("scatter(dst,", "scatter(src,");
+        let error = compile_to_ast(&const_scatter).unwrap_err();
+        assert!(
+            error.to_string().contains("scatter() requires a *mut pointer"),
+            "{}",
+            error
+        );
+
+        let float_indices = source.replace("gather(src, rows)", "gather(src, a)");
+        let error = compile_to_ast(&float_indices).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("gather() indices must be an integer SIMD vector, got f32x4"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_native_simd_width() {
+        let source = r#"
+            func scale(dst: *mut f32, src: *f32, len: i32, factor: simd<f32>) -> i32 {
+                let step = lanes<f32>();
+                unsafe {
+                    for (let i: i32 = 0; i < len; i += step) {
+                        let v = load_vector(src.offset(i), simd<f32>);
+                        store_vector(dst.offset(i), v .* factor);
+                    }
+                }
+                return step;
+            }
+        "#;
+        let program = parse(source).unwrap();
+
+        // Baseline x86_64 has SSE, so simd<f32> is four lanes wide
+        let mut type_checker = TypeChecker::for_target("x86_64");
+        let context = type_checker.check_program(&program).unwrap();
+        let scale = &context.functions["scale"];
+        assert_eq!(scale.params[3].to_string(), "f32x4");
+
+        // Targets without vector registers have no width to pick
+        let mut type_checker = TypeChecker::for_target("riscv64");
+        assert!(type_checker.check_program(&program).is_err());
+        let error = &type_checker.get_errors()[0];
+        assert!(
+            error
+                .to_string()
+                .contains("simd<f32> has no vector width on target architecture riscv64"),
+            "{}",
+            error
+        );
+
+        let not_a_number = source.replace("factor: simd<f32>", "factor: simd<bool>");
+        let error = compile_to_ast(&not_a_number).unwrap_err();
+        assert!(
+            error.to_string().contains("simd<bool> is not a SIMD vector type"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_type_check_for_target() {
+        use crate::type_system::hardware::CompileTarget;
+
+        let program = parse("func square(v: simd<f32>) -> simd<f32> { return v .* v; }").unwrap();
+        let wide = parse("func square(v: f32x8) -> f32x8 { return v .* v; }").unwrap();
+
+        // NEON vectors are 128 bits wide, so f32x8 does not exist on aarch64
+        let aarch64 = CompileTarget {
+            triple: Some("aarch64-unknown-linux-gnu".to_string()),
+            ..CompileTarget::default()
+        };
+        let (context, _warnings) = type_check_for_target(&program, &aarch64).unwrap();
+        assert_eq!(context.functions["square"].params[0].to_string(), "f32x4");
+        let errors = type_check_for_target(&wide, &aarch64).unwrap_err();
+        assert!(
+            errors[0]
+                .to_string()
+                .contains("f32x8 is not supported on target architecture aarch64"),
+            "{:?}",
+            errors
+        );
+
+        // Features given with --target-features make wider vectors legal
+        let avx = CompileTarget {
+            triple: Some("x86_64-unknown-linux-gnu".to_string()),
+            features: Some("+avx".to_string()),
+            ..CompileTarget::default()
+        };
+        let (context, _warnings) = type_check_for_target(&program, &avx).unwrap();
+        assert_eq!(context.functions["square"].params[0].to_string(), "f32x8");
+        assert!(type_check_for_target(&wide, &avx).is_ok());
+    }
+
+    #[test]
+    fn test_target_clones() {
+        let source = r#"
+            @target_clones(avx512, avx2, sse4)
+            func sum_to(n: i32) -> i32 {
+                let sum: i32 = 0;
+                for (let i: i32 = 1; i <= n; i += 1) {
+                    sum += i;
+                }
+                return sum;
+            }
+        "#;
+        let program = parse(source).unwrap();
+        let mut type_checker = TypeChecker::for_target("x86_64");
+        assert!(type_checker.check_program(&program).is_ok());
+
+        // Other targets have no CPUID to dispatch on
+        let mut type_checker = TypeChecker::for_target("aarch64");
+        let error = type_checker.check_program(&program).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("which target architecture aarch64 does not have"),
+            "{}",
+            error
+        );
+
+        for (attribute, message) in [
+            ("@target_clones(avx1024)", "unknown feature level 'avx1024'"),
+            ("@target_clones(avx2, avx2)", "feature level 'avx2' is listed twice"),
+            ("@target_clones(level: avx2)", "expected a feature level"),
+            ("@target_clones()", "expected feature levels"),
+        ] {
+            let program = parse(&source.replace("@target_clones(avx512, avx2, sse4)", attribute))
+                .unwrap();
+            let mut type_checker = TypeChecker::for_target("x86_64");
+            assert!(type_checker.check_program(&program).is_err(), "{}", attribute);
+            let error = type_checker.get_errors()[0].to_string();
+            assert!(error.contains(message), "{}", error);
+        }
+
+        let program = parse("@target_clones(avx2) func main() -> i32 { return 0; }").unwrap();
+        let mut type_checker = TypeChecker::for_target("x86_64");
+        let error = type_checker.check_program(&program).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("main is called before clones can be resolved"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_overflow_arithmetic_builtins() {
+        let source = r#"
+            func mix(a: u8, b: u8) -> u8 {
+                let sum: u8 = wrapping_add(a, b);
+                return saturating_sub(sum, 10);
+            }
+
+            func brighten(pixels: u8x16, amount: u8x16) -> u8x16 {
+                return saturating_add(pixels, amount);
+            }
+
+            func main() -> i32 {
+                let samples = [100; 16]i8x16;
+                let louder = saturating_add(samples, samples);
+                let scaled: i64 = wrapping_mul(3000000000, 4);
+                return 0;
+            }
+        "#;
+        let (_, context) = compile_to_ast(source).unwrap();
+        assert_eq!(context.functions["mix"].to_string(), "func(u8, u8) -> u8");
+        assert_eq!(
+            context.functions["brighten"].to_string(),
+            "func(u8x16, u8x16) -> u8x16"
+        );
+
+        let float_operands =
+            source.replace("wrapping_mul(3000000000, 4)", "wrapping_mul(1.5, 2.0)");
+        let error = compile_to_ast(&float_operands).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("wrapping_mul() requires two integer or integer vector operands"),
+            "{}",
+            error
+        );
+
+        let one_operand = source.replace("saturating_sub(sum, 10)", "saturating_sub(sum)");
+        let error = compile_to_ast(&one_operand).unwrap_err();
+        assert!(
+            error.to_string().contains("saturating_sub() takes exactly 2 arguments, got 1"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_type_check_reports_all_errors() {
+        let source = r#"
+            func main() -> i32 {
+                let flag: i32 = true;
+                let value = undefined_name;
+                let doubled = value * 2;
+                if (doubled) {
+                    missing_function(doubled);
+                }
+                return "text";
+            }
+        "#;
+        let program = parse(source).unwrap();
+        let errors = type_check_with_errors(&program).unwrap_err();
+        let messages: Vec<String> = errors.iter().map(|error| error.to_string()).collect();
+
+        // Uses of the poisoned `value` and `doubled` are not reported again
+        assert_eq!(messages.len(), 4, "{:#?}", messages);
+        assert!(messages[0].contains("Type mismatch in variable 'flag'"));
+        assert!(messages[1].contains("undefined_name"));
+        assert!(messages[2].contains("missing_function"));
+        assert!(messages[3].contains("Return type mismatch"));
+
+        assert!(type_check_with_errors(&parse("func main() -> () { return; }").unwrap()).is_ok());
+    }
+
+    #[test]
+    fn test_break_continue_checking() {
+        let source = r#"
+            func main() -> () {
+                'outer: for (let i = 0; i < 10; i += 1) {
+                    let j = 0;
+                    while (j < 10) {
+                        if (j == i) { continue 'outer; }
+                        if (j > 5) { break; }
+                        j += 1;
+                    }
+                }
+                return;
+            }
+        "#;
+        assert!(compile_to_ast(source).is_ok());
+
+        let outside_loop = r#"
+            func main() -> () {
+                break;
+                return;
+            }
+        "#;
+        let error = compile_to_ast(outside_loop).unwrap_err();
+        assert!(
+            error.to_string().contains("'break' outside of a loop"),
+            "{}",
+            error
+        );
+
+        let unknown_label = r#"
+            func main() -> () {
+                while (true) {
+                    break 'missing;
+                }
+                return;
+            }
+        "#;
+        let error = compile_to_ast(unknown_label).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("Use of undeclared loop label 'missing' in 'break'"),
+            "{}",
+            error
+        );
+    }
 }
@@ -0,0 +1,436 @@
+//! Ahead-of-time output: object files, assembly, native executables and libraries.
+//!
+//! Executables and shared libraries are linked by the system C compiler driver
+//! (`cc`, or `$CC`) against the C runtime in `src/runtime/`; static libraries
+//! are archived with `ar` (or `$AR`) together with the runtime's objects. `build.rs` compiles the runtime
+//! into the static archive `libea_runtime.a`, which is embedded in the
+//! compiler so a deployed `ea` binary can link programs without the source tree.
+
+use crate::error::{CompileError, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The C runtime archive built by `build.rs`
+const RUNTIME_ARCHIVE: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/libea_runtime.a"));
+
+/// Environment variable that overrides the linker driver
+pub const LINKER_ENV: &str = "CC";
+
+/// Linker driver used when `$CC` is not set
+pub const DEFAULT_LINKER: &str = "cc";
+
+/// Environment variable that overrides the archiver used for static libraries
+pub const ARCHIVER_ENV: &str = "AR";
+
+/// Archiver used when `$AR` is not set
+pub const DEFAULT_ARCHIVER: &str = "ar";
+
+/// A kind of ahead-of-time output, as selected with `--emit=obj,asm,exe`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmitKind {
+    /// Relocatable object file (`.o`)
+    Object,
+    /// Target assembly (`.s`)
+    Assembly,
+    /// Linked output: a native executable, or a library for `--crate-type`
+    Executable,
+}
+
+impl EmitKind {
+    /// Parses a comma separated list such as `obj,asm,exe`.
+    pub fn parse_list(list: &str) -> Result<Vec<EmitKind>> {
+        let mut kinds = Vec::new();
+        for item in list
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+        {
+            let kind = match item {
+                "obj" => EmitKind::Object,
+                "asm" => EmitKind::Assembly,
+                "exe" => EmitKind::Executable,
+                other => {
+                    return Err(link_error(format!(
+                        "Unknown --emit kind '{}' (expected obj, asm or exe)",
+                        other
+                    )))
+                }
+            };
+            if !kinds.contains(&kind) {
+                kinds.push(kind);
+            }
+        }
+
+        if kinds.is_empty() {
+            return Err(link_error("--emit requires at least one kind".to_string()));
+        }
+        Ok(kinds)
+    }
+
+    /// Path of this output for the output name given with `-o`.
+    ///
+    /// Executables are written to the output name itself; objects and
+    /// assembly get a `.o` / `.s` extension.
+    pub fn output_path(&self, output: &Path) -> PathBuf {
+        match self {
+            EmitKind::Object => output.with_extension("o"),
+            EmitKind::Assembly => output.with_extension("s"),
+            EmitKind::Executable => output.to_path_buf(),
+        }
+    }
+}
+
+/// What the linked output is, as selected with `--crate-type`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CrateType {
+    /// Executable program with a `main` function
+    #[default]
+    Bin,
+    /// Shared library exporting the `@export` functions, for use from C
+    Cdylib,
+    /// Static library exporting the `@export` functions, for use from C
+    Staticlib,
+}
+
+impl CrateType {
+    /// Parses `bin`, `cdylib` or `staticlib`.
+    pub fn parse(name: &str) -> Result<CrateType> {
+        match name {
+            "bin" => Ok(CrateType::Bin),
+            "cdylib" => Ok(CrateType::Cdylib),
+            "staticlib" => Ok(CrateType::Staticlib),
+            other => Err(link_error(format!(
+                "Unknown --crate-type '{}' (expected bin, cdylib or staticlib)",
+                other
+            ))),
+        }
+    }
+
+    /// Whether the output is a library, which comes with a generated C header.
+    pub fn is_library(&self) -> bool {
+        *self != CrateType::Bin
+    }
+
+    /// Path of the linked output for the output name given with `-o`.
+    ///
+    /// A library name without an extension gets the platform's file name,
+    /// e.g. `build/kernels` becomes `build/libkernels.so` or `build/libkernels.a`.
+    pub fn output_path(&self, output: &Path) -> PathBuf {
+        let (prefix, suffix) = match self {
+            CrateType::Bin => return output.to_path_buf(),
+            CrateType::Cdylib => (std::env::consts::DLL_PREFIX, std::env::consts::DLL_SUFFIX),
+            CrateType::Staticlib => ("lib", ".a"),
+        };
+        match (output.extension(), output.file_name()) {
+            (None, Some(name)) => {
+                output.with_file_name(format!("{}{}{}", prefix, name.to_string_lossy(), suffix))
+            }
+            _ => output.to_path_buf(),
+        }
+    }
+
+    /// Path of the C header written next to a library, e.g. `build/kernels.h`.
+    pub fn header_path(&self, output: &Path) -> PathBuf {
+        output.with_extension("h")
+    }
+}
+
+/// How an executable or library is linked.
+#[derive(Debug, Clone, Default)]
+pub struct LinkOptions {
+    /// The kind of output to link, given with `--crate-type`
+    pub crate_type: CrateType,
+    /// Link statically (`-static`), so the binary has no shared library dependencies
+    pub static_link: bool,
+    /// Libraries defining `extern "C"` functions, given with `--link-lib`: a
+    /// name such as `png` (linked as `-lpng`) or a path to the library file
+    pub libraries: Vec<String>,
+    /// Directories searched for `libraries`, given with `--link-search`
+    pub search_paths: Vec<PathBuf>,
+}
+
+impl LinkOptions {
+    /// The shared library files of `libraries`, for loading them into a program
+    /// that is run without being linked (JIT or `lli`).
+    ///
+    /// A name is looked up as `lib<name>.so` (`.dylib` on macOS) in
+    /// `search_paths`; when it is not found there the bare file name is left to
+    /// the dynamic loader's own search path.
+    pub fn shared_library_paths(&self) -> Vec<PathBuf> {
+        self.libraries
+            .iter()
+            .map(|library| {
+                if is_library_path(library) {
+                    return PathBuf::from(library);
+                }
+                let file_name = format!(
+                    "{}{}{}",
+                    std::env::consts::DLL_PREFIX,
+                    library,
+                    std::env::consts::DLL_SUFFIX
+                );
+                self.search_paths
+                    .iter()
+                    .map(|dir| dir.join(&file_name))
+                    .find(|path| path.exists())
+                    .unwrap_or_else(|| PathBuf::from(file_name))
+            })
+            .collect()
+    }
+}
+
+/// Whether a `--link-lib` argument names a library file rather than a library
+/// to look up, e.g. `./libpng.so` rather than `png`.
+fn is_library_path(library: &str) -> bool {
+    let path = Path::new(library);
+    path.components().count() > 1 || path.extension().is_some()
+}
+
+/// Links `objects` with the C runtime into the executable `output`.
+pub fn link_executable(objects: &[PathBuf], output: &Path, options: &LinkOptions) -> Result<()> {
+    run_linker(objects, output, options, false)
+}
+
+/// Links `objects` with the C runtime into the shared library `output`.
+pub fn link_shared_library(
+    objects: &[PathBuf],
+    output: &Path,
+    options: &LinkOptions,
+) -> Result<()> {
+    run_linker(objects, output, options, true)
+}
+
+/// Archives `objects` and the objects of the C runtime into the static library
+/// `output`, so C programs only need to link the one archive.
+pub fn archive_static_library(objects: &[PathBuf], output: &Path) -> Result<()> {
+    let archiver = std::env::var(ARCHIVER_ENV).unwrap_or_else(|_| DEFAULT_ARCHIVER.to_string());
+    let runtime_dir = write_runtime_archive()?;
+    let runtime_archive = runtime_dir.path().join("libea_runtime.a");
+
+    // Unpack the runtime next to its archive and collect its members
+    run_archiver(
+        Command::new(&archiver)
+            .arg("x")
+            .arg(&runtime_archive)
+            .current_dir(runtime_dir.path()),
+        &archiver,
+        &runtime_archive,
+    )?;
+    let mut runtime_objects: Vec<PathBuf> = fs::read_dir(runtime_dir.path())
+        .map_err(|e| link_error(format!("Failed to read the unpacked runtime archive: {}", e)))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|extension| extension == "o"))
+        .collect();
+    runtime_objects.sort();
+
+    // `ar r` adds to an existing archive, so start from an empty one
+    if output.exists() {
+        fs::remove_file(output).map_err(|e| {
+            link_error(format!("Failed to replace '{}': {}", output.display(), e))
+        })?;
+    }
+    run_archiver(
+        Command::new(&archiver)
+            .arg("rcs")
+            .arg(output)
+            .args(objects)
+            .args(&runtime_objects),
+        &archiver,
+        output,
+    )
+}
+
+fn run_linker(
+    objects: &[PathBuf],
+    output: &Path,
+    options: &LinkOptions,
+    shared: bool,
+) -> Result<()> {
+    let linker = std::env::var(LINKER_ENV).unwrap_or_else(|_| DEFAULT_LINKER.to_string());
+    let runtime_dir = write_runtime_archive()?;
+    let runtime_archive = runtime_dir.path().join("libea_runtime.a");
+
+    let mut command = Command::new(&linker);
+    if shared {
+        command.arg("-shared");
+    }
+    command.args(objects).arg(&runtime_archive);
+    for dir in &options.search_paths {
+        command.arg(format!("-L{}", dir.display()));
+        // Let the output find shared libraries there without LD_LIBRARY_PATH
+        if !options.static_link {
+            command.arg(format!("-Wl,-rpath,{}", dir.display()));
+        }
+    }
+    for library in &options.libraries {
+        if is_library_path(library) {
+            command.arg(library);
+        } else {
+            command.arg(format!("-l{}", library));
+        }
+    }
+    command.arg("-lm");
+    if options.static_link && !shared {
+        command.arg("-static");
+    }
+    command.arg("-o").arg(output);
+
+    let result = command.output().map_err(|e| {
+        link_error(format!(
+            "Failed to run linker '{}' (set ${} to choose another): {}",
+            linker, LINKER_ENV, e
+        ))
+    })?;
+
+    if !result.status.success() {
+        return Err(link_error(format!(
+            "Linking '{}' failed:\n{}",
+            output.display(),
+            String::from_utf8_lossy(&result.stderr).trim_end()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Writes the embedded runtime archive as `libea_runtime.a` into a temporary directory.
+fn write_runtime_archive() -> Result<tempfile::TempDir> {
+    let runtime_dir = tempfile::tempdir().map_err(|e| {
+        link_error(format!("Failed to create a directory for the runtime archive: {}", e))
+    })?;
+    let runtime_archive = runtime_dir.path().join("libea_runtime.a");
+    fs::write(&runtime_archive, RUNTIME_ARCHIVE).map_err(|e| {
+        link_error(format!(
+            "Failed to write runtime archive '{}': {}",
+            runtime_archive.display(),
+            e
+        ))
+    })?;
+    Ok(runtime_dir)
+}
+
+fn run_archiver(command: &mut Command, archiver: &str, archive: &Path) -> Result<()> {
+    let result = command.output().map_err(|e| {
+        link_error(format!(
+            "Failed to run archiver '{}' (set ${} to choose another): {}",
+            archiver, ARCHIVER_ENV, e
+        ))
+    })?;
+
+    if !result.status.success() {
+        return Err(link_error(format!(
+            "Archiving '{}' failed:\n{}",
+            archive.display(),
+            String::from_utf8_lossy(&result.stderr).trim_end()
+        )));
+    }
+
+    Ok(())
+}
+
+fn link_error(message: String) -> CompileError {
+    CompileError::codegen_error(message, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_emit_kinds() {
+        assert_eq!(
+            EmitKind::parse_list("obj,asm,exe").unwrap(),
+            vec![EmitKind::Object, EmitKind::Assembly, EmitKind::Executable]
+        );
+        assert_eq!(
+            EmitKind::parse_list("exe,exe").unwrap(),
+            vec![EmitKind::Executable]
+        );
+        assert!(EmitKind::parse_list("").is_err());
+        assert!(EmitKind::parse_list("obj,bin").is_err());
+
+        let output = Path::new("build/app");
+        assert_eq!(
+            EmitKind::Object.output_path(output),
+            PathBuf::from("build/app.o")
+        );
+        assert_eq!(
+            EmitKind::Assembly.output_path(output),
+            PathBuf::from("build/app.s")
+        );
+        assert_eq!(
+            EmitKind::Executable.output_path(output),
+            PathBuf::from("build/app")
+        );
+    }
+
+    #[test]
+    fn test_crate_type_outputs() {
+        assert_eq!(CrateType::parse("cdylib").unwrap(), CrateType::Cdylib);
+        assert_eq!(CrateType::parse("staticlib").unwrap(), CrateType::Staticlib);
+        assert!(CrateType::parse("rlib").is_err());
+        assert!(!CrateType::Bin.is_library());
+
+        let output = Path::new("build/kernels");
+        assert_eq!(
+            CrateType::Bin.output_path(output),
+            PathBuf::from("build/kernels")
+        );
+        assert_eq!(
+            CrateType::Cdylib.output_path(output),
+            PathBuf::from(format!(
+                "build/{}kernels{}",
+                std::env::consts::DLL_PREFIX,
+                std::env::consts::DLL_SUFFIX
+            ))
+        );
+        assert_eq!(
+            CrateType::Staticlib.output_path(output),
+            PathBuf::from("build/libkernels.a")
+        );
+        // An explicit file name is kept as given
+        assert_eq!(
+            CrateType::Cdylib.output_path(Path::new("kernels.so")),
+            PathBuf::from("kernels.so")
+        );
+        assert_eq!(
+            CrateType::Staticlib.header_path(output),
+            PathBuf::from("build/kernels.h")
+        );
+    }
+
+    #[test]
+    fn test_shared_library_paths() {
+        let search_dir = tempfile::tempdir().unwrap();
+        let local_name = format!(
+            "{}local{}",
+            std::env::consts::DLL_PREFIX,
+            std::env::consts::DLL_SUFFIX
+        );
+        fs::write(search_dir.path().join(&local_name), b"").unwrap();
+
+        let options = LinkOptions {
+            libraries: vec![
+                "local".to_string(),
+                "system".to_string(),
+                "./vendor/libpng.so".to_string(),
+            ],
+            search_paths: vec![search_dir.path().to_path_buf()],
+            ..LinkOptions::default()
+        };
+        let paths = options.shared_library_paths();
+
+        // Found in a search directory, left to the dynamic loader, or given as a path
+        assert_eq!(paths[0], search_dir.path().join(local_name));
+        assert_eq!(
+            paths[1],
+            PathBuf::from(format!(
+                "{}system{}",
+                std::env::consts::DLL_PREFIX,
+                std::env::consts::DLL_SUFFIX
+            ))
+        );
+        assert_eq!(paths[2], PathBuf::from("./vendor/libpng.so"));
+    }
+}
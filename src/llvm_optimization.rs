@@ -6,10 +6,11 @@
 //! and compilation speed optimization.
 
 use crate::error::{CompileError, Result};
+use crate::type_system::hardware::CompileTarget;
 use inkwell::module::Module;
 use inkwell::passes::{PassManager, PassManagerBuilder};
 use inkwell::targets::{
-    CodeModel, FileType, InitializationConfig, RelocMode, Target, TargetMachine,
+    CodeModel, FileType, InitializationConfig, RelocMode, Target, TargetMachine, TargetTriple,
 };
 use inkwell::OptimizationLevel;
 use std::sync::{LazyLock, Mutex};
@@ -34,6 +35,8 @@ pub struct LLVMOptimizationConfig {
     pub enable_tail_call_optimization: bool,
     /// Enable SIMD optimization
     pub enable_simd_optimization: bool,
+    /// Target triple (e.g., "aarch64-unknown-linux-gnu"), or None for the host
+    pub target_triple: Option<String>,
     /// Target CPU architecture
     pub target_cpu: String,
     /// Target features (e.g., "+avx2", "+sse4.1")
@@ -42,6 +45,7 @@ pub struct LLVMOptimizationConfig {
 
 impl Default for LLVMOptimizationConfig {
     fn default() -> Self {
+        let host = CompileTarget::default();
         Self {
             optimization_level: OptimizationLevel::Default,
             enable_inlining: true,
@@ -51,12 +55,23 @@ impl Default for LLVMOptimizationConfig {
             enable_constant_propagation: true,
             enable_tail_call_optimization: true,
             enable_simd_optimization: true,
-            target_cpu: "x86-64".to_string(),
-            target_features: "+avx2,+sse4.1".to_string(),
+            target_triple: None,
+            target_cpu: host.llvm_cpu(),
+            target_features: host.llvm_features(),
         }
     }
 }
 
+impl LLVMOptimizationConfig {
+    /// Generate code for `target` instead of the host
+    pub fn with_target(mut self, target: &CompileTarget) -> Self {
+        self.target_triple = target.triple.clone();
+        self.target_cpu = target.llvm_cpu();
+        self.target_features = target.llvm_features();
+        self
+    }
+}
+
 /// LLVM optimization statistics
 #[derive(Debug, Default, Clone)]
 pub struct LLVMOptimizationStats {
@@ -268,12 +283,22 @@ impl LLVMOptimizer {
 
     /// Create optimized target machine
     pub fn create_target_machine(&self) -> Result<TargetMachine> {
-        // Initialize targets
-        Target::initialize_native(&InitializationConfig::default()).map_err(|e| {
-            CompileError::codegen_error(format!("Failed to initialize native target: {}", e), None)
-        })?;
-
-        let triple = TargetMachine::get_default_triple();
+        // Initialize targets: every one LLVM was built with when cross compiling
+        let triple = match &self.config.target_triple {
+            Some(triple) => {
+                Target::initialize_all(&InitializationConfig::default());
+                TargetTriple::create(triple)
+            }
+            None => {
+                Target::initialize_native(&InitializationConfig::default()).map_err(|e| {
+                    CompileError::codegen_error(
+                        format!("Failed to initialize native target: {}", e),
+                        None,
+                    )
+                })?;
+                TargetMachine::get_default_triple()
+            }
+        };
         let target = Target::from_triple(&triple).map_err(|e| {
             CompileError::codegen_error(format!("Failed to create target from triple: {}", e), None)
         })?;
@@ -362,8 +387,7 @@ pub fn apply_fast_optimization_preset() -> LLVMOptimizationConfig {
         enable_constant_propagation: true,
         enable_tail_call_optimization: false,
         enable_simd_optimization: false,
-        target_cpu: "x86-64".to_string(),
-        target_features: "+sse4.1".to_string(),
+        ..LLVMOptimizationConfig::default()
     }
 }
 
@@ -378,8 +402,7 @@ pub fn apply_emit_llvm_preset() -> LLVMOptimizationConfig {
         enable_constant_propagation: false,
         enable_tail_call_optimization: false,
         enable_simd_optimization: false,
-        target_cpu: "x86-64".to_string(),
-        target_features: "+sse4.1".to_string(),
+        ..LLVMOptimizationConfig::default()
     }
 }
 
@@ -394,8 +417,7 @@ pub fn apply_production_optimization_preset() -> LLVMOptimizationConfig {
         enable_constant_propagation: true,
         enable_tail_call_optimization: true,
         enable_simd_optimization: true,
-        target_cpu: "x86-64".to_string(),
-        target_features: "+avx2,+sse4.1,+bmi2".to_string(),
+        ..LLVMOptimizationConfig::default()
     }
 }
 
@@ -422,6 +444,22 @@ mod tests {
         assert!(production.enable_vectorization);
     }
 
+    #[test]
+    fn test_config_for_target() {
+        let target = CompileTarget {
+            triple: Some("aarch64-unknown-linux-gnu".to_string()),
+            ..CompileTarget::default()
+        };
+        let config = apply_emit_llvm_preset().with_target(&target);
+        assert_eq!(config.optimization_level, OptimizationLevel::None);
+        assert_eq!(
+            config.target_triple.as_deref(),
+            Some("aarch64-unknown-linux-gnu")
+        );
+        assert_eq!(config.target_cpu, "generic");
+        assert_eq!(config.target_features, "+neon");
+    }
+
     #[test]
     fn test_llvm_optimizer_creation() {
         let optimizer = LLVMOptimizer::new();
@@ -31,7 +31,7 @@ use crate::parser::Parser;
 #[cfg(feature = "lsp")]
 use crate::type_system::{TypeChecker, TypeContext};
 #[cfg(feature = "lsp")]
-use crate::{compile_to_ast, CompileError};
+use crate::{parse_with_errors, type_check_with_errors, CompileError};
 
 /// Performance analysis data for a function or expression
 #[cfg(feature = "lsp")]
@@ -153,17 +153,24 @@ impl EaLanguageServer {
             version,
         };
 
-        // Try to compile and analyze
-        match compile_to_ast(content) {
-            Ok((ast, type_context)) => {
+        // Try to compile and analyze, collecting every syntax or type error
+        let ast = match parse_with_errors(content) {
+            Ok(ast) => ast,
+            Err(errors) => {
+                state.errors = errors;
+                return state;
+            }
+        };
+        match type_check_with_errors(&ast) {
+            Ok(type_context) => {
                 state.ast = Some(ast.clone());
                 state.type_context = Some(type_context);
 
                 // Perform performance analysis
                 state.performance = Some(self.analyze_performance(&ast).await);
             }
-            Err(error) => {
-                state.errors.push(error);
+            Err(errors) => {
+                state.errors = errors;
             }
         }
 
@@ -199,7 +206,7 @@ impl EaLanguageServer {
                 analysis.estimated_memory_usage += 128 + (params.len() * 32); // Stack frame + parameters
 
                 // Analyze function body
-                if let Stmt::Block(statements) = body.as_ref() {
+                if let Stmt::Block(statements, _) = body.as_ref() {
                     for stmt in statements {
                         self.analyze_statement_performance(stmt, analysis);
                     }
@@ -240,11 +247,11 @@ impl EaLanguageServer {
                 }
             }
 
-            Stmt::Expression(expr) => {
+            Stmt::Expression(expr, _) => {
                 self.analyze_expression_performance(expr, analysis);
             }
 
-            Stmt::Block(statements) => {
+            Stmt::Block(statements, _) => {
                 // Block overhead for scope management
                 analysis.estimated_execution_time += 200;
                 analysis.estimated_memory_usage += 16;
@@ -258,6 +265,7 @@ impl EaLanguageServer {
                 condition,
                 then_branch,
                 else_branch,
+                ..
             } => {
                 // Branch overhead
                 analysis.estimated_execution_time += 1000;
@@ -293,7 +301,7 @@ impl EaLanguageServer {
                     });
             }
 
-            Stmt::Return(expr) => {
+            Stmt::Return(expr, _) => {
                 analysis.estimated_execution_time += 300; // Return overhead
 
                 if let Some(expr) = expr {
@@ -318,17 +326,17 @@ impl EaLanguageServer {
         use crate::ast::Expr;
 
         match expr {
-            Expr::Literal(_) => {
+            Expr::Literal(_, _) => {
                 // Literals are nearly free
                 analysis.estimated_execution_time += 10;
             }
 
-            Expr::Variable(_) => {
+            Expr::Variable(_, _) => {
                 // Variable access cost
                 analysis.estimated_execution_time += 50;
             }
 
-            Expr::Binary(left, op, right) => {
+            Expr::Binary(left, op, right, _) => {
                 // Binary operation cost
                 analysis.estimated_execution_time += 200;
 
@@ -360,7 +368,7 @@ impl EaLanguageServer {
                 }
             }
 
-            Expr::Call(func, args) => {
+            Expr::Call(func, args, _) => {
                 // Function call overhead
                 analysis.estimated_execution_time += 2000 + (args.len() as u64 * 100);
 
@@ -370,7 +378,7 @@ impl EaLanguageServer {
                 }
 
                 // Check for mathematical functions that could use SIMD
-                if let Expr::Variable(func_name) = func.as_ref() {
+                if let Expr::Variable(func_name, _) = func.as_ref() {
                     if ["sin", "cos", "sqrt", "abs", "max", "min"].contains(&func_name.as_str()) {
                         analysis.simd_opportunities.push(SIMDOptimization {
                             range: Range {
@@ -394,7 +402,7 @@ impl EaLanguageServer {
                 }
             }
 
-            Expr::Index(array, index) => {
+            Expr::Index(array, index, _) => {
                 analysis.estimated_execution_time += 300; // Array access cost
 
                 self.analyze_expression_performance(array, analysis);
@@ -461,26 +469,28 @@ impl EaLanguageServer {
                     },
                 )
             }
-            CompileError::TypeError { position, .. } => {
+            CompileError::TypeError { position, end, .. } => {
                 let pos = Self::ea_position_to_lsp(position);
-                (
-                    pos,
-                    Position {
+                let end_pos = match end {
+                    Some(end) => Self::ea_position_to_lsp(end),
+                    None => Position {
                         line: pos.line,
                         character: pos.character + 3,
                     },
-                )
+                };
+                (pos, end_pos)
             }
-            CompileError::CodeGenError { position, .. } => {
+            CompileError::CodeGenError { position, end, .. } => {
                 if let Some(pos) = position {
                     let lsp_pos = Self::ea_position_to_lsp(pos);
-                    (
-                        lsp_pos,
-                        Position {
+                    let end_pos = match end {
+                        Some(end) => Self::ea_position_to_lsp(end),
+                        None => Position {
                             line: lsp_pos.line,
                             character: lsp_pos.character + 5,
                         },
-                    )
+                    };
+                    (lsp_pos, end_pos)
                 } else {
                     // CodeGenError without position info, use document start
                     let pos = Position {
@@ -826,7 +836,7 @@ impl EaLanguageServer {
             }
 
             Stmt::VarDeclaration {
-                name,
+                pattern,
                 type_annotation,
                 ..
             } => {
@@ -835,17 +845,19 @@ impl EaLanguageServer {
                     .map(|ta| format!("{:?}", ta))
                     .unwrap_or_else(|| "inferred".to_string());
 
-                completions.push(CompletionItem {
-                    label: name.clone(),
-                    kind: Some(CompletionItemKind::VARIABLE),
-                    detail: Some(format!("var {}: {}", name, type_info)),
-                    documentation: Some(Documentation::String(format!("Variable '{}'", name))),
-                    insert_text: Some(name.clone()),
-                    ..Default::default()
-                });
+                for name in pattern.bound_names() {
+                    completions.push(CompletionItem {
+                        label: name.to_string(),
+                        kind: Some(CompletionItemKind::VARIABLE),
+                        detail: Some(format!("var {}: {}", name, type_info)),
+                        documentation: Some(Documentation::String(format!("Variable '{}'", name))),
+                        insert_text: Some(name.to_string()),
+                        ..Default::default()
+                    });
+                }
             }
 
-            Stmt::Block(statements) => {
+            Stmt::Block(statements, _) => {
                 for stmt in statements {
                     self.extract_symbols_from_statement(stmt, completions);
                 }
@@ -1,30 +1,42 @@
-use ea_compiler::{NAME, VERSION, init_config, get_config};
 #[cfg(feature = "llvm")]
 use ea_compiler::llvm_context_pool;
+use ea_compiler::{get_config, init_config, NAME, VERSION};
 use std::env;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process;
 use std::time::Instant;
 
+use ea_compiler::ast::Stmt;
+#[cfg(feature = "llvm")]
+use ea_compiler::codegen::CodegenOptions;
+use ea_compiler::diagnostics::{self, Diagnostic, ErrorFormat};
 use ea_compiler::incremental_compilation::initialize_default_incremental_compiler;
 use ea_compiler::jit_cache::initialize_default_jit_cache;
 use ea_compiler::jit_cached::jit_execute_cached;
+use ea_compiler::linker::{CrateType, EmitKind, LinkOptions};
 use ea_compiler::llvm_optimization::{
     apply_emit_llvm_preset, apply_fast_optimization_preset, apply_production_optimization_preset,
     initialize_default_llvm_optimizer, initialize_llvm_optimizer,
 };
 use ea_compiler::parallel_compilation::initialize_default_parallel_compiler;
+use ea_compiler::CompileError;
+#[cfg(feature = "llvm")]
+use ea_compiler::{compile_modules_and_execute, compile_modules_to_llvm};
+#[cfg(feature = "llvm")]
+use ea_compiler::{compile_modules_to_native, compile_to_native};
 #[cfg(feature = "llvm")]
 use ea_compiler::{compile_to_llvm, diagnose_jit_execution, smart_execute};
 
 use ea_compiler::memory_profiler::{generate_memory_report, reset_profiler, set_memory_limit};
 use ea_compiler::parser_optimization;
 use ea_compiler::resource_manager;
+use ea_compiler::type_system::hardware::CompileTarget;
 
 /// Command line arguments
 struct Args {
     input_file: Option<String>,
+    extra_input_files: Vec<String>, // Additional modules, importable by file stem
     output_file: Option<String>,
     emit_ast: bool,
     emit_tokens: bool,
@@ -45,6 +57,17 @@ struct Args {
     incremental_compilation: bool,
     parallel_compilation: bool,
     optimization_preset: Option<String>,
+    emit: Vec<EmitKind>,
+    crate_type: CrateType,
+    static_link: bool,
+    link_libraries: Vec<String>,
+    link_search_paths: Vec<PathBuf>,
+    bounds_checks: bool,
+    overflow_checks: bool,
+    target: Option<String>,
+    target_cpu: Option<String>,
+    target_features: Option<String>,
+    error_format: ErrorFormat,
 }
 
 impl Args {
@@ -52,6 +75,7 @@ impl Args {
         let args: Vec<String> = env::args().collect();
         let mut parsed = Args {
             input_file: None,
+            extra_input_files: Vec::new(),
             output_file: None,
             emit_ast: false,
             emit_tokens: false,
@@ -72,6 +96,17 @@ impl Args {
             incremental_compilation: false,
             parallel_compilation: false,
             optimization_preset: None,
+            emit: Vec::new(),
+            crate_type: CrateType::Bin,
+            static_link: false,
+            link_libraries: Vec::new(),
+            link_search_paths: Vec::new(),
+            bounds_checks: true,
+            overflow_checks: false,
+            target: None,
+            target_cpu: None,
+            target_features: None,
+            error_format: ErrorFormat::Human,
         };
 
         let mut i = 1;
@@ -95,12 +130,60 @@ impl Args {
                 "--llvm-optimization" => parsed.llvm_optimization = true,
                 "--incremental-compilation" => parsed.incremental_compilation = true,
                 "--parallel-compilation" => parsed.parallel_compilation = true,
+                "--static" => parsed.static_link = true,
+                "--no-bounds-checks" => parsed.bounds_checks = false,
+                "--overflow-checks" => parsed.overflow_checks = true,
                 "--optimization-preset" => {
                     i += 1;
                     if i < args.len() {
                         parsed.optimization_preset = Some(args[i].clone());
                     }
                 }
+                "--link-lib" => {
+                    if i + 1 < args.len() {
+                        parsed.link_libraries.push(args[i + 1].clone());
+                        i += 1;
+                    } else {
+                        eprintln!("Error: --link-lib requires a library name or path");
+                        process::exit(1);
+                    }
+                }
+                "--link-search" => {
+                    if i + 1 < args.len() {
+                        parsed.link_search_paths.push(PathBuf::from(&args[i + 1]));
+                        i += 1;
+                    } else {
+                        eprintln!("Error: --link-search requires a directory");
+                        process::exit(1);
+                    }
+                }
+                "--target" => {
+                    if i + 1 < args.len() {
+                        parsed.target = Some(args[i + 1].clone());
+                        i += 1;
+                    } else {
+                        eprintln!("Error: --target requires a target triple");
+                        process::exit(1);
+                    }
+                }
+                "--target-cpu" => {
+                    if i + 1 < args.len() {
+                        parsed.target_cpu = Some(args[i + 1].clone());
+                        i += 1;
+                    } else {
+                        eprintln!("Error: --target-cpu requires a CPU name");
+                        process::exit(1);
+                    }
+                }
+                "--target-features" => {
+                    if i + 1 < args.len() {
+                        parsed.target_features = Some(args[i + 1].clone());
+                        i += 1;
+                    } else {
+                        eprintln!("Error: --target-features requires a feature list");
+                        process::exit(1);
+                    }
+                }
                 "--output" | "-o" => {
                     if i + 1 < args.len() {
                         parsed.output_file = Some(args[i + 1].clone());
@@ -110,6 +193,33 @@ impl Args {
                         process::exit(1);
                     }
                 }
+                arg if arg.starts_with("--error-format=") => {
+                    match ErrorFormat::parse(&arg["--error-format=".len()..]) {
+                        Some(format) => parsed.error_format = format,
+                        None => {
+                            eprintln!("Error: Unknown {} (expected human or json)", arg);
+                            process::exit(1);
+                        }
+                    }
+                }
+                arg if arg.starts_with("--emit=") => {
+                    match EmitKind::parse_list(&arg["--emit=".len()..]) {
+                        Ok(kinds) => parsed.emit = kinds,
+                        Err(e) => {
+                            eprintln!("Error: {}", e);
+                            process::exit(1);
+                        }
+                    }
+                }
+                arg if arg.starts_with("--crate-type=") => {
+                    match CrateType::parse(&arg["--crate-type=".len()..]) {
+                        Ok(crate_type) => parsed.crate_type = crate_type,
+                        Err(e) => {
+                            eprintln!("Error: {}", e);
+                            process::exit(1);
+                        }
+                    }
+                }
                 arg if arg.starts_with('-') => {
                     eprintln!("Error: Unknown option '{}'", arg);
                     process::exit(1);
@@ -118,16 +228,63 @@ impl Args {
                     if parsed.input_file.is_none() {
                         parsed.input_file = Some(args[i].clone());
                     } else {
-                        eprintln!("Error: Multiple input files not supported");
-                        process::exit(1);
+                        parsed.extra_input_files.push(args[i].clone());
                     }
                 }
             }
             i += 1;
         }
 
+        if parsed.run && parsed.target.is_some() {
+            eprintln!("Error: --run executes on the host and cannot be combined with --target");
+            process::exit(1);
+        }
+
         parsed
     }
+
+    /// Native outputs to produce: those given with `--emit=`, or an executable
+    /// (a library with `--crate-type`) when `-o` or a library `--crate-type` is
+    /// given without another LLVM mode.
+    fn native_emit(&self) -> Vec<EmitKind> {
+        if !self.emit.is_empty() {
+            return self.emit.clone();
+        }
+        let other_llvm_mode =
+            self.emit_llvm || self.emit_llvm_only || self.run || self.diagnose_jit;
+        if (self.output_file.is_some() || self.crate_type.is_library()) && !other_llvm_mode {
+            vec![EmitKind::Executable]
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// The machine to compile for: the host unless `--target` is given.
+    fn compile_target(&self) -> CompileTarget {
+        CompileTarget {
+            triple: self.target.clone(),
+            cpu: self.target_cpu.clone(),
+            features: self.target_features.clone(),
+        }
+    }
+
+    #[cfg(feature = "llvm")]
+    fn codegen_options(&self) -> CodegenOptions {
+        CodegenOptions {
+            bounds_checks: self.bounds_checks,
+            overflow_checks: self.overflow_checks,
+            target: self.compile_target(),
+        }
+    }
+
+    fn link_options(&self) -> LinkOptions {
+        LinkOptions {
+            crate_type: self.crate_type,
+            static_link: self.static_link,
+            libraries: self.link_libraries.clone(),
+            search_paths: self.link_search_paths.clone(),
+        }
+    }
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -225,7 +382,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     match args.input_file.as_ref() {
-        Some(filename) => compile_file(filename, &args)?,
+        Some(filename) => {
+            if let Err(error) = compile_file(filename, &args) {
+                match error.downcast_ref::<CompileError>() {
+                    Some(error) => report_compile_error(error, filename, &args),
+                    None => return Err(error),
+                }
+            }
+        }
         None => {
             if env::args().len() == 1 {
                 // No arguments - show usage
@@ -245,7 +409,11 @@ fn print_help() {
     println!("A compiler for the Eä programming language");
     println!();
     println!("USAGE:");
-    println!("    ea [OPTIONS] <INPUT_FILE>");
+    println!("    ea [OPTIONS] <INPUT_FILE> [MODULE_FILES...]");
+    println!();
+    println!("    The first file is the entry module. Further files can be imported");
+    println!("    by their file stem; other imports are resolved relative to the");
+    println!("    importing file (a::b -> a/b.ea or a/b/mod.ea).");
     println!();
     println!("OPTIONS:");
     println!("    -h, --help          Print help information");
@@ -253,7 +421,20 @@ fn print_help() {
     println!("    -v, --verbose       Enable verbose output");
     println!("    -q, --quiet         Suppress diagnostic messages");
     println!("    -r, --run           Compile and execute immediately (JIT)");
-    println!("    -o, --output FILE   Specify output file");
+    println!("    -o, --output FILE   Specify output file (builds a native executable)");
+    println!("        --emit=KINDS    Write native output: obj, asm and/or exe (comma separated)");
+    println!("        --crate-type=TYPE Link a bin (default), cdylib or staticlib with a C header");
+    println!("        --static        Link the executable statically");
+    println!("        --link-lib LIB  Link or load library LIB (a name or a path) for extern \"C\" functions");
+    println!("        --link-search DIR Search DIR for --link-lib libraries");
+    println!("        --no-bounds-checks Omit runtime array bounds checks from native output");
+    println!("        --overflow-checks Panic on integer overflow in native output");
+    println!("        --target TRIPLE Generate obj/asm for another target, e.g. aarch64-unknown-linux-gnu");
+    println!("        --target-cpu CPU Generate code for CPU, e.g. skylake or cortex-a72");
+    println!(
+        "        --target-features FEATURES Enable or disable target features, e.g. +avx2,-fma"
+    );
+    println!("        --error-format=FORMAT Print diagnostics as human (default) or json");
     println!("        --emit-tokens   Print tokenization output");
     println!("        --emit-ast      Print AST output");
     println!("        --emit-llvm     Print LLVM IR output (with diagnostics)");
@@ -272,9 +453,22 @@ fn print_help() {
     println!("EXAMPLES:");
     println!("    ea hello.ea                         # Compile hello.ea");
     println!("    ea --run fibonacci.ea               # Compile and execute immediately");
+    println!("    ea --run main.ea geometry.ea        # Compile a program made of two modules");
+    println!("    ea hello.ea -o hello                # Build a native executable");
+    println!(
+        "    ea --emit=obj,asm,exe --static app.ea -o app  # Object, assembly and static binary"
+    );
+    println!("    ea --run --link-search ./lib --link-lib png app.ea  # Call into libpng");
+    println!(
+        "    ea --target aarch64-unknown-linux-gnu --emit=obj,asm app.ea -o app  # Cross compile"
+    );
+    println!(
+        "    ea --crate-type=cdylib kernels.ea -o build/kernels  # libkernels.so and kernels.h"
+    );
     println!("    ea --emit-ast program.ea            # Show AST for program.ea");
     println!("    ea --emit-llvm-only program.ea | lli  # Pipe clean IR to lli");
     println!("    ea --verbose fibonacci.ea           # Compile with verbose output");
+    println!("    ea --error-format=json app.ea       # Machine-readable diagnostics for CI");
     println!("    ea --test                           # Run compiler self-tests");
 }
 
@@ -287,11 +481,68 @@ fn print_usage() {
     println!("A systems programming language with built-in SIMD and memory safety");
     println!();
     println!("USAGE:");
-    println!("    ea [OPTIONS] <INPUT_FILE>");
+    println!("    ea [OPTIONS] <INPUT_FILE> [MODULE_FILES...]");
     println!();
     println!("Try 'ea --help' for more information.");
 }
 
+/// Whether `program` is compiled through the module resolver: it imports other
+/// modules, or further module files were given on the command line.
+fn uses_module_graph(program: &[Stmt], args: &Args) -> bool {
+    !args.extra_input_files.is_empty()
+        || program
+            .iter()
+            .any(|stmt| matches!(stmt, Stmt::ImportDeclaration { .. }))
+}
+
+/// Reports the error that stopped compiling `filename`, then exits.
+fn report_compile_error(error: &CompileError, filename: &str, args: &Args) -> ! {
+    let source = fs::read_to_string(filename).unwrap_or_default();
+    let program = ea_compiler::parse(&source).unwrap_or_default();
+    if uses_module_graph(&program, args) {
+        // The error may point into any module, so the entry file is not quoted
+        report_errors(std::slice::from_ref(error), "", "", &[], args);
+    }
+    report_errors(
+        std::slice::from_ref(error),
+        filename,
+        &source,
+        &program,
+        args,
+    )
+}
+
+/// Prints `errors` as diagnostics in the selected format and exits with a failure status.
+fn report_errors(
+    errors: &[CompileError],
+    filename: &str,
+    source: &str,
+    program: &[Stmt],
+    args: &Args,
+) -> ! {
+    let diagnostics: Vec<Diagnostic> = errors
+        .iter()
+        .map(|error| Diagnostic::from_error(error, source).suggest_declared_names(program))
+        .collect();
+    eprint!(
+        "{}",
+        diagnostics::render_report(&diagnostics, filename, source, args.error_format)
+    );
+    process::exit(1);
+}
+
+/// Prints `warnings` as diagnostics in the selected format; compilation carries on.
+fn report_warnings(warnings: &[CompileError], filename: &str, source: &str, args: &Args) {
+    let diagnostics: Vec<Diagnostic> = warnings
+        .iter()
+        .map(|warning| Diagnostic::warning_from_error(warning, source))
+        .collect();
+    eprint!(
+        "{}",
+        diagnostics::render_report(&diagnostics, filename, source, args.error_format)
+    );
+}
+
 fn compile_file(filename: &str, args: &Args) -> Result<(), Box<dyn std::error::Error>> {
     // Determine output mode
     let show_diagnostics = !args.quiet && !args.emit_llvm_only;
@@ -318,7 +569,10 @@ fn compile_file(filename: &str, args: &Args) -> Result<(), Box<dyn std::error::E
 
     // Tokenization
 
-    let tokens = ea_compiler::tokenize(&source)?;
+    let tokens = match ea_compiler::tokenize(&source) {
+        Ok(tokens) => tokens,
+        Err(error) => report_errors(&[error], filename, &source, &[], args),
+    };
 
     if args.emit_tokens {
         println!("📋 Tokens:");
@@ -331,8 +585,11 @@ fn compile_file(filename: &str, args: &Args) -> Result<(), Box<dyn std::error::E
         println!();
     }
 
-    // Parsing
-    let program = ea_compiler::parse(&source)?;
+    // Parsing, reporting every syntax error found rather than only the first
+    let program = match ea_compiler::parse_with_errors(&source) {
+        Ok(program) => program,
+        Err(errors) => report_errors(&errors, filename, &source, &[], args),
+    };
 
     if args.emit_ast {
         println!("🌳 Abstract Syntax Tree:");
@@ -342,6 +599,12 @@ fn compile_file(filename: &str, args: &Args) -> Result<(), Box<dyn std::error::E
         println!();
     }
 
+    // Programs that import other modules, or were given several input files,
+    // are compiled through the module resolver instead of the single-source pipeline
+    if uses_module_graph(&program, args) {
+        return compile_module_graph(filename, args, start_time);
+    }
+
     // Type checking
     eprintln!("🎯 Starting type checking...");
     if verbose_mode {
@@ -361,7 +624,14 @@ fn compile_file(filename: &str, args: &Args) -> Result<(), Box<dyn std::error::E
         }
         (Vec::new(), context) // Return empty program vector for streaming
     } else {
-        ea_compiler::compile_to_ast(&source)?
+        // Report every type error found rather than only the first
+        match ea_compiler::type_check_for_target(&program, &args.compile_target()) {
+            Ok((context, warnings)) => {
+                report_warnings(&warnings, filename, &source, args);
+                (program, context)
+            }
+            Err(errors) => report_errors(&errors, filename, &source, &program, args),
+        }
     };
 
     if verbose_mode {
@@ -426,6 +696,34 @@ fn compile_file(filename: &str, args: &Args) -> Result<(), Box<dyn std::error::E
             eprintln!("📄 Generated LLVM IR: {}.ll", output_name);
         }
 
+        // Ahead-of-time native output
+        let native_emit = args.native_emit();
+        if !native_emit.is_empty() {
+            let output_name = args
+                .output_file
+                .as_ref()
+                .map(|s| s.as_str())
+                .unwrap_or_else(|| {
+                    Path::new(filename)
+                        .file_stem()
+                        .and_then(|s| s.to_str())
+                        .unwrap_or("output")
+                });
+
+            let written = compile_to_native(
+                &source,
+                Path::new(output_name),
+                &native_emit,
+                &args.codegen_options(),
+                &args.link_options(),
+            )?;
+            if verbose_mode {
+                for path in &written {
+                    eprintln!("📦 Wrote {}", path.display());
+                }
+            }
+        }
+
         // Handle JIT diagnostics
         if args.diagnose_jit {
             if show_diagnostics {
@@ -472,7 +770,7 @@ fn compile_file(filename: &str, args: &Args) -> Result<(), Box<dyn std::error::E
                         .unwrap_or("output")
                 });
 
-            match smart_execute(&source, output_name) {
+            match smart_execute(&source, output_name, &args.link_options()) {
                 Ok(exit_code) => {
                     if verbose_mode {
                         eprintln!(
@@ -500,7 +798,12 @@ fn compile_file(filename: &str, args: &Args) -> Result<(), Box<dyn std::error::E
 
     #[cfg(not(feature = "llvm"))]
     {
-        if args.emit_llvm || args.emit_llvm_only || args.run || args.diagnose_jit {
+        if args.emit_llvm
+            || args.emit_llvm_only
+            || args.run
+            || args.diagnose_jit
+            || !args.native_emit().is_empty()
+        {
             eprintln!("⚠️  LLVM code generation not available (compile with --features=llvm)");
         }
     }
@@ -540,6 +843,126 @@ fn compile_file(filename: &str, args: &Args) -> Result<(), Box<dyn std::error::E
     Ok(())
 }
 
+/// Compiles a program made of several modules: the entry file plus everything
+/// it imports and any extra files given on the command line.
+fn compile_module_graph(
+    filename: &str,
+    args: &Args,
+    start_time: Instant,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let show_diagnostics = !args.quiet && !args.emit_llvm_only;
+    let verbose_mode = args.verbose && show_diagnostics;
+
+    let extra_files: Vec<PathBuf> = args.extra_input_files.iter().map(PathBuf::from).collect();
+    for file in &extra_files {
+        if !file.exists() {
+            eprintln!("Error: File '{}' not found", file.display());
+            process::exit(1);
+        }
+    }
+
+    let (modules, context) = ea_compiler::compile_modules_to_ast_for_target(
+        Path::new(filename),
+        &extra_files,
+        &args.compile_target(),
+    )?;
+
+    if verbose_mode {
+        eprintln!("📦 Resolved {} modules:", modules.len());
+        for module in &modules {
+            eprintln!("   {} ({})", module.name, module.path.display());
+        }
+        eprintln!("   Functions: {}", context.functions.len());
+    }
+
+    #[cfg(feature = "llvm")]
+    {
+        let output_name = args.output_file.as_deref().unwrap_or_else(|| {
+            Path::new(filename)
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("output")
+        });
+
+        if args.emit_llvm || args.emit_llvm_only || verbose_mode {
+            compile_modules_to_llvm(&modules, &context, output_name)?;
+
+            let ir_file = format!("{}.ll", output_name);
+            if Path::new(&ir_file).exists() {
+                let ir_content = fs::read_to_string(&ir_file)?;
+
+                if args.emit_llvm_only {
+                    print!("{}", ir_content);
+                } else if args.emit_llvm {
+                    println!("🔧 LLVM IR:");
+                    println!("{}", ir_content);
+                }
+            }
+
+            if verbose_mode {
+                eprintln!("📄 Generated LLVM IR: {}.ll", output_name);
+            }
+        }
+
+        let native_emit = args.native_emit();
+        if !native_emit.is_empty() {
+            let written = compile_modules_to_native(
+                &modules,
+                &context,
+                Path::new(output_name),
+                &native_emit,
+                &args.codegen_options(),
+                &args.link_options(),
+            )?;
+            if verbose_mode {
+                for path in &written {
+                    eprintln!("📦 Wrote {}", path.display());
+                }
+            }
+        }
+
+        if args.run {
+            if show_diagnostics {
+                eprintln!("🚀 Executing program...");
+            }
+
+            match compile_modules_and_execute(&modules, &context, output_name, &args.link_options())
+            {
+                Ok(exit_code) => {
+                    if exit_code != 0 {
+                        process::exit(exit_code);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("❌ Runtime error: {}", e);
+                    process::exit(1);
+                }
+            }
+        }
+    }
+
+    #[cfg(not(feature = "llvm"))]
+    {
+        if args.emit_llvm
+            || args.emit_llvm_only
+            || args.run
+            || args.diagnose_jit
+            || !args.native_emit().is_empty()
+        {
+            eprintln!("⚠️  LLVM code generation not available (compile with --features=llvm)");
+        }
+    }
+
+    if verbose_mode {
+        eprintln!(
+            "✅ Compilation completed in {:.2}ms",
+            start_time.elapsed().as_secs_f64() * 1000.0
+        );
+    }
+
+    Ok(())
+}
+
 fn run_builtin_tests() {
     println!("🧪 Running built-in compiler tests...");
     println!();
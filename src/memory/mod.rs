@@ -75,10 +75,11 @@ fn analyze_statement(stmt: &Stmt, analysis: &mut MemoryAnalysis, position: &mut
     *position += 1;
 
     match stmt {
-        Stmt::VarDeclaration { name, type_annotation, initializer } => {
+        Stmt::VarDeclaration { pattern, type_annotation, initializer, .. } => {
             let size = estimate_type_size(type_annotation);
             let region_type = classify_memory_region(size, type_annotation);
             let access_pattern = analyze_access_pattern(initializer);
+            let name = pattern.to_string();
 
             let var_info = VariableInfo {
                 name: name.clone(),
@@ -117,7 +118,7 @@ fn analyze_statement(stmt: &Stmt, analysis: &mut MemoryAnalysis, position: &mut
         Stmt::While { body, .. } => {
             analyze_statement(body, analysis, position);
         }
-        Stmt::Block(stmts) => {
+        Stmt::Block(stmts, _) => {
             for stmt in stmts {
                 analyze_statement(stmt, analysis, position);
             }
@@ -167,8 +168,8 @@ fn classify_memory_region(size: usize, _type_annotation: &Option<TypeAnnotation>
 /// Analyze how a variable is accessed
 fn analyze_access_pattern(initializer: &Option<Expr>) -> AccessPattern {
     match initializer {
-        Some(Expr::Literal(Literal::Vector { .. })) => AccessPattern::Sequential,
-        Some(Expr::Literal(Literal::String(_))) => AccessPattern::WriteOnce,
+        Some(Expr::Literal(Literal::Vector { .. }, _)) => AccessPattern::Sequential,
+        Some(Expr::Literal(Literal::String(_), _)) => AccessPattern::WriteOnce,
         _ => AccessPattern::Random,
     }
 }
@@ -229,7 +230,8 @@ pub fn print_memory_analysis(analysis: &MemoryAnalysis) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ast::{Stmt, Expr};
+    use crate::ast::{Expr, Pattern, Stmt};
+    use crate::lexer::Span;
 
     #[test]
     fn test_memory_analysis_creation() {
@@ -271,7 +273,10 @@ mod tests {
 
     #[test]
     fn test_access_pattern_analysis() {
-        let pattern = analyze_access_pattern(&Some(Expr::Literal(Literal::String("test".to_string()))));
+        let pattern = analyze_access_pattern(&Some(Expr::Literal(
+            Literal::String("test".to_string()),
+            Span::default(),
+        )));
         assert_eq!(pattern, AccessPattern::WriteOnce);
         
         let pattern = analyze_access_pattern(&None);
@@ -283,22 +288,35 @@ mod tests {
         // Create a simple program with variable declarations
         let program = vec![
             Stmt::VarDeclaration {
-                name: "stack_var".to_string(),
-                type_annotation: Some(TypeAnnotation { name: "i32".to_string(), is_mutable: false }),
-                initializer: Some(Expr::Literal(Literal::Integer(42))),
+                pattern: Pattern::Variable("stack_var".to_string()),
+                is_mutable: false,
+                type_annotation: Some(TypeAnnotation {
+                    name: "i32".to_string(),
+                    is_mutable: false,
+                }),
+                initializer: Some(Expr::Literal(Literal::Integer(42), Span::default())),
+                span: Span::default(),
             },
             Stmt::VarDeclaration {
-                name: "working_data".to_string(),
-                type_annotation: Some(TypeAnnotation { name: "[i32; 4]".to_string(), is_mutable: false }),
-                initializer: Some(Expr::Literal(Literal::Vector {
-                    elements: vec![
-                        Literal::Integer(1),
-                        Literal::Integer(2),
-                        Literal::Integer(3),
-                        Literal::Integer(4),
-                    ],
-                    vector_type: None,
-                })),
+                pattern: Pattern::Variable("working_data".to_string()),
+                is_mutable: false,
+                type_annotation: Some(TypeAnnotation {
+                    name: "[i32; 4]".to_string(),
+                    is_mutable: false,
+                }),
+                initializer: Some(Expr::Literal(
+                    Literal::Vector {
+                        elements: vec![
+                            Literal::Integer(1),
+                            Literal::Integer(2),
+                            Literal::Integer(3),
+                            Literal::Integer(4),
+                        ],
+                        vector_type: None,
+                    },
+                    Span::default(),
+                )),
+                span: Span::default(),
             },
         ];
 
@@ -0,0 +1,276 @@
+//! Module resolution for multi-file Eä programs.
+//!
+//! Maps `import`/`use`/`from` paths such as `math::vector` onto source files,
+//! parses every reachable module once, and returns them in dependency order
+//! (dependencies before the modules that import them) so later phases can
+//! check and generate each module with its imports already available.
+
+use crate::ast::Stmt;
+use crate::error::{CompileError, Result};
+use crate::lexer::Position;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File extension used by Eä source files
+pub const SOURCE_EXTENSION: &str = "ea";
+
+/// A parsed source file together with its module name.
+#[derive(Debug, Clone)]
+pub struct ResolvedModule {
+    /// Module path as written in imports, e.g. `math::vector`
+    pub name: String,
+    /// Source file the module was loaded from
+    pub path: PathBuf,
+    /// Parsed statements of the module
+    pub program: Vec<Stmt>,
+    /// The entry module keeps its symbol names unmangled
+    pub is_root: bool,
+    /// Name of the module each import path of this module resolved to
+    pub import_targets: HashMap<String, String>,
+}
+
+impl ResolvedModule {
+    /// Module paths this module imports, in source order.
+    pub fn imports(&self) -> Vec<&str> {
+        self.program
+            .iter()
+            .filter_map(|stmt| match stmt {
+                Stmt::ImportDeclaration { module_path, .. } => Some(module_path.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Returns the linker-visible name of `name` defined in module `module_path`.
+///
+/// `math::vector` + `dot` becomes `math__vector__dot`.
+pub fn mangle_symbol(module_path: &str, name: &str) -> String {
+    format!("{}__{}", module_path.replace("::", "__"), name)
+}
+
+/// Returns the name of the module `module_path` refers to in `imports`, the
+/// import targets of the module doing the lookup.
+pub fn import_target<'a>(imports: &'a HashMap<String, String>, module_path: &'a str) -> &'a str {
+    imports
+        .get(module_path)
+        .map(|name| name.as_str())
+        .unwrap_or(module_path)
+}
+
+/// Resolves module paths to files and loads the module graph.
+pub struct ModuleResolver {
+    search_paths: Vec<PathBuf>,
+    /// Modules given explicitly (e.g. extra files on the command line), by module name
+    explicit_files: HashMap<String, PathBuf>,
+    modules: Vec<ResolvedModule>,
+    /// Loaded modules by canonical file path
+    loaded: HashMap<PathBuf, usize>,
+    in_progress: Vec<(PathBuf, String)>,
+    /// Canonical path of the entry file
+    root: Option<PathBuf>,
+}
+
+impl ModuleResolver {
+    /// Creates a resolver with no extra search paths.
+    pub fn new() -> Self {
+        Self {
+            search_paths: Vec::new(),
+            explicit_files: HashMap::new(),
+            modules: Vec::new(),
+            loaded: HashMap::new(),
+            in_progress: Vec::new(),
+            root: None,
+        }
+    }
+
+    /// Adds a directory that is searched after the importing file's directory.
+    pub fn add_search_path<P: AsRef<Path>>(&mut self, path: P) {
+        self.search_paths.push(path.as_ref().to_path_buf());
+    }
+
+    /// Registers a source file as a module named after its file stem.
+    pub fn add_file<P: AsRef<Path>>(&mut self, path: P) -> Result<String> {
+        let path = path.as_ref();
+        let name = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .ok_or_else(|| {
+                Self::resolve_error(format!("Invalid module file name '{}'", path.display()))
+            })?
+            .to_string();
+        self.explicit_files.insert(name.clone(), path.to_path_buf());
+        Ok(name)
+    }
+
+    /// Finds the file for `module_path` as imported from `importer_dir`.
+    ///
+    /// `a::b` is looked up as `a/b.ea` and then `a/b/mod.ea`, first relative to
+    /// the importing file and then in each search path.
+    pub fn resolve_file(&self, module_path: &str, importer_dir: &Path) -> Result<PathBuf> {
+        if let Some(path) = self.explicit_files.get(module_path) {
+            return Ok(path.clone());
+        }
+
+        let relative: PathBuf = module_path.split("::").collect();
+        let candidates =
+            std::iter::once(importer_dir).chain(self.search_paths.iter().map(|p| p.as_path()));
+
+        for base in candidates {
+            let file = base.join(&relative).with_extension(SOURCE_EXTENSION);
+            if file.is_file() {
+                return Ok(file);
+            }
+            let dir_module = base.join(&relative).join(format!("mod.{}", SOURCE_EXTENSION));
+            if dir_module.is_file() {
+                return Ok(dir_module);
+            }
+        }
+
+        Err(Self::resolve_error(format!(
+            "Cannot find module '{}' (looked for '{}.{}' next to the importing file and in the search paths)",
+            module_path,
+            relative.display(),
+            SOURCE_EXTENSION
+        )))
+    }
+
+    /// Loads `entry` and every module it transitively imports.
+    ///
+    /// Explicitly added files that are not imported are loaded as well, so
+    /// every file given on the command line is checked and compiled.
+    pub fn load_program<P: AsRef<Path>>(mut self, entry: P) -> Result<Vec<ResolvedModule>> {
+        let entry = entry.as_ref();
+        self.root = Some(Self::canonical_path(entry)?);
+        let root_name = entry
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or("main")
+            .to_string();
+
+        let mut extra: Vec<(String, PathBuf)> = self
+            .explicit_files
+            .iter()
+            .filter(|(_, path)| fs::canonicalize(path).ok() != self.root)
+            .map(|(name, path)| (name.clone(), path.clone()))
+            .collect();
+        extra.sort();
+        for (name, path) in extra {
+            self.load_module(&name, &path)?;
+        }
+
+        self.load_module(&root_name, entry)?;
+        Ok(self.modules)
+    }
+
+    /// Loads the module at `path` unless the same file is already loaded, and
+    /// returns the name the module is known by.
+    fn load_module(&mut self, name: &str, path: &Path) -> Result<String> {
+        let canonical = Self::canonical_path(path)?;
+        if let Some(&index) = self.loaded.get(&canonical) {
+            return Ok(self.modules[index].name.clone());
+        }
+
+        if let Some(start) = self.in_progress.iter().position(|(p, _)| *p == canonical) {
+            let mut cycle: Vec<&str> = self.in_progress[start..]
+                .iter()
+                .map(|(_, name)| name.as_str())
+                .collect();
+            cycle.push(name);
+            return Err(Self::resolve_error(format!(
+                "Circular import: {}",
+                cycle.join(" -> ")
+            )));
+        }
+
+        let source = fs::read_to_string(path).map_err(|e| {
+            Self::resolve_error(format!(
+                "Failed to read module '{}' from '{}': {}",
+                name,
+                path.display(),
+                e
+            ))
+        })?;
+        let program = crate::parse(&source)?;
+
+        // Different files imported under the same path (e.g. `util` from two
+        // directories) still need distinct symbol prefixes
+        let name = self.unused_module_name(name);
+        self.in_progress.push((canonical.clone(), name.clone()));
+
+        let importer_dir = path
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+            .to_path_buf();
+        let mut import_targets = HashMap::new();
+        for stmt in &program {
+            if let Stmt::ImportDeclaration { module_path, .. } = stmt {
+                let dependency = self.resolve_file(module_path, &importer_dir)?;
+                let target = self.load_module(module_path, &dependency)?;
+                import_targets.insert(module_path.clone(), target);
+            }
+        }
+
+        self.in_progress.pop();
+        self.loaded.insert(canonical.clone(), self.modules.len());
+        self.modules.push(ResolvedModule {
+            name: name.clone(),
+            path: path.to_path_buf(),
+            program,
+            is_root: self.root.as_ref() == Some(&canonical),
+            import_targets,
+        });
+
+        Ok(name)
+    }
+
+    /// Returns `name`, or `name` with a numeric suffix if a different file
+    /// already uses it.
+    fn unused_module_name(&self, name: &str) -> String {
+        let taken = |candidate: &str| {
+            self.modules.iter().any(|m| m.name == candidate)
+                || self.in_progress.iter().any(|(_, n)| n == candidate)
+        };
+        let mut candidate = name.to_string();
+        let mut suffix = 2;
+        while taken(&candidate) {
+            candidate = format!("{}_{}", name, suffix);
+            suffix += 1;
+        }
+        candidate
+    }
+
+    fn canonical_path(path: &Path) -> Result<PathBuf> {
+        fs::canonicalize(path).map_err(|e| {
+            Self::resolve_error(format!(
+                "Failed to read module file '{}': {}",
+                path.display(),
+                e
+            ))
+        })
+    }
+
+    fn resolve_error(message: String) -> CompileError {
+        CompileError::parse_error(message, Position::new(0, 0, 0))
+    }
+}
+
+impl Default for ModuleResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Loads a multi-file program rooted at `entry`, with `extra_files` available
+/// as modules named after their file stems.
+pub fn load_modules<P: AsRef<Path>>(
+    entry: P,
+    extra_files: &[PathBuf],
+) -> Result<Vec<ResolvedModule>> {
+    let mut resolver = ModuleResolver::new();
+    for file in extra_files {
+        resolver.add_file(file)?;
+    }
+    resolver.load_program(entry)
+}
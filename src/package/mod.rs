@@ -3,6 +3,7 @@
 //! This module provides dependency resolution, package building, and integrated
 //! benchmarking with a focus on performance characteristics and optimization targets.
 
+use crate::linker::{CrateType, LinkOptions};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -1761,25 +1762,40 @@ impl PackageManager {
     fn create_library(
         &self,
         objects: &[PathBuf],
-        _target: &BuildTarget,
+        target: &BuildTarget,
     ) -> Result<PathBuf, PackageError> {
-        // Simulate library creation
-        Ok(PathBuf::from(format!(
-            "target/release/lib{}.a",
-            objects.len()
-        )))
+        let library = self.library_output_path(target, CrateType::Staticlib)?;
+        crate::linker::archive_static_library(objects, &library)
+            .map_err(|e| PackageError::BuildFailed(e.to_string()))?;
+        Ok(library)
     }
 
     fn create_dynamic_library(
         &self,
         objects: &[PathBuf],
-        _target: &BuildTarget,
+        target: &BuildTarget,
     ) -> Result<PathBuf, PackageError> {
-        // Simulate dynamic library creation
-        Ok(PathBuf::from(format!(
-            "target/release/lib{}.so",
-            objects.len()
-        )))
+        let library = self.library_output_path(target, CrateType::Cdylib)?;
+        let options = LinkOptions {
+            crate_type: CrateType::Cdylib,
+            ..LinkOptions::default()
+        };
+        crate::linker::link_shared_library(objects, &library, &options)
+            .map_err(|e| PackageError::BuildFailed(e.to_string()))?;
+        Ok(library)
+    }
+
+    /// `target/release/lib<name>.a` or `.so` for a library target.
+    fn library_output_path(
+        &self,
+        target: &BuildTarget,
+        crate_type: CrateType,
+    ) -> Result<PathBuf, PackageError> {
+        let output_dir = std::path::Path::new("target/release");
+        std::fs::create_dir_all(output_dir).map_err(|e| {
+            PackageError::BuildFailed(format!("Failed to create output directory: {}", e))
+        })?;
+        Ok(crate_type.output_path(&output_dir.join(&target.name)))
     }
 
     fn create_benchmark(
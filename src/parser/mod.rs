@@ -8,15 +8,51 @@
 
 use crate::{
     ast::{
-        BinaryOp, EnumVariant, Expr, Literal, MatchArm, Parameter, Pattern, ReductionOp, SIMDExpr,
-        SIMDOperator, SIMDVectorType, Stmt, TypeAnnotation, UnaryOp,
+        Attribute, AttributeParam, AttributeValue, BinaryOp, EnumVariant, Expr, ExternFunction,
+        FieldPattern, ImportItem, Literal, MatchArm, Parameter, Pattern, ReductionOp, SIMDExpr,
+        SIMDOperator, SIMDVectorType, Stmt, TraitMethod, TypeAnnotation, UnaryOp,
     }, // Added Pattern and MatchArm imports
     error::{CompileError, Result},
-    lexer::{Position, Token, TokenKind}, // Re-added Position for error recovery
+    lexer::{Position, Span, Token, TokenKind}, // Re-added Position for error recovery
     memory_profiler::{check_memory_limit, record_memory_usage, CompilationPhase},
     parser_optimization::{enter_parse_recursion, exit_parse_recursion, time_parsing_operation},
 };
 
+/// Keywords and built-in type names, the candidates for keyword typo suggestions
+pub const KEYWORDS: &[&str] = &[
+    "func",
+    "let",
+    "if",
+    "else",
+    "while",
+    "for",
+    "return",
+    "struct",
+    "enum",
+    "match",
+    "true",
+    "false",
+    "vectorize",
+    "unroll",
+    "align",
+    "reduce",
+    "i8",
+    "i16",
+    "i32",
+    "i64",
+    "u8",
+    "u16",
+    "u32",
+    "u64",
+    "f32",
+    "f64",
+    "bool",
+    "string",
+    "f32x4",
+    "i32x4",
+    "f64x2",
+];
+
 /// Error suggestions for common mistakes
 #[derive(Debug, Clone)]
 pub struct ErrorSuggestion {
@@ -167,10 +203,22 @@ impl Parser {
 
     /// Parses a declaration statement (function, variable, or regular statement).
     fn declaration(&mut self) -> Result<Stmt> {
+        if self.check(&TokenKind::At) {
+            return self.attributed_function_declaration();
+        }
+
         if self.match_tokens(&[TokenKind::Func]) {
             return self.function_declaration("function");
         }
 
+        if self.check(&TokenKind::Comptime) && self.peek_next().kind == TokenKind::Func {
+            return self.comptime_function_declaration();
+        }
+
+        if self.match_tokens(&[TokenKind::Const]) {
+            return self.const_declaration();
+        }
+
         if self.match_tokens(&[TokenKind::Struct]) {
             return self.struct_declaration();
         }
@@ -179,16 +227,233 @@ impl Parser {
             return self.enum_declaration();
         }
 
+        if self.match_tokens(&[TokenKind::Trait]) {
+            return self.trait_declaration();
+        }
+
+        if self.match_tokens(&[TokenKind::Impl]) {
+            return self.impl_declaration();
+        }
+
+        if self.match_tokens(&[TokenKind::Extern]) {
+            return self.extern_block();
+        }
+
         if self.match_tokens(&[TokenKind::Let]) {
             return self.var_declaration();
         }
 
+        if self.match_tokens(&[TokenKind::Import]) {
+            return self.import_declaration();
+        }
+
+        if self.match_tokens(&[TokenKind::Use]) {
+            return self.use_declaration();
+        }
+
+        if self.match_tokens(&[TokenKind::From]) {
+            return self.from_import_declaration();
+        }
+
         self.statement()
     }
 
     /// Parses a function declaration.
     fn function_declaration(&mut self, kind: &str) -> Result<Stmt> {
+        let start = self.previous().position.clone();
+        let (name, type_params, parameters, return_type) = self.function_signature(kind)?;
+
+        // Parse function body
+        self.consume(
+            TokenKind::LeftBrace,
+            format!("Expected '{{' before {kind} body"),
+        )?;
+
+        let body = self.block()?;
+
+        Ok(Stmt::FunctionDeclaration {
+            name,
+            type_params,
+            params: parameters,
+            return_type,
+            body: Box::new(body),
+            attributes: Vec::new(), // Note: Attributes not yet implemented
+            span: self.span_from(&start),
+        })
+    }
+
+    /// Parses attributes such as `@optimize(unroll: 4)` and the function they annotate.
+    fn attributed_function_declaration(&mut self) -> Result<Stmt> {
+        let start = self.peek().position.clone();
+        let mut attributes = Vec::new();
+        while self.match_tokens(&[TokenKind::At]) {
+            attributes.push(self.attribute()?);
+        }
+
+        self.consume(
+            TokenKind::Func,
+            "Expected 'func' after attributes".to_string(),
+        )?;
+
+        match self.function_declaration("function")? {
+            Stmt::FunctionDeclaration {
+                name,
+                type_params,
+                params,
+                return_type,
+                body,
+                ..
+            } => Ok(Stmt::FunctionDeclaration {
+                name,
+                type_params,
+                params,
+                return_type,
+                body,
+                attributes,
+                span: self.span_from(&start),
+            }),
+            other => Ok(other),
+        }
+    }
+
+    /// Parses `comptime func ...`, a function that is only evaluated at compile time.
+    fn comptime_function_declaration(&mut self) -> Result<Stmt> {
+        let start = self.peek().position.clone();
+        self.consume(TokenKind::Comptime, "Expected 'comptime'".to_string())?;
+        self.consume(
+            TokenKind::Func,
+            "Expected 'func' after 'comptime'".to_string(),
+        )?;
+
+        let mut function = self.function_declaration("function")?;
+        if let Stmt::FunctionDeclaration {
+            attributes, span, ..
+        } = &mut function
+        {
+            attributes.push(Attribute::comptime());
+            *span = self.span_from(&start);
+        }
+        Ok(function)
+    }
+
+    /// Parses one attribute after its `@`: `name`, `name(key: value, ...)` or
+    /// `name(value, ...)`.
+    fn attribute(&mut self) -> Result<Attribute> {
+        let name = self.attribute_word("Expected attribute name after '@'".to_string())?;
+
+        let mut params = Vec::new();
+        if self.match_tokens(&[TokenKind::LeftParen]) {
+            if !self.check(&TokenKind::RightParen) {
+                loop {
+                    // Positional parameters have no key: @target_clones(avx2, sse4)
+                    let key = if self.peek_next().kind == TokenKind::Colon {
+                        let key =
+                            self.attribute_word("Expected attribute parameter name".to_string())?;
+                        self.consume(
+                            TokenKind::Colon,
+                            "Expected ':' after attribute parameter name".to_string(),
+                        )?;
+                        key
+                    } else {
+                        String::new()
+                    };
+                    let value = self.attribute_value()?;
+                    params.push(AttributeParam { key, value });
+
+                    if !self.match_tokens(&[TokenKind::Comma]) {
+                        break;
+                    }
+                }
+            }
+            self.consume(
+                TokenKind::RightParen,
+                "Expected ')' after attribute parameters".to_string(),
+            )?;
+        }
+
+        Ok(Attribute { name, params })
+    }
+
+    fn attribute_value(&mut self) -> Result<AttributeValue> {
+        let value = match &self.peek().kind {
+            TokenKind::Integer(n) => AttributeValue::Integer(*n),
+            TokenKind::Float(n) => AttributeValue::Float(*n),
+            TokenKind::StringLiteral(s) => AttributeValue::String(s.clone()),
+            TokenKind::True => AttributeValue::Boolean(true),
+            TokenKind::False => AttributeValue::Boolean(false),
+            // Identifiers may name constants; they are resolved where the attribute is used
+            _ => {
+                return Ok(AttributeValue::Identifier(
+                    self.attribute_word("Expected attribute value".to_string())?,
+                ))
+            }
+        };
+        self.advance();
+        Ok(value)
+    }
+
+    /// Consumes an identifier or a keyword used as a word inside an attribute.
+    fn attribute_word(&mut self, message: String) -> Result<String> {
+        let kind = self.peek().kind.clone();
+        if let TokenKind::Identifier(name) = kind {
+            self.advance();
+            return Ok(name);
+        }
+        if crate::lexer::tokens::is_keyword(&kind)
+            || crate::lexer::tokens::is_simd_keyword(&kind)
+            || crate::lexer::tokens::is_hardware_feature(&kind)
+        {
+            self.advance();
+            return Ok(kind.to_string());
+        }
+
+        Err(CompileError::parse_error(
+            message,
+            self.peek().position.clone(),
+        ))
+    }
+
+    /// Parses a constant declaration: `const N: i32 = 16;`
+    fn const_declaration(&mut self) -> Result<Stmt> {
+        let start = self.previous().position.clone();
+        let name = self.consume_identifier("Expected constant name".to_string())?;
+
+        self.consume(
+            TokenKind::Colon,
+            format!("Expected ':' and a type after constant '{}'", name),
+        )?;
+        let type_name = self.consume_type_name("Expected constant type".to_string())?;
+
+        self.consume(
+            TokenKind::Assign,
+            format!("Expected '=' after type of constant '{}'", name),
+        )?;
+        let value = self.expression()?;
+
+        self.consume(
+            TokenKind::Semicolon,
+            "Expected ';' after constant declaration".to_string(),
+        )?;
+
+        Ok(Stmt::ConstDeclaration {
+            name,
+            type_annotation: TypeAnnotation {
+                name: type_name,
+                is_mutable: false,
+            },
+            value,
+            span: self.span_from(&start),
+        })
+    }
+
+    /// Parses a function signature: name, type parameters, parameters and return type.
+    #[allow(clippy::type_complexity)]
+    fn function_signature(
+        &mut self,
+        kind: &str,
+    ) -> Result<(String, Vec<String>, Vec<Parameter>, Option<TypeAnnotation>)> {
         let name = self.consume_identifier(format!("Expected {kind} name"))?;
+        let type_params = self.type_parameters()?;
 
         self.consume(
             TokenKind::LeftParen,
@@ -201,6 +466,21 @@ impl Parser {
                 // Parse parameter name
                 let param_name = self.consume_identifier("Expected parameter name".to_string())?;
 
+                // A leading `self` is the method receiver and has the implementing type
+                if param_name == "self" && parameters.is_empty() && !self.check(&TokenKind::Colon) {
+                    parameters.push(Parameter {
+                        name: param_name,
+                        type_annotation: TypeAnnotation {
+                            name: "Self".to_string(),
+                            is_mutable: false,
+                        },
+                    });
+                    if !self.match_tokens(&[TokenKind::Comma]) {
+                        break;
+                    }
+                    continue;
+                }
+
                 // Parse parameter type
                 self.consume(
                     TokenKind::Colon,
@@ -241,12 +521,10 @@ impl Parser {
         // Parse optional return type
         let return_type = if self.match_tokens(&[TokenKind::Arrow]) {
             let is_mutable = self.match_tokens(&[TokenKind::Mut]);
-            if self.match_tokens(&[TokenKind::LeftParen]) {
-                // Unit type ()
-                self.consume(
-                    TokenKind::RightParen,
-                    "Expected ')' for unit return type".to_string(),
-                )?;
+            if self.check(&TokenKind::LeftParen) && self.peek_next().kind == TokenKind::RightParen {
+                // Unit type (); other parenthesized types are tuples
+                self.advance();
+                self.advance();
                 Some(TypeAnnotation {
                     name: "()".to_string(),
                     is_mutable,
@@ -262,26 +540,166 @@ impl Parser {
             None
         };
 
-        // Parse function body
+        Ok((name, type_params, parameters, return_type))
+    }
+
+    /// Parses an extern block: `extern "C" { func png_read(path: string) -> i32; }`
+    fn extern_block(&mut self) -> Result<Stmt> {
+        let start = self.previous().position.clone();
+        let abi = match self.peek().kind.clone() {
+            TokenKind::StringLiteral(abi) => {
+                self.advance();
+                abi
+            }
+            _ => {
+                return Err(CompileError::parse_error(
+                    "Expected ABI string after 'extern', e.g. extern \"C\"".to_string(),
+                    self.peek().position.clone(),
+                ))
+            }
+        };
+        if abi != "C" {
+            return Err(CompileError::parse_error(
+                format!("Unsupported ABI \"{}\"; only \"C\" is supported", abi),
+                self.previous().position.clone(),
+            ));
+        }
+
         self.consume(
             TokenKind::LeftBrace,
-            format!("Expected '{{' before {kind} body"),
+            "Expected '{' after extern ABI".to_string(),
         )?;
 
-        let body = self.block()?;
+        let mut functions = Vec::new();
+        while !self.check(&TokenKind::RightBrace) && !self.is_at_end() {
+            self.consume(
+                TokenKind::Func,
+                "Expected 'func' in extern block".to_string(),
+            )?;
+            let (name, type_params, params, return_type) = self.function_signature("function")?;
+            if !type_params.is_empty() {
+                return Err(CompileError::parse_error(
+                    format!("Extern function '{}' cannot have type parameters", name),
+                    self.previous().position.clone(),
+                ));
+            }
+            self.consume(
+                TokenKind::Semicolon,
+                "Expected ';' after extern function signature".to_string(),
+            )?;
 
-        Ok(Stmt::FunctionDeclaration {
+            functions.push(ExternFunction {
+                name,
+                params,
+                return_type,
+            });
+        }
+
+        self.consume(
+            TokenKind::RightBrace,
+            "Expected '}' after extern block".to_string(),
+        )?;
+
+        Ok(Stmt::ExternBlock {
+            abi,
+            functions,
+            span: self.span_from(&start),
+        })
+    }
+
+    /// Parses a trait declaration: `trait Shape { func area(self) -> f32; }`
+    fn trait_declaration(&mut self) -> Result<Stmt> {
+        let start = self.previous().position.clone();
+        let name = self.consume_identifier("Expected trait name".to_string())?;
+
+        self.consume(
+            TokenKind::LeftBrace,
+            "Expected '{' after trait name".to_string(),
+        )?;
+
+        let mut methods = Vec::new();
+        while !self.check(&TokenKind::RightBrace) && !self.is_at_end() {
+            self.consume(
+                TokenKind::Func,
+                "Expected 'func' in trait body".to_string(),
+            )?;
+            let (method_name, type_params, params, return_type) =
+                self.function_signature("method")?;
+            if !type_params.is_empty() {
+                return Err(CompileError::parse_error(
+                    format!("Trait method '{}' cannot have type parameters", method_name),
+                    self.previous().position.clone(),
+                ));
+            }
+            self.consume(
+                TokenKind::Semicolon,
+                "Expected ';' after trait method signature".to_string(),
+            )?;
+
+            methods.push(TraitMethod {
+                name: method_name,
+                params,
+                return_type,
+            });
+        }
+
+        self.consume(
+            TokenKind::RightBrace,
+            "Expected '}' after trait body".to_string(),
+        )?;
+
+        Ok(Stmt::TraitDeclaration {
             name,
-            params: parameters,
-            return_type,
-            body: Box::new(body),
-            attributes: Vec::new(), // Note: Attributes not yet implemented
+            methods,
+            span: self.span_from(&start),
+        })
+    }
+
+    /// Parses an impl block: `impl Point { ... }` or `impl Shape for Circle { ... }`
+    fn impl_declaration(&mut self) -> Result<Stmt> {
+        let start = self.previous().position.clone();
+        let name =
+            self.consume_type_name("Expected type or trait name after 'impl'".to_string())?;
+
+        let (trait_name, type_name) = if self.match_tokens(&[TokenKind::For]) {
+            let type_name = self.consume_type_name("Expected type name after 'for'".to_string())?;
+            (Some(name), type_name)
+        } else {
+            (None, name)
+        };
+
+        self.consume(
+            TokenKind::LeftBrace,
+            "Expected '{' after impl type".to_string(),
+        )?;
+
+        let mut methods = Vec::new();
+        while !self.check(&TokenKind::RightBrace) && !self.is_at_end() {
+            self.consume(
+                TokenKind::Func,
+                "Expected 'func' in impl body".to_string(),
+            )?;
+            methods.push(self.function_declaration("method")?);
+        }
+
+        self.consume(
+            TokenKind::RightBrace,
+            "Expected '}' after impl body".to_string(),
+        )?;
+
+        Ok(Stmt::ImplBlock {
+            trait_name,
+            type_name,
+            methods,
+            span: self.span_from(&start),
         })
     }
 
     /// Parses a struct declaration.
     fn struct_declaration(&mut self) -> Result<Stmt> {
+        let start = self.previous().position.clone();
         let name = self.consume_identifier("Expected struct name".to_string())?;
+        let type_params = self.type_parameters()?;
 
         self.consume(
             TokenKind::LeftBrace,
@@ -325,11 +743,46 @@ impl Parser {
             "Expected '}' after struct fields".to_string(),
         )?;
 
-        Ok(Stmt::StructDeclaration { name, fields })
+        Ok(Stmt::StructDeclaration {
+            name,
+            type_params,
+            fields,
+            span: self.span_from(&start),
+        })
+    }
+
+    /// Parses an optional type parameter list: `<T, U>`.
+    fn type_parameters(&mut self) -> Result<Vec<String>> {
+        let mut type_params = Vec::new();
+        if !self.match_tokens(&[TokenKind::Less]) {
+            return Ok(type_params);
+        }
+
+        loop {
+            let param = self.consume_identifier("Expected type parameter name".to_string())?;
+            if type_params.contains(&param) {
+                return Err(CompileError::parse_error(
+                    format!("Duplicate type parameter '{}'", param),
+                    self.previous().position.clone(),
+                ));
+            }
+            type_params.push(param);
+
+            if !self.match_tokens(&[TokenKind::Comma]) {
+                break;
+            }
+        }
+
+        self.consume(
+            TokenKind::Greater,
+            "Expected '>' after type parameters".to_string(),
+        )?;
+        Ok(type_params)
     }
 
     /// Parses an enum declaration.
     fn enum_declaration(&mut self) -> Result<Stmt> {
+        let start = self.previous().position.clone();
         let name = self.consume_identifier("Expected enum name".to_string())?;
 
         self.consume(
@@ -392,13 +845,18 @@ impl Parser {
             "Expected '}' after enum variants".to_string(),
         )?;
 
-        Ok(Stmt::EnumDeclaration { name, variants })
+        Ok(Stmt::EnumDeclaration {
+            name,
+            variants,
+            span: self.span_from(&start),
+        })
     }
 
     /// Parses a variable declaration.
     fn var_declaration(&mut self) -> Result<Stmt> {
+        let start = self.previous().position.clone();
         let is_mutable = self.match_tokens(&[TokenKind::Mut]);
-        let name = self.consume_identifier("Expected variable name".to_string())?;
+        let pattern = self.binding_pattern()?;
 
         // Parse optional type annotation
         let type_annotation = if self.match_tokens(&[TokenKind::Colon]) {
@@ -424,43 +882,277 @@ impl Parser {
         )?;
 
         Ok(Stmt::VarDeclaration {
-            name,
+            pattern,
+            is_mutable,
             type_annotation,
             initializer,
+            span: self.span_from(&start),
         })
     }
 
-    /// Parses a regular statement.
-    fn statement(&mut self) -> Result<Stmt> {
-        if self.match_tokens(&[TokenKind::Return]) {
-            return self.return_statement();
+    /// Parses what a `let` binds: a variable name, `_`, or a tuple of those
+    /// to destructure, e.g. `(quotient, remainder)`.
+    fn binding_pattern(&mut self) -> Result<Pattern> {
+        if self.match_tokens(&[TokenKind::LeftParen]) {
+            return self.parse_tuple_pattern(Self::binding_pattern);
         }
 
-        if self.match_tokens(&[TokenKind::LeftBrace]) {
-            return self.block();
+        let name = self.consume_identifier("Expected variable name".to_string())?;
+        if name == "_" {
+            Ok(Pattern::Wildcard)
+        } else {
+            Ok(Pattern::Variable(name))
         }
+    }
 
-        if self.match_tokens(&[TokenKind::If]) {
-            return self.if_statement();
-        }
+    /// Parses a whole-module import: `import math::vec;` or `import math::vec as v;`
+    fn import_declaration(&mut self) -> Result<Stmt> {
+        let start = self.previous().position.clone();
+        let module_path = self.module_path()?;
 
-        if self.match_tokens(&[TokenKind::While]) {
-            return self.while_statement();
-        }
+        let alias = if self.match_tokens(&[TokenKind::As]) {
+            Some(self.consume_identifier("Expected module alias after 'as'".to_string())?)
+        } else {
+            None
+        };
 
-        if self.match_tokens(&[TokenKind::For]) {
-            return self.for_statement();
-        }
+        self.consume(
+            TokenKind::Semicolon,
+            "Expected ';' after import".to_string(),
+        )?;
 
-        if self.match_tokens(&[TokenKind::Match]) {
+        Ok(Stmt::ImportDeclaration {
+            module_path,
+            items: Vec::new(),
+            alias,
+            span: self.span_from(&start),
+        })
+    }
+
+    /// Parses a use declaration: `use math::vec::dot;`, `use math::vec::dot as d;`
+    /// or `use math::vec::{dot, cross as x};`
+    fn use_declaration(&mut self) -> Result<Stmt> {
+        let start = self.previous().position.clone();
+        let mut segments =
+            vec![self.consume_identifier("Expected module name after 'use'".to_string())?];
+        let mut items = Vec::new();
+
+        while self.match_tokens(&[TokenKind::DoubleColon]) {
+            if self.match_tokens(&[TokenKind::LeftBrace]) {
+                items = self.import_items(&TokenKind::RightBrace)?;
+                self.consume(
+                    TokenKind::RightBrace,
+                    "Expected '}' after imported names".to_string(),
+                )?;
+                break;
+            }
+            segments.push(self.consume_identifier("Expected name after '::'".to_string())?);
+        }
+
+        if items.is_empty() {
+            // `use a::b::item [as alias];` - the last segment is the imported item
+            if segments.len() < 2 {
+                return Err(CompileError::parse_error(
+                    "Expected 'module::item' after 'use'".to_string(),
+                    self.previous().position.clone(),
+                ));
+            }
+            let name = segments.pop().unwrap();
+            let alias = if self.match_tokens(&[TokenKind::As]) {
+                Some(self.consume_identifier("Expected alias after 'as'".to_string())?)
+            } else {
+                None
+            };
+            items.push(ImportItem { name, alias });
+        }
+
+        self.consume(
+            TokenKind::Semicolon,
+            "Expected ';' after use declaration".to_string(),
+        )?;
+
+        Ok(Stmt::ImportDeclaration {
+            module_path: segments.join("::"),
+            items,
+            alias: None,
+            span: self.span_from(&start),
+        })
+    }
+
+    /// Parses a from-import: `from math::vec import dot, cross as x;`
+    fn from_import_declaration(&mut self) -> Result<Stmt> {
+        let start = self.previous().position.clone();
+        let module_path = self.module_path()?;
+
+        self.consume(
+            TokenKind::Import,
+            "Expected 'import' after module path".to_string(),
+        )?;
+
+        let items = self.import_items(&TokenKind::Semicolon)?;
+
+        self.consume(
+            TokenKind::Semicolon,
+            "Expected ';' after import".to_string(),
+        )?;
+
+        Ok(Stmt::ImportDeclaration {
+            module_path,
+            items,
+            alias: None,
+            span: self.span_from(&start),
+        })
+    }
+
+    /// Parses a module path: `name` or `name::name::...`
+    fn module_path(&mut self) -> Result<String> {
+        let mut segments = vec![self.consume_identifier("Expected module name".to_string())?];
+        while self.match_tokens(&[TokenKind::DoubleColon]) {
+            segments.push(self.consume_identifier("Expected module name after '::'".to_string())?);
+        }
+        Ok(segments.join("::"))
+    }
+
+    /// Parses a comma-separated list of `name [as alias]` up to (but not including) `terminator`.
+    fn import_items(&mut self, terminator: &TokenKind) -> Result<Vec<ImportItem>> {
+        let mut items = Vec::new();
+
+        loop {
+            let name = self.consume_identifier("Expected name to import".to_string())?;
+            let alias = if self.match_tokens(&[TokenKind::As]) {
+                Some(self.consume_identifier("Expected alias after 'as'".to_string())?)
+            } else {
+                None
+            };
+            items.push(ImportItem { name, alias });
+
+            if !self.match_tokens(&[TokenKind::Comma]) {
+                break;
+            }
+
+            // Allow trailing comma
+            if self.check(terminator) {
+                break;
+            }
+        }
+
+        Ok(items)
+    }
+
+    /// Parses a regular statement.
+    fn statement(&mut self) -> Result<Stmt> {
+        if self.match_tokens(&[TokenKind::Return]) {
+            return self.return_statement();
+        }
+
+        if self.match_tokens(&[TokenKind::LeftBrace]) {
+            return self.block();
+        }
+
+        if self.match_tokens(&[TokenKind::If]) {
+            return self.if_statement();
+        }
+
+        if self.match_tokens(&[TokenKind::While]) {
+            return self.while_statement();
+        }
+
+        if self.match_tokens(&[TokenKind::For]) {
+            return self.for_statement();
+        }
+
+        if self.match_tokens(&[TokenKind::Match]) {
             return self.match_statement();
         }
 
+        if self.match_tokens(&[TokenKind::Break]) {
+            let start = self.previous().position.clone();
+            let label = self.loop_control_label("break")?;
+            return Ok(Stmt::Break(label, self.span_from(&start)));
+        }
+
+        if self.match_tokens(&[TokenKind::Continue]) {
+            let start = self.previous().position.clone();
+            let label = self.loop_control_label("continue")?;
+            return Ok(Stmt::Continue(label, self.span_from(&start)));
+        }
+
+        if let TokenKind::Label(label) = self.peek().kind.clone() {
+            return self.labeled_loop(label);
+        }
+
+        // Like other blocks, an unsafe block needs no `;` after it
+        if self.match_tokens(&[TokenKind::Unsafe]) {
+            let block = self.unsafe_block()?;
+            self.match_tokens(&[TokenKind::Semicolon]);
+            let span = block.span();
+            return Ok(Stmt::Expression(block, span));
+        }
+
         self.expression_statement()
     }
 
+    /// Parses the body of an unsafe block after the `unsafe` keyword.
+    fn unsafe_block(&mut self) -> Result<Expr> {
+        let start = self.previous().position.clone();
+        self.consume(
+            TokenKind::LeftBrace,
+            "Expected '{' after 'unsafe'".to_string(),
+        )?;
+        match self.parse_block_expression()? {
+            Expr::Block(statements, _) => Ok(Expr::Unsafe(statements, self.span_from(&start))),
+            other => Ok(other),
+        }
+    }
+
+    /// Parses the optional `'label` and the `;` after `break` or `continue`.
+    fn loop_control_label(&mut self, keyword: &str) -> Result<Option<String>> {
+        let label = if let TokenKind::Label(label) = self.peek().kind.clone() {
+            self.advance();
+            Some(label)
+        } else {
+            None
+        };
+
+        self.consume(
+            TokenKind::Semicolon,
+            format!("Expected ';' after '{}'", keyword),
+        )?;
+
+        Ok(label)
+    }
+
+    /// Parses a labelled loop: `'outer: while (...) { ... }` or `'outer: for ...`
+    fn labeled_loop(&mut self, label: String) -> Result<Stmt> {
+        let label_position = self.peek().position.clone();
+        self.advance();
+
+        self.consume(
+            TokenKind::Colon,
+            format!("Expected ':' after loop label '{}", label),
+        )?;
+
+        let body = if self.match_tokens(&[TokenKind::While]) {
+            self.while_statement()?
+        } else if self.match_tokens(&[TokenKind::For]) {
+            self.for_statement()?
+        } else {
+            return Err(CompileError::parse_error(
+                format!("Label '{} must be followed by a 'while' or 'for' loop", label),
+                label_position,
+            ));
+        };
+
+        Ok(Stmt::Labeled {
+            label,
+            body: Box::new(body),
+            span: self.span_from(&label_position),
+        })
+    }
+
     /// Parses a return statement.
     fn return_statement(&mut self) -> Result<Stmt> {
+        let start = self.previous().position.clone();
         let expr = if !self.check(&TokenKind::Semicolon) {
             Some(self.expression()?)
         } else {
@@ -472,11 +1164,12 @@ impl Parser {
             "Expected ';' after return value".to_string(),
         )?;
 
-        Ok(Stmt::Return(expr))
+        Ok(Stmt::Return(expr, self.span_from(&start)))
     }
 
     /// Parses a block statement.
     fn block(&mut self) -> Result<Stmt> {
+        let start = self.previous().position.clone();
         let mut statements = Vec::new();
 
         while !self.check(&TokenKind::RightBrace) && !self.is_at_end() {
@@ -488,11 +1181,12 @@ impl Parser {
             "Expected '}' after block".to_string(),
         )?;
 
-        Ok(Stmt::Block(statements))
+        Ok(Stmt::Block(statements, self.span_from(&start)))
     }
 
     /// Parses an if statement.
     fn if_statement(&mut self) -> Result<Stmt> {
+        let start = self.previous().position.clone();
         self.consume(TokenKind::LeftParen, "Expected '(' after 'if'".to_string())?;
 
         let condition = self.expression()?;
@@ -514,11 +1208,13 @@ impl Parser {
             condition,
             then_branch,
             else_branch,
+            span: self.span_from(&start),
         })
     }
 
     /// Parses a while statement.
     fn while_statement(&mut self) -> Result<Stmt> {
+        let start = self.previous().position.clone();
         self.consume(
             TokenKind::LeftParen,
             "Expected '(' after 'while'".to_string(),
@@ -533,11 +1229,17 @@ impl Parser {
 
         let body = Box::new(self.statement()?);
 
-        Ok(Stmt::While { condition, body })
+        Ok(Stmt::While {
+            condition,
+            body,
+            span: self.span_from(&start),
+        })
     }
 
     /// Parses a for statement.
     fn for_statement(&mut self) -> Result<Stmt> {
+        let start = self.previous().position.clone();
+
         // Check if this is a for-in loop by looking for the pattern: identifier 'in'
         // At this point, the 'For' token has already been consumed by match_tokens
 
@@ -559,6 +1261,7 @@ impl Parser {
                         variable,
                         iterable,
                         body,
+                        span: self.span_from(&start),
                     });
                 }
             }
@@ -609,17 +1312,20 @@ impl Parser {
             condition,
             increment,
             body,
+            span: self.span_from(&start),
         })
     }
 
     /// Parses a match statement.
     fn match_statement(&mut self) -> Result<Stmt> {
         let match_expr = self.parse_match_expression()?;
-        Ok(Stmt::Expression(match_expr))
+        let span = match_expr.span();
+        Ok(Stmt::Expression(match_expr, span))
     }
 
     /// Parses an expression statement.
     fn expression_statement(&mut self) -> Result<Stmt> {
+        let start = self.peek().position.clone();
         let expr = self.expression()?;
 
         self.consume(
@@ -627,7 +1333,7 @@ impl Parser {
             "Expected ';' after expression".to_string(),
         )?;
 
-        Ok(Stmt::Expression(expr))
+        Ok(Stmt::Expression(expr, self.span_from(&start)))
     }
 
     /// Parses an expression.
@@ -667,8 +1373,11 @@ impl Parser {
             let operator = self.previous().clone();
             let value = time_parsing_operation(|| self.assignment())?;
 
-            // Only variables can be assigned to
-            if let Expr::Variable(name) = expr {
+            // Variables, array elements and dereferenced pointers can be assigned to
+            if matches!(
+                expr,
+                Expr::Variable(..) | Expr::Index(..) | Expr::Unary(UnaryOp::Deref, ..)
+            ) {
                 let op = match operator.kind {
                     TokenKind::Assign => BinaryOp::Assign,
                     TokenKind::PlusAssign => BinaryOp::PlusAssign,
@@ -678,11 +1387,7 @@ impl Parser {
                     _ => unreachable!(),
                 };
 
-                return Ok(Expr::Binary(
-                    Box::new(Expr::Variable(name)),
-                    op,
-                    Box::new(value),
-                ));
+                return Ok(binary_expr(expr, op, value));
             }
 
             // If we get here, the left side wasn't a valid assignment target
@@ -703,7 +1408,7 @@ impl Parser {
 
         while self.match_tokens(&[TokenKind::Or]) {
             let right = self.logical_and()?;
-            expr = Expr::Binary(Box::new(expr), BinaryOp::Or, Box::new(right));
+            expr = binary_expr(expr, BinaryOp::Or, right);
         }
 
         Ok(expr)
@@ -715,7 +1420,7 @@ impl Parser {
 
         while self.match_tokens(&[TokenKind::And]) {
             let right = self.simd_or()?;
-            expr = Expr::Binary(Box::new(expr), BinaryOp::And, Box::new(right));
+            expr = binary_expr(expr, BinaryOp::And, right);
         }
 
         Ok(expr)
@@ -736,26 +1441,26 @@ impl Parser {
 
             match operator.kind {
                 TokenKind::Equal => {
-                    expr = Expr::Binary(Box::new(expr), BinaryOp::Equal, Box::new(right));
+                    expr = binary_expr(expr, BinaryOp::Equal, right);
                 }
                 TokenKind::NotEqual => {
-                    expr = Expr::Binary(Box::new(expr), BinaryOp::NotEqual, Box::new(right));
+                    expr = binary_expr(expr, BinaryOp::NotEqual, right);
                 }
                 TokenKind::DotEqual => {
-                    expr = Expr::SIMD(SIMDExpr::ElementWise {
-                        left: Box::new(expr),
-                        operator: SIMDOperator::DotEqual,
-                        right: Box::new(right),
-                        position: operator.position,
-                    });
+                    expr = element_wise_expr(
+                        expr,
+                        SIMDOperator::DotEqual,
+                        right,
+                        operator.position,
+                    );
                 }
                 TokenKind::DotNotEqual => {
-                    expr = Expr::SIMD(SIMDExpr::ElementWise {
-                        left: Box::new(expr),
-                        operator: SIMDOperator::DotNotEqual,
-                        right: Box::new(right),
-                        position: operator.position,
-                    });
+                    expr = element_wise_expr(
+                        expr,
+                        SIMDOperator::DotNotEqual,
+                        right,
+                        operator.position,
+                    );
                 }
                 _ => unreachable!(),
             }
@@ -783,48 +1488,43 @@ impl Parser {
 
             match operator.kind {
                 TokenKind::Less => {
-                    expr = Expr::Binary(Box::new(expr), BinaryOp::Less, Box::new(right));
+                    expr = binary_expr(expr, BinaryOp::Less, right);
                 }
                 TokenKind::LessEqual => {
-                    expr = Expr::Binary(Box::new(expr), BinaryOp::LessEqual, Box::new(right));
+                    expr = binary_expr(expr, BinaryOp::LessEqual, right);
                 }
                 TokenKind::Greater => {
-                    expr = Expr::Binary(Box::new(expr), BinaryOp::Greater, Box::new(right));
+                    expr = binary_expr(expr, BinaryOp::Greater, right);
                 }
                 TokenKind::GreaterEqual => {
-                    expr = Expr::Binary(Box::new(expr), BinaryOp::GreaterEqual, Box::new(right));
+                    expr = binary_expr(expr, BinaryOp::GreaterEqual, right);
                 }
                 TokenKind::DotLess => {
-                    expr = Expr::SIMD(SIMDExpr::ElementWise {
-                        left: Box::new(expr),
-                        operator: SIMDOperator::DotLess,
-                        right: Box::new(right),
-                        position: operator.position,
-                    });
+                    expr = element_wise_expr(expr, SIMDOperator::DotLess, right, operator.position);
                 }
                 TokenKind::DotLessEqual => {
-                    expr = Expr::SIMD(SIMDExpr::ElementWise {
-                        left: Box::new(expr),
-                        operator: SIMDOperator::DotLessEqual,
-                        right: Box::new(right),
-                        position: operator.position,
-                    });
+                    expr = element_wise_expr(
+                        expr,
+                        SIMDOperator::DotLessEqual,
+                        right,
+                        operator.position,
+                    );
                 }
                 TokenKind::DotGreater => {
-                    expr = Expr::SIMD(SIMDExpr::ElementWise {
-                        left: Box::new(expr),
-                        operator: SIMDOperator::DotGreater,
-                        right: Box::new(right),
-                        position: operator.position,
-                    });
+                    expr = element_wise_expr(
+                        expr,
+                        SIMDOperator::DotGreater,
+                        right,
+                        operator.position,
+                    );
                 }
                 TokenKind::DotGreaterEqual => {
-                    expr = Expr::SIMD(SIMDExpr::ElementWise {
-                        left: Box::new(expr),
-                        operator: SIMDOperator::DotGreaterEqual,
-                        right: Box::new(right),
-                        position: operator.position,
-                    });
+                    expr = element_wise_expr(
+                        expr,
+                        SIMDOperator::DotGreaterEqual,
+                        right,
+                        operator.position,
+                    );
                 }
                 _ => unreachable!(),
             }
@@ -847,7 +1547,7 @@ impl Parser {
                 _ => unreachable!(),
             };
 
-            expr = Expr::Binary(Box::new(expr), op, Box::new(right));
+            expr = binary_expr(expr, op, right);
         }
 
         Ok(expr)
@@ -868,7 +1568,7 @@ impl Parser {
                 _ => unreachable!(),
             };
 
-            expr = Expr::Binary(Box::new(expr), op, Box::new(right));
+            expr = binary_expr(expr, op, right);
         }
 
         Ok(expr)
@@ -876,7 +1576,12 @@ impl Parser {
 
     /// Parses a unary expression.
     fn unary(&mut self) -> Result<Expr> {
-        if self.match_tokens(&[TokenKind::Minus, TokenKind::Not, TokenKind::Ampersand]) {
+        if self.match_tokens(&[
+            TokenKind::Minus,
+            TokenKind::Not,
+            TokenKind::Ampersand,
+            TokenKind::Star,
+        ]) {
             let operator = self.previous().clone();
             let right = self.unary()?;
 
@@ -884,10 +1589,12 @@ impl Parser {
                 TokenKind::Minus => UnaryOp::Negate,
                 TokenKind::Not => UnaryOp::Not,
                 TokenKind::Ampersand => UnaryOp::Reference,
+                TokenKind::Star => UnaryOp::Deref,
                 _ => unreachable!(),
             };
 
-            return Ok(Expr::Unary(op, Box::new(right)));
+            let span = self.span_from(&operator.position);
+            return Ok(Expr::Unary(op, Box::new(right), span));
         }
 
         self.call()
@@ -913,6 +1620,7 @@ impl Parser {
                         "slice_close",
                     )?;
                     expr = Expr::Slice {
+                        span: self.span_from(&expr.span().start),
                         array: Box::new(expr),
                         start: Box::new(first_expr),
                         end: Box::new(end_expr),
@@ -924,12 +1632,22 @@ impl Parser {
                         "Expected ']' after array index".to_string(),
                         "array_index_close",
                     )?;
-                    expr = Expr::Index(Box::new(expr), Box::new(first_expr));
+                    let span = self.span_from(&expr.span().start);
+                    expr = Expr::Index(Box::new(expr), Box::new(first_expr), span);
                 }
             } else if self.match_tokens(&[TokenKind::Dot]) {
-                let name =
-                    self.consume_identifier("Expected property name after '.'".to_string())?;
-                expr = Expr::FieldAccess(Box::new(expr), name);
+                // Tuple fields are numbered: `pair.0`. In `nested.0.1` the
+                // lexer reads `0.1` as a float, which then holds two fields.
+                let names = if matches!(self.peek().kind, TokenKind::Integer(_) | TokenKind::Float(_)) {
+                    let lexeme = self.advance().lexeme.clone();
+                    lexeme.split('.').map(str::to_string).collect()
+                } else {
+                    vec![self.consume_identifier("Expected property name after '.'".to_string())?]
+                };
+                for name in names {
+                    let span = self.span_from(&expr.span().start);
+                    expr = Expr::FieldAccess(Box::new(expr), name, span);
+                }
             } else {
                 break;
             }
@@ -957,39 +1675,45 @@ impl Parser {
             "Expected ')' after arguments".to_string(),
         )?;
 
-        Ok(Expr::Call(Box::new(callee), arguments))
+        let span = self.span_from(&callee.span().start);
+        Ok(Expr::Call(Box::new(callee), arguments, span))
     }
 
     /// Parses primary expressions: literals, variables, parentheses, SIMD vectors, and arrays
     fn primary(&mut self) -> Result<Expr> {
         // Handle literals and variables
         if self.match_tokens(&[TokenKind::True]) {
-            return Ok(Expr::Literal(Literal::Boolean(true)));
+            return Ok(Expr::Literal(Literal::Boolean(true), self.previous_span()));
         }
 
         if self.match_tokens(&[TokenKind::False]) {
-            return Ok(Expr::Literal(Literal::Boolean(false)));
+            return Ok(Expr::Literal(Literal::Boolean(false), self.previous_span()));
         }
 
         if let Some(token) = self.match_tokens_and_get(&[TokenKind::Integer(0)]) {
             if let TokenKind::Integer(n) = token.kind {
-                return Ok(Expr::Literal(Literal::Integer(n)));
+                return Ok(Expr::Literal(Literal::Integer(n), self.previous_span()));
             }
         }
 
         if let Some(token) = self.match_tokens_and_get(&[TokenKind::Float(0.0)]) {
             if let TokenKind::Float(f) = token.kind {
-                return Ok(Expr::Literal(Literal::Float(f)));
+                return Ok(Expr::Literal(Literal::Float(f), self.previous_span()));
             }
         }
 
         if let Some(token) = self.match_tokens_and_get(&[TokenKind::StringLiteral("".to_string())])
         {
             if let TokenKind::StringLiteral(s) = token.kind {
-                return Ok(Expr::Literal(Literal::String(s)));
+                return Ok(Expr::Literal(Literal::String(s), self.previous_span()));
             }
         }
 
+        // Closures: |x: f32| -> f32 { x * 2.0 }, or || { ... } without parameters
+        if self.check(&TokenKind::Pipe) || self.check(&TokenKind::Or) {
+            return self.parse_closure();
+        }
+
         // Handle built-in functions (print, println, etc.)
         if !self.is_at_end() && matches!(self.peek().kind, TokenKind::Print) {
             self.advance(); // consume Print token
@@ -997,13 +1721,13 @@ impl Parser {
 
             // This should be a function call
             if self.check(&TokenKind::LeftParen) {
-                let var_expr = Expr::Variable(func_name.to_string());
+                let var_expr = Expr::Variable(func_name.to_string(), self.previous_span());
                 self.advance(); // consume '('
                 return self.finish_call(var_expr);
             }
 
             // If not a function call, treat as variable reference
-            return Ok(Expr::Variable(func_name.to_string()));
+            return Ok(Expr::Variable(func_name.to_string(), self.previous_span()));
         }
 
         // Handle println function
@@ -1013,13 +1737,13 @@ impl Parser {
 
             // This should be a function call
             if self.check(&TokenKind::LeftParen) {
-                let var_expr = Expr::Variable(func_name.to_string());
+                let var_expr = Expr::Variable(func_name.to_string(), self.previous_span());
                 self.advance(); // consume '('
                 return self.finish_call(var_expr);
             }
 
             // If not a function call, treat as variable reference
-            return Ok(Expr::Variable(func_name.to_string()));
+            return Ok(Expr::Variable(func_name.to_string(), self.previous_span()));
         }
 
         // Handle standard library types (Vec, HashMap, etc.)
@@ -1086,20 +1810,55 @@ impl Parser {
                 // Check if this is a function call (Vec::new())
                 if self.check(&TokenKind::LeftParen) {
                     // This is a module-scoped function call like Vec::new()
-                    let module_expr = Expr::Variable(type_name.to_string());
-                    let field_access = Expr::FieldAccess(Box::new(module_expr), second_name);
+                    let module_expr = Expr::Variable(type_name.to_string(), token.span());
+                    let field_access = Expr::FieldAccess(
+                        Box::new(module_expr),
+                        second_name,
+                        self.span_from(&token.position),
+                    );
                     self.advance(); // consume '('
                     return self.finish_call(field_access);
                 }
             }
 
             // If not a static method call, treat as type literal
-            return Ok(Expr::Variable(type_name.to_string()));
+            return Ok(Expr::Variable(type_name.to_string(), token.span()));
+        }
+
+        // lanes<f32>(): the lane count of simd<f32>, known once the target is
+        if self.match_tokens(&[TokenKind::Lanes]) {
+            return self.parse_lanes_function();
+        }
+
+        // Vector memory operations take a vector type argument, so they cannot be
+        // parsed as ordinary function calls; the other SIMD builtins lower to LLVM
+        // intrinsics rather than calls
+        if let TokenKind::Identifier(name) = &self.peek().kind {
+            if self.peek_next().kind == TokenKind::LeftParen {
+                match name.as_str() {
+                    "load_vector" => {
+                        self.advance(); // consume identifier
+                        return self.parse_load_vector_function();
+                    }
+                    "store_vector" => {
+                        self.advance(); // consume identifier
+                        return self.parse_store_vector_function();
+                    }
+                    "select" | "masked_load" | "masked_store" | "gather" | "scatter"
+                    | "shuffle" => {
+                        let name = name.clone();
+                        self.advance(); // consume identifier
+                        return self.parse_simd_builtin_function(&name);
+                    }
+                    _ => {}
+                }
+            }
         }
 
         // Handle identifiers (variables, function calls, struct literals, enum literals)
         if !self.is_at_end() && matches!(self.peek().kind, TokenKind::Identifier(_)) {
             let token = self.advance().clone();
+            let name_span = token.span();
             if let TokenKind::Identifier(name) = token.kind {
                 // Check if this is a module-scoped call or enum literal (Name::Something)
                 if !self.is_at_end() && matches!(self.peek().kind, TokenKind::DoubleColon) {
@@ -1110,8 +1869,10 @@ impl Parser {
                     // Check if this is a function call (Vec::new() or HashMap::new())
                     if self.check(&TokenKind::LeftParen) {
                         // This is a module-scoped function call like Vec::new()
-                        let module_expr = Expr::Variable(name);
-                        let field_access = Expr::FieldAccess(Box::new(module_expr), second_name);
+                        let path_span = self.span_from(&name_span.start);
+                        let module_expr = Expr::Variable(name, name_span);
+                        let field_access =
+                            Expr::FieldAccess(Box::new(module_expr), second_name, path_span);
                         self.advance(); // consume '('
                         return self.finish_call(field_access);
                     } else {
@@ -1136,6 +1897,7 @@ impl Parser {
                             enum_name: name,
                             variant: second_name,
                             args,
+                            span: self.span_from(&name_span.start),
                         });
                     }
                 }
@@ -1151,7 +1913,7 @@ impl Parser {
                     return self.parse_struct_literal(name);
                 }
 
-                return Ok(Expr::Variable(name));
+                return Ok(Expr::Variable(name, name_span));
             }
         }
 
@@ -1174,14 +1936,6 @@ impl Parser {
                     self.advance(); // consume identifier
                     return self.parse_dot_product_function();
                 }
-                "load_vector" if self.peek_next().kind == TokenKind::LeftParen => {
-                    self.advance(); // consume identifier
-                    return self.parse_load_vector_function();
-                }
-                "store_vector" if self.peek_next().kind == TokenKind::LeftParen => {
-                    self.advance(); // consume identifier
-                    return self.parse_store_vector_function();
-                }
                 _ => {}
             }
         }
@@ -1189,18 +1943,37 @@ impl Parser {
         // Handle SIMD literals that already come as a single token
         if let Some(token) = self.match_tokens_and_get(&[TokenKind::SimdLiteral("".to_string())]) {
             if let TokenKind::SimdLiteral(s) = token.kind {
-                return Ok(Expr::Literal(Literal::String(s))); // Temporary - will be enhanced in SIMD type system
+                // Temporary - will be enhanced in SIMD type system
+                return Ok(Expr::Literal(Literal::String(s), self.previous_span()));
             }
         }
 
-        // Handle grouping with parentheses
+        // Handle grouping with parentheses, or a tuple literal if a comma follows
         if self.match_tokens(&[TokenKind::LeftParen]) {
+            let start = self.previous().position.clone();
             let expr = self.expression()?;
+            if self.match_tokens(&[TokenKind::Comma]) {
+                let mut elements = vec![expr];
+                while !self.check(&TokenKind::RightParen) {
+                    elements.push(self.expression()?);
+                    if !self.match_tokens(&[TokenKind::Comma]) {
+                        break;
+                    }
+                }
+                self.consume(
+                    TokenKind::RightParen,
+                    "Expected ')' after tuple elements".to_string(),
+                )?;
+                return Ok(Expr::Tuple {
+                    elements,
+                    span: self.span_from(&start),
+                });
+            }
             self.consume(
                 TokenKind::RightParen,
                 "Expected ')' after expression".to_string(),
             )?;
-            return Ok(Expr::Grouping(Box::new(expr)));
+            return Ok(Expr::Grouping(Box::new(expr), self.span_from(&start)));
         }
 
         // Handle array literals and SIMD vector literals starting with [
@@ -1213,6 +1986,26 @@ impl Parser {
             return self.parse_block_expression();
         }
 
+        // Compile-time blocks: comptime { ... }
+        if self.match_tokens(&[TokenKind::Comptime]) {
+            let start = self.previous().position.clone();
+            self.consume(
+                TokenKind::LeftBrace,
+                "Expected '{' after 'comptime'".to_string(),
+            )?;
+            return match self.parse_block_expression()? {
+                Expr::Block(statements, _) => {
+                    Ok(Expr::Comptime(statements, self.span_from(&start)))
+                }
+                other => Ok(other),
+            };
+        }
+
+        // Unsafe blocks: unsafe { ... }
+        if self.match_tokens(&[TokenKind::Unsafe]) {
+            return self.unsafe_block();
+        }
+
         // If we get here, we couldn't match any expression
         Err(CompileError::parse_error(
             format!("Expected expression, got {:?}", self.peek().kind),
@@ -1229,12 +2022,7 @@ impl Parser {
             let right = self.simd_xor()?;
             let position = self.previous().position.clone();
 
-            expr = Expr::SIMD(SIMDExpr::ElementWise {
-                left: Box::new(expr),
-                operator,
-                right: Box::new(right),
-                position,
-            });
+            expr = element_wise_expr(expr, operator, right, position);
         }
 
         Ok(expr)
@@ -1249,12 +2037,7 @@ impl Parser {
             let right = self.simd_and()?;
             let position = self.previous().position.clone();
 
-            expr = Expr::SIMD(SIMDExpr::ElementWise {
-                left: Box::new(expr),
-                operator,
-                right: Box::new(right),
-                position,
-            });
+            expr = element_wise_expr(expr, operator, right, position);
         }
 
         Ok(expr)
@@ -1269,12 +2052,7 @@ impl Parser {
             let right = self.equality()?;
             let position = self.previous().position.clone();
 
-            expr = Expr::SIMD(SIMDExpr::ElementWise {
-                left: Box::new(expr),
-                operator,
-                right: Box::new(right),
-                position,
-            });
+            expr = element_wise_expr(expr, operator, right, position);
         }
 
         Ok(expr)
@@ -1294,12 +2072,7 @@ impl Parser {
                     let right = self.enhanced_factor()?;
                     let position = self.previous().position.clone();
 
-                    expr = Expr::SIMD(SIMDExpr::ElementWise {
-                        left: Box::new(expr),
-                        operator,
-                        right: Box::new(right),
-                        position,
-                    });
+                    expr = element_wise_expr(expr, operator, right, position);
                 }
                 TokenKind::DotSubtract => {
                     self.advance();
@@ -1307,12 +2080,7 @@ impl Parser {
                     let right = self.enhanced_factor()?;
                     let position = self.previous().position.clone();
 
-                    expr = Expr::SIMD(SIMDExpr::ElementWise {
-                        left: Box::new(expr),
-                        operator,
-                        right: Box::new(right),
-                        position,
-                    });
+                    expr = element_wise_expr(expr, operator, right, position);
                 }
                 TokenKind::Plus | TokenKind::Minus => {
                     let op = if matches!(token.kind, TokenKind::Plus) {
@@ -1322,7 +2090,7 @@ impl Parser {
                     };
                     self.advance();
                     let right = self.enhanced_factor()?;
-                    expr = Expr::Binary(Box::new(expr), op, Box::new(right));
+                    expr = binary_expr(expr, op, right);
                 }
                 _ => break,
             }
@@ -1345,12 +2113,7 @@ impl Parser {
                     let right = self.unary()?;
                     let position = self.previous().position.clone();
 
-                    expr = Expr::SIMD(SIMDExpr::ElementWise {
-                        left: Box::new(expr),
-                        operator,
-                        right: Box::new(right),
-                        position,
-                    });
+                    expr = element_wise_expr(expr, operator, right, position);
                 }
                 TokenKind::DotDivide => {
                     self.advance();
@@ -1358,12 +2121,7 @@ impl Parser {
                     let right = self.unary()?;
                     let position = self.previous().position.clone();
 
-                    expr = Expr::SIMD(SIMDExpr::ElementWise {
-                        left: Box::new(expr),
-                        operator,
-                        right: Box::new(right),
-                        position,
-                    });
+                    expr = element_wise_expr(expr, operator, right, position);
                 }
                 TokenKind::Star | TokenKind::Slash | TokenKind::Percent => {
                     let operator = self.advance();
@@ -1374,7 +2132,7 @@ impl Parser {
                         _ => unreachable!(),
                     };
                     let right = self.unary()?;
-                    expr = Expr::Binary(Box::new(expr), op, Box::new(right));
+                    expr = binary_expr(expr, op, right);
                 }
                 _ => break,
             }
@@ -1601,23 +2359,87 @@ impl Parser {
             return Ok(type_name.to_string());
         }
 
+        // Function types: func(f32, f32) -> f32
+        if self.match_tokens(&[TokenKind::Func]) {
+            self.consume(TokenKind::LeftParen, "Expected '(' after 'func' in function type".to_string())?;
+            let mut param_types = Vec::new();
+            while !self.check(&TokenKind::RightParen) {
+                param_types.push(self.consume_type_name("Expected parameter type".to_string())?);
+                if !self.match_tokens(&[TokenKind::Comma]) {
+                    break;
+                }
+            }
+            self.consume(TokenKind::RightParen, "Expected ')' after parameter types".to_string())?;
+            let return_type = if self.match_tokens(&[TokenKind::Arrow]) {
+                self.consume_type_name("Expected return type".to_string())?
+            } else {
+                "()".to_string()
+            };
+            return Ok(crate::ast::function_type_name(&param_types, &return_type));
+        }
+
+        // Tuple types: (i32, f32), or (i32,) with a single element
+        if self.match_tokens(&[TokenKind::LeftParen]) {
+            let mut element_types = Vec::new();
+            while !self.check(&TokenKind::RightParen) {
+                element_types.push(self.consume_type_name("Expected tuple element type".to_string())?);
+                if !self.match_tokens(&[TokenKind::Comma]) {
+                    break;
+                }
+            }
+            self.consume(TokenKind::RightParen, "Expected ')' after tuple element types".to_string())?;
+            return Ok(crate::ast::tuple_type_name(&element_types));
+        }
+
+        // Raw pointer types: *f32, or *mut f32 when writes go through it
+        if self.match_tokens(&[TokenKind::Star]) {
+            let is_mutable = self.match_tokens(&[TokenKind::Mut]);
+            let target_type = self.consume_type_name("Expected type after '*'".to_string())?;
+            return Ok(crate::ast::pointer_type_name(&target_type, is_mutable));
+        }
+
+        // Fixed-size array types: [f32; 64], or [f32; SIZE] with a constant length
+        if self.match_tokens(&[TokenKind::LeftBracket]) {
+            let element_type = self.consume_type_name("Expected array element type".to_string())?;
+            self.consume(TokenKind::Semicolon, "Expected ';' after array element type".to_string())?;
+            let length = match self.peek().kind.clone() {
+                TokenKind::Integer(n) if n >= 0 => n.to_string(),
+                TokenKind::Identifier(name) => name,
+                _ => {
+                    return Err(CompileError::parse_error(
+                        "Expected integer or constant as array length".to_string(),
+                        self.peek().position.clone(),
+                    ))
+                }
+            };
+            self.advance();
+            self.consume(TokenKind::RightBracket, "Expected ']' after array length".to_string())?;
+            return Ok(crate::ast::array_type_name(&element_type, &length));
+        }
+
         // Fall back to identifier for custom types
         if self.check(&TokenKind::Identifier(String::new())) {
             let token = self.advance();
             if let TokenKind::Identifier(name) = &token.kind {
                 let type_name = name.clone();
                 
-                // Check for generic syntax like Vec<String>
+                // Check for generic syntax like Pair<i32, f32>
                 if self.check(&TokenKind::Less) {
                     self.advance(); // consume '<'
-                    
-                    // Parse the inner type
-                    let inner_type = self.consume_type_name("Expected type parameter".to_string())?;
-                    
+
+                    // Parse the type arguments
+                    let mut type_args = Vec::new();
+                    loop {
+                        type_args.push(self.consume_type_name("Expected type argument".to_string())?);
+                        if !self.match_tokens(&[TokenKind::Comma]) {
+                            break;
+                        }
+                    }
+
                     // Consume '>'
-                    self.consume(TokenKind::Greater, "Expected '>' after type parameter".to_string())?;
-                    
-                    return Ok(format!("{}<{}>", type_name, inner_type));
+                    self.consume(TokenKind::Greater, "Expected '>' after type arguments".to_string())?;
+
+                    return Ok(crate::ast::generic_instance_name(&type_name, &type_args));
                 }
                 return Ok(type_name);
             }
@@ -1709,17 +2531,31 @@ impl Parser {
         }
     }
 
+    /// Span from `start` to the end of the last consumed token.
+    fn span_from(&self, start: &Position) -> Span {
+        Span::new(start.clone(), self.previous().end_position())
+    }
+
+    /// Span of the last consumed token.
+    fn previous_span(&self) -> Span {
+        self.previous().span()
+    }
+
     /// Parse array literals or SIMD vector literals starting with [
     fn parse_array_or_simd_literal(&mut self) -> Result<Expr> {
+        let start = self.previous().position.clone();
         let mut expressions = Vec::new();
 
         // Handle empty array/vector
         if self.check(&TokenKind::RightBracket) {
             self.advance(); // consume ]
-            return Ok(Expr::Literal(Literal::Vector {
-                elements: vec![],
-                vector_type: None,
-            }));
+            return Ok(Expr::Literal(
+                Literal::Vector {
+                    elements: vec![],
+                    vector_type: None,
+                },
+                self.span_from(&start),
+            ));
         }
 
         // Parse elements
@@ -1731,6 +2567,27 @@ impl Parser {
             }
         }
 
+        // Repeat form: [value; count] or [value; count]f32x4
+        if expressions.len() == 1 && self.match_tokens(&[TokenKind::Semicolon]) {
+            let count = self.expression()?;
+            self.consume(
+                TokenKind::RightBracket,
+                "Expected ']' after array length".to_string(),
+            )?;
+            let vector_type = if self.check_simd_type() {
+                let simd_type_token_kind = self.advance().kind.clone();
+                Some(self.parse_simd_vector_type(&simd_type_token_kind)?)
+            } else {
+                None
+            };
+            return Ok(Expr::ArrayRepeat {
+                value: Box::new(expressions.remove(0)),
+                count: Box::new(count),
+                vector_type,
+                span: self.span_from(&start),
+            });
+        }
+
         self.consume_with_recovery(
             TokenKind::RightBracket,
             "Expected ']' after array elements".to_string(),
@@ -1741,18 +2598,21 @@ impl Parser {
         if self.check_simd_type() {
             let simd_type_token_kind = self.advance().kind.clone();
             let simd_type = self.parse_simd_vector_type(&simd_type_token_kind)?;
-            return Ok(Expr::SIMD(SIMDExpr::VectorLiteral {
-                elements: expressions,
-                vector_type: Some(simd_type),
-                position: self.previous().position.clone(),
-            }));
+            return Ok(Expr::SIMD(
+                SIMDExpr::VectorLiteral {
+                    elements: expressions,
+                    vector_type: Some(simd_type),
+                    position: self.previous().position.clone(),
+                },
+                self.span_from(&start),
+            ));
         }
 
         // Regular array literal - convert expressions to literals where possible
         let mut literal_elements = Vec::new();
         for expr in expressions {
             match expr {
-                Expr::Literal(lit) => literal_elements.push(lit),
+                Expr::Literal(lit, _) => literal_elements.push(lit),
                 _ => {
                     return Err(CompileError::parse_error(
                         "Array literals can only contain literal values".to_string(),
@@ -1762,10 +2622,13 @@ impl Parser {
             }
         }
 
-        Ok(Expr::Literal(Literal::Vector {
-            elements: literal_elements,
-            vector_type: None,
-        }))
+        Ok(Expr::Literal(
+            Literal::Vector {
+                elements: literal_elements,
+                vector_type: None,
+            },
+            self.span_from(&start),
+        ))
     }
 
     /// Check if current token is a SIMD type
@@ -1887,6 +2750,7 @@ impl Parser {
 
     /// Parse a function call expression
     fn parse_function_call(&mut self, name: String) -> Result<Expr> {
+        let callee = Expr::Variable(name, self.previous_span());
         self.consume_with_recovery(
             TokenKind::LeftParen,
             "Expected '(' after function name".to_string(),
@@ -1910,11 +2774,13 @@ impl Parser {
             "function_call_close",
         )?;
 
-        Ok(Expr::Call(Box::new(Expr::Variable(name)), arguments))
+        let span = self.span_from(&callee.span().start);
+        Ok(Expr::Call(Box::new(callee), arguments, span))
     }
 
     /// Parse a SIMD reduction function call
     fn parse_reduction_function(&mut self, operation: ReductionOp) -> Result<Expr> {
+        let start = self.previous().position.clone();
         self.consume_with_recovery(
             TokenKind::LeftParen,
             "Expected '(' after reduction function".to_string(),
@@ -1931,15 +2797,19 @@ impl Parser {
 
         let position = self.previous().position.clone();
 
-        Ok(Expr::SIMD(SIMDExpr::Reduction {
-            vector: Box::new(vector),
-            operation,
-            position,
-        }))
+        Ok(Expr::SIMD(
+            SIMDExpr::Reduction {
+                vector: Box::new(vector),
+                operation,
+                position,
+            },
+            self.span_from(&start),
+        ))
     }
 
     /// Parse a SIMD dot product function call
     fn parse_dot_product_function(&mut self) -> Result<Expr> {
+        let start = self.previous().position.clone();
         self.consume(
             TokenKind::LeftParen,
             "Expected '(' after dot_product".to_string(),
@@ -1961,15 +2831,19 @@ impl Parser {
 
         let position = self.previous().position.clone();
 
-        Ok(Expr::SIMD(SIMDExpr::DotProduct {
-            left: Box::new(left),
-            right: Box::new(right),
-            position,
-        }))
+        Ok(Expr::SIMD(
+            SIMDExpr::DotProduct {
+                left: Box::new(left),
+                right: Box::new(right),
+                position,
+            },
+            self.span_from(&start),
+        ))
     }
 
     /// Parse load_vector(address, vector_type) function call
     fn parse_load_vector_function(&mut self) -> Result<Expr> {
+        let start = self.previous().position.clone();
         self.consume(
             TokenKind::LeftParen,
             "Expected '(' after load_vector".to_string(),
@@ -2013,12 +2887,14 @@ impl Parser {
             TokenKind::U8x16,
             TokenKind::U8x32,
         ]) {
-            self.parse_simd_vector_type(&token.kind)?
+            Some(self.parse_simd_vector_type(&token.kind)?)
         } else {
-            return Err(CompileError::parse_error(
-                "Expected vector type identifier".to_string(),
-                self.peek().position.clone(),
-            ));
+            None
+        };
+        // simd<f32> loads are given their width by the type checker
+        let native_element_type = match vector_type {
+            Some(_) => String::new(),
+            None => self.parse_native_vector_type()?,
         };
 
         // Optional alignment parameter
@@ -2034,17 +2910,71 @@ impl Parser {
         )?;
 
         let position = self.previous().position.clone();
+        let simd_expr = match vector_type {
+            Some(vector_type) => SIMDExpr::VectorLoad {
+                address: Box::new(address),
+                vector_type,
+                alignment,
+                position,
+            },
+            None => SIMDExpr::NativeVectorLoad {
+                address: Box::new(address),
+                element_type: native_element_type,
+                alignment,
+                position,
+            },
+        };
 
-        Ok(Expr::SIMD(SIMDExpr::VectorLoad {
-            address: Box::new(address),
-            vector_type,
-            alignment,
+        Ok(Expr::SIMD(simd_expr, self.span_from(&start)))
+    }
+
+    /// Parse a lane-width-agnostic vector type such as `simd<f32>`, returning its
+    /// element type
+    fn parse_native_vector_type(&mut self) -> Result<String> {
+        let position = self.peek().position.clone();
+        if matches!(&self.peek().kind, TokenKind::Identifier(name) if name == "simd") {
+            let type_name = self.consume_type_name("Expected vector type".to_string())?;
+            if let Some(element_type) = crate::ast::split_native_vector_type_name(&type_name) {
+                return Ok(element_type);
+            }
+        }
+        Err(CompileError::parse_error(
+            "Expected vector type identifier".to_string(),
             position,
-        }))
+        ))
+    }
+
+    /// Parse lanes<element_type>() after the `lanes` keyword
+    fn parse_lanes_function(&mut self) -> Result<Expr> {
+        let start = self.previous().position.clone();
+        self.consume(TokenKind::Less, "Expected '<' after lanes".to_string())?;
+        let element_type = self.consume_type_name("Expected element type".to_string())?;
+        self.consume(
+            TokenKind::Greater,
+            "Expected '>' after lanes element type".to_string(),
+        )?;
+        self.consume(
+            TokenKind::LeftParen,
+            "Expected '(' after lanes<...>".to_string(),
+        )?;
+        self.consume(
+            TokenKind::RightParen,
+            "lanes<...>() takes no arguments".to_string(),
+        )?;
+
+        let position = self.previous().position.clone();
+        Ok(Expr::SIMD(
+            SIMDExpr::Lanes {
+                element_type,
+                position,
+            },
+            self.span_from(&start),
+        ))
     }
 
     /// Parse store_vector(address, vector) function call
     fn parse_store_vector_function(&mut self) -> Result<Expr> {
+        let start = self.previous().position.clone();
         self.consume(
             TokenKind::LeftParen,
             "Expected '(' after store_vector".to_string(),
@@ -2073,12 +3003,79 @@ impl Parser {
 
         let position = self.previous().position.clone();
 
-        Ok(Expr::SIMD(SIMDExpr::VectorStore {
-            address: Box::new(address),
-            vector: Box::new(vector),
-            alignment,
-            position,
-        }))
+        Ok(Expr::SIMD(
+            SIMDExpr::VectorStore {
+                address: Box::new(address),
+                vector: Box::new(vector),
+                alignment,
+                position,
+            },
+            self.span_from(&start),
+        ))
+    }
+
+    /// Parse select(mask, a, b), masked_load(address, mask, passthru),
+    /// masked_store(address, vector, mask), gather(base, indices),
+    /// scatter(base, indices, vector) or shuffle(a, b, [lane, ...])
+    fn parse_simd_builtin_function(&mut self, name: &str) -> Result<Expr> {
+        let start = self.previous().position.clone();
+        self.consume(TokenKind::LeftParen, format!("Expected '(' after {}", name))?;
+
+        let arity = if name == "gather" { 2 } else { 3 };
+        let arity_error = format!("{}() takes exactly {} arguments", name, arity);
+        let mut args = Vec::with_capacity(arity);
+        for i in 0..arity {
+            if i > 0 {
+                self.consume(TokenKind::Comma, arity_error.clone())?;
+            }
+            args.push(Box::new(self.expression()?));
+        }
+        self.consume(TokenKind::RightParen, arity_error)?;
+
+        let position = self.previous().position.clone();
+        // Struct fields are evaluated in the order written, so this takes the
+        // arguments left to right
+        let mut args = args.into_iter();
+        let mut next = || args.next().expect("all arguments were parsed");
+        let simd_expr = match name {
+            "select" => SIMDExpr::Select {
+                mask: next(),
+                if_true: next(),
+                if_false: next(),
+                position,
+            },
+            "masked_load" => SIMDExpr::MaskedLoad {
+                address: next(),
+                mask: next(),
+                passthru: next(),
+                position,
+            },
+            "masked_store" => SIMDExpr::MaskedStore {
+                address: next(),
+                vector: next(),
+                mask: next(),
+                position,
+            },
+            "gather" => SIMDExpr::Gather {
+                base: next(),
+                indices: next(),
+                position,
+            },
+            "scatter" => SIMDExpr::Scatter {
+                base: next(),
+                indices: next(),
+                vector: next(),
+                position,
+            },
+            _ => SIMDExpr::Shuffle {
+                left: next(),
+                right: next(),
+                mask: next(),
+                position,
+            },
+        };
+
+        Ok(Expr::SIMD(simd_expr, self.span_from(&start)))
     }
 
     /// Parse alignment parameter (expects integer literal)
@@ -2111,6 +3108,7 @@ impl Parser {
 
     /// Parse struct literal: StructName { field1: value1, field2: value2 }
     fn parse_struct_literal(&mut self, struct_name: String) -> Result<Expr> {
+        let start = self.previous().position.clone();
         self.consume(
             TokenKind::LeftBrace,
             "Expected '{' for struct literal".to_string(),
@@ -2148,11 +3146,13 @@ impl Parser {
         Ok(Expr::StructLiteral {
             name: struct_name,
             fields,
+            span: self.span_from(&start),
         })
     }
 
     /// Parse enum literal: EnumName::Variant or EnumName::Variant(args)
     fn parse_enum_literal(&mut self, enum_name: String) -> Result<Expr> {
+        let start = self.previous().position.clone();
         // Consume the DoubleColon token directly since check() has issues with it
         if !self.is_at_end() && matches!(self.peek().kind, TokenKind::DoubleColon) {
             self.advance();
@@ -2189,6 +3189,7 @@ impl Parser {
             enum_name,
             variant: variant_name,
             args,
+            span: self.span_from(&start),
         })
     }
 
@@ -2203,7 +3204,7 @@ impl Parser {
 
         while self.match_tokens(&[TokenKind::Or]) {
             let right = self.match_value_logical_and()?;
-            expr = Expr::Binary(Box::new(expr), BinaryOp::Or, Box::new(right));
+            expr = binary_expr(expr, BinaryOp::Or, right);
         }
 
         Ok(expr)
@@ -2215,7 +3216,7 @@ impl Parser {
 
         while self.match_tokens(&[TokenKind::And]) {
             let right = self.match_value_simd_or()?;
-            expr = Expr::Binary(Box::new(expr), BinaryOp::And, Box::new(right));
+            expr = binary_expr(expr, BinaryOp::And, right);
         }
 
         Ok(expr)
@@ -2227,12 +3228,12 @@ impl Parser {
 
         while self.match_tokens(&[TokenKind::DotOr]) {
             let right = self.match_value_equality()?;
-            expr = Expr::SIMD(SIMDExpr::ElementWise {
-                left: Box::new(expr),
-                operator: SIMDOperator::DotOr,
-                right: Box::new(right),
-                position: self.previous().position.clone(),
-            });
+            expr = element_wise_expr(
+                expr,
+                SIMDOperator::DotOr,
+                right,
+                self.previous().position.clone(),
+            );
         }
 
         Ok(expr)
@@ -2253,26 +3254,26 @@ impl Parser {
 
             match operator.kind {
                 TokenKind::Equal => {
-                    expr = Expr::Binary(Box::new(expr), BinaryOp::Equal, Box::new(right));
+                    expr = binary_expr(expr, BinaryOp::Equal, right);
                 }
                 TokenKind::NotEqual => {
-                    expr = Expr::Binary(Box::new(expr), BinaryOp::NotEqual, Box::new(right));
+                    expr = binary_expr(expr, BinaryOp::NotEqual, right);
                 }
                 TokenKind::DotEqual => {
-                    expr = Expr::SIMD(SIMDExpr::ElementWise {
-                        left: Box::new(expr),
-                        operator: SIMDOperator::DotEqual,
-                        right: Box::new(right),
-                        position: operator.position,
-                    });
+                    expr = element_wise_expr(
+                        expr,
+                        SIMDOperator::DotEqual,
+                        right,
+                        operator.position,
+                    );
                 }
                 TokenKind::DotNotEqual => {
-                    expr = Expr::SIMD(SIMDExpr::ElementWise {
-                        left: Box::new(expr),
-                        operator: SIMDOperator::DotNotEqual,
-                        right: Box::new(right),
-                        position: operator.position,
-                    });
+                    expr = element_wise_expr(
+                        expr,
+                        SIMDOperator::DotNotEqual,
+                        right,
+                        operator.position,
+                    );
                 }
                 _ => unreachable!(),
             }
@@ -2302,7 +3303,7 @@ impl Parser {
                 _ => unreachable!(),
             };
 
-            expr = Expr::Binary(Box::new(expr), binary_op, Box::new(right));
+            expr = binary_expr(expr, binary_op, right);
         }
 
         Ok(expr)
@@ -2313,33 +3314,34 @@ impl Parser {
         // Handle literals
         if let Some(token) = self.match_tokens_and_get(&[TokenKind::Integer(0)]) {
             if let TokenKind::Integer(value) = token.kind {
-                return Ok(Expr::Literal(Literal::Integer(value)));
+                return Ok(Expr::Literal(Literal::Integer(value), self.previous_span()));
             }
         }
 
         if let Some(token) = self.match_tokens_and_get(&[TokenKind::Float(0.0)]) {
             if let TokenKind::Float(value) = token.kind {
-                return Ok(Expr::Literal(Literal::Float(value)));
+                return Ok(Expr::Literal(Literal::Float(value), self.previous_span()));
             }
         }
 
         if let Some(token) = self.match_tokens_and_get(&[TokenKind::StringLiteral("".to_string())])
         {
             if let TokenKind::StringLiteral(value) = token.kind {
-                return Ok(Expr::Literal(Literal::String(value)));
+                return Ok(Expr::Literal(Literal::String(value), self.previous_span()));
             }
         }
 
         if self.match_tokens(&[TokenKind::True]) {
-            return Ok(Expr::Literal(Literal::Boolean(true)));
+            return Ok(Expr::Literal(Literal::Boolean(true), self.previous_span()));
         }
 
         if self.match_tokens(&[TokenKind::False]) {
-            return Ok(Expr::Literal(Literal::Boolean(false)));
+            return Ok(Expr::Literal(Literal::Boolean(false), self.previous_span()));
         }
 
         // Handle identifiers (variables, function calls, enum literals)
         if let Some(token) = self.match_tokens_and_get(&[TokenKind::Identifier("".to_string())]) {
+            let name_span = token.span();
             if let TokenKind::Identifier(name) = token.kind {
                 // Check for enum literal with ::
                 if self.match_tokens(&[TokenKind::DoubleColon]) {
@@ -2369,6 +3371,7 @@ impl Parser {
                             enum_name: name,
                             variant: variant_token,
                             args,
+                            span: self.span_from(&name_span.start),
                         });
                     } else {
                         // Simple enum literal without arguments
@@ -2376,6 +3379,7 @@ impl Parser {
                             enum_name: name,
                             variant: variant_token,
                             args: vec![],
+                            span: self.span_from(&name_span.start),
                         });
                     }
                 }
@@ -2387,7 +3391,7 @@ impl Parser {
 
                 // NOTE: NO struct literal check here - this is the key difference
 
-                return Ok(Expr::Variable(name));
+                return Ok(Expr::Variable(name, name_span));
             }
         }
 
@@ -2402,7 +3406,12 @@ impl Parser {
         }
 
         // Handle unary expressions
-        if self.match_tokens(&[TokenKind::Minus, TokenKind::Not, TokenKind::Ampersand]) {
+        if self.match_tokens(&[
+            TokenKind::Minus,
+            TokenKind::Not,
+            TokenKind::Ampersand,
+            TokenKind::Star,
+        ]) {
             let operator = self.previous().clone();
             let expr = self.match_value_primary()?;
 
@@ -2410,10 +3419,12 @@ impl Parser {
                 TokenKind::Minus => UnaryOp::Negate,
                 TokenKind::Not => UnaryOp::Not,
                 TokenKind::Ampersand => UnaryOp::Reference,
+                TokenKind::Star => UnaryOp::Deref,
                 _ => unreachable!(),
             };
 
-            return Ok(Expr::Unary(unary_op, Box::new(expr)));
+            let span = self.span_from(&operator.position);
+            return Ok(Expr::Unary(unary_op, Box::new(expr), span));
         }
 
         Err(CompileError::parse_error(
@@ -2424,6 +3435,7 @@ impl Parser {
 
     /// Parse match expression: match value { pattern => expr, ... }
     fn parse_match_expression(&mut self) -> Result<Expr> {
+        let start = self.previous().position.clone();
         let value = Box::new(self.match_value()?);
 
         self.consume(
@@ -2436,6 +3448,12 @@ impl Parser {
         while !self.check(&TokenKind::RightBrace) && !self.is_at_end() {
             let pattern = self.parse_pattern()?;
 
+            let guard = if self.match_tokens(&[TokenKind::If]) {
+                Some(self.expression()?)
+            } else {
+                None
+            };
+
             self.consume(
                 TokenKind::FatArrow, // =>
                 "Expected '=>' after pattern".to_string(),
@@ -2445,6 +3463,7 @@ impl Parser {
 
             arms.push(MatchArm {
                 pattern,
+                guard,
                 expression,
             });
 
@@ -2462,12 +3481,35 @@ impl Parser {
             "Expected '}' after match arms".to_string(),
         )?;
 
-        Ok(Expr::Match { value, arms })
+        Ok(Expr::Match {
+            value,
+            arms,
+            span: self.span_from(&start),
+        })
     }
 
-    /// Parse a pattern in a match arm
+    /// Parse a pattern in a match arm, including `A | B` alternatives
     fn parse_pattern(&mut self) -> Result<Pattern> {
-        // Handle identifier patterns (variable, wildcard, enum variant)
+        let mut alternatives = vec![self.parse_single_pattern()?];
+        while self.match_tokens(&[TokenKind::Pipe]) {
+            alternatives.push(self.parse_single_pattern()?);
+        }
+
+        if alternatives.len() == 1 {
+            Ok(alternatives.remove(0))
+        } else {
+            Ok(Pattern::Or(alternatives))
+        }
+    }
+
+    /// Parse a pattern without top-level alternatives
+    fn parse_single_pattern(&mut self) -> Result<Pattern> {
+        // Handle tuple patterns: (a, _)
+        if self.match_tokens(&[TokenKind::LeftParen]) {
+            return self.parse_tuple_pattern(Self::parse_pattern);
+        }
+
+        // Handle identifier patterns (variable, wildcard, enum variant, struct)
         if self.check_token_type(&TokenKind::Identifier("".to_string())) {
             let token = self.advance().clone();
             if let TokenKind::Identifier(name) = token.kind {
@@ -2485,41 +3527,246 @@ impl Parser {
                     return self.parse_variant_pattern_with_subpatterns(name);
                 }
 
+                // Check if this is a struct pattern (Point { x, y })
+                if self.match_tokens(&[TokenKind::LeftBrace]) {
+                    return self.parse_struct_pattern(name);
+                }
+
                 // Otherwise, it's a variable pattern
                 return Ok(Pattern::Variable(name));
             }
         }
 
-        // Handle literals
-        if self.match_tokens(&[TokenKind::True]) {
-            return Ok(Pattern::Literal(Literal::Boolean(true)));
+        // Handle literals and ranges of them
+        if let Some(start) = self.pattern_literal()? {
+            let inclusive = if self.match_tokens(&[TokenKind::DotDotEq]) {
+                true
+            } else if self.match_tokens(&[TokenKind::DotDot]) {
+                false
+            } else {
+                return Ok(Pattern::Literal(start));
+            };
+
+            let end = self.pattern_literal()?.ok_or_else(|| {
+                CompileError::parse_error(
+                    "Expected literal at end of range pattern".to_string(),
+                    self.peek().position.clone(),
+                )
+            })?;
+            return Ok(Pattern::Range {
+                start,
+                end,
+                inclusive,
+            });
         }
 
-        if self.match_tokens(&[TokenKind::False]) {
-            return Ok(Pattern::Literal(Literal::Boolean(false)));
+        Err(CompileError::parse_error(
+            "Expected pattern".to_string(),
+            self.peek().position.clone(),
+        ))
+    }
+
+    /// Parses the literal of a literal or range pattern, if one comes next.
+    /// Numbers may be negated: `-1`.
+    fn pattern_literal(&mut self) -> Result<Option<Literal>> {
+        if self.match_tokens(&[TokenKind::True]) {
+            return Ok(Some(Literal::Boolean(true)));
         }
 
-        if let Some(token) = self.match_tokens_and_get(&[TokenKind::Integer(0)]) {
-            if let TokenKind::Integer(n) = token.kind {
-                return Ok(Pattern::Literal(Literal::Integer(n)));
-            }
+        if self.match_tokens(&[TokenKind::False]) {
+            return Ok(Some(Literal::Boolean(false)));
         }
 
         if let Some(token) = self.match_tokens_and_get(&[TokenKind::StringLiteral("".to_string())])
         {
             if let TokenKind::StringLiteral(s) = token.kind {
-                return Ok(Pattern::Literal(Literal::String(s)));
+                return Ok(Some(Literal::String(s)));
             }
         }
 
-        Err(CompileError::parse_error(
-            "Expected pattern".to_string(),
-            self.peek().position.clone(),
-        ))
+        let negative = self.check(&TokenKind::Minus)
+            && matches!(
+                self.tokens.get(self.current + 1).map(|token| &token.kind),
+                Some(TokenKind::Integer(_)) | Some(TokenKind::Float(_))
+            );
+        if negative {
+            self.advance();
+        }
+
+        match self.peek().kind.clone() {
+            TokenKind::Integer(n) => {
+                self.advance();
+                Ok(Some(Literal::Integer(if negative { -n } else { n })))
+            }
+            TokenKind::Float(n) => {
+                self.advance();
+                Ok(Some(Literal::Float(if negative { -n } else { n })))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Parses the fields of a struct pattern after its '{': `x`, `y: pattern`,
+    /// and a final `..` to skip the fields not named.
+    fn parse_struct_pattern(&mut self, name: String) -> Result<Pattern> {
+        let mut fields = Vec::new();
+        let mut has_rest = false;
+
+        while !self.check(&TokenKind::RightBrace) && !self.is_at_end() {
+            if self.match_tokens(&[TokenKind::DotDot]) {
+                has_rest = true;
+                break;
+            }
+
+            let field_name = self.consume_identifier("Expected field name in struct pattern".to_string())?;
+            let pattern = if self.match_tokens(&[TokenKind::Colon]) {
+                self.parse_pattern()?
+            } else {
+                Pattern::Variable(field_name.clone())
+            };
+            fields.push(FieldPattern {
+                name: field_name,
+                pattern,
+            });
+
+            if !self.match_tokens(&[TokenKind::Comma]) {
+                break;
+            }
+        }
+
+        self.consume(
+            TokenKind::RightBrace,
+            "Expected '}' after struct pattern".to_string(),
+        )?;
+
+        Ok(Pattern::Struct {
+            name,
+            fields,
+            has_rest,
+        })
+    }
+
+    /// Parses the elements of a tuple pattern after its '(', using `element`
+    /// for each of them.
+    fn parse_tuple_pattern(
+        &mut self,
+        element: fn(&mut Self) -> Result<Pattern>,
+    ) -> Result<Pattern> {
+        let mut patterns = Vec::new();
+        while !self.check(&TokenKind::RightParen) {
+            patterns.push(element(self)?);
+            if !self.match_tokens(&[TokenKind::Comma]) {
+                break;
+            }
+        }
+        self.consume(
+            TokenKind::RightParen,
+            "Expected ')' after tuple pattern".to_string(),
+        )?;
+        Ok(Pattern::Tuple { patterns })
     }
 
     /// Parse a block expression: { statements... }
+    /// Parses a closure: `|a: i32, b: i32| -> i32 { a + b }` or `|x: f32| x * 2.0`.
+    /// `||` starts a closure without parameters.
+    fn parse_closure(&mut self) -> Result<Expr> {
+        let start = self.peek().position.clone();
+        let mut params = Vec::new();
+        if !self.match_tokens(&[TokenKind::Or]) {
+            self.consume(TokenKind::Pipe, "Expected '|' to start closure".to_string())?;
+            while !self.check(&TokenKind::Pipe) {
+                let name = self.consume_identifier("Expected closure parameter name".to_string())?;
+                self.consume(
+                    TokenKind::Colon,
+                    format!("Expected ':' and a type after closure parameter '{}'", name),
+                )?;
+                let type_name = self.consume_type_name("Expected parameter type".to_string())?;
+                params.push(Parameter {
+                    name,
+                    type_annotation: TypeAnnotation {
+                        name: type_name,
+                        is_mutable: false,
+                    },
+                });
+                if !self.match_tokens(&[TokenKind::Comma]) {
+                    break;
+                }
+            }
+            self.consume(TokenKind::Pipe, "Expected '|' after closure parameters".to_string())?;
+        }
+
+        let return_type = if self.match_tokens(&[TokenKind::Arrow]) {
+            let type_name = self.consume_type_name("Expected closure return type".to_string())?;
+            Some(TypeAnnotation {
+                name: type_name,
+                is_mutable: false,
+            })
+        } else {
+            None
+        };
+
+        let body = if self.match_tokens(&[TokenKind::LeftBrace]) {
+            self.parse_closure_block()?
+        } else {
+            self.expression()?
+        };
+
+        Ok(Expr::Closure {
+            params,
+            return_type,
+            body: Box::new(body),
+            span: self.span_from(&start),
+        })
+    }
+
+    /// Parses the `{ ... }` body of a closure. Unlike other blocks, the final
+    /// expression may leave out its `;`, as in `|x: f32| -> f32 { x * 2.0 }`.
+    fn parse_closure_block(&mut self) -> Result<Expr> {
+        let start = self.previous().position.clone();
+        let mut statements = Vec::new();
+
+        while !self.check(&TokenKind::RightBrace) && !self.is_at_end() {
+            let starts_statement = matches!(
+                self.peek().kind,
+                TokenKind::Let
+                    | TokenKind::Const
+                    | TokenKind::Func
+                    | TokenKind::Return
+                    | TokenKind::LeftBrace
+                    | TokenKind::If
+                    | TokenKind::While
+                    | TokenKind::For
+                    | TokenKind::Match
+                    | TokenKind::Break
+                    | TokenKind::Continue
+                    | TokenKind::Label(_)
+            );
+            if starts_statement {
+                statements.push(self.declaration()?);
+                continue;
+            }
+
+            let expr_start = self.peek().position.clone();
+            let expr = self.expression()?;
+            if !self.check(&TokenKind::RightBrace) {
+                self.consume(
+                    TokenKind::Semicolon,
+                    "Expected ';' after expression".to_string(),
+                )?;
+            }
+            statements.push(Stmt::Expression(expr, self.span_from(&expr_start)));
+        }
+
+        self.consume(
+            TokenKind::RightBrace,
+            "Expected '}' after closure body".to_string(),
+        )?;
+
+        Ok(Expr::Block(statements, self.span_from(&start)))
+    }
+
     fn parse_block_expression(&mut self) -> Result<Expr> {
+        let start = self.previous().position.clone();
         let mut statements = Vec::new();
 
         while !self.check(&TokenKind::RightBrace) && !self.is_at_end() {
@@ -2531,7 +3778,7 @@ impl Parser {
             "Expected '}' after block".to_string(),
         )?;
 
-        Ok(Expr::Block(statements))
+        Ok(Expr::Block(statements, self.span_from(&start)))
     }
 
     /// Parse enum variant pattern: EnumName::Variant(patterns...)
@@ -2623,12 +3870,22 @@ impl Parser {
                 self.peek().kind,
                 TokenKind::Func
                     | TokenKind::Let
+                    | TokenKind::Const
+                    | TokenKind::Comptime
+                    | TokenKind::At
                     | TokenKind::Struct
                     | TokenKind::Enum
+                    | TokenKind::Trait
+                    | TokenKind::Impl
                     | TokenKind::If
                     | TokenKind::While
                     | TokenKind::For
                     | TokenKind::Return
+                    | TokenKind::Break
+                    | TokenKind::Continue
+                    | TokenKind::Import
+                    | TokenKind::Use
+                    | TokenKind::From
             ) {
                 return;
             }
@@ -2639,7 +3896,7 @@ impl Parser {
 
     /// Recover from a parse error with suggestions
     fn recover_from_parse_error(&mut self, error: CompileError) -> RecoveryAction {
-        let suggestions = self.suggest_fixes(&error);
+        let suggestions = Self::suggest_fixes(&error);
 
         // Log suggestions (in a real implementation, these would be shown to the user)
         for suggestion in suggestions {
@@ -2669,7 +3926,7 @@ impl Parser {
     }
 
     /// Generate intelligent error suggestions
-    fn suggest_fixes(&self, error: &CompileError) -> Vec<ErrorSuggestion> {
+    pub fn suggest_fixes(error: &CompileError) -> Vec<ErrorSuggestion> {
         let mut suggestions = Vec::new();
 
         match error {
@@ -2803,7 +4060,7 @@ impl Parser {
 
         // 3. Check for typos in keywords
         if let TokenKind::Identifier(name) = &self.peek().kind {
-            let suggestions = self.suggest_keyword_corrections(name);
+            let suggestions = Self::suggest_keyword_corrections(name);
             if !suggestions.is_empty() {
                 // For now, just skip the incorrect identifier
                 return Some(RecoveryAction::Skip);
@@ -2815,44 +4072,10 @@ impl Parser {
     }
 
     /// Suggest corrections for mistyped keywords
-    fn suggest_keyword_corrections(&self, identifier: &str) -> Vec<String> {
-        let keywords = vec![
-            "func",
-            "let",
-            "if",
-            "else",
-            "while",
-            "for",
-            "return",
-            "struct",
-            "enum",
-            "match",
-            "true",
-            "false",
-            "vectorize",
-            "unroll",
-            "align",
-            "reduce",
-            "i8",
-            "i16",
-            "i32",
-            "i64",
-            "u8",
-            "u16",
-            "u32",
-            "u64",
-            "f32",
-            "f64",
-            "bool",
-            "string",
-            "f32x4",
-            "i32x4",
-            "f64x2",
-        ];
-
+    fn suggest_keyword_corrections(identifier: &str) -> Vec<String> {
         let mut suggestions = Vec::new();
 
-        for keyword in keywords {
+        for keyword in KEYWORDS {
             if Self::levenshtein_distance(identifier, keyword) <= 2 {
                 suggestions.push(keyword.to_string());
             }
@@ -2939,6 +4162,26 @@ impl Parser {
     }
 }
 
+/// Builds `left op right`, spanning both operands.
+fn binary_expr(left: Expr, op: BinaryOp, right: Expr) -> Expr {
+    let span = left.span().to(&right.span());
+    Expr::Binary(Box::new(left), op, Box::new(right), span)
+}
+
+/// Builds an element-wise SIMD operation such as `a .+ b`, spanning both operands.
+fn element_wise_expr(left: Expr, operator: SIMDOperator, right: Expr, position: Position) -> Expr {
+    let span = left.span().to(&right.span());
+    Expr::SIMD(
+        SIMDExpr::ElementWise {
+            left: Box::new(left),
+            operator,
+            right: Box::new(right),
+            position,
+        },
+        span,
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2955,33 +4198,42 @@ mod tests {
     #[test]
     fn test_parse_integer_literal() {
         let expr = parse_expr("42").unwrap();
-        assert_eq!(expr, Expr::Literal(Literal::Integer(42)));
+        assert_eq!(expr, Expr::Literal(Literal::Integer(42), Span::default()));
     }
 
     #[test]
     fn test_parse_float_literal() {
         let expr = parse_expr("3.14").unwrap();
-        assert_eq!(expr, Expr::Literal(Literal::Float(3.14)));
+        assert_eq!(expr, Expr::Literal(Literal::Float(3.14), Span::default()));
     }
 
     #[test]
     fn test_parse_string_literal() {
         let expr = parse_expr(r#""hello""#).unwrap();
-        assert_eq!(expr, Expr::Literal(Literal::String("hello".to_string())));
+        assert_eq!(
+            expr,
+            Expr::Literal(Literal::String("hello".to_string()), Span::default())
+        );
     }
 
     #[test]
     fn test_parse_boolean_literal() {
         let expr1 = parse_expr("true").unwrap();
         let expr2 = parse_expr("false").unwrap();
-        assert_eq!(expr1, Expr::Literal(Literal::Boolean(true)));
-        assert_eq!(expr2, Expr::Literal(Literal::Boolean(false)));
+        assert_eq!(
+            expr1,
+            Expr::Literal(Literal::Boolean(true), Span::default())
+        );
+        assert_eq!(
+            expr2,
+            Expr::Literal(Literal::Boolean(false), Span::default())
+        );
     }
 
     #[test]
     fn test_parse_variable() {
         let expr = parse_expr("foo").unwrap();
-        assert_eq!(expr, Expr::Variable("foo".to_string()));
+        assert_eq!(expr, Expr::Variable("foo".to_string(), Span::default()));
     }
 
     #[test]
@@ -2993,7 +4245,8 @@ mod tests {
             expr1,
             Expr::Unary(
                 UnaryOp::Negate,
-                Box::new(Expr::Literal(Literal::Integer(42)))
+                Box::new(Expr::Literal(Literal::Integer(42), Span::default())),
+                Span::default()
             )
         );
 
@@ -3001,7 +4254,8 @@ mod tests {
             expr2,
             Expr::Unary(
                 UnaryOp::Not,
-                Box::new(Expr::Literal(Literal::Boolean(true)))
+                Box::new(Expr::Literal(Literal::Boolean(true), Span::default())),
+                Span::default()
             )
         );
     }
@@ -3013,9 +4267,10 @@ mod tests {
         assert_eq!(
             expr,
             Expr::Binary(
-                Box::new(Expr::Literal(Literal::Integer(1))),
+                Box::new(Expr::Literal(Literal::Integer(1), Span::default())),
                 BinaryOp::Add,
-                Box::new(Expr::Literal(Literal::Integer(2)))
+                Box::new(Expr::Literal(Literal::Integer(2), Span::default())),
+                Span::default()
             )
         );
     }
@@ -3028,13 +4283,15 @@ mod tests {
         assert_eq!(
             expr,
             Expr::Binary(
-                Box::new(Expr::Literal(Literal::Integer(1))),
+                Box::new(Expr::Literal(Literal::Integer(1), Span::default())),
                 BinaryOp::Add,
                 Box::new(Expr::Binary(
-                    Box::new(Expr::Literal(Literal::Integer(2))),
+                    Box::new(Expr::Literal(Literal::Integer(2), Span::default())),
                     BinaryOp::Multiply,
-                    Box::new(Expr::Literal(Literal::Integer(3)))
-                ))
+                    Box::new(Expr::Literal(Literal::Integer(3), Span::default())),
+                    Span::default()
+                )),
+                Span::default()
             )
         );
     }
@@ -3047,13 +4304,18 @@ mod tests {
         assert_eq!(
             expr,
             Expr::Binary(
-                Box::new(Expr::Grouping(Box::new(Expr::Binary(
-                    Box::new(Expr::Literal(Literal::Integer(1))),
-                    BinaryOp::Add,
-                    Box::new(Expr::Literal(Literal::Integer(2)))
-                )))),
+                Box::new(Expr::Grouping(
+                    Box::new(Expr::Binary(
+                        Box::new(Expr::Literal(Literal::Integer(1), Span::default())),
+                        BinaryOp::Add,
+                        Box::new(Expr::Literal(Literal::Integer(2), Span::default())),
+                        Span::default()
+                    )),
+                    Span::default()
+                )),
                 BinaryOp::Multiply,
-                Box::new(Expr::Literal(Literal::Integer(3)))
+                Box::new(Expr::Literal(Literal::Integer(3), Span::default())),
+                Span::default()
             )
         );
     }
@@ -3065,11 +4327,12 @@ mod tests {
         assert_eq!(
             expr,
             Expr::Call(
-                Box::new(Expr::Variable("foo".to_string())),
+                Box::new(Expr::Variable("foo".to_string(), Span::default())),
                 vec![
-                    Expr::Literal(Literal::Integer(1)),
-                    Expr::Literal(Literal::Integer(2))
-                ]
+                    Expr::Literal(Literal::Integer(1), Span::default()),
+                    Expr::Literal(Literal::Integer(2), Span::default())
+                ],
+                Span::default()
             )
         );
     }
@@ -3081,12 +4344,14 @@ mod tests {
         assert_eq!(
             expr,
             Expr::Index(
-                Box::new(Expr::Variable("array".to_string())),
+                Box::new(Expr::Variable("array".to_string(), Span::default())),
                 Box::new(Expr::Binary(
-                    Box::new(Expr::Literal(Literal::Integer(1))),
+                    Box::new(Expr::Literal(Literal::Integer(1), Span::default())),
                     BinaryOp::Add,
-                    Box::new(Expr::Literal(Literal::Integer(2)))
-                ))
+                    Box::new(Expr::Literal(Literal::Integer(2), Span::default())),
+                    Span::default()
+                )),
+                Span::default()
             )
         );
     }
@@ -3098,8 +4363,9 @@ mod tests {
         assert_eq!(
             expr,
             Expr::FieldAccess(
-                Box::new(Expr::Variable("object".to_string())),
-                "field".to_string()
+                Box::new(Expr::Variable("object".to_string(), Span::default())),
+                "field".to_string(),
+                Span::default()
             )
         );
     }
@@ -3111,9 +4377,10 @@ mod tests {
         assert_eq!(
             expr,
             Expr::Binary(
-                Box::new(Expr::Variable("x".to_string())),
+                Box::new(Expr::Variable("x".to_string(), Span::default())),
                 BinaryOp::Assign,
-                Box::new(Expr::Literal(Literal::Integer(42)))
+                Box::new(Expr::Literal(Literal::Integer(42), Span::default())),
+                Span::default()
             )
         );
     }
@@ -3126,18 +4393,20 @@ mod tests {
         assert_eq!(
             expr1,
             Expr::Binary(
-                Box::new(Expr::Variable("x".to_string())),
+                Box::new(Expr::Variable("x".to_string(), Span::default())),
                 BinaryOp::PlusAssign,
-                Box::new(Expr::Literal(Literal::Integer(5)))
+                Box::new(Expr::Literal(Literal::Integer(5), Span::default())),
+                Span::default()
             )
         );
 
         assert_eq!(
             expr2,
             Expr::Binary(
-                Box::new(Expr::Variable("y".to_string())),
+                Box::new(Expr::Variable("y".to_string(), Span::default())),
                 BinaryOp::MultiplyAssign,
-                Box::new(Expr::Variable("z".to_string()))
+                Box::new(Expr::Variable("z".to_string(), Span::default())),
+                Span::default()
             )
         );
     }
@@ -3156,14 +4425,18 @@ mod tests {
         let result = parse_statement(source).unwrap();
 
         if let Stmt::VarDeclaration {
-            name,
+            pattern,
             type_annotation,
             initializer,
+            ..
         } = result
         {
-            assert_eq!(name, "x");
+            assert_eq!(pattern, Pattern::Variable("x".to_string()));
             assert!(type_annotation.is_none());
-            assert_eq!(initializer.unwrap(), Expr::Literal(Literal::Integer(42)));
+            assert_eq!(
+                initializer.unwrap(),
+                Expr::Literal(Literal::Integer(42), Span::default())
+            );
         } else {
             panic!("Expected variable declaration, got {:?}", result);
         }
@@ -3175,19 +4448,23 @@ mod tests {
         let result = parse_statement(source).unwrap();
 
         if let Stmt::VarDeclaration {
-            name,
+            pattern,
             type_annotation,
             initializer,
+            ..
         } = result
         {
-            assert_eq!(name, "y");
+            assert_eq!(pattern, Pattern::Variable("y".to_string()));
 
             assert!(type_annotation.is_some());
             let type_ann = type_annotation.unwrap();
             assert_eq!(type_ann.name, "i32");
             assert!(type_ann.is_mutable);
 
-            assert_eq!(initializer.unwrap(), Expr::Literal(Literal::Integer(10)));
+            assert_eq!(
+                initializer.unwrap(),
+                Expr::Literal(Literal::Integer(10), Span::default())
+            );
         } else {
             panic!("Expected variable declaration, got {:?}", result);
         }
@@ -3198,12 +4475,12 @@ mod tests {
         let source = "foo(1, 2);";
         let result = parse_statement(source).unwrap();
 
-        if let Stmt::Expression(expr) = result {
-            if let Expr::Call(callee, args) = expr {
-                assert_eq!(*callee, Expr::Variable("foo".to_string()));
+        if let Stmt::Expression(expr, _) = result {
+            if let Expr::Call(callee, args, _) = expr {
+                assert_eq!(*callee, Expr::Variable("foo".to_string(), Span::default()));
                 assert_eq!(args.len(), 2);
-                assert_eq!(args[0], Expr::Literal(Literal::Integer(1)));
-                assert_eq!(args[1], Expr::Literal(Literal::Integer(2)));
+                assert_eq!(args[0], Expr::Literal(Literal::Integer(1), Span::default()));
+                assert_eq!(args[1], Expr::Literal(Literal::Integer(2), Span::default()));
             } else {
                 panic!("Expected call expression, got {:?}", expr);
             }
@@ -3217,9 +4494,12 @@ mod tests {
         let source = "return 42;";
         let result = parse_statement(source).unwrap();
 
-        if let Stmt::Return(expr) = result {
+        if let Stmt::Return(expr, _) = result {
             assert!(expr.is_some());
-            assert_eq!(expr.unwrap(), Expr::Literal(Literal::Integer(42)));
+            assert_eq!(
+                expr.unwrap(),
+                Expr::Literal(Literal::Integer(42), Span::default())
+            );
         } else {
             panic!("Expected return statement, got {:?}", result);
         }
@@ -3230,7 +4510,7 @@ mod tests {
         let source = "return;";
         let result = parse_statement(source).unwrap();
 
-        if let Stmt::Return(expr) = result {
+        if let Stmt::Return(expr, _) = result {
             assert!(expr.is_none());
         } else {
             panic!("Expected return statement, got {:?}", result);
@@ -3242,17 +4522,17 @@ mod tests {
         let source = "{ let x = 1; let y = 2; }";
         let result = parse_statement(source).unwrap();
 
-        if let Stmt::Block(statements) = result {
+        if let Stmt::Block(statements, _) = result {
             assert_eq!(statements.len(), 2);
 
-            if let Stmt::VarDeclaration { name, .. } = &statements[0] {
-                assert_eq!(name, "x");
+            if let Stmt::VarDeclaration { pattern, .. } = &statements[0] {
+                assert_eq!(pattern, &Pattern::Variable("x".to_string()));
             } else {
                 panic!("Expected variable declaration");
             }
 
-            if let Stmt::VarDeclaration { name, .. } = &statements[1] {
-                assert_eq!(name, "y");
+            if let Stmt::VarDeclaration { pattern, .. } = &statements[1] {
+                assert_eq!(pattern, &Pattern::Variable("y".to_string()));
             } else {
                 panic!("Expected variable declaration");
             }
@@ -3270,24 +4550,28 @@ mod tests {
             condition,
             then_branch,
             else_branch,
+            ..
         } = result
         {
             // Check condition
-            assert!(matches!(condition, Expr::Binary(_, BinaryOp::Greater, _)));
+            assert!(matches!(
+                condition,
+                Expr::Binary(_, BinaryOp::Greater, _, _)
+            ));
 
             // Check then branch
-            if let Stmt::Block(stmts) = *then_branch {
+            if let Stmt::Block(stmts, _) = *then_branch {
                 assert_eq!(stmts.len(), 1);
-                assert!(matches!(stmts[0], Stmt::Return(_)));
+                assert!(matches!(stmts[0], Stmt::Return(_, _)));
             } else {
                 panic!("Expected block in then branch");
             }
 
             // Check else branch
             assert!(else_branch.is_some());
-            if let Stmt::Block(stmts) = *else_branch.unwrap() {
+            if let Stmt::Block(stmts, _) = *else_branch.unwrap() {
                 assert_eq!(stmts.len(), 1);
-                assert!(matches!(stmts[0], Stmt::Return(_)));
+                assert!(matches!(stmts[0], Stmt::Return(_, _)));
             } else {
                 panic!("Expected block in else branch");
             }
@@ -3301,14 +4585,17 @@ mod tests {
         let source = "while (i < 10) { i += 1; }";
         let result = parse_statement(source).unwrap();
 
-        if let Stmt::While { condition, body } = result {
+        if let Stmt::While {
+            condition, body, ..
+        } = result
+        {
             // Check condition
-            assert!(matches!(condition, Expr::Binary(_, BinaryOp::Less, _)));
+            assert!(matches!(condition, Expr::Binary(_, BinaryOp::Less, _, _)));
 
             // Check body
-            if let Stmt::Block(stmts) = *body {
+            if let Stmt::Block(stmts, _) = *body {
                 assert_eq!(stmts.len(), 1);
-                assert!(matches!(stmts[0], Stmt::Expression(_)));
+                assert!(matches!(stmts[0], Stmt::Expression(_, _)));
             } else {
                 panic!("Expected block in while body");
             }
@@ -3327,6 +4614,7 @@ mod tests {
             condition,
             increment,
             body,
+            ..
         } = result
         {
             // Check initializer
@@ -3337,20 +4625,20 @@ mod tests {
             assert!(condition.is_some());
             assert!(matches!(
                 condition.unwrap(),
-                Expr::Binary(_, BinaryOp::Less, _)
+                Expr::Binary(_, BinaryOp::Less, _, _)
             ));
 
             // Check increment
             assert!(increment.is_some());
             assert!(matches!(
                 increment.unwrap(),
-                Expr::Binary(_, BinaryOp::PlusAssign, _)
+                Expr::Binary(_, BinaryOp::PlusAssign, _, _)
             ));
 
             // Check body
-            if let Stmt::Block(stmts) = *body {
+            if let Stmt::Block(stmts, _) = *body {
                 assert_eq!(stmts.len(), 1);
-                assert!(matches!(stmts[0], Stmt::Expression(_)));
+                assert!(matches!(stmts[0], Stmt::Expression(_, _)));
             } else {
                 panic!("Expected block in for body");
             }
@@ -3387,9 +4675,9 @@ mod tests {
             assert_eq!(return_type.unwrap().name, "i32");
 
             // Check body
-            if let Stmt::Block(stmts) = *body {
+            if let Stmt::Block(stmts, _) = *body {
                 assert_eq!(stmts.len(), 1);
-                assert!(matches!(stmts[0], Stmt::Return(_)));
+                assert!(matches!(stmts[0], Stmt::Return(_, _)));
             } else {
                 panic!("Expected block in function body");
             }
@@ -3424,10 +4712,10 @@ mod tests {
             assert_eq!(return_type.unwrap().name, "()");
 
             // Check body
-            if let Stmt::Block(stmts) = *body {
+            if let Stmt::Block(stmts, _) = *body {
                 assert_eq!(stmts.len(), 2);
-                assert!(matches!(stmts[0], Stmt::Expression(_)));
-                assert!(matches!(stmts[1], Stmt::Return(None)));
+                assert!(matches!(stmts[0], Stmt::Expression(_, _)));
+                assert!(matches!(stmts[1], Stmt::Return(None, _)));
             } else {
                 panic!("Expected block in function body");
             }
@@ -3485,9 +4773,9 @@ mod tests {
 
         // Verify the structure
         match expr {
-            Expr::Index(array, index) => {
-                assert!(matches!(*array, Expr::Variable(_)));
-                assert!(matches!(*index, Expr::Binary(_, _, _)));
+            Expr::Index(array, index, _) => {
+                assert!(matches!(*array, Expr::Variable(_, _)));
+                assert!(matches!(*index, Expr::Binary(_, _, _, _)));
             }
             _ => panic!("Expected Index expression, got {:?}", expr),
         }
@@ -3504,11 +4792,14 @@ mod tests {
 
         // Verify SIMD vector literal structure
         match expr {
-            Expr::SIMD(SIMDExpr::VectorLiteral {
-                elements,
-                vector_type,
-                ..
-            }) => {
+            Expr::SIMD(
+                SIMDExpr::VectorLiteral {
+                    elements,
+                    vector_type,
+                    ..
+                },
+                _,
+            ) => {
                 assert_eq!(elements.len(), 4);
                 assert_eq!(vector_type, Some(SIMDVectorType::F32x4));
             }
@@ -3517,8 +4808,8 @@ mod tests {
     }
 
     #[test]
-    fn test_regular_array_literal() {
-        let source = "[1, 2, 3]";
+    fn test_simd_mask_operation_parsing() {
+        let source = "select(a .< b, a, b)";
         let mut lexer = Lexer::new(source);
         let tokens = lexer.tokenize_all().expect("Lexing should succeed");
         let mut parser = Parser::new(tokens);
@@ -3526,20 +4817,39 @@ mod tests {
         let expr = parser.expression().expect("Parsing should succeed");
 
         match expr {
-            Expr::Literal(Literal::Vector {
-                elements,
-                vector_type,
-            }) => {
-                assert_eq!(elements.len(), 3);
-                assert_eq!(vector_type, None);
+            Expr::SIMD(SIMDExpr::Select { mask, .. }, _) => {
+                assert!(matches!(
+                    *mask,
+                    Expr::SIMD(
+                        SIMDExpr::ElementWise {
+                            operator: SIMDOperator::DotLess,
+                            ..
+                        },
+                        _
+                    )
+                ));
             }
-            _ => panic!("Expected Vector literal, got {:?}", expr),
+            _ => panic!("Expected SIMD Select, got {:?}", expr),
         }
+
+        let source = "masked_store(dst, v, m)";
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.tokenize_all().expect("Lexing should succeed");
+        let mut parser = Parser::new(tokens);
+        let expr = parser.expression().expect("Parsing should succeed");
+        assert!(matches!(expr, Expr::SIMD(SIMDExpr::MaskedStore { .. }, _)));
+
+        // All three operands are required
+        let source = "masked_load(src, m)";
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.tokenize_all().expect("Lexing should succeed");
+        let mut parser = Parser::new(tokens);
+        assert!(parser.expression().is_err());
     }
 
     #[test]
-    fn test_empty_array() {
-        let source = "[]";
+    fn test_simd_gather_shuffle_parsing() {
+        let source = "shuffle(a, b, [0, 4, 1, 5])";
         let mut lexer = Lexer::new(source);
         let tokens = lexer.tokenize_all().expect("Lexing should succeed");
         let mut parser = Parser::new(tokens);
@@ -3547,14 +4857,737 @@ mod tests {
         let expr = parser.expression().expect("Parsing should succeed");
 
         match expr {
-            Expr::Literal(Literal::Vector {
-                elements,
-                vector_type,
-            }) => {
-                assert_eq!(elements.len(), 0);
-                assert_eq!(vector_type, None);
+            Expr::SIMD(SIMDExpr::Shuffle { mask, .. }, _) => {
+                assert_eq!(
+                    crate::ast::constant_lane_indices(&mask),
+                    Some(vec![0, 4, 1, 5])
+                );
             }
-            _ => panic!("Expected empty Vector literal, got {:?}", expr),
+            _ => panic!("Expected SIMD Shuffle, got {:?}", expr),
         }
+
+        let source = "gather(base, idx)";
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.tokenize_all().expect("Lexing should succeed");
+        let mut parser = Parser::new(tokens);
+        let expr = parser.expression().expect("Parsing should succeed");
+        assert!(matches!(expr, Expr::SIMD(SIMDExpr::Gather { .. }, _)));
+
+        // scatter needs the vector to write
+        let source = "scatter(base, idx)";
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.tokenize_all().expect("Lexing should succeed");
+        let mut parser = Parser::new(tokens);
+        assert!(parser.expression().is_err());
+    }
+
+    #[test]
+    fn test_native_simd_type_parsing() {
+        let source = "load_vector(src, simd<f32>)";
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.tokenize_all().expect("Lexing should succeed");
+        let mut parser = Parser::new(tokens);
+
+        let expr = parser.expression().expect("Parsing should succeed");
+
+        match expr {
+            Expr::SIMD(SIMDExpr::NativeVectorLoad { element_type, .. }, _) => {
+                assert_eq!(element_type, "f32");
+            }
+            _ => panic!("Expected native vector load, got {:?}", expr),
+        }
+
+        let source = "i + lanes<u8>()";
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.tokenize_all().expect("Lexing should succeed");
+        let mut parser = Parser::new(tokens);
+        let expr = parser.expression().expect("Parsing should succeed");
+        match expr {
+            Expr::Binary(_, BinaryOp::Add, right, _) => assert!(matches!(
+                *right,
+                Expr::SIMD(SIMDExpr::Lanes { ref element_type, .. }, _) if element_type == "u8"
+            )),
+            _ => panic!("Expected addition, got {:?}", expr),
+        }
+
+        let result = parse_statement("func f(v: simd<f32>) -> simd<f32> { return v; }").unwrap();
+        match result {
+            Stmt::FunctionDeclaration {
+                params,
+                return_type,
+                ..
+            } => {
+                assert_eq!(params[0].type_annotation.name, "simd<f32>");
+                assert_eq!(return_type.unwrap().name, "simd<f32>");
+            }
+            _ => panic!("Expected function declaration"),
+        }
+    }
+
+    #[test]
+    fn test_regular_array_literal() {
+        let source = "[1, 2, 3]";
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.tokenize_all().expect("Lexing should succeed");
+        let mut parser = Parser::new(tokens);
+
+        let expr = parser.expression().expect("Parsing should succeed");
+
+        match expr {
+            Expr::Literal(
+                Literal::Vector {
+                    elements,
+                    vector_type,
+                },
+                _,
+            ) => {
+                assert_eq!(elements.len(), 3);
+                assert_eq!(vector_type, None);
+            }
+            _ => panic!("Expected Vector literal, got {:?}", expr),
+        }
+    }
+
+    #[test]
+    fn test_empty_array() {
+        let source = "[]";
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.tokenize_all().expect("Lexing should succeed");
+        let mut parser = Parser::new(tokens);
+
+        let expr = parser.expression().expect("Parsing should succeed");
+
+        match expr {
+            Expr::Literal(
+                Literal::Vector {
+                    elements,
+                    vector_type,
+                },
+                _,
+            ) => {
+                assert_eq!(elements.len(), 0);
+                assert_eq!(vector_type, None);
+            }
+            _ => panic!("Expected empty Vector literal, got {:?}", expr),
+        }
+    }
+
+    #[test]
+    fn test_parse_import_with_alias() {
+        let result = parse_statement("import math::vector as vec;").unwrap();
+
+        assert_eq!(
+            result,
+            Stmt::ImportDeclaration {
+                module_path: "math::vector".to_string(),
+                items: Vec::new(),
+                alias: Some("vec".to_string()),
+                span: Span::default(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_use_declarations() {
+        let single = parse_statement("use math::vector::dot as d;").unwrap();
+        assert_eq!(
+            single,
+            Stmt::ImportDeclaration {
+                module_path: "math::vector".to_string(),
+                items: vec![ImportItem {
+                    name: "dot".to_string(),
+                    alias: Some("d".to_string()),
+                }],
+                alias: None,
+                span: Span::default(),
+            }
+        );
+
+        let group = parse_statement("use utils::{min, max,};").unwrap();
+        if let Stmt::ImportDeclaration {
+            module_path, items, ..
+        } = group
+        {
+            assert_eq!(module_path, "utils");
+            let names: Vec<&str> = items.iter().map(|item| item.local_name()).collect();
+            assert_eq!(names, vec!["min", "max"]);
+        } else {
+            panic!("Expected import declaration, got {:?}", group);
+        }
+
+        assert!(parse_statement("use utils;").is_err());
+    }
+
+    #[test]
+    fn test_parse_from_import() {
+        let result = parse_statement("from geometry import area, perimeter as perim;").unwrap();
+
+        if let Stmt::ImportDeclaration {
+            module_path,
+            items,
+            alias,
+            ..
+        } = result
+        {
+            assert_eq!(module_path, "geometry");
+            assert!(alias.is_none());
+            assert_eq!(items.len(), 2);
+            assert_eq!(items[1].name, "perimeter");
+            assert_eq!(items[1].local_name(), "perim");
+        } else {
+            panic!("Expected import declaration, got {:?}", result);
+        }
+    }
+
+    #[test]
+    fn test_parse_break_and_continue() {
+        assert_eq!(
+            parse_statement("break;").unwrap(),
+            Stmt::Break(None, Span::default())
+        );
+        assert_eq!(
+            parse_statement("continue 'rows;").unwrap(),
+            Stmt::Continue(Some("rows".to_string()), Span::default())
+        );
+        assert!(parse_statement("break").is_err());
+    }
+
+    #[test]
+    fn test_parse_labeled_loops() {
+        let source = "'outer: while (i < 10) { for j in data { if (j == 3) { break 'outer; } } }";
+        let result = parse_statement(source).unwrap();
+
+        if let Stmt::Labeled { label, body, .. } = result {
+            assert_eq!(label, "outer");
+            assert!(matches!(*body, Stmt::While { .. }));
+        } else {
+            panic!("Expected labeled loop, got {:?}", result);
+        }
+
+        assert!(parse_statement("'outer: x = 1;").is_err());
+    }
+
+    #[test]
+    fn test_parse_generic_function() {
+        let result = parse_statement("func sum<T>(a: T, b: T) -> T { return a + b; }").unwrap();
+
+        if let Stmt::FunctionDeclaration {
+            name,
+            type_params,
+            params,
+            return_type,
+            ..
+        } = result
+        {
+            assert_eq!(name, "sum");
+            assert_eq!(type_params, vec!["T".to_string()]);
+            assert_eq!(params[1].type_annotation.name, "T");
+            assert_eq!(return_type.unwrap().name, "T");
+        } else {
+            panic!("Expected function declaration, got {:?}", result);
+        }
+
+        assert!(parse_statement("func bad<T, T>(a: T) -> T { return a; }").is_err());
+    }
+
+    #[test]
+    fn test_parse_generic_struct() {
+        let result =
+            parse_statement("struct Pair<A, B> { first: A, second: Pair<B, i32> }").unwrap();
+
+        if let Stmt::StructDeclaration {
+            name,
+            type_params,
+            fields,
+            ..
+        } = result
+        {
+            assert_eq!(name, "Pair");
+            assert_eq!(type_params, vec!["A".to_string(), "B".to_string()]);
+            assert_eq!(fields[1].type_annotation.name, "Pair<B, i32>");
+        } else {
+            panic!("Expected struct declaration, got {:?}", result);
+        }
+    }
+
+    #[test]
+    fn test_parse_trait_declaration() {
+        let result = parse_statement(
+            "trait Shape { func area(self) -> f32; func scale(self, k: f32) -> Self; }",
+        )
+        .unwrap();
+
+        if let Stmt::TraitDeclaration { name, methods, .. } = result {
+            assert_eq!(name, "Shape");
+            assert_eq!(methods.len(), 2);
+            assert_eq!(methods[0].name, "area");
+            assert_eq!(methods[0].params[0].name, "self");
+            assert_eq!(methods[0].params[0].type_annotation.name, "Self");
+            assert_eq!(methods[1].params[1].type_annotation.name, "f32");
+            assert_eq!(methods[1].return_type.as_ref().unwrap().name, "Self");
+        } else {
+            panic!("Expected trait declaration, got {:?}", result);
+        }
+    }
+
+    #[test]
+    fn test_parse_trait_impl() {
+        let result = parse_statement(
+            "impl Shape for Circle { func area(self) -> f32 { return self.r * self.r; } }",
+        )
+        .unwrap();
+
+        if let Stmt::ImplBlock {
+            trait_name,
+            type_name,
+            methods,
+            ..
+        } = result
+        {
+            assert_eq!(trait_name.as_deref(), Some("Shape"));
+            assert_eq!(type_name, "Circle");
+            assert_eq!(methods.len(), 1);
+            assert!(
+                matches!(&methods[0], Stmt::FunctionDeclaration { name, .. } if name == "area")
+            );
+        } else {
+            panic!("Expected impl block, got {:?}", result);
+        }
+
+        assert!(parse_statement("impl Shape for { }").is_err());
+        assert!(parse_statement("trait Shape { func area(self) -> f32 { return 1.0; } }").is_err());
+    }
+
+    #[test]
+    fn test_parse_inherent_impl() {
+        let result = parse_statement(
+            "impl Point { func new(x: f32, y: f32) -> Point { return Point { x: x, y: y }; } func length(self) -> f32 { return self.x; } }",
+        )
+        .unwrap();
+
+        if let Stmt::ImplBlock {
+            trait_name,
+            type_name,
+            methods,
+            ..
+        } = result
+        {
+            assert_eq!(trait_name, None);
+            assert_eq!(type_name, "Point");
+            assert_eq!(methods.len(), 2);
+            if let Stmt::FunctionDeclaration { params, .. } = &methods[1] {
+                assert_eq!(params[0].name, "self");
+            } else {
+                panic!("Expected method declaration, got {:?}", methods[1]);
+            }
+        } else {
+            panic!("Expected impl block, got {:?}", result);
+        }
+    }
+
+    #[test]
+    fn test_parse_const_declaration() {
+        let result = parse_statement("const LANES: i32 = 4 * 2;").unwrap();
+
+        if let Stmt::ConstDeclaration {
+            name,
+            type_annotation,
+            value,
+            ..
+        } = result
+        {
+            assert_eq!(name, "LANES");
+            assert_eq!(type_annotation.name, "i32");
+            assert!(matches!(value, Expr::Binary(_, BinaryOp::Multiply, _, _)));
+        } else {
+            panic!("Expected constant declaration, got {:?}", result);
+        }
+
+        assert!(parse_statement("const N = 4;").is_err());
+        assert!(parse_statement("const N: i32;").is_err());
+    }
+
+    #[test]
+    fn test_parse_attributes_and_array_repeat() {
+        let result = parse_statement(
+            "@optimize(unroll: UNROLL, simd: auto) func f() -> () { let v = [0.0; LANES]f32x4; return; }",
+        )
+        .unwrap();
+
+        if let Stmt::FunctionDeclaration {
+            attributes, body, ..
+        } = result
+        {
+            assert_eq!(attributes.len(), 1);
+            assert_eq!(attributes[0].name, "optimize");
+            assert_eq!(
+                attributes[0].params[0].value,
+                AttributeValue::Identifier("UNROLL".to_string())
+            );
+
+            if let Stmt::Block(stmts, _) = *body {
+                if let Stmt::VarDeclaration {
+                    initializer:
+                        Some(Expr::ArrayRepeat {
+                            count, vector_type, ..
+                        }),
+                    ..
+                } = &stmts[0]
+                {
+                    assert_eq!(
+                        **count,
+                        Expr::Variable("LANES".to_string(), Span::default())
+                    );
+                    assert_eq!(*vector_type, Some(SIMDVectorType::F32x4));
+                } else {
+                    panic!("Expected array repeat, got {:?}", stmts[0]);
+                }
+            }
+        } else {
+            panic!("Expected function declaration, got {:?}", result);
+        }
+    }
+
+    #[test]
+    fn test_parse_positional_attribute_params() {
+        let result =
+            parse_statement("@target_clones(avx512, avx2, sse4) func f() -> () { return; }")
+                .unwrap();
+        if let Stmt::FunctionDeclaration { attributes, .. } = &result {
+            assert_eq!(attributes[0].name, "target_clones");
+            assert!(attributes[0]
+                .params
+                .iter()
+                .all(|param| param.key.is_empty()));
+            assert_eq!(
+                crate::ast::target_clones(attributes),
+                Some(vec![
+                    "avx512".to_string(),
+                    "avx2".to_string(),
+                    "sse4".to_string()
+                ])
+            );
+            assert_eq!(
+                attributes[0].to_string(),
+                "@target_clones(avx512, avx2, sse4)"
+            );
+        } else {
+            panic!("Expected function declaration, got {:?}", result);
+        }
+    }
+
+    #[test]
+    fn test_parse_comptime() {
+        let result =
+            parse_statement("comptime func square(x: i32) -> i32 { return x * x; }").unwrap();
+        if let Stmt::FunctionDeclaration {
+            name, attributes, ..
+        } = &result
+        {
+            assert_eq!(name, "square");
+            assert!(crate::ast::is_comptime_function(attributes));
+        } else {
+            panic!("Expected function declaration, got {:?}", result);
+        }
+
+        let result = parse_statement("let table = comptime { let n = 4; square(n); };").unwrap();
+        if let Stmt::VarDeclaration {
+            initializer: Some(Expr::Comptime(statements, _)),
+            ..
+        } = &result
+        {
+            assert_eq!(statements.len(), 2);
+        } else {
+            panic!("Expected comptime block initializer, got {:?}", result);
+        }
+
+        assert!(parse_statement("let x = comptime 4;").is_err());
+    }
+
+    #[test]
+    fn test_nodes_carry_source_spans() {
+        let result = parse_statement("let total = a +\n    22;").unwrap();
+
+        let span = result.span();
+        assert_eq!((span.start.line, span.start.column), (1, 1));
+        assert_eq!((span.end.line, span.end.column), (2, 8));
+
+        if let Stmt::VarDeclaration {
+            initializer: Some(Expr::Binary(left, _, right, span)),
+            ..
+        } = result
+        {
+            assert_eq!((span.start.line, span.start.column), (1, 13));
+            assert_eq!((span.end.line, span.end.column), (2, 7));
+            assert_eq!(left.span().end.column, 14);
+            assert_eq!((right.span().start.line, right.span().start.column), (2, 5));
+        } else {
+            panic!("Expected binary initializer");
+        }
+    }
+
+    #[test]
+    fn test_parse_tuples() {
+        let result =
+            parse_statement("func divmod(a: i32, b: i32) -> (i32, i32) { return (a / b, a % b); }")
+                .unwrap();
+        if let Stmt::FunctionDeclaration { return_type, .. } = &result {
+            assert_eq!(return_type.as_ref().unwrap().name, "(i32, i32)");
+        } else {
+            panic!("Expected function declaration, got {:?}", result);
+        }
+
+        let result =
+            parse_statement("let (q, (_, r)): (i32, (bool, i32)) = divmod(7, 2);").unwrap();
+        if let Stmt::VarDeclaration {
+            pattern,
+            type_annotation,
+            ..
+        } = &result
+        {
+            assert_eq!(pattern.bound_names(), vec!["q", "r"]);
+            assert_eq!(pattern.to_string(), "(q, (_, r))");
+            assert_eq!(type_annotation.as_ref().unwrap().name, "(i32, (bool, i32))");
+        } else {
+            panic!("Expected variable declaration, got {:?}", result);
+        }
+
+        let result = parse_statement("let single = (1,);").unwrap();
+        if let Stmt::VarDeclaration {
+            initializer: Some(Expr::Tuple { elements, .. }),
+            ..
+        } = &result
+        {
+            assert_eq!(elements.len(), 1);
+        } else {
+            panic!("Expected tuple initializer, got {:?}", result);
+        }
+
+        // `(1)` is still a parenthesized expression
+        let result = parse_statement("let one = (1);").unwrap();
+        assert!(matches!(
+            result,
+            Stmt::VarDeclaration {
+                initializer: Some(Expr::Grouping(..)),
+                ..
+            }
+        ));
+
+        let result = parse_statement("let x = nested.0.1 + pair.1;").unwrap();
+        if let Stmt::VarDeclaration {
+            initializer: Some(Expr::Binary(left, _, right, _)),
+            ..
+        } = &result
+        {
+            assert_eq!(left.to_string(), "nested.0.1");
+            assert_eq!(right.to_string(), "pair.1");
+        } else {
+            panic!("Expected binary initializer, got {:?}", result);
+        }
+    }
+
+    #[test]
+    fn test_parse_array_types() {
+        let result = parse_statement(
+            "func sum(values: [f32; 64], grid: [[i32; 4]; ROWS]) -> f32 { return 0.0; }",
+        )
+        .unwrap();
+        if let Stmt::FunctionDeclaration { params, .. } = &result {
+            assert_eq!(params[0].type_annotation.name, "[f32; 64]");
+            assert_eq!(params[1].type_annotation.name, "[[i32; 4]; ROWS]");
+            assert_eq!(
+                crate::ast::split_array_type_name(&params[1].type_annotation.name),
+                Some(("[i32; 4]".to_string(), "ROWS".to_string()))
+            );
+        } else {
+            panic!("Expected function declaration, got {:?}", result);
+        }
+
+        assert!(parse_statement("let values: [f32; -1] = [1.0];").is_err());
+        assert!(parse_statement("let values: [f32] = [1.0];").is_err());
+    }
+
+    #[test]
+    fn test_parse_extern_block() {
+        let result = parse_statement(
+            r#"extern "C" { func png_read(path: string, out: *mut u8) -> i32; func abort(); }"#,
+        )
+        .unwrap();
+        if let Stmt::ExternBlock { abi, functions, .. } = &result {
+            assert_eq!(abi, "C");
+            assert_eq!(functions.len(), 2);
+            assert_eq!(
+                functions[0].to_string(),
+                "func png_read(path: string, out: *mut u8) -> i32;"
+            );
+            assert!(functions[1].params.is_empty());
+            assert!(functions[1].return_type.is_none());
+        } else {
+            panic!("Expected extern block, got {:?}", result);
+        }
+
+        assert!(parse_statement(r#"extern "Rust" { func f(); }"#).is_err());
+        assert!(parse_statement(r#"extern "C" { func f() { } }"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_pointers_and_unsafe() {
+        let result = parse_statement("func fill(dst: *mut f32, src: *f32) { }").unwrap();
+        if let Stmt::FunctionDeclaration { params, .. } = &result {
+            assert_eq!(params[0].type_annotation.name, "*mut f32");
+            assert_eq!(params[1].type_annotation.name, "*f32");
+            assert_eq!(
+                crate::ast::split_pointer_type_name(&params[0].type_annotation.name),
+                Some(("f32".to_string(), true))
+            );
+        } else {
+            panic!("Expected function declaration, got {:?}", result);
+        }
+
+        let result = parse_statement("unsafe { *dst = *src.offset(1); }").unwrap();
+        if let Stmt::Expression(Expr::Unsafe(statements, _), _) = &result {
+            assert_eq!(statements.len(), 1);
+            assert!(matches!(
+                &statements[0],
+                Stmt::Expression(Expr::Binary(target, BinaryOp::Assign, value, _), _)
+                    if matches!(**target, Expr::Unary(UnaryOp::Deref, ..))
+                        && matches!(**value, Expr::Unary(UnaryOp::Deref, ..))
+            ));
+        } else {
+            panic!("Expected unsafe block, got {:?}", result);
+        }
+
+        let result = parse_statement("let x = unsafe { *src; };").unwrap();
+        assert!(matches!(
+            result,
+            Stmt::VarDeclaration {
+                initializer: Some(Expr::Unsafe(..)),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_parse_closures() {
+        let result = parse_statement("let double = |x: f32| -> f32 { x * 2.0 };").unwrap();
+        if let Stmt::VarDeclaration {
+            initializer:
+                Some(Expr::Closure {
+                    params,
+                    return_type,
+                    body,
+                    ..
+                }),
+            ..
+        } = &result
+        {
+            assert_eq!(params.len(), 1);
+            assert_eq!(params[0].to_string(), "x: f32");
+            assert_eq!(return_type.as_ref().unwrap().name, "f32");
+            assert!(matches!(**body, Expr::Block(ref stmts, _) if stmts.len() == 1));
+        } else {
+            panic!("Expected closure initializer, got {:?}", result);
+        }
+
+        let result =
+            parse_statement("let total = values.reduce(0, |acc: i32, x: i32| acc + x);").unwrap();
+        if let Stmt::VarDeclaration {
+            initializer: Some(Expr::Call(_, args, _)),
+            ..
+        } = &result
+        {
+            assert_eq!(args[1].to_string(), "|acc: i32, x: i32| (acc + x)");
+        } else {
+            panic!("Expected call initializer, got {:?}", result);
+        }
+
+        // `||` starts a closure without parameters
+        let result = parse_statement("let tick = || { count + 1 };").unwrap();
+        assert!(matches!(
+            result,
+            Stmt::VarDeclaration {
+                initializer: Some(Expr::Closure { ref params, .. }),
+                ..
+            } if params.is_empty()
+        ));
+
+        let result = parse_statement(
+            "func apply(f: func(i32, i32) -> i32, x: i32) -> func(i32) -> bool { return f; }",
+        )
+        .unwrap();
+        if let Stmt::FunctionDeclaration {
+            params, return_type, ..
+        } = &result
+        {
+            assert_eq!(params[0].type_annotation.name, "func(i32, i32) -> i32");
+            assert_eq!(return_type.as_ref().unwrap().name, "func(i32) -> bool");
+        } else {
+            panic!("Expected function declaration, got {:?}", result);
+        }
+
+        assert!(parse_statement("let f = |x| x;").is_err());
+    }
+
+    #[test]
+    fn test_parse_rich_patterns() {
+        let result = parse_statement(
+            "let grade = match point { Point { x: 0, y } => y, Point { x, .. } if x > 10 => x, _ => 0 };",
+        )
+        .unwrap();
+        if let Stmt::VarDeclaration {
+            initializer: Some(Expr::Match { arms, .. }),
+            ..
+        } = &result
+        {
+            assert_eq!(arms.len(), 3);
+            assert_eq!(arms[0].pattern.to_string(), "Point { x: 0, y }");
+            assert!(arms[0].guard.is_none());
+            assert_eq!(arms[1].pattern.to_string(), "Point { x, .. }");
+            assert_eq!(arms[1].guard.as_ref().unwrap().to_string(), "(x > 10)");
+            assert_eq!(arms[1].pattern.bound_names(), vec!["x"]);
+        } else {
+            panic!("Expected match initializer, got {:?}", result);
+        }
+
+        let result = parse_statement(
+            "let size = match n { -9..=-1 => 0, 1..=9 => 1, 10..100 => 2, 0 | 100 => 3, _ => 4 };",
+        )
+        .unwrap();
+        if let Stmt::VarDeclaration {
+            initializer: Some(Expr::Match { arms, .. }),
+            ..
+        } = &result
+        {
+            assert_eq!(
+                arms[0].pattern,
+                Pattern::Range {
+                    start: Literal::Integer(-9),
+                    end: Literal::Integer(-1),
+                    inclusive: true,
+                }
+            );
+            assert_eq!(arms[1].pattern.to_string(), "1..=9");
+            assert_eq!(arms[2].pattern.to_string(), "10..100");
+            assert_eq!(arms[3].pattern.to_string(), "0 | 100");
+        } else {
+            panic!("Expected match initializer, got {:?}", result);
+        }
+
+        let result = parse_statement(
+            "let area = match shape { Shape::Circle(r) | Shape::Ring(r) => r, Shape::Empty => 0 };",
+        )
+        .unwrap();
+        if let Stmt::VarDeclaration {
+            initializer: Some(Expr::Match { arms, .. }),
+            ..
+        } = &result
+        {
+            assert!(matches!(&arms[0].pattern, Pattern::Or(alternatives) if alternatives.len() == 2));
+            assert_eq!(arms[0].pattern.bound_names(), vec!["r"]);
+        } else {
+            panic!("Expected match initializer, got {:?}", result);
+        }
+
+        assert!(parse_statement("let n = match x { 1..= => 0, _ => 1 };").is_err());
     }
 }
@@ -134,6 +134,7 @@ impl ResourceManager {
                         self.usage.compilation_time_elapsed, self.limits.max_compilation_time
                     ),
                     position: None,
+                    end: None,
                 });
             }
         }
@@ -244,6 +245,7 @@ impl ResourceManager {
                              - Increasing memory limits"
                     .to_string(),
                 position: None,
+                end: None,
             }),
         }
     }
@@ -0,0 +1,641 @@
+//! Exhaustiveness and reachability checking for `match` expressions.
+//!
+//! The arms of a match are lowered into a pattern matrix, one row per arm,
+//! and checked with the usefulness algorithm from Maranget's "Warnings for
+//! pattern matching": a match is exhaustive when no value slips past every
+//! row, and an arm is unreachable when its row is not useful after the rows
+//! above it.
+
+use super::EaType;
+use crate::ast::{Literal, MatchArm, Pattern};
+use std::collections::HashMap;
+
+/// What a pattern is built from: an enum variant, a boolean, a tuple or
+/// struct, or a literal or range taken from a domain too large to list.
+#[derive(Debug, Clone, PartialEq)]
+enum Constructor {
+    Variant(String),
+    Bool(bool),
+    Tuple,
+    Struct(String),
+    /// Integers from the first bound to the second, both included; a
+    /// literal is the range of its one value
+    IntRange(i64, i64),
+    /// Float and string literals and float ranges, compared by their source text
+    Literal(String),
+}
+
+impl Constructor {
+    /// Whether every value `other` matches is also matched by `self`.
+    fn covers(&self, other: &Constructor) -> bool {
+        match (self, other) {
+            (Constructor::IntRange(start, end), Constructor::IntRange(other_start, other_end)) => {
+                start <= other_start && other_end <= end
+            }
+            _ => self == other,
+        }
+    }
+}
+
+/// A pattern reduced to what matters for coverage. Variable bindings and
+/// `_` both match anything, so both become `Wildcard`.
+#[derive(Debug, Clone)]
+enum Pat {
+    Wildcard,
+    Constructor(Constructor, Vec<Pat>),
+    Or(Vec<Pat>),
+}
+
+type Row = Vec<Pat>;
+
+/// How well the arms of a match cover the matched type.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MatchCoverage {
+    /// Patterns, such as `Shape::Square(_)`, for the values no arm matches
+    pub missing: Vec<String>,
+    /// Indices of the arms that earlier arms leave nothing to match
+    pub unreachable: Vec<usize>,
+}
+
+impl MatchCoverage {
+    pub fn is_exhaustive(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+/// Checks the `arms` of a match on a value of `value_type`. The patterns
+/// must already have been type checked against it; `struct_fields` looks up
+/// the field types of the structs they destructure.
+///
+/// Guarded arms may fail to match, so they never count towards coverage.
+pub fn check_match(
+    value_type: &EaType,
+    arms: &[MatchArm],
+    struct_fields: &dyn Fn(&str) -> Option<HashMap<String, EaType>>,
+) -> MatchCoverage {
+    let checker = Checker { struct_fields };
+    let types = [value_type.clone()];
+    let mut rows: Vec<Row> = Vec::new();
+    let mut coverage = MatchCoverage::default();
+
+    for (index, arm) in arms.iter().enumerate() {
+        let row = vec![checker.lower(&arm.pattern, value_type)];
+        if !checker.is_useful(&rows, &row, &types) {
+            coverage.unreachable.push(index);
+        }
+        if arm.guard.is_none() {
+            rows.push(row);
+        }
+    }
+
+    coverage.missing = checker
+        .missing_values(&rows, &types)
+        .into_iter()
+        .map(|mut witness| witness.remove(0))
+        .collect();
+    coverage
+}
+
+/// The type information the algorithm needs beyond `EaType` itself.
+struct Checker<'a> {
+    struct_fields: &'a dyn Fn(&str) -> Option<HashMap<String, EaType>>,
+}
+
+impl Checker<'_> {
+    fn lower(&self, pattern: &Pattern, ty: &EaType) -> Pat {
+        match pattern {
+            Pattern::Wildcard | Pattern::Variable(_) => Pat::Wildcard,
+            Pattern::Literal(literal) => Pat::Constructor(literal_constructor(literal), Vec::new()),
+            Pattern::Range {
+                start: Literal::Integer(start),
+                end: Literal::Integer(end),
+                inclusive,
+            } => {
+                let last = if *inclusive { *end } else { end - 1 };
+                Pat::Constructor(Constructor::IntRange(*start, last), Vec::new())
+            }
+            Pattern::Range { .. } => {
+                Pat::Constructor(Constructor::Literal(pattern.to_string()), Vec::new())
+            }
+            Pattern::EnumVariant {
+                variant, patterns, ..
+            } => {
+                let constructor = Constructor::Variant(variant.clone());
+                let fields = if patterns.is_empty() {
+                    // `Shape::Circle` without sub-patterns matches any data
+                    let arity = self.constructor_fields(ty, &constructor).len();
+                    vec![Pat::Wildcard; arity]
+                } else {
+                    let field_types = self.field_types(ty, &constructor, patterns.len());
+                    self.lower_all(patterns, &field_types)
+                };
+                Pat::Constructor(constructor, fields)
+            }
+            Pattern::Tuple { patterns } => {
+                let element_types = self.field_types(ty, &Constructor::Tuple, patterns.len());
+                Pat::Constructor(Constructor::Tuple, self.lower_all(patterns, &element_types))
+            }
+            Pattern::Struct { name, fields, .. } => {
+                let struct_name = match ty {
+                    EaType::Struct(struct_name) => struct_name.clone(),
+                    _ => name.clone(),
+                };
+                // One column per declared field; fields the pattern leaves out match anything
+                let columns = self
+                    .sorted_fields(&struct_name)
+                    .into_iter()
+                    .map(|(field_name, field_type)| {
+                        match fields.iter().find(|field| field.name == field_name) {
+                            Some(field) => self.lower(&field.pattern, &field_type),
+                            None => Pat::Wildcard,
+                        }
+                    })
+                    .collect();
+                Pat::Constructor(Constructor::Struct(struct_name), columns)
+            }
+            Pattern::Or(alternatives) => Pat::Or(
+                alternatives
+                    .iter()
+                    .map(|alternative| self.lower(alternative, ty))
+                    .collect(),
+            ),
+        }
+    }
+
+    fn lower_all(&self, patterns: &[Pattern], types: &[EaType]) -> Vec<Pat> {
+        patterns
+            .iter()
+            .zip(types)
+            .map(|(pattern, ty)| self.lower(pattern, ty))
+            .collect()
+    }
+
+    /// The fields of a struct ordered by name, so that every pattern on it
+    /// lays its columns out the same way.
+    fn sorted_fields(&self, struct_name: &str) -> Vec<(String, EaType)> {
+        let mut fields: Vec<(String, EaType)> = (self.struct_fields)(struct_name)
+            .map(|fields| fields.into_iter().collect())
+            .unwrap_or_default();
+        fields.sort_by(|a, b| a.0.cmp(&b.0));
+        fields
+    }
+
+    /// Every constructor of `ty` with the types of its fields, or `None` when
+    /// its values cannot be listed (integers, floats, strings, ...).
+    fn all_constructors(&self, ty: &EaType) -> Option<Vec<(Constructor, Vec<EaType>)>> {
+        match ty {
+            EaType::Bool => Some(vec![
+                (Constructor::Bool(true), Vec::new()),
+                (Constructor::Bool(false), Vec::new()),
+            ]),
+            EaType::Tuple(element_types) => Some(vec![(Constructor::Tuple, element_types.clone())]),
+            EaType::Struct(name) => {
+                let field_types = self.sorted_fields(name).into_iter().map(|(_, ty)| ty);
+                Some(vec![(
+                    Constructor::Struct(name.clone()),
+                    field_types.collect(),
+                )])
+            }
+            EaType::Enum { variants, .. } => {
+                let mut names: Vec<&String> = variants.keys().collect();
+                names.sort();
+                Some(
+                    names
+                        .into_iter()
+                        .map(|name| (Constructor::Variant(name.clone()), variants[name].clone()))
+                        .collect(),
+                )
+            }
+            _ => None,
+        }
+    }
+
+    /// The field types `constructor` takes as a value of `ty`.
+    fn constructor_fields(&self, ty: &EaType, constructor: &Constructor) -> Vec<EaType> {
+        match (ty, constructor) {
+            (EaType::Enum { variants, .. }, Constructor::Variant(name)) => {
+                variants.get(name).cloned().unwrap_or_default()
+            }
+            (EaType::Tuple(element_types), Constructor::Tuple) => element_types.clone(),
+            (EaType::Struct(name), Constructor::Struct(_)) => self
+                .sorted_fields(name)
+                .into_iter()
+                .map(|(_, ty)| ty)
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Like `constructor_fields`, but always `arity` long so that rows keep
+    /// their shape even when the type is unknown.
+    fn field_types(&self, ty: &EaType, constructor: &Constructor, arity: usize) -> Vec<EaType> {
+        let mut types = self.constructor_fields(ty, constructor);
+        types.resize(arity, EaType::Error);
+        types
+    }
+
+    /// The constructors of `ty` when the first column of `rows` mentions every
+    /// one of them, so that a wildcard there can be split into each in turn.
+    fn complete_signature(
+        &self,
+        rows: &[Row],
+        ty: &EaType,
+    ) -> Option<Vec<(Constructor, Vec<EaType>)>> {
+        let heads = head_constructors(rows);
+        self.all_constructors(ty).filter(|all| {
+            !heads.is_empty()
+                && all
+                    .iter()
+                    .all(|(constructor, _)| heads.contains(&constructor))
+        })
+    }
+
+    /// Whether some value matched by `row` is matched by none of `rows`.
+    fn is_useful(&self, rows: &[Row], row: &[Pat], types: &[EaType]) -> bool {
+        let (first, ty) = match (row.first(), types.first()) {
+            (Some(first), Some(ty)) => (first, ty),
+            _ => return rows.is_empty(),
+        };
+        let rows = expand_or_heads(rows);
+
+        match first {
+            Pat::Or(alternatives) => alternatives.iter().any(|alternative| {
+                let row: Row = std::iter::once(alternative.clone())
+                    .chain(row[1..].iter().cloned())
+                    .collect();
+                self.is_useful(&rows, &row, types)
+            }),
+            Pat::Constructor(constructor, fields) => {
+                let specialized = specialize(&rows, constructor, fields.len());
+                let row: Row = fields.iter().chain(&row[1..]).cloned().collect();
+                let types: Vec<EaType> = self
+                    .field_types(ty, constructor, fields.len())
+                    .into_iter()
+                    .chain(types[1..].iter().cloned())
+                    .collect();
+                self.is_useful(&specialized, &row, &types)
+            }
+            Pat::Wildcard => match self.complete_signature(&rows, ty) {
+                Some(constructors) => constructors.iter().any(|(constructor, fields)| {
+                    let specialized = specialize(&rows, constructor, fields.len());
+                    let row: Row = std::iter::repeat_n(Pat::Wildcard, fields.len())
+                        .chain(row[1..].iter().cloned())
+                        .collect();
+                    let types: Vec<EaType> = fields.iter().chain(&types[1..]).cloned().collect();
+                    self.is_useful(&specialized, &row, &types)
+                }),
+                None => self.is_useful(&default_rows(&rows), &row[1..], &types[1..]),
+            },
+        }
+    }
+
+    /// Values that none of `rows` match, rendered as one pattern per column.
+    fn missing_values(&self, rows: &[Row], types: &[EaType]) -> Vec<Vec<String>> {
+        let ty = match types.first() {
+            Some(ty) => ty,
+            None if rows.is_empty() => return vec![Vec::new()],
+            None => return Vec::new(),
+        };
+        let rows = expand_or_heads(rows);
+
+        if let Some(constructors) = self.complete_signature(&rows, ty) {
+            let mut witnesses = Vec::new();
+            for (constructor, fields) in &constructors {
+                let specialized = specialize(&rows, constructor, fields.len());
+                let types: Vec<EaType> = fields.iter().chain(&types[1..]).cloned().collect();
+                for mut witness in self.missing_values(&specialized, &types) {
+                    let field_patterns: Vec<String> = witness.drain(..fields.len()).collect();
+                    witness.insert(0, self.render(ty, constructor, &field_patterns));
+                    witnesses.push(witness);
+                }
+            }
+            return witnesses;
+        }
+
+        let rest = self.missing_values(&default_rows(&rows), &types[1..]);
+        if rest.is_empty() {
+            return rest;
+        }
+
+        // Name the constructors no arm mentions; when the arms mention none, or
+        // the type has too many values to list, `_` stands for all of them
+        let heads = head_constructors(&rows);
+        let firsts: Vec<String> = match self.all_constructors(ty) {
+            Some(all) if !heads.is_empty() => all
+                .iter()
+                .filter(|(constructor, _)| !heads.contains(&constructor))
+                .map(|(constructor, fields)| {
+                    self.render(ty, constructor, &vec!["_".to_string(); fields.len()])
+                })
+                .collect(),
+            _ => vec!["_".to_string()],
+        };
+
+        rest.iter()
+            .flat_map(|witness| {
+                firsts.iter().map(move |first| {
+                    std::iter::once(first.clone())
+                        .chain(witness.iter().cloned())
+                        .collect()
+                })
+            })
+            .collect()
+    }
+
+    fn render(&self, ty: &EaType, constructor: &Constructor, fields: &[String]) -> String {
+        match constructor {
+            Constructor::Variant(variant) => {
+                let enum_name = match ty {
+                    EaType::Enum { name, .. } => name.as_str(),
+                    _ => "",
+                };
+                let mut rendered = format!("{}::{}", enum_name, variant);
+                if !fields.is_empty() {
+                    rendered.push_str(&format!("({})", fields.join(", ")));
+                }
+                rendered
+            }
+            Constructor::Bool(value) => value.to_string(),
+            Constructor::Tuple if fields.len() == 1 => format!("({},)", fields[0]),
+            Constructor::Tuple => format!("({})", fields.join(", ")),
+            Constructor::Struct(name) => {
+                let field_patterns: Vec<String> = self
+                    .sorted_fields(name)
+                    .into_iter()
+                    .zip(fields)
+                    .map(|((field_name, _), field)| format!("{}: {}", field_name, field))
+                    .collect();
+                format!("{} {{ {} }}", name, field_patterns.join(", "))
+            }
+            Constructor::IntRange(start, end) if start == end => start.to_string(),
+            Constructor::IntRange(start, end) => format!("{}..={}", start, end),
+            Constructor::Literal(text) => text.clone(),
+        }
+    }
+}
+
+fn literal_constructor(literal: &Literal) -> Constructor {
+    match literal {
+        Literal::Boolean(value) => Constructor::Bool(*value),
+        Literal::Integer(value) => Constructor::IntRange(*value, *value),
+        literal => Constructor::Literal(literal.to_string()),
+    }
+}
+
+/// `rows` with each or-pattern in the first column split into one row per
+/// alternative.
+fn expand_or_heads(rows: &[Row]) -> Vec<Row> {
+    let mut expanded = Vec::new();
+    for row in rows {
+        match row.first() {
+            Some(Pat::Or(alternatives)) => {
+                let alternative_rows: Vec<Row> = alternatives
+                    .iter()
+                    .map(|alternative| {
+                        std::iter::once(alternative.clone())
+                            .chain(row[1..].iter().cloned())
+                            .collect()
+                    })
+                    .collect();
+                expanded.extend(expand_or_heads(&alternative_rows));
+            }
+            _ => expanded.push(row.clone()),
+        }
+    }
+    expanded
+}
+
+/// The rows that match every value of `constructor` in the first column, with
+/// its `arity` fields spliced in place of that column. `rows` must not start
+/// with or-patterns.
+fn specialize(rows: &[Row], constructor: &Constructor, arity: usize) -> Vec<Row> {
+    rows.iter()
+        .filter_map(|row| match &row[0] {
+            Pat::Constructor(head, fields) if head.covers(constructor) => {
+                Some(fields.iter().chain(&row[1..]).cloned().collect())
+            }
+            Pat::Constructor(..) => None,
+            Pat::Wildcard => Some(
+                std::iter::repeat_n(Pat::Wildcard, arity)
+                    .chain(row[1..].iter().cloned())
+                    .collect(),
+            ),
+            Pat::Or(_) => unreachable!("or-patterns are expanded before specializing"),
+        })
+        .collect()
+}
+
+/// The rows that match whatever the first column holds, without that column.
+fn default_rows(rows: &[Row]) -> Vec<Row> {
+    rows.iter()
+        .filter(|row| matches!(row[0], Pat::Wildcard))
+        .map(|row| row[1..].to_vec())
+        .collect()
+}
+
+fn head_constructors(rows: &[Row]) -> Vec<&Constructor> {
+    rows.iter()
+        .filter_map(|row| match &row[0] {
+            Pat::Constructor(constructor, _) => Some(constructor),
+            Pat::Wildcard | Pat::Or(_) => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{Expr, FieldPattern};
+    use crate::lexer::Span;
+
+    fn shape() -> EaType {
+        let mut variants = HashMap::new();
+        variants.insert("Circle".to_string(), vec![EaType::F32]);
+        variants.insert("Square".to_string(), vec![EaType::F32]);
+        variants.insert("Empty".to_string(), Vec::new());
+        EaType::Enum {
+            name: "Shape".to_string(),
+            variants,
+        }
+    }
+
+    fn variant(name: &str, patterns: Vec<Pattern>) -> Pattern {
+        Pattern::EnumVariant {
+            enum_name: "Shape".to_string(),
+            variant: name.to_string(),
+            patterns,
+        }
+    }
+
+    fn arm(pattern: Pattern) -> MatchArm {
+        MatchArm {
+            pattern,
+            guard: None,
+            expression: Expr::Literal(Literal::Integer(0), Span::default()),
+        }
+    }
+
+    fn point_fields(name: &str) -> Option<HashMap<String, EaType>> {
+        if name != "Point" {
+            return None;
+        }
+        let mut fields = HashMap::new();
+        fields.insert("x".to_string(), EaType::I32);
+        fields.insert("visible".to_string(), EaType::Bool);
+        Some(fields)
+    }
+
+    fn check(value_type: &EaType, patterns: Vec<Pattern>) -> MatchCoverage {
+        let arms: Vec<MatchArm> = patterns.into_iter().map(arm).collect();
+        check_match(value_type, &arms, &point_fields)
+    }
+
+    #[test]
+    fn test_missing_enum_variants_are_named() {
+        let coverage = check(&shape(), vec![variant("Circle", vec![Pattern::Wildcard])]);
+        assert_eq!(coverage.missing, vec!["Shape::Empty", "Shape::Square(_)"]);
+        assert!(coverage.unreachable.is_empty());
+    }
+
+    #[test]
+    fn test_variable_pattern_covers_the_rest() {
+        let coverage = check(
+            &shape(),
+            vec![
+                variant("Circle", vec![Pattern::Variable("r".to_string())]),
+                Pattern::Variable("other".to_string()),
+            ],
+        );
+        assert!(coverage.is_exhaustive());
+    }
+
+    #[test]
+    fn test_arms_after_wildcard_are_unreachable() {
+        let coverage = check(
+            &shape(),
+            vec![
+                Pattern::Wildcard,
+                variant("Empty", Vec::new()),
+                Pattern::Variable("other".to_string()),
+            ],
+        );
+        assert!(coverage.is_exhaustive());
+        assert_eq!(coverage.unreachable, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_repeated_variant_is_unreachable() {
+        let coverage = check(
+            &shape(),
+            vec![
+                variant("Circle", vec![Pattern::Wildcard]),
+                variant("Circle", vec![Pattern::Literal(Literal::Float(1.0))]),
+                variant("Square", Vec::new()),
+                variant("Empty", Vec::new()),
+            ],
+        );
+        assert!(coverage.is_exhaustive());
+        assert_eq!(coverage.unreachable, vec![1]);
+    }
+
+    #[test]
+    fn test_nested_tuple_patterns() {
+        let pair = EaType::Tuple(vec![shape(), EaType::Bool]);
+        let tuple = |first: Pattern, second: bool| Pattern::Tuple {
+            patterns: vec![first, Pattern::Literal(Literal::Boolean(second))],
+        };
+        let coverage = check(
+            &pair,
+            vec![
+                tuple(Pattern::Wildcard, true),
+                tuple(variant("Empty", Vec::new()), false),
+                tuple(variant("Circle", Vec::new()), false),
+            ],
+        );
+        assert_eq!(coverage.missing, vec!["(Shape::Square(_), false)"]);
+        assert!(coverage.unreachable.is_empty());
+    }
+
+    #[test]
+    fn test_literals_need_a_catch_all() {
+        let one = Pattern::Literal(Literal::Integer(1));
+        let coverage = check(&EaType::I32, vec![one.clone(), one]);
+        assert_eq!(coverage.missing, vec!["_"]);
+        assert_eq!(coverage.unreachable, vec![1]);
+
+        let coverage = check(
+            &EaType::Bool,
+            vec![
+                Pattern::Literal(Literal::Boolean(true)),
+                Pattern::Literal(Literal::Boolean(false)),
+            ],
+        );
+        assert!(coverage.is_exhaustive());
+    }
+
+    #[test]
+    fn test_or_patterns_and_ranges() {
+        let coverage = check(
+            &shape(),
+            vec![
+                Pattern::Or(vec![
+                    variant("Circle", vec![Pattern::Wildcard]),
+                    variant("Square", vec![Pattern::Wildcard]),
+                ]),
+                variant("Empty", Vec::new()),
+            ],
+        );
+        assert!(coverage.is_exhaustive());
+        assert!(coverage.unreachable.is_empty());
+
+        let range = |start: i64, end: i64| Pattern::Range {
+            start: Literal::Integer(start),
+            end: Literal::Integer(end),
+            inclusive: true,
+        };
+        let coverage = check(
+            &EaType::I32,
+            vec![
+                range(1, 9),
+                Pattern::Literal(Literal::Integer(5)),
+                range(2, 3),
+                range(5, 20),
+                Pattern::Wildcard,
+            ],
+        );
+        assert!(coverage.is_exhaustive());
+        assert_eq!(coverage.unreachable, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_struct_patterns() {
+        let point = EaType::Struct("Point".to_string());
+        let point_pattern = |visible: bool| Pattern::Struct {
+            name: "Point".to_string(),
+            fields: vec![FieldPattern {
+                name: "visible".to_string(),
+                pattern: Pattern::Literal(Literal::Boolean(visible)),
+            }],
+            has_rest: true,
+        };
+        let coverage = check(&point, vec![point_pattern(true)]);
+        assert_eq!(coverage.missing, vec!["Point { visible: false, x: _ }"]);
+
+        let coverage = check(&point, vec![point_pattern(true), point_pattern(false)]);
+        assert!(coverage.is_exhaustive());
+    }
+
+    #[test]
+    fn test_guarded_arms_do_not_cover() {
+        let mut guarded = arm(variant("Empty", Vec::new()));
+        guarded.guard = Some(Expr::Literal(Literal::Boolean(true), Span::default()));
+        let arms = vec![
+            arm(variant("Circle", Vec::new())),
+            arm(variant("Square", Vec::new())),
+            guarded,
+        ];
+        let coverage = check_match(&shape(), &arms, &point_fields);
+        assert_eq!(coverage.missing, vec!["Shape::Empty"]);
+        assert!(coverage.unreachable.is_empty());
+    }
+}
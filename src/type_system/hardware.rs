@@ -38,6 +38,206 @@ pub enum SIMDFeature {
     F16C,
 }
 
+impl SIMDFeature {
+    /// The LLVM target feature name, as used in `target-features` strings.
+    pub fn llvm_name(&self) -> &'static str {
+        match self {
+            SIMDFeature::SSE => "sse",
+            SIMDFeature::SSE2 => "sse2",
+            SIMDFeature::SSE3 => "sse3",
+            SIMDFeature::SSSE3 => "ssse3",
+            SIMDFeature::SSE41 => "sse4.1",
+            SIMDFeature::SSE42 => "sse4.2",
+            SIMDFeature::AVX => "avx",
+            SIMDFeature::AVX2 => "avx2",
+            SIMDFeature::AVX512F => "avx512f",
+            SIMDFeature::AVX512DQ => "avx512dq",
+            SIMDFeature::AVX512CD => "avx512cd",
+            SIMDFeature::AVX512BW => "avx512bw",
+            SIMDFeature::AVX512VL => "avx512vl",
+            SIMDFeature::NEON => "neon",
+            SIMDFeature::ArmSve => "sve",
+            SIMDFeature::ArmSve2 => "sve2",
+            SIMDFeature::ALTIVEC => "altivec",
+            SIMDFeature::VSX => "vsx",
+            SIMDFeature::FMA => "fma",
+            SIMDFeature::F16C => "f16c",
+        }
+    }
+
+    /// The feature an LLVM feature name such as `avx2` stands for, if it is one
+    /// the detector models.
+    pub fn from_llvm_name(name: &str) -> Option<SIMDFeature> {
+        let feature = match name {
+            "sse" => SIMDFeature::SSE,
+            "sse2" => SIMDFeature::SSE2,
+            "sse3" => SIMDFeature::SSE3,
+            "ssse3" => SIMDFeature::SSSE3,
+            "sse4.1" => SIMDFeature::SSE41,
+            "sse4.2" => SIMDFeature::SSE42,
+            "avx" => SIMDFeature::AVX,
+            "avx2" => SIMDFeature::AVX2,
+            "avx512f" => SIMDFeature::AVX512F,
+            "avx512dq" => SIMDFeature::AVX512DQ,
+            "avx512cd" => SIMDFeature::AVX512CD,
+            "avx512bw" => SIMDFeature::AVX512BW,
+            "avx512vl" => SIMDFeature::AVX512VL,
+            "neon" => SIMDFeature::NEON,
+            "sve" => SIMDFeature::ArmSve,
+            "sve2" => SIMDFeature::ArmSve2,
+            "altivec" => SIMDFeature::ALTIVEC,
+            "vsx" => SIMDFeature::VSX,
+            "fma" => SIMDFeature::FMA,
+            "f16c" => SIMDFeature::F16C,
+            _ => return None,
+        };
+        Some(feature)
+    }
+
+    /// Where x86 CPUID reports the feature: `(leaf, register, bit)`, with the
+    /// registers numbered eax = 0, ebx = 1, ecx = 2 and edx = 3.
+    pub fn cpuid_bit(&self) -> Option<(u32, u32, u32)> {
+        let location = match self {
+            SIMDFeature::SSE => (1, 3, 25),
+            SIMDFeature::SSE2 => (1, 3, 26),
+            SIMDFeature::SSE3 => (1, 2, 0),
+            SIMDFeature::SSSE3 => (1, 2, 9),
+            SIMDFeature::SSE41 => (1, 2, 19),
+            SIMDFeature::SSE42 => (1, 2, 20),
+            SIMDFeature::FMA => (1, 2, 12),
+            SIMDFeature::AVX => (1, 2, 28),
+            SIMDFeature::F16C => (1, 2, 29),
+            SIMDFeature::AVX2 => (7, 1, 5),
+            SIMDFeature::AVX512F => (7, 1, 16),
+            SIMDFeature::AVX512DQ => (7, 1, 17),
+            SIMDFeature::AVX512CD => (7, 1, 28),
+            SIMDFeature::AVX512BW => (7, 1, 30),
+            SIMDFeature::AVX512VL => (7, 1, 31),
+            _ => return None,
+        };
+        Some(location)
+    }
+
+    /// The XCR0 bits the operating system must set before the feature's
+    /// registers can be used: the YMM state for AVX and the ZMM state for AVX-512.
+    pub fn xcr0_mask(&self) -> u32 {
+        match self {
+            SIMDFeature::AVX | SIMDFeature::AVX2 | SIMDFeature::FMA | SIMDFeature::F16C => 0x6,
+            SIMDFeature::AVX512F
+            | SIMDFeature::AVX512DQ
+            | SIMDFeature::AVX512CD
+            | SIMDFeature::AVX512BW
+            | SIMDFeature::AVX512VL => 0xe6,
+            _ => 0,
+        }
+    }
+}
+
+/// The feature levels `@target_clones` accepts, from the newest CPUs to the oldest.
+/// The dispatcher picks the first level in this order that the CPU supports.
+pub const TARGET_CLONE_LEVELS: [&str; 5] = ["avx512", "avx2", "avx", "sse4", "sse3"];
+
+/// The features a clone of `@target_clones(avx512, avx2, sse4)` is compiled for,
+/// or `None` if `name` is not a known x86 feature level.
+pub fn target_clone_features(name: &str) -> Option<Vec<SIMDFeature>> {
+    let features = match name {
+        "sse3" => vec![SIMDFeature::SSE3],
+        "sse4" => vec![SIMDFeature::SSE41, SIMDFeature::SSE42],
+        "avx" => vec![SIMDFeature::AVX],
+        "avx2" => vec![SIMDFeature::AVX2, SIMDFeature::FMA],
+        "avx512" => vec![
+            SIMDFeature::AVX512F,
+            SIMDFeature::AVX512DQ,
+            SIMDFeature::AVX512BW,
+            SIMDFeature::AVX512VL,
+        ],
+        _ => return None,
+    };
+    Some(features)
+}
+
+/// Formats features as an LLVM `target-features` string, e.g. `+avx2,+fma`.
+pub fn llvm_target_features(features: &[SIMDFeature]) -> String {
+    features
+        .iter()
+        .map(|feature| format!("+{}", feature.llvm_name()))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// The machine code is generated for, as selected with `--target`,
+/// `--target-cpu` and `--target-features`. The default is the host.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompileTarget {
+    /// LLVM target triple, e.g. `aarch64-unknown-linux-gnu`; `None` for the host
+    pub triple: Option<String>,
+    /// LLVM CPU name, e.g. `skylake` or `cortex-a72`; `None` for the architecture's baseline
+    pub cpu: Option<String>,
+    /// LLVM features enabled or disabled on top of the baseline, e.g. `+avx512f,-fma`
+    pub features: Option<String>,
+}
+
+impl CompileTarget {
+    /// The architecture of the target as `HardwareDetector` names it, e.g. `aarch64`.
+    pub fn arch(&self) -> String {
+        let triple = match &self.triple {
+            Some(triple) => triple,
+            None => return std::env::consts::ARCH.to_string(),
+        };
+        match triple.split('-').next().unwrap_or_default() {
+            "arm64" => "aarch64".to_string(),
+            "amd64" => "x86_64".to_string(),
+            "i386" | "i586" | "i686" => "x86".to_string(),
+            arch => arch.to_string(),
+        }
+    }
+
+    /// The SIMD features code for this target may use, which decide the vector
+    /// types that are legal and the width of `simd<T>`. A given triple only
+    /// assumes the features every CPU of its architecture has.
+    pub fn hardware_detector(&self) -> HardwareDetector {
+        let mut detector = match self.triple {
+            Some(_) => HardwareDetector::for_target(&self.arch()),
+            None => HardwareDetector::new(),
+        };
+        if let Some(features) = &self.features {
+            detector.apply_target_features(features);
+        }
+        detector
+    }
+
+    /// The LLVM CPU name to generate code for.
+    pub fn llvm_cpu(&self) -> String {
+        match (&self.cpu, self.arch().as_str()) {
+            (Some(cpu), _) => cpu.clone(),
+            (None, "x86_64") => "x86-64".to_string(),
+            (None, _) => "generic".to_string(),
+        }
+    }
+
+    /// The LLVM `target-features` string: the baseline features of the target,
+    /// followed by those given with `--target-features`.
+    pub fn llvm_features(&self) -> String {
+        let baseline = if self.triple.is_none() && self.arch() == "x86_64" {
+            // Native x86_64 builds have always been generated for AVX2 machines
+            "+avx2,+sse4.2,+fma".to_string()
+        } else {
+            let mut features: Vec<SIMDFeature> = HardwareDetector::for_target(&self.arch())
+                .available_features()
+                .iter()
+                .cloned()
+                .collect();
+            features.sort_by_key(|feature| feature.llvm_name());
+            llvm_target_features(&features)
+        };
+        match self.features.as_deref() {
+            Some(features) if !baseline.is_empty() => format!("{},{}", baseline, features),
+            Some(features) => features.to_string(),
+            None => baseline,
+        }
+    }
+}
+
 /// Hardware capability detector for SIMD features.
 #[derive(Debug, Clone)]
 pub struct HardwareDetector {
@@ -68,6 +268,24 @@ impl HardwareDetector {
         detector
     }
 
+    /// Enables and disables features given as an LLVM `target-features` string
+    /// such as `+avx2,-fma`. Features the detector does not model are ignored.
+    pub fn apply_target_features(&mut self, features: &str) {
+        for item in features.split(',').map(str::trim) {
+            let (enable, name) = match item.strip_prefix('-') {
+                Some(name) => (false, name),
+                None => (true, item.strip_prefix('+').unwrap_or(item)),
+            };
+            if let Some(feature) = SIMDFeature::from_llvm_name(name) {
+                if enable {
+                    self.available_features.insert(feature);
+                } else {
+                    self.available_features.remove(&feature);
+                }
+            }
+        }
+    }
+
     /// Check if a SIMD vector type is supported on the current hardware.
     pub fn is_supported(&self, vector_type: &SIMDVectorType) -> bool {
         match vector_type {
@@ -158,6 +376,15 @@ impl HardwareDetector {
         }
     }
 
+    /// The widest supported vector of `element_type` (e.g. `f32`), which is what
+    /// the lane-width-agnostic `simd<f32>` resolves to on this target.
+    pub fn native_vector_type(&self, element_type: &str) -> Option<SIMDVectorType> {
+        [64, 32, 16, 8, 4, 2]
+            .iter()
+            .filter_map(|&width| SIMDVectorType::from_element_type(element_type, width))
+            .find(|vector_type| self.is_supported(vector_type))
+    }
+
     /// Get a list of all available SIMD features.
     pub fn available_features(&self) -> &HashSet<SIMDFeature> {
         &self.available_features
@@ -203,7 +430,7 @@ impl HardwareDetector {
             );
         }
 
-        if self.target_arch.starts_with("arm") && self.has_feature(&SIMDFeature::NEON) {
+        if is_arm_arch(&self.target_arch) && self.has_feature(&SIMDFeature::NEON) {
             recommendations.push(
                 "NEON operations are most efficient with interleaved data layouts".to_string(),
             );
@@ -217,7 +444,7 @@ impl HardwareDetector {
     fn detect_features(&mut self) {
         match self.target_arch.as_str() {
             "x86_64" | "x86" => self.detect_x86_features(),
-            arch if arch.starts_with("arm") => self.detect_arm_features(),
+            arch if is_arm_arch(arch) => self.detect_arm_features(),
             "powerpc64" | "powerpc" => self.detect_powerpc_features(),
             _ => self.detect_generic_features(),
         }
@@ -232,7 +459,7 @@ impl HardwareDetector {
                 self.available_features.insert(SIMDFeature::SSE2);
                 self.available_features.insert(SIMDFeature::SSE3);
             }
-            arch if arch.starts_with("arm") => {
+            arch if is_arm_arch(arch) => {
                 // Assume NEON is available on modern ARM
                 self.available_features.insert(SIMDFeature::NEON);
             }
@@ -310,13 +537,18 @@ impl HardwareDetector {
     fn has_basic_simd(&self) -> bool {
         match self.target_arch.as_str() {
             "x86_64" | "x86" => self.has_feature(&SIMDFeature::SSE),
-            arch if arch.starts_with("arm") => self.has_feature(&SIMDFeature::NEON),
+            arch if is_arm_arch(arch) => self.has_feature(&SIMDFeature::NEON),
             "powerpc64" | "powerpc" => self.has_feature(&SIMDFeature::ALTIVEC),
             _ => false,
         }
     }
 }
 
+/// Whether `arch` is 32- or 64-bit ARM, which both have NEON's 128-bit vectors.
+fn is_arm_arch(arch: &str) -> bool {
+    arch.starts_with("arm") || arch == "aarch64"
+}
+
 impl Default for HardwareDetector {
     fn default() -> Self {
         Self::new()
@@ -394,6 +626,83 @@ mod tests {
         assert!(!detector.is_supported(&SIMDVectorType::F32x16));
     }
 
+    #[test]
+    fn test_native_vector_type() {
+        let detector = HardwareDetector::for_target("x86_64");
+
+        // Baseline x86_64 has 128-bit SSE registers
+        assert_eq!(
+            detector.native_vector_type("f32"),
+            Some(SIMDVectorType::F32x4)
+        );
+        assert_eq!(
+            detector.native_vector_type("f64"),
+            Some(SIMDVectorType::F64x2)
+        );
+        assert_eq!(
+            detector.native_vector_type("u8"),
+            Some(SIMDVectorType::U8x16)
+        );
+        assert_eq!(detector.native_vector_type("bool"), None);
+
+        // No vector registers to pick a width for
+        let generic = HardwareDetector::for_target("riscv64");
+        assert_eq!(generic.native_vector_type("f32"), None);
+    }
+
+    #[test]
+    fn test_compile_target() {
+        let aarch64 = CompileTarget {
+            triple: Some("aarch64-unknown-linux-gnu".to_string()),
+            ..CompileTarget::default()
+        };
+        assert_eq!(aarch64.arch(), "aarch64");
+        assert_eq!(aarch64.llvm_cpu(), "generic");
+        assert_eq!(aarch64.llvm_features(), "+neon");
+
+        // NEON registers are 128 bits wide
+        let detector = aarch64.hardware_detector();
+        assert_eq!(
+            detector.native_vector_type("f32"),
+            Some(SIMDVectorType::F32x4)
+        );
+        assert!(!detector.is_supported(&SIMDVectorType::F32x8));
+
+        let skylake = CompileTarget {
+            triple: Some("x86_64-unknown-linux-gnu".to_string()),
+            cpu: Some("skylake".to_string()),
+            features: Some("+avx,+avx2,-sse3".to_string()),
+        };
+        assert_eq!(skylake.llvm_cpu(), "skylake");
+        assert_eq!(skylake.llvm_features(), "+sse,+sse2,+sse3,+avx,+avx2,-sse3");
+        let detector = skylake.hardware_detector();
+        assert!(detector.is_supported(&SIMDVectorType::I32x8));
+        assert!(!detector.available_features().contains(&SIMDFeature::SSE3));
+
+        let apple = CompileTarget {
+            triple: Some("arm64-apple-darwin".to_string()),
+            ..CompileTarget::default()
+        };
+        assert_eq!(apple.arch(), "aarch64");
+    }
+
+    #[test]
+    fn test_target_clone_features() {
+        let avx2 = target_clone_features("avx2").unwrap();
+        assert_eq!(llvm_target_features(&avx2), "+avx2,+fma");
+        assert_eq!(
+            llvm_target_features(&target_clone_features("sse4").unwrap()),
+            "+sse4.1,+sse4.2"
+        );
+        assert_eq!(target_clone_features("avx1024"), None);
+
+        // AVX2 is bit 5 of EBX in leaf 7, and needs the OS to save YMM registers
+        assert_eq!(SIMDFeature::AVX2.cpuid_bit(), Some((7, 1, 5)));
+        assert_eq!(SIMDFeature::AVX2.xcr0_mask(), 0x6);
+        assert_eq!(SIMDFeature::SSE42.xcr0_mask(), 0);
+        assert_eq!(SIMDFeature::NEON.cpuid_bit(), None);
+    }
+
     #[test]
     fn test_target_features() {
         let features = TargetFeatures::new()
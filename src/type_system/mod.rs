@@ -4,14 +4,19 @@
 //! This module implements type checking, type inference, and type compatibility
 //! checking for all Eä language constructs.
 
-use crate::ast::{BinaryOp, Expr, Literal, Stmt, TypeAnnotation, UnaryOp};
+use crate::ast::{
+    BinaryOp, Expr, ExternFunction, Literal, Pattern, Stmt, TraitMethod, TypeAnnotation, UnaryOp,
+};
+use crate::comptime::{ComptimeEngine, ComptimeFunction, ComptimeValue};
 use crate::error::{CompileError, Result};
-use crate::lexer::Position;
+use crate::lexer::{Position, Span};
 use crate::memory_profiler::{check_memory_limit, record_memory_usage, CompilationPhase};
 pub mod types;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::ops::Range;
 
+pub mod exhaustiveness;
 pub mod hardware;
 
 /// Simple element types for SIMD vectors to avoid recursive type issues
@@ -46,7 +51,16 @@ pub enum EaType {
     String,
     Unit,
     Array(Box<EaType>),
-    Reference(Box<EaType>),
+    FixedArray(Box<EaType>, usize), // [f32; 64]
+    Tuple(Vec<EaType>),             // (i32, f32)
+    Reference {
+        target: Box<EaType>,
+        is_mutable: bool, // taken from a `let mut` variable, so it may become a `*mut T`
+    },
+    Pointer {
+        target: Box<EaType>,
+        is_mutable: bool, // `*mut T` allows writes through the pointer
+    },
     Function(Box<FunctionType>),
     Struct(String), // Struct type with name
     Custom(String),
@@ -83,6 +97,27 @@ pub struct FunctionType {
     pub is_variadic: bool,
 }
 
+/// A function marked `@export`, callable from C under `symbol`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportedFunction {
+    pub symbol: String,
+    pub params: Vec<(String, EaType)>,
+    pub return_type: EaType,
+}
+
+/// Identifies an expression for code generation: the generic function instance
+/// whose body holds it (empty outside generic functions) and its source range.
+pub type ExpressionKey = (String, Range<usize>);
+
+/// What the type checker found out about expressions that their LLVM types
+/// cannot tell code generation.
+#[derive(Debug, Clone, Default)]
+pub struct CheckedExpressions {
+    pub unsigned: HashSet<ExpressionKey>, // expressions checked as unsigned integers
+    pub type_arguments: HashMap<ExpressionKey, Vec<String>>, // generic calls and struct literals -> inferred type arguments
+    pub closure_results: HashMap<ExpressionKey, String>, // closures without `-> T` -> type of their result
+}
+
 /// Type checking context that maintains type information for variables and functions.
 #[derive(Debug, Clone)]
 pub struct TypeContext {
@@ -91,12 +126,140 @@ pub struct TypeContext {
     pub structs: HashMap<String, HashMap<String, EaType>>, // struct_name -> {field_name -> field_type}
     pub types: HashMap<String, EaType>,                    // enum_name -> EaType::Enum
     pub current_function_return: Option<EaType>,
+    pub constants: HashMap<String, ComptimeValue>, // const name -> folded value
+    pub exports: Vec<ExportedFunction>,            // `@export` functions in declaration order
+    pub mutable_variables: HashSet<String>,        // variables declared with `let mut`
+    pub expressions: CheckedExpressions, // facts about single expressions for code generation
+    pub module_expressions: HashMap<String, CheckedExpressions>, // module name -> its checked expressions
+}
+
+/// The items a module makes available to modules that import it.
+///
+/// Impls are not imported by name: every import of a module brings in the
+/// impls it declares or imported itself.
+#[derive(Debug, Clone, Default)]
+pub struct ModuleInterface {
+    pub functions: HashMap<String, FunctionType>,
+    pub structs: HashMap<String, HashMap<String, EaType>>,
+    pub types: HashMap<String, EaType>,
+    pub constants: HashMap<String, (EaType, ComptimeValue)>,
+    pub(crate) generic_functions: HashMap<String, GenericFunction>,
+    pub(crate) generic_structs: HashMap<String, GenericStruct>,
+    pub(crate) comptime_functions: HashMap<String, ComptimeFunction>,
+    pub(crate) traits: HashMap<String, Vec<TraitMethod>>,
+    pub(crate) methods: HashMap<String, HashMap<String, MethodSignature>>, // type name -> method name -> signature
+    pub(crate) trait_impls: HashSet<(String, String)>,
+    pub(crate) impl_functions: HashMap<String, FunctionType>, // `Type::name` -> signature
+}
+
+impl ModuleInterface {
+    /// Collects the top-level declarations of `program` from its checked context.
+    pub fn from_program(program: &[Stmt], context: &TypeContext) -> Self {
+        let mut interface = Self::default();
+        for stmt in program {
+            match stmt {
+                Stmt::FunctionDeclaration { name, .. } => {
+                    if let Some(func_type) = context.functions.get(name) {
+                        interface.functions.insert(name.clone(), func_type.clone());
+                    }
+                }
+                Stmt::ExternBlock { functions, .. } => {
+                    for function in functions {
+                        if let Some(func_type) = context.functions.get(&function.name) {
+                            interface
+                                .functions
+                                .insert(function.name.clone(), func_type.clone());
+                        }
+                    }
+                }
+                Stmt::StructDeclaration { name, .. } => {
+                    if let Some(fields) = context.structs.get(name) {
+                        interface.structs.insert(name.clone(), fields.clone());
+                    }
+                }
+                Stmt::EnumDeclaration { name, .. } => {
+                    if let Some(enum_type) = context.types.get(name) {
+                        interface.types.insert(name.clone(), enum_type.clone());
+                    }
+                }
+                Stmt::ConstDeclaration { name, .. } => {
+                    if let (Some(const_type), Some(value)) =
+                        (context.variables.get(name), context.constants.get(name))
+                    {
+                        interface
+                            .constants
+                            .insert(name.clone(), (const_type.clone(), value.clone()));
+                    }
+                }
+                _ => {}
+            }
+        }
+        interface
+    }
+}
+
+/// A generic function. Its body is checked once per distinct set of type arguments.
+#[derive(Debug, Clone)]
+pub(crate) struct GenericFunction {
+    name: String,
+    type_params: Vec<String>,
+    params: Vec<crate::ast::Parameter>,
+    return_type: Option<TypeAnnotation>,
+    body: Box<Stmt>,
+    module: Option<String>, // declaring module of an imported function, whose scope its body sees
+}
+
+/// A generic struct, instantiated as `Name<Args>` where it is used.
+#[derive(Debug, Clone)]
+pub(crate) struct GenericStruct {
+    type_params: Vec<String>,
+    fields: Vec<crate::ast::StructField>,
+}
+
+/// A method of a type, callable as `value.method(args)`.
+#[derive(Debug, Clone)]
+pub(crate) struct MethodSignature {
+    trait_name: Option<String>, // None for methods of inherent impls
+    func_type: FunctionType,    // includes the `self` receiver as the first parameter
+}
+
+/// The items declared in or imported into a module. Kept once the module is
+/// checked, since its generic functions are checked where they are called.
+#[derive(Debug, Clone)]
+struct ModuleScope {
+    context: TypeContext,
+    generic_functions: HashMap<String, GenericFunction>,
+    generic_structs: HashMap<String, GenericStruct>,
+    traits: HashMap<String, Vec<TraitMethod>>,
+    methods: HashMap<String, HashMap<String, MethodSignature>>,
+    trait_impls: HashSet<(String, String)>,
+    comptime_functions: HashMap<String, ComptimeFunction>,
 }
 
 /// Main type checker for the Eä language.
 pub struct TypeChecker {
     context: TypeContext,
     hardware_detector: hardware::HardwareDetector,
+    module_interfaces: HashMap<String, ModuleInterface>, // module path -> exported items
+    module_scopes: HashMap<String, ModuleScope>,         // module name -> items visible in it
+    import_targets: HashMap<String, String>, // import path -> module name, for the current module
+    loop_labels: Vec<Option<String>>,        // enclosing loops, innermost last
+    pending_loop_label: Option<String>,      // label for the loop about to be checked
+    generic_functions: HashMap<String, GenericFunction>,
+    generic_structs: HashMap<String, GenericStruct>,
+    function_instances: HashMap<String, FunctionType>, // `sum<i32>` -> signature
+    struct_instances: HashMap<String, HashMap<String, EaType>>, // `Pair<i32>` -> fields
+    type_bindings: HashMap<String, EaType>, // type parameters of the instantiation being checked
+    traits: HashMap<String, Vec<TraitMethod>>,
+    methods: HashMap<String, HashMap<String, MethodSignature>>, // type name -> method name -> signature
+    trait_impls: HashSet<(String, String)>,                     // (trait, type) pairs with an impl
+    comptime_functions: HashMap<String, ComptimeFunction>, // `comptime func`s, run by the engine
+    comptime_depth: usize, // > 0 while checking code that only runs at compile time
+    unsafe_depth: usize,   // > 0 while checking the body of an `unsafe` block
+    current_instance: String, // generic function instance whose body is being checked, empty outside
+    expressions: CheckedExpressions, // found by the current check_program
+    errors: Vec<CompileError>, // every error recovered from by the current check_program
+    warnings: Vec<CompileError>, // problems that do not stop compilation, e.g. unreachable match arms
 }
 
 impl fmt::Display for EaType {
@@ -116,7 +279,23 @@ impl fmt::Display for EaType {
             EaType::String => write!(f, "string"),
             EaType::Unit => write!(f, "()"),
             EaType::Array(elem_type) => write!(f, "[{}]", elem_type),
-            EaType::Reference(inner_type) => write!(f, "&{}", inner_type),
+            EaType::FixedArray(elem_type, length) => write!(f, "[{}; {}]", elem_type, length),
+            EaType::Tuple(element_types) => {
+                let names: Vec<String> = element_types.iter().map(|t| t.to_string()).collect();
+                write!(f, "{}", crate::ast::tuple_type_name(&names))
+            }
+            EaType::Reference {
+                target,
+                is_mutable: false,
+            } => write!(f, "&{}", target),
+            EaType::Reference {
+                target,
+                is_mutable: true,
+            } => write!(f, "&mut {}", target),
+            EaType::Pointer { target, is_mutable } => {
+                let target = target.to_string();
+                write!(f, "{}", crate::ast::pointer_type_name(&target, *is_mutable))
+            }
             EaType::Function(func_type) => write!(f, "{}", func_type),
             EaType::Struct(name) => write!(f, "{}", name),
             EaType::Custom(name) => write!(f, "{}", name),
@@ -137,7 +316,7 @@ impl fmt::Display for EaType {
 
 impl fmt::Display for FunctionType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "(")?;
+        write!(f, "func(")?;
         for (i, param) in self.params.iter().enumerate() {
             if i > 0 {
                 write!(f, ", ")?;
@@ -267,6 +446,11 @@ impl TypeContext {
             structs: HashMap::new(),
             types: HashMap::new(),
             current_function_return: None,
+            constants: HashMap::new(),
+            exports: Vec::new(),
+            mutable_variables: HashSet::new(),
+            expressions: CheckedExpressions::default(),
+            module_expressions: HashMap::new(),
         };
 
         // Add built-in print function
@@ -287,9 +471,20 @@ impl TypeContext {
     }
 
     pub fn define_variable(&mut self, name: String, ty: EaType) {
+        self.mutable_variables.remove(&name);
         self.variables.insert(name, ty);
     }
 
+    /// Defines a variable declared with `let mut`.
+    pub fn define_mutable_variable(&mut self, name: String, ty: EaType) {
+        self.mutable_variables.insert(name.clone());
+        self.variables.insert(name, ty);
+    }
+
+    pub fn is_mutable_variable(&self, name: &str) -> bool {
+        self.mutable_variables.contains(name)
+    }
+
     pub fn get_variable_type(&self, name: &str) -> Option<&EaType> {
         self.variables.get(name)
     }
@@ -315,6 +510,26 @@ impl TypeChecker {
         let mut checker = Self {
             context: TypeContext::new(),
             hardware_detector: hardware::HardwareDetector::new(),
+            module_interfaces: HashMap::new(),
+            module_scopes: HashMap::new(),
+            import_targets: HashMap::new(),
+            loop_labels: Vec::new(),
+            pending_loop_label: None,
+            generic_functions: HashMap::new(),
+            generic_structs: HashMap::new(),
+            function_instances: HashMap::new(),
+            struct_instances: HashMap::new(),
+            type_bindings: HashMap::new(),
+            traits: HashMap::new(),
+            methods: HashMap::new(),
+            trait_impls: HashSet::new(),
+            comptime_functions: HashMap::new(),
+            comptime_depth: 0,
+            unsafe_depth: 0,
+            current_instance: String::new(),
+            expressions: CheckedExpressions::default(),
+            errors: Vec::new(),
+            warnings: Vec::new(),
         };
         checker.add_builtin_functions();
         checker.add_builtin_types();
@@ -549,7 +764,10 @@ impl TypeChecker {
         // array_push<T>(&mut []T, T) -> ()
         let array_push_type = FunctionType {
             params: vec![
-                EaType::Reference(Box::new(EaType::Array(Box::new(EaType::I32)))),
+                EaType::Reference {
+                    target: Box::new(EaType::Array(Box::new(EaType::I32))),
+                    is_mutable: true,
+                },
                 EaType::I32,
             ], // Simplified
             return_type: Box::new(EaType::Unit),
@@ -560,9 +778,10 @@ impl TypeChecker {
 
         // array_pop<T>(&mut []T) -> Option<T>
         let array_pop_type = FunctionType {
-            params: vec![EaType::Reference(Box::new(EaType::Array(Box::new(
-                EaType::I32,
-            ))))], // Simplified
+            params: vec![EaType::Reference {
+                target: Box::new(EaType::Array(Box::new(EaType::I32))),
+                is_mutable: true,
+            }], // Simplified
             return_type: Box::new(EaType::I32), // Simplified - should be Option<T>
             is_variadic: false,
         };
@@ -767,11 +986,16 @@ impl TypeChecker {
 
     /// Create a type checker for a specific target architecture.
     pub fn for_target(target_arch: &str) -> Self {
-        let mut checker = Self {
-            context: TypeContext::new(),
-            hardware_detector: hardware::HardwareDetector::for_target(target_arch),
-        };
-        checker.add_builtin_functions();
+        let mut checker = Self::new();
+        checker.hardware_detector = hardware::HardwareDetector::for_target(target_arch);
+        checker
+    }
+
+    /// Create a type checker for the target selected with `--target`,
+    /// `--target-cpu` and `--target-features`.
+    pub fn for_compile_target(target: &hardware::CompileTarget) -> Self {
+        let mut checker = Self::new();
+        checker.hardware_detector = target.hardware_detector();
         checker
     }
 
@@ -833,7 +1057,14 @@ impl TypeChecker {
     }
 
     /// Type checks a complete program.
+    ///
+    /// Checking carries on past errors so that all of them are found in one
+    /// run; the first is returned and the rest are available from `get_errors`.
     pub fn check_program(&mut self, program: &[Stmt]) -> Result<TypeContext> {
+        self.errors.clear();
+        self.warnings.clear();
+        self.expressions = CheckedExpressions::default();
+
         // Record initial memory usage for type checking
         let initial_memory =
             std::mem::size_of::<TypeContext>() + program.len() * std::mem::size_of::<Stmt>();
@@ -844,7 +1075,8 @@ impl TypeChecker {
         );
 
         for (i, stmt) in program.iter().enumerate() {
-            self.check_statement(stmt)?;
+            // The error is recorded, so move on to the next statement
+            let _ = self.check_statement(stmt);
 
             // Check memory usage periodically
             if i % 50 == 0 {
@@ -871,7 +1103,283 @@ impl TypeChecker {
             }
         }
 
-        Ok(self.context.clone())
+        if let Some(error) = self.errors.first() {
+            return Err(error.clone());
+        }
+
+        let mut context = self.context.clone();
+        context.expressions = std::mem::take(&mut self.expressions);
+        Ok(context)
+    }
+
+    /// Returns every error found by the last call to `check_program`.
+    pub fn get_errors(&self) -> &[CompileError] {
+        &self.errors
+    }
+
+    /// Returns the warnings found by the last call to `check_program`.
+    pub fn get_warnings(&self) -> &[CompileError] {
+        &self.warnings
+    }
+
+    /// Records `error` unless an error was already recorded since `reported`,
+    /// in which case it is only a knock-on effect of that earlier one.
+    fn report(&mut self, reported: usize, error: CompileError) {
+        if self.errors.len() == reported {
+            self.errors.push(error);
+        }
+    }
+
+    /// Type checks a multi-file program whose modules are in dependency order.
+    ///
+    /// Every module is checked in its own namespace starting from the builtin
+    /// items; only the names it imports are visible to it. Returns the
+    /// context of the root module, whose exports are those of every module and
+    /// which holds the checked expressions of each module by its name.
+    pub fn check_modules(
+        &mut self,
+        modules: &[crate::module_resolver::ResolvedModule],
+    ) -> Result<TypeContext> {
+        let builtin_scope = self.scope();
+        let mut root_context = None;
+        let mut exports: Vec<ExportedFunction> = Vec::new();
+        let mut module_expressions = HashMap::new();
+
+        for module in modules {
+            self.replace_scope(builtin_scope.clone());
+            self.function_instances.clear();
+            self.struct_instances.clear();
+            self.import_targets = module.import_targets.clone();
+            let module_context = self.check_program(&module.program)?;
+
+            // Exported symbols share one C namespace across all modules
+            for export in &module_context.exports {
+                if exports.iter().any(|other| other.symbol == export.symbol) {
+                    return Err(Self::duplicate_export_error(&export.symbol));
+                }
+                exports.push(export.clone());
+            }
+
+            let interface = self.module_interface(&module.name, &module.program, &module_context);
+            self.module_interfaces.insert(module.name.clone(), interface);
+            // Its generic functions are instantiated later with the items of this module
+            let mut scope = self.scope();
+            for generic in scope.generic_functions.values_mut() {
+                generic.module.get_or_insert_with(|| module.name.clone());
+            }
+            self.module_scopes.insert(module.name.clone(), scope);
+            // Source ranges are only unique within a file
+            module_expressions.insert(module.name.clone(), module_context.expressions.clone());
+            if module.is_root {
+                root_context = Some(module_context);
+            }
+        }
+        self.import_targets.clear();
+
+        let mut context = root_context.unwrap_or_else(|| self.context.clone());
+        context.exports = exports;
+        context.module_expressions = module_expressions;
+        Ok(context)
+    }
+
+    /// The items visible in the module being checked.
+    fn scope(&self) -> ModuleScope {
+        ModuleScope {
+            context: self.context.clone(),
+            generic_functions: self.generic_functions.clone(),
+            generic_structs: self.generic_structs.clone(),
+            traits: self.traits.clone(),
+            methods: self.methods.clone(),
+            trait_impls: self.trait_impls.clone(),
+            comptime_functions: self.comptime_functions.clone(),
+        }
+    }
+
+    /// Makes `scope` the items visible to the code being checked and returns
+    /// the ones that were.
+    fn replace_scope(&mut self, scope: ModuleScope) -> ModuleScope {
+        ModuleScope {
+            context: std::mem::replace(&mut self.context, scope.context),
+            generic_functions: std::mem::replace(
+                &mut self.generic_functions,
+                scope.generic_functions,
+            ),
+            generic_structs: std::mem::replace(&mut self.generic_structs, scope.generic_structs),
+            traits: std::mem::replace(&mut self.traits, scope.traits),
+            methods: std::mem::replace(&mut self.methods, scope.methods),
+            trait_impls: std::mem::replace(&mut self.trait_impls, scope.trait_impls),
+            comptime_functions: std::mem::replace(
+                &mut self.comptime_functions,
+                scope.comptime_functions,
+            ),
+        }
+    }
+
+    /// Collects the items `module` declares at the top level, with the impls
+    /// it declares, from the checker state left by checking it.
+    fn module_interface(
+        &self,
+        module: &str,
+        program: &[Stmt],
+        context: &TypeContext,
+    ) -> ModuleInterface {
+        let mut interface = ModuleInterface::from_program(program, context);
+        for stmt in program {
+            match stmt {
+                Stmt::FunctionDeclaration { name, .. } => {
+                    if let Some(generic) = self.generic_functions.get(name) {
+                        let generic = GenericFunction {
+                            module: Some(module.to_string()),
+                            ..generic.clone()
+                        };
+                        interface.generic_functions.insert(name.clone(), generic);
+                    }
+                    if let Some(function) = self.comptime_functions.get(name) {
+                        interface
+                            .comptime_functions
+                            .insert(name.clone(), function.clone());
+                    }
+                }
+                Stmt::StructDeclaration { name, .. } => {
+                    if let Some(generic) = self.generic_structs.get(name) {
+                        interface
+                            .generic_structs
+                            .insert(name.clone(), generic.clone());
+                    }
+                }
+                Stmt::TraitDeclaration { name, .. } => {
+                    if let Some(methods) = self.traits.get(name) {
+                        interface.traits.insert(name.clone(), methods.clone());
+                    }
+                }
+                Stmt::ImplBlock {
+                    trait_name,
+                    type_name,
+                    methods,
+                    ..
+                } => {
+                    if let Some(trait_name) = trait_name {
+                        interface
+                            .trait_impls
+                            .insert((trait_name.clone(), type_name.clone()));
+                    }
+                    for method in methods {
+                        let Stmt::FunctionDeclaration { name, .. } = method else {
+                            continue;
+                        };
+                        if let Some(signature) = self
+                            .methods
+                            .get(type_name)
+                            .and_then(|methods| methods.get(name))
+                        {
+                            interface
+                                .methods
+                                .entry(type_name.clone())
+                                .or_default()
+                                .insert(name.clone(), signature.clone());
+                        }
+                        let qualified_name = format!("{}::{}", type_name, name);
+                        if let Some(func_type) = context.functions.get(&qualified_name) {
+                            interface
+                                .impl_functions
+                                .insert(qualified_name, func_type.clone());
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        interface
+    }
+
+    /// Brings the impls declared by an imported module into the current namespace.
+    fn import_impls(&mut self, interface: &ModuleInterface) {
+        for (type_name, methods) in &interface.methods {
+            let type_methods = self.methods.entry(type_name.clone()).or_default();
+            for (name, signature) in methods {
+                type_methods
+                    .entry(name.clone())
+                    .or_insert_with(|| signature.clone());
+            }
+        }
+        self.trait_impls
+            .extend(interface.trait_impls.iter().cloned());
+        for (name, func_type) in &interface.impl_functions {
+            self.context
+                .functions
+                .entry(name.clone())
+                .or_insert_with(|| func_type.clone());
+        }
+    }
+
+    /// Brings the items of an already checked module into the current namespace.
+    fn check_import_declaration(
+        &mut self,
+        module_path: &str,
+        items: &[crate::ast::ImportItem],
+        alias: &Option<String>,
+    ) -> Result<()> {
+        let interface = self
+            .module_interfaces
+            .get(crate::module_resolver::import_target(
+                &self.import_targets,
+                module_path,
+            ))
+            .cloned()
+            .ok_or_else(|| {
+                CompileError::type_error(
+                    format!("Unresolved module '{}'", module_path),
+                    Position::new(0, 0, 0),
+                )
+            })?;
+        self.import_impls(&interface);
+
+        if items.is_empty() {
+            // `import a::b [as m];` - functions are reachable as `m::name(...)`
+            let prefix = alias
+                .clone()
+                .unwrap_or_else(|| module_path.rsplit("::").next().unwrap_or(module_path).to_string());
+            for (name, func_type) in interface.functions {
+                self.context
+                    .define_function(format!("{}::{}", prefix, name), func_type);
+            }
+            for (name, generic) in interface.generic_functions {
+                self.generic_functions
+                    .insert(format!("{}::{}", prefix, name), generic);
+            }
+            return Ok(());
+        }
+
+        for item in items {
+            let local_name = item.local_name().to_string();
+            if let Some(func_type) = interface.functions.get(&item.name) {
+                if let Some(function) = interface.comptime_functions.get(&item.name) {
+                    self.comptime_functions
+                        .insert(local_name.clone(), function.clone());
+                }
+                self.context.define_function(local_name, func_type.clone());
+            } else if let Some(generic) = interface.generic_functions.get(&item.name) {
+                self.generic_functions.insert(local_name, generic.clone());
+            } else if let Some(generic) = interface.generic_structs.get(&item.name) {
+                self.generic_structs.insert(local_name, generic.clone());
+            } else if let Some(methods) = interface.traits.get(&item.name) {
+                self.traits.insert(local_name, methods.clone());
+            } else if let Some(fields) = interface.structs.get(&item.name) {
+                self.context.structs.insert(local_name, fields.clone());
+            } else if let Some(enum_type) = interface.types.get(&item.name) {
+                self.context.types.insert(local_name, enum_type.clone());
+            } else if let Some((const_type, value)) = interface.constants.get(&item.name) {
+                self.context.define_variable(local_name.clone(), const_type.clone());
+                self.context.constants.insert(local_name, value.clone());
+            } else {
+                return Err(CompileError::type_error(
+                    format!("Module '{}' has no item named '{}'", module_path, item.name),
+                    Position::new(0, 0, 0),
+                ));
+            }
+        }
+
+        Ok(())
     }
 
     /// Get the current type context (for streaming compilation)
@@ -880,124 +1388,851 @@ impl TypeChecker {
     }
 
     /// Check a single statement (made public for streaming compilation)
+    ///
+    /// Errors inside the statement are recorded and checking continues; the
+    /// first of them is returned once the whole statement has been checked.
     pub fn check_statement(&mut self, stmt: &Stmt) -> Result<()> {
+        let reported = self.errors.len();
+        if let Err(error) = self.check_statement_node(stmt) {
+            self.report(reported, error.with_span(stmt.span()));
+        }
+        match self.errors.get(reported) {
+            Some(error) => Err(error.clone()),
+            None => Ok(()),
+        }
+    }
+
+    fn check_statement_node(&mut self, stmt: &Stmt) -> Result<()> {
         match stmt {
             Stmt::FunctionDeclaration {
                 name,
+                type_params,
                 params,
                 return_type,
                 body,
-                attributes: _,
-            } => self.check_function_declaration(name, params, return_type, body),
+                attributes,
+                ..
+            } => {
+                if params.first().map_or(false, |param| param.name == "self") {
+                    return Err(CompileError::type_error(
+                        format!(
+                            "'self' parameter of function '{}' is only allowed in methods of impl blocks",
+                            name
+                        ),
+                        Position::new(0, 0, 0),
+                    ));
+                }
+                if let Some(symbol) = crate::ast::exported_symbol(attributes, name) {
+                    self.check_export(name, symbol, type_params, params, return_type, attributes)?;
+                }
+                if attributes
+                    .iter()
+                    .any(|attribute| attribute.name == crate::ast::Attribute::TARGET_CLONES)
+                {
+                    self.check_target_clones(name, type_params, attributes)?;
+                }
+                if crate::ast::is_comptime_function(attributes) {
+                    self.check_comptime_function(name, type_params, params, return_type, body)
+                } else if type_params.is_empty() {
+                    self.check_function_declaration(name, params, return_type, body)
+                } else {
+                    self.generic_functions.insert(
+                        name.clone(),
+                        GenericFunction {
+                            type_params: type_params.clone(),
+                            params: params.clone(),
+                            return_type: return_type.clone(),
+                            body: body.clone(),
+                            name: name.clone(),
+                            module: None,
+                        },
+                    );
+                    Ok(())
+                }
+            }
             Stmt::VarDeclaration {
-                name,
+                pattern,
+                is_mutable,
                 type_annotation,
                 initializer,
-            } => self.check_var_declaration(name, type_annotation, initializer),
-            Stmt::Expression(expr) => {
+                ..
+            } => self.check_var_declaration(pattern, *is_mutable, type_annotation, initializer),
+            Stmt::Expression(expr, _) => {
                 self.check_expression(expr)?;
                 Ok(())
             }
-            Stmt::Return(expr) => self.check_return_statement(expr),
-            Stmt::Block(stmts) => self.check_block(stmts),
+            Stmt::Return(expr, _) => self.check_return_statement(expr),
+            Stmt::Block(stmts, _) => self.check_block(stmts),
             Stmt::If {
                 condition,
                 then_branch,
                 else_branch,
+                ..
             } => self.check_if_statement(condition, then_branch, else_branch),
-            Stmt::While { condition, body } => self.check_while_statement(condition, body),
+            Stmt::While {
+                condition, body, ..
+            } => self.check_while_statement(condition, body),
             Stmt::For {
                 initializer,
                 condition,
                 increment,
                 body,
+                ..
             } => self.check_for_statement(initializer, condition, increment, body),
             Stmt::ForIn {
                 variable,
                 iterable,
                 body,
+                ..
             } => self.check_for_in_statement(variable, iterable, body),
-            Stmt::StructDeclaration { name, fields } => self.check_struct_declaration(name, fields),
-            Stmt::EnumDeclaration { name, variants } => self.check_enum_declaration(name, variants),
+            Stmt::StructDeclaration {
+                name,
+                type_params,
+                fields,
+                ..
+            } => {
+                if type_params.is_empty() {
+                    self.check_struct_declaration(name, fields)
+                } else {
+                    self.generic_structs.insert(
+                        name.clone(),
+                        GenericStruct {
+                            type_params: type_params.clone(),
+                            fields: fields.clone(),
+                        },
+                    );
+                    Ok(())
+                }
+            }
+            Stmt::EnumDeclaration { name, variants, .. } => {
+                self.check_enum_declaration(name, variants)
+            }
+            Stmt::ImportDeclaration {
+                module_path,
+                items,
+                alias,
+                ..
+            } => self.check_import_declaration(module_path, items, alias),
+            Stmt::Break(label, _) => self.check_loop_control("break", label),
+            Stmt::Continue(label, _) => self.check_loop_control("continue", label),
+            Stmt::Labeled { label, body, .. } => self.check_labeled_loop(label, body),
+            Stmt::ConstDeclaration {
+                name,
+                type_annotation,
+                value,
+                ..
+            } => self.check_const_declaration(name, type_annotation, value),
+            Stmt::TraitDeclaration { name, methods, .. } => {
+                self.check_trait_declaration(name, methods)
+            }
+            Stmt::ImplBlock {
+                trait_name,
+                type_name,
+                methods,
+                ..
+            } => self.check_impl_block(trait_name, type_name, methods),
+            Stmt::ExternBlock { functions, .. } => self.check_extern_block(functions),
         }
     }
 
-    fn check_function_declaration(
+    /// Declares the functions of an `extern "C"` block; calls to them are
+    /// checked like calls to any other function.
+    fn check_extern_block(&mut self, functions: &[ExternFunction]) -> Result<()> {
+        for function in functions {
+            let mut param_types = Vec::new();
+            for param in &function.params {
+                if param.name == "self" {
+                    return Err(CompileError::type_error(
+                        format!("Extern function '{}' cannot take 'self'", function.name),
+                        Position::new(0, 0, 0),
+                    ));
+                }
+                param_types.push(self.annotation_to_type(&param.type_annotation)?);
+            }
+            let return_type = match &function.return_type {
+                Some(type_ann) => self.annotation_to_type(type_ann)?,
+                None => EaType::Unit,
+            };
+
+            self.context.define_function(
+                function.name.clone(),
+                FunctionType {
+                    params: param_types,
+                    return_type: Box::new(return_type),
+                    is_variadic: false,
+                },
+            );
+        }
+        Ok(())
+    }
+
+    fn check_const_declaration(
         &mut self,
         name: &str,
-        params: &[crate::ast::Parameter],
-        return_type: &Option<TypeAnnotation>,
-        body: &Box<Stmt>,
+        type_annotation: &TypeAnnotation,
+        value: &Expr,
     ) -> Result<()> {
-        let mut param_types = Vec::new();
-        for param in params {
-            let param_type = self.annotation_to_type(&param.type_annotation)?;
-            param_types.push(param_type);
+        let declared_type = self.annotation_to_type(type_annotation)?;
+        self.comptime_depth += 1;
+        let value_type = self.check_expression(value);
+        self.comptime_depth -= 1;
+        let value_type = value_type?;
+        if !self.types_compatible(&declared_type, &value_type) {
+            return Err(CompileError::type_error(
+                format!(
+                    "Type mismatch in constant '{}': declared as {}, initialized with {}",
+                    name, declared_type, value_type
+                ),
+                Position::new(0, 0, 0),
+            ));
         }
 
-        let return_ea_type = match return_type {
-            Some(type_ann) => self.annotation_to_type(type_ann)?,
-            None => EaType::Unit,
-        };
-
-        let func_type = FunctionType {
-            params: param_types.clone(),
-            return_type: Box::new(return_ea_type.clone()),
-            is_variadic: false,
-        };
+        let folded = self.fold_constant(value).map_err(|e| {
+            CompileError::type_error(
+                format!("Value of constant '{}' is not a compile-time constant: {}", name, e),
+                Position::new(0, 0, 0),
+            )
+        })?;
 
-        self.context.define_function(name.to_string(), func_type);
+        self.context.define_variable(name.to_string(), declared_type);
+        self.context.constants.insert(name.to_string(), folded);
+        Ok(())
+    }
 
-        let mut function_context = self.context.enter_scope();
-        function_context.current_function_return = Some(return_ea_type.clone());
+    /// Evaluates `expr` at compile time with the constants in scope.
+    fn fold_constant(
+        &self,
+        expr: &Expr,
+    ) -> std::result::Result<ComptimeValue, crate::comptime::ComptimeError> {
+        self.comptime_engine().evaluate_expression(expr)
+    }
 
-        for (param, param_type) in params.iter().zip(param_types.iter()) {
-            function_context.define_variable(param.name.clone(), param_type.clone());
+    /// A fresh engine that sees the constants in scope and every `comptime func`.
+    fn comptime_engine(&self) -> ComptimeEngine {
+        let mut engine = ComptimeEngine::new();
+        for (name, value) in &self.context.constants {
+            engine.define_value(name, value.clone());
+        }
+        for function in self.comptime_functions.values() {
+            engine.define_function(function.clone());
         }
+        engine
+    }
 
-        let old_context = std::mem::replace(&mut self.context, function_context);
-        let result = self.check_statement(body);
+    /// Checks that `@target_clones(avx512, avx2, sse4)` names distinct x86 feature
+    /// levels, and that the function is one that can be dispatched at runtime.
+    fn check_target_clones(
+        &self,
+        name: &str,
+        type_params: &[String],
+        attributes: &[crate::ast::Attribute],
+    ) -> Result<()> {
+        let clones_error = |message: String| {
+            Err(CompileError::type_error(
+                format!(
+                    "Cannot use @target_clones on function '{}': {}",
+                    name, message
+                ),
+                Position::new(0, 0, 0),
+            ))
+        };
 
-        // Check if function with non-unit return type actually returns a value
-        if result.is_ok() && !matches!(return_ea_type, EaType::Unit) {
-            if !self.statement_returns(body) {
-                self.context = old_context;
-                return Err(CompileError::type_error(
-                    format!(
-                        "Function '{}' with return type {:?} is missing a return statement",
-                        name, return_ea_type
-                    ),
-                    Position::new(0, 0, 0),
+        if self.hardware_detector.target_arch() != "x86_64" {
+            return clones_error(format!(
+                "clones are dispatched with CPUID, which target architecture {} does not have",
+                self.hardware_detector.target_arch()
+            ));
+        }
+        if name == "main" {
+            return clones_error("main is called before clones can be resolved".to_string());
+        }
+        if !type_params.is_empty() {
+            return clones_error("generic functions are cloned per instantiation".to_string());
+        }
+        if crate::ast::is_comptime_function(attributes) {
+            return clones_error("comptime functions only exist at compile time".to_string());
+        }
+
+        let attribute = attributes
+            .iter()
+            .find(|attribute| attribute.name == crate::ast::Attribute::TARGET_CLONES)
+            .expect("caller found the attribute");
+        if attribute.params.is_empty() {
+            return clones_error(
+                "expected feature levels such as avx512, avx2 or sse4".to_string(),
+            );
+        }
+        let mut levels: Vec<String> = Vec::new();
+        for param in &attribute.params {
+            let level = match (&param.key[..], &param.value) {
+                ("", crate::ast::AttributeValue::Identifier(level)) => level.clone(),
+                _ => {
+                    return clones_error(format!(
+                        "expected a feature level such as avx2, got {}",
+                        param.value
+                    ))
+                }
+            };
+            if hardware::target_clone_features(&level).is_none() {
+                return clones_error(format!(
+                    "unknown feature level '{}'; expected one of {}",
+                    level,
+                    hardware::TARGET_CLONE_LEVELS.join(", ")
                 ));
             }
+            if levels.contains(&level) {
+                return clones_error(format!("feature level '{}' is listed twice", level));
+            }
+            levels.push(level);
         }
-
-        self.context = old_context;
-        result
+        Ok(())
     }
 
-    fn check_var_declaration(
+    /// Checks that a function marked `@export` has a C signature and records it
+    /// in the context's exports.
+    fn check_export(
         &mut self,
         name: &str,
-        type_annotation: &Option<TypeAnnotation>,
-        initializer: &Option<Expr>,
+        symbol: String,
+        type_params: &[String],
+        params: &[crate::ast::Parameter],
+        return_type: &Option<TypeAnnotation>,
+        attributes: &[crate::ast::Attribute],
     ) -> Result<()> {
-        let var_type = match (type_annotation, initializer) {
-            (Some(type_ann), Some(init)) => {
-                let declared_type = self.annotation_to_type(type_ann)?;
-                let init_type = self.check_expression(init)?;
+        let export_error = |message: String| {
+            Err(CompileError::type_error(
+                format!("Cannot export function '{}': {}", name, message),
+                Position::new(0, 0, 0),
+            ))
+        };
 
-                if !self.types_compatible(&declared_type, &init_type) {
-                    return Err(CompileError::type_error(
-                        format!(
-                            "Type mismatch in variable '{}': declared as {:?}, initialized with {:?}",
-                            name, declared_type, init_type
-                        ),
-                        Position::new(0, 0, 0),
-                    ));
-                }
+        if !type_params.is_empty() {
+            return export_error("generic functions have no single C signature".to_string());
+        }
+        if crate::ast::is_comptime_function(attributes) {
+            return export_error("comptime functions only exist at compile time".to_string());
+        }
 
-                declared_type
+        let export_attribute = attributes
+            .iter()
+            .find(|attribute| attribute.name == crate::ast::Attribute::EXPORT);
+        for param in export_attribute.map_or(&[][..], |attribute| &attribute.params[..]) {
+            match (&param.key[..], &param.value) {
+                ("name", crate::ast::AttributeValue::String(_)) => {}
+                ("name", value) => {
+                    return export_error(format!("symbol name {} must be a string", value))
+                }
+                (key, _) => return export_error(format!("unknown @export parameter '{}'", key)),
+            }
+        }
+        let is_c_identifier = symbol
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && symbol.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !is_c_identifier {
+            return export_error(format!("'{}' is not a valid C symbol name", symbol));
+        }
+        if self.context.exports.iter().any(|export| export.symbol == symbol) {
+            return Err(Self::duplicate_export_error(&symbol));
+        }
+
+        let mut exported_params = Vec::new();
+        for param in params {
+            let param_type = self.annotation_to_type(&param.type_annotation)?;
+            if param_type == EaType::Unit || crate::c_header::c_type_name(&param_type).is_none() {
+                return export_error(format!(
+                    "parameter '{}' has type {}, which has no C equivalent",
+                    param.name, param_type
+                ));
+            }
+            exported_params.push((param.name.clone(), param_type));
+        }
+        let exported_return = match return_type {
+            Some(annotation) => self.annotation_to_type(annotation)?,
+            None => EaType::Unit,
+        };
+        if crate::c_header::c_type_name(&exported_return).is_none() {
+            return export_error(format!(
+                "return type {} has no C equivalent",
+                exported_return
+            ));
+        }
+
+        self.context.exports.push(ExportedFunction {
+            symbol,
+            params: exported_params,
+            return_type: exported_return,
+        });
+        Ok(())
+    }
+
+    fn duplicate_export_error(symbol: &str) -> CompileError {
+        CompileError::type_error(
+            format!("Symbol '{}' is exported more than once", symbol),
+            Position::new(0, 0, 0),
+        )
+    }
+
+    /// Checks a `comptime func` like a normal function and hands it to the engine.
+    /// Its body may call other `comptime func`s; runtime code may not call it.
+    fn check_comptime_function(
+        &mut self,
+        name: &str,
+        type_params: &[String],
+        params: &[crate::ast::Parameter],
+        return_type: &Option<TypeAnnotation>,
+        body: &Box<Stmt>,
+    ) -> Result<()> {
+        if !type_params.is_empty() {
+            return Err(CompileError::type_error(
+                format!("comptime function '{}' cannot be generic", name),
+                Position::new(0, 0, 0),
+            ));
+        }
+
+        // Registered first so the function can call itself
+        self.comptime_functions.insert(
+            name.to_string(),
+            ComptimeFunction::from_declaration(name, params, return_type, body),
+        );
+
+        self.comptime_depth += 1;
+        let result = self.check_function_declaration(name, params, return_type, body);
+        self.comptime_depth -= 1;
+        result
+    }
+
+    /// Runs a `comptime { ... }` block in the engine. The block is typed by the
+    /// value it produces, which codegen embeds as an LLVM constant.
+    fn check_comptime_block(&mut self, statements: &[Stmt]) -> Result<EaType> {
+        let value = self
+            .comptime_engine()
+            .evaluate_block(statements)
+            .map_err(|e| {
+                CompileError::type_error(
+                    format!("Failed to evaluate comptime block: {}", e),
+                    Position::new(0, 0, 0),
+                )
+            })?;
+        self.comptime_value_type(&value)
+    }
+
+    /// Runtime type of a value computed at compile time, following the literal rules.
+    fn comptime_value_type(&self, value: &ComptimeValue) -> Result<EaType> {
+        match value {
+            ComptimeValue::Integer(_) => Ok(EaType::I64),
+            ComptimeValue::Float(_) => Ok(EaType::F32),
+            ComptimeValue::Boolean(_) => Ok(EaType::Bool),
+            ComptimeValue::String(_) => Ok(EaType::String),
+            ComptimeValue::Tuple(elements) => {
+                let mut element_types = Vec::new();
+                for element in elements {
+                    element_types.push(self.comptime_value_type(element)?);
+                }
+                Ok(EaType::Tuple(element_types))
+            }
+            _ => {
+                let elements = value.array_elements().ok_or_else(|| {
+                    CompileError::type_error(
+                        format!("Comptime value {:?} cannot be used at runtime", value),
+                        Position::new(0, 0, 0),
+                    )
+                })?;
+                if elements.iter().any(|e| matches!(e, ComptimeValue::Float(_))) {
+                    return Ok(EaType::Array(Box::new(EaType::F32)));
+                }
+                match elements.first() {
+                    Some(first @ (ComptimeValue::Integer(_) | ComptimeValue::Boolean(_))) => {
+                        Ok(EaType::Array(Box::new(self.comptime_value_type(first)?)))
+                    }
+                    Some(other) => Err(CompileError::type_error(
+                        format!("Comptime arrays of {:?} cannot be used at runtime", other),
+                        Position::new(0, 0, 0),
+                    )),
+                    None => Err(CompileError::type_error(
+                        "Comptime arrays must not be empty".to_string(),
+                        Position::new(0, 0, 0),
+                    )),
+                }
+            }
+        }
+    }
+
+    /// Folds an array length or lane count, which must be a non-negative integer constant.
+    fn constant_length(&self, expr: &Expr) -> Result<usize> {
+        match self.fold_constant(expr) {
+            Ok(ComptimeValue::Integer(n)) if n >= 0 => Ok(n as usize),
+            Ok(other) => Err(CompileError::type_error(
+                format!("Array length '{}' must be a non-negative integer, got {:?}", expr, other),
+                Position::new(0, 0, 0),
+            )),
+            Err(e) => Err(CompileError::type_error(
+                format!("Array length '{}' is not a compile-time constant: {}", expr, e),
+                Position::new(0, 0, 0),
+            )),
+        }
+    }
+
+    fn check_array_repeat(
+        &mut self,
+        value: &Expr,
+        count: &Expr,
+        vector_type: &Option<crate::ast::SIMDVectorType>,
+    ) -> Result<EaType> {
+        let count = self.constant_length(count)?;
+        let expanded = crate::ast::expand_array_repeat(value, count, vector_type).ok_or_else(|| {
+            CompileError::type_error(
+                format!(
+                    "Repeated array value '{}' must be a literal unless a SIMD vector type is given",
+                    value
+                ),
+                Position::new(0, 0, 0),
+            )
+        })?;
+        self.check_expression(&expanded)
+    }
+
+    fn check_trait_declaration(&mut self, name: &str, methods: &[TraitMethod]) -> Result<()> {
+        if self.traits.contains_key(name) {
+            return Err(CompileError::type_error(
+                format!("Trait '{}' is already defined", name),
+                Position::new(0, 0, 0),
+            ));
+        }
+
+        let mut seen = HashSet::new();
+        for method in methods {
+            if !seen.insert(method.name.as_str()) {
+                return Err(CompileError::type_error(
+                    format!("Duplicate method '{}' in trait '{}'", method.name, name),
+                    Position::new(0, 0, 0),
+                ));
+            }
+            if method.params.first().map_or(true, |param| param.name != "self") {
+                return Err(CompileError::type_error(
+                    format!(
+                        "Trait method '{}' must take 'self' as its first parameter",
+                        method.name
+                    ),
+                    Position::new(0, 0, 0),
+                ));
+            }
+        }
+
+        self.traits.insert(name.to_string(), methods.to_vec());
+        Ok(())
+    }
+
+    /// Checks an impl block and registers its methods on the type.
+    ///
+    /// Trait impls must provide exactly the trait's methods with matching
+    /// signatures; inherent impls may define any methods and associated functions.
+    fn check_impl_block(
+        &mut self,
+        trait_name: &Option<String>,
+        type_name: &str,
+        methods: &[Stmt],
+    ) -> Result<()> {
+        let self_type = self.annotation_to_type(&TypeAnnotation {
+            name: type_name.to_string(),
+            is_mutable: false,
+        })?;
+        let type_key = self_type.to_string();
+
+        let trait_methods = match trait_name {
+            Some(trait_name) => {
+                let trait_methods = self.traits.get(trait_name).cloned().ok_or_else(|| {
+                    CompileError::undefined_type_error(
+                        format!("Unknown trait '{}'", trait_name),
+                        Position::new(0, 0, 0),
+                    )
+                })?;
+                if let EaType::Custom(name) = &self_type {
+                    if !self.context.types.contains_key(name) {
+                        return Err(CompileError::undefined_type_error(
+                            format!("Unknown type '{}' in impl of trait '{}'", name, trait_name),
+                            Position::new(0, 0, 0),
+                        ));
+                    }
+                }
+                if !self
+                    .trait_impls
+                    .insert((trait_name.clone(), type_key.clone()))
+                {
+                    return Err(CompileError::type_error(
+                        format!("Trait '{}' is already implemented for '{}'", trait_name, type_key),
+                        Position::new(0, 0, 0),
+                    ));
+                }
+                Some((trait_name.as_str(), trait_methods))
+            }
+            None => {
+                let is_user_type = match &self_type {
+                    EaType::Struct(_) => true,
+                    EaType::Custom(name) => {
+                        matches!(self.context.types.get(name), Some(EaType::Enum { .. }))
+                    }
+                    _ => false,
+                };
+                if !is_user_type {
+                    return Err(CompileError::type_error(
+                        format!(
+                            "Inherent impl for '{}' requires a struct or enum declared in this program",
+                            type_name
+                        ),
+                        Position::new(0, 0, 0),
+                    ));
+                }
+                None
+            }
+        };
+
+        // `Self` names the implementing type inside the impl
+        let saved_self = self.type_bindings.insert("Self".to_string(), self_type);
+        let result = self.check_impl_methods(
+            trait_methods
+                .as_ref()
+                .map(|(name, methods)| (*name, methods.as_slice())),
+            &type_key,
+            methods,
+        );
+        match saved_self {
+            Some(previous) => self.type_bindings.insert("Self".to_string(), previous),
+            None => self.type_bindings.remove("Self"),
+        };
+        result
+    }
+
+    fn check_impl_methods(
+        &mut self,
+        implemented_trait: Option<(&str, &[TraitMethod])>,
+        type_key: &str,
+        methods: &[Stmt],
+    ) -> Result<()> {
+        let impl_description = match implemented_trait {
+            Some((trait_name, _)) => format!("impl of trait '{}' for '{}'", trait_name, type_key),
+            None => format!("impl of '{}'", type_key),
+        };
+
+        // Signatures are registered before any body is checked so methods can call each other
+        let mut defined = HashSet::new();
+        let mut provided: HashMap<String, MethodSignature> = HashMap::new();
+        for method in methods {
+            let Stmt::FunctionDeclaration {
+                name,
+                type_params,
+                params,
+                return_type,
+                ..
+            } = method
+            else {
+                continue;
+            };
+
+            if !type_params.is_empty() {
+                return Err(CompileError::type_error(
+                    format!("Method '{}' in {} cannot have type parameters", name, impl_description),
+                    Position::new(0, 0, 0),
+                ));
+            }
+            if !defined.insert(name.clone()) {
+                return Err(CompileError::type_error(
+                    format!("Duplicate method '{}' in {}", name, impl_description),
+                    Position::new(0, 0, 0),
+                ));
+            }
+
+            let func_type = self.signature_type(params, return_type)?;
+            if let Some((trait_name, trait_methods)) = implemented_trait {
+                let declared = trait_methods
+                    .iter()
+                    .find(|declared| declared.name == *name)
+                    .ok_or_else(|| {
+                        CompileError::type_error(
+                            format!("Method '{}' is not a member of trait '{}'", name, trait_name),
+                            Position::new(0, 0, 0),
+                        )
+                    })?;
+                let expected = self.signature_type(&declared.params, &declared.return_type)?;
+                if func_type != expected {
+                    return Err(CompileError::type_error(
+                        format!(
+                            "Method '{}' in {} has type {}, but the trait declares {}",
+                            name, impl_description, func_type, expected
+                        ),
+                        Position::new(0, 0, 0),
+                    ));
+                }
+            }
+
+            // Functions without a receiver are only reachable as `Type::name(...)`
+            if params.first().map_or(false, |param| param.name == "self") {
+                provided.insert(
+                    name.clone(),
+                    MethodSignature {
+                        trait_name: implemented_trait.map(|(trait_name, _)| trait_name.to_string()),
+                        func_type,
+                    },
+                );
+            }
+        }
+
+        if let Some((_, trait_methods)) = implemented_trait {
+            for declared in trait_methods {
+                if !defined.contains(&declared.name) {
+                    return Err(CompileError::type_error(
+                        format!("Missing method '{}' in {}", declared.name, impl_description),
+                        Position::new(0, 0, 0),
+                    ));
+                }
+            }
+        }
+
+        let type_methods = self.methods.entry(type_key.to_string()).or_default();
+        for (name, signature) in provided {
+            if let Some(existing) = type_methods.get(&name) {
+                let existing_source = match &existing.trait_name {
+                    Some(trait_name) => format!("trait '{}'", trait_name),
+                    None => "an inherent impl".to_string(),
+                };
+                return Err(CompileError::type_error(
+                    format!(
+                        "Method '{}' of '{}' is already provided by {}",
+                        name, type_key, existing_source
+                    ),
+                    Position::new(0, 0, 0),
+                ));
+            }
+            type_methods.insert(name, signature);
+        }
+
+        // Every function of the block is also callable as `Type::name(...)`
+        for method in methods {
+            if let Stmt::FunctionDeclaration {
+                name,
+                params,
+                return_type,
+                body,
+                ..
+            } = method
+            {
+                let qualified_name = format!("{}::{}", type_key, name);
+                if implemented_trait.is_none() && self.context.functions.contains_key(&qualified_name) {
+                    return Err(CompileError::type_error(
+                        format!("Function '{}' is already defined", qualified_name),
+                        Position::new(0, 0, 0),
+                    ));
+                }
+                self.check_function_declaration(&qualified_name, params, return_type, body)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Resolves the parameter and return annotations of a signature.
+    fn signature_type(
+        &mut self,
+        params: &[crate::ast::Parameter],
+        return_type: &Option<TypeAnnotation>,
+    ) -> Result<FunctionType> {
+        let mut param_types = Vec::new();
+        for param in params {
+            param_types.push(self.annotation_to_type(&param.type_annotation)?);
+        }
+        let return_type = match return_type {
+            Some(type_ann) => self.annotation_to_type(type_ann)?,
+            None => EaType::Unit,
+        };
+        Ok(FunctionType {
+            params: param_types,
+            return_type: Box::new(return_type),
+            is_variadic: false,
+        })
+    }
+
+    fn check_function_declaration(
+        &mut self,
+        name: &str,
+        params: &[crate::ast::Parameter],
+        return_type: &Option<TypeAnnotation>,
+        body: &Box<Stmt>,
+    ) -> Result<()> {
+        let mut param_types = Vec::new();
+        for param in params {
+            let param_type = self.annotation_to_type(&param.type_annotation)?;
+            param_types.push(param_type);
+        }
+
+        let return_ea_type = match return_type {
+            Some(type_ann) => self.annotation_to_type(type_ann)?,
+            None => EaType::Unit,
+        };
+
+        let func_type = FunctionType {
+            params: param_types.clone(),
+            return_type: Box::new(return_ea_type.clone()),
+            is_variadic: false,
+        };
+
+        self.context.define_function(name.to_string(), func_type);
+
+        let mut function_context = self.context.enter_scope();
+        function_context.current_function_return = Some(return_ea_type.clone());
+
+        for (param, param_type) in params.iter().zip(param_types.iter()) {
+            if param.type_annotation.is_mutable {
+                function_context.define_mutable_variable(param.name.clone(), param_type.clone());
+            } else {
+                function_context.define_variable(param.name.clone(), param_type.clone());
+            }
+        }
+
+        let old_context = std::mem::replace(&mut self.context, function_context);
+        let result = self.check_statement(body);
+
+        // Check if function with non-unit return type actually returns a value
+        if result.is_ok() && !matches!(return_ea_type, EaType::Unit) {
+            if !self.statement_returns(body) {
+                self.context = old_context;
+                return Err(CompileError::type_error(
+                    format!(
+                        "Function '{}' with return type {:?} is missing a return statement",
+                        name, return_ea_type
+                    ),
+                    Position::new(0, 0, 0),
+                ));
+            }
+        }
+
+        self.context = old_context;
+        result
+    }
+
+    fn check_var_declaration(
+        &mut self,
+        pattern: &Pattern,
+        is_mutable: bool,
+        type_annotation: &Option<TypeAnnotation>,
+        initializer: &Option<Expr>,
+    ) -> Result<()> {
+        let var_type = match (type_annotation, initializer) {
+            (Some(type_ann), Some(init)) => {
+                let declared_type = self.annotation_to_type(type_ann)?;
+                let init_type = self.check_expression(init)?;
+
+                if !self.types_compatible(&declared_type, &init_type) {
+                    return Err(CompileError::type_error(
+                        format!(
+                            "Type mismatch in variable '{}': declared as {:?}, initialized with {:?}",
+                            pattern, declared_type, init_type
+                        ),
+                        Position::new(0, 0, 0),
+                    ));
+                }
+
+                declared_type
             }
             (Some(type_ann), None) => self.annotation_to_type(type_ann)?,
             (None, Some(init)) => self.check_expression(init)?,
@@ -1005,15 +2240,62 @@ impl TypeChecker {
                 return Err(CompileError::type_error(
                     format!(
                         "Variable '{}' must have either a type annotation or an initializer",
-                        name
+                        pattern
                     ),
                     Position::new(0, 0, 0),
                 ));
             }
         };
 
-        self.context.define_variable(name.to_string(), var_type);
-        Ok(())
+        self.bind_pattern(pattern, var_type, is_mutable)
+    }
+
+    /// Defines the variables a `let` binds, destructuring tuples element by element.
+    fn bind_pattern(
+        &mut self,
+        pattern: &Pattern,
+        var_type: EaType,
+        is_mutable: bool,
+    ) -> Result<()> {
+        match (pattern, var_type) {
+            (Pattern::Variable(name), var_type) => {
+                // A local variable shadows a constant of the same name
+                self.context.constants.remove(name);
+                if is_mutable {
+                    self.context.define_mutable_variable(name.clone(), var_type);
+                } else {
+                    self.context.define_variable(name.clone(), var_type);
+                }
+                Ok(())
+            }
+            (Pattern::Wildcard, _) => Ok(()),
+            (Pattern::Tuple { patterns }, EaType::Tuple(element_types))
+                if patterns.len() == element_types.len() =>
+            {
+                for (pattern, element_type) in patterns.iter().zip(element_types) {
+                    self.bind_pattern(pattern, element_type, is_mutable)?;
+                }
+                Ok(())
+            }
+            (Pattern::Tuple { patterns }, EaType::Error) => {
+                for pattern in patterns {
+                    self.bind_pattern(pattern, EaType::Error, is_mutable)?;
+                }
+                Ok(())
+            }
+            (Pattern::Tuple { patterns }, var_type) => Err(CompileError::type_error(
+                format!(
+                    "Cannot destructure a value of type {} into a tuple of {} elements",
+                    var_type,
+                    patterns.len()
+                ),
+                Position::new(0, 0, 0),
+            )),
+            (pattern, _) => Err(CompileError::type_error(
+                format!("Pattern '{}' cannot be used in a let binding", pattern),
+                Position::new(0, 0, 0),
+            )),
+        }
     }
 
     fn check_return_statement(&mut self, expr: &Option<Expr>) -> Result<()> {
@@ -1040,15 +2322,15 @@ impl TypeChecker {
     /// Check if a statement guarantees a return (control flow analysis)
     fn statement_returns(&self, stmt: &Stmt) -> bool {
         match stmt {
-            Stmt::Return(_) => true,
-            Stmt::Block(stmts) => {
+            Stmt::Return(..) => true,
+            Stmt::Block(stmts, _) => {
                 // A block returns if any statement in it returns
                 stmts.iter().any(|s| self.statement_returns(s))
             }
             Stmt::If {
-                condition: _,
                 then_branch,
                 else_branch,
+                ..
             } => {
                 // If statement returns if both branches return
                 if let Some(else_stmt) = else_branch {
@@ -1057,27 +2339,15 @@ impl TypeChecker {
                     false // If without else cannot guarantee return
                 }
             }
-            Stmt::While {
-                condition: _,
-                body: _,
-            } => {
+            Stmt::While { .. } => {
                 // While loop cannot guarantee return (might not execute)
                 false
             }
-            Stmt::For {
-                initializer: _,
-                condition: _,
-                increment: _,
-                body: _,
-            } => {
+            Stmt::For { .. } => {
                 // For loop cannot guarantee return (might not execute)
                 false
             }
-            Stmt::ForIn {
-                variable: _,
-                iterable: _,
-                body: _,
-            } => {
+            Stmt::ForIn { .. } => {
                 // For-in loop cannot guarantee return (might not execute)
                 false
             }
@@ -1091,9 +2361,11 @@ impl TypeChecker {
 
         let mut result = Ok(());
         for stmt in stmts {
+            // The error is recorded, so keep checking the rest of the block
             if let Err(e) = self.check_statement(stmt) {
-                result = Err(e);
-                break;
+                if result.is_ok() {
+                    result = Err(e);
+                }
             }
         }
 
@@ -1108,32 +2380,35 @@ impl TypeChecker {
         else_branch: &Option<Box<Stmt>>,
     ) -> Result<()> {
         let condition_type = self.check_expression(condition)?;
-        if !matches!(condition_type, EaType::Bool) {
+        if !matches!(condition_type, EaType::Bool | EaType::Error) {
             return Err(CompileError::type_error(
                 format!("If condition must be boolean, got {:?}", condition_type),
                 Position::new(0, 0, 0),
             ));
         }
 
-        self.check_statement(then_branch)?;
+        let then_result = self.check_statement(then_branch);
 
         if let Some(else_stmt) = else_branch {
-            self.check_statement(else_stmt)?;
+            // Check the else branch even if the then branch had errors
+            let else_result = self.check_statement(else_stmt);
+            then_result.and(else_result)
+        } else {
+            then_result
         }
-
-        Ok(())
     }
 
     fn check_while_statement(&mut self, condition: &Expr, body: &Box<Stmt>) -> Result<()> {
+        let label = self.pending_loop_label.take();
         let condition_type = self.check_expression(condition)?;
-        if !matches!(condition_type, EaType::Bool) {
+        if !matches!(condition_type, EaType::Bool | EaType::Error) {
             return Err(CompileError::type_error(
                 format!("While condition must be boolean, got {:?}", condition_type),
                 Position::new(0, 0, 0),
             ));
         }
 
-        self.check_statement(body)
+        self.check_loop_body(label, body)
     }
 
     fn check_for_statement(
@@ -1143,6 +2418,7 @@ impl TypeChecker {
         increment: &Option<Expr>,
         body: &Box<Stmt>,
     ) -> Result<()> {
+        let label = self.pending_loop_label.take();
         let loop_context = self.context.enter_scope();
         let old_context = std::mem::replace(&mut self.context, loop_context);
 
@@ -1158,7 +2434,7 @@ impl TypeChecker {
             if let Some(cond) = condition {
                 match self.check_expression(cond) {
                     Ok(condition_type) => {
-                        if !matches!(condition_type, EaType::Bool) {
+                        if !matches!(condition_type, EaType::Bool | EaType::Error) {
                             result = Err(CompileError::type_error(
                                 format!("For condition must be boolean, got {:?}", condition_type),
                                 Position::new(0, 0, 0),
@@ -1179,7 +2455,7 @@ impl TypeChecker {
         }
 
         if result.is_ok() {
-            result = self.check_statement(body);
+            result = self.check_loop_body(label, body);
         }
 
         self.context = old_context;
@@ -1192,12 +2468,15 @@ impl TypeChecker {
         iterable: &Expr,
         body: &Box<Stmt>,
     ) -> Result<()> {
+        let label = self.pending_loop_label.take();
+
         // Check the iterable expression type
         let iterable_type = self.check_expression(iterable)?;
 
         // Ensure the iterable is an array type
         let element_type = match iterable_type {
-            EaType::Array(element_type) => *element_type,
+            EaType::Array(element_type) | EaType::FixedArray(element_type, _) => *element_type,
+            EaType::Error => EaType::Error,
             _ => {
                 return Err(CompileError::type_error(
                     format!("For-in loop requires array type, got {:?}", iterable_type),
@@ -1215,7 +2494,7 @@ impl TypeChecker {
             .define_variable(variable.to_string(), element_type);
 
         // Check the body
-        let result = self.check_statement(body);
+        let result = self.check_loop_body(label, body);
 
         // Restore the previous context
         self.context = old_context;
@@ -1223,26 +2502,153 @@ impl TypeChecker {
         result
     }
 
+    /// Checks a loop body with the loop registered as a `break`/`continue` target.
+    fn check_loop_body(&mut self, label: Option<String>, body: &Stmt) -> Result<()> {
+        self.loop_labels.push(label);
+        let result = self.check_statement(body);
+        self.loop_labels.pop();
+        result
+    }
+
+    fn check_labeled_loop(&mut self, label: &str, body: &Stmt) -> Result<()> {
+        if !matches!(body, Stmt::While { .. } | Stmt::For { .. } | Stmt::ForIn { .. }) {
+            return Err(CompileError::type_error(
+                format!("Label '{} can only be applied to a loop", label),
+                Position::new(0, 0, 0),
+            ));
+        }
+
+        if self
+            .loop_labels
+            .iter()
+            .any(|enclosing| enclosing.as_deref() == Some(label))
+        {
+            return Err(CompileError::type_error(
+                format!("Label '{} shadows the label of an enclosing loop", label),
+                Position::new(0, 0, 0),
+            ));
+        }
+
+        self.pending_loop_label = Some(label.to_string());
+        self.check_statement(body)
+    }
+
+    /// Validates that `break`/`continue` appear inside a loop and name an enclosing label.
+    fn check_loop_control(&self, keyword: &str, label: &Option<String>) -> Result<()> {
+        if self.loop_labels.is_empty() {
+            return Err(CompileError::type_error(
+                format!("'{}' outside of a loop", keyword),
+                Position::new(0, 0, 0),
+            ));
+        }
+
+        if let Some(label) = label {
+            if !self
+                .loop_labels
+                .iter()
+                .any(|enclosing| enclosing.as_deref() == Some(label.as_str()))
+            {
+                return Err(CompileError::type_error(
+                    format!("Use of undeclared loop label '{}' in '{}'", label, keyword),
+                    Position::new(0, 0, 0),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks `expr`, recording any error and poisoning the expression with
+    /// `EaType::Error` so that checking can continue around it.
     pub fn check_expression(&mut self, expr: &Expr) -> Result<EaType> {
+        let reported = self.errors.len();
+        match self.check_expression_node(expr) {
+            Ok(expr_type) => {
+                self.record_signedness(expr, &expr_type);
+                Ok(expr_type)
+            }
+            Err(error) => {
+                self.report(reported, error.with_span(expr.span()));
+                Ok(EaType::Error)
+            }
+        }
+    }
+
+    /// Remembers where unsigned integers are computed. LLVM integers carry no
+    /// sign, so code generation looks the expression up to pick the unsigned
+    /// form of overflow checks and saturation. Inside a generic function the
+    /// instance is part of the key, as `T` may be unsigned in only some of them.
+    fn record_signedness(&mut self, expr: &Expr, expr_type: &EaType) {
+        let span = expr.span();
+        if span.is_known() && crate::ast::is_unsigned_type_name(&expr_type.to_string()) {
+            self.expressions
+                .unsigned
+                .insert((self.current_instance.clone(), span.range()));
+        }
+    }
+
+    /// Remembers the type arguments inferred for the generic call or struct
+    /// literal at `span`, so that code generation instantiates the same items.
+    fn record_type_arguments(&mut self, span: &Span, type_args: &[EaType]) {
+        if span.is_known() {
+            self.expressions.type_arguments.insert(
+                (self.current_instance.clone(), span.range()),
+                type_args.iter().map(|ty| ty.to_string()).collect(),
+            );
+        }
+    }
+
+    fn check_expression_node(&mut self, expr: &Expr) -> Result<EaType> {
         match expr {
-            Expr::Literal(lit) => Ok(self.literal_type(lit)),
-            Expr::Variable(name) => self.check_variable(name),
-            Expr::Binary(left, op, right) => self.check_binary_expression(left, op, right),
-            Expr::Unary(op, expr) => self.check_unary_expression(op, expr),
-            Expr::Call(callee, args) => self.check_function_call(callee, args),
-            Expr::Grouping(expr) => self.check_expression(expr),
-            Expr::Index(array, index) => self.check_index_expression(array, index),
-            Expr::Slice { array, start, end } => self.check_slice_expression(array, start, end),
-            Expr::FieldAccess(object, field) => self.check_field_access(object, field),
-            Expr::StructLiteral { name, fields } => self.check_struct_literal(name, fields),
+            Expr::Literal(lit, _) => Ok(self.literal_type(lit)),
+            Expr::Variable(name, _) => self.check_variable(name),
+            Expr::Binary(left, op, right, _) => self.check_binary_expression(left, op, right),
+            Expr::Unary(op, expr, _) => self.check_unary_expression(op, expr),
+            Expr::Call(callee, args, span) => self.check_function_call(callee, args, span),
+            Expr::Grouping(expr, _) => self.check_expression(expr),
+            Expr::Index(array, index, _) => self.check_index_expression(array, index),
+            Expr::Slice {
+                array, start, end, ..
+            } => self.check_slice_expression(array, start, end),
+            Expr::FieldAccess(object, field, _) => self.check_field_access(object, field),
+            Expr::StructLiteral { name, fields, span } => {
+                self.check_struct_literal(name, fields, span)
+            }
             Expr::EnumLiteral {
                 enum_name,
                 variant,
                 args,
+                ..
             } => self.check_enum_literal(enum_name, variant, args),
-            Expr::Match { value, arms } => self.check_match_expression(value, arms),
-            Expr::Block(statements) => self.check_block_expression(statements),
-            Expr::SIMD(simd_expr) => self.check_simd_expression(simd_expr),
+            Expr::Match { value, arms, .. } => self.check_match_expression(value, arms),
+            Expr::Block(statements, _) => self.check_block_expression(statements),
+            Expr::Closure {
+                params,
+                return_type,
+                body,
+                span,
+            } => self.check_closure(params, return_type, body, span),
+            Expr::Tuple { elements, .. } => {
+                let mut element_types = Vec::new();
+                for element in elements {
+                    element_types.push(self.check_expression(element)?);
+                }
+                Ok(EaType::Tuple(element_types))
+            }
+            Expr::ArrayRepeat {
+                value,
+                count,
+                vector_type,
+                ..
+            } => self.check_array_repeat(value, count, vector_type),
+            Expr::Comptime(statements, _) => self.check_comptime_block(statements),
+            Expr::Unsafe(statements, _) => {
+                self.unsafe_depth += 1;
+                let block_type = self.check_block_expression(statements);
+                self.unsafe_depth -= 1;
+                block_type
+            }
+            Expr::SIMD(simd_expr, _) => self.check_simd_expression(simd_expr),
         }
     }
 
@@ -1265,10 +2671,10 @@ impl TypeChecker {
                         EaType::Error
                     }
                 } else {
-                    // Regular array literal without SIMD type - create Array type
+                    // Regular array literal without SIMD type - its length is part of the type
                     if let Some(first_element) = elements.first() {
                         let element_type = self.literal_type(first_element);
-                        EaType::Array(Box::new(element_type))
+                        EaType::FixedArray(Box::new(element_type), elements.len())
                     } else {
                         EaType::Error
                     }
@@ -1278,11 +2684,18 @@ impl TypeChecker {
     }
 
     fn check_variable(&self, name: &str) -> Result<EaType> {
+        // A named function used as a value, e.g. `values.map(double)`
+        if self.context.get_variable_type(name).is_none() {
+            if let Some(func_type) = self.context.get_function_type(name) {
+                return Ok(EaType::Function(Box::new(func_type.clone())));
+            }
+        }
+
         self.context
             .get_variable_type(name)
             .cloned()
             .ok_or_else(|| {
-                CompileError::type_error(
+                CompileError::undefined_variable_error(
                     format!("Variable '{}' not found", name),
                     Position::new(0, 0, 0),
                 )
@@ -1295,9 +2708,53 @@ impl TypeChecker {
         op: &BinaryOp,
         right: &Box<Expr>,
     ) -> Result<EaType> {
+        if let (
+            Expr::Variable(name, _),
+            BinaryOp::Assign
+            | BinaryOp::PlusAssign
+            | BinaryOp::MinusAssign
+            | BinaryOp::MultiplyAssign
+            | BinaryOp::DivideAssign,
+        ) = (&**left, op)
+        {
+            if self.context.constants.contains_key(name) {
+                return Err(CompileError::type_error(
+                    format!("Cannot assign to constant '{}'", name),
+                    Position::new(0, 0, 0),
+                ));
+            }
+        }
+
+        if let (
+            Expr::Unary(UnaryOp::Deref, pointer, _),
+            BinaryOp::Assign
+            | BinaryOp::PlusAssign
+            | BinaryOp::MinusAssign
+            | BinaryOp::MultiplyAssign
+            | BinaryOp::DivideAssign,
+        ) = (&**left, op)
+        {
+            let pointer_type = self.check_expression(pointer)?;
+            if let EaType::Pointer {
+                is_mutable: false, ..
+            } = pointer_type
+            {
+                return Err(CompileError::type_error(
+                    format!("Cannot assign through immutable pointer {}", pointer_type),
+                    Position::new(0, 0, 0),
+                ));
+            }
+        }
+
         let left_type = self.check_expression(left)?;
         let right_type = self.check_expression(right)?;
 
+        // A poisoned operand has already been reported
+        if left_type == EaType::Error || right_type == EaType::Error {
+            return Ok(EaType::Error);
+        }
+        let left_type = self.literal_operand_type(left, left_type, &right_type);
+
         match op {
             BinaryOp::Add => {
                 // Handle String concatenation
@@ -1420,6 +2877,11 @@ impl TypeChecker {
     fn check_unary_expression(&mut self, op: &UnaryOp, expr: &Box<Expr>) -> Result<EaType> {
         let expr_type = self.check_expression(expr)?;
 
+        // A poisoned operand has already been reported
+        if expr_type == EaType::Error {
+            return Ok(EaType::Error);
+        }
+
         match op {
             UnaryOp::Negate => {
                 if self.is_numeric_type(&expr_type) {
@@ -1441,24 +2903,96 @@ impl TypeChecker {
                     ))
                 }
             }
-            UnaryOp::Reference => Ok(EaType::Reference(Box::new(expr_type))),
+            UnaryOp::Reference => Ok(EaType::Reference {
+                target: Box::new(expr_type),
+                is_mutable: self.is_mutable_place(expr),
+            }),
+            UnaryOp::Deref => match expr_type {
+                EaType::Pointer { target, .. } => {
+                    self.require_unsafe("Dereferencing a raw pointer")?;
+                    Ok(*target)
+                }
+                _ => Err(CompileError::type_error(
+                    format!("Cannot dereference non-pointer type {}", expr_type),
+                    Position::new(0, 0, 0),
+                )),
+            },
+        }
+    }
+
+    /// Whether `expr` names storage declared with `let mut`, or part of it.
+    fn is_mutable_place(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Variable(name, _) => self.context.is_mutable_variable(name),
+            Expr::Index(base, _, _) | Expr::FieldAccess(base, _, _) => self.is_mutable_place(base),
+            Expr::Grouping(inner, _) => self.is_mutable_place(inner),
+            _ => false,
+        }
+    }
+
+    /// Rejects raw pointer operations outside an `unsafe` block.
+    fn require_unsafe(&self, operation: &str) -> Result<()> {
+        if self.unsafe_depth == 0 {
+            return Err(CompileError::type_error(
+                format!("{} requires an unsafe block", operation),
+                Position::new(0, 0, 0),
+            ));
+        }
+        Ok(())
+    }
+
+    /// `p.offset(n)`: the pointer `n` elements past `p`.
+    fn check_pointer_offset(&mut self, pointer_type: &EaType, args: &[Expr]) -> Result<EaType> {
+        if args.len() != 1 {
+            return Err(CompileError::type_error(
+                format!("offset() takes exactly 1 argument, got {}", args.len()),
+                Position::new(0, 0, 0),
+            ));
         }
+        let count_type = self.check_expression(&args[0])?;
+        if !self.is_integer_type(&count_type) {
+            return Err(CompileError::type_error(
+                format!("offset() requires an integer argument, got {}", count_type),
+                Position::new(0, 0, 0),
+            ));
+        }
+        self.require_unsafe("Pointer arithmetic")?;
+        Ok(pointer_type.clone())
     }
 
-    fn check_function_call(&mut self, callee: &Box<Expr>, args: &[Expr]) -> Result<EaType> {
+    fn check_function_call(
+        &mut self,
+        callee: &Box<Expr>,
+        args: &[Expr],
+        span: &Span,
+    ) -> Result<EaType> {
         match &**callee {
             // Direct function call: func_name(args)
-            Expr::Variable(func_name) => self.check_direct_function_call(func_name, args),
+            Expr::Variable(func_name, _) => self.check_direct_function_call(func_name, args, span),
             // Method call: object.method(args) or Type::method(args)
-            Expr::FieldAccess(base, method_name) => self.check_method_call(base, method_name, args),
-            _ => Err(CompileError::type_error(
-                "Only direct function calls and method calls are supported".to_string(),
-                Position::new(0, 0, 0),
-            )),
+            Expr::FieldAccess(base, method_name, _) => {
+                self.check_method_call(base, method_name, args, span)
+            }
+            // Calls through any other expression need it to be a function value
+            _ => match self.check_expression(callee)? {
+                EaType::Function(func_type) => {
+                    self.check_call_arguments(&callee.to_string(), &func_type, args)
+                }
+                EaType::Error => Ok(EaType::Error),
+                other => Err(CompileError::type_error(
+                    format!("Cannot call a value of type {}", other),
+                    Position::new(0, 0, 0),
+                )),
+            },
         }
     }
 
-    fn check_direct_function_call(&mut self, func_name: &str, args: &[Expr]) -> Result<EaType> {
+    fn check_direct_function_call(
+        &mut self,
+        func_name: &str,
+        args: &[Expr],
+        span: &Span,
+    ) -> Result<EaType> {
         // Real type inference for Result constructors (not placeholder)
         if func_name == "Ok" {
             if args.len() != 1 {
@@ -1498,43 +3032,460 @@ impl TypeChecker {
             });
         }
         
-        // Clone the function type to avoid borrowing issues
-        if let Some(func_type) = self.context.get_function_type(func_name).cloned() {
-            if args.len() != func_type.params.len() {
+        if let Some(generic) = self.generic_functions.get(func_name).cloned() {
+            return self.check_generic_function_call(func_name, &generic, args, span);
+        }
+
+        // Builtins unless a function or value of the same name shadows them
+        if crate::ast::OVERFLOW_ARITHMETIC_BUILTINS.contains(&func_name)
+            && self.context.get_function_type(func_name).is_none()
+            && self.context.get_variable_type(func_name).is_none()
+        {
+            return self.check_overflow_arithmetic_builtin(func_name, args);
+        }
+
+        if self.comptime_depth == 0 && self.comptime_functions.contains_key(func_name) {
+            return Err(CompileError::type_error(
+                format!(
+                    "comptime function '{}' can only be called in a comptime block, a const initializer or another comptime function",
+                    func_name
+                ),
+                Position::new(0, 0, 0),
+            ));
+        }
+
+        // A local closure or function value shadows a function of the same name
+        let func_type = match self.context.get_variable_type(func_name) {
+            Some(EaType::Function(func_type)) => Some((**func_type).clone()),
+            Some(EaType::Error) => return Ok(EaType::Error),
+            _ => self.context.get_function_type(func_name).cloned(),
+        };
+        if let Some(func_type) = func_type {
+            self.check_call_arguments(func_name, &func_type, args)
+        } else {
+            Err(CompileError::undefined_function_error(
+                format!("Function '{}' not found", func_name),
+                Position::new(0, 0, 0),
+            ))
+        }
+    }
+
+    /// `wrapping_add(a, b)` and the other overflow arithmetic builtins take two
+    /// operands of one integer or integer SIMD vector type and return that type.
+    fn check_overflow_arithmetic_builtin(&mut self, func_name: &str, args: &[Expr]) -> Result<EaType> {
+        if args.len() != 2 {
+            return Err(CompileError::type_error(
+                format!("{}() takes exactly 2 arguments, got {}", func_name, args.len()),
+                Position::new(0, 0, 0),
+            ));
+        }
+        let left_type = self.check_expression(&args[0])?;
+        let right_type = self.check_expression(&args[1])?;
+        if left_type == EaType::Error || right_type == EaType::Error {
+            return Ok(EaType::Error);
+        }
+        let left_type = self.literal_operand_type(&args[0], left_type, &right_type);
+
+        let is_integer = match &left_type {
+            EaType::SIMDVector { element_type, .. } => {
+                self.is_integer_type(&element_type.to_ea_type())
+            }
+            other => self.is_integer_type(other),
+        };
+        if !is_integer || !self.types_compatible(&left_type, &right_type) {
+            return Err(CompileError::type_error(
+                format!(
+                    "{}() requires two integer or integer vector operands of the same type, got {} and {}",
+                    func_name, left_type, right_type
+                ),
+                Position::new(0, 0, 0),
+            ));
+        }
+        Ok(left_type)
+    }
+
+    /// Checks the arguments of a call against `func_type` and returns its result type.
+    fn check_call_arguments(
+        &mut self,
+        func_name: &str,
+        func_type: &FunctionType,
+        args: &[Expr],
+    ) -> Result<EaType> {
+        if args.len() != func_type.params.len() {
+            return Err(CompileError::type_error(
+                format!(
+                    "Function '{}' expects {} arguments, got {}",
+                    func_name,
+                    func_type.params.len(),
+                    args.len()
+                ),
+                Position::new(0, 0, 0),
+            ));
+        }
+
+        for (i, (arg, expected_type)) in args.iter().zip(func_type.params.iter()).enumerate() {
+            let arg_type = self.check_expression(arg)?;
+            if !self.types_compatible(expected_type, &arg_type) {
                 return Err(CompileError::type_error(
                     format!(
-                        "Function '{}' expects {} arguments, got {}",
+                        "Argument {} of function '{}': expected {:?}, got {:?}",
+                        i + 1,
                         func_name,
-                        func_type.params.len(),
-                        args.len()
+                        expected_type,
+                        arg_type
                     ),
                     Position::new(0, 0, 0),
                 ));
             }
+        }
+
+        Ok(*func_type.return_type.clone())
+    }
+
+    /// Checks a closure. Its body sees the enclosing scope, which is how it
+    /// captures variables, plus its own parameters.
+    fn check_closure(
+        &mut self,
+        params: &[crate::ast::Parameter],
+        return_type: &Option<TypeAnnotation>,
+        body: &Expr,
+        span: &Span,
+    ) -> Result<EaType> {
+        let mut param_types = Vec::new();
+        for param in params {
+            param_types.push(self.annotation_to_type(&param.type_annotation)?);
+        }
+        let declared_return = match return_type {
+            Some(type_ann) => Some(self.annotation_to_type(type_ann)?),
+            None => None,
+        };
 
-            for (i, (arg, expected_type)) in args.iter().zip(func_type.params.iter()).enumerate() {
-                let arg_type = self.check_expression(arg)?;
-                if !self.types_compatible(expected_type, &arg_type) {
+        let mut closure_context = self.context.enter_scope();
+        for (param, param_type) in params.iter().zip(param_types.iter()) {
+            closure_context.define_variable(param.name.clone(), param_type.clone());
+        }
+        closure_context.current_function_return = declared_return.clone();
+        let enclosing_context = std::mem::replace(&mut self.context, closure_context);
+        // `break` and `continue` cannot leave the closure body
+        let enclosing_loops = std::mem::take(&mut self.loop_labels);
+
+        let body_type = self.check_expression(body);
+
+        self.context = enclosing_context;
+        self.loop_labels = enclosing_loops;
+        let body_type = body_type?;
+
+        let return_type = match declared_return {
+            Some(declared) => {
+                let returns_explicitly = matches!(body, Expr::Block(stmts, _)
+                    if stmts.iter().any(|stmt| self.statement_returns(stmt)));
+                if !returns_explicitly && !self.types_compatible(&declared, &body_type) {
                     return Err(CompileError::type_error(
                         format!(
-                            "Argument {} of function '{}': expected {:?}, got {:?}",
-                            i + 1,
-                            func_name,
-                            expected_type,
-                            arg_type
+                            "Closure declared to return {}, but its body has type {}",
+                            declared, body_type
                         ),
                         Position::new(0, 0, 0),
                     ));
                 }
+                declared
+            }
+            None => {
+                if span.is_known() {
+                    self.expressions.closure_results.insert(
+                        (self.current_instance.clone(), span.range()),
+                        body_type.to_string(),
+                    );
+                }
+                body_type
+            }
+        };
+
+        Ok(EaType::Function(Box::new(FunctionType {
+            params: param_types,
+            return_type: Box::new(return_type),
+            is_variadic: false,
+        })))
+    }
+
+    /// Checks a call to a generic function against the instantiation for the
+    /// type arguments inferred from the call's arguments.
+    fn check_generic_function_call(
+        &mut self,
+        func_name: &str,
+        generic: &GenericFunction,
+        args: &[Expr],
+        span: &Span,
+    ) -> Result<EaType> {
+        if args.len() != generic.params.len() {
+            return Err(CompileError::type_error(
+                format!(
+                    "Function '{}' expects {} arguments, got {}",
+                    func_name,
+                    generic.params.len(),
+                    args.len()
+                ),
+                Position::new(0, 0, 0),
+            ));
+        }
+
+        let mut uses = Vec::new();
+        for (param, arg) in generic.params.iter().zip(args.iter()) {
+            let arg_type = self.check_expression(arg)?;
+            uses.push((param.type_annotation.name.as_str(), arg, arg_type));
+        }
+
+        let type_args = self.infer_type_arguments(func_name, &generic.type_params, &uses)?;
+        self.record_type_arguments(span, &type_args);
+        let func_type = self.instantiate_generic_function(func_name, generic, &type_args)?;
+
+        for (i, ((_, _, arg_type), expected_type)) in
+            uses.iter().zip(func_type.params.iter()).enumerate()
+        {
+            if !self.types_compatible(expected_type, arg_type) {
+                return Err(CompileError::type_error(
+                    format!(
+                        "Argument {} of function '{}': expected {:?}, got {:?}",
+                        i + 1,
+                        func_name,
+                        expected_type,
+                        arg_type
+                    ),
+                    Position::new(0, 0, 0),
+                ));
+            }
+        }
+
+        Ok(*func_type.return_type)
+    }
+
+    /// Infers the type arguments of `item` from `(declared type, expression, actual type)` uses.
+    ///
+    /// Typed expressions are matched first; integer literals default to i32 only
+    /// for parameters nothing else has fixed.
+    fn infer_type_arguments(
+        &mut self,
+        item: &str,
+        type_params: &[String],
+        uses: &[(&str, &Expr, EaType)],
+    ) -> Result<Vec<EaType>> {
+        let mut bindings = HashMap::new();
+        let mut known_types = HashMap::new();
+
+        for literal_pass in [false, true] {
+            for (declared, expr, actual) in uses {
+                let is_literal = Self::is_integer_literal(expr);
+                if is_literal != literal_pass {
+                    continue;
+                }
+
+                let concrete = if is_literal { EaType::I32 } else { actual.clone() };
+                let concrete_name = concrete.to_string();
+                known_types.insert(concrete_name.clone(), concrete);
+
+                if let Err(param) =
+                    crate::ast::bind_type_params(type_params, declared, &concrete_name, &mut bindings)
+                {
+                    // A literal that disagrees is reported by the argument check instead
+                    if !literal_pass {
+                        return Err(CompileError::type_error(
+                            format!(
+                                "Conflicting types for type parameter '{}' of '{}': {} and {}",
+                                param, item, bindings[&param], concrete_name
+                            ),
+                            Position::new(0, 0, 0),
+                        ));
+                    }
+                }
             }
+        }
+
+        let mut type_args = Vec::new();
+        for param in type_params {
+            let bound = bindings.get(param).ok_or_else(|| {
+                CompileError::type_error(
+                    format!("Cannot infer type parameter '{}' of '{}'", param, item),
+                    Position::new(0, 0, 0),
+                )
+            })?;
+            let type_arg = match known_types.get(bound) {
+                Some(known) => known.clone(),
+                None => self.annotation_to_type(&TypeAnnotation {
+                    name: bound.clone(),
+                    is_mutable: false,
+                })?,
+            };
+            type_args.push(type_arg);
+        }
 
-            Ok(*func_type.return_type.clone())
+        Ok(type_args)
+    }
+
+    /// An integer literal on the left of `1 + x` takes the type of the integer
+    /// on the right, as one on the right of `x + 1` already does.
+    fn literal_operand_type(&self, left: &Expr, left_type: EaType, right_type: &EaType) -> EaType {
+        if Self::is_integer_literal(left) && self.is_integer_type(right_type) {
+            right_type.clone()
         } else {
-            Err(CompileError::type_error(
-                format!("Function '{}' not found", func_name),
+            left_type
+        }
+    }
+
+    fn is_integer_literal(expr: &Expr) -> bool {
+        match expr {
+            Expr::Literal(Literal::Integer(_), _) => true,
+            Expr::Unary(_, inner, _) | Expr::Grouping(inner, _) => Self::is_integer_literal(inner),
+            _ => false,
+        }
+    }
+
+    /// Returns the signature of `name<type_args>`, checking its body the first
+    /// time the instantiation is used.
+    fn instantiate_generic_function(
+        &mut self,
+        name: &str,
+        generic: &GenericFunction,
+        type_args: &[EaType],
+    ) -> Result<FunctionType> {
+        let arg_names: Vec<String> = type_args.iter().map(|ty| ty.to_string()).collect();
+        // Instances of imported functions are named after their declaring module
+        let base_name = match &generic.module {
+            Some(module) => format!("{}::{}", module, generic.name),
+            None => name.to_string(),
+        };
+        let instance_name = crate::ast::generic_instance_name(&base_name, &arg_names);
+        if let Some(func_type) = self.function_instances.get(&instance_name) {
+            return Ok(func_type.clone());
+        }
+
+        let bindings = generic
+            .type_params
+            .iter()
+            .cloned()
+            .zip(type_args.iter().cloned())
+            .collect();
+        let saved_bindings = std::mem::replace(&mut self.type_bindings, bindings);
+        let saved_instance = std::mem::replace(&mut self.current_instance, instance_name.clone());
+        // Functions imported from another module see the items of that module
+        let declaring_scope = generic
+            .module
+            .as_ref()
+            .and_then(|module| self.module_scopes.get(module))
+            .cloned();
+        let caller_scope = declaring_scope.map(|scope| self.replace_scope(scope));
+        let result = self.check_function_instance(&instance_name, generic);
+        if let Some(scope) = caller_scope {
+            self.replace_scope(scope);
+        }
+        self.current_instance = saved_instance;
+        self.type_bindings = saved_bindings;
+        result
+    }
+
+    fn check_function_instance(
+        &mut self,
+        instance_name: &str,
+        generic: &GenericFunction,
+    ) -> Result<FunctionType> {
+        let mut param_types = Vec::new();
+        for param in &generic.params {
+            param_types.push(self.annotation_to_type(&param.type_annotation)?);
+        }
+        let return_type = match &generic.return_type {
+            Some(type_ann) => self.annotation_to_type(type_ann)?,
+            None => EaType::Unit,
+        };
+        let func_type = FunctionType {
+            params: param_types,
+            return_type: Box::new(return_type),
+            is_variadic: false,
+        };
+
+        // Registered before the body is checked so recursive calls find it
+        self.function_instances
+            .insert(instance_name.to_string(), func_type.clone());
+
+        // The body sees the declaration's scope, not the caller's locals or loops
+        let mut instance_context = self.context.clone();
+        instance_context.variables.clear();
+        let caller_context = std::mem::replace(&mut self.context, instance_context);
+        let caller_loops = std::mem::take(&mut self.loop_labels);
+
+        let result = self.check_function_declaration(
+            instance_name,
+            &generic.params,
+            &generic.return_type,
+            &generic.body,
+        );
+
+        self.context = caller_context;
+        self.loop_labels = caller_loops;
+        result.map(|_| func_type)
+    }
+
+    /// Registers the fields of `base<type_args>` and returns the instance name.
+    fn instantiate_generic_struct(
+        &mut self,
+        base: &str,
+        generic: &GenericStruct,
+        type_args: &[EaType],
+    ) -> Result<String> {
+        if type_args.len() != generic.type_params.len() {
+            return Err(CompileError::type_error(
+                format!(
+                    "Struct '{}' expects {} type arguments, got {}",
+                    base,
+                    generic.type_params.len(),
+                    type_args.len()
+                ),
                 Position::new(0, 0, 0),
-            ))
+            ));
+        }
+
+        let arg_names: Vec<String> = type_args.iter().map(|ty| ty.to_string()).collect();
+        let instance_name = crate::ast::generic_instance_name(base, &arg_names);
+        if self.struct_instances.contains_key(&instance_name) {
+            return Ok(instance_name);
+        }
+
+        // Placeholder first so self-referencing fields resolve to the instance
+        self.struct_instances
+            .insert(instance_name.clone(), HashMap::new());
+
+        let bindings = generic
+            .type_params
+            .iter()
+            .cloned()
+            .zip(type_args.iter().cloned())
+            .collect();
+        let saved_bindings = std::mem::replace(&mut self.type_bindings, bindings);
+        let mut field_types = HashMap::new();
+        let mut result = Ok(());
+        for field in &generic.fields {
+            match self.annotation_to_type(&field.type_annotation) {
+                Ok(field_type) => {
+                    field_types.insert(field.name.clone(), field_type);
+                }
+                Err(e) => {
+                    result = Err(e);
+                    break;
+                }
+            }
         }
+        self.type_bindings = saved_bindings;
+        result?;
+
+        self.struct_instances
+            .insert(instance_name.clone(), field_types);
+        Ok(instance_name)
+    }
+
+    /// Fields of a declared struct or of an instantiated generic struct.
+    fn struct_fields(&self, name: &str) -> Option<&HashMap<String, EaType>> {
+        self.context
+            .structs
+            .get(name)
+            .or_else(|| self.struct_instances.get(name))
     }
 
     fn check_method_call(
@@ -1542,39 +3493,56 @@ impl TypeChecker {
         base: &Box<Expr>,
         method_name: &str,
         args: &[Expr],
+        span: &Span,
     ) -> Result<EaType> {
         match &**base {
+            // Qualified call into an imported module: math::dot(a, b)
+            Expr::Variable(module_alias, _)
+                if self
+                    .context
+                    .functions
+                    .contains_key(&format!("{}::{}", module_alias, method_name))
+                    || self
+                        .generic_functions
+                        .contains_key(&format!("{}::{}", module_alias, method_name)) =>
+            {
+                let func_name = format!("{}::{}", module_alias, method_name);
+                self.check_direct_function_call(&func_name, args, span)
+            }
             // Static method call: Vec::new(), HashMap::new(), etc.
-            Expr::Variable(type_name) if type_name == "Vec" => {
+            Expr::Variable(type_name, _) if type_name == "Vec" => {
                 self.check_vec_static_method(method_name, args)
             }
-            Expr::Variable(type_name) if type_name == "HashMap" => {
+            Expr::Variable(type_name, _) if type_name == "HashMap" => {
                 self.check_hashmap_static_method(method_name, args)
             }
-            Expr::Variable(type_name) if type_name == "HashSet" => {
+            Expr::Variable(type_name, _) if type_name == "HashSet" => {
                 self.check_hashset_static_method(method_name, args)
             }
-            Expr::Variable(type_name) if type_name == "String" => {
+            Expr::Variable(type_name, _) if type_name == "String" => {
                 self.check_string_static_method(method_name, args)
             }
-            Expr::Variable(type_name) if type_name == "File" => {
+            Expr::Variable(type_name, _) if type_name == "File" => {
                 self.check_file_static_method(method_name, args)
             }
-            Expr::Variable(type_name) if type_name == "Package" => {
+            Expr::Variable(type_name, _) if type_name == "Package" => {
                 self.check_package_static_method(method_name, args)
             }
-            Expr::Variable(type_name) if type_name == "PackageManager" => {
+            Expr::Variable(type_name, _) if type_name == "PackageManager" => {
                 self.check_package_manager_static_method(method_name, args)
             }
-            Expr::Variable(type_name) if type_name == "BuildConfig" => {
+            Expr::Variable(type_name, _) if type_name == "BuildConfig" => {
                 self.check_build_config_static_method(method_name, args)
             }
-            Expr::Variable(type_name) if type_name == "BenchmarkConfig" => {
+            Expr::Variable(type_name, _) if type_name == "BenchmarkConfig" => {
                 self.check_benchmark_config_static_method(method_name, args)
             }
             // Instance method call: vec.push(), vec.len(), etc.
             _ => {
                 let base_type = self.check_expression(base)?;
+                if base_type == EaType::Error {
+                    return Ok(EaType::Error);
+                }
                 self.check_instance_method(&base_type, method_name, args)
             }
         }
@@ -1592,7 +3560,7 @@ impl TypeChecker {
                 // Return Vec<i32> type for now (we can extend this for generics later)
                 Ok(EaType::StdVec(Box::new(EaType::I32)))
             }
-            _ => Err(CompileError::type_error(
+            _ => Err(CompileError::unknown_member_error(
                 format!("Unknown static method 'Vec::{}'", method_name),
                 Position::new(0, 0, 0),
             )),
@@ -1614,7 +3582,7 @@ impl TypeChecker {
                     Box::new(EaType::I32),
                 ))
             }
-            _ => Err(CompileError::type_error(
+            _ => Err(CompileError::unknown_member_error(
                 format!("Unknown static method 'HashMap::{}'", method_name),
                 Position::new(0, 0, 0),
             )),
@@ -1633,7 +3601,7 @@ impl TypeChecker {
                 // Return HashSet<i32> type for now (we can extend this for generics later)
                 Ok(EaType::StdHashSet(Box::new(EaType::I32)))
             }
-            _ => Err(CompileError::type_error(
+            _ => Err(CompileError::unknown_member_error(
                 format!("Unknown static method 'HashSet::{}'", method_name),
                 Position::new(0, 0, 0),
             )),
@@ -1668,7 +3636,7 @@ impl TypeChecker {
                 }
                 Ok(EaType::StdString)
             }
-            _ => Err(CompileError::type_error(
+            _ => Err(CompileError::unknown_member_error(
                 format!("Unknown static method 'String::{}'", method_name),
                 Position::new(0, 0, 0),
             )),
@@ -1847,7 +3815,7 @@ impl TypeChecker {
                     variants: Self::create_result_variants(),
                 })
             }
-            _ => Err(CompileError::type_error(
+            _ => Err(CompileError::unknown_member_error(
                 format!("Unknown static method 'File::{}'", method_name),
                 Position::new(0, 0, 0),
             )),
@@ -1873,14 +3841,18 @@ impl TypeChecker {
                 }
                 Ok(EaType::Custom("Package".to_string()))
             }
-            _ => Err(CompileError::type_error(
+            _ => Err(CompileError::unknown_member_error(
                 format!("Unknown Package static method: {}", method_name),
                 Position::new(0, 0, 0),
             )),
         }
     }
 
-    fn check_package_manager_static_method(&mut self, method_name: &str, args: &[Expr]) -> Result<EaType> {
+    fn check_package_manager_static_method(
+        &mut self,
+        method_name: &str,
+        args: &[Expr],
+    ) -> Result<EaType> {
         match method_name {
             "new" => {
                 if !args.is_empty() {
@@ -1891,14 +3863,18 @@ impl TypeChecker {
                 }
                 Ok(EaType::Custom("PackageManager".to_string()))
             }
-            _ => Err(CompileError::type_error(
+            _ => Err(CompileError::unknown_member_error(
                 format!("Unknown PackageManager static method: {}", method_name),
                 Position::new(0, 0, 0),
             )),
         }
     }
 
-    fn check_build_config_static_method(&mut self, method_name: &str, args: &[Expr]) -> Result<EaType> {
+    fn check_build_config_static_method(
+        &mut self,
+        method_name: &str,
+        args: &[Expr],
+    ) -> Result<EaType> {
         match method_name {
             "new" => {
                 if !args.is_empty() {
@@ -1909,14 +3885,18 @@ impl TypeChecker {
                 }
                 Ok(EaType::Custom("BuildConfig".to_string()))
             }
-            _ => Err(CompileError::type_error(
+            _ => Err(CompileError::unknown_member_error(
                 format!("Unknown BuildConfig static method: {}", method_name),
                 Position::new(0, 0, 0),
             )),
         }
     }
 
-    fn check_benchmark_config_static_method(&mut self, method_name: &str, args: &[Expr]) -> Result<EaType> {
+    fn check_benchmark_config_static_method(
+        &mut self,
+        method_name: &str,
+        args: &[Expr],
+    ) -> Result<EaType> {
         match method_name {
             "new" => {
                 if !args.is_empty() {
@@ -1927,7 +3907,7 @@ impl TypeChecker {
                 }
                 Ok(EaType::Custom("BenchmarkConfig".to_string()))
             }
-            _ => Err(CompileError::type_error(
+            _ => Err(CompileError::unknown_member_error(
                 format!("Unknown BenchmarkConfig static method: {}", method_name),
                 Position::new(0, 0, 0),
             )),
@@ -1940,6 +3920,16 @@ impl TypeChecker {
         method_name: &str,
         args: &[Expr],
     ) -> Result<EaType> {
+        // Methods of impl blocks, dispatched statically on the receiver type
+        if let Some(signature) = self
+            .methods
+            .get(&base_type.to_string())
+            .and_then(|methods| methods.get(method_name))
+            .cloned()
+        {
+            return self.check_trait_method_call(base_type, method_name, &signature, args);
+        }
+
         match base_type {
             EaType::Custom(type_name) if type_name == "Vec" => {
                 // For generic Vec without element type, assume i32 for backward compatibility
@@ -1973,14 +3963,62 @@ impl TypeChecker {
             EaType::Custom(type_name) if type_name == "BenchmarkResults" => {
                 self.check_benchmark_results_instance_method(method_name, args)
             }
-            _ => Err(CompileError::type_error(
+            EaType::Pointer { .. } if method_name == "offset" => {
+                self.check_pointer_offset(base_type, args)
+            }
+            _ => Err(CompileError::unknown_member_error(
                 format!("Type {:?} has no method '{}'", base_type, method_name),
                 Position::new(0, 0, 0),
             )),
         }
     }
 
-    fn check_package_instance_method(&mut self, method_name: &str, args: &[Expr]) -> Result<EaType> {
+    fn check_trait_method_call(
+        &mut self,
+        base_type: &EaType,
+        method_name: &str,
+        signature: &MethodSignature,
+        args: &[Expr],
+    ) -> Result<EaType> {
+        // The receiver fills the `self` parameter
+        let expected_params = &signature.func_type.params[1..];
+        if args.len() != expected_params.len() {
+            return Err(CompileError::type_error(
+                format!(
+                    "Method '{}' of '{}' expects {} arguments, got {}",
+                    method_name,
+                    base_type,
+                    expected_params.len(),
+                    args.len()
+                ),
+                Position::new(0, 0, 0),
+            ));
+        }
+
+        for (i, (arg, expected_type)) in args.iter().zip(expected_params.iter()).enumerate() {
+            let arg_type = self.check_expression(arg)?;
+            if !self.types_compatible(expected_type, &arg_type) {
+                return Err(CompileError::type_error(
+                    format!(
+                        "Argument {} of method '{}': expected {:?}, got {:?}",
+                        i + 1,
+                        method_name,
+                        expected_type,
+                        arg_type
+                    ),
+                    Position::new(0, 0, 0),
+                ));
+            }
+        }
+
+        Ok(*signature.func_type.return_type.clone())
+    }
+
+    fn check_package_instance_method(
+        &mut self,
+        method_name: &str,
+        args: &[Expr],
+    ) -> Result<EaType> {
         match method_name {
             "add_dependency" => {
                 if args.len() != 3 {
@@ -2018,14 +4056,18 @@ impl TypeChecker {
                 }
                 Ok(EaType::Unit)
             }
-            _ => Err(CompileError::type_error(
+            _ => Err(CompileError::unknown_member_error(
                 format!("Unknown Package method: {}", method_name),
                 Position::new(0, 0, 0),
             )),
         }
     }
 
-    fn check_package_manager_instance_method(&mut self, method_name: &str, args: &[Expr]) -> Result<EaType> {
+    fn check_package_manager_instance_method(
+        &mut self,
+        method_name: &str,
+        args: &[Expr],
+    ) -> Result<EaType> {
         match method_name {
             "resolve_dependencies" => {
                 if args.len() != 1 {
@@ -2089,14 +4131,18 @@ impl TypeChecker {
                 }
                 Ok(EaType::Custom("BenchmarkResults".to_string()))
             }
-            _ => Err(CompileError::type_error(
+            _ => Err(CompileError::unknown_member_error(
                 format!("Unknown PackageManager method: {}", method_name),
                 Position::new(0, 0, 0),
             )),
         }
     }
 
-    fn check_build_config_instance_method(&mut self, method_name: &str, args: &[Expr]) -> Result<EaType> {
+    fn check_build_config_instance_method(
+        &mut self,
+        method_name: &str,
+        args: &[Expr],
+    ) -> Result<EaType> {
         match method_name {
             "add_target" => {
                 if args.len() != 2 {
@@ -2131,14 +4177,18 @@ impl TypeChecker {
                 }
                 Ok(EaType::Unit)
             }
-            _ => Err(CompileError::type_error(
+            _ => Err(CompileError::unknown_member_error(
                 format!("Unknown BuildConfig method: {}", method_name),
                 Position::new(0, 0, 0),
             )),
         }
     }
 
-    fn check_benchmark_config_instance_method(&mut self, method_name: &str, args: &[Expr]) -> Result<EaType> {
+    fn check_benchmark_config_instance_method(
+        &mut self,
+        method_name: &str,
+        args: &[Expr],
+    ) -> Result<EaType> {
         match method_name {
             "set_iterations" => {
                 if args.len() != 1 {
@@ -2172,14 +4222,18 @@ impl TypeChecker {
                 }
                 Ok(EaType::Unit)
             }
-            _ => Err(CompileError::type_error(
+            _ => Err(CompileError::unknown_member_error(
                 format!("Unknown BenchmarkConfig method: {}", method_name),
                 Position::new(0, 0, 0),
             )),
         }
     }
 
-    fn check_dependency_resolution_instance_method(&mut self, method_name: &str, args: &[Expr]) -> Result<EaType> {
+    fn check_dependency_resolution_instance_method(
+        &mut self,
+        method_name: &str,
+        args: &[Expr],
+    ) -> Result<EaType> {
         match method_name {
             "count" => {
                 if !args.is_empty() {
@@ -2190,14 +4244,18 @@ impl TypeChecker {
                 }
                 Ok(EaType::I32)
             }
-            _ => Err(CompileError::type_error(
+            _ => Err(CompileError::unknown_member_error(
                 format!("Unknown DependencyResolution method: {}", method_name),
                 Position::new(0, 0, 0),
             )),
         }
     }
 
-    fn check_build_result_instance_method(&mut self, method_name: &str, args: &[Expr]) -> Result<EaType> {
+    fn check_build_result_instance_method(
+        &mut self,
+        method_name: &str,
+        args: &[Expr],
+    ) -> Result<EaType> {
         match method_name {
             "compilation_time_ms" => {
                 if !args.is_empty() {
@@ -2244,14 +4302,18 @@ impl TypeChecker {
                 }
                 Ok(EaType::Bool)
             }
-            _ => Err(CompileError::type_error(
+            _ => Err(CompileError::unknown_member_error(
                 format!("Unknown BuildResult method: {}", method_name),
                 Position::new(0, 0, 0),
             )),
         }
     }
 
-    fn check_benchmark_results_instance_method(&mut self, method_name: &str, args: &[Expr]) -> Result<EaType> {
+    fn check_benchmark_results_instance_method(
+        &mut self,
+        method_name: &str,
+        args: &[Expr],
+    ) -> Result<EaType> {
         match method_name {
             "count" => {
                 if !args.is_empty() {
@@ -2262,14 +4324,19 @@ impl TypeChecker {
                 }
                 Ok(EaType::I32)
             }
-            _ => Err(CompileError::type_error(
+            _ => Err(CompileError::unknown_member_error(
                 format!("Unknown BenchmarkResults method: {}", method_name),
                 Position::new(0, 0, 0),
             )),
         }
     }
 
-    fn check_vec_instance_method(&mut self, method_name: &str, args: &[Expr], element_type: &EaType) -> Result<EaType> {
+    fn check_vec_instance_method(
+        &mut self,
+        method_name: &str,
+        args: &[Expr],
+        element_type: &EaType,
+    ) -> Result<EaType> {
         match method_name {
             "push" => {
                 if args.len() != 1 {
@@ -2350,13 +4417,102 @@ impl TypeChecker {
                 }
                 Ok(EaType::Unit) // clear returns void
             }
-            _ => Err(CompileError::type_error(
+            "map" | "filter" => {
+                if args.len() != 1 {
+                    return Err(CompileError::type_error(
+                        format!("Vec::{}() takes exactly 1 argument", method_name),
+                        Position::new(0, 0, 0),
+                    ));
+                }
+                let func_type = self.check_function_argument(
+                    &format!("Vec::{}()", method_name),
+                    &args[0],
+                    &[element_type.clone()],
+                )?;
+                if method_name == "map" {
+                    return Ok(EaType::StdVec(func_type.return_type)); // map returns Vec<U>
+                }
+                if !self.types_compatible(&EaType::Bool, &func_type.return_type) {
+                    return Err(CompileError::type_error(
+                        format!(
+                            "Vec::filter() expects a function returning bool, got {}",
+                            func_type.return_type
+                        ),
+                        Position::new(0, 0, 0),
+                    ));
+                }
+                Ok(EaType::StdVec(Box::new(element_type.clone()))) // filter keeps the element type
+            }
+            "reduce" => {
+                if args.len() != 2 {
+                    return Err(CompileError::type_error(
+                        "Vec::reduce() takes exactly 2 arguments".to_string(),
+                        Position::new(0, 0, 0),
+                    ));
+                }
+                let initial_type = self.check_expression(&args[0])?;
+                let func_type = self.check_function_argument(
+                    "Vec::reduce()",
+                    &args[1],
+                    &[initial_type.clone(), element_type.clone()],
+                )?;
+                let accumulator_type = func_type.params[0].clone();
+                if !self.types_compatible(&accumulator_type, &func_type.return_type) {
+                    return Err(CompileError::type_error(
+                        format!(
+                            "Vec::reduce() expects a function returning {}, got {}",
+                            accumulator_type, func_type.return_type
+                        ),
+                        Position::new(0, 0, 0),
+                    ));
+                }
+                Ok(accumulator_type) // reduce returns the accumulator
+            }
+            _ => Err(CompileError::unknown_member_error(
                 format!("Unknown Vec method '{}'", method_name),
                 Position::new(0, 0, 0),
             )),
         }
     }
 
+    /// Checks that `arg` is a closure or function that accepts `arg_types`.
+    fn check_function_argument(
+        &mut self,
+        method: &str,
+        arg: &Expr,
+        arg_types: &[EaType],
+    ) -> Result<FunctionType> {
+        let func_type = match self.check_expression(arg)? {
+            EaType::Function(func_type) => *func_type,
+            other => {
+                return Err(CompileError::type_error(
+                    format!("{} expects a closure or function, got {}", method, other),
+                    Position::new(0, 0, 0),
+                ))
+            }
+        };
+
+        let accepts_args = func_type.params.len() == arg_types.len()
+            && func_type
+                .params
+                .iter()
+                .zip(arg_types.iter())
+                .all(|(param, arg_type)| self.types_compatible(param, arg_type));
+        if !accepts_args {
+            let arg_names: Vec<String> = arg_types.iter().map(|t| t.to_string()).collect();
+            return Err(CompileError::type_error(
+                format!(
+                    "{} expects a function taking ({}), got {}",
+                    method,
+                    arg_names.join(", "),
+                    func_type
+                ),
+                Position::new(0, 0, 0),
+            ));
+        }
+        Ok(func_type)
+    }
+
     fn check_hashmap_instance_method(
         &mut self,
         method_name: &str,
@@ -2469,7 +4625,7 @@ impl TypeChecker {
                 }
                 Ok(EaType::Unit) // clear returns void
             }
-            _ => Err(CompileError::type_error(
+            _ => Err(CompileError::unknown_member_error(
                 format!("Unknown HashMap method '{}'", method_name),
                 Position::new(0, 0, 0),
             )),
@@ -2561,7 +4717,7 @@ impl TypeChecker {
                 }
                 Ok(EaType::Unit) // clear returns void
             }
-            _ => Err(CompileError::type_error(
+            _ => Err(CompileError::unknown_member_error(
                 format!("Unknown HashSet method '{}'", method_name),
                 Position::new(0, 0, 0),
             )),
@@ -2690,7 +4846,7 @@ impl TypeChecker {
                 }
                 Ok(EaType::StdString)
             }
-            _ => Err(CompileError::type_error(
+            _ => Err(CompileError::unknown_member_error(
                 format!("Unknown String method '{}'", method_name),
                 Position::new(0, 0, 0),
             )),
@@ -2701,6 +4857,11 @@ impl TypeChecker {
         let array_type = self.check_expression(array)?;
         let index_type = self.check_expression(index)?;
 
+        // A poisoned operand has already been reported
+        if array_type == EaType::Error || index_type == EaType::Error {
+            return Ok(EaType::Error);
+        }
+
         if !self.is_integer_type(&index_type) {
             return Err(CompileError::type_error(
                 format!("Array index must be integer type, got {:?}", index_type),
@@ -2710,9 +4871,24 @@ impl TypeChecker {
 
         match array_type {
             EaType::Array(element_type) => Ok(*element_type),
+            EaType::FixedArray(element_type, length) => {
+                // Constant indices are checked here; others are checked at runtime
+                if let Ok(ComptimeValue::Integer(index_val)) = self.fold_constant(index) {
+                    if index_val < 0 || index_val >= length as i64 {
+                        return Err(CompileError::type_error(
+                            format!(
+                                "Array index {} out of bounds for array of length {}",
+                                index_val, length
+                            ),
+                            Position::new(0, 0, 0),
+                        ));
+                    }
+                }
+                Ok(*element_type)
+            }
             EaType::SIMDVector { element_type, width, .. } => {
                 // Check if index is a compile-time constant and within bounds
-                if let Expr::Literal(Literal::Integer(index_val)) = &**index {
+                if let Expr::Literal(Literal::Integer(index_val), _) = &**index {
                     if *index_val < 0 || *index_val >= width as i64 {
                         return Err(CompileError::type_error(
                             format!("SIMD vector index {} out of bounds for vector of width {}", index_val, width),
@@ -2758,7 +4934,10 @@ impl TypeChecker {
         }
 
         match array_type {
-            EaType::Array(element_type) => Ok(EaType::Array(element_type)), // Slice returns same array type
+            // Slices have a length only known at runtime
+            EaType::Array(element_type) | EaType::FixedArray(element_type, _) => {
+                Ok(EaType::Array(element_type))
+            }
             _ => Err(CompileError::type_error(
                 format!("Cannot slice non-array type {:?}", array_type),
                 Position::new(0, 0, 0),
@@ -2768,7 +4947,7 @@ impl TypeChecker {
 
     fn check_field_access(&mut self, object: &Box<Expr>, field: &str) -> Result<EaType> {
         // Check if this is a static method call on a type (e.g., HashMap::new())
-        if let Expr::Variable(type_name) = &**object {
+        if let Expr::Variable(type_name, _) = &**object {
             // Check if this is a known type with static methods
             if let Some(_type) = self.context.types.get(type_name) {
                 // Handle static method calls on built-in types
@@ -2799,18 +4978,30 @@ impl TypeChecker {
         let object_type = self.check_expression(object)?;
 
         match &object_type {
+            EaType::Error => Ok(EaType::Error),
+            EaType::Tuple(element_types) => field
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| element_types.get(index))
+                .cloned()
+                .ok_or_else(|| {
+                    CompileError::unknown_member_error(
+                        format!("Tuple type {} has no field '{}'", object_type, field),
+                        Position::new(0, 0, 0),
+                    )
+                }),
             EaType::Struct(struct_name) => {
-                if let Some(struct_fields) = self.context.structs.get(struct_name) {
+                if let Some(struct_fields) = self.struct_fields(struct_name) {
                     if let Some(field_type) = struct_fields.get(field) {
                         Ok(field_type.clone())
                     } else {
-                        Err(CompileError::type_error(
+                        Err(CompileError::unknown_member_error(
                             format!("Struct '{}' has no field '{}'", struct_name, field),
                             Position::new(0, 0, 0),
                         ))
                     }
                 } else {
-                    Err(CompileError::type_error(
+                    Err(CompileError::undefined_type_error(
                         format!("Unknown struct type '{}'", struct_name),
                         Position::new(0, 0, 0),
                     ))
@@ -2885,12 +5076,28 @@ impl TypeChecker {
         &mut self,
         name: &str,
         fields: &[crate::ast::StructFieldInit],
+        span: &Span,
     ) -> Result<EaType> {
+        // Generic structs take their type arguments from the field values
+        if let Some(generic) = self.generic_structs.get(name).cloned() {
+            let mut uses = Vec::new();
+            for field_init in fields {
+                if let Some(field) = generic.fields.iter().find(|f| f.name == field_init.name) {
+                    let value_type = self.check_expression(&field_init.value)?;
+                    uses.push((field.type_annotation.name.as_str(), &field_init.value, value_type));
+                }
+            }
+            let type_args = self.infer_type_arguments(name, &generic.type_params, &uses)?;
+            self.record_type_arguments(span, &type_args);
+            let instance_name = self.instantiate_generic_struct(name, &generic, &type_args)?;
+            return self.check_struct_literal(&instance_name, fields, span);
+        }
+
         // Check if struct is defined
-        let struct_fields = match self.context.structs.get(name) {
+        let struct_fields = match self.struct_fields(name) {
             Some(fields) => fields.clone(),
             None => {
-                return Err(CompileError::type_error(
+                return Err(CompileError::undefined_type_error(
                     format!("Undefined struct '{}'", name),
                     Position::new(0, 0, 0),
                 ))
@@ -2930,7 +5137,7 @@ impl TypeChecker {
         // Check for extra fields
         for field_init in fields {
             if !struct_fields.contains_key(&field_init.name) {
-                return Err(CompileError::type_error(
+                return Err(CompileError::unknown_member_error(
                     format!("Unknown field '{}' in struct '{}'", field_init.name, name),
                     Position::new(0, 0, 0),
                 ));
@@ -2947,6 +5154,10 @@ impl TypeChecker {
     ) -> Result<EaType> {
         // Type check the value being matched
         let value_type = self.check_expression(value)?;
+        if value_type == EaType::Error {
+            // Arms can't be checked against a type that is already in error
+            return Ok(EaType::Error);
+        }
 
         if arms.is_empty() {
             return Err(CompileError::type_error(
@@ -2971,66 +5182,65 @@ impl TypeChecker {
             }
         }
 
-        // Real exhaustiveness checking implementation (not placeholder)
-        self.check_match_exhaustiveness(&value_type, arms)?;
+        self.check_match_exhaustiveness(value, &value_type, arms)?;
 
         Ok(first_arm_type)
     }
 
-    /// Real exhaustiveness checking for match expressions (not placeholder)
+    /// Checks that the arms of a match cover every value of `value_type`,
+    /// warning about arms that earlier arms leave nothing to match.
+    ///
+    /// Matches involving an enum must be exhaustive; matches on numbers,
+    /// strings and booleans that miss values only get a warning.
     fn check_match_exhaustiveness(
-        &self,
+        &mut self,
+        value: &Expr,
         value_type: &EaType,
         arms: &[crate::ast::MatchArm],
     ) -> Result<()> {
-        match value_type {
-            EaType::Enum { name: enum_name, variants } => {
-                // Collect all variants covered by match arms
-                let mut covered_variants = std::collections::HashSet::new();
-                let mut has_wildcard = false;
-
-                for arm in arms {
-                    match &arm.pattern {
-                        crate::ast::Pattern::EnumVariant { variant, .. } => {
-                            covered_variants.insert(variant.clone());
-                        }
-                        crate::ast::Pattern::Wildcard => {
-                            has_wildcard = true;
-                        }
-                        _ => {
-                            // Other pattern types (literals, variables) don't contribute to enum coverage
-                        }
-                    }
-                }
+        let coverage = exhaustiveness::check_match(value_type, arms, &|name| {
+            self.struct_fields(name).cloned()
+        });
 
-                // Check if all enum variants are covered
-                if !has_wildcard {
-                    let all_variants: std::collections::HashSet<String> = variants.keys().cloned().collect();
-                    let missing_variants: Vec<String> = all_variants
-                        .difference(&covered_variants)
-                        .cloned()
-                        .collect();
+        for &index in &coverage.unreachable {
+            self.warnings.push(
+                CompileError::type_error(
+                    "Unreachable match arm: its pattern is already covered by earlier arms"
+                        .to_string(),
+                    Position::new(0, 0, 0),
+                )
+                .with_span(arms[index].expression.span()),
+            );
+        }
 
-                    if !missing_variants.is_empty() {
-                        return Err(CompileError::type_error(
-                            format!(
-                                "Non-exhaustive match on enum {}: missing variants [{}]",
-                                enum_name,
-                                missing_variants.join(", ")
-                            ),
-                            Position::new(0, 0, 0),
-                        ));
-                    }
-                }
+        if coverage.is_exhaustive() {
+            return Ok(());
+        }
 
-                Ok(())
-            }
-            _ => {
-                // For non-enum types, exhaustiveness checking is more complex
-                // For now, we'll assume they're exhaustive (this could be extended)
-                // Note: Full exhaustiveness checking for integers/booleans requires advanced analysis
-                Ok(())
-            }
+        let missing = coverage.missing.join(", ");
+        let message = match value_type {
+            EaType::Enum { name, .. } => format!(
+                "Non-exhaustive match on enum {}: missing variants [{}]",
+                name, missing
+            ),
+            _ => format!(
+                "Non-exhaustive match on {}: patterns [{}] not covered",
+                value_type, missing
+            ),
+        };
+        let error = CompileError::type_error(message, Position::new(0, 0, 0));
+        if Self::contains_enum(value_type) {
+            return Err(error);
+        }
+        self.warnings.push(error.with_span(value.span()));
+        Ok(())
+    }
+
+    fn contains_enum(ty: &EaType) -> bool {
+        match ty {
+            EaType::Enum { .. } => true,
+            EaType::Tuple(element_types) => element_types.iter().any(Self::contains_enum),
+            _ => false,
         }
     }
 
@@ -3042,6 +5252,16 @@ impl TypeChecker {
         // Type check the pattern against the value type
         self.check_pattern(&arm.pattern, value_type)?;
 
+        if let Some(guard) = &arm.guard {
+            let guard_type = self.check_expression(guard)?;
+            if !self.types_compatible(&EaType::Bool, &guard_type) {
+                return Err(CompileError::type_error(
+                    format!("Match guard must be a bool, found {}", guard_type),
+                    Position::new(0, 0, 0),
+                ));
+            }
+        }
+
         // Type check the expression with any variables bound by the pattern
         self.check_expression(&arm.expression)
     }
@@ -3093,7 +5313,7 @@ impl TypeChecker {
                         }
 
                         if !variants.contains_key(variant) {
-                            return Err(CompileError::type_error(
+                            return Err(CompileError::unknown_member_error(
                                 format!("Variant {} not found in enum {}", variant, enum_name),
                                 Position::new(0, 0, 0),
                             ));
@@ -3120,7 +5340,7 @@ impl TypeChecker {
                                     self.check_pattern(pattern, concrete_data_type)?;
                                 }
                             } else {
-                                return Err(CompileError::type_error(
+                                return Err(CompileError::unknown_member_error(
                                     format!("Unknown variant {} in enum {}", variant, enum_name),
                                     Position::new(0, 0, 0),
                                 ));
@@ -3138,6 +5358,126 @@ impl TypeChecker {
                     )),
                 }
             }
+            Pattern::Tuple { patterns } => match expected_type {
+                EaType::Tuple(element_types) if element_types.len() == patterns.len() => {
+                    for (pattern, element_type) in patterns.iter().zip(element_types) {
+                        self.check_pattern(pattern, element_type)?;
+                    }
+                    Ok(())
+                }
+                _ => Err(CompileError::type_error(
+                    format!(
+                        "Tuple pattern of {} elements does not match value type {}",
+                        patterns.len(),
+                        expected_type
+                    ),
+                    Position::new(0, 0, 0),
+                )),
+            },
+            Pattern::Struct {
+                name,
+                fields,
+                has_rest,
+            } => self.check_struct_pattern(name, fields, *has_rest, expected_type),
+            Pattern::Range {
+                start,
+                end,
+                inclusive,
+            } => {
+                for bound in [start, end] {
+                    let bound_type = self.literal_type(bound);
+                    if !bound_type.is_numeric() {
+                        return Err(CompileError::type_error(
+                            format!("Range pattern bounds must be numbers, found {}", bound_type),
+                            Position::new(0, 0, 0),
+                        ));
+                    }
+                    if !self.types_compatible(&bound_type, expected_type) {
+                        return Err(CompileError::type_error(
+                            format!(
+                                "Pattern type {} does not match value type {}",
+                                bound_type, expected_type
+                            ),
+                            Position::new(0, 0, 0),
+                        ));
+                    }
+                }
+
+                let empty = match (start, end) {
+                    (Literal::Integer(start), Literal::Integer(end)) => {
+                        start > end || (!inclusive && start == end)
+                    }
+                    (Literal::Float(start), Literal::Float(end)) => {
+                        start > end || (!inclusive && start == end)
+                    }
+                    _ => {
+                        return Err(CompileError::type_error(
+                            format!("Range pattern {} mixes integer and float bounds", pattern),
+                            Position::new(0, 0, 0),
+                        ))
+                    }
+                };
+                if empty {
+                    return Err(CompileError::type_error(
+                        format!("Range pattern {} matches no values", pattern),
+                        Position::new(0, 0, 0),
+                    ));
+                }
+                Ok(())
+            }
+            Pattern::Or(alternatives) => {
+                // Every alternative must bind the same variables with the same types
+                let mut first_bindings: Option<HashMap<String, EaType>> = None;
+                for alternative in alternatives {
+                    self.check_pattern(alternative, expected_type)?;
+                    let bindings: HashMap<String, EaType> = alternative
+                        .bound_names()
+                        .into_iter()
+                        .map(|name| {
+                            let name_type = self
+                                .context
+                                .variables
+                                .get(name)
+                                .cloned()
+                                .unwrap_or(EaType::Error);
+                            (name.to_string(), name_type)
+                        })
+                        .collect();
+
+                    let first = match &first_bindings {
+                        Some(first) => first,
+                        None => {
+                            first_bindings = Some(bindings);
+                            continue;
+                        }
+                    };
+                    for name in first.keys().chain(bindings.keys()) {
+                        match (first.get(name), bindings.get(name)) {
+                            (Some(first_type), Some(binding_type)) => {
+                                if first_type != binding_type {
+                                    return Err(CompileError::type_error(
+                                        format!(
+                                            "Variable '{}' is bound to {} in one alternative of '{}' and to {} in another",
+                                            name, first_type, pattern, binding_type
+                                        ),
+                                        Position::new(0, 0, 0),
+                                    ));
+                                }
+                            }
+                            _ => {
+                                return Err(CompileError::type_error(
+                                    format!(
+                                        "Variable '{}' is not bound in every alternative of '{}'",
+                                        name, pattern
+                                    ),
+                                    Position::new(0, 0, 0),
+                                ))
+                            }
+                        }
+                    }
+                }
+                Ok(())
+            }
             Pattern::Wildcard => {
                 // Wildcard patterns always match
                 Ok(())
@@ -3145,6 +5485,79 @@ impl TypeChecker {
         }
     }
 
+    /// Checks a struct pattern such as `Point { x, y: 0 }` against the matched value.
+    /// Every field must be named unless the pattern ends in `..`.
+    fn check_struct_pattern(
+        &mut self,
+        name: &str,
+        fields: &[crate::ast::FieldPattern],
+        has_rest: bool,
+        expected_type: &EaType,
+    ) -> Result<()> {
+        let struct_name = match expected_type {
+            // Patterns on generic structs name the struct without its type arguments
+            EaType::Struct(struct_name)
+                if struct_name == name
+                    || crate::ast::split_generic_type_name(struct_name)
+                        .map_or(false, |(base, _)| base == name) =>
+            {
+                struct_name.clone()
+            }
+            _ => {
+                return Err(CompileError::type_error(
+                    format!(
+                        "Struct pattern {} does not match value type {}",
+                        name, expected_type
+                    ),
+                    Position::new(0, 0, 0),
+                ))
+            }
+        };
+        let field_types = self.struct_fields(&struct_name).cloned().ok_or_else(|| {
+            CompileError::undefined_type_error(
+                format!("Undefined struct '{}'", name),
+                Position::new(0, 0, 0),
+            )
+        })?;
+
+        let mut named = HashSet::new();
+        for field in fields {
+            let field_type = field_types.get(&field.name).ok_or_else(|| {
+                CompileError::unknown_member_error(
+                    format!("Unknown field '{}' in struct {}", field.name, name),
+                    Position::new(0, 0, 0),
+                )
+            })?;
+            if !named.insert(field.name.as_str()) {
+                return Err(CompileError::type_error(
+                    format!("Field '{}' appears more than once in the pattern", field.name),
+                    Position::new(0, 0, 0),
+                ));
+            }
+            self.check_pattern(&field.pattern, field_type)?;
+        }
+
+        if !has_rest {
+            let mut missing: Vec<&String> = field_types
+                .keys()
+                .filter(|field_name| !named.contains(field_name.as_str()))
+                .collect();
+            if !missing.is_empty() {
+                missing.sort();
+                let missing: Vec<String> = missing.into_iter().cloned().collect();
+                return Err(CompileError::type_error(
+                    format!(
+                        "Pattern for struct {} does not mention fields [{}]; add `..` to ignore them",
+                        name,
+                        missing.join(", ")
+                    ),
+                    Position::new(0, 0, 0),
+                ));
+            }
+        }
+        Ok(())
+    }
+
     fn check_enum_literal(
         &mut self,
         enum_name: &str,
@@ -3161,29 +5574,116 @@ impl TypeChecker {
             Some(EaType::Enum { name, variants }) => {
                 // Check if variant exists
                 if !variants.contains_key(&variant.to_string()) {
-                    return Err(CompileError::type_error(
+                    return Err(CompileError::unknown_member_error(
                         format!("Unknown variant '{}' in enum '{}'", variant, enum_name),
                         Position::new(0, 0, 0),
                     ));
                 }
 
-                EaType::Enum {
-                    name: name.clone(),
-                    variants: variants.clone(),
+                EaType::Enum {
+                    name: name.clone(),
+                    variants: variants.clone(),
+                }
+            }
+            _ => {
+                return Err(CompileError::undefined_type_error(
+                    format!("Undefined enum '{}'", enum_name),
+                    Position::new(0, 0, 0),
+                ))
+            }
+        };
+
+        Ok(enum_type)
+    }
+
+    fn annotation_to_type(&mut self, annotation: &TypeAnnotation) -> Result<EaType> {
+        // Type parameters of the generic instantiation being checked
+        if let Some(bound) = self.type_bindings.get(&annotation.name) {
+            return Ok(bound.clone());
+        }
+
+        // Lane-width-agnostic vectors, e.g. `simd<f32>`, are as wide as the target allows
+        if let Some(element_name) = crate::ast::split_native_vector_type_name(&annotation.name) {
+            let vector_type = self.native_vector_type(&element_name)?;
+            return self.annotation_to_type(&TypeAnnotation {
+                name: vector_type.to_string(),
+                is_mutable: false,
+            });
+        }
+
+        // Tuple types, e.g. `(i32, f32)`
+        if let Some(element_names) = crate::ast::split_tuple_type_name(&annotation.name) {
+            let mut element_types = Vec::new();
+            for name in element_names {
+                element_types.push(self.annotation_to_type(&TypeAnnotation {
+                    name,
+                    is_mutable: false,
+                })?);
+            }
+            return Ok(EaType::Tuple(element_types));
+        }
+
+        // Raw pointer types, e.g. `*f32` or `*mut f32`
+        if let Some((target_name, is_mutable)) = crate::ast::split_pointer_type_name(&annotation.name)
+        {
+            let target = self.annotation_to_type(&TypeAnnotation {
+                name: target_name,
+                is_mutable: false,
+            })?;
+            return Ok(EaType::Pointer {
+                target: Box::new(target),
+                is_mutable,
+            });
+        }
+
+        // Fixed-size array types, e.g. `[f32; 64]` or `[f32; SIZE]`
+        if let Some((element_name, length)) = crate::ast::split_array_type_name(&annotation.name) {
+            let element_type = self.annotation_to_type(&TypeAnnotation {
+                name: element_name,
+                is_mutable: false,
+            })?;
+            let length = self.constant_length(&crate::ast::array_length_expr(&length))?;
+            return Ok(EaType::FixedArray(Box::new(element_type), length));
+        }
+
+        // Function types, e.g. `func(f32) -> f32`
+        if let Some((param_names, return_name)) =
+            crate::ast::split_function_type_name(&annotation.name)
+        {
+            let mut param_types = Vec::new();
+            for name in param_names {
+                param_types.push(self.annotation_to_type(&TypeAnnotation {
+                    name,
+                    is_mutable: false,
+                })?);
+            }
+            let return_type = self.annotation_to_type(&TypeAnnotation {
+                name: return_name,
+                is_mutable: false,
+            })?;
+            return Ok(EaType::Function(Box::new(FunctionType {
+                params: param_types,
+                return_type: Box::new(return_type),
+                is_variadic: false,
+            })));
+        }
+
+        // Uses of generic structs, e.g. `Pair<i32>` or `Pair<T>` inside a generic function
+        if let Some((base, args)) = crate::ast::split_generic_type_name(&annotation.name) {
+            if let Some(generic) = self.generic_structs.get(base).cloned() {
+                let base = base.to_string();
+                let mut type_args = Vec::new();
+                for arg in args {
+                    type_args.push(self.annotation_to_type(&TypeAnnotation {
+                        name: arg,
+                        is_mutable: false,
+                    })?);
                 }
+                let instance_name = self.instantiate_generic_struct(&base, &generic, &type_args)?;
+                return Ok(EaType::Struct(instance_name));
             }
-            _ => {
-                return Err(CompileError::type_error(
-                    format!("Undefined enum '{}'", enum_name),
-                    Position::new(0, 0, 0),
-                ))
-            }
-        };
-
-        Ok(enum_type)
-    }
+        }
 
-    fn annotation_to_type(&self, annotation: &TypeAnnotation) -> Result<EaType> {
         match annotation.name.as_str() {
             "i8" => Ok(EaType::I8),
             "i16" => Ok(EaType::I16),
@@ -3393,6 +5893,10 @@ impl TypeChecker {
                 // Check if it's a defined struct
                 if self.context.structs.contains_key(&annotation.name) {
                     Ok(EaType::Struct(annotation.name.clone()))
+                } else if let Some(enum_type @ EaType::Enum { .. }) =
+                    self.context.types.get(&annotation.name)
+                {
+                    Ok(enum_type.clone())
                 } else {
                     // Treat unknown types as Custom for now
                     Ok(EaType::Custom(annotation.name.clone()))
@@ -3405,6 +5909,20 @@ impl TypeChecker {
         match (expected, actual) {
             (a, b) if a == b => true,
 
+            // An expression in error has already been reported
+            (EaType::Error, _) | (_, EaType::Error) => true,
+
+            // Function values match when their signatures line up
+            (EaType::Function(expected), EaType::Function(actual)) => {
+                expected.params.len() == actual.params.len()
+                    && expected
+                        .params
+                        .iter()
+                        .zip(actual.params.iter())
+                        .all(|(expected, actual)| self.types_compatible(actual, expected))
+                    && self.types_compatible(&expected.return_type, &actual.return_type)
+            }
+
             // Handle custom types that are actually primitive types
             (EaType::I32, EaType::Custom(name)) if name == "i32" => true,
             (EaType::Custom(name), EaType::I32) if name == "i32" => true,
@@ -3468,6 +5986,62 @@ impl TypeChecker {
             // Float promotions
             (EaType::F64, EaType::F32) => true,
 
+            // Arrays of the same length, or any length where the length is not part of the type
+            (EaType::FixedArray(expected, expected_len), EaType::FixedArray(actual, actual_len)) => {
+                expected_len == actual_len && self.types_compatible(expected, actual)
+            }
+            (EaType::Array(expected), EaType::FixedArray(actual, _)) => {
+                self.types_compatible(expected, actual)
+            }
+
+            // Raw pointers: `*mut T` may be used as `*T`, and a reference to a value
+            // or to an array of values coerces to a pointer to it. Only references
+            // to `let mut` variables coerce to `*mut T`.
+            (
+                EaType::Pointer {
+                    target: expected,
+                    is_mutable: expected_mut,
+                },
+                EaType::Pointer {
+                    target: actual,
+                    is_mutable: actual_mut,
+                },
+            ) => (*actual_mut || !*expected_mut) && expected == actual,
+            (
+                EaType::Pointer {
+                    target,
+                    is_mutable: pointer_mut,
+                },
+                EaType::Reference {
+                    target: referent,
+                    is_mutable: reference_mut,
+                },
+            ) if *reference_mut || !*pointer_mut => match &**referent {
+                EaType::FixedArray(element, _) | EaType::Array(element) => {
+                    target == element || target == referent
+                }
+                _ => target == referent,
+            },
+            (
+                EaType::Reference {
+                    target: expected,
+                    is_mutable: expected_mut,
+                },
+                EaType::Reference {
+                    target: actual,
+                    is_mutable: actual_mut,
+                },
+            ) => (*actual_mut || !*expected_mut) && expected == actual,
+
+            // Tuples are compatible element by element
+            (EaType::Tuple(expected), EaType::Tuple(actual)) => {
+                expected.len() == actual.len()
+                    && expected
+                        .iter()
+                        .zip(actual)
+                        .all(|(expected, actual)| self.types_compatible(expected, actual))
+            }
+
             // SIMD vector compatibility
             (
                 EaType::SIMDVector {
@@ -3584,7 +6158,7 @@ impl TypeChecker {
         // A block expression returns the type of its last expression statement,
         // or Unit if there are no expression statements or if the last statement is not an expression
         if let Some(last_stmt) = statements.last() {
-            if let Stmt::Expression(expr) = last_stmt {
+            if let Stmt::Expression(expr, _) = last_stmt {
                 return self.check_expression(expr);
             }
         }
@@ -3639,6 +6213,57 @@ impl TypeChecker {
                 alignment: _,
                 position,
             } => self.check_simd_vector_store(address, vector, position),
+            SIMDExpr::Select {
+                mask,
+                if_true,
+                if_false,
+                position: _,
+            } => self.check_simd_select(mask, if_true, if_false),
+            SIMDExpr::MaskedLoad {
+                address,
+                mask,
+                passthru,
+                position: _,
+            } => self.check_simd_masked_load(address, mask, passthru),
+            SIMDExpr::MaskedStore {
+                address,
+                vector,
+                mask,
+                position: _,
+            } => self.check_simd_masked_store(address, vector, mask),
+            SIMDExpr::Gather {
+                base,
+                indices,
+                position: _,
+            } => self.check_simd_gather(base, indices),
+            SIMDExpr::Scatter {
+                base,
+                indices,
+                vector,
+                position: _,
+            } => self.check_simd_scatter(base, indices, vector),
+            SIMDExpr::Shuffle {
+                left,
+                right,
+                mask,
+                position: _,
+            } => self.check_simd_shuffle(left, right, mask),
+            SIMDExpr::NativeVectorLoad {
+                address,
+                element_type,
+                alignment: _,
+                position,
+            } => {
+                let vector_type = self.native_vector_type(element_type)?;
+                self.check_simd_vector_load(address, &vector_type, position)
+            }
+            SIMDExpr::Lanes {
+                element_type,
+                position: _,
+            } => {
+                self.native_vector_type(element_type)?;
+                Ok(EaType::I32)
+            }
         }
     }
 
@@ -3930,10 +6555,14 @@ impl TypeChecker {
         _position: &Position,
     ) -> Result<EaType> {
         let address_type = self.check_expression(address)?;
+        if let EaType::Pointer { target, .. } = &address_type {
+            let element_type = self.simd_vector_type_to_element_type(vector_type);
+            self.check_raw_vector_address(target, &element_type, "Vector load")?;
+        }
 
         // Check that address is a reference/pointer type
         match address_type {
-            EaType::Reference(_) => {
+            EaType::Reference { .. } | EaType::Pointer { .. } => {
                 // Return the vector type being loaded
                 let element_type = self.simd_vector_type_to_element_type(vector_type);
                 // Convert element_type to SIMDElementType to avoid recursion
@@ -3965,10 +6594,21 @@ impl TypeChecker {
     ) -> Result<EaType> {
         let address_type = self.check_expression(address)?;
         let vector_type = self.check_expression(vector)?;
+        if let (EaType::Pointer { target, is_mutable }, EaType::SIMDVector { element_type, .. }) =
+            (&address_type, &vector_type)
+        {
+            if !is_mutable {
+                return Err(CompileError::type_error(
+                    format!("Vector store requires a *mut pointer, got {}", address_type),
+                    Position::new(0, 0, 0),
+                ));
+            }
+            self.check_raw_vector_address(target, &element_type.to_ea_type(), "Vector store")?;
+        }
 
         // Check that address is a reference/pointer type
         match address_type {
-            EaType::Reference(_) => {
+            EaType::Reference { .. } | EaType::Pointer { .. } => {
                 // Check that vector is a SIMD vector type
                 match vector_type {
                     EaType::SIMDVector { .. } => {
@@ -3994,6 +6634,398 @@ impl TypeChecker {
         }
     }
 
+    fn check_simd_select(
+        &mut self,
+        mask: &crate::ast::Expr,
+        if_true: &crate::ast::Expr,
+        if_false: &crate::ast::Expr,
+    ) -> Result<EaType> {
+        let true_type = self.check_expression(if_true)?;
+        let false_type = self.check_expression(if_false)?;
+        match (&true_type, &false_type) {
+            (
+                EaType::SIMDVector {
+                    vector_type: true_vector,
+                    ..
+                },
+                EaType::SIMDVector {
+                    vector_type: false_vector,
+                    ..
+                },
+            ) if true_vector.is_compatible_with(false_vector) => {}
+            _ => {
+                return Err(CompileError::type_error(
+                    format!(
+                        "select() requires two SIMD vectors of the same type, got {} and {}",
+                        true_type, false_type
+                    ),
+                    Position::new(0, 0, 0),
+                ))
+            }
+        }
+
+        self.check_simd_mask("select", mask, &true_type)?;
+        Ok(true_type)
+    }
+
+    fn check_simd_masked_load(
+        &mut self,
+        address: &crate::ast::Expr,
+        mask: &crate::ast::Expr,
+        passthru: &crate::ast::Expr,
+    ) -> Result<EaType> {
+        let address_type = self.check_expression(address)?;
+        let passthru_type = self.check_expression(passthru)?;
+        let element_type = match &passthru_type {
+            EaType::SIMDVector { element_type, .. } => element_type.to_ea_type(),
+            _ => {
+                return Err(CompileError::type_error(
+                    format!(
+                        "masked_load() passthru must be a SIMD vector, got {}",
+                        passthru_type
+                    ),
+                    Position::new(0, 0, 0),
+                ))
+            }
+        };
+
+        match &address_type {
+            EaType::Pointer { target, .. } => {
+                self.check_raw_vector_address(target, &element_type, "Masked load")?
+            }
+            EaType::Reference { .. } => {}
+            _ => {
+                return Err(CompileError::type_error(
+                    format!(
+                        "masked_load() requires pointer address, got {}",
+                        address_type
+                    ),
+                    Position::new(0, 0, 0),
+                ))
+            }
+        }
+
+        self.check_simd_mask("masked_load", mask, &passthru_type)?;
+        Ok(passthru_type)
+    }
+
+    fn check_simd_masked_store(
+        &mut self,
+        address: &crate::ast::Expr,
+        vector: &crate::ast::Expr,
+        mask: &crate::ast::Expr,
+    ) -> Result<EaType> {
+        let address_type = self.check_expression(address)?;
+        let vector_type = self.check_expression(vector)?;
+        let element_type = match &vector_type {
+            EaType::SIMDVector { element_type, .. } => element_type.to_ea_type(),
+            _ => {
+                return Err(CompileError::type_error(
+                    format!(
+                        "masked_store() requires SIMD vector value, got {}",
+                        vector_type
+                    ),
+                    Position::new(0, 0, 0),
+                ))
+            }
+        };
+
+        match &address_type {
+            EaType::Pointer { target, is_mutable } => {
+                if !is_mutable {
+                    return Err(CompileError::type_error(
+                        format!(
+                            "masked_store() requires a *mut pointer, got {}",
+                            address_type
+                        ),
+                        Position::new(0, 0, 0),
+                    ));
+                }
+                self.check_raw_vector_address(target, &element_type, "Masked store")?
+            }
+            EaType::Reference { .. } => {}
+            _ => {
+                return Err(CompileError::type_error(
+                    format!(
+                        "masked_store() requires pointer address, got {}",
+                        address_type
+                    ),
+                    Position::new(0, 0, 0),
+                ))
+            }
+        }
+
+        self.check_simd_mask("masked_store", mask, &vector_type)?;
+        Ok(EaType::Unit)
+    }
+
+    /// A mask is a SIMD vector, usually the result of a lane-wise comparison,
+    /// with one lane for every lane of the vector it applies to.
+    fn check_simd_mask(
+        &mut self,
+        operation: &str,
+        mask: &crate::ast::Expr,
+        vector_type: &EaType,
+    ) -> Result<()> {
+        let mask_type = self.check_expression(mask)?;
+        let (mask_lanes, vector_lanes) = match (&mask_type, vector_type) {
+            (EaType::SIMDVector { width: mask_lanes, .. }, EaType::SIMDVector { width, .. }) => {
+                (*mask_lanes, *width)
+            }
+            _ => {
+                return Err(CompileError::type_error(
+                    format!(
+                        "{}() mask must be a SIMD vector such as a .< comparison, got {}",
+                        operation, mask_type
+                    ),
+                    Position::new(0, 0, 0),
+                ))
+            }
+        };
+        if mask_lanes != vector_lanes {
+            return Err(CompileError::type_error(
+                format!(
+                    "{}() mask has {} lanes but {} has {}",
+                    operation, mask_lanes, vector_type, vector_lanes
+                ),
+                Position::new(0, 0, 0),
+            ));
+        }
+        Ok(())
+    }
+
+    fn check_simd_gather(
+        &mut self,
+        base: &crate::ast::Expr,
+        indices: &crate::ast::Expr,
+    ) -> Result<EaType> {
+        let base_type = self.check_expression(base)?;
+        let lanes = self.check_simd_indices("gather", indices)?;
+        match &base_type {
+            EaType::Pointer { target, .. } => {
+                self.require_unsafe("Gather through a raw pointer")?;
+                self.simd_vector_of("gather", target, lanes)
+            }
+            _ => Err(CompileError::type_error(
+                format!("gather() requires a raw pointer base, got {}", base_type),
+                Position::new(0, 0, 0),
+            )),
+        }
+    }
+
+    fn check_simd_scatter(
+        &mut self,
+        base: &crate::ast::Expr,
+        indices: &crate::ast::Expr,
+        vector: &crate::ast::Expr,
+    ) -> Result<EaType> {
+        let base_type = self.check_expression(base)?;
+        let lanes = self.check_simd_indices("scatter", indices)?;
+        let vector_type = self.check_expression(vector)?;
+
+        let target = match &base_type {
+            EaType::Pointer {
+                target,
+                is_mutable: true,
+            } => target,
+            _ => {
+                return Err(CompileError::type_error(
+                    format!("scatter() requires a *mut pointer base, got {}", base_type),
+                    Position::new(0, 0, 0),
+                ))
+            }
+        };
+        self.require_unsafe("Scatter through a raw pointer")?;
+
+        match &vector_type {
+            EaType::SIMDVector {
+                element_type,
+                width,
+                ..
+            } if element_type.to_ea_type() == **target && *width == lanes => Ok(EaType::Unit),
+            _ => Err(CompileError::type_error(
+                format!(
+                    "scatter() through {} with {} indices requires a vector of {} {} lanes, got {}",
+                    base_type, lanes, lanes, target, vector_type
+                ),
+                Position::new(0, 0, 0),
+            )),
+        }
+    }
+
+    /// `shuffle(a, b, mask)` picks lanes of `a` and then `b` by constant index, so
+    /// every index must be below twice the lane count.
+    fn check_simd_shuffle(
+        &mut self,
+        left: &crate::ast::Expr,
+        right: &crate::ast::Expr,
+        mask: &crate::ast::Expr,
+    ) -> Result<EaType> {
+        let left_type = self.check_expression(left)?;
+        let right_type = self.check_expression(right)?;
+        let (element_type, lanes) = match (&left_type, &right_type) {
+            (
+                EaType::SIMDVector {
+                    element_type,
+                    width,
+                    vector_type: left_vector,
+                },
+                EaType::SIMDVector {
+                    vector_type: right_vector,
+                    ..
+                },
+            ) if left_vector.is_compatible_with(right_vector) => {
+                (element_type.to_ea_type(), *width)
+            }
+            _ => {
+                return Err(CompileError::type_error(
+                    format!(
+                        "shuffle() requires two SIMD vectors of the same type, got {} and {}",
+                        left_type, right_type
+                    ),
+                    Position::new(0, 0, 0),
+                ))
+            }
+        };
+
+        let indices = crate::ast::constant_lane_indices(mask).ok_or_else(|| {
+            CompileError::type_error(
+                format!(
+                    "shuffle() mask must be a constant array of lane indices such as [0, {}, 1, {}]",
+                    lanes,
+                    lanes + 1
+                ),
+                Position::new(0, 0, 0),
+            )
+        })?;
+        if let Some(index) = indices
+            .iter()
+            .find(|&&index| index < 0 || index >= 2 * lanes as i64)
+        {
+            return Err(CompileError::type_error(
+                format!(
+                    "shuffle() lane index {} is out of range for two {} vectors ({} lanes)",
+                    index,
+                    left_type,
+                    2 * lanes
+                ),
+                Position::new(0, 0, 0),
+            ));
+        }
+
+        self.simd_vector_of("shuffle", &element_type, indices.len())
+    }
+
+    /// Checks the index vector of a gather or scatter and returns its lane count.
+    fn check_simd_indices(&mut self, operation: &str, indices: &crate::ast::Expr) -> Result<usize> {
+        let indices_type = self.check_expression(indices)?;
+        match &indices_type {
+            EaType::SIMDVector {
+                element_type,
+                width,
+                ..
+            } if self.is_integer_type(&element_type.to_ea_type()) => Ok(*width),
+            _ => Err(CompileError::type_error(
+                format!(
+                    "{}() indices must be an integer SIMD vector, got {}",
+                    operation, indices_type
+                ),
+                Position::new(0, 0, 0),
+            )),
+        }
+    }
+
+    /// The SIMD vector type with `lanes` lanes of `element_type`, produced by `operation`.
+    fn simd_vector_of(
+        &self,
+        operation: &str,
+        element_type: &EaType,
+        lanes: usize,
+    ) -> Result<EaType> {
+        let vector_type = SIMDElementType::from_ea_type(element_type).and_then(|simd_element| {
+            crate::ast::SIMDVectorType::from_element_type(&element_type.to_string(), lanes)
+                .map(|vector_type| (simd_element, vector_type))
+        });
+        let Some((simd_element, vector_type)) = vector_type else {
+            return Err(CompileError::type_error(
+                format!(
+                    "{}() would produce {} lanes of {}, which is not a SIMD vector type",
+                    operation, lanes, element_type
+                ),
+                Position::new(0, 0, 0),
+            ));
+        };
+
+        if !self.hardware_detector.is_supported(&vector_type) {
+            return Err(CompileError::type_error(
+                format!(
+                    "SIMD vector type {} is not supported on target architecture {}",
+                    vector_type,
+                    self.hardware_detector.target_arch()
+                ),
+                Position::new(0, 0, 0),
+            ));
+        }
+
+        Ok(EaType::SIMDVector {
+            element_type: simd_element,
+            width: lanes,
+            vector_type,
+        })
+    }
+
+    /// The vector type `simd<element_type>` resolves to: the widest vector of
+    /// `element_type` the target supports.
+    fn native_vector_type(&self, element_type: &str) -> Result<crate::ast::SIMDVectorType> {
+        if let Some(vector_type) = self.hardware_detector.native_vector_type(element_type) {
+            return Ok(vector_type);
+        }
+        let has_vector_types = [2, 4, 8, 16, 32, 64].iter().any(|&width| {
+            crate::ast::SIMDVectorType::from_element_type(element_type, width).is_some()
+        });
+        let message = if has_vector_types {
+            format!(
+                "simd<{}> has no vector width on target architecture {}",
+                element_type,
+                self.hardware_detector.target_arch()
+            )
+        } else {
+            format!(
+                "simd<{}> is not a SIMD vector type; lanes must be a number type such as f32",
+                element_type
+            )
+        };
+        Err(CompileError::type_error(message, Position::new(0, 0, 0)))
+    }
+
+    /// A raw pointer passed to `load_vector`/`store_vector` must be `unsafe` and
+    /// point at the vector's elements.
+    fn check_raw_vector_address(
+        &self,
+        target: &EaType,
+        element_type: &EaType,
+        operation: &str,
+    ) -> Result<()> {
+        self.require_unsafe(&format!("{} through a raw pointer", operation))?;
+        let points_at_elements = match target {
+            EaType::SIMDVector {
+                element_type: target_element,
+                ..
+            } => target_element.to_ea_type() == *element_type,
+            _ => target == element_type,
+        };
+        if !points_at_elements {
+            return Err(CompileError::type_error(
+                format!(
+                    "{} through *{} does not match vector element type {}",
+                    operation, target, element_type
+                ),
+                Position::new(0, 0, 0),
+            ));
+        }
+        Ok(())
+    }
+
     /// Convert SIMD vector type to corresponding element type
     pub fn simd_vector_type_to_element_type(
         &self,
@@ -127,6 +127,13 @@ pub enum SIMDValidationError {
         vector_type: SIMDVectorType,
         position: Position,
     },
+    
+    /// Mask lane count differs from the vector it selects lanes of
+    MaskWidthMismatch {
+        mask_type: SIMDVectorType,
+        vector_type: SIMDVectorType,
+        position: Position,
+    },
 }
 
 pub type ValidationResult<T> = Result<T, SIMDValidationError>;
@@ -148,16 +155,16 @@ impl SIMDValidator {
     /// Validate an expression and return its type
     pub fn validate_expression(&self, expr: &Expr) -> ValidationResult<EaType> {
         match expr {
-            Expr::Literal(literal) => Ok(self.literal_type(literal)),
-            Expr::Variable(name) => self.validate_variable(name),
+            Expr::Literal(literal, _) => Ok(self.literal_type(literal)),
+            Expr::Variable(name, _) => self.validate_variable(name),
             Expr::Binary { left, op, right } => self.validate_binary_expr(left, op, right),
             Expr::Unary { op, expr } => self.validate_unary_expr(op, expr),
-            Expr::SIMD(simd_expr) => self.validate_simd_expression(simd_expr),
+            Expr::SIMD(simd_expr, _) => self.validate_simd_expression(simd_expr),
             Expr::Call { name, args } => self.validate_function_call(name, args),
             Expr::Index { expr, index } => self.validate_index_expr(expr, index),
             Expr::FieldAccess { expr, field } => self.validate_field_access(expr, field),
             Expr::Assignment { target, value } => self.validate_assignment(target, value),
-            Expr::Grouping(expr) => self.validate_expression(expr),
+            Expr::Grouping(expr, _) => self.validate_expression(expr),
         }
     }
     
@@ -183,6 +190,22 @@ impl SIMDValidator {
             SIMDExpr::Reduction { vector, operation, position } => {
                 self.validate_reduction_operation(vector, operation, position)
             }
+            
+            SIMDExpr::Select { mask, if_true, if_false, position } => {
+                self.validate_select_operation(mask, if_true, if_false, position)
+            }
+            
+            SIMDExpr::MaskedLoad { mask, passthru, position, .. } => {
+                let passthru_type = self.validate_vector_operand("masked_load", passthru, position)?;
+                self.validate_mask(mask, &passthru_type, position)?;
+                Ok(EaType::SIMD(passthru_type))
+            }
+            
+            SIMDExpr::MaskedStore { vector, mask, position, .. } => {
+                let vector_type = self.validate_vector_operand("masked_store", vector, position)?;
+                self.validate_mask(mask, &vector_type, position)?;
+                Ok(EaType::Inferred)
+            }
         }
     }
     
@@ -422,8 +445,68 @@ impl SIMDValidator {
         Ok(scalar_type)
     }
     
+    /// Validate select(mask, a, b)
+    fn validate_select_operation(
+        &self,
+        mask: &Expr,
+        if_true: &Expr,
+        if_false: &Expr,
+        position: &Position
+    ) -> ValidationResult<EaType> {
+        let true_type = self.validate_vector_operand("select", if_true, position)?;
+        let false_type = self.validate_vector_operand("select", if_false, position)?;
+        
+        if !true_type.is_compatible_with(&false_type) {
+            return Err(SIMDValidationError::TypeMismatch {
+                expected: true_type,
+                found: false_type,
+                position: *position,
+            });
+        }
+        
+        self.validate_mask(mask, &true_type, position)?;
+        Ok(EaType::SIMD(true_type))
+    }
+    
     /// Helper methods for validation
     
+    fn validate_vector_operand(
+        &self,
+        operation: &str,
+        operand: &Expr,
+        position: &Position
+    ) -> ValidationResult<SIMDVectorType> {
+        match self.validate_expression(operand)? {
+            EaType::SIMD(vector_type) => Ok(vector_type),
+            scalar_type => Err(SIMDValidationError::VectorOnScalar {
+                operation: operation.to_string(),
+                scalar_type,
+                position: *position,
+            }),
+        }
+    }
+    
+    /// A mask needs one lane for every lane of the vector it applies to
+    fn validate_mask(
+        &self,
+        mask: &Expr,
+        vector_type: &SIMDVectorType,
+        position: &Position
+    ) -> ValidationResult<()> {
+        let mask_type = self.validate_vector_operand("mask", mask, position)?;
+        
+        if mask_type.width() != vector_type.width() {
+            return Err(SIMDValidationError::MaskWidthMismatch {
+                mask_type,
+                vector_type: vector_type.clone(),
+                position: *position,
+            });
+        }
+        
+        Ok(())
+    }
+    
+    
     fn validate_hardware_support(
         &self, 
         vector_type: &SIMDVectorType, 
@@ -873,6 +956,11 @@ impl std::fmt::Display for SIMDValidationError {
                 write!(f, "Unsupported operation at {}:{}: '{}' not supported for {}", 
                        position.line, position.column, operation, vector_type)
             }
+            SIMDValidationError::MaskWidthMismatch { mask_type, vector_type, position } => {
+                write!(f, "Mask width mismatch at {}:{}: {} has {} lanes but {} has {}", 
+                       position.line, position.column, mask_type, mask_type.width(),
+                       vector_type, vector_type.width())
+            }
         }
     }
 }
@@ -893,6 +981,7 @@ pub enum UnaryOp {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::lexer::Span;
 
     #[test]
     fn test_vector_literal_validation() {
@@ -900,10 +989,10 @@ mod tests {
         
         // Valid f32x4 literal
         let elements = vec![
-            Expr::Literal(Literal::Float(1.0)),
-            Expr::Literal(Literal::Float(2.0)),
-            Expr::Literal(Literal::Float(3.0)),
-            Expr::Literal(Literal::Float(4.0)),
+            Expr::Literal(Literal::Float(1.0), Span::default()),
+            Expr::Literal(Literal::Float(2.0), Span::default()),
+            Expr::Literal(Literal::Float(3.0), Span::default()),
+            Expr::Literal(Literal::Float(4.0), Span::default()),
         ];
         
         let result = validator.validate_vector_literal(
@@ -922,8 +1011,8 @@ mod tests {
         validator.add_variable("vec1".to_string(), EaType::SIMD(SIMDVectorType::F32x4));
         validator.add_variable("vec2".to_string(), EaType::SIMD(SIMDVectorType::F32x4));
         
-        let left = Expr::Variable("vec1".to_string());
-        let right = Expr::Variable("vec2".to_string());
+        let left = Expr::Variable("vec1".to_string(), Span::default());
+        let right = Expr::Variable("vec2".to_string(), Span::default());
         let operator = SIMDOperator::DotAdd;
         let position = Position { line: 1, column: 1 };
         
@@ -951,7 +1040,7 @@ mod tests {
         let mut validator = SIMDValidator::new(vec![HardwareFeature::AVX]);
         validator.add_variable("vec".to_string(), EaType::SIMD(SIMDVectorType::F32x4));
         
-        let vector = Expr::Variable("vec".to_string());
+        let vector = Expr::Variable("vec".to_string(), Span::default());
         let pattern = SwizzlePattern::Named("xyz".to_string());
         let position = Position { line: 1, column: 1 };
         
@@ -967,7 +1056,7 @@ mod tests {
         let mut validator = SIMDValidator::new(vec![HardwareFeature::AVX]);
         validator.add_variable("vec".to_string(), EaType::SIMD(SIMDVectorType::F32x4));
         
-        let vector = Expr::Variable("vec".to_string());
+        let vector = Expr::Variable("vec".to_string(), Span::default());
         let operation = ReductionOp::Sum;
         let position = Position { line: 1, column: 1 };
         
@@ -984,8 +1073,8 @@ mod tests {
         validator.add_variable("vec1".to_string(), EaType::SIMD(SIMDVectorType::F32x4));
         validator.add_variable("vec2".to_string(), EaType::SIMD(SIMDVectorType::I32x4));
         
-        let left = Expr::Variable("vec1".to_string());
-        let right = Expr::Variable("vec2".to_string());
+        let left = Expr::Variable("vec1".to_string(), Span::default());
+        let right = Expr::Variable("vec2".to_string(), Span::default());
         let operator = SIMDOperator::DotAdd;
         let position = Position { line: 1, column: 1 };
         
@@ -998,4 +1087,25 @@ mod tests {
             panic!("Expected IncompatibleOperation error");
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_select_mask_width() {
+        let mut validator = SIMDValidator::new(vec![HardwareFeature::AVX]);
+        validator.add_variable("mask".to_string(), EaType::SIMD(SIMDVectorType::I32x8));
+        validator.add_variable("a".to_string(), EaType::SIMD(SIMDVectorType::F32x4));
+        validator.add_variable("b".to_string(), EaType::SIMD(SIMDVectorType::F32x4));
+        
+        let mask = Expr::Variable("mask".to_string(), Span::default());
+        let a = Expr::Variable("a".to_string(), Span::default());
+        let b = Expr::Variable("b".to_string(), Span::default());
+        let position = Position { line: 1, column: 1 };
+        
+        let result = validator.validate_select_operation(&mask, &a, &b, &position);
+        
+        if let Err(SIMDValidationError::MaskWidthMismatch { .. }) = result {
+            // Expected error: 8 mask lanes for a 4 lane vector
+        } else {
+            panic!("Expected MaskWidthMismatch error");
+        }
+    }
+}
@@ -137,6 +137,1490 @@ func bad_function() -> i32 {
     let _ = std::fs::remove_file("test_error.ll");
 }
 
+#[cfg(feature = "llvm")]
+#[test]
+fn test_compile_generic_instantiations() {
+    let source = r#"
+struct Pair<T> {
+    first: T,
+    second: T,
+}
+
+func sum<T>(a: T, b: T) -> T {
+    return a + b;
+}
+
+func main() -> i32 {
+    let ints = sum(1, 2);
+    let floats = sum(1.5, 2.5);
+    let pair = Pair { first: ints, second: 3 };
+    return sum(pair.first, pair.second);
+}
+"#;
+
+    let result = compile_to_llvm(source, "test_generics");
+    assert!(result.is_ok(), "Generic code should compile to LLVM");
+
+    // One instance per distinct set of type arguments
+    let ir = std::fs::read_to_string("test_generics.ll").unwrap_or_default();
+    assert!(ir.contains("sum<i32>"));
+    assert!(ir.contains("sum<f32>"));
+
+    let _ = std::fs::remove_file("test_generics.ll");
+}
+
+#[cfg(feature = "llvm")]
+#[test]
+fn test_compile_generic_instances_follow_checked_types() {
+    let source = r#"
+struct Meters {
+    value: f32,
+}
+
+struct Seconds {
+    value: f32,
+}
+
+func id<T>(x: T) -> T {
+    return x;
+}
+
+func main() -> i32 {
+    let distance = Meters { value: 1.0 };
+    let time = Seconds { value: 2.0 };
+    let small: u8 = 200;
+    let a = id(distance);
+    let b = id(time);
+    let c = id(small);
+    return 0;
+}
+"#;
+
+    let result = compile_to_llvm(source, "test_generic_instances");
+    assert!(result.is_ok(), "Generic calls should compile to LLVM");
+
+    // Neither the layout nor the width of an argument decides the instance
+    let ir = std::fs::read_to_string("test_generic_instances.ll").unwrap_or_default();
+    assert!(ir.contains("id<Meters>"));
+    assert!(ir.contains("id<Seconds>"));
+    assert!(ir.contains("id<u8>"));
+    assert!(!ir.contains("id<i8>"));
+
+    let _ = std::fs::remove_file("test_generic_instances.ll");
+}
+
+#[cfg(feature = "llvm")]
+#[test]
+fn test_compile_trait_impl() {
+    let source = r#"
+trait Shape {
+    func area(self) -> f32;
+}
+
+struct Square {
+    side: f32,
+}
+
+impl Shape for Square {
+    func area(self) -> f32 {
+        return self.side * self.side;
+    }
+}
+
+func main() -> i32 {
+    let s = Square { side: 3.0 };
+    let a = s.area();
+    return 0;
+}
+"#;
+
+    let result = compile_to_llvm(source, "test_traits");
+    assert!(result.is_ok(), "Trait impls should compile to LLVM");
+
+    let ir = std::fs::read_to_string("test_traits.ll").unwrap_or_default();
+    assert!(ir.contains("Square::Shape::area"));
+
+    let _ = std::fs::remove_file("test_traits.ll");
+}
+
+#[cfg(feature = "llvm")]
+#[test]
+fn test_compile_trait_impls_same_layout() {
+    use ea_compiler::codegen::CodegenOptions;
+    use ea_compiler::compile_to_native;
+    use ea_compiler::linker::{EmitKind, LinkOptions};
+
+    let source = r#"
+trait Shape {
+    func area(self) -> f32;
+}
+
+struct Circle {
+    radius: f32,
+}
+
+struct Square {
+    side: f32,
+}
+
+impl Shape for Circle {
+    func area(self) -> f32 {
+        return 3.0 * self.radius * self.radius;
+    }
+}
+
+impl Shape for Square {
+    func area(self) -> f32 {
+        return self.side * self.side;
+    }
+}
+
+func main() -> i32 {
+    let circle = Circle { radius: 2.0 };
+    let square = Square { side: 2.0 };
+    if (circle.area() != 12.0) {
+        return 1;
+    }
+    if (square.area() != 4.0) {
+        return 2;
+    }
+    return 0;
+}
+"#;
+
+    // Both structs are a single f32; each call must still reach its own impl
+    let dir = tempfile::tempdir().unwrap();
+    let program = dir.path().join("shapes");
+    compile_to_native(
+        source,
+        &program,
+        &[EmitKind::Executable],
+        &CodegenOptions::default(),
+        &LinkOptions::default(),
+    )
+    .expect("trait impls should compile");
+    let status = std::process::Command::new(&program).status().unwrap();
+    assert_eq!(status.code(), Some(0));
+}
+
+#[cfg(feature = "llvm")]
+#[test]
+fn test_compile_impl_methods() {
+    let source = r#"
+struct Point {
+    x: f32,
+    y: f32,
+}
+
+impl Point {
+    func new(x: f32, y: f32) -> Point {
+        return Point { x: x, y: y };
+    }
+    func dot(self, other: Point) -> f32 {
+        return self.x * other.x + self.y * other.y;
+    }
+}
+
+func main() -> i32 {
+    let p = Point::new(1.0, 2.0);
+    let d = p.dot(p);
+    return 0;
+}
+"#;
+
+    let result = compile_to_llvm(source, "test_impl_methods");
+    assert!(result.is_ok(), "Impl methods should compile to LLVM");
+
+    let ir = std::fs::read_to_string("test_impl_methods.ll").unwrap_or_default();
+    assert!(ir.contains("Point::new"));
+    assert!(ir.contains("Point::dot"));
+
+    let _ = std::fs::remove_file("test_impl_methods.ll");
+}
+
+#[cfg(feature = "llvm")]
+#[test]
+fn test_compile_impl_methods_same_layout() {
+    use ea_compiler::codegen::CodegenOptions;
+    use ea_compiler::compile_to_native;
+    use ea_compiler::linker::{EmitKind, LinkOptions};
+
+    let source = r#"
+struct Celsius {
+    degrees: f32,
+}
+
+struct Fahrenheit {
+    degrees: f32,
+}
+
+impl Celsius {
+    func kelvin(self) -> f32 {
+        return self.degrees + 273.0;
+    }
+}
+
+impl Fahrenheit {
+    func kelvin(self) -> f32 {
+        return (self.degrees - 32.0) * 5.0 / 9.0 + 273.0;
+    }
+}
+
+func main() -> i32 {
+    let celsius = Celsius { degrees: 32.0 };
+    let fahrenheit = Fahrenheit { degrees: 32.0 };
+    if (celsius.kelvin() != 305.0) {
+        return 1;
+    }
+    if (fahrenheit.kelvin() != 273.0) {
+        return 2;
+    }
+    return 0;
+}
+"#;
+
+    // Both structs wrap one f32; each call must still reach its own method
+    let dir = tempfile::tempdir().unwrap();
+    let program = dir.path().join("temperatures");
+    compile_to_native(
+        source,
+        &program,
+        &[EmitKind::Executable],
+        &CodegenOptions::default(),
+        &LinkOptions::default(),
+    )
+    .expect("impl methods should compile");
+    let status = std::process::Command::new(&program).status().unwrap();
+    assert_eq!(status.code(), Some(0));
+}
+
+#[cfg(feature = "llvm")]
+#[test]
+fn test_compile_constants() {
+    let source = r#"
+const LANES: i32 = 4;
+const BIAS: i32 = LANES * 10 + 2;
+
+func main() -> i32 {
+    let ones = [1.0; LANES]f32x4;
+    return BIAS;
+}
+"#;
+
+    let result = compile_to_llvm(source, "test_constants");
+    assert!(result.is_ok(), "Constants should compile to LLVM");
+
+    // Folded at compile time: no alloca for the constant, just the value
+    let ir = std::fs::read_to_string("test_constants.ll").unwrap_or_default();
+    assert!(ir.contains("ret i32 42"));
+    assert!(!ir.contains("%BIAS"));
+
+    let _ = std::fs::remove_file("test_constants.ll");
+}
+
+#[cfg(feature = "llvm")]
+#[test]
+fn test_compile_comptime() {
+    let source = r#"
+comptime func factorial(n: i32) -> i32 {
+    if (n <= 1) {
+        return 1;
+    }
+    return n * factorial(n - 1);
+}
+
+func main() -> i32 {
+    let table = comptime {
+        let t = [0; 4];
+        for (let mut i: i32 = 0; i < 4; i += 1) {
+            t[i] = factorial(i + 1);
+        }
+        t;
+    };
+    let sines = comptime { generate_lookup_table("sin", 0.0, 3.0, 4); };
+    return table[3] + comptime { factorial(5); };
+}
+"#;
+
+    let result = compile_to_llvm(source, "test_comptime");
+    assert!(result.is_ok(), "comptime blocks should compile to LLVM");
+
+    // Tables become private constants; the comptime function itself is never emitted
+    let ir = std::fs::read_to_string("test_comptime.ll").unwrap_or_default();
+    assert!(ir.contains("private unnamed_addr constant [4 x i32] [i32 1, i32 2, i32 6, i32 24]"));
+    assert!(ir.contains("constant [4 x float]"));
+    assert!(ir.contains("120"));
+    assert!(!ir.contains("@factorial"));
+
+    let _ = std::fs::remove_file("test_comptime.ll");
+}
+
+#[cfg(feature = "llvm")]
+#[test]
+fn test_compile_tuples() {
+    let source = r#"
+func divmod(a: i32, b: i32) -> (i32, i32) {
+    return (a / b, a % b);
+}
+
+func main() -> i32 {
+    let (q, r) = divmod(17, 5);
+    let nested = ((q, r), true);
+    let (pair, _) = nested;
+    return pair.0 * 10 + nested.0.1;
+}
+"#;
+
+    let result = compile_to_llvm(source, "test_tuples");
+    assert!(result.is_ok(), "tuple programs should compile to LLVM");
+
+    // Tuples are lowered to anonymous structs
+    let ir = std::fs::read_to_string("test_tuples.ll").unwrap_or_default();
+    assert!(ir.contains("define { i32, i32 } @divmod"));
+    assert!(ir.contains("extractvalue"));
+
+    let _ = std::fs::remove_file("test_tuples.ll");
+}
+
+#[cfg(feature = "llvm")]
+#[test]
+fn test_compile_closures() {
+    let source = r#"
+func double(x: i32) -> i32 {
+    return x * 2;
+}
+
+func make_adder(offset: i32) -> func(i32) -> i32 {
+    return |x: i32| -> i32 { x + offset };
+}
+
+func main() -> i32 {
+    let threshold = 5;
+    let values = Vec::new();
+    values.push(1);
+    values.push(4);
+    let doubled = values.map(double);
+    let large = doubled.filter(|x: i32| x > threshold);
+    let total = large.reduce(0, |acc: i32, x: i32| acc + x);
+    let add_one = make_adder(1);
+    return add_one(total);
+}
+"#;
+
+    let result = compile_to_llvm(source, "test_closures");
+    assert!(result.is_ok(), "closure programs should compile to LLVM");
+
+    // Closures are a code pointer paired with their environment
+    let ir = std::fs::read_to_string("test_closures.ll").unwrap_or_default();
+    assert!(ir.contains("define { i32 (i8*, i32)*, i8* } @make_adder"));
+    assert!(ir.contains("@__closure_1(i8*"));
+    assert!(ir.contains("@__fn_value_double"));
+
+    let _ = std::fs::remove_file("test_closures.ll");
+}
+
+#[cfg(feature = "llvm")]
+#[test]
+fn test_compile_closure_environments() {
+    use ea_compiler::codegen::CodegenOptions;
+    use ea_compiler::compile_to_native;
+    use ea_compiler::linker::{EmitKind, LinkOptions};
+
+    let source = r#"
+func apply(f: func(i32) -> i32, x: i32) -> i32 {
+    return f(x);
+}
+
+func make_adder(offset: i32) -> func(i32) -> i32 {
+    return |x: i32| -> i32 { x + offset };
+}
+
+func main() -> i32 {
+    let base = 2;
+    let outer = |x: i32| {
+        let inner = |y: i32| y + base;
+        inner(x) * 2
+    };
+    let add_one = make_adder(1);
+    return apply(outer, 3) + add_one(0);
+}
+"#;
+
+    let result = compile_to_llvm(source, "test_closure_environments");
+    assert!(result.is_ok(), "closure programs should compile to LLVM");
+
+    // Only the closure returned by make_adder needs its environment on the heap,
+    // and the nested closure is generated once
+    let ir = std::fs::read_to_string("test_closure_environments.ll").unwrap_or_default();
+    let function = |name: &str| {
+        let start = ir.find(&format!("@{}(", name)).unwrap();
+        ir[start..].split("\n}\n").next().unwrap().to_string()
+    };
+    assert!(function("make_adder").contains("@malloc"));
+    assert!(!function("main").contains("@malloc"));
+    assert!(ir.contains("@__closure_3("));
+    assert!(!ir.contains("@__closure_4("));
+    assert!(!ir.contains("__closure_probe"));
+    let _ = std::fs::remove_file("test_closure_environments.ll");
+
+    let dir = tempfile::tempdir().unwrap();
+    let program = dir.path().join("closures");
+    compile_to_native(
+        source,
+        &program,
+        &[EmitKind::Executable],
+        &CodegenOptions::default(),
+        &LinkOptions::default(),
+    )
+    .expect("closures should compile");
+    let status = std::process::Command::new(&program).status().unwrap();
+    assert_eq!(status.code(), Some(11));
+}
+
+#[cfg(feature = "llvm")]
+#[test]
+fn test_compile_rich_patterns() {
+    let source = r#"
+enum Shape {
+    Circle(i32),
+    Square(i32),
+    Empty,
+}
+
+func size(shape: Shape, n: i32) -> i32 {
+    let base = match shape {
+        Shape::Circle(r) if r > 10 => 3,
+        Shape::Circle(_) | Shape::Square(_) => 2,
+        Shape::Empty => 0,
+    };
+    let scale = match n {
+        0 => 0,
+        1..=9 => 1,
+        _ => 2,
+    };
+    return base + scale;
+}
+
+func main() -> i32 {
+    return size(Shape::Empty, 5);
+}
+"#;
+
+    let result = compile_to_llvm(source, "test_rich_patterns");
+    assert!(result.is_ok(), "rich patterns should compile to LLVM");
+
+    // Variants are dispatched on their tag; the guard and range become branches
+    let ir = std::fs::read_to_string("test_rich_patterns.ll").unwrap_or_default();
+    assert!(ir.contains("switch i32"));
+    assert!(ir.contains("match_guard_failed"));
+    assert!(ir.contains("pattern_in_range"));
+
+    let _ = std::fs::remove_file("test_rich_patterns.ll");
+}
+
+#[cfg(feature = "llvm")]
+#[test]
+fn test_compile_unmatched_value() {
+    use ea_compiler::codegen::CodegenOptions;
+    use ea_compiler::compile_to_native;
+    use ea_compiler::linker::{EmitKind, LinkOptions};
+
+    let source = r#"
+func pick(n: i32) -> i32 {
+    match n {
+        0 => { return 1; }
+        1 => { return 2; }
+    }
+    return 3;
+}
+
+func main() -> i32 {
+    if (pick(0) != 1 || pick(1) != 2) {
+        return 1;
+    }
+    return pick(7);
+}
+"#;
+
+    // Every arm returns, yet a value no arm matches carries on after the match
+    let dir = tempfile::tempdir().unwrap();
+    let program = dir.path().join("unmatched");
+    compile_to_native(
+        source,
+        &program,
+        &[EmitKind::Executable],
+        &CodegenOptions::default(),
+        &LinkOptions::default(),
+    )
+    .expect("match without a `_` arm should compile");
+    let status = std::process::Command::new(&program).status().unwrap();
+    assert_eq!(status.code(), Some(3));
+}
+
+#[cfg(feature = "llvm")]
+#[test]
+fn test_compile_fields_of_structs_with_the_same_layout() {
+    use ea_compiler::codegen::CodegenOptions;
+    use ea_compiler::compile_to_native;
+    use ea_compiler::linker::{EmitKind, LinkOptions};
+
+    let source = r#"
+struct A {
+    x: i32,
+    y: i32,
+}
+
+struct B {
+    y: i32,
+    x: i32,
+}
+
+func second_of_a(a: A) -> i32 {
+    let second = match a {
+        A { x: 1, y } => y,
+        A { x, .. } => x,
+    };
+    return second;
+}
+
+func second_of_b(b: B) -> i32 {
+    let second = match b {
+        B { x: 10, y } => y,
+        B { x, .. } => x,
+    };
+    return second;
+}
+
+func main() -> i32 {
+    let a = A { x: 1, y: 2 };
+    let b = B { y: 20, x: 10 };
+    if (a.x != 1 || b.x != 10) {
+        return 1;
+    }
+    if (second_of_a(a) != 2 || second_of_b(b) != 20) {
+        return 2;
+    }
+    return 0;
+}
+"#;
+
+    // Both structs are two i32s; fields are found by name in their own declaration
+    let dir = tempfile::tempdir().unwrap();
+    let program = dir.path().join("fields");
+    compile_to_native(
+        source,
+        &program,
+        &[EmitKind::Executable],
+        &CodegenOptions::default(),
+        &LinkOptions::default(),
+    )
+    .expect("field accesses should compile");
+    let status = std::process::Command::new(&program).status().unwrap();
+    assert_eq!(status.code(), Some(0));
+}
+
+#[cfg(feature = "llvm")]
+#[test]
+fn test_compile_raw_pointers() {
+    let source = r#"
+func scale4(dst: *mut f32, src: *f32) {
+    unsafe {
+        let v = load_vector(src, f32x4);
+        store_vector(dst, v);
+        *dst.offset(3) = *src.offset(3) * 2.0;
+    }
+}
+
+func main() -> i32 {
+    let values: [f32; 4] = [1.0, 2.0, 3.0, 4.0];
+    let mut out: [f32; 4] = [0.0, 0.0, 0.0, 0.0];
+    scale4(&out, &values);
+    return 0;
+}
+"#;
+
+    let result = compile_to_llvm(source, "test_raw_pointers");
+    assert!(result.is_ok(), "raw pointers should compile to LLVM");
+
+    // References to arrays decay to element pointers, and offsets index through them
+    let ir = std::fs::read_to_string("test_raw_pointers.ll").unwrap_or_default();
+    assert!(ir.contains("array_decay"));
+    assert!(ir.contains("getelementptr inbounds float, float*"));
+
+    let _ = std::fs::remove_file("test_raw_pointers.ll");
+}
+
+#[cfg(feature = "llvm")]
+#[test]
+fn test_compile_masked_simd() {
+    let source = r#"
+func clamp_tail(dst: *mut f32, src: *f32, limit: f32x4, count: i32x4) {
+    unsafe {
+        let in_range = [0, 1, 2, 3]i32x4 .< count;
+        let values = masked_load(src, in_range, [0.0, 0.0, 0.0, 0.0]f32x4);
+        let clamped = select(values .> limit, limit, values);
+        masked_store(dst, clamped, in_range);
+    }
+}
+
+func main() -> i32 {
+    let values: [f32; 4] = [1.0, 5.0, 2.0, 9.0];
+    let mut out: [f32; 4] = [0.0, 0.0, 0.0, 0.0];
+    clamp_tail(&out, &values, [4.0, 4.0, 4.0, 4.0]f32x4, [3, 3, 3, 3]i32x4);
+    return 0;
+}
+"#;
+
+    let result = compile_to_llvm(source, "test_masked_simd");
+    assert!(
+        result.is_ok(),
+        "masked SIMD operations should compile to LLVM"
+    );
+
+    let ir = std::fs::read_to_string("test_masked_simd.ll").unwrap_or_default();
+    assert!(ir.contains("@llvm.masked.load.v4f32"));
+    assert!(ir.contains("@llvm.masked.store.v4f32"));
+    assert!(ir.contains("select <4 x i1>"));
+
+    let _ = std::fs::remove_file("test_masked_simd.ll");
+}
+
+#[cfg(feature = "llvm")]
+#[test]
+fn test_compile_gather_scatter_shuffle() {
+    let source = r#"
+func interleave(dst: *mut f32, src: *f32, a: f32x4, b: f32x4) -> f32x4 {
+    unsafe {
+        let rows = [0, 4, 8, 12]i32x4;
+        let column = gather(src, rows);
+        scatter(dst, rows, column);
+    }
+    return shuffle(a, b, [0, 4, 1, 5]);
+}
+
+func main() -> i32 {
+    let values: [f32; 16] = [1.0; 16];
+    let mut out: [f32; 16] = [0.0; 16];
+    let mixed = interleave(&out, &values, [1.0, 2.0, 3.0, 4.0]f32x4, [5.0, 6.0, 7.0, 8.0]f32x4);
+    return 0;
+}
+"#;
+
+    let result = compile_to_llvm(source, "test_gather_shuffle");
+    assert!(
+        result.is_ok(),
+        "gather, scatter and shuffle should compile to LLVM"
+    );
+
+    let ir = std::fs::read_to_string("test_gather_shuffle.ll").unwrap_or_default();
+    assert!(ir.contains("@llvm.masked.gather.v4f32"));
+    assert!(ir.contains("@llvm.masked.scatter.v4f32"));
+    assert!(ir.contains("shufflevector <4 x float>"));
+
+    let _ = std::fs::remove_file("test_gather_shuffle.ll");
+}
+
+#[cfg(feature = "llvm")]
+#[test]
+fn test_compile_native_simd_width() {
+    use ea_compiler::type_system::hardware::HardwareDetector;
+
+    let source = r#"
+func scale(dst: *mut f32, src: *f32, len: i32, factor: simd<f32>) {
+    unsafe {
+        for (let i: i32 = 0; i < len; i += lanes<f32>()) {
+            let v = load_vector(src.offset(i), simd<f32>);
+            store_vector(dst.offset(i), v .* factor);
+        }
+    }
+}
+
+func main() -> i32 {
+    return lanes<f32>();
+}
+"#;
+
+    let result = compile_to_llvm(source, "test_native_simd");
+    assert!(result.is_ok(), "simd<f32> kernels should compile to LLVM");
+
+    // The kernel is as wide as the vector registers of the machine compiling it
+    let lanes = HardwareDetector::new()
+        .native_vector_type("f32")
+        .unwrap()
+        .width();
+    let ir = std::fs::read_to_string("test_native_simd.ll").unwrap_or_default();
+    assert!(ir.contains(&format!("<{} x float>", lanes)));
+    assert!(ir.contains(&format!("ret i32 {}", lanes)));
+
+    let _ = std::fs::remove_file("test_native_simd.ll");
+}
+
+#[cfg(feature = "llvm")]
+#[test]
+fn test_compile_target_clones() {
+    let source = r#"
+@target_clones(avx2, sse4)
+func sum_to(n: i32) -> i32 {
+    if (n == 0) {
+        return 0;
+    }
+    return n + sum_to(n - 1);
+}
+
+func main() -> i32 {
+    return sum_to(4);
+}
+"#;
+
+    let result = compile_to_llvm(source, "test_target_clones");
+    assert!(
+        result.is_ok(),
+        "@target_clones functions should compile to LLVM"
+    );
+
+    // One clone per feature level plus a baseline clone, picked with CPUID
+    let ir = std::fs::read_to_string("test_target_clones.ll").unwrap_or_default();
+    assert!(ir.contains("@sum_to.avx2"));
+    assert!(ir.contains("@sum_to.sse4"));
+    assert!(ir.contains("@sum_to.default"));
+    assert!(ir.contains("\"target-features\"=\"+avx2,+fma\""));
+    assert!(ir.contains("\"target-features\"=\"+sse4.1,+sse4.2\""));
+    assert!(ir.contains("@sum_to.resolved"));
+    assert!(ir.contains("cpuid"));
+
+    let _ = std::fs::remove_file("test_target_clones.ll");
+}
+
+#[cfg(feature = "llvm")]
+#[test]
+fn test_compile_extern_c_block() {
+    let source = r#"
+extern "C" {
+    func labs(x: i64) -> i64;
+}
+
+func main() -> i32 {
+    let magnitude = labs(0 - 42);
+    if (magnitude == 42) {
+        return 0;
+    }
+    return 1;
+}
+"#;
+
+    let result = compile_to_llvm(source, "test_extern_c");
+    assert!(result.is_ok(), "extern \"C\" blocks should compile to LLVM");
+
+    // Extern functions are plain external declarations, called by their C name
+    let ir = std::fs::read_to_string("test_extern_c.ll").unwrap_or_default();
+    assert!(ir.contains("declare i64 @labs(i64)"));
+
+    let _ = std::fs::remove_file("test_extern_c.ll");
+}
+
+#[cfg(feature = "llvm")]
+#[test]
+fn test_compile_native_executable() {
+    use ea_compiler::codegen::CodegenOptions;
+    use ea_compiler::compile_to_native;
+    use ea_compiler::linker::{EmitKind, LinkOptions};
+
+    let source = r#"
+func answer() -> i32 {
+    return 40 + 2;
+}
+
+func main() -> i32 {
+    return answer();
+}
+"#;
+
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("answer");
+    let written = compile_to_native(
+        source,
+        &output,
+        &[EmitKind::Object, EmitKind::Assembly, EmitKind::Executable],
+        &CodegenOptions::default(),
+        &LinkOptions::default(),
+    )
+    .expect("program should compile and link to a native executable");
+
+    assert_eq!(
+        written,
+        vec![output.with_extension("o"), output.with_extension("s"), output.clone()]
+    );
+    let asm = std::fs::read_to_string(output.with_extension("s")).unwrap();
+    assert!(asm.contains("main"));
+
+    let status = std::process::Command::new(&output).status().unwrap();
+    assert_eq!(status.code(), Some(42));
+}
+
+#[cfg(feature = "llvm")]
+#[test]
+fn test_cross_compile_aarch64() {
+    use ea_compiler::codegen::CodegenOptions;
+    use ea_compiler::compile_to_native;
+    use ea_compiler::linker::{EmitKind, LinkOptions};
+    use ea_compiler::type_system::hardware::CompileTarget;
+
+    let source = r#"
+func scale(v: simd<f32>, factor: simd<f32>) -> simd<f32> {
+    return v .+ factor;
+}
+
+func main() -> i32 {
+    return lanes<f32>();
+}
+"#;
+
+    let options = CodegenOptions {
+        target: CompileTarget {
+            triple: Some("aarch64-unknown-linux-gnu".to_string()),
+            cpu: Some("cortex-a72".to_string()),
+            features: None,
+        },
+        ..CodegenOptions::default()
+    };
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("scale");
+    compile_to_native(
+        source,
+        &output,
+        &[EmitKind::Object, EmitKind::Assembly],
+        &options,
+        &LinkOptions::default(),
+    )
+    .expect("programs should cross compile to aarch64 objects and assembly");
+
+    // An ELF object for EM_AARCH64 (183)
+    let object = std::fs::read(output.with_extension("o")).unwrap();
+    assert_eq!(&object[..4], b"\x7fELF");
+    assert_eq!(u16::from_le_bytes([object[18], object[19]]), 183);
+
+    // simd<f32> is a 128-bit NEON vector of four lanes
+    let asm = std::fs::read_to_string(output.with_extension("s")).unwrap();
+    assert!(asm.contains("fadd") && asm.contains(".4s"), "{}", asm);
+
+    // The runtime executables link against is only built for the host
+    let error = compile_to_native(
+        source,
+        &output,
+        &[EmitKind::Executable],
+        &options,
+        &LinkOptions::default(),
+    )
+    .unwrap_err();
+    assert!(error.to_string().contains("Cannot link for target"));
+}
+
+#[cfg(feature = "llvm")]
+#[test]
+fn test_cli_cross_compile_aarch64() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = dir.path().join("scale.ea");
+    std::fs::write(
+        &input,
+        r#"
+func scale(v: simd<f32>, factor: simd<f32>) -> simd<f32> {
+    return v .+ factor;
+}
+
+func main() -> i32 {
+    return lanes<f32>();
+}
+"#,
+    )
+    .unwrap();
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_ea"))
+        .current_dir(dir.path())
+        .args([
+            "--target",
+            "aarch64-unknown-linux-gnu",
+            "--target-cpu",
+            "cortex-a72",
+            "--emit=obj",
+            "-o",
+            "scale",
+            "scale.ea",
+        ])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // An ELF object for EM_AARCH64 (183)
+    let object = std::fs::read(dir.path().join("scale.o")).unwrap();
+    assert_eq!(&object[..4], b"\x7fELF");
+    assert_eq!(u16::from_le_bytes([object[18], object[19]]), 183);
+
+    // Code for another machine cannot run on the host
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_ea"))
+        .current_dir(dir.path())
+        .args(["--run", "--target", "aarch64-unknown-linux-gnu", "scale.ea"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("cannot be combined with --target"));
+}
+
+#[cfg(feature = "llvm")]
+#[test]
+fn test_compile_c_library() {
+    use ea_compiler::codegen::CodegenOptions;
+    use ea_compiler::compile_to_native;
+    use ea_compiler::linker::{CrateType, EmitKind, LinkOptions};
+
+    let source = r#"
+@export(name: "ea_sum")
+func sum(values: *f32, len: i64) -> f32 {
+    let total: f32 = 0.0;
+    for (let i: i64 = 0; i < len; i += 1) {
+        total = total + unsafe { *values.offset(i); };
+    }
+    return total;
+}
+"#;
+
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("kernels");
+    let written = compile_to_native(
+        source,
+        &output,
+        &[EmitKind::Executable],
+        &CodegenOptions::default(),
+        &LinkOptions {
+            crate_type: CrateType::Cdylib,
+            ..LinkOptions::default()
+        },
+    )
+    .expect("exported functions should link into a shared library");
+
+    let library = CrateType::Cdylib.output_path(&output);
+    let header = output.with_extension("h");
+    assert_eq!(written, vec![library.clone(), header.clone()]);
+    let header_text = std::fs::read_to_string(&header).unwrap();
+    assert!(header_text.contains("float ea_sum(const float* values, int64_t len);"));
+
+    // A C program calls the kernel through the generated header
+    let program = dir.path().join("main.c");
+    std::fs::write(
+        &program,
+        "#include \"kernels.h\"\n\
+         int main(void) {\n\
+             float values[4] = {1.0f, 2.0f, 3.0f, 4.5f};\n\
+             return (int)ea_sum(values, 4);\n\
+         }\n",
+    )
+    .unwrap();
+    let executable = dir.path().join("main");
+    let status = std::process::Command::new("cc")
+        .arg(&program)
+        .arg(&library)
+        .arg(format!("-Wl,-rpath,{}", dir.path().display()))
+        .arg("-o")
+        .arg(&executable)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let status = std::process::Command::new(&executable).status().unwrap();
+    assert_eq!(status.code(), Some(10));
+}
+
+#[cfg(feature = "llvm")]
+#[test]
+fn test_compile_exported_bools() {
+    use ea_compiler::codegen::CodegenOptions;
+    use ea_compiler::compile_to_native;
+    use ea_compiler::linker::{CrateType, EmitKind, LinkOptions};
+
+    let source = r#"
+@export(name: "ea_in_range")
+func in_range(x: i32, inclusive: bool) -> bool {
+    if (inclusive) {
+        return x >= 0 && x <= 10;
+    }
+    return x > 0 && x < 10;
+}
+"#;
+
+    // C passes and expects bools widened to a byte, which `i1` alone does not promise
+    let result = compile_to_llvm(source, "test_exported_bools");
+    assert!(result.is_ok(), "bool exports should compile to LLVM");
+    let ir = std::fs::read_to_string("test_exported_bools.ll").unwrap_or_default();
+    let _ = std::fs::remove_file("test_exported_bools.ll");
+    let definition = ir
+        .lines()
+        .find(|line| line.contains("@ea_in_range("))
+        .unwrap();
+    assert!(definition.contains("zeroext i1 @ea_in_range("));
+    assert!(definition.contains("i1 zeroext"));
+
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("ranges");
+    compile_to_native(
+        source,
+        &output,
+        &[EmitKind::Executable],
+        &CodegenOptions::default(),
+        &LinkOptions {
+            crate_type: CrateType::Cdylib,
+            ..LinkOptions::default()
+        },
+    )
+    .expect("bool exports should link into a shared library");
+    let header_text = std::fs::read_to_string(output.with_extension("h")).unwrap();
+    assert!(header_text.contains("bool ea_in_range(int32_t x, bool inclusive);"));
+
+    let program = dir.path().join("main.c");
+    std::fs::write(
+        &program,
+        "#include \"ranges.h\"\n\
+         int main(void) {\n\
+             return ea_in_range(10, true) + 2 * ea_in_range(10, false);\n\
+         }\n",
+    )
+    .unwrap();
+    let executable = dir.path().join("main");
+    let status = std::process::Command::new("cc")
+        .arg(&program)
+        .arg(CrateType::Cdylib.output_path(&output))
+        .arg(format!("-Wl,-rpath,{}", dir.path().display()))
+        .arg("-o")
+        .arg(&executable)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let status = std::process::Command::new(&executable).status().unwrap();
+    assert_eq!(status.code(), Some(1));
+}
+
+#[cfg(feature = "llvm")]
+#[test]
+fn test_compile_c_library_from_modules() {
+    use ea_compiler::codegen::CodegenOptions;
+    use ea_compiler::linker::{CrateType, EmitKind, LinkOptions};
+    use ea_compiler::{compile_modules_to_ast, compile_modules_to_native};
+
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("math.ea"),
+        r#"
+@export(name: "ea_add")
+func add(a: i32, b: i32) -> i32 {
+    return a + b;
+}
+"#,
+    )
+    .unwrap();
+    let entry = dir.path().join("kernels.ea");
+    std::fs::write(
+        &entry,
+        r#"
+import math;
+
+@export(name: "ea_twice")
+func twice(x: i32) -> i32 {
+    return math::add(x, x);
+}
+"#,
+    )
+    .unwrap();
+
+    let (modules, type_context) = compile_modules_to_ast(&entry, &[]).unwrap();
+    let output = dir.path().join("kernels");
+    compile_modules_to_native(
+        &modules,
+        &type_context,
+        &output,
+        &[EmitKind::Executable],
+        &CodegenOptions::default(),
+        &LinkOptions {
+            crate_type: CrateType::Cdylib,
+            ..LinkOptions::default()
+        },
+    )
+    .expect("exported functions of every module should link into one library");
+
+    // The header declares the exports of the imported module as well
+    let header_text = std::fs::read_to_string(output.with_extension("h")).unwrap();
+    assert!(header_text.contains("int32_t ea_add(int32_t a, int32_t b);"));
+    assert!(header_text.contains("int32_t ea_twice(int32_t x);"));
+
+    let program = dir.path().join("main.c");
+    std::fs::write(
+        &program,
+        "#include \"kernels.h\"\n\
+         int main(void) {\n\
+             return ea_add(ea_twice(10), 1);\n\
+         }\n",
+    )
+    .unwrap();
+    let executable = dir.path().join("main");
+    let status = std::process::Command::new("cc")
+        .arg(&program)
+        .arg(CrateType::Cdylib.output_path(&output))
+        .arg(format!("-Wl,-rpath,{}", dir.path().display()))
+        .arg("-o")
+        .arg(&executable)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let status = std::process::Command::new(&executable).status().unwrap();
+    assert_eq!(status.code(), Some(21));
+}
+
+#[cfg(feature = "llvm")]
+#[test]
+fn test_compile_generics_traits_and_impls_across_modules() {
+    use ea_compiler::codegen::CodegenOptions;
+    use ea_compiler::linker::{EmitKind, LinkOptions};
+    use ea_compiler::{compile_modules_to_ast, compile_modules_to_native};
+
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("geometry.ea"),
+        r#"
+trait Area {
+    func area(self) -> i32;
+}
+
+struct Square {
+    side: i32,
+}
+
+impl Square {
+    func new(side: i32) -> Square {
+        return Square { side: side };
+    }
+}
+
+impl Area for Square {
+    func area(self) -> i32 {
+        return self.side * self.side;
+    }
+}
+
+func pick(a: i32, b: i32) -> i32 {
+    if (a > b) {
+        return a;
+    }
+    return b;
+}
+
+func larger<T>(a: T, b: T) -> T {
+    return pick(a, b);
+}
+"#,
+    )
+    .unwrap();
+    let entry = dir.path().join("main.ea");
+    std::fs::write(
+        &entry,
+        r#"
+import geometry;
+use geometry::{Square, Area, larger};
+
+func pick() -> i32 {
+    return 100;
+}
+
+func main() -> i32 {
+    let square = Square::new(3);
+    return larger(square.area(), geometry::larger(2, 1));
+}
+"#,
+    )
+    .unwrap();
+
+    // `larger` is instantiated in main, but calls the `pick` of its own module
+    let (modules, type_context) = compile_modules_to_ast(&entry, &[]).unwrap();
+    let program = dir.path().join("main");
+    compile_modules_to_native(
+        &modules,
+        &type_context,
+        &program,
+        &[EmitKind::Executable],
+        &CodegenOptions::default(),
+        &LinkOptions::default(),
+    )
+    .expect("imported generics and impls should compile");
+    let status = std::process::Command::new(&program).status().unwrap();
+    assert_eq!(status.code(), Some(9));
+}
+
+#[cfg(feature = "llvm")]
+#[test]
+fn test_compile_array_bounds_checks() {
+    use ea_compiler::codegen::CodegenOptions;
+    use ea_compiler::compile_to_native;
+    use ea_compiler::linker::{EmitKind, LinkOptions};
+
+    let source = r#"
+func pick(values: [i32; 4], index: i32) -> i32 {
+    return values[index];
+}
+
+func sum(values: [i32; 4]) -> i32 {
+    let mut total: i32 = 0;
+    for value in values {
+        total = total + value;
+    }
+    return total;
+}
+
+func main() -> i32 {
+    let values: [i32; 4] = [1, 2, 3, 4];
+    return pick(values, 3) + pick(values, 7);
+}
+"#;
+
+    let dir = tempfile::tempdir().unwrap();
+    let checked = dir.path().join("checked");
+    compile_to_native(
+        source,
+        &checked,
+        &[EmitKind::Executable],
+        &CodegenOptions::default(),
+        &LinkOptions::default(),
+    )
+    .expect("program should compile with bounds checks");
+
+    let output = std::process::Command::new(&checked).output().unwrap();
+    assert_eq!(output.status.code(), Some(101));
+    assert!(String::from_utf8_lossy(&output.stdout)
+        .contains("index out of bounds: the length is 4 but the index is 7"));
+
+    // Arrays are passed by value and iterated over their whole length
+    let in_bounds = dir.path().join("in_bounds");
+    compile_to_native(
+        &source.replace("pick(values, 7)", "sum(values)"),
+        &in_bounds,
+        &[EmitKind::Executable],
+        &CodegenOptions::default(),
+        &LinkOptions::default(),
+    )
+    .expect("program should compile with bounds checks");
+    let status = std::process::Command::new(&in_bounds).status().unwrap();
+    assert_eq!(status.code(), Some(14));
+
+    // Indices that only fold to a constant during codegen are still checked
+    let folded = dir.path().join("folded");
+    compile_to_native(
+        &source.replace("pick(values, 7)", "values[wrapping_add(3, 1)]"),
+        &folded,
+        &[EmitKind::Executable],
+        &CodegenOptions::default(),
+        &LinkOptions::default(),
+    )
+    .expect("program should compile with bounds checks");
+    let output = std::process::Command::new(&folded).output().unwrap();
+    assert_eq!(output.status.code(), Some(101));
+    assert!(String::from_utf8_lossy(&output.stdout)
+        .contains("index out of bounds: the length is 4 but the index is 4"));
+
+    // Release kernels can opt out of the checks
+    let unchecked = dir.path().join("unchecked");
+    compile_to_native(
+        source,
+        &unchecked,
+        &[EmitKind::Assembly],
+        &CodegenOptions {
+            bounds_checks: false,
+            ..CodegenOptions::default()
+        },
+        &LinkOptions::default(),
+    )
+    .expect("program should compile without bounds checks");
+    let asm = std::fs::read_to_string(unchecked.with_extension("s")).unwrap();
+    assert!(!asm.contains("index out of bounds"));
+}
+
+#[cfg(feature = "llvm")]
+#[test]
+fn test_compile_overflow_checks() {
+    use ea_compiler::codegen::CodegenOptions;
+    use ea_compiler::compile_to_native;
+    use ea_compiler::linker::{EmitKind, LinkOptions};
+
+    let source = r#"
+func add(a: i32, b: i32) -> i32 {
+    return a + b;
+}
+
+func clamp(a: i32, b: i32) -> i32 {
+    return saturating_add(a, b);
+}
+
+func main() -> i32 {
+    let wrapped = wrapping_add(2147483647, 1);
+    if (clamp(2147483647, 1) != 2147483647) {
+        return 1;
+    }
+    return add(2147483647, 1) - wrapped;
+}
+"#;
+
+    let dir = tempfile::tempdir().unwrap();
+    let checked = dir.path().join("checked");
+    compile_to_native(
+        source,
+        &checked,
+        &[EmitKind::Executable],
+        &CodegenOptions {
+            overflow_checks: true,
+            ..CodegenOptions::default()
+        },
+        &LinkOptions::default(),
+    )
+    .expect("program should compile with overflow checks");
+
+    let output = std::process::Command::new(&checked).output().unwrap();
+    assert_eq!(output.status.code(), Some(101));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("attempt to add with overflow"));
+
+    // Without the checks `+` wraps around like `wrapping_add`
+    let unchecked = dir.path().join("unchecked");
+    compile_to_native(
+        source,
+        &unchecked,
+        &[EmitKind::Executable],
+        &CodegenOptions::default(),
+        &LinkOptions::default(),
+    )
+    .expect("program should compile without overflow checks");
+    let status = std::process::Command::new(&unchecked).status().unwrap();
+    assert_eq!(status.code(), Some(0));
+}
+
+#[cfg(feature = "llvm")]
+#[test]
+fn test_compile_unsigned_overflow_checks() {
+    use ea_compiler::codegen::CodegenOptions;
+    use ea_compiler::compile_to_native;
+    use ea_compiler::linker::{EmitKind, LinkOptions};
+
+    // Each sum is 4000000000, which overflows i32 but fits u32; generic code
+    // saturates as unsigned only in its instances for unsigned types
+    let source = r#"
+struct Pixel {
+    value: u32,
+}
+
+func big() -> u32 {
+    return 2000000000;
+}
+
+func clamp_add<T>(a: T, b: T) -> T {
+    return saturating_add(a, b);
+}
+
+func main() -> i32 {
+    let x: u32 = 2000000000;
+    let literal_left = 2000000000 + x;
+    let saturated = saturating_add(2000000000, x);
+    let pixel = Pixel { value: 2000000000 };
+    let field = pixel.value + 2000000000;
+    let call = big() + 2000000000;
+    if (literal_left != saturated) {
+        return 1;
+    }
+    if (field != call) {
+        return 2;
+    }
+    let bright: u8 = 200;
+    let dim: u8 = 100;
+    let white: u8 = 255;
+    if (clamp_add(bright, dim) != white) {
+        return 3;
+    }
+    let high: i8 = 100;
+    let top: i8 = 127;
+    if (clamp_add(high, high) != top) {
+        return 4;
+    }
+    return 0;
+}
+"#;
+
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("unsigned");
+    let options = CodegenOptions {
+        overflow_checks: true,
+        ..CodegenOptions::default()
+    };
+    compile_to_native(
+        source,
+        &output,
+        &[EmitKind::Executable],
+        &options,
+        &LinkOptions::default(),
+    )
+    .expect("program should compile with overflow checks");
+    let status = std::process::Command::new(&output).status().unwrap();
+    assert_eq!(status.code(), Some(0));
+
+    // Past the u32 range the checks still fire
+    let overflowing = source.replace("big() + 2000000000", "big() + 2300000000");
+    compile_to_native(
+        &overflowing,
+        &output,
+        &[EmitKind::Executable],
+        &options,
+        &LinkOptions::default(),
+    )
+    .expect("program should compile with overflow checks");
+    let output = std::process::Command::new(&output).output().unwrap();
+    assert_eq!(output.status.code(), Some(101));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("attempt to add with overflow"));
+}
+
+#[cfg(feature = "llvm")]
+#[test]
+fn test_compile_saturating_simd() {
+    use ea_compiler::codegen::CodegenOptions;
+    use ea_compiler::compile_to_native;
+    use ea_compiler::linker::{EmitKind, LinkOptions};
+
+    let source = r#"
+func brighten(pixels: u8x16, amount: u8x16) -> u8x16 {
+    return saturating_add(pixels, amount);
+}
+
+func amplify(samples: i8x16) -> i8x16 {
+    return saturating_add(samples, samples);
+}
+
+func main() -> i32 {
+    let pixels = brighten([200; 16]u8x16, [100; 16]u8x16);
+    let samples = amplify([100; 16]i8x16);
+    return 0;
+}
+"#;
+
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("saturating");
+    compile_to_native(
+        source,
+        &output,
+        &[EmitKind::Assembly],
+        &CodegenOptions::default(),
+        &LinkOptions::default(),
+    )
+    .expect("saturating SIMD arithmetic should compile");
+    let asm = std::fs::read_to_string(output.with_extension("s")).unwrap();
+    assert!(
+        asm.contains("paddusb"),
+        "u8x16 should use unsigned saturation"
+    );
+    assert!(asm.contains("paddsb"), "i8x16 should use signed saturation");
+}
+
 #[cfg(not(feature = "llvm"))]
 #[test]
 fn test_llvm_feature_disabled() {
@@ -225,3 +225,199 @@ func test_{}() -> {} {{
         assert!(result.is_ok(), "SIMD type {} should parse", type_name);
     }
 }
+
+#[test]
+fn test_multi_module_program() {
+    let dir = tempfile::tempdir().expect("Should create temp dir");
+    std::fs::create_dir(dir.path().join("math")).unwrap();
+    std::fs::write(
+        dir.path().join("math").join("ops.ea"),
+        r#"
+func double(x: i32) -> i32 {
+    return x * 2;
+}
+
+func square(x: i32) -> i32 {
+    return x * x;
+}
+"#,
+    )
+    .unwrap();
+    let entry = dir.path().join("main.ea");
+    std::fs::write(
+        &entry,
+        r#"
+import math::ops as ops;
+use math::ops::square as sq;
+
+func main() -> i32 {
+    return ops::double(sq(3));
+}
+"#,
+    )
+    .unwrap();
+
+    let result = ea_compiler::compile_modules_to_ast(&entry, &[]);
+    assert!(
+        result.is_ok(),
+        "Imported module should resolve: {:?}",
+        result.err()
+    );
+
+    let (modules, context) = result.unwrap();
+    assert_eq!(modules.len(), 2, "Should load entry and imported module");
+    assert_eq!(modules[0].name, "math::ops", "Dependencies come first");
+    assert!(modules[1].is_root);
+    assert!(context.functions.contains_key("ops::double"));
+    assert!(context.functions.contains_key("sq"));
+    assert!(
+        !context.functions.contains_key("double"),
+        "Unimported names must not leak into the root namespace"
+    );
+}
+
+#[test]
+fn test_generics_and_impls_stay_in_their_module() {
+    let dir = tempfile::tempdir().expect("Should create temp dir");
+    std::fs::write(
+        dir.path().join("shapes.ea"),
+        r#"
+struct Square {
+    side: i32,
+}
+
+impl Square {
+    func area(self) -> i32 {
+        return self.side * self.side;
+    }
+}
+
+func id<T>(x: T) -> T {
+    return x;
+}
+"#,
+    )
+    .unwrap();
+
+    // Generic functions are only visible once imported
+    let entry = dir.path().join("main.ea");
+    std::fs::write(
+        &entry,
+        "import shapes;\nfunc main() -> i32 { return id(1); }\n",
+    )
+    .unwrap();
+    assert!(ea_compiler::compile_modules_to_ast(&entry, &[]).is_err());
+
+    // ... but reachable through the module, and impls come with any import
+    std::fs::write(
+        &entry,
+        r#"
+import shapes;
+use shapes::Square;
+
+func id(x: i32) -> i32 {
+    return x + 1;
+}
+
+func main() -> i32 {
+    let square = Square { side: 2 };
+    return shapes::id(square.area()) + id(0);
+}
+"#,
+    )
+    .unwrap();
+    let result = ea_compiler::compile_modules_to_ast(&entry, &[]);
+    assert!(
+        result.is_ok(),
+        "Imported generics and impls should resolve: {:?}",
+        result.err()
+    );
+}
+
+#[test]
+fn test_unresolved_and_circular_imports() {
+    let dir = tempfile::tempdir().expect("Should create temp dir");
+
+    let missing = dir.path().join("missing.ea");
+    std::fs::write(
+        &missing,
+        "import nowhere;\nfunc main() -> i32 { return 0; }\n",
+    )
+    .unwrap();
+    assert!(ea_compiler::compile_modules_to_ast(&missing, &[]).is_err());
+
+    std::fs::write(
+        dir.path().join("a.ea"),
+        "import b;\nfunc fa() -> i32 { return 1; }\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("b.ea"),
+        "import a;\nfunc fb() -> i32 { return 2; }\n",
+    )
+    .unwrap();
+    let cyclic = dir.path().join("cyclic.ea");
+    std::fs::write(&cyclic, "import a;\nfunc main() -> i32 { return 0; }\n").unwrap();
+    let error = ea_compiler::compile_modules_to_ast(&cyclic, &[]).unwrap_err();
+    assert!(error.to_string().contains("Circular import"));
+}
+
+#[test]
+fn test_module_reached_by_two_import_paths_loads_once() {
+    let dir = tempfile::tempdir().expect("Should create temp dir");
+    std::fs::create_dir(dir.path().join("lib")).unwrap();
+    std::fs::write(
+        dir.path().join("lib").join("util.ea"),
+        "func one() -> i32 { return 1; }\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("lib").join("shapes.ea"),
+        "import util;\nfunc two() -> i32 { return util::one() + util::one(); }\n",
+    )
+    .unwrap();
+    let entry = dir.path().join("main.ea");
+    std::fs::write(
+        &entry,
+        r#"
+import lib::util as util;
+import lib::shapes as shapes;
+
+func main() -> i32 {
+    return util::one() + shapes::two();
+}
+"#,
+    )
+    .unwrap();
+
+    let (modules, context) = ea_compiler::compile_modules_to_ast(&entry, &[])
+        .expect("Both import paths should resolve to the same module");
+    let names: Vec<&str> = modules.iter().map(|m| m.name.as_str()).collect();
+    assert_eq!(names, ["lib::util", "lib::shapes", "main"]);
+    assert_eq!(modules[1].import_targets["util"], "lib::util");
+    assert!(context.functions.contains_key("util::one"));
+    assert!(context.functions.contains_key("shapes::two"));
+}
+
+#[test]
+fn test_entry_file_imported_by_extra_file_is_root() {
+    let dir = tempfile::tempdir().expect("Should create temp dir");
+    let entry = dir.path().join("main.ea");
+    std::fs::write(&entry, "func main() -> i32 { return 0; }\n").unwrap();
+    let helper = dir.path().join("helper.ea");
+    std::fs::write(
+        &helper,
+        "import main;\nfunc helper() -> i32 { return 1; }\n",
+    )
+    .unwrap();
+
+    let (modules, _) = ea_compiler::compile_modules_to_ast(&entry, &[helper])
+        .expect("Extra file importing the entry should resolve");
+    assert_eq!(modules.len(), 2, "The entry file must only be loaded once");
+    let roots: Vec<&str> = modules
+        .iter()
+        .filter(|m| m.is_root)
+        .map(|m| m.name.as_str())
+        .collect();
+    assert_eq!(roots, ["main"]);
+}